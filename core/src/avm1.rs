@@ -20,12 +20,14 @@ pub mod error;
 mod fscommand;
 pub mod function;
 pub mod globals;
+mod local_connection;
 pub mod object;
 mod property;
 mod scope;
 mod string;
 mod timer;
 mod value;
+mod xml_socket;
 
 #[cfg(test)]
 mod tests;
@@ -35,6 +37,7 @@ use crate::avm1::error::Error;
 use crate::avm1::globals::as_broadcaster;
 use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
 pub use globals::SystemPrototypes;
+pub use local_connection::LocalConnections;
 pub use object::script_object::ScriptObject;
 pub use object::sound_object::SoundObject;
 pub use object::stage_object::StageObject;
@@ -44,6 +47,7 @@ use smallvec::alloc::borrow::Cow;
 pub use string::AvmString;
 pub use timer::Timers;
 pub use value::Value;
+pub use xml_socket::XmlSockets;
 
 macro_rules! avm_debug {
     ($avm: expr, $($arg:tt)*) => (
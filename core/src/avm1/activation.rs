@@ -1163,7 +1163,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 let display_properties = self.context.avm1.display_properties;
                 let props = display_properties.write(self.context.gc_context);
                 if let Some(property) = props.get_by_index(prop_index) {
-                    property.get(self, clip)?
+                    if self.check_script_access(clip) {
+                        property.get(self, clip)?
+                    } else {
+                        Value::Undefined
+                    }
                 } else {
                     avm_warn!(self, "GetProperty: Invalid property index {}", prop_index);
                     Value::Undefined
@@ -1216,6 +1220,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                             fetch,
                             url,
                             None,
+                            None,
                         );
                         self.context.navigator.spawn_future(process);
                     }
@@ -1312,6 +1317,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                         fetch,
                         url.to_string(),
                         None,
+                        None,
                     );
                     self.context.navigator.spawn_future(process);
                 }
@@ -1330,6 +1336,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                         fetch,
                         url.to_string(),
                         None,
+                        None,
                     );
                     self.context.navigator.spawn_future(process);
                 }
@@ -1830,7 +1837,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 let display_properties = self.context.avm1.display_properties;
                 let props = display_properties.read();
                 if let Some(property) = props.get_by_index(prop_index) {
-                    property.set(self, clip, value)?;
+                    if self.check_script_access(clip) {
+                        property.set(self, clip, value)?;
+                    }
                 }
             } else {
                 avm_warn!(self, "SetProperty: Invalid target");
@@ -1871,7 +1880,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             .resolve_target_path(root, start, target, false)?
             .and_then(|o| o.as_display_object())
         {
-            new_target_clip = Some(clip);
+            // A clip that can't be scripted is treated as if it wasn't found,
+            // but without the error message.
+            new_target_clip = Some(clip).filter(|clip| self.check_script_access(*clip));
         } else {
             avm_warn!(self, "SetTarget failed: {} not found", target);
             // TODO: Emulate AVM1 trace error message.
@@ -1919,7 +1930,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             Value::Object(o) => {
                 if let Some(clip) = o.as_display_object() {
                     // Movieclips can be targeted directly
-                    self.set_target_clip(Some(clip));
+                    let clip = Some(clip).filter(|clip| self.check_script_access(*clip));
+                    self.set_target_clip(clip);
                 } else {
                     // Other objects get coerced to string
                     let target = target.coerce_to_string(self)?;
@@ -2865,6 +2877,33 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.target_clip = value;
     }
 
+    /// Check whether the code running in this stack frame may access the
+    /// given display object, as restricted by `System.security.allowDomain`.
+    pub fn can_script(&self, target: DisplayObject<'gc>) -> bool {
+        match (self.base_clip.movie(), target.movie()) {
+            (Some(caller), Some(target)) => self.context.script_permissions.can_script(
+                caller.url(),
+                target.url(),
+                self.context.system.sandbox_type_for_movie(&caller),
+            ),
+            _ => true,
+        }
+    }
+
+    /// Check whether the code running in this stack frame may access the
+    /// given display object, logging a security violation if it may not.
+    pub fn check_script_access(&self, target: DisplayObject<'gc>) -> bool {
+        let can_script = self.can_script(target);
+        if !can_script {
+            avm_warn!(
+                self,
+                "*** Security Sandbox Violation *** Access to {} was denied",
+                target.path()
+            );
+        }
+        can_script
+    }
+
     /// Define a named local variable within this activation.
     pub fn define(&self, name: &str, value: impl Into<Value<'gc>>) {
         self.scope().define(name, value, self.context.gc_context)
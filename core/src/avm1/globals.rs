@@ -12,21 +12,29 @@ use std::f64;
 mod array;
 pub(crate) mod as_broadcaster;
 mod bevel_filter;
-mod bitmap_filter;
+pub(crate) mod bitmap_filter;
 mod blur_filter;
 pub(crate) mod boolean;
 pub(crate) mod button;
 mod color;
+mod color_matrix_filter;
 mod color_transform;
 pub(crate) mod context_menu;
 pub(crate) mod context_menu_item;
+mod convolution_filter;
 mod date;
+mod displacement_map_filter;
 pub(crate) mod display_object;
+mod drop_shadow_filter;
 pub(crate) mod error;
 mod external_interface;
 mod function;
+mod glow_filter;
+mod gradient_bevel_filter;
+mod gradient_glow_filter;
 mod key;
 mod load_vars;
+mod local_connection;
 mod math;
 mod matrix;
 pub(crate) mod mouse;
@@ -41,6 +49,7 @@ pub(crate) mod shared_object;
 mod sound;
 mod stage;
 pub(crate) mod string;
+mod style_sheet;
 pub(crate) mod system;
 pub(crate) mod system_capabilities;
 pub(crate) mod system_ime;
@@ -49,6 +58,7 @@ pub(crate) mod text_field;
 mod text_format;
 mod transform;
 mod xml;
+mod xml_socket;
 
 pub fn random<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -317,9 +327,11 @@ pub struct SystemPrototypes<'gc> {
     pub sound: Object<'gc>,
     pub text_field: Object<'gc>,
     pub text_format: Object<'gc>,
+    pub style_sheet: Object<'gc>,
     pub array: Object<'gc>,
     pub array_constructor: Object<'gc>,
     pub xml_node: Object<'gc>,
+    pub xml_constructor: Object<'gc>,
     pub string: Object<'gc>,
     pub number: Object<'gc>,
     pub boolean: Object<'gc>,
@@ -345,6 +357,20 @@ pub struct SystemPrototypes<'gc> {
     pub blur_filter_constructor: Object<'gc>,
     pub bevel_filter: Object<'gc>,
     pub bevel_filter_constructor: Object<'gc>,
+    pub drop_shadow_filter: Object<'gc>,
+    pub drop_shadow_filter_constructor: Object<'gc>,
+    pub glow_filter: Object<'gc>,
+    pub glow_filter_constructor: Object<'gc>,
+    pub gradient_bevel_filter: Object<'gc>,
+    pub gradient_bevel_filter_constructor: Object<'gc>,
+    pub gradient_glow_filter: Object<'gc>,
+    pub gradient_glow_filter_constructor: Object<'gc>,
+    pub convolution_filter: Object<'gc>,
+    pub convolution_filter_constructor: Object<'gc>,
+    pub color_matrix_filter: Object<'gc>,
+    pub color_matrix_filter_constructor: Object<'gc>,
+    pub displacement_map_filter: Object<'gc>,
+    pub displacement_map_filter_constructor: Object<'gc>,
     pub date: Object<'gc>,
 }
 
@@ -368,8 +394,6 @@ pub fn create_globals<'gc>(
 
     let sound_proto: Object<'gc> = sound::create_proto(gc_context, object_proto, function_proto);
 
-    let text_field_proto: Object<'gc> =
-        text_field::create_proto(gc_context, object_proto, function_proto);
     let text_format_proto: Object<'gc> =
         text_format::create_proto(gc_context, object_proto, function_proto);
 
@@ -390,6 +414,10 @@ pub fn create_globals<'gc>(
         boolean::create_proto(gc_context, object_proto, function_proto);
     let load_vars_proto: Object<'gc> =
         load_vars::create_proto(gc_context, object_proto, function_proto);
+    let xml_socket_proto: Object<'gc> =
+        xml_socket::create_proto(gc_context, object_proto, function_proto);
+    let local_connection_proto: Object<'gc> =
+        local_connection::create_proto(gc_context, object_proto, function_proto);
     let matrix_proto: Object<'gc> = matrix::create_proto(gc_context, object_proto, function_proto);
     let point_proto: Object<'gc> = point::create_proto(gc_context, object_proto, function_proto);
     let rectangle_proto: Object<'gc> =
@@ -405,6 +433,13 @@ pub fn create_globals<'gc>(
     let (broadcaster_functions, as_broadcaster) =
         as_broadcaster::create(gc_context, Some(object_proto), function_proto);
 
+    let text_field_proto: Object<'gc> = text_field::create_proto(
+        gc_context,
+        object_proto,
+        function_proto,
+        broadcaster_functions,
+    );
+
     let movie_clip_loader_proto: Object<'gc> = movie_clip_loader::create_proto(
         gc_context,
         object_proto,
@@ -459,6 +494,12 @@ pub fn create_globals<'gc>(
         Some(function_proto),
         load_vars_proto,
     );
+    let local_connection = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(local_connection::constructor),
+        Some(function_proto),
+        local_connection_proto,
+    );
     let movie_clip = FunctionObject::constructor(
         gc_context,
         Executable::Native(movie_clip::constructor),
@@ -484,6 +525,21 @@ pub fn create_globals<'gc>(
         Some(function_proto),
         text_format_proto,
     );
+
+    let style_sheet_proto: Object<'gc> =
+        style_sheet::create_proto(gc_context, object_proto, function_proto);
+    let style_sheet = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(style_sheet::constructor),
+        Some(function_proto),
+        style_sheet_proto,
+    );
+    text_field.define_value(
+        gc_context,
+        "StyleSheet",
+        style_sheet.into(),
+        DontEnum.into(),
+    );
     let array = array::create_array_object(gc_context, array_proto, Some(function_proto));
     let xmlnode = FunctionObject::constructor(
         gc_context,
@@ -497,6 +553,12 @@ pub fn create_globals<'gc>(
         Some(function_proto),
         xml_proto,
     );
+    let xml_socket = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(xml_socket::constructor),
+        Some(function_proto),
+        xml_socket_proto,
+    );
     let string = string::create_string_object(gc_context, string_proto, Some(function_proto));
     let number = number::create_number_object(gc_context, number_proto, Some(function_proto));
     let boolean = boolean::create_boolean_object(gc_context, boolean_proto, Some(function_proto));
@@ -564,6 +626,69 @@ pub fn create_globals<'gc>(
         bevel_filter_proto,
     );
 
+    let drop_shadow_filter_proto =
+        drop_shadow_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let drop_shadow_filter = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(drop_shadow_filter::constructor),
+        Some(function_proto),
+        drop_shadow_filter_proto,
+    );
+
+    let glow_filter_proto =
+        glow_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let glow_filter = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(glow_filter::constructor),
+        Some(function_proto),
+        glow_filter_proto,
+    );
+
+    let gradient_bevel_filter_proto =
+        gradient_bevel_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let gradient_bevel_filter = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(gradient_bevel_filter::constructor),
+        Some(function_proto),
+        gradient_bevel_filter_proto,
+    );
+
+    let gradient_glow_filter_proto =
+        gradient_glow_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let gradient_glow_filter = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(gradient_glow_filter::constructor),
+        Some(function_proto),
+        gradient_glow_filter_proto,
+    );
+
+    let convolution_filter_proto =
+        convolution_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let convolution_filter = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(convolution_filter::constructor),
+        Some(function_proto),
+        convolution_filter_proto,
+    );
+
+    let color_matrix_filter_proto =
+        color_matrix_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let color_matrix_filter = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(color_matrix_filter::constructor),
+        Some(function_proto),
+        color_matrix_filter_proto,
+    );
+
+    let displacement_map_filter_proto =
+        displacement_map_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let displacement_map_filter = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(displacement_map_filter::constructor),
+        Some(function_proto),
+        displacement_map_filter_proto,
+    );
+
     filters.define_value(
         gc_context,
         "BitmapFilter",
@@ -582,6 +707,48 @@ pub fn create_globals<'gc>(
         bevel_filter.into(),
         EnumSet::empty(),
     );
+    filters.define_value(
+        gc_context,
+        "DropShadowFilter",
+        drop_shadow_filter.into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "GlowFilter",
+        glow_filter.into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "GradientBevelFilter",
+        gradient_bevel_filter.into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "GradientGlowFilter",
+        gradient_glow_filter.into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "ConvolutionFilter",
+        convolution_filter.into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "ColorMatrixFilter",
+        color_matrix_filter.into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "DisplacementMapFilter",
+        displacement_map_filter.into(),
+        EnumSet::empty(),
+    );
 
     let external = ScriptObject::object(gc_context, Some(object_proto));
     let external_interface = external_interface::create_external_interface_object(
@@ -613,6 +780,12 @@ pub fn create_globals<'gc>(
     globals.define_value(gc_context, "Object", object.into(), DontEnum.into());
     globals.define_value(gc_context, "Function", function.into(), DontEnum.into());
     globals.define_value(gc_context, "LoadVars", load_vars.into(), DontEnum.into());
+    globals.define_value(
+        gc_context,
+        "LocalConnection",
+        local_connection.into(),
+        DontEnum.into(),
+    );
     globals.define_value(gc_context, "MovieClip", movie_clip.into(), DontEnum.into());
     globals.define_value(
         gc_context,
@@ -630,6 +803,7 @@ pub fn create_globals<'gc>(
     );
     globals.define_value(gc_context, "XMLNode", xmlnode.into(), DontEnum.into());
     globals.define_value(gc_context, "XML", xml.into(), DontEnum.into());
+    globals.define_value(gc_context, "XMLSocket", xml_socket.into(), DontEnum.into());
     globals.define_value(gc_context, "String", string.into(), DontEnum.into());
     globals.define_value(gc_context, "Number", number.into(), DontEnum.into());
     globals.define_value(gc_context, "Boolean", boolean.into(), DontEnum.into());
@@ -838,9 +1012,11 @@ pub fn create_globals<'gc>(
             sound: sound_proto,
             text_field: text_field_proto,
             text_format: text_format_proto,
+            style_sheet: style_sheet_proto,
             array: array_proto,
             array_constructor: array,
             xml_node: xmlnode_proto,
+            xml_constructor: xml,
             string: string_proto,
             number: number_proto,
             boolean: boolean_proto,
@@ -866,6 +1042,20 @@ pub fn create_globals<'gc>(
             blur_filter_constructor: blur_filter,
             bevel_filter: bevel_filter_proto,
             bevel_filter_constructor: bevel_filter,
+            drop_shadow_filter: drop_shadow_filter_proto,
+            drop_shadow_filter_constructor: drop_shadow_filter,
+            glow_filter: glow_filter_proto,
+            glow_filter_constructor: glow_filter,
+            gradient_bevel_filter: gradient_bevel_filter_proto,
+            gradient_bevel_filter_constructor: gradient_bevel_filter,
+            gradient_glow_filter: gradient_glow_filter_proto,
+            gradient_glow_filter_constructor: gradient_glow_filter,
+            convolution_filter: convolution_filter_proto,
+            convolution_filter_constructor: convolution_filter,
+            color_matrix_filter: color_matrix_filter_proto,
+            color_matrix_filter_constructor: color_matrix_filter,
+            displacement_map_filter: displacement_map_filter_proto,
+            displacement_map_filter_constructor: displacement_map_filter,
             date: date_proto,
         },
         globals.into(),
@@ -2,9 +2,22 @@
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::object::bevel_filter::{BevelFilterObject, BevelFilterType};
+use crate::avm1::object::blur_filter::BlurFilterObject;
+use crate::avm1::object::color_matrix_filter::ColorMatrixFilterObject;
+use crate::avm1::object::convolution_filter::ConvolutionFilterObject;
+use crate::avm1::object::displacement_map_filter::{
+    DisplacementMapFilterMode, DisplacementMapFilterObject,
+};
+use crate::avm1::object::drop_shadow_filter::DropShadowFilterObject;
+use crate::avm1::object::glow_filter::GlowFilterObject;
+use crate::avm1::object::gradient_bevel_filter::GradientBevelFilterObject;
+use crate::avm1::object::gradient_glow_filter::GradientGlowFilterObject;
 use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::backend::render::filters::{DisplacementMapFilter, DisplacementMapMode, Filter};
 use enumset::EnumSet;
 use gc_arena::MutationContext;
+use swf::{Color, GradientRecord};
 
 pub fn constructor<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
@@ -66,9 +79,144 @@ pub fn clone<'gc>(
         return Ok(cloned.into());
     }
 
+    if this.as_drop_shadow_filter_object().is_some() {
+        let proto = activation
+            .context
+            .avm1
+            .prototypes
+            .drop_shadow_filter_constructor;
+        return clone_with_properties(
+            activation,
+            this,
+            proto,
+            &[
+                "distance",
+                "angle",
+                "color",
+                "alpha",
+                "blurX",
+                "blurY",
+                "strength",
+                "quality",
+                "inner",
+                "knockout",
+                "hideObject",
+            ],
+        );
+    }
+
+    if this.as_glow_filter_object().is_some() {
+        let proto = activation.context.avm1.prototypes.glow_filter_constructor;
+        return clone_with_properties(
+            activation,
+            this,
+            proto,
+            &[
+                "color", "alpha", "blurX", "blurY", "strength", "quality", "inner", "knockout",
+            ],
+        );
+    }
+
+    if this.as_color_matrix_filter_object().is_some() {
+        let proto = activation
+            .context
+            .avm1
+            .prototypes
+            .color_matrix_filter_constructor;
+        return clone_with_properties(activation, this, proto, &["matrix"]);
+    }
+
+    if this.as_convolution_filter_object().is_some() {
+        let proto = activation
+            .context
+            .avm1
+            .prototypes
+            .convolution_filter_constructor;
+        return clone_with_properties(
+            activation,
+            this,
+            proto,
+            &[
+                "matrixX",
+                "matrixY",
+                "matrix",
+                "divisor",
+                "bias",
+                "preserveAlpha",
+                "clamp",
+                "color",
+                "alpha",
+            ],
+        );
+    }
+
+    let gradient_properties = [
+        "distance", "angle", "colors", "alphas", "ratios", "blurX", "blurY", "strength", "quality",
+        "type", "knockout",
+    ];
+
+    if this.as_gradient_glow_filter_object().is_some() {
+        let proto = activation
+            .context
+            .avm1
+            .prototypes
+            .gradient_glow_filter_constructor;
+        return clone_with_properties(activation, this, proto, &gradient_properties);
+    }
+
+    if this.as_gradient_bevel_filter_object().is_some() {
+        let proto = activation
+            .context
+            .avm1
+            .prototypes
+            .gradient_bevel_filter_constructor;
+        return clone_with_properties(activation, this, proto, &gradient_properties);
+    }
+
+    if this.as_displacement_map_filter_object().is_some() {
+        let proto = activation
+            .context
+            .avm1
+            .prototypes
+            .displacement_map_filter_constructor;
+        return clone_with_properties(
+            activation,
+            this,
+            proto,
+            &[
+                "mapBitmap",
+                "mapPoint",
+                "componentX",
+                "componentY",
+                "scaleX",
+                "scaleY",
+                "mode",
+                "color",
+                "alpha",
+            ],
+        );
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Construct a new filter by passing the named properties of `this`, in
+/// order, to the given constructor.
+fn clone_with_properties<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    constructor: Object<'gc>,
+    properties: &[&str],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut args = Vec::with_capacity(properties.len());
+    for name in properties {
+        args.push(this.get(name, activation)?);
+    }
+
+    let cloned = constructor.construct(activation, &args)?;
+    Ok(cloned.into())
+}
+
 pub fn create_proto<'gc>(
     gc_context: MutationContext<'gc, '_>,
     proto: Object<'gc>,
@@ -80,3 +228,392 @@ pub fn create_proto<'gc>(
 
     object.into()
 }
+
+fn color_with_alpha(color: u32, alpha: f64) -> Color {
+    Color::from_rgb(color, (alpha * 255.0) as u8)
+}
+
+fn gradient_records(colors: &[u32], alphas: &[f64], ratios: &[u8]) -> Vec<GradientRecord> {
+    colors
+        .iter()
+        .zip(alphas)
+        .zip(ratios)
+        .map(|((color, alpha), ratio)| GradientRecord {
+            ratio: *ratio,
+            color: color_with_alpha(*color, *alpha),
+        })
+        .collect()
+}
+
+/// Returns the `(is_inner, is_on_top)` flags of a bevel or glow type.
+fn filter_type_flags(type_: BevelFilterType) -> (bool, bool) {
+    match type_ {
+        BevelFilterType::Inner => (true, false),
+        BevelFilterType::Outer => (false, false),
+        BevelFilterType::Full => (false, true),
+    }
+}
+
+fn filter_type_from_flags(is_inner: bool, is_on_top: bool) -> BevelFilterType {
+    if is_on_top {
+        BevelFilterType::Full
+    } else if is_inner {
+        BevelFilterType::Inner
+    } else {
+        BevelFilterType::Outer
+    }
+}
+
+/// Convert an AVM1 filter object into the filter used for rendering.
+///
+/// Returns `None` if the object is not a filter.
+pub fn avm1_to_filter(object: Object<'_>) -> Option<Filter> {
+    if let Some(filter) = object.as_displacement_map_filter_object() {
+        // AVM1 has no `BitmapData` yet, so there are no pixels to read from `mapBitmap`.
+        // Without a map, nothing is displaced.
+        return Some(Filter::DisplacementMap(Box::new(DisplacementMapFilter {
+            map_width: 0,
+            map_height: 0,
+            map_pixels: vec![],
+            map_point: (
+                filter.get_map_point_x() as i32,
+                filter.get_map_point_y() as i32,
+            ),
+            component_x: filter.get_component_x() as u32,
+            component_y: filter.get_component_y() as u32,
+            scale_x: filter.get_scale_x(),
+            scale_y: filter.get_scale_y(),
+            mode: match filter.get_mode() {
+                DisplacementMapFilterMode::Wrap => DisplacementMapMode::Wrap,
+                DisplacementMapFilterMode::Clamp => DisplacementMapMode::Clamp,
+                DisplacementMapFilterMode::Ignore => DisplacementMapMode::Ignore,
+                DisplacementMapFilterMode::Color => DisplacementMapMode::Color,
+            },
+            color: color_with_alpha(filter.get_color(), filter.get_alpha()),
+        })));
+    }
+
+    avm1_to_swf_filter(object).map(Filter::Swf)
+}
+
+fn avm1_to_swf_filter(object: Object<'_>) -> Option<swf::Filter> {
+    if let Some(filter) = object.as_blur_filter_object() {
+        return Some(swf::Filter::BlurFilter(Box::new(swf::BlurFilter {
+            blur_x: filter.get_blur_x(),
+            blur_y: filter.get_blur_y(),
+            num_passes: filter.get_quality() as u8,
+        })));
+    }
+
+    if let Some(filter) = object.as_bevel_filter_object() {
+        let (is_inner, is_on_top) = filter_type_flags(filter.get_type());
+        return Some(swf::Filter::BevelFilter(Box::new(swf::BevelFilter {
+            shadow_color: color_with_alpha(filter.get_shadow_color(), filter.get_shadow_alpha()),
+            highlight_color: color_with_alpha(
+                filter.get_highlight_color(),
+                filter.get_highlight_alpha(),
+            ),
+            blur_x: filter.get_blur_x(),
+            blur_y: filter.get_blur_y(),
+            angle: filter.get_angle().to_radians(),
+            distance: filter.get_distance(),
+            strength: filter.get_strength() as f32,
+            is_inner,
+            is_knockout: filter.get_knockout(),
+            is_on_top,
+            num_passes: filter.get_quality() as u8,
+        })));
+    }
+
+    if let Some(filter) = object.as_drop_shadow_filter_object() {
+        return Some(swf::Filter::DropShadowFilter(Box::new(
+            swf::DropShadowFilter {
+                color: color_with_alpha(filter.get_color(), filter.get_alpha()),
+                blur_x: filter.get_blur_x(),
+                blur_y: filter.get_blur_y(),
+                angle: filter.get_angle().to_radians(),
+                distance: filter.get_distance(),
+                strength: filter.get_strength() as f32,
+                is_inner: filter.get_inner(),
+                is_knockout: filter.get_knockout(),
+                num_passes: filter.get_quality() as u8,
+            },
+        )));
+    }
+
+    if let Some(filter) = object.as_glow_filter_object() {
+        return Some(swf::Filter::GlowFilter(Box::new(swf::GlowFilter {
+            color: color_with_alpha(filter.get_color(), filter.get_alpha()),
+            blur_x: filter.get_blur_x(),
+            blur_y: filter.get_blur_y(),
+            strength: filter.get_strength() as f32,
+            is_inner: filter.get_inner(),
+            is_knockout: filter.get_knockout(),
+            num_passes: filter.get_quality() as u8,
+        })));
+    }
+
+    if let Some(filter) = object.as_color_matrix_filter_object() {
+        let mut matrix = [0.0; 20];
+        matrix.copy_from_slice(&filter.get_matrix()[..20]);
+        return Some(swf::Filter::ColorMatrixFilter(Box::new(
+            swf::ColorMatrixFilter { matrix },
+        )));
+    }
+
+    if let Some(filter) = object.as_convolution_filter_object() {
+        return Some(swf::Filter::ConvolutionFilter(Box::new(
+            swf::ConvolutionFilter {
+                num_matrix_rows: filter.get_matrix_y(),
+                num_matrix_cols: filter.get_matrix_x(),
+                matrix: filter.get_matrix(),
+                divisor: filter.get_divisor(),
+                bias: filter.get_bias(),
+                default_color: color_with_alpha(filter.get_color(), filter.get_alpha()),
+                is_clamped: filter.get_clamp(),
+                is_preserve_alpha: filter.get_preserve_alpha(),
+            },
+        )));
+    }
+
+    if let Some(filter) = object.as_gradient_glow_filter_object() {
+        let (is_inner, is_on_top) = filter_type_flags(filter.get_type());
+        return Some(swf::Filter::GradientGlowFilter(Box::new(
+            swf::GradientGlowFilter {
+                colors: gradient_records(
+                    &filter.get_colors(),
+                    &filter.get_alphas(),
+                    &filter.get_ratios(),
+                ),
+                blur_x: filter.get_blur_x(),
+                blur_y: filter.get_blur_y(),
+                angle: filter.get_angle().to_radians(),
+                distance: filter.get_distance(),
+                strength: filter.get_strength() as f32,
+                is_inner,
+                is_knockout: filter.get_knockout(),
+                is_on_top,
+                num_passes: filter.get_quality() as u8,
+            },
+        )));
+    }
+
+    if let Some(filter) = object.as_gradient_bevel_filter_object() {
+        let (is_inner, is_on_top) = filter_type_flags(filter.get_type());
+        return Some(swf::Filter::GradientBevelFilter(Box::new(
+            swf::GradientBevelFilter {
+                colors: gradient_records(
+                    &filter.get_colors(),
+                    &filter.get_alphas(),
+                    &filter.get_ratios(),
+                ),
+                blur_x: filter.get_blur_x(),
+                blur_y: filter.get_blur_y(),
+                angle: filter.get_angle().to_radians(),
+                distance: filter.get_distance(),
+                strength: filter.get_strength() as f32,
+                is_inner,
+                is_knockout: filter.get_knockout(),
+                is_on_top,
+                num_passes: filter.get_quality() as u8,
+            },
+        )));
+    }
+
+    None
+}
+
+/// Create a new AVM1 filter object representing the given filter.
+pub fn filter_to_avm1<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    filter: &Filter,
+) -> Object<'gc> {
+    let filter = match filter {
+        Filter::Swf(filter) => filter,
+        Filter::DisplacementMap(filter) => {
+            return displacement_map_filter_to_avm1(activation, filter)
+        }
+    };
+    swf_filter_to_avm1(activation, filter)
+}
+
+fn displacement_map_filter_to_avm1<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    filter: &DisplacementMapFilter,
+) -> Object<'gc> {
+    let gc_context = activation.context.gc_context;
+    let object = DisplacementMapFilterObject::empty_object(
+        gc_context,
+        Some(activation.context.avm1.prototypes.displacement_map_filter),
+    );
+    object.set_map_point_x(gc_context, filter.map_point.0.into());
+    object.set_map_point_y(gc_context, filter.map_point.1.into());
+    object.set_component_x(gc_context, filter.component_x as i32);
+    object.set_component_y(gc_context, filter.component_y as i32);
+    object.set_scale_x(gc_context, filter.scale_x);
+    object.set_scale_y(gc_context, filter.scale_y);
+    object.set_mode(
+        gc_context,
+        match filter.mode {
+            DisplacementMapMode::Wrap => DisplacementMapFilterMode::Wrap,
+            DisplacementMapMode::Clamp => DisplacementMapFilterMode::Clamp,
+            DisplacementMapMode::Ignore => DisplacementMapFilterMode::Ignore,
+            DisplacementMapMode::Color => DisplacementMapFilterMode::Color,
+        },
+    );
+    object.set_color(gc_context, rgb(&filter.color));
+    object.set_alpha(gc_context, alpha(&filter.color));
+    object.into()
+}
+
+fn swf_filter_to_avm1<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    filter: &swf::Filter,
+) -> Object<'gc> {
+    let gc_context = activation.context.gc_context;
+    let prototypes = &activation.context.avm1.prototypes;
+
+    match filter {
+        swf::Filter::BlurFilter(filter) => {
+            let object = BlurFilterObject::empty_object(gc_context, Some(prototypes.blur_filter));
+            object.set_blur_x(gc_context, filter.blur_x);
+            object.set_blur_y(gc_context, filter.blur_y);
+            object.set_quality(gc_context, filter.num_passes.into());
+            object.into()
+        }
+        swf::Filter::BevelFilter(filter) => {
+            let object = BevelFilterObject::empty_object(gc_context, Some(prototypes.bevel_filter));
+            object.set_shadow_color(gc_context, rgb(&filter.shadow_color));
+            object.set_shadow_alpha(gc_context, alpha(&filter.shadow_color));
+            object.set_highlight_color(gc_context, rgb(&filter.highlight_color));
+            object.set_highlight_alpha(gc_context, alpha(&filter.highlight_color));
+            object.set_blur_x(gc_context, filter.blur_x);
+            object.set_blur_y(gc_context, filter.blur_y);
+            object.set_angle(gc_context, filter.angle.to_degrees());
+            object.set_distance(gc_context, filter.distance);
+            object.set_strength(gc_context, filter.strength.into());
+            object.set_type(
+                gc_context,
+                filter_type_from_flags(filter.is_inner, filter.is_on_top),
+            );
+            object.set_knockout(gc_context, filter.is_knockout);
+            object.set_quality(gc_context, filter.num_passes.into());
+            object.into()
+        }
+        swf::Filter::DropShadowFilter(filter) => {
+            let object = DropShadowFilterObject::empty_object(
+                gc_context,
+                Some(prototypes.drop_shadow_filter),
+            );
+            object.set_color(gc_context, rgb(&filter.color));
+            object.set_alpha(gc_context, alpha(&filter.color));
+            object.set_blur_x(gc_context, filter.blur_x);
+            object.set_blur_y(gc_context, filter.blur_y);
+            object.set_angle(gc_context, filter.angle.to_degrees());
+            object.set_distance(gc_context, filter.distance);
+            object.set_strength(gc_context, filter.strength.into());
+            object.set_inner(gc_context, filter.is_inner);
+            object.set_knockout(gc_context, filter.is_knockout);
+            object.set_quality(gc_context, filter.num_passes.into());
+            object.into()
+        }
+        swf::Filter::GlowFilter(filter) => {
+            let object = GlowFilterObject::empty_object(gc_context, Some(prototypes.glow_filter));
+            object.set_color(gc_context, rgb(&filter.color));
+            object.set_alpha(gc_context, alpha(&filter.color));
+            object.set_blur_x(gc_context, filter.blur_x);
+            object.set_blur_y(gc_context, filter.blur_y);
+            object.set_strength(gc_context, filter.strength.into());
+            object.set_inner(gc_context, filter.is_inner);
+            object.set_knockout(gc_context, filter.is_knockout);
+            object.set_quality(gc_context, filter.num_passes.into());
+            object.into()
+        }
+        swf::Filter::ColorMatrixFilter(filter) => {
+            let object = ColorMatrixFilterObject::empty_object(
+                gc_context,
+                Some(prototypes.color_matrix_filter),
+            );
+            object.set_matrix(gc_context, filter.matrix.to_vec());
+            object.into()
+        }
+        swf::Filter::ConvolutionFilter(filter) => {
+            let object = ConvolutionFilterObject::empty_object(
+                gc_context,
+                Some(prototypes.convolution_filter),
+            );
+            object.set_matrix_x(gc_context, filter.num_matrix_cols);
+            object.set_matrix_y(gc_context, filter.num_matrix_rows);
+            object.set_matrix(gc_context, filter.matrix.clone());
+            object.set_divisor(gc_context, filter.divisor);
+            object.set_bias(gc_context, filter.bias);
+            object.set_color(gc_context, rgb(&filter.default_color));
+            object.set_alpha(gc_context, alpha(&filter.default_color));
+            object.set_clamp(gc_context, filter.is_clamped);
+            object.set_preserve_alpha(gc_context, filter.is_preserve_alpha);
+            object.into()
+        }
+        swf::Filter::GradientGlowFilter(filter) => {
+            let object = GradientGlowFilterObject::empty_object(
+                gc_context,
+                Some(prototypes.gradient_glow_filter),
+            );
+            object.set_colors(
+                gc_context,
+                filter.colors.iter().map(|r| rgb(&r.color)).collect(),
+            );
+            object.set_alphas(
+                gc_context,
+                filter.colors.iter().map(|r| alpha(&r.color)).collect(),
+            );
+            object.set_ratios(gc_context, filter.colors.iter().map(|r| r.ratio).collect());
+            object.set_blur_x(gc_context, filter.blur_x);
+            object.set_blur_y(gc_context, filter.blur_y);
+            object.set_angle(gc_context, filter.angle.to_degrees());
+            object.set_distance(gc_context, filter.distance);
+            object.set_strength(gc_context, filter.strength.into());
+            object.set_type(
+                gc_context,
+                filter_type_from_flags(filter.is_inner, filter.is_on_top),
+            );
+            object.set_knockout(gc_context, filter.is_knockout);
+            object.set_quality(gc_context, filter.num_passes.into());
+            object.into()
+        }
+        swf::Filter::GradientBevelFilter(filter) => {
+            let object = GradientBevelFilterObject::empty_object(
+                gc_context,
+                Some(prototypes.gradient_bevel_filter),
+            );
+            object.set_colors(
+                gc_context,
+                filter.colors.iter().map(|r| rgb(&r.color)).collect(),
+            );
+            object.set_alphas(
+                gc_context,
+                filter.colors.iter().map(|r| alpha(&r.color)).collect(),
+            );
+            object.set_ratios(gc_context, filter.colors.iter().map(|r| r.ratio).collect());
+            object.set_blur_x(gc_context, filter.blur_x);
+            object.set_blur_y(gc_context, filter.blur_y);
+            object.set_angle(gc_context, filter.angle.to_degrees());
+            object.set_distance(gc_context, filter.distance);
+            object.set_strength(gc_context, filter.strength.into());
+            object.set_type(
+                gc_context,
+                filter_type_from_flags(filter.is_inner, filter.is_on_top),
+            );
+            object.set_knockout(gc_context, filter.is_knockout);
+            object.set_quality(gc_context, filter.num_passes.into());
+            object.into()
+        }
+    }
+}
+
+fn rgb(color: &Color) -> u32 {
+    (u32::from(color.r) << 16) | (u32::from(color.g) << 8) | u32::from(color.b)
+}
+
+fn alpha(color: &Color) -> f64 {
+    f64::from(color.a) / 255.0
+}
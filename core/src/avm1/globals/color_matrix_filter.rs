@@ -0,0 +1,90 @@
+//! flash.filter.ColorMatrixFilter object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::color_matrix_filter::ColorMatrixFilterObject;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use enumset::EnumSet;
+use gc_arena::MutationContext;
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    set_matrix(activation, this, args.get(0..1).unwrap_or(&[]))?;
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let array = ScriptObject::array(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes.array),
+    );
+
+    for (i, value) in this
+        .as_color_matrix_filter_object()
+        .unwrap()
+        .get_matrix()
+        .iter()
+        .enumerate()
+    {
+        array.set_array_element(i, (*value).into(), activation.context.gc_context);
+    }
+
+    Ok(array.into())
+}
+
+pub fn set_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut matrix = vec![];
+
+    if let Some(Value::Object(array)) = args.get(0) {
+        for i in 0..array.length() {
+            matrix.push(array.array_element(i).coerce_to_f64(activation)?);
+        }
+    }
+
+    this.as_color_matrix_filter_object()
+        .unwrap()
+        .set_matrix(activation.context.gc_context, matrix);
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ColorMatrixFilterObject::empty_object(gc_context, Some(proto));
+    let script_object = object.as_script_object().unwrap();
+
+    script_object.add_property(
+        gc_context,
+        "matrix",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_matrix),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_matrix),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    object.into()
+}
@@ -4,6 +4,10 @@ use crate::avm1::object::TObject;
 use crate::avm1::property::Attribute;
 use crate::avm1::Object;
 use crate::avm1::{ScriptObject, Value};
+use crate::context_menu::{
+    BuiltInItemFlags, ContextMenuCallback, ContextMenuItem, ContextMenuState,
+};
+use crate::display_object::{DisplayObject, TDisplayObject};
 use gc_arena::MutationContext;
 
 pub fn constructor<'gc>(
@@ -155,3 +159,72 @@ pub fn create_proto<'gc>(
 
     object.into()
 }
+
+/// Build the context menu shown when `target` is right-clicked, from the
+/// `ContextMenu` object assigned to its `menu` property (if any).
+///
+/// Custom items come first, followed by the built-in items that the menu
+/// has not hidden. Items that are not `visible` are left out.
+pub fn make_context_menu_state<'gc>(
+    menu: Option<Object<'gc>>,
+    target: DisplayObject<'gc>,
+    is_zoomed: bool,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<ContextMenuState<'gc>, Error<'gc>> {
+    let mut state = ContextMenuState::new();
+    let mut builtin_items = BuiltInItemFlags::default();
+    let swf_version = activation.current_swf_version();
+
+    if let Some(menu) = menu {
+        if let Value::Object(built_in) = menu.get("builtInItems", activation)? {
+            builtin_items.zoom = built_in.get("zoom", activation)?.as_bool(swf_version);
+            builtin_items.play = built_in.get("play", activation)?.as_bool(swf_version);
+            builtin_items.loop_ = built_in.get("loop", activation)?.as_bool(swf_version);
+            builtin_items.rewind = built_in.get("rewind", activation)?.as_bool(swf_version);
+            builtin_items.forward_and_back = built_in
+                .get("forward_back", activation)?
+                .as_bool(swf_version);
+        }
+
+        if let Value::Object(custom_items) = menu.get("customItems", activation)? {
+            for i in 0..custom_items.length() {
+                let item = match custom_items.array_element(i) {
+                    Value::Object(item) => item,
+                    _ => continue,
+                };
+
+                if !item.get("visible", activation)?.as_bool(swf_version) {
+                    continue;
+                }
+
+                let caption = item
+                    .get("caption", activation)?
+                    .coerce_to_string(activation)?
+                    .to_string();
+                let enabled = item.get("enabled", activation)?.as_bool(swf_version);
+                let separator_before = item
+                    .get("separatorBefore", activation)?
+                    .as_bool(swf_version);
+
+                state.push(
+                    ContextMenuItem {
+                        enabled,
+                        separator_before,
+                        checked: false,
+                        caption,
+                    },
+                    ContextMenuCallback::Avm1 { target, item },
+                );
+            }
+        }
+    }
+
+    let root = activation
+        .context
+        .levels
+        .get(&0)
+        .and_then(|root| root.as_movie_clip());
+    state.build_builtin_items(builtin_items, root, is_zoomed);
+
+    Ok(state)
+}
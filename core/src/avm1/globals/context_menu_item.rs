@@ -72,7 +72,7 @@ pub fn copy<'gc>(
         .get("enabled", activation)?
         .as_bool(activation.swf_version());
     let separator_before = this
-        .get("separator_before", activation)?
+        .get("separatorBefore", activation)?
         .as_bool(activation.swf_version());
     let visible = this
         .get("visible", activation)?
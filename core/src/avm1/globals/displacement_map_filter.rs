@@ -0,0 +1,456 @@
+//! flash.filter.DisplacementMapFilter object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::point::{point_to_object, value_to_point};
+use crate::avm1::object::displacement_map_filter::{
+    DisplacementMapFilterMode, DisplacementMapFilterObject,
+};
+use crate::avm1::{AvmString, Object, TObject, Value};
+use enumset::EnumSet;
+use gc_arena::MutationContext;
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    set_map_bitmap(activation, this, args.get(0..1).unwrap_or(&[]))?;
+    set_map_point(activation, this, args.get(1..2).unwrap_or(&[]))?;
+    set_component_x(activation, this, args.get(2..3).unwrap_or(&[]))?;
+    set_component_y(activation, this, args.get(3..4).unwrap_or(&[]))?;
+    set_scale_x(activation, this, args.get(4..5).unwrap_or(&[]))?;
+    set_scale_y(activation, this, args.get(5..6).unwrap_or(&[]))?;
+    set_mode(activation, this, args.get(6..7).unwrap_or(&[]))?;
+    set_color(activation, this, args.get(7..8).unwrap_or(&[]))?;
+    set_alpha(activation, this, args.get(8..9).unwrap_or(&[]))?;
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_map_bitmap<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_displacement_map_filter_object()
+        .unwrap()
+        .get_map_bitmap()
+        .map(Value::Object)
+        .unwrap_or(Value::Undefined))
+}
+
+pub fn set_map_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let map_bitmap = match args.get(0) {
+        Some(Value::Object(object)) => Some(*object),
+        _ => None,
+    };
+
+    this.as_displacement_map_filter_object()
+        .unwrap()
+        .set_map_bitmap(activation.context.gc_context, map_bitmap);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_map_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let filter = this.as_displacement_map_filter_object().unwrap();
+    let point = (filter.get_map_point_x(), filter.get_map_point_y());
+    Ok(point_to_object(point, activation)?.into())
+}
+
+pub fn set_map_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (x, y) = match args.get(0) {
+        Some(value @ Value::Object(_)) => value_to_point(value.to_owned(), activation)?,
+        _ => (0.0, 0.0),
+    };
+
+    let filter = this.as_displacement_map_filter_object().unwrap();
+    filter.set_map_point_x(activation.context.gc_context, x);
+    filter.set_map_point_y(activation.context.gc_context, y);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_component_x<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_displacement_map_filter_object()
+        .unwrap()
+        .get_component_x()
+        .into())
+}
+
+pub fn set_component_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let component_x = args.get(0).unwrap_or(&0.into()).coerce_to_i32(activation)?;
+
+    this.as_displacement_map_filter_object()
+        .unwrap()
+        .set_component_x(activation.context.gc_context, component_x);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_component_y<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_displacement_map_filter_object()
+        .unwrap()
+        .get_component_y()
+        .into())
+}
+
+pub fn set_component_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let component_y = args.get(0).unwrap_or(&0.into()).coerce_to_i32(activation)?;
+
+    this.as_displacement_map_filter_object()
+        .unwrap()
+        .set_component_y(activation.context.gc_context, component_y);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_scale_x<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_displacement_map_filter_object()
+        .unwrap()
+        .get_scale_x()
+        .into())
+}
+
+pub fn set_scale_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let scale_x = args
+        .get(0)
+        .unwrap_or(&0.0.into())
+        .coerce_to_f64(activation)
+        .map(|x| x.max(-65535.0).min(65535.0))?;
+
+    this.as_displacement_map_filter_object()
+        .unwrap()
+        .set_scale_x(activation.context.gc_context, scale_x);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_scale_y<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_displacement_map_filter_object()
+        .unwrap()
+        .get_scale_y()
+        .into())
+}
+
+pub fn set_scale_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let scale_y = args
+        .get(0)
+        .unwrap_or(&0.0.into())
+        .coerce_to_f64(activation)
+        .map(|x| x.max(-65535.0).min(65535.0))?;
+
+    this.as_displacement_map_filter_object()
+        .unwrap()
+        .set_scale_y(activation.context.gc_context, scale_y);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mode: &str = this
+        .as_displacement_map_filter_object()
+        .unwrap()
+        .get_mode()
+        .into();
+    Ok(AvmString::new(activation.context.gc_context, mode.to_string()).into())
+}
+
+pub fn set_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mode: DisplacementMapFilterMode = args
+        .get(0)
+        .unwrap_or(&Value::String(AvmString::new(
+            activation.context.gc_context,
+            "wrap".to_string(),
+        )))
+        .coerce_to_string(activation)
+        .map(|s| s.as_str().into())?;
+
+    this.as_displacement_map_filter_object()
+        .unwrap()
+        .set_mode(activation.context.gc_context, mode);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_color<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_displacement_map_filter_object()
+        .unwrap()
+        .get_color()
+        .into())
+}
+
+pub fn set_color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let color = args
+        .get(0)
+        .unwrap_or(&0x000000.into())
+        .coerce_to_u32(activation)?;
+
+    this.as_displacement_map_filter_object()
+        .unwrap()
+        .set_color(activation.context.gc_context, color & 0xFFFFFF);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_alpha<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_displacement_map_filter_object()
+        .unwrap()
+        .get_alpha()
+        .into())
+}
+
+pub fn set_alpha<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let alpha = args
+        .get(0)
+        .unwrap_or(&0.0.into())
+        .coerce_to_f64(activation)
+        .map(|x| x.max(0.0).min(1.0))?;
+
+    this.as_displacement_map_filter_object()
+        .unwrap()
+        .set_alpha(activation.context.gc_context, alpha);
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = DisplacementMapFilterObject::empty_object(gc_context, Some(proto));
+    let script_object = object.as_script_object().unwrap();
+
+    script_object.add_property(
+        gc_context,
+        "mapBitmap",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_map_bitmap),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_map_bitmap),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "mapPoint",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_map_point),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_map_point),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "componentX",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_component_x),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_component_x),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "componentY",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_component_y),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_component_y),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "scaleX",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_scale_x),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_scale_x),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "scaleY",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_scale_y),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_scale_y),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "mode",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_mode),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_mode),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "color",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_color),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_color),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "alpha",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_alpha),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_alpha),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    object.into()
+}
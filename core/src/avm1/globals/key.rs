@@ -32,6 +32,48 @@ pub fn get_code<'gc>(
     Ok(code.into())
 }
 
+pub fn get_ascii<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let ascii = activation
+        .context
+        .input
+        .get_last_key_char()
+        .map_or(0, |c| c as u32);
+    Ok(ascii.into())
+}
+
+pub fn is_toggled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(key) = args
+        .get(0)
+        .and_then(|v| v.coerce_to_f64(activation).ok())
+        .and_then(|k| KeyCode::try_from(k as u8).ok())
+    {
+        Ok(activation.context.input.is_key_toggled(key).into())
+    } else {
+        Ok(false.into())
+    }
+}
+
+pub fn is_accessible<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // The last key is hidden from movies that can't script the movie which had focus.
+    let accessible = match activation.context.focus_tracker.key_target() {
+        Some(key_target) => activation.can_script(key_target),
+        None => true,
+    };
+    Ok(accessible.into())
+}
+
 pub fn create_key_object<'gc>(
     gc_context: MutationContext<'gc, '_>,
     proto: Option<Object<'gc>>,
@@ -174,5 +216,29 @@ pub fn create_key_object<'gc>(
         fn_proto,
     );
 
+    key.force_set_function(
+        "getAscii",
+        get_ascii,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    key.force_set_function(
+        "isToggled",
+        is_toggled,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    key.force_set_function(
+        "isAccessible",
+        is_accessible,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
     key.into()
 }
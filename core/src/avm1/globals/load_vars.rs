@@ -1,5 +1,4 @@
 //! AVM1 LoadVars object
-//! TODO: bytesLoaded, bytesTotal, contentType, addRequestHeader
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
@@ -10,6 +9,52 @@ use crate::backend::navigator::{NavigationMethod, RequestOptions};
 use gc_arena::MutationContext;
 use std::borrow::Cow;
 
+/// Request headers that Flash Player refuses to send from `addRequestHeader`.
+const FORBIDDEN_REQUEST_HEADERS: &[&str] = &[
+    "Accept-Charset",
+    "Accept-Encoding",
+    "Accept-Ranges",
+    "Age",
+    "Allow",
+    "Allowed",
+    "Connection",
+    "Content-Length",
+    "Content-Location",
+    "Content-Range",
+    "Date",
+    "Delete",
+    "ETag",
+    "Expect",
+    "Get",
+    "Host",
+    "Keep-Alive",
+    "Last-Modified",
+    "Location",
+    "Max-Forwards",
+    "Options",
+    "Post",
+    "Proxy-Authenticate",
+    "Proxy-Authorization",
+    "Public",
+    "Put",
+    "Range",
+    "Referer",
+    "Retry-After",
+    "Server",
+    "TE",
+    "Trace",
+    "Trailer",
+    "Transfer-Encoding",
+    "Upgrade",
+    "URI",
+    "User-Agent",
+    "Vary",
+    "Via",
+    "Warning",
+    "WWW-Authenticate",
+    "x-flash-version",
+];
+
 /// Implements `LoadVars`
 pub fn constructor<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
@@ -88,8 +133,8 @@ pub fn create_proto<'gc>(
     object.define_value(
         gc_context,
         "contentType",
-        "application/x-www-form-url-encoded".into(),
-        DontDelete | DontEnum | ReadOnly,
+        "application/x-www-form-urlencoded".into(),
+        DontDelete | DontEnum,
     );
 
     object.force_set_function(
@@ -119,15 +164,102 @@ pub fn create_proto<'gc>(
     object.into()
 }
 
-fn add_request_header<'gc>(
+/// Implements `addRequestHeader` for both `LoadVars` and `XML`.
+///
+/// Headers are either given as a name and value, or as a single array of
+/// alternating names and values. They are stored in the hidden
+/// `_customHeaders` array, just like Flash Player does.
+pub fn add_request_header<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "LoadVars.addRequestHeader: Unimplemented");
+    let new_headers = match args {
+        [Value::Object(array), ..] => array.array(),
+        [name, value, ..] => vec![name.clone(), value.clone()],
+        _ => return Ok(Value::Undefined),
+    };
+
+    let headers = match this.get("_customHeaders", activation)? {
+        Value::Object(headers) => headers,
+        _ => {
+            let headers: Object<'gc> = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            )
+            .into();
+            this.define_value(
+                activation.context.gc_context,
+                "_customHeaders",
+                headers.into(),
+                Attribute::DontDelete | Attribute::DontEnum,
+            );
+            headers
+        }
+    };
+
+    for pair in new_headers.chunks_exact(2) {
+        // Only string names and values are accepted.
+        if let [Value::String(name), Value::String(value)] = pair {
+            let length = headers.length();
+            headers.set_array_element(length, (*name).into(), activation.context.gc_context);
+            headers.set_array_element(length + 1, (*value).into(), activation.context.gc_context);
+        } else {
+            avm_warn!(activation, "addRequestHeader: Ignoring non-string header");
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Collect the headers added to an object with `addRequestHeader`.
+///
+/// Headers that Flash Player refuses to send are dropped.
+pub fn custom_request_headers<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<Vec<(String, String)>, Error<'gc>> {
+    let headers = match object.get("_customHeaders", activation)? {
+        Value::Object(headers) => headers.array(),
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut result = Vec::with_capacity(headers.len() / 2);
+    for pair in headers.chunks_exact(2) {
+        let name = pair[0].coerce_to_string(activation)?.to_string();
+        if FORBIDDEN_REQUEST_HEADERS
+            .iter()
+            .any(|forbidden| forbidden.eq_ignore_ascii_case(&name))
+        {
+            avm_warn!(activation, "Refusing to send request header {}", name);
+            continue;
+        }
+
+        let value = pair[1].coerce_to_string(activation)?.to_string();
+        result.push((name, value));
+    }
+
+    Ok(result)
+}
+
+/// Apply the custom headers and content type of a sending object to a
+/// request.
+pub fn apply_send_options<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    request_options: &mut RequestOptions,
+) -> Result<(), Error<'gc>> {
+    let content_type = object.get("contentType", activation)?;
+    if !matches!(content_type, Value::Undefined | Value::Null) {
+        let content_type = content_type.coerce_to_string(activation)?.to_string();
+        request_options.set_content_type(content_type);
+    }
+
+    request_options.set_headers(custom_request_headers(activation, object)?);
+
+    Ok(())
+}
+
 fn decode<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -324,7 +456,10 @@ fn spawn_load_var_fetch<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let (url, request_options) = if let Some((send_object, method)) = send_object {
         // Send properties from `send_object`.
-        activation.object_into_request_options(send_object, Cow::Borrowed(&url), Some(method))
+        let (url, mut request_options) =
+            activation.object_into_request_options(send_object, Cow::Borrowed(&url), Some(method));
+        apply_send_options(activation, send_object, &mut request_options)?;
+        (url, request_options)
     } else {
         // Not sending any parameters.
         (Cow::Borrowed(url.as_str()), RequestOptions::get())
@@ -349,6 +484,17 @@ fn spawn_load_var_fetch<'gc>(
         loader_object.set("_bytesLoaded", 0.into(), activation)?;
     }
 
+    if !loader_object.has_property(activation, "_bytesTotal") {
+        loader_object.define_value(
+            activation.context.gc_context,
+            "_bytesTotal",
+            Value::Undefined,
+            Attribute::DontDelete | Attribute::DontEnum,
+        );
+    } else {
+        loader_object.set("_bytesTotal", Value::Undefined, activation)?;
+    }
+
     if !loader_object.has_property(activation, "loaded") {
         loader_object.define_value(
             activation.context.gc_context,
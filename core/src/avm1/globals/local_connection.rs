@@ -0,0 +1,196 @@
+//! AVM1 LocalConnection object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property::Attribute;
+use crate::avm1::{AvmString, LocalConnections, Object, ScriptObject, Value};
+use crate::backend::local_connection::LocalConnectionMessage;
+use crate::display_object::TDisplayObject;
+use crate::external::Value as ExternalValue;
+use gc_arena::MutationContext;
+use url::Url;
+
+/// Method names that can't be called through `send`, as they would clash
+/// with the receiver's own `LocalConnection` methods.
+const RESERVED_METHOD_NAMES: &[&str] = &[
+    "send",
+    "connect",
+    "close",
+    "allowDomain",
+    "allowInsecureDomain",
+    "domain",
+];
+
+/// Implements `LocalConnection`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // No-op constructor
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    use Attribute::*;
+
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function(
+        "connect",
+        connect,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "send",
+        send,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "close",
+        close,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "domain",
+        domain,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.into()
+}
+
+/// The domain of the calling movie, which is `localhost` for local movies.
+fn movie_domain(activation: &Activation<'_, '_, '_>) -> String {
+    let movie = activation.base_clip().movie();
+    movie
+        .as_ref()
+        .and_then(|movie| movie.url())
+        .and_then(|url| Url::parse(url).ok())
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+/// Qualifies a connection name with the given domain.
+///
+/// Names starting with an underscore are global and aren't qualified, and
+/// neither are names that already include a domain. Connection names are
+/// case insensitive.
+fn qualify_connection_name(name: &str, domain: &str) -> String {
+    if name.starts_with('_') || name.contains(':') {
+        name.to_ascii_lowercase()
+    } else {
+        format!("{}:{}", domain, name.to_ascii_lowercase())
+    }
+}
+
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match args.get(0) {
+        Some(Value::String(name)) => name.to_string(),
+        _ => return Ok(false.into()),
+    };
+    if name.is_empty() || name.contains(':') {
+        return Ok(false.into());
+    }
+
+    let domain = movie_domain(activation);
+    let name = qualify_connection_name(&name, &domain);
+    let connected = LocalConnections::connect(&mut activation.context, this, name, domain);
+
+    Ok(connected.into())
+}
+
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (name, method_name) = match args {
+        [Value::String(name), Value::String(method_name), ..] => {
+            (name.to_string(), method_name.to_string())
+        }
+        _ => return Ok(false.into()),
+    };
+    if name.is_empty()
+        || method_name.is_empty()
+        || RESERVED_METHOD_NAMES.contains(&method_name.as_str())
+    {
+        return Ok(false.into());
+    }
+
+    let mut message_args = Vec::with_capacity(args.len() - 2);
+    for arg in &args[2..] {
+        message_args.push(ExternalValue::from_avm1(activation, arg.clone())?);
+    }
+
+    let sender_domain = movie_domain(activation);
+    let message = LocalConnectionMessage {
+        connection_name: qualify_connection_name(&name, &sender_domain),
+        method_name,
+        args: message_args,
+        sender_domain,
+    };
+    LocalConnections::send(&mut activation.context, this, message);
+
+    Ok(true.into())
+}
+
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    LocalConnections::close(&mut activation.context, this);
+
+    Ok(Value::Undefined)
+}
+
+fn domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let domain = movie_domain(activation);
+
+    Ok(AvmString::new(activation.context.gc_context, domain).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::qualify_connection_name;
+
+    #[test]
+    fn connection_names() {
+        assert_eq!(
+            qualify_connection_name("Channel", "example.com"),
+            "example.com:channel"
+        );
+        assert_eq!(
+            qualify_connection_name("_Channel", "example.com"),
+            "_channel"
+        );
+        assert_eq!(
+            qualify_connection_name("other.com:channel", "example.com"),
+            "other.com:channel"
+        );
+    }
+}
@@ -3,6 +3,7 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::bitmap_filter;
 use crate::avm1::globals::display_object::{self, AVM_DEPTH_BIAS, AVM_MAX_DEPTH};
 use crate::avm1::globals::matrix::gradient_object_to_matrix;
 use crate::avm1::property::Attribute::*;
@@ -19,8 +20,8 @@ use crate::vminterface::Instantiator;
 use gc_arena::MutationContext;
 use std::borrow::Cow;
 use swf::{
-    FillStyle, Gradient, GradientInterpolation, GradientRecord, GradientSpread, LineCapStyle,
-    LineJoinStyle, LineStyle, Twips,
+    BlendMode, FillStyle, Gradient, GradientInterpolation, GradientRecord, GradientSpread,
+    LineCapStyle, LineJoinStyle, LineStyle, Twips,
 };
 
 /// Implements `MovieClip`
@@ -207,6 +208,9 @@ pub fn create_proto<'gc>(
         proto, gc_context, fn_proto,
         "transform" => [transform, set_transform],
         "focusEnabled" => [focus_enabled, set_focus_enabled],
+        "tabChildren" => [tab_children, set_tab_children],
+        "filters" => [filters, set_filters],
+        "blendMode" => [blend_mode, set_blend_mode],
     );
 
     object.into()
@@ -1132,6 +1136,7 @@ fn load_movie<'gc>(
         fetch,
         url.to_string(),
         None,
+        None,
     );
 
     activation.context.navigator.spawn_future(process);
@@ -1192,6 +1197,103 @@ fn set_transform<'gc>(
     Ok(())
 }
 
+fn filters<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let array = ScriptObject::array(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes.array),
+    );
+    for (i, filter) in this.filters().iter().enumerate() {
+        let filter = bitmap_filter::filter_to_avm1(activation, filter);
+        array.set_array_element(i, filter.into(), activation.context.gc_context);
+    }
+    Ok(array.into())
+}
+
+fn set_filters<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let mut filters = vec![];
+    if let Value::Object(array) = value {
+        for i in 0..array.length() {
+            if let Value::Object(filter) = array.array_element(i) {
+                filters.extend(bitmap_filter::avm1_to_filter(filter));
+            }
+        }
+    }
+    this.set_filters(activation.context.gc_context, filters);
+    Ok(())
+}
+
+fn blend_mode<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = crate::display_object::blend_mode_name(this.blend_mode());
+    Ok(AvmString::new(activation.context.gc_context, name.to_string()).into())
+}
+
+fn set_blend_mode<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    // Blend modes may also be set by their index, as stored in the SWF.
+    let blend_mode = match &value {
+        Value::Number(index) => match *index as i32 {
+            1 => Some(BlendMode::Normal),
+            2 => Some(BlendMode::Layer),
+            3 => Some(BlendMode::Multiply),
+            4 => Some(BlendMode::Screen),
+            5 => Some(BlendMode::Lighten),
+            6 => Some(BlendMode::Darken),
+            7 => Some(BlendMode::Difference),
+            8 => Some(BlendMode::Add),
+            9 => Some(BlendMode::Subtract),
+            10 => Some(BlendMode::Invert),
+            11 => Some(BlendMode::Alpha),
+            12 => Some(BlendMode::Erase),
+            13 => Some(BlendMode::Overlay),
+            14 => Some(BlendMode::HardLight),
+            _ => None,
+        },
+        value => {
+            let name = value.coerce_to_string(activation)?;
+            crate::display_object::blend_mode_from_name(&name)
+        }
+    };
+
+    if let Some(blend_mode) = blend_mode {
+        this.set_blend_mode(activation.context.gc_context, blend_mode);
+    } else {
+        avm_warn!(activation, "Unknown blend mode {:?}", value);
+    }
+    Ok(())
+}
+
+fn tab_children<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.tab_children().into())
+}
+
+fn set_tab_children<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    this.set_tab_children(
+        activation.context.gc_context,
+        value.as_bool(activation.current_swf_version()),
+    );
+    Ok(())
+}
+
 fn focus_enabled<'gc>(
     this: MovieClip<'gc>,
     _activation: &mut Activation<'_, 'gc, '_>,
@@ -1,12 +1,13 @@
 //! AVM1 Sound object
-//! TODO: Sound position, transform, loadSound
+//! TODO: Sound position, loadSound
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::property::Attribute::*;
-use crate::avm1::{Object, SoundObject, TObject, Value};
+use crate::avm1::{Object, ScriptObject, SoundObject, TObject, Value};
 use crate::avm_warn;
+use crate::backend::audio::SoundTransform;
 use crate::character::Character;
 use crate::display_object::TDisplayObject;
 use gc_arena::MutationContext;
@@ -265,31 +266,87 @@ fn get_bytes_total<'gc>(
     }
 }
 
+/// The sound transform controlled by a `Sound`.
+///
+/// A `Sound` with an owner controls the transform of that movie clip, while a
+/// `Sound` without one controls the global transform of all sounds.
+fn sound_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    sound: SoundObject<'gc>,
+) -> SoundTransform {
+    if let Some(owner) = sound.owner() {
+        owner
+            .as_movie_clip()
+            .map(|clip| clip.sound_transform())
+            .unwrap_or_default()
+    } else {
+        *activation.context.audio_manager.global_sound_transform()
+    }
+}
+
+fn set_sound_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    sound: SoundObject<'gc>,
+    transform: SoundTransform,
+) {
+    if let Some(owner) = sound.owner() {
+        if let Some(clip) = owner.as_movie_clip() {
+            clip.set_sound_transform(&mut activation.context, transform);
+        }
+    } else {
+        activation
+            .context
+            .audio_manager
+            .set_global_sound_transform(transform);
+    }
+}
+
 fn get_pan<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Sound.getPan: Unimplemented");
-    Ok(0.into())
+    if let Some(sound) = this.as_sound_object() {
+        Ok(sound_transform(activation, sound).pan().into())
+    } else {
+        avm_warn!(activation, "Sound.getPan: this is not a Sound");
+        Ok(Value::Undefined)
+    }
 }
 
 fn get_transform<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Sound.getTransform: Unimplemented");
-    Ok(Value::Undefined)
+    if let Some(sound) = this.as_sound_object() {
+        let transform = sound_transform(activation, sound);
+        let out = ScriptObject::object(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.object),
+        );
+        out.set("ll", transform.left_to_left.into(), activation)?;
+        out.set("lr", transform.left_to_right.into(), activation)?;
+        out.set("rl", transform.right_to_left.into(), activation)?;
+        out.set("rr", transform.right_to_right.into(), activation)?;
+        Ok(out.into())
+    } else {
+        avm_warn!(activation, "Sound.getTransform: this is not a Sound");
+        Ok(Value::Undefined)
+    }
 }
 
 fn get_volume<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Sound.getVolume: Unimplemented");
-    Ok(100.into())
+    if let Some(sound) = this.as_sound_object() {
+        Ok(sound_transform(activation, sound).volume.into())
+    } else {
+        avm_warn!(activation, "Sound.getVolume: this is not a Sound");
+        Ok(Value::Undefined)
+    }
 }
 
 fn id3<'gc>(
@@ -339,28 +396,76 @@ fn position<'gc>(
 
 fn set_pan<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Sound.setPan: Unimplemented");
+    let pan = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation)?;
+    if let Some(sound) = this.as_sound_object() {
+        let mut transform = sound_transform(activation, sound);
+        transform.set_pan(pan);
+        set_sound_transform(activation, sound, transform);
+    } else {
+        avm_warn!(activation, "Sound.setPan: this is not a Sound");
+    }
     Ok(Value::Undefined)
 }
 
 fn set_transform<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Sound.setTransform: Unimplemented");
+    fn set_coefficient<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        transform: Object<'gc>,
+        property: &str,
+        out: &mut i32,
+    ) -> Result<(), Error<'gc>> {
+        // The parameters are set only if the property exists on the object itself (prototype excluded).
+        if transform.has_own_property(activation, property) {
+            *out = transform
+                .get(property, activation)?
+                .coerce_to_i32(activation)?;
+        }
+        Ok(())
+    }
+
+    let obj = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_object(activation);
+    if let Some(sound) = this.as_sound_object() {
+        let mut transform = sound_transform(activation, sound);
+        set_coefficient(activation, obj, "ll", &mut transform.left_to_left)?;
+        set_coefficient(activation, obj, "lr", &mut transform.left_to_right)?;
+        set_coefficient(activation, obj, "rl", &mut transform.right_to_left)?;
+        set_coefficient(activation, obj, "rr", &mut transform.right_to_right)?;
+        set_sound_transform(activation, sound, transform);
+    } else {
+        avm_warn!(activation, "Sound.setTransform: this is not a Sound");
+    }
     Ok(Value::Undefined)
 }
 
 fn set_volume<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Sound.setVolume: Unimplemented");
+    let volume = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation)?;
+    if let Some(sound) = this.as_sound_object() {
+        let mut transform = sound_transform(activation, sound);
+        transform.volume = volume;
+        set_sound_transform(activation, sound, transform);
+    } else {
+        avm_warn!(activation, "Sound.setVolume: this is not a Sound");
+    }
     Ok(Value::Undefined)
 }
 
@@ -387,7 +492,8 @@ fn start<'gc>(
     use swf::{SoundEvent, SoundInfo};
     if let Some(sound_object) = this.as_sound_object() {
         if let Some(sound) = sound_object.sound() {
-            let sound_instance = activation.context.audio.start_sound(
+            let sound_instance = activation.context.audio_manager.start_sound(
+                activation.context.audio,
                 sound,
                 &SoundInfo {
                     event: SoundEvent::Start,
@@ -400,8 +506,9 @@ fn start<'gc>(
                     num_loops: loops,
                     envelope: None,
                 },
+                sound_object.owner(),
             );
-            if let Ok(sound_instance) = sound_instance {
+            if let Some(sound_instance) = sound_instance {
                 sound_object
                     .set_sound_instance(activation.context.gc_context, Some(sound_instance));
             }
@@ -447,12 +554,12 @@ fn stop<'gc>(
                     name
                 )
             }
-        } else if let Some(_owner) = sound.owner() {
+        } else if let Some(owner) = sound.owner() {
             // Usage 2: Stop all sound running within a given clip.
-            // TODO: We just stop the last played sound for now.
-            if let Some(sound_instance) = sound.sound_instance() {
-                activation.context.audio.stop_sound(sound_instance);
-            }
+            activation
+                .context
+                .audio_manager
+                .stop_sounds_with_display_object(activation.context.audio, owner);
         } else {
             // Usage 3: If there is no owner and no name, this call acts like `stopAllSounds()`.
             activation.context.audio.stop_all_sounds();
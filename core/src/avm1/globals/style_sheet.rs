@@ -0,0 +1,275 @@
+//! `TextField.StyleSheet` impl
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::object::style_sheet_object::StyleSheetObject;
+use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
+use crate::avm_warn;
+use crate::backend::navigator::RequestOptions;
+use crate::html::{CssDeclarations, TextFormat};
+use enumset::EnumSet;
+use gc_arena::MutationContext;
+
+/// Implements `TextField.StyleSheet`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Read the CSS declarations out of a style object.
+///
+/// Every enumerable property of the object becomes a declaration, with its
+/// value coerced to a string.
+fn declarations_from_object<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<CssDeclarations, Error<'gc>> {
+    let mut declarations = CssDeclarations::new();
+    for name in object.get_keys(activation) {
+        let value = object.get(&name, activation)?;
+        let value = value.coerce_to_string(activation)?.to_string();
+        declarations.push((name, value));
+    }
+
+    Ok(declarations)
+}
+
+fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.as_style_sheet_object() {
+        style_sheet
+            .style_sheet_mut(activation.context.gc_context)
+            .clear();
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let style_sheet = match this.as_style_sheet_object() {
+        Some(style_sheet) => style_sheet,
+        None => return Ok(Value::Undefined),
+    };
+
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let declarations = match style_sheet.style_sheet().style(&name) {
+        Some(declarations) => declarations.clone(),
+        None => return Ok(Value::Null),
+    };
+
+    let style = ScriptObject::object(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes.object),
+    );
+    for (name, value) in declarations {
+        let value = AvmString::new(activation.context.gc_context, value);
+        style.set(&name, value.into(), activation)?;
+    }
+
+    Ok(style.into())
+}
+
+fn get_style_names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = ScriptObject::array(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes.array),
+    );
+
+    if let Some(style_sheet) = this.as_style_sheet_object() {
+        for (i, name) in style_sheet.style_sheet().style_names().enumerate() {
+            let name = AvmString::new(activation.context.gc_context, name.to_string());
+            names.set_array_element(i, name.into(), activation.context.gc_context);
+        }
+    }
+
+    Ok(names.into())
+}
+
+fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = match args.get(0) {
+        Some(val) => val.coerce_to_string(activation)?,
+        None => return Ok(false.into()),
+    };
+
+    // The loaded CSS is handed to `onData`, just like with `LoadVars`.
+    let fetch = activation
+        .context
+        .navigator
+        .fetch(&url, RequestOptions::get());
+    let process = activation.context.load_manager.load_form_into_load_vars(
+        activation.context.player.clone().unwrap(),
+        this,
+        fetch,
+    );
+    activation.context.navigator.spawn_future(process);
+
+    Ok(true.into())
+}
+
+fn on_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Default implementation parses the CSS and forwards to onLoad.
+    let success = match args.get(0) {
+        None | Some(Value::Undefined) | Some(Value::Null) => false,
+        Some(val) => this
+            .call_method("parseCSS", &[val.clone()], activation)?
+            .as_bool(activation.current_swf_version()),
+    };
+
+    this.call_method("onLoad", &[success.into()], activation)?;
+
+    Ok(Value::Undefined)
+}
+
+fn parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let style_sheet = match this.as_style_sheet_object() {
+        Some(style_sheet) => style_sheet,
+        None => return Ok(false.into()),
+    };
+
+    let css = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let success = style_sheet
+        .style_sheet_mut(activation.context.gc_context)
+        .parse_css(&css);
+    if !success {
+        avm_warn!(activation, "StyleSheet.parseCSS: Could not parse CSS");
+    }
+
+    Ok(success.into())
+}
+
+fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let style_sheet = match this.as_style_sheet_object() {
+        Some(style_sheet) => style_sheet,
+        None => return Ok(Value::Undefined),
+    };
+
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let declarations = match args.get(1) {
+        Some(Value::Object(style)) => Some(declarations_from_object(*style, activation)?),
+        _ => None,
+    };
+
+    style_sheet
+        .style_sheet_mut(activation.context.gc_context)
+        .set_style(&name, declarations);
+
+    Ok(Value::Undefined)
+}
+
+fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let style = match args.get(0) {
+        Some(Value::Object(style)) => *style,
+        _ => return Ok(Value::Null),
+    };
+
+    let declarations = declarations_from_object(style, activation)?;
+    let text_format = TextFormat::from_css_declarations(&declarations, Default::default());
+
+    Ok(text_format.as_avm1_object(activation)?.into())
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let style_sheet = StyleSheetObject::empty_style_sheet(gc_context, Some(proto));
+    let mut object = style_sheet.as_script_object().unwrap();
+
+    object.force_set_function("clear", clear, gc_context, EnumSet::empty(), Some(fn_proto));
+
+    object.force_set_function(
+        "getStyle",
+        get_style,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "getStyleNames",
+        get_style_names,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+
+    object.force_set_function("load", load, gc_context, EnumSet::empty(), Some(fn_proto));
+
+    object.force_set_function(
+        "onData",
+        on_data,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "parseCSS",
+        parse_css,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "setStyle",
+        set_style,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "transform",
+        transform,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+
+    style_sheet.into()
+}
@@ -4,6 +4,8 @@ use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::object::Object;
 use crate::avm1::{Avm1, ScriptObject, TObject, Value};
 use crate::avm_warn;
+use crate::security::SandboxType;
+use crate::tag_utils::SwfMovie;
 use core::fmt;
 use enumset::{EnumSet, EnumSetType};
 use gc_arena::MutationContext;
@@ -29,25 +31,6 @@ impl fmt::Display for CpuArchitecture {
     }
 }
 
-/// Available type of sandbox for a given SWF
-pub enum SandboxType {
-    Remote,
-    LocalWithFile,
-    LocalWithNetwork,
-    LocalTrusted,
-}
-
-impl fmt::Display for SandboxType {
-    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt.write_str(match self {
-            SandboxType::Remote => "remote",
-            SandboxType::LocalWithFile => "localWithFile",
-            SandboxType::LocalWithNetwork => "localWithNetwork",
-            SandboxType::LocalTrusted => "localTrusted",
-        })
-    }
-}
-
 /// The available host operating systems
 pub enum OperatingSystem {
     WindowsXp,
@@ -266,6 +249,10 @@ pub struct SystemProperties {
     pub os: OperatingSystem,
     /// The type of the player sandbox
     pub sandbox_type: SandboxType,
+    /// Whether local movies are trusted, and so play in the `localTrusted`
+    /// sandbox instead of being restricted to either local files or the
+    /// network.
+    pub trust_local_movies: bool,
     /// The cpu architecture of the platform
     pub cpu_architecture: CpuArchitecture,
     /// The highest supported h264 decoder level
@@ -273,6 +260,18 @@ pub struct SystemProperties {
 }
 
 impl SystemProperties {
+    /// The sandbox that the given movie plays in.
+    ///
+    /// This is usually the same as `sandbox_type`, which belongs to the root
+    /// movie, but a loaded movie may come from somewhere else.
+    pub fn sandbox_type_for_movie(&self, movie: &SwfMovie) -> SandboxType {
+        SandboxType::for_movie(
+            movie.url(),
+            movie.use_network_sandbox(),
+            self.trust_local_movies,
+        )
+    }
+
     pub fn get_version_string(&self, avm: &mut Avm1) -> String {
         format!(
             "{} {},0,0,0",
@@ -392,6 +391,7 @@ impl Default for SystemProperties {
             manufacturer: Manufacturer::Linux,
             os: OperatingSystem::Linux,
             sandbox_type: SandboxType::LocalTrusted,
+            trust_local_movies: false,
             cpu_architecture: CpuArchitecture::X86,
             idc_level: "5.1".into(),
         }
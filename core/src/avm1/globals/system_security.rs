@@ -4,6 +4,7 @@ use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::object::Object;
 use crate::avm1::{AvmString, ScriptObject, TObject, Value};
 use crate::avm_warn;
+use crate::display_object::TDisplayObject;
 use enumset::EnumSet;
 use gc_arena::MutationContext;
 use std::convert::Into;
@@ -11,33 +12,50 @@ use std::convert::Into;
 fn allow_domain<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "System.security.allowDomain() not implemented");
+    // Domains are granted access to the movie that called `allowDomain`.
+    let movie = activation.base_clip().movie();
+    let movie_url = movie.as_ref().and_then(|movie| movie.url());
+
+    for domain in args {
+        let domain = domain.coerce_to_string(activation)?;
+        activation
+            .context
+            .script_permissions
+            .allow_domain(movie_url, &domain);
+    }
+
     Ok(Value::Undefined)
 }
 
 fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(
-        activation,
-        "System.security.allowInsecureDomain() not implemented"
-    );
-    Ok(Value::Undefined)
+    // We don't distinguish between HTTP and HTTPS movies of the same domain
+    // when checking script access, so this is the same as `allowDomain`.
+    allow_domain(activation, this, args)
 }
 
 fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(
-        activation,
-        "System.security.allowInsecureDomain() not implemented"
-    );
+    let url = match args.get(0) {
+        Some(url) => url.coerce_to_string(activation)?,
+        None => return Ok(Value::Undefined),
+    };
+
+    let url = activation
+        .context
+        .navigator
+        .resolve_relative_url(&url)
+        .into_owned();
+    activation.context.navigator.load_policy_file(&url);
+
     Ok(Value::Undefined)
 }
 
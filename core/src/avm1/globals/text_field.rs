@@ -1,6 +1,7 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
 use crate::avm1::globals::display_object;
 use crate::avm1::property::Attribute::*;
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
@@ -432,15 +433,394 @@ pub fn set_auto_size<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_type<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(if etext.is_editable() {
+            "input".into()
+        } else {
+            "dynamic".into()
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        // Other values are ignored.
+        if value.eq_ignore_ascii_case("input") {
+            etext.set_editable(true, activation.context.gc_context);
+        } else if value.eq_ignore_ascii_case("dynamic") {
+            etext.set_editable(false, activation.context.gc_context);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn password<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.is_password().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_password<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let is_password = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .as_bool(activation.current_swf_version());
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        etext.set_password(is_password, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn mouse_wheel_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.is_mouse_wheel_enabled().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_mouse_wheel_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let is_enabled = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .as_bool(activation.current_swf_version());
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        etext.set_mouse_wheel_enabled(is_enabled, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        if let Some(restrict) = etext.restrict() {
+            return Ok(AvmString::new(activation.context.gc_context, restrict.to_string()).into());
+        }
+    }
+
+    // Unset `restrict` returns null, not undefined
+    Ok(Value::Null)
+}
+
+fn set_restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let restrict = match args.get(0) {
+        None | Some(Value::Undefined) | Some(Value::Null) => None,
+        Some(v) => Some(v.coerce_to_string(activation)?.to_string()),
+    };
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        etext.set_restrict(restrict, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn max_chars<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        let max_chars = etext.max_chars();
+        if max_chars > 0 {
+            return Ok(max_chars.into());
+        }
+    }
+
+    // No limit on the number of characters returns null
+    Ok(Value::Null)
+}
+
+fn set_max_chars<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let max_chars = match args.get(0) {
+        None | Some(Value::Undefined) | Some(Value::Null) => 0,
+        Some(v) => v.coerce_to_i32(activation)?,
+    };
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        etext.set_max_chars(max_chars, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn scroll<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.scroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_scroll<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let scroll = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_f64(activation)?;
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        let old_scroll = etext.scroll();
+        etext.set_scroll(scroll, activation.context.gc_context);
+        if etext.scroll() != old_scroll {
+            etext.broadcast_event(activation, "onScroller");
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn max_scroll<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.max_scroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn bottom_scroll<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.bottom_scroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn hscroll<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.hscroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+#[allow(clippy::float_cmp)]
+pub fn set_hscroll<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let hscroll = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_f64(activation)?;
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        let old_hscroll = etext.hscroll();
+        etext.set_hscroll(hscroll, activation.context.gc_context);
+        if etext.hscroll() != old_hscroll {
+            etext.broadcast_event(activation, "onScroller");
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn max_hscroll<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.max_hscroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn style_sheet<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        if let Some(style_sheet) = etext.style_sheet() {
+            return Ok(Object::from(style_sheet).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let style_sheet = match args.get(0) {
+        Some(Value::Object(object)) => object.as_style_sheet_object(),
+        _ => None,
+    };
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        etext.set_style_sheet(&mut activation.context, style_sheet);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn create_proto<'gc>(
     gc_context: MutationContext<'gc, '_>,
     proto: Object<'gc>,
     fn_proto: Object<'gc>,
+    broadcaster_functions: BroadcasterFunctions<'gc>,
 ) -> Object<'gc> {
     let mut object = ScriptObject::object(gc_context, Some(proto));
 
     display_object::define_display_object_proto(gc_context, object, fn_proto);
 
+    // Text fields broadcast `onChanged` and `onScroller` to their listeners.
+    // Each text field has its own `_listeners` array; see `attach_virtual_properties`.
+    object.define_value(
+        gc_context,
+        "addListener",
+        broadcaster_functions.add_listener.into(),
+        DontDelete | DontEnum,
+    );
+    object.define_value(
+        gc_context,
+        "removeListener",
+        broadcaster_functions.remove_listener.into(),
+        DontDelete | DontEnum,
+    );
+    object.define_value(
+        gc_context,
+        "broadcastMessage",
+        broadcaster_functions.broadcast_message.into(),
+        DontDelete | DontEnum,
+    );
+
     with_text_field!(
         gc_context,
         object,
@@ -449,7 +829,8 @@ pub fn create_proto<'gc>(
         "setNewTextFormat" => set_new_text_format,
         "getTextFormat" => get_text_format,
         "setTextFormat" => set_text_format,
-        "replaceText" => replace_text
+        "replaceText" => replace_text,
+        "getImageReference" => get_image_reference
     );
 
     object.into()
@@ -459,7 +840,13 @@ pub fn attach_virtual_properties<'gc>(
     gc_context: MutationContext<'gc, '_>,
     object: Object<'gc>,
     fn_proto: Object<'gc>,
+    array_proto: Object<'gc>,
 ) {
+    // A text field is initially a listener of its own events.
+    let listeners = ScriptObject::array(gc_context, Some(array_proto));
+    listeners.set_array_element(0, object.into(), gc_context);
+    object.define_value(gc_context, "_listeners", listeners.into(), DontEnum.into());
+
     object.add_property(
         gc_context,
         "text",
@@ -666,6 +1053,178 @@ pub fn attach_virtual_properties<'gc>(
         )),
         ReadOnly.into(),
     );
+    object.add_property(
+        gc_context,
+        "type",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_type),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_type),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "password",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(password),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_password),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "restrict",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(restrict),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_restrict),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "maxChars",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(max_chars),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_max_chars),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "scroll",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(scroll),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_scroll),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "maxscroll",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(max_scroll),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        None,
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "bottomScroll",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(bottom_scroll),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        None,
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "hscroll",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(hscroll),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_hscroll),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "mouseWheelEnabled",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(mouse_wheel_enabled),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_mouse_wheel_enabled),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "maxhscroll",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(max_hscroll),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        None,
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "styleSheet",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(style_sheet),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_style_sheet),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
 }
 
 fn get_new_text_format<'gc>(
@@ -770,3 +1329,20 @@ fn replace_text<'gc>(
 
     Ok(Value::Undefined)
 }
+
+fn get_image_reference<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    Ok(text_field
+        .image_reference(&id)
+        .map(|image| image.object())
+        .unwrap_or(Value::Undefined))
+}
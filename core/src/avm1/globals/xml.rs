@@ -3,6 +3,7 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::load_vars;
 use crate::avm1::object::script_object::ScriptObject;
 use crate::avm1::object::xml_object::XMLObject;
 use crate::avm1::property::Attribute::*;
@@ -904,15 +905,71 @@ pub fn xml_load<'gc>(
         return Ok(false.into());
     }
 
-    if let Some(node) = this.as_xml_node() {
-        let url = url.coerce_to_string(activation)?;
+    let url = url.coerce_to_string(activation)?;
+
+    spawn_xml_fetch(activation, this, &url, RequestOptions::get())
+}
+
+pub fn xml_send_and_load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    if let Value::Null = url {
+        return Ok(false.into());
+    }
+
+    let target = match args.get(1) {
+        Some(&Value::Object(target)) => target,
+        _ => return Ok(false.into()),
+    };
+
+    let url = url.coerce_to_string(activation)?;
+    let body = this
+        .call_method("toString", &[], activation)?
+        .coerce_to_string(activation)?;
 
-        this.set("loaded", false.into(), activation)?;
+    let mut request_options = RequestOptions::post(Some((
+        body.as_str().as_bytes().to_vec(),
+        "application/x-www-form-urlencoded".to_string(),
+    )));
+    load_vars::apply_send_options(activation, this, &mut request_options)?;
 
-        let fetch = activation
-            .context
-            .navigator
-            .fetch(&url, RequestOptions::get());
+    spawn_xml_fetch(activation, target, &url, request_options)
+}
+
+/// Start loading an XML document into `target`, which must be an XML object.
+fn spawn_xml_fetch<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    url: &str,
+    request_options: RequestOptions,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(node) = target.as_xml_node() {
+        target.set("loaded", false.into(), activation)?;
+
+        // Create hidden properties backing `getBytesLoaded` and `getBytesTotal`.
+        for (name, value) in [
+            ("_bytesLoaded", 0.into()),
+            ("_bytesTotal", Value::Undefined),
+        ]
+        .iter()
+        {
+            if !target.has_property(activation, name) {
+                target.define_value(
+                    activation.context.gc_context,
+                    name,
+                    value.clone(),
+                    DontDelete | DontEnum,
+                );
+            } else {
+                target.set(name, value.clone(), activation)?;
+            }
+        }
+
+        let fetch = activation.context.navigator.fetch(url, request_options);
         let target_clip = activation.target_clip_or_root();
         let process = activation.context.load_manager.load_xml_into_node(
             activation.context.player.clone().unwrap(),
@@ -929,6 +986,24 @@ pub fn xml_load<'gc>(
     }
 }
 
+pub fn xml_get_bytes_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Forwards to undocumented property on the object.
+    this.get("_bytesLoaded", activation)
+}
+
+pub fn xml_get_bytes_total<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Forwards to undocumented property on the object.
+    this.get("_bytesTotal", activation)
+}
+
 pub fn xml_on_data<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -1126,6 +1201,40 @@ pub fn create_xml_proto<'gc>(
         EnumSet::empty(),
         Some(fn_proto),
     );
+    xml_proto.as_script_object().unwrap().force_set_function(
+        "sendAndLoad",
+        xml_send_and_load,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    xml_proto.as_script_object().unwrap().force_set_function(
+        "addRequestHeader",
+        load_vars::add_request_header,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    xml_proto.define_value(
+        gc_context,
+        "contentType",
+        "application/x-www-form-urlencoded".into(),
+        EnumSet::empty(),
+    );
+    xml_proto.as_script_object().unwrap().force_set_function(
+        "getBytesLoaded",
+        xml_get_bytes_loaded,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    xml_proto.as_script_object().unwrap().force_set_function(
+        "getBytesTotal",
+        xml_get_bytes_total,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
     xml_proto.as_script_object().unwrap().force_set_function(
         "onData",
         xml_on_data,
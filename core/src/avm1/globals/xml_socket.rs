@@ -0,0 +1,152 @@
+//! AVM1 XMLSocket object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property::Attribute;
+use crate::avm1::{Object, ScriptObject, TObject, Value, XmlSockets};
+use crate::avm_warn;
+use crate::display_object::TDisplayObject;
+use gc_arena::MutationContext;
+use url::Url;
+
+/// Implements `XMLSocket`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // No-op constructor
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    use Attribute::*;
+
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function(
+        "connect",
+        connect,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "send",
+        send,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "close",
+        close,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "onData",
+        on_data,
+        gc_context,
+        DontDelete | DontEnum,
+        Some(fn_proto),
+    );
+
+    object.into()
+}
+
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let movie = activation.base_clip().movie();
+    let movie_url = movie.as_ref().and_then(|movie| movie.url());
+
+    // A null host means the host that the movie was loaded from.
+    let host = match args.get(0).unwrap_or(&Value::Undefined) {
+        Value::Undefined | Value::Null => movie_url
+            .and_then(|url| Url::parse(url).ok())
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| "localhost".to_string()),
+        host => host.coerce_to_string(activation)?.to_string(),
+    };
+
+    // A loaded movie plays in its own sandbox, which may differ from the root movie's.
+    let sandbox_type = match &movie {
+        Some(movie) => activation.context.system.sandbox_type_for_movie(movie),
+        None => activation.context.system.sandbox_type,
+    };
+    if !sandbox_type.can_connect_socket(movie_url, &host) {
+        avm_warn!(
+            activation,
+            "*** Security Sandbox Violation *** XMLSocket.connect to {} from a {} movie",
+            host,
+            sandbox_type
+        );
+        return Ok(false.into());
+    }
+
+    // Flash Player refuses to connect to well-known ports.
+    let port = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation)?;
+    if !(1024..=i32::from(u16::MAX)).contains(&port) {
+        return Ok(false.into());
+    }
+
+    XmlSockets::connect(&mut activation.context, this, &host, port as u16);
+
+    Ok(true.into())
+}
+
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let message = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    XmlSockets::send(&mut activation.context, this, &message);
+
+    Ok(Value::Undefined)
+}
+
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    XmlSockets::close(&mut activation.context, this);
+
+    Ok(Value::Undefined)
+}
+
+/// The default `onData` handler, which parses each message into an `XML`
+/// object and passes it to `onXML`.
+fn on_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let src = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    let constructor = activation.context.avm1.prototypes.xml_constructor;
+    let xml = constructor.construct(activation, &[src])?;
+
+    this.call_method("onXML", &[xml.into()], activation)?;
+
+    Ok(Value::Undefined)
+}
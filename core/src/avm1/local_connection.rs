@@ -0,0 +1,200 @@
+//! Message handling for `LocalConnection` objects.
+//!
+//! Messages are routed by the player's `LocalConnectionBackend`, which is
+//! polled during our normal frame loop, so messages are always delivered
+//! asynchronously.
+
+use crate::avm1::{Activation, ActivationIdentifier, AvmString, Object, ScriptObject, TObject};
+use crate::backend::local_connection::{LocalConnectionEvent, LocalConnectionMessage};
+use crate::context::UpdateContext;
+use gc_arena::Collect;
+
+/// Manages the `LocalConnection` objects that are listening or waiting for
+/// the status of a message.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct LocalConnections<'gc> {
+    /// Objects listening on a connection.
+    listeners: Vec<Listener<'gc>>,
+
+    /// Objects waiting for the status of a message that they sent.
+    senders: Vec<Sender<'gc>>,
+}
+
+impl<'gc> LocalConnections<'gc> {
+    /// Creates an empty collection of connections.
+    pub fn new() -> Self {
+        Self {
+            listeners: Vec::new(),
+            senders: Vec::new(),
+        }
+    }
+
+    /// Starts listening on the given fully qualified connection name.
+    ///
+    /// Returns `false` if the object is already listening, or if the name is
+    /// already in use.
+    pub fn connect(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: Object<'gc>,
+        name: String,
+        domain: String,
+    ) -> bool {
+        if context.local_connections.listener(object).is_some()
+            || !context.local_connection.connect(&name)
+        {
+            return false;
+        }
+
+        context.local_connections.listeners.push(Listener {
+            object,
+            name,
+            domain,
+        });
+        true
+    }
+
+    /// Stops the given object from listening, if it is.
+    pub fn close(context: &mut UpdateContext<'_, 'gc, '_>, object: Object<'gc>) {
+        if let Some(index) = context.local_connections.listener(object) {
+            let listener = context.local_connections.listeners.remove(index);
+            context.local_connection.close(&listener.name);
+        }
+    }
+
+    /// Sends a message on behalf of the given object, which will be told
+    /// whether it was handled through `onStatus`.
+    pub fn send(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: Object<'gc>,
+        message: LocalConnectionMessage,
+    ) {
+        let id = context.local_connection.send(message);
+        context.local_connections.senders.push(Sender {
+            object,
+            message: id,
+        });
+    }
+
+    /// Polls the backend, delivering received messages and reporting the
+    /// status of sent ones.
+    pub fn update_local_connections(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let events = context.local_connection.poll_events();
+        if events.is_empty() {
+            return;
+        }
+
+        let level0 = match context.levels.get(&0).copied() {
+            Some(level0) => level0,
+            None => return,
+        };
+        let version = context.swf.header().version;
+        let globals = context.avm1.global_object_cell();
+
+        let mut activation = Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[LocalConnection]"),
+            version,
+            globals,
+            level0,
+        );
+
+        for event in events {
+            match event {
+                LocalConnectionEvent::Message(id, message) => {
+                    let success = Self::deliver(&mut activation, message);
+                    activation.context.local_connection.reply(id, success);
+                }
+                LocalConnectionEvent::Status(id, success) => {
+                    let senders = &mut activation.context.local_connections.senders;
+                    let object = match senders.iter().position(|sender| sender.message == id) {
+                        Some(index) => senders.remove(index).object,
+                        None => continue,
+                    };
+
+                    let info = ScriptObject::object(
+                        activation.context.gc_context,
+                        Some(activation.context.avm1.prototypes().object),
+                    );
+                    let level = if success { "status" } else { "error" };
+                    let _ = info.set("level", level.into(), &mut activation);
+
+                    let _ = object.call_method("onStatus", &[info.into()], &mut activation);
+                }
+            }
+        }
+    }
+
+    /// Calls the method of a received message on the object listening for it.
+    ///
+    /// Messages from other domains must be accepted by the receiver's
+    /// `allowDomain` handler. Returns whether the message was handled.
+    fn deliver(activation: &mut Activation<'_, 'gc, '_>, message: LocalConnectionMessage) -> bool {
+        let listener = activation
+            .context
+            .local_connections
+            .listeners
+            .iter()
+            .find(|listener| listener.name == message.connection_name);
+        let (object, domain) = match listener {
+            Some(listener) => (listener.object, listener.domain.clone()),
+            None => return false,
+        };
+
+        if !message.sender_domain.eq_ignore_ascii_case(&domain) {
+            let sender_domain =
+                AvmString::new(activation.context.gc_context, message.sender_domain);
+            let allowed = object
+                .call_method("allowDomain", &[sender_domain.into()], activation)
+                .map(|allowed| allowed.as_bool(activation.current_swf_version()))
+                .unwrap_or(false);
+            if !allowed {
+                return false;
+            }
+        }
+
+        let args: Vec<_> = message
+            .args
+            .into_iter()
+            .map(|arg| arg.into_avm1(activation))
+            .collect();
+        object
+            .call_method(&message.method_name, &args, activation)
+            .is_ok()
+    }
+
+    fn listener(&self, object: Object<'gc>) -> Option<usize> {
+        self.listeners
+            .iter()
+            .position(|listener| Object::ptr_eq(listener.object, object))
+    }
+}
+
+impl Default for LocalConnections<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A `LocalConnection` object listening on a connection.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct Listener<'gc> {
+    object: Object<'gc>,
+
+    /// The fully qualified connection name.
+    name: String,
+
+    /// The domain of the movie that created the connection.
+    domain: String,
+}
+
+/// A `LocalConnection` object waiting for the status of a message.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct Sender<'gc> {
+    object: Object<'gc>,
+
+    /// The ID of the message that was sent.
+    message: u64,
+}
@@ -3,6 +3,7 @@
 use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::object::shared_object::SharedObject;
+use crate::avm1::object::style_sheet_object::StyleSheetObject;
 use crate::avm1::object::super_object::SuperObject;
 use crate::avm1::object::value_object::ValueObject;
 use crate::avm1::property::Attribute;
@@ -10,8 +11,15 @@ use crate::avm1::property::Attribute;
 use crate::avm1::activation::Activation;
 use crate::avm1::object::bevel_filter::BevelFilterObject;
 use crate::avm1::object::blur_filter::BlurFilterObject;
+use crate::avm1::object::color_matrix_filter::ColorMatrixFilterObject;
 use crate::avm1::object::color_transform_object::ColorTransformObject;
+use crate::avm1::object::convolution_filter::ConvolutionFilterObject;
 use crate::avm1::object::date_object::DateObject;
+use crate::avm1::object::displacement_map_filter::DisplacementMapFilterObject;
+use crate::avm1::object::drop_shadow_filter::DropShadowFilterObject;
+use crate::avm1::object::glow_filter::GlowFilterObject;
+use crate::avm1::object::gradient_bevel_filter::GradientBevelFilterObject;
+use crate::avm1::object::gradient_glow_filter::GradientGlowFilterObject;
 use crate::avm1::object::transform_object::TransformObject;
 use crate::avm1::object::xml_attributes_object::XMLAttributesObject;
 use crate::avm1::object::xml_idmap_object::XMLIDMapObject;
@@ -28,13 +36,21 @@ use std::fmt::Debug;
 
 pub mod bevel_filter;
 pub mod blur_filter;
+pub mod color_matrix_filter;
 pub mod color_transform_object;
+pub mod convolution_filter;
 mod custom_object;
 pub mod date_object;
+pub mod displacement_map_filter;
+pub mod drop_shadow_filter;
+pub mod glow_filter;
+pub mod gradient_bevel_filter;
+pub mod gradient_glow_filter;
 pub mod script_object;
 pub mod shared_object;
 pub mod sound_object;
 pub mod stage_object;
+pub mod style_sheet_object;
 pub mod super_object;
 pub mod transform_object;
 pub mod value_object;
@@ -62,7 +78,15 @@ pub mod xml_object;
         TransformObject(TransformObject<'gc>),
         BlurFilterObject(BlurFilterObject<'gc>),
         BevelFilterObject(BevelFilterObject<'gc>),
+        ColorMatrixFilterObject(ColorMatrixFilterObject<'gc>),
+        ConvolutionFilterObject(ConvolutionFilterObject<'gc>),
+        DisplacementMapFilterObject(DisplacementMapFilterObject<'gc>),
+        DropShadowFilterObject(DropShadowFilterObject<'gc>),
+        GlowFilterObject(GlowFilterObject<'gc>),
+        GradientBevelFilterObject(GradientBevelFilterObject<'gc>),
+        GradientGlowFilterObject(GradientGlowFilterObject<'gc>),
         DateObject(DateObject<'gc>),
+        StyleSheetObject(StyleSheetObject<'gc>),
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -429,6 +453,11 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    /// Get the underlying `StyleSheetObject`, if it exists
+    fn as_style_sheet_object(&self) -> Option<StyleSheetObject<'gc>> {
+        None
+    }
+
     /// Get the underlying `ColorTransformObject`, if it exists
     fn as_color_transform_object(&self) -> Option<ColorTransformObject<'gc>> {
         None
@@ -449,6 +478,41 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    /// Get the underlying `ColorMatrixFilterObject`, if it exists
+    fn as_color_matrix_filter_object(&self) -> Option<ColorMatrixFilterObject<'gc>> {
+        None
+    }
+
+    /// Get the underlying `ConvolutionFilterObject`, if it exists
+    fn as_convolution_filter_object(&self) -> Option<ConvolutionFilterObject<'gc>> {
+        None
+    }
+
+    /// Get the underlying `DisplacementMapFilterObject`, if it exists
+    fn as_displacement_map_filter_object(&self) -> Option<DisplacementMapFilterObject<'gc>> {
+        None
+    }
+
+    /// Get the underlying `DropShadowFilterObject`, if it exists
+    fn as_drop_shadow_filter_object(&self) -> Option<DropShadowFilterObject<'gc>> {
+        None
+    }
+
+    /// Get the underlying `GlowFilterObject`, if it exists
+    fn as_glow_filter_object(&self) -> Option<GlowFilterObject<'gc>> {
+        None
+    }
+
+    /// Get the underlying `GradientBevelFilterObject`, if it exists
+    fn as_gradient_bevel_filter_object(&self) -> Option<GradientBevelFilterObject<'gc>> {
+        None
+    }
+
+    /// Get the underlying `GradientGlowFilterObject`, if it exists
+    fn as_gradient_glow_filter_object(&self) -> Option<GradientGlowFilterObject<'gc>> {
+        None
+    }
+
     fn as_ptr(&self) -> *const ObjectPtr;
 
     /// Check if this object is in the prototype chain of the specified test object.
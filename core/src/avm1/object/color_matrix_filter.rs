@@ -0,0 +1,96 @@
+use crate::avm1::error::Error;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::impl_custom_object_without_set;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+use crate::avm1::activation::Activation;
+use std::fmt;
+
+/// A ColorMatrixFilter
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct ColorMatrixFilterObject<'gc>(GcCell<'gc, ColorMatrixFilterData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct ColorMatrixFilterData<'gc> {
+    /// The underlying script object.
+    base: ScriptObject<'gc>,
+
+    /// The 4x5 matrix that transforms the RGBA components of each pixel, in row-major order.
+    matrix: Vec<f64>,
+}
+
+impl fmt::Debug for ColorMatrixFilterObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let this = self.0.read();
+        f.debug_struct("ColorMatrixFilter")
+            .field("matrix", &this.matrix)
+            .finish()
+    }
+}
+
+impl<'gc> ColorMatrixFilterObject<'gc> {
+    /// Sets the matrix, which is padded with zeroes or truncated to 20 elements.
+    pub fn set_matrix(&self, gc_context: MutationContext<'gc, '_>, mut matrix: Vec<f64>) {
+        matrix.resize(20, 0.0);
+        self.0.write(gc_context).matrix = matrix;
+    }
+
+    pub fn get_matrix(&self) -> Vec<f64> {
+        self.0.read().matrix.clone()
+    }
+
+    pub fn empty_object(gc_context: MutationContext<'gc, '_>, proto: Option<Object<'gc>>) -> Self {
+        #[rustfmt::skip]
+        let identity = vec![
+            1.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 1.0, 0.0,
+        ];
+        ColorMatrixFilterObject(GcCell::allocate(
+            gc_context,
+            ColorMatrixFilterData {
+                base: ScriptObject::object(gc_context, proto),
+                matrix: identity,
+            },
+        ))
+    }
+}
+
+impl<'gc> TObject<'gc> for ColorMatrixFilterObject<'gc> {
+    impl_custom_object_without_set!(base);
+
+    fn set(
+        &self,
+        name: &str,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        let base = self.0.read().base;
+        base.internal_set(
+            name,
+            value,
+            activation,
+            (*self).into(),
+            Some(activation.context.avm1.prototypes.color_matrix_filter),
+        )
+    }
+
+    fn as_color_matrix_filter_object(&self) -> Option<ColorMatrixFilterObject<'gc>> {
+        Some(*self)
+    }
+
+    fn create_bare_object(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Object<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        Ok(ColorMatrixFilterObject::empty_object(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.color_matrix_filter),
+        )
+        .into())
+    }
+}
@@ -0,0 +1,148 @@
+use crate::add_field_accessors;
+use crate::avm1::error::Error;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::impl_custom_object_without_set;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+use crate::avm1::activation::Activation;
+use std::fmt;
+
+/// A ConvolutionFilter
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct ConvolutionFilterObject<'gc>(GcCell<'gc, ConvolutionFilterData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct ConvolutionFilterData<'gc> {
+    /// The underlying script object.
+    base: ScriptObject<'gc>,
+
+    alpha: f64,
+    bias: f64,
+    clamp: bool,
+    color: u32,
+    divisor: f64,
+    /// The `matrix_x` by `matrix_y` matrix, in row-major order.
+    matrix: Vec<f64>,
+    matrix_x: u8,
+    matrix_y: u8,
+    preserve_alpha: bool,
+}
+
+impl fmt::Debug for ConvolutionFilterObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let this = self.0.read();
+        f.debug_struct("ConvolutionFilter")
+            .field("alpha", &this.alpha)
+            .field("bias", &this.bias)
+            .field("clamp", &this.clamp)
+            .field("color", &this.color)
+            .field("divisor", &this.divisor)
+            .field("matrix", &this.matrix)
+            .field("matrixX", &this.matrix_x)
+            .field("matrixY", &this.matrix_y)
+            .field("preserveAlpha", &this.preserve_alpha)
+            .finish()
+    }
+}
+
+impl<'gc> ConvolutionFilterObject<'gc> {
+    add_field_accessors!(
+        [set_alpha, get_alpha, alpha, f64],
+        [set_bias, get_bias, bias, f64],
+        [set_clamp, get_clamp, clamp, bool],
+        [set_color, get_color, color, u32],
+        [set_divisor, get_divisor, divisor, f64],
+        [set_preserve_alpha, get_preserve_alpha, preserve_alpha, bool],
+    );
+
+    pub fn get_matrix(&self) -> Vec<f64> {
+        self.0.read().matrix.clone()
+    }
+
+    /// Sets the matrix, which is padded with zeroes or truncated to fit the
+    /// current dimensions.
+    pub fn set_matrix(&self, gc_context: MutationContext<'gc, '_>, mut matrix: Vec<f64>) {
+        let mut write = self.0.write(gc_context);
+        matrix.resize(
+            usize::from(write.matrix_x) * usize::from(write.matrix_y),
+            0.0,
+        );
+        write.matrix = matrix;
+    }
+
+    pub fn get_matrix_x(&self) -> u8 {
+        self.0.read().matrix_x
+    }
+
+    pub fn set_matrix_x(&self, gc_context: MutationContext<'gc, '_>, matrix_x: u8) {
+        let matrix = self.get_matrix();
+        self.0.write(gc_context).matrix_x = matrix_x;
+        self.set_matrix(gc_context, matrix);
+    }
+
+    pub fn get_matrix_y(&self) -> u8 {
+        self.0.read().matrix_y
+    }
+
+    pub fn set_matrix_y(&self, gc_context: MutationContext<'gc, '_>, matrix_y: u8) {
+        let matrix = self.get_matrix();
+        self.0.write(gc_context).matrix_y = matrix_y;
+        self.set_matrix(gc_context, matrix);
+    }
+
+    pub fn empty_object(gc_context: MutationContext<'gc, '_>, proto: Option<Object<'gc>>) -> Self {
+        ConvolutionFilterObject(GcCell::allocate(
+            gc_context,
+            ConvolutionFilterData {
+                base: ScriptObject::object(gc_context, proto),
+                alpha: 0.0,
+                bias: 0.0,
+                clamp: true,
+                color: 0x000000,
+                divisor: 1.0,
+                matrix: vec![],
+                matrix_x: 0,
+                matrix_y: 0,
+                preserve_alpha: true,
+            },
+        ))
+    }
+}
+
+impl<'gc> TObject<'gc> for ConvolutionFilterObject<'gc> {
+    impl_custom_object_without_set!(base);
+
+    fn set(
+        &self,
+        name: &str,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        let base = self.0.read().base;
+        base.internal_set(
+            name,
+            value,
+            activation,
+            (*self).into(),
+            Some(activation.context.avm1.prototypes.convolution_filter),
+        )
+    }
+
+    fn as_convolution_filter_object(&self) -> Option<ConvolutionFilterObject<'gc>> {
+        Some(*self)
+    }
+
+    fn create_bare_object(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Object<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        Ok(ConvolutionFilterObject::empty_object(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.convolution_filter),
+        )
+        .into())
+    }
+}
@@ -0,0 +1,149 @@
+use crate::add_field_accessors;
+use crate::avm1::error::Error;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::impl_custom_object_without_set;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+use crate::avm1::activation::Activation;
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub enum DisplacementMapFilterMode {
+    Wrap,
+    Clamp,
+    Ignore,
+    Color,
+}
+
+impl From<&str> for DisplacementMapFilterMode {
+    fn from(value: &str) -> Self {
+        match value {
+            "clamp" => DisplacementMapFilterMode::Clamp,
+            "ignore" => DisplacementMapFilterMode::Ignore,
+            "color" => DisplacementMapFilterMode::Color,
+            _ => DisplacementMapFilterMode::Wrap,
+        }
+    }
+}
+
+impl From<DisplacementMapFilterMode> for &str {
+    fn from(v: DisplacementMapFilterMode) -> Self {
+        match v {
+            DisplacementMapFilterMode::Wrap => "wrap",
+            DisplacementMapFilterMode::Clamp => "clamp",
+            DisplacementMapFilterMode::Ignore => "ignore",
+            DisplacementMapFilterMode::Color => "color",
+        }
+    }
+}
+
+/// A DisplacementMapFilter
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct DisplacementMapFilterObject<'gc>(GcCell<'gc, DisplacementMapFilterData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct DisplacementMapFilterData<'gc> {
+    /// The underlying script object.
+    base: ScriptObject<'gc>,
+
+    alpha: f64,
+    color: u32,
+    component_x: i32,
+    component_y: i32,
+    map_bitmap: Option<Object<'gc>>,
+    map_point_x: f64,
+    map_point_y: f64,
+    mode: DisplacementMapFilterMode,
+    scale_x: f64,
+    scale_y: f64,
+}
+
+impl fmt::Debug for DisplacementMapFilterObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let this = self.0.read();
+        f.debug_struct("DisplacementMapFilter")
+            .field("alpha", &this.alpha)
+            .field("color", &this.color)
+            .field("componentX", &this.component_x)
+            .field("componentY", &this.component_y)
+            .field("mapBitmap", &this.map_bitmap)
+            .field("mapPoint", &(this.map_point_x, this.map_point_y))
+            .field("mode", &this.mode)
+            .field("scaleX", &this.scale_x)
+            .field("scaleY", &this.scale_y)
+            .finish()
+    }
+}
+
+impl<'gc> DisplacementMapFilterObject<'gc> {
+    add_field_accessors!(
+        [set_alpha, get_alpha, alpha, f64],
+        [set_color, get_color, color, u32],
+        [set_component_x, get_component_x, component_x, i32],
+        [set_component_y, get_component_y, component_y, i32],
+        [set_map_bitmap, get_map_bitmap, map_bitmap, Option<Object<'gc>>],
+        [set_map_point_x, get_map_point_x, map_point_x, f64],
+        [set_map_point_y, get_map_point_y, map_point_y, f64],
+        [set_mode, get_mode, mode, DisplacementMapFilterMode],
+        [set_scale_x, get_scale_x, scale_x, f64],
+        [set_scale_y, get_scale_y, scale_y, f64],
+    );
+
+    pub fn empty_object(gc_context: MutationContext<'gc, '_>, proto: Option<Object<'gc>>) -> Self {
+        DisplacementMapFilterObject(GcCell::allocate(
+            gc_context,
+            DisplacementMapFilterData {
+                base: ScriptObject::object(gc_context, proto),
+                alpha: 0.0,
+                color: 0x000000,
+                component_x: 0,
+                component_y: 0,
+                map_bitmap: None,
+                map_point_x: 0.0,
+                map_point_y: 0.0,
+                mode: DisplacementMapFilterMode::Wrap,
+                scale_x: 0.0,
+                scale_y: 0.0,
+            },
+        ))
+    }
+}
+
+impl<'gc> TObject<'gc> for DisplacementMapFilterObject<'gc> {
+    impl_custom_object_without_set!(base);
+
+    fn set(
+        &self,
+        name: &str,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        let base = self.0.read().base;
+        base.internal_set(
+            name,
+            value,
+            activation,
+            (*self).into(),
+            Some(activation.context.avm1.prototypes.displacement_map_filter),
+        )
+    }
+
+    fn as_displacement_map_filter_object(&self) -> Option<DisplacementMapFilterObject<'gc>> {
+        Some(*self)
+    }
+
+    fn create_bare_object(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Object<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        Ok(DisplacementMapFilterObject::empty_object(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.displacement_map_filter),
+        )
+        .into())
+    }
+}
@@ -133,6 +133,10 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Value<'gc>, Error<'gc>> {
         let obj = self.0.read();
+        if !activation.check_script_access(obj.display_object) {
+            return Ok(Value::Undefined);
+        }
+
         let props = activation.context.avm1.display_properties;
         let case_sensitive = activation.is_case_sensitive();
         // Property search order for DisplayObjects:
@@ -175,6 +179,10 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<(), Error<'gc>> {
         let obj = self.0.read();
+        if !activation.check_script_access(obj.display_object) {
+            return Ok(());
+        }
+
         let props = activation.context.avm1.display_properties;
 
         // Check if a text field is bound to this property and update the text if so.
@@ -247,7 +255,12 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
     }
 
     fn delete(&self, activation: &mut Activation<'_, 'gc, '_>, name: &str) -> bool {
-        self.0.read().base.delete(activation, name)
+        let obj = self.0.read();
+        if !activation.check_script_access(obj.display_object) {
+            return false;
+        }
+
+        obj.base.delete(activation, name)
     }
 
     fn proto(&self) -> Option<Object<'gc>> {
@@ -395,6 +408,10 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         // Keys from the underlying object are listed first, followed by
         // child display objects in order from highest depth to lowest depth.
         let obj = self.0.read();
+        if !activation.check_script_access(obj.display_object) {
+            return Vec::new();
+        }
+
         let mut keys = obj.base.get_keys(activation);
         keys.extend(
             obj.display_object
@@ -856,19 +873,22 @@ fn set_high_quality<'gc>(
 }
 
 fn focus_rect<'gc>(
-    activation: &mut Activation<'_, 'gc, '_>,
-    _this: DisplayObject<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
-    Ok(Value::Null)
+    Ok(this.focus_rect().map(Value::from).unwrap_or(Value::Null))
 }
 
 fn set_focus_rect<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    this: DisplayObject<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
+    let focus_rect = match val {
+        Value::Undefined | Value::Null => None,
+        val => Some(val.as_bool(activation.current_swf_version())),
+    };
+    this.set_focus_rect(activation.context.gc_context, focus_rect);
     Ok(())
 }
 
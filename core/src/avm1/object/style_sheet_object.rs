@@ -0,0 +1,77 @@
+use crate::html::StyleSheet;
+use crate::impl_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::{Object, ScriptObject, TObject};
+use std::cell::{Ref, RefMut};
+use std::fmt;
+
+/// A TextField.StyleSheet
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct StyleSheetObject<'gc>(GcCell<'gc, StyleSheetObjectData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct StyleSheetObjectData<'gc> {
+    /// The underlying script object.
+    base: ScriptObject<'gc>,
+
+    /// The styles defined on this stylesheet.
+    style_sheet: StyleSheet,
+}
+
+impl fmt::Debug for StyleSheetObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let this = self.0.read();
+        f.debug_struct("StyleSheetObject")
+            .field("style_sheet", &this.style_sheet)
+            .finish()
+    }
+}
+
+impl<'gc> StyleSheetObject<'gc> {
+    pub fn empty_style_sheet(
+        gc_context: MutationContext<'gc, '_>,
+        proto: Option<Object<'gc>>,
+    ) -> Self {
+        StyleSheetObject(GcCell::allocate(
+            gc_context,
+            StyleSheetObjectData {
+                base: ScriptObject::object(gc_context, proto),
+                style_sheet: StyleSheet::new(),
+            },
+        ))
+    }
+
+    pub fn style_sheet(&self) -> Ref<StyleSheet> {
+        Ref::map(self.0.read(), |data| &data.style_sheet)
+    }
+
+    pub fn style_sheet_mut(&self, gc_context: MutationContext<'gc, '_>) -> RefMut<StyleSheet> {
+        RefMut::map(self.0.write(gc_context), |data| &mut data.style_sheet)
+    }
+}
+
+impl<'gc> TObject<'gc> for StyleSheetObject<'gc> {
+    impl_custom_object!(base);
+
+    #[allow(clippy::new_ret_no_self)]
+    fn create_bare_object(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Object<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        Ok(StyleSheetObject::empty_style_sheet(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.style_sheet),
+        )
+        .into())
+    }
+
+    fn as_style_sheet_object(&self) -> Option<StyleSheetObject<'gc>> {
+        Some(*self)
+    }
+}
@@ -1,21 +1,25 @@
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::error::Error;
 use crate::avm1::globals::system::SystemProperties;
-use crate::avm1::{Avm1, Object, Timers, UpdateContext};
+use crate::avm1::{Avm1, LocalConnections, Object, Timers, UpdateContext, XmlSockets};
 use crate::avm2::Avm2;
-use crate::backend::audio::NullAudioBackend;
+use crate::backend::audio::{AudioManager, NullAudioBackend};
 use crate::backend::input::NullInputBackend;
+use crate::backend::local_connection::LocalConnectionBroker;
 use crate::backend::locale::NullLocaleBackend;
 use crate::backend::log::NullLogBackend;
 use crate::backend::navigator::NullNavigatorBackend;
 use crate::backend::render::NullRenderer;
+use crate::backend::socket::NullSocketBackend;
 use crate::backend::storage::MemoryStorageBackend;
+use crate::backend::video::NullVideoBackend;
 use crate::context::ActionQueue;
 use crate::display_object::{MovieClip, TDisplayObject};
 use crate::focus_tracker::FocusTracker;
 use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::prelude::*;
+use crate::security::ScriptPermissions;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::vminterface::Instantiator;
 use gc_arena::{rootless_arena, MutationContext};
@@ -51,6 +55,7 @@ where
             levels: &mut levels,
             rng: &mut SmallRng::from_seed([0u8; 16]),
             audio: &mut NullAudioBackend::new(),
+            audio_manager: &mut AudioManager::new(),
             input: &mut NullInputBackend::new(),
             action_queue: &mut ActionQueue::new(),
             background_color: &mut Color {
@@ -64,6 +69,9 @@ where
             renderer: &mut NullRenderer::new(),
             locale: &mut NullLocaleBackend::new(),
             log: &mut NullLogBackend::new(),
+            video: &mut NullVideoBackend::new(),
+            socket: &mut NullSocketBackend::new(),
+            local_connection: &mut LocalConnectionBroker::new().endpoint(),
             system_prototypes: avm1.prototypes().clone(),
             mouse_hovered_object: None,
             mouse_position: &(Twips::new(0), Twips::new(0)),
@@ -72,11 +80,14 @@ where
             player: None,
             load_manager: &mut LoadManager::new(),
             system: &mut SystemProperties::default(),
+            script_permissions: &mut ScriptPermissions::new(),
             instance_counter: &mut 0,
             storage: &mut MemoryStorageBackend::default(),
             shared_objects: &mut HashMap::new(),
             unbound_text_fields: &mut Vec::new(),
             timers: &mut Timers::new(),
+            xml_sockets: &mut XmlSockets::new(),
+            local_connections: &mut LocalConnections::new(),
             needs_render: &mut false,
             avm1: &mut avm1,
             avm2: &mut avm2,
@@ -84,6 +95,7 @@ where
             update_start: Instant::now(),
             max_execution_duration: Duration::from_secs(15),
             focus_tracker: FocusTracker::new(gc_context),
+            current_context_menu: &mut None,
         };
         root.post_instantiation(&mut context, root, None, Instantiator::Movie, false);
         root.set_name(context.gc_context, "");
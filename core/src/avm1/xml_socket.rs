@@ -0,0 +1,215 @@
+//! Connection handling for `XMLSocket` objects.
+//!
+//! Sockets are polled during our normal frame loop, and any received data is
+//! split into null-terminated messages that are handed to `onData`.
+
+use crate::avm1::{Activation, ActivationIdentifier, AvmString, Object, TObject};
+use crate::backend::socket::{SocketEvent, SocketHandle};
+use crate::context::UpdateContext;
+use gc_arena::Collect;
+
+/// Manages the collection of open `XMLSocket` connections.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct XmlSockets<'gc> {
+    sockets: Vec<XmlSocket<'gc>>,
+}
+
+impl<'gc> XmlSockets<'gc> {
+    /// Creates an empty collection of sockets.
+    pub fn new() -> Self {
+        Self {
+            sockets: Vec::new(),
+        }
+    }
+
+    /// Registers a new connection attempt for the given `XMLSocket` object.
+    ///
+    /// Any connection that the object already had is closed.
+    pub fn connect(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: Object<'gc>,
+        host: &str,
+        port: u16,
+    ) {
+        Self::close(context, object);
+        let handle = context.socket.connect(host, port);
+        context.xml_sockets.sockets.push(XmlSocket {
+            object,
+            handle,
+            is_connected: false,
+            buffer: Vec::new(),
+        });
+    }
+
+    /// Sends a message over the connection of the given `XMLSocket` object.
+    ///
+    /// The message is null-terminated, as expected by XMLSocket servers.
+    /// Returns `false` if the object isn't connected.
+    pub fn send(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: Object<'gc>,
+        message: &str,
+    ) -> bool {
+        let handle = match context.xml_sockets.get(object) {
+            Some(socket) if socket.is_connected => socket.handle,
+            _ => return false,
+        };
+
+        let mut data = Vec::with_capacity(message.len() + 1);
+        data.extend_from_slice(message.as_bytes());
+        data.push(0);
+        context.socket.send(handle, data);
+        true
+    }
+
+    /// Closes the connection of the given `XMLSocket` object, if it has one.
+    ///
+    /// `onClose` is not called for connections closed by the movie itself.
+    pub fn close(context: &mut UpdateContext<'_, 'gc, '_>, object: Object<'gc>) {
+        if let Some(socket) = context.xml_sockets.remove(object) {
+            context.socket.close(socket.handle);
+        }
+    }
+
+    /// Polls the socket backend and runs the callbacks of any affected sockets.
+    pub fn update_sockets(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let events = context.socket.poll_events();
+        if events.is_empty() {
+            return;
+        }
+
+        let level0 = match context.levels.get(&0).copied() {
+            Some(level0) => level0,
+            None => return,
+        };
+        let version = context.swf.header().version;
+        let globals = context.avm1.global_object_cell();
+
+        let mut activation = Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[XMLSocket]"),
+            version,
+            globals,
+            level0,
+        );
+
+        for event in events {
+            match event {
+                SocketEvent::Connect(handle, success) => {
+                    let socket = match activation.context.xml_sockets.get_by_handle_mut(handle) {
+                        Some(socket) => socket,
+                        None => continue,
+                    };
+                    socket.is_connected = success;
+                    let object = socket.object;
+                    if !success {
+                        activation.context.xml_sockets.remove(object);
+                    }
+
+                    let _ = object.call_method("onConnect", &[success.into()], &mut activation);
+                }
+                SocketEvent::Data(handle, data) => {
+                    let socket = match activation.context.xml_sockets.get_by_handle_mut(handle) {
+                        Some(socket) => socket,
+                        None => continue,
+                    };
+                    socket.buffer.extend_from_slice(&data);
+                    let messages = split_messages(&mut socket.buffer);
+                    let object = socket.object;
+
+                    for message in messages {
+                        let message = AvmString::new(activation.context.gc_context, message);
+                        let _ = object.call_method("onData", &[message.into()], &mut activation);
+                    }
+                }
+                SocketEvent::Close(handle) => {
+                    let object = match activation.context.xml_sockets.get_by_handle_mut(handle) {
+                        Some(socket) => socket.object,
+                        None => continue,
+                    };
+                    activation.context.xml_sockets.remove(object);
+
+                    let _ = object.call_method("onClose", &[], &mut activation);
+                }
+            }
+        }
+    }
+
+    fn get(&self, object: Object<'gc>) -> Option<&XmlSocket<'gc>> {
+        self.sockets
+            .iter()
+            .find(|socket| Object::ptr_eq(socket.object, object))
+    }
+
+    fn get_by_handle_mut(&mut self, handle: SocketHandle) -> Option<&mut XmlSocket<'gc>> {
+        self.sockets
+            .iter_mut()
+            .find(|socket| socket.handle == handle)
+    }
+
+    fn remove(&mut self, object: Object<'gc>) -> Option<XmlSocket<'gc>> {
+        let index = self
+            .sockets
+            .iter()
+            .position(|socket| Object::ptr_eq(socket.object, object))?;
+        Some(self.sockets.remove(index))
+    }
+}
+
+impl Default for XmlSockets<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single `XMLSocket` connection.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct XmlSocket<'gc> {
+    /// The `XMLSocket` object that receives callbacks for this connection.
+    object: Object<'gc>,
+
+    /// The backend handle of this connection.
+    handle: SocketHandle,
+
+    /// Whether the connection attempt has succeeded.
+    is_connected: bool,
+
+    /// Received data that doesn't form a complete message yet.
+    buffer: Vec<u8>,
+}
+
+/// Removes all complete, null-terminated messages from the front of `buffer`.
+fn split_messages(buffer: &mut Vec<u8>) -> Vec<String> {
+    let end = match buffer.iter().rposition(|&b| b == 0) {
+        Some(end) => end,
+        None => return Vec::new(),
+    };
+
+    let messages = buffer[..end]
+        .split(|&b| b == 0)
+        .map(|message| String::from_utf8_lossy(message).into_owned())
+        .collect();
+    buffer.drain(..=end);
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_messages;
+
+    #[test]
+    fn split_null_terminated_messages() {
+        let mut buffer = b"<a/>\0<b>text</b>\0<c".to_vec();
+        assert_eq!(split_messages(&mut buffer), vec!["<a/>", "<b>text</b>"]);
+        assert_eq!(buffer, b"<c");
+
+        assert!(split_messages(&mut buffer).is_empty());
+        assert_eq!(buffer, b"<c");
+
+        buffer.extend_from_slice(b"/>\0\0");
+        assert_eq!(split_messages(&mut buffer), vec!["<c/>", ""]);
+        assert!(buffer.is_empty());
+    }
+}
@@ -1,10 +1,14 @@
 //! ActionScript Virtual Machine 2 (AS3) support
 
+use crate::avm2::events::{dispatch_event_to_target, EventPhase};
 use crate::avm2::globals::SystemPrototypes;
 use crate::avm2::method::Method;
+use crate::avm2::object::EventObject;
 use crate::avm2::script::{Script, TranslationUnit};
+use crate::avm2::string::AvmString;
 use crate::context::UpdateContext;
 use crate::tag_utils::SwfSlice;
+use fnv::FnvHashMap;
 use gc_arena::{Collect, MutationContext};
 use std::rc::Rc;
 use swf::avm2::read::Reader;
@@ -20,8 +24,13 @@ macro_rules! avm_debug {
 
 mod activation;
 mod array;
+mod bitmapdata;
+mod bytearray;
 mod class;
 mod domain;
+mod e4x;
+mod error;
+mod events;
 mod function;
 mod globals;
 mod method;
@@ -29,6 +38,7 @@ mod names;
 mod object;
 mod property;
 mod property_map;
+mod regexp;
 mod return_value;
 mod scope;
 mod script;
@@ -36,19 +46,16 @@ mod slot;
 mod string;
 mod traits;
 mod value;
+mod vector;
 
 pub use crate::avm2::activation::Activation;
 pub use crate::avm2::domain::Domain;
+pub use crate::avm2::error::{Error, ErrorClass};
+pub use crate::avm2::events::Event;
 pub use crate::avm2::names::{Namespace, QName};
 pub use crate::avm2::object::{Object, StageObject, TObject};
 pub use crate::avm2::value::Value;
 
-/// Boxed error alias.
-///
-/// As AVM2 is a far stricter VM than AVM1, this may eventually be replaced
-/// with a proper Avm2Error enum.
-pub type Error = Box<dyn std::error::Error>;
-
 /// The state of an AVM2 interpreter.
 #[derive(Collect)]
 #[collect(no_drop)]
@@ -62,6 +69,22 @@ pub struct Avm2<'gc> {
     /// System prototypes.
     system_prototypes: Option<SystemPrototypes<'gc>>,
 
+    /// A list of objects which are capable of recieving broadcasts.
+    ///
+    /// Certain types of events are "broadcast events" that are emitted on all
+    /// constructed objects in order of their creation, whether or not they are
+    /// currently present on the display list. This list keeps track of that.
+    broadcast_list: FnvHashMap<AvmString<'gc>, Vec<Object<'gc>>>,
+
+    /// Specializations of `Vector` that have been created so far, keyed by
+    /// the class of their value type.
+    ///
+    /// A value type of `None` indicates `Vector.<*>`.
+    pub vector_classes: Vec<(Option<Object<'gc>>, Object<'gc>)>,
+
+    /// The `Stage` object, created the first time script asks for it.
+    pub stage_object: Option<Object<'gc>>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -75,13 +98,16 @@ impl<'gc> Avm2<'gc> {
             stack: Vec::new(),
             globals,
             system_prototypes: None,
+            broadcast_list: Default::default(),
+            vector_classes: Vec::new(),
+            stage_object: None,
 
             #[cfg(feature = "avm_debug")]
             debug_output: false,
         }
     }
 
-    pub fn load_player_globals(context: &mut UpdateContext<'_, 'gc, '_>) -> Result<(), Error> {
+    pub fn load_player_globals(context: &mut UpdateContext<'_, 'gc, '_>) -> Result<(), Error<'gc>> {
         let globals = context.avm2.globals;
         let mut activation = Activation::from_nothing(context.reborrow());
         globals::load_player_globals(&mut activation, globals)
@@ -98,7 +124,7 @@ impl<'gc> Avm2<'gc> {
     pub fn run_script_initializer(
         script: Script<'gc>,
         context: &mut UpdateContext<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let mut init_activation = Activation::from_script(context.reborrow(), script)?;
 
         let (method, scope) = script.init();
@@ -119,7 +145,7 @@ impl<'gc> Avm2<'gc> {
         reciever: Option<Object<'gc>>,
         args: &[Value<'gc>],
         context: &mut UpdateContext<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let mut evt_activation = Activation::from_nothing(context.reborrow());
         callable.call(
             reciever,
@@ -131,6 +157,116 @@ impl<'gc> Avm2<'gc> {
         Ok(())
     }
 
+    /// Dispatch an event on an object.
+    ///
+    /// The event is constructed as an instance of the class whose prototype
+    /// is `event_proto`, with `args` passed to that class's constructor. It
+    /// is then captured, fired, and bubbled through the display hierarchy
+    /// containing `target`.
+    ///
+    /// This function returns if the event was cancelled or not.
+    pub fn dispatch_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_proto: Object<'gc>,
+        args: &[Value<'gc>],
+        target: Object<'gc>,
+    ) -> Result<bool, Error<'gc>> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        let event_object = construct_event(&mut activation, event_proto, args)?;
+
+        events::dispatch_event(&mut activation, target, event_object)
+    }
+
+    /// Add an object to the broadcast list.
+    ///
+    /// Each broadcastable event contains its own broadcast list. You must
+    /// register all objects that have event handlers with that event's
+    /// broadcast list by calling this function. Attempting to register a
+    /// broadcast listener for a non-broadcast event will do nothing.
+    ///
+    /// Attempts to register the same listener for the same event will also do
+    /// nothing.
+    pub fn register_broadcast_listener(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: Object<'gc>,
+        event_name: AvmString<'gc>,
+    ) {
+        if !BROADCAST_WHITELIST.iter().any(|x| *x == event_name) {
+            return;
+        }
+
+        let bucket = context
+            .avm2
+            .broadcast_list
+            .entry(event_name)
+            .or_insert_with(Vec::new);
+
+        if bucket.iter().any(|x| Object::ptr_eq(*x, object)) {
+            return;
+        }
+
+        bucket.push(object);
+    }
+
+    /// Remove an object from the broadcast list of a given event.
+    pub fn unregister_broadcast_listener(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: Object<'gc>,
+        event_name: AvmString<'gc>,
+    ) {
+        if let Some(bucket) = context.avm2.broadcast_list.get_mut(&event_name) {
+            bucket.retain(|x| !Object::ptr_eq(*x, object));
+        }
+    }
+
+    /// Dispatch an event on all objects in the current execution list.
+    ///
+    /// `on_class_proto` specifies a class or interface prototype whose
+    /// instances, implementers, and/or subclasses define the set of objects
+    /// that will receive the event. You can broadcast to just display objects,
+    /// or specific interfaces, and so on.
+    ///
+    /// Broadcast events are fired on each receiving object individually; they
+    /// neither capture nor bubble. Errors raised by any one handler are
+    /// reported without preventing the event from reaching other objects.
+    pub fn broadcast_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event: Event<'gc>,
+        on_class_proto: Object<'gc>,
+    ) {
+        let event_name = event.event_type();
+        if !BROADCAST_WHITELIST.iter().any(|x| *x == event_name) {
+            return;
+        }
+
+        let el_length = context
+            .avm2
+            .broadcast_list
+            .get(&event_name)
+            .map(|bl| bl.len())
+            .unwrap_or(0);
+
+        for i in 0..el_length {
+            let object = context
+                .avm2
+                .broadcast_list
+                .get(&event_name)
+                .and_then(|bl| bl.get(i))
+                .copied();
+
+            if let Some(object) = object {
+                let mut activation = Activation::from_nothing(context.reborrow());
+                let result =
+                    broadcast_to_object(&mut activation, event.clone(), object, on_class_proto);
+                drop(activation);
+
+                if let Err(e) = result {
+                    root_error_handler(context, e);
+                }
+            }
+        }
+    }
+
     /// Load an ABC file embedded in a `SwfSlice`.
     ///
     /// The `SwfSlice` must resolve to the contents of an ABC file.
@@ -140,7 +276,7 @@ impl<'gc> Avm2<'gc> {
         lazy_init: bool,
         context: &mut UpdateContext<'_, 'gc, '_>,
         domain: Domain<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let mut read = Reader::new(abc.as_ref());
 
         let abc_file = Rc::new(read.read()?);
@@ -210,3 +346,76 @@ impl<'gc> Avm2<'gc> {
     #[cfg(not(feature = "avm_debug"))]
     pub const fn set_show_debug_output(&self, _visible: bool) {}
 }
+
+/// Events which are broadcast to every object listening for them.
+const BROADCAST_WHITELIST: [&str; 3] = ["enterFrame", "exitFrame", "frameConstructed"];
+
+/// Construct a new event object.
+///
+/// `event_proto` is the prototype of the event class to instantiate; `args`
+/// are passed to that class's constructor, exactly as if user code had
+/// constructed it.
+fn construct_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut event_proto: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Object<'gc>, Error<'gc>> {
+    let constr = event_proto
+        .get_property(
+            event_proto,
+            &QName::new(Namespace::public_namespace(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let event_object = event_proto.construct(activation, args)?;
+
+    constr.call(Some(event_object), args, activation, Some(event_proto))?;
+
+    Ok(event_object)
+}
+
+/// Fire a broadcast event on a single object, if it is an instance of the
+/// given class.
+fn broadcast_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    event: Event<'gc>,
+    object: Object<'gc>,
+    on_class_proto: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    if !object.has_prototype_in_chain(on_class_proto, true)? {
+        return Ok(());
+    }
+
+    let event_proto = activation.context.avm2.prototypes().event;
+    let event_object =
+        EventObject::from_event(activation.context.gc_context, Some(event_proto), event);
+
+    let mut evtmut = event_object
+        .as_event_mut(activation.context.gc_context)
+        .unwrap();
+    evtmut.set_phase(EventPhase::AtTarget);
+    evtmut.set_target(object);
+    drop(evtmut);
+
+    dispatch_event_to_target(activation, object, event_object)
+}
+
+/// Report an error that was not handled by any AVM2 activation.
+///
+/// Uncaught thrown values are printed to the trace log, the same way the Flash
+/// debug player would. Internal errors are logged as such.
+pub fn root_error_handler<'gc>(context: &mut UpdateContext<'_, 'gc, '_>, error: Error<'gc>) {
+    if let Error::ThrownValue(value) = error {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        let message = value
+            .coerce_to_string(&mut activation)
+            .map(|s| s.to_string())
+            .unwrap_or_else(|_| "undefined".to_string());
+        activation
+            .context
+            .log
+            .avm_trace(&format!("Uncaught exception: {}", message));
+    } else {
+        log::error!("Unhandled AVM2 error: {}", error);
+    }
+}
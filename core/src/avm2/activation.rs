@@ -2,24 +2,28 @@
 
 use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::e4x;
+use crate::avm2::globals;
 use crate::avm2::method::BytecodeMethod;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Multiname, Namespace, QName};
-use crate::avm2::object::{ArrayObject, FunctionObject, NamespaceObject, ScriptObject};
+use crate::avm2::object::{
+    ArrayObject, DictionaryObject, FunctionObject, NamespaceObject, ScriptObject,
+};
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
 use crate::avm2::string::AvmString;
 use crate::avm2::value::Value;
-use crate::avm2::{value, Avm2, Error};
+use crate::avm2::{value, Avm2, Error, ErrorClass};
 use crate::context::UpdateContext;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use smallvec::SmallVec;
 use std::io::Cursor;
 use swf::avm2::read::Reader;
 use swf::avm2::types::{
-    Class as AbcClass, Index, Method as AbcMethod, Multiname as AbcMultiname,
-    Namespace as AbcNamespace, Op,
+    Class as AbcClass, Exception as AbcException, Index, Method as AbcMethod,
+    Multiname as AbcMultiname, Namespace as AbcNamespace, Op,
 };
 
 /// Represents a particular register set.
@@ -102,6 +106,13 @@ pub struct Activation<'a, 'gc: 'a, 'gc_context: 'a> {
     /// This will not be available if this is not a method call.
     base_proto: Option<Object<'gc>>,
 
+    /// The default XML namespace, as set by the `dxns` and `dxnslate`
+    /// opcodes.
+    ///
+    /// A value of `None` indicates that no default namespace was set in this
+    /// activation.
+    default_xml_namespace: Option<Namespace<'gc>>,
+
     pub context: UpdateContext<'a, 'gc, 'gc_context>,
 }
 
@@ -126,6 +137,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope: None,
             base_proto: None,
+            default_xml_namespace: None,
             context,
         }
     }
@@ -135,14 +147,14 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     pub fn from_script(
         context: UpdateContext<'a, 'gc, 'gc_context>,
         script: Script<'gc>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, Error<'gc>> {
         let (method, script_scope) = script.init();
         let scope = Some(Scope::push_scope(None, script_scope, context.gc_context));
 
         let num_locals = match method {
             Method::Native(_nm) => 0,
             Method::Entry(bytecode) => {
-                let body: Result<_, Error> = bytecode.body().ok_or_else(|| {
+                let body: Result<_, Error<'gc>> = bytecode.body().ok_or_else(|| {
                     "Cannot execute non-native method (for script) without body".into()
                 });
                 body?.num_locals
@@ -165,6 +177,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
             base_proto: None,
+            default_xml_namespace: None,
             context,
         })
     }
@@ -178,8 +191,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         this: Option<Object<'gc>>,
         arguments: &[Value<'gc>],
         base_proto: Option<Object<'gc>>,
-    ) -> Result<Self, Error> {
-        let body: Result<_, Error> = method
+    ) -> Result<Self, Error<'gc>> {
+        let body: Result<_, Error<'gc>> = method
             .body()
             .ok_or_else(|| "Cannot execute non-native method without body".into());
         let num_locals = body?.num_locals;
@@ -210,12 +223,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
             base_proto,
+            default_xml_namespace: None,
             context,
         })
     }
 
     /// Execute a script initializer.
-    pub fn run_stack_frame_for_script(&mut self, script: Script<'gc>) -> Result<(), Error> {
+    pub fn run_stack_frame_for_script(&mut self, script: Script<'gc>) -> Result<(), Error<'gc>> {
         let init = script.init().0.into_bytecode()?;
 
         self.run_actions(init)?;
@@ -226,7 +240,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     /// Attempts to lock the activation frame for execution.
     ///
     /// If this frame is already executing, that is an error condition.
-    pub fn lock(&mut self) -> Result<(), Error> {
+    pub fn lock(&mut self) -> Result<(), Error<'gc>> {
         if self.is_executing {
             return Err("Attempted to execute the same frame twice".into());
         }
@@ -243,7 +257,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     /// Retrieve a local register.
-    pub fn local_register(&self, id: u32) -> Result<Value<'gc>, Error> {
+    pub fn local_register(&self, id: u32) -> Result<Value<'gc>, Error<'gc>> {
         self.local_registers
             .read()
             .get(id)
@@ -269,7 +283,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         id: u32,
         value: impl Into<Value<'gc>>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if let Some(r) = self.local_registers.write(mc).get_mut(id) {
             *r = value.into();
 
@@ -288,6 +302,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.return_value = Some(value);
     }
 
+    /// Get the default XML namespace set in this activation, if any.
+    pub fn default_xml_namespace(&self) -> Option<&Namespace<'gc>> {
+        self.default_xml_namespace.as_ref()
+    }
+
     /// Get the base prototype of the object that the currently executing
     /// method was retrieved from, if one exists.
     pub fn base_proto(&self) -> Option<Object<'gc>> {
@@ -299,7 +318,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<i32>,
-    ) -> Result<i32, Error> {
+    ) -> Result<i32, Error<'gc>> {
         value::abc_int(method.translation_unit(), index)
     }
 
@@ -308,7 +327,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<u32>,
-    ) -> Result<u32, Error> {
+    ) -> Result<u32, Error<'gc>> {
         value::abc_uint(method.translation_unit(), index)
     }
 
@@ -317,7 +336,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<f64>,
-    ) -> Result<f64, Error> {
+    ) -> Result<f64, Error<'gc>> {
         value::abc_double(method.translation_unit(), index)
     }
 
@@ -327,7 +346,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: &'b BytecodeMethod<'gc>,
         index: Index<String>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<AvmString<'gc>, Error> {
+    ) -> Result<AvmString<'gc>, Error<'gc>> {
         method.translation_unit().pool_string(index.0, mc)
     }
 
@@ -337,7 +356,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcNamespace>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Namespace<'gc>, Error> {
+    ) -> Result<Namespace<'gc>, Error<'gc>> {
         Namespace::from_abc_namespace(method.translation_unit(), index, mc)
     }
 
@@ -346,7 +365,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<Multiname<'gc>, Error> {
+    ) -> Result<Multiname<'gc>, Error<'gc>> {
         Multiname::from_abc_multiname(method.translation_unit(), index, self)
     }
 
@@ -357,7 +376,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Multiname<'gc>, Error> {
+    ) -> Result<Multiname<'gc>, Error<'gc>> {
         Multiname::from_abc_multiname_static(method.translation_unit(), index, mc)
     }
 
@@ -367,7 +386,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMethod>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Gc<'gc, BytecodeMethod<'gc>>, Error> {
+    ) -> Result<Gc<'gc, BytecodeMethod<'gc>>, Error<'gc>> {
         BytecodeMethod::from_method_index(method.translation_unit(), index.clone(), mc)
             .ok_or_else(|| format!("Method index {} does not exist", index.0).into())
     }
@@ -377,37 +396,314 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcClass>,
-    ) -> Result<GcCell<'gc, Class<'gc>>, Error> {
+    ) -> Result<GcCell<'gc, Class<'gc>>, Error<'gc>> {
         method
             .translation_unit()
             .load_class(index.0, self.context.avm2, self.context.gc_context)
     }
 
+    /// Resolve a type name from the current constant pool into the class
+    /// object it names.
+    fn resolve_type(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcMultiname>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let type_name = self.pool_multiname_static(method, index, self.context.gc_context)?;
+
+        self.resolve_type_name(&type_name)?
+            .ok_or_else(|| "Attempted to resolve the any type as a class".into())
+    }
+
+    /// Resolve a static type name into the class object it names.
+    ///
+    /// Parameterized type names, such as `Vector.<int>`, are resolved into
+    /// the specialized class. The any type resolves to `None`.
+    fn resolve_type_name(
+        &mut self,
+        type_name: &Multiname<'gc>,
+    ) -> Result<Option<Object<'gc>>, Error<'gc>> {
+        if type_name.local_name().is_none() {
+            return Ok(None);
+        }
+
+        let type_object = if let Some(scope) = self.scope() {
+            scope
+                .write(self.context.gc_context)
+                .resolve(type_name, self)?
+        } else {
+            None
+        };
+
+        let type_object = match type_object {
+            Some(Value::Object(type_object)) => type_object,
+            _ => {
+                return Err(format!(
+                    "Attempted to resolve nonexistent type {:?}",
+                    type_name.local_name()
+                )
+                .into())
+            }
+        };
+
+        if type_name.params().is_empty() {
+            return Ok(Some(type_object));
+        }
+
+        let mut params = Vec::with_capacity(type_name.params().len());
+        for param in type_name.params() {
+            params.push(self.resolve_type_name(param)?);
+        }
+
+        Ok(Some(self.apply_type(type_object, &params)?))
+    }
+
+    /// Apply type parameters to a parameterized class, as the `applytype`
+    /// opcode would.
+    ///
+    /// `Vector` is the only class that accepts type parameters.
+    fn apply_type(
+        &mut self,
+        mut base: Object<'gc>,
+        params: &[Option<Object<'gc>>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let base_proto = base
+            .get_property(base, &QName::dynamic_name("prototype"), self)?
+            .coerce_to_object(self)?;
+        let vector_proto = self.context.avm2.prototypes().vector;
+
+        if !Object::ptr_eq(base_proto, vector_proto) {
+            return Err(Error::avm_error(
+                ErrorClass::TypeError,
+                1127,
+                "Type application attempted on a non-parameterized type.",
+            ));
+        }
+
+        if params.len() != 1 {
+            return Err(Error::avm_error(
+                ErrorClass::TypeError,
+                1128,
+                format!(
+                    "Incorrect number of type parameters for Vector. Expected 1, got {}.",
+                    params.len()
+                ),
+            ));
+        }
+
+        globals::vector::specialize(self, base, params[0])
+    }
+
+    /// Coerce a value to a given type, as the `coerce` opcode would.
+    ///
+    /// Coercions to primitive types convert the value rather than checking
+    /// its type. All other coercions fail with a `TypeError` if the value is
+    /// not an instance of the type.
+    pub fn coerce_to_type(
+        &mut self,
+        value: Value<'gc>,
+        mut type_object: Object<'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let type_proto = type_object
+            .get_property(type_object, &QName::dynamic_name("prototype"), self)?
+            .coerce_to_object(self)?;
+        let prototypes = self.context.avm2.prototypes().clone();
+
+        Ok(if Object::ptr_eq(type_proto, prototypes.number) {
+            value.coerce_to_number(self)?.into()
+        } else if Object::ptr_eq(type_proto, prototypes.int) {
+            value.coerce_to_i32(self)?.into()
+        } else if Object::ptr_eq(type_proto, prototypes.uint) {
+            value.coerce_to_u32(self)?.into()
+        } else if Object::ptr_eq(type_proto, prototypes.boolean) {
+            value.coerce_to_boolean().into()
+        } else if Object::ptr_eq(type_proto, prototypes.string) {
+            match value {
+                Value::Undefined | Value::Null => Value::Null,
+                _ => value.coerce_to_string(self)?.into(),
+            }
+        } else {
+            match value {
+                Value::Undefined | Value::Null => Value::Null,
+                value => {
+                    let object = value.coerce_to_object(self)?;
+
+                    if !object.has_prototype_in_chain(type_proto, true)? {
+                        return Err(Error::avm_error(
+                            ErrorClass::TypeError,
+                            1034,
+                            format!(
+                                "Type Coercion failed: cannot convert {} to {}.",
+                                self.type_name_of(&value),
+                                self.type_name_of(&type_proto.into())
+                            ),
+                        ));
+                    }
+
+                    value
+                }
+            }
+        })
+    }
+
     pub fn run_actions(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-    ) -> Result<Value<'gc>, Error> {
-        let body: Result<_, Error> = method
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let body: Result<_, Error<'gc>> = method
             .body()
             .ok_or_else(|| "Cannot execute non-native method without body".into());
         let mut read = Reader::new(Cursor::new(body?.code.as_ref()));
+        let stack_depth = self.context.avm2.stack.len();
+        let base_scope = self.scope();
 
         loop {
-            let result = self.do_next_opcode(method, &mut read);
+            let instruction_start = read.pos()?;
+            let result = self.do_next_opcode(method, &mut read, instruction_start);
             match result {
                 Ok(FrameControl::Return(value)) => break Ok(value),
                 Ok(FrameControl::Continue) => {}
-                Err(e) => break Err(e),
+                Err(e) => {
+                    // Everything this frame left on the operand stack is
+                    // discarded, whether or not the error is caught here.
+                    self.context.avm2.stack.truncate(stack_depth);
+
+                    match self.handle_exception(method, instruction_start, base_scope, e) {
+                        Ok(target) => {
+                            read.seek_absolute(target)?;
+                        }
+                        Err(e) => break Err(e),
+                    }
+                }
             }
         }
     }
 
+    /// Attempt to find an exception handler in the current method for an
+    /// error raised by the instruction at `offset`.
+    ///
+    /// If a handler exists, the scope stack is reset to `base_scope`, the
+    /// thrown value is pushed onto the operand stack, and the offset of the
+    /// handler is returned. Otherwise, the error is handed back to be
+    /// propagated to the caller.
+    ///
+    /// Errors raised by the VM itself, such as failed coercions, are caught as
+    /// instances of their error class. Internal errors always unwind to the
+    /// root of the current execution.
+    fn handle_exception(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        offset: u64,
+        base_scope: Option<GcCell<'gc, Scope<'gc>>>,
+        error: Error<'gc>,
+    ) -> Result<u64, Error<'gc>> {
+        let covers = |exception: &AbcException| {
+            offset >= exception.from_offset as u64 && offset < exception.to_offset as u64
+        };
+        let exceptions = match method.body() {
+            Some(body) if body.exceptions.iter().any(covers) => body.exceptions.clone(),
+            _ => return Err(error),
+        };
+
+        let thrown = match error {
+            Error::ThrownValue(thrown) => thrown,
+            Error::AvmError { class, id, message } => {
+                self.error_object(class, id, &message)?.into()
+            }
+            Error::RustError(error) => return Err(Error::RustError(error)),
+        };
+
+        for exception in exceptions.iter().filter(|e| covers(e)) {
+            self.set_scope(base_scope);
+
+            if self.exception_matches(method, exception, &thrown)? {
+                self.context.avm2.push(thrown);
+
+                return Ok(exception.target_offset as u64);
+            }
+        }
+
+        Err(Error::ThrownValue(thrown))
+    }
+
+    /// Construct the ActionScript error object for an error raised by the VM.
+    ///
+    /// The object's message is prefixed with its error ID, if it has one, in
+    /// the same way as Flash Player's own error messages.
+    fn error_object(
+        &mut self,
+        class: ErrorClass,
+        id: u32,
+        message: &str,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let message = if id == 0 {
+            message.to_string()
+        } else {
+            format!("Error #{}: {}", id, message)
+        };
+        let domain = self.context.avm2.global_domain();
+        let name = QName::new(Namespace::package(class.package()), class.name());
+
+        let mut constr = domain
+            .get_defined_value(self, name)?
+            .coerce_to_object(self)?;
+        let proto = constr
+            .get_property(
+                constr,
+                &QName::new(Namespace::public_namespace(), "prototype"),
+                self,
+            )?
+            .coerce_to_object(self)?;
+        let args = [
+            AvmString::new(self.context.gc_context, message).into(),
+            id.into(),
+        ];
+        let object = proto.construct(self, &args)?;
+        constr.call(Some(object), &args, self, Some(proto))?;
+
+        Ok(object)
+    }
+
+    /// Determine if a thrown value is caught by a given exception table entry.
+    fn exception_matches(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        exception: &AbcException,
+        thrown: &Value<'gc>,
+    ) -> Result<bool, Error<'gc>> {
+        // A type name of zero indicates a handler for any value, as used by
+        // untyped catch blocks and `finally`.
+        if exception.type_name.0 == 0 {
+            return Ok(true);
+        }
+
+        if let Value::Undefined | Value::Null = thrown {
+            return Ok(false);
+        }
+
+        let type_object = match self.resolve_type(method, exception.type_name.clone()) {
+            Ok(type_object) => type_object,
+            Err(e) => {
+                log::warn!("Exception handler has invalid type: {}", e);
+                return Ok(false);
+            }
+        };
+
+        let thrown = thrown.coerce_to_object(self)?;
+
+        thrown.is_instance_of(self, type_object, true)
+    }
+
     /// Run a single action from a given action reader.
+    ///
+    /// `instruction_start` is the offset of the opcode about to be read, which
+    /// some branching instructions are relative to.
     fn do_next_opcode(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+        instruction_start: u64,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         if self.context.update_start.elapsed() >= self.context.max_execution_duration {
             return Err(
                 "A script in this movie has taken too long to execute and has been terminated."
@@ -434,6 +730,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::PushUndefined => self.op_push_undefined(),
                 Op::Pop => self.op_pop(),
                 Op::Dup => self.op_dup(),
+                Op::Swap => self.op_swap(),
                 Op::GetLocal { index } => self.op_get_local(index),
                 Op::SetLocal { index } => self.op_set_local(index),
                 Op::Kill { index } => self.op_kill(index),
@@ -546,9 +843,26 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 } => self.op_has_next_2(object_register, index_register),
                 Op::NextName => self.op_next_name(),
                 Op::NextValue => self.op_next_value(),
+                Op::LookupSwitch {
+                    default_offset,
+                    case_offsets,
+                } => {
+                    self.op_lookup_switch(default_offset, &case_offsets, instruction_start, reader)
+                }
                 Op::IsType { index } => self.op_is_type(method, index),
                 Op::IsTypeLate => self.op_is_type_late(),
                 Op::InstanceOf => self.op_instance_of(),
+                Op::TypeOf => self.op_type_of(),
+                Op::AsType { type_name } => self.op_as_type(method, type_name),
+                Op::AsTypeLate => self.op_as_type_late(),
+                Op::Coerce { index } => self.op_coerce(method, index),
+                Op::CheckFilter => self.op_check_filter(),
+                Op::Dxns { index } => self.op_dxns(method, index),
+                Op::DxnsLate => self.op_dxns_late(),
+                Op::GetDescendants { index } => self.op_get_descendants(method, index),
+                Op::ApplyType { num_types } => self.op_apply_type(num_types),
+                Op::EscXElem => self.op_esc_xelem(),
+                Op::EscXAttr => self.op_esc_xattr(),
                 Op::Label => Ok(FrameControl::Continue),
                 Op::Debug {
                     is_local_register,
@@ -557,11 +871,15 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 } => self.op_debug(method, is_local_register, register_name, register),
                 Op::DebugFile { file_name } => self.op_debug_file(method, file_name),
                 Op::DebugLine { line_num } => self.op_debug_line(line_num),
+                Op::Throw => self.op_throw(),
+                Op::NewCatch { index } => self.op_new_catch(method, index),
                 _ => self.unknown_op(op),
             };
 
             if let Err(e) = result {
-                log::error!("AVM2 error: {}", e);
+                if !e.is_thrown_value() {
+                    log::error!("AVM2 error: {}", e);
+                }
                 return Err(e);
             }
             result
@@ -576,12 +894,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         }
     }
 
-    fn unknown_op(&mut self, op: swf::avm2::types::Op) -> Result<FrameControl<'gc>, Error> {
+    fn unknown_op(&mut self, op: swf::avm2::types::Op) -> Result<FrameControl<'gc>, Error<'gc>> {
         log::error!("Unknown AVM2 opcode: {:?}", op);
         Err("Unknown op".into())
     }
 
-    fn op_push_byte(&mut self, value: u8) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_byte(&mut self, value: u8) -> Result<FrameControl<'gc>, Error<'gc>> {
         //TODO: Adobe Animate CC appears to generate signed byte values, and
         //JPEXS appears to take them.
         self.context.avm2.push(value as i8 as i32);
@@ -592,12 +910,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         value: Index<f64>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(self.pool_double(method, value)?);
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_false(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_false(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(false);
         Ok(FrameControl::Continue)
     }
@@ -606,7 +924,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         value: Index<i32>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(self.pool_int(method, value)?);
         Ok(FrameControl::Continue)
     }
@@ -615,7 +933,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         value: Index<AbcNamespace>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let ns = self.pool_namespace(method, value, self.context.gc_context)?;
 
         self.context.avm2.push(NamespaceObject::from_namespace(
@@ -626,17 +944,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_nan(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_nan(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(std::f64::NAN);
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_null(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_null(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(Value::Null);
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_short(&mut self, value: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_short(&mut self, value: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(value);
         Ok(FrameControl::Continue)
     }
@@ -645,14 +963,14 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         value: Index<String>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context
             .avm2
             .push(self.pool_string(&method, value, self.context.gc_context)?);
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_true(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_true(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(true);
         Ok(FrameControl::Continue)
     }
@@ -661,23 +979,23 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         value: Index<u32>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(self.pool_uint(method, value)?);
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_undefined(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_undefined(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(Value::Undefined);
         Ok(FrameControl::Continue)
     }
 
-    fn op_pop(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_pop(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.pop();
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_dup(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_dup(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(
             self.context
                 .avm2
@@ -690,12 +1008,22 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_get_local(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_swap(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value2 = self.context.avm2.pop();
+        let value1 = self.context.avm2.pop();
+
+        self.context.avm2.push(value2);
+        self.context.avm2.push(value1);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_get_local(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(self.local_register(register_index)?);
         Ok(FrameControl::Continue)
     }
 
-    fn op_set_local(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_set_local(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop();
 
         self.set_local_register(register_index, value, self.context.gc_context)?;
@@ -703,13 +1031,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_kill(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_kill(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.set_local_register(register_index, Value::Undefined, self.context.gc_context)?;
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_call(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_call(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let receiver = self.context.avm2.pop().coerce_to_object(self).ok();
         let function = self.context.avm2.pop().coerce_to_object(self)?;
@@ -725,10 +1053,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         index: Index<AbcMethod>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let function: Result<Object<'gc>, Error> = receiver
+        let function: Result<Object<'gc>, Error<'gc>> = receiver
             .get_method(index.0)
             .ok_or_else(|| format!("Object method {} does not exist", index.0).into());
         let base_proto = receiver.proto();
@@ -744,13 +1072,21 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
+        let name: Result<QName, Error<'gc>> =
+            receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+                Error::avm_error(
+                    ErrorClass::TypeError,
+                    1006,
+                    format!(
+                        "{} is not a function.",
+                        multiname.local_name().unwrap_or_else(|| "value".into())
+                    ),
+                )
+            });
         let name = name?;
         let base_proto = receiver.get_base_proto(&name)?;
         let function = receiver
@@ -768,13 +1104,21 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
+        let name: Result<QName, Error<'gc>> =
+            receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+                Error::avm_error(
+                    ErrorClass::TypeError,
+                    1006,
+                    format!(
+                        "{} is not a function.",
+                        multiname.local_name().unwrap_or_else(|| "value".into())
+                    ),
+                )
+            });
         let function = receiver
             .get_property(receiver, &name?, self)?
             .coerce_to_object(self)?;
@@ -790,13 +1134,21 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
+        let name: Result<QName, Error<'gc>> =
+            receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+                Error::avm_error(
+                    ErrorClass::TypeError,
+                    1006,
+                    format!(
+                        "{} is not a function.",
+                        multiname.local_name().unwrap_or_else(|| "value".into())
+                    ),
+                )
+            });
         let name = name?;
         let base_proto = receiver.get_base_proto(&name)?;
         let function = receiver
@@ -813,7 +1165,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMethod>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
         let method = self.table_method(method, index, self.context.gc_context)?;
@@ -837,14 +1189,22 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
-        let base_proto: Result<Object<'gc>, Error> =
+        let name: Result<QName, Error<'gc>> =
+            receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+                Error::avm_error(
+                    ErrorClass::TypeError,
+                    1006,
+                    format!(
+                        "{} is not a function.",
+                        multiname.local_name().unwrap_or_else(|| "value".into())
+                    ),
+                )
+            });
+        let base_proto: Result<Object<'gc>, Error<'gc>> =
             self.base_proto().and_then(|bp| bp.proto()).ok_or_else(|| {
                 "Attempted to call super method without a superclass."
                     .to_string()
@@ -869,14 +1229,22 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
-        let base_proto: Result<Object<'gc>, Error> =
+        let name: Result<QName, Error<'gc>> =
+            receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+                Error::avm_error(
+                    ErrorClass::TypeError,
+                    1006,
+                    format!(
+                        "{} is not a function.",
+                        multiname.local_name().unwrap_or_else(|| "value".into())
+                    ),
+                )
+            });
+        let base_proto: Result<Object<'gc>, Error<'gc>> =
             self.base_proto().and_then(|bp| bp.proto()).ok_or_else(|| {
                 "Attempted to call super method without a superclass."
                     .to_string()
@@ -894,13 +1262,21 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_return_value(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_throw(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop();
+
+        avm_debug!(self.avm2(), "Thrown exception: {:?}", value);
+
+        Err(Error::ThrownValue(value))
+    }
+
+    fn op_return_value(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let return_value = self.context.avm2.pop();
 
         Ok(FrameControl::Return(return_value))
     }
 
-    fn op_return_void(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_return_void(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         Ok(FrameControl::Return(Value::Undefined))
     }
 
@@ -908,13 +1284,37 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        if let Some((dictionary, key)) = self.peek_dictionary_key(method, &index) {
+            self.context.avm2.pop();
+            self.context.avm2.pop();
+            self.context
+                .avm2
+                .push(dictionary.get_property_by_object(key));
+
+            return Ok(FrameControl::Continue);
+        }
+
         let multiname = self.pool_multiname(method, index)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let name: Result<QName, Error> = object.resolve_multiname(&multiname)?.ok_or_else(|| {
-            format!("Could not resolve property {:?}", multiname.local_name()).into()
-        });
+        if e4x::is_xml_like(object) {
+            let local_name = multiname.local_name();
+            let local_name = local_name.as_deref().unwrap_or("*");
+
+            if let Some(value) =
+                e4x::get_property(self, object, local_name, multiname.is_attribute())
+            {
+                self.context.avm2.push(value);
+
+                return Ok(FrameControl::Continue);
+            }
+        }
+
+        let name: Result<QName, Error<'gc>> =
+            object.resolve_multiname(&multiname)?.ok_or_else(|| {
+                format!("Could not resolve property {:?}", multiname.local_name()).into()
+            });
 
         // Special case for dynamic properties as scripts may attempt to get
         // dynamic properties not yet set
@@ -938,17 +1338,35 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop();
+
+        if let Some((dictionary, key)) = self.peek_dictionary_key(method, &index) {
+            self.context.avm2.pop();
+            self.context.avm2.pop();
+            dictionary.set_property_by_object(self.context.gc_context, key, value);
+
+            return Ok(FrameControl::Continue);
+        }
+
         let multiname = self.pool_multiname(method, index)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
+        if e4x::is_xml_like(object) {
+            let local_name = multiname.local_name();
+            let local_name = local_name.as_deref().unwrap_or("*");
+
+            e4x::set_property(self, object, local_name, multiname.is_attribute(), value)?;
+
+            return Ok(FrameControl::Continue);
+        }
+
         if let Some(name) = object.resolve_multiname(&multiname)? {
             object.set_property(object, &name, value, self)?;
         } else {
             //TODO: Non-dynamic objects should fail
             //TODO: This should only work if the public namespace is present
-            let local_name: Result<AvmString<'gc>, Error> = multiname
+            let local_name: Result<AvmString<'gc>, Error<'gc>> = multiname
                 .local_name()
                 .ok_or_else(|| "Cannot set property using any name".into());
             let name = QName::dynamic_name(local_name?);
@@ -962,17 +1380,35 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop();
+
+        if let Some((dictionary, key)) = self.peek_dictionary_key(method, &index) {
+            self.context.avm2.pop();
+            self.context.avm2.pop();
+            dictionary.set_property_by_object(self.context.gc_context, key, value);
+
+            return Ok(FrameControl::Continue);
+        }
+
         let multiname = self.pool_multiname(method, index)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
+        if e4x::is_xml_like(object) {
+            let local_name = multiname.local_name();
+            let local_name = local_name.as_deref().unwrap_or("*");
+
+            e4x::set_property(self, object, local_name, multiname.is_attribute(), value)?;
+
+            return Ok(FrameControl::Continue);
+        }
+
         if let Some(name) = object.resolve_multiname(&multiname)? {
             object.init_property(object, &name, value, self)?;
         } else {
             //TODO: Non-dynamic objects should fail
             //TODO: This should only work if the public namespace is present
-            let local_name: Result<AvmString<'gc>, Error> = multiname
+            let local_name: Result<AvmString<'gc>, Error<'gc>> = multiname
                 .local_name()
                 .ok_or_else(|| "Cannot set property using any name".into());
             let name = QName::dynamic_name(local_name?);
@@ -986,10 +1422,35 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        if let Some((dictionary, key)) = self.peek_dictionary_key(method, &index) {
+            self.context.avm2.pop();
+            self.context.avm2.pop();
+            self.context
+                .avm2
+                .push(dictionary.delete_property_by_object(self.context.gc_context, key));
+
+            return Ok(FrameControl::Continue);
+        }
+
         let multiname = self.pool_multiname(method, index)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
+        if e4x::is_xml_like(object) {
+            let local_name = multiname.local_name();
+            let local_name = local_name.as_deref().unwrap_or("*");
+            let deleted = e4x::delete_property(
+                self.context.gc_context,
+                object,
+                local_name,
+                multiname.is_attribute(),
+            )?;
+
+            self.context.avm2.push(deleted.unwrap_or(false));
+
+            return Ok(FrameControl::Continue);
+        }
+
         if let Some(name) = object.resolve_multiname(&multiname)? {
             self.context
                 .avm2
@@ -1011,23 +1472,24 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let multiname = self.pool_multiname(method, index)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
-        let base_proto: Result<Object<'gc>, Error> = self
+        let base_proto: Result<Object<'gc>, Error<'gc>> = self
             .base_proto()
             .and_then(|p| p.proto())
             .ok_or_else(|| "Attempted to get property on non-existent super object".into());
         let base_proto = base_proto?;
         let mut base = base_proto.construct(self, &[])?; //TODO: very hacky workaround
 
-        let name: Result<QName, Error> = base.resolve_multiname(&multiname)?.ok_or_else(|| {
-            format!(
-                "Could not resolve {:?} as super property",
-                multiname.local_name()
-            )
-            .into()
-        });
+        let name: Result<QName, Error<'gc>> =
+            base.resolve_multiname(&multiname)?.ok_or_else(|| {
+                format!(
+                    "Could not resolve {:?} as super property",
+                    multiname.local_name()
+                )
+                .into()
+            });
 
         let value = base.get_property(object, &name?, self)?;
 
@@ -1040,43 +1502,59 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop();
         let multiname = self.pool_multiname(method, index)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
-        let base_proto: Result<Object<'gc>, Error> = self
+        let base_proto: Result<Object<'gc>, Error<'gc>> = self
             .base_proto()
             .and_then(|p| p.proto())
             .ok_or_else(|| "Attempted to get property on non-existent super object".into());
         let base_proto = base_proto?;
         let mut base = base_proto.construct(self, &[])?; //TODO: very hacky workaround
 
-        let name: Result<QName, Error> = base.resolve_multiname(&multiname)?.ok_or_else(|| {
-            format!(
-                "Could not resolve {:?} as super property",
-                multiname.local_name()
-            )
-            .into()
-        });
+        let name: Result<QName, Error<'gc>> =
+            base.resolve_multiname(&multiname)?.ok_or_else(|| {
+                format!(
+                    "Could not resolve {:?} as super property",
+                    multiname.local_name()
+                )
+                .into()
+            });
 
         base.set_property(object, &name?, value, self)?;
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_in(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_in(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let obj = self.context.avm2.pop().coerce_to_object(self)?;
-        let name = self.context.avm2.pop().coerce_to_string(self)?;
+        let name_value = self.context.avm2.pop();
+
+        if let (Some(dictionary), Value::Object(key)) = (obj.as_dictionary_object(), &name_value) {
+            self.context
+                .avm2
+                .push(dictionary.has_property_by_object(*key));
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let name = name_value.coerce_to_string(self)?;
+
+        let has_prop = if e4x::is_xml_like(obj) {
+            e4x::has_property(obj, &name, false)
+        } else {
+            let qname = QName::new(Namespace::public_namespace(), name);
 
-        let qname = QName::new(Namespace::public_namespace(), name);
-        let has_prop = obj.has_property(&qname)?;
+            obj.has_property(&qname)?
+        };
 
         self.context.avm2.push(has_prop);
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_scope(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_scope(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let object = self.context.avm2.pop().coerce_to_object(self)?;
         let scope_stack = self.scope();
         let new_scope = Scope::push_scope(scope_stack, object, self.context.gc_context);
@@ -1086,7 +1564,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_with(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_with(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let object = self.context.avm2.pop().coerce_to_object(self)?;
         let scope_stack = self.scope();
         let new_scope = Scope::push_with(scope_stack, object, self.context.gc_context);
@@ -1096,7 +1574,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_pop_scope(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_pop_scope(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let scope_stack = self.scope();
         let new_scope = scope_stack.and_then(|s| s.read().pop_scope());
 
@@ -1105,7 +1583,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_get_scope_object(&mut self, mut index: u8) -> Result<FrameControl<'gc>, Error> {
+    fn op_get_scope_object(&mut self, mut index: u8) -> Result<FrameControl<'gc>, Error<'gc>> {
         let mut scope = self.scope();
 
         while index > 0 {
@@ -1125,7 +1603,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_get_global_scope(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_get_global_scope(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let mut scope = self.scope();
 
         while let Some(this_scope) = scope {
@@ -1150,7 +1628,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let multiname = self.pool_multiname(method, index)?;
         avm_debug!(self.context.avm2, "Resolving {:?}", multiname);
         let result = if let Some(scope) = self.scope() {
@@ -1170,15 +1648,24 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let multiname = self.pool_multiname(method, index)?;
         avm_debug!(self.context.avm2, "Resolving {:?}", multiname);
-        let found: Result<Object<'gc>, Error> = if let Some(scope) = self.scope() {
+        let found: Result<Object<'gc>, Error<'gc>> = if let Some(scope) = self.scope() {
             scope.read().find(&multiname, self)?
         } else {
             None
         }
-        .ok_or_else(|| format!("Property does not exist: {:?}", multiname.local_name()).into());
+        .ok_or_else(|| {
+            Error::avm_error(
+                ErrorClass::ReferenceError,
+                1065,
+                format!(
+                    "Variable {} is not defined.",
+                    multiname.local_name().unwrap_or_else(|| "*".into())
+                ),
+            )
+        });
         let result: Value<'gc> = found?.into();
 
         self.context.avm2.push(result);
@@ -1190,17 +1677,26 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let multiname = self.pool_multiname_static(method, index, self.context.gc_context)?;
         avm_debug!(self.avm2(), "Resolving {:?}", multiname);
-        let found: Result<Value<'gc>, Error> = if let Some(scope) = self.scope() {
+        let found: Result<Value<'gc>, Error<'gc>> = if let Some(scope) = self.scope() {
             scope
                 .write(self.context.gc_context)
                 .resolve(&multiname, self)?
         } else {
             None
         }
-        .ok_or_else(|| format!("Property does not exist: {:?}", multiname.local_name()).into());
+        .ok_or_else(|| {
+            Error::avm_error(
+                ErrorClass::ReferenceError,
+                1065,
+                format!(
+                    "Variable {} is not defined.",
+                    multiname.local_name().unwrap_or_else(|| "*".into())
+                ),
+            )
+        });
         let result: Value<'gc> = found?;
 
         self.context.avm2.push(result);
@@ -1208,7 +1704,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_get_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_get_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let object = self.context.avm2.pop().coerce_to_object(self)?;
         let value = object.get_slot(index)?;
 
@@ -1217,16 +1713,16 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_set_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
-        let object = self.context.avm2.pop().coerce_to_object(self)?;
+    fn op_set_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop();
+        let object = self.context.avm2.pop().coerce_to_object(self)?;
 
         object.set_slot(index, value, self.context.gc_context)?;
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_get_global_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_get_global_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.scope.unwrap().read().globals().get_slot(index)?;
 
         self.context.avm2.push(value);
@@ -1234,7 +1730,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_set_global_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_set_global_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop();
 
         self.scope
@@ -1246,7 +1742,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_construct(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_construct(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let mut ctor = self.context.avm2.pop().coerce_to_object(self)?;
 
@@ -1271,12 +1767,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let mut source = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let ctor_name: Result<QName, Error> =
+        let ctor_name: Result<QName, Error<'gc>> =
             source.resolve_multiname(&multiname)?.ok_or_else(|| {
                 format!("Could not resolve property {:?}", multiname.local_name()).into()
             });
@@ -1299,11 +1795,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_construct_super(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_construct_super(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
         let name = QName::new(Namespace::public_namespace(), "constructor");
-        let base_proto: Result<Object<'gc>, Error> =
+        let base_proto: Result<Object<'gc>, Error<'gc>> =
             self.base_proto().and_then(|p| p.proto()).ok_or_else(|| {
                 "Attempted to call super constructor without a superclass."
                     .to_string()
@@ -1320,7 +1816,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_new_activation(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_new_activation(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context
             .avm2
             .push(ScriptObject::bare_object(self.context.gc_context));
@@ -1328,7 +1824,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_new_object(&mut self, num_args: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_new_object(&mut self, num_args: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let mut object = ScriptObject::object(
             self.context.gc_context,
             self.context.avm2.prototypes().object,
@@ -1355,7 +1851,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMethod>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let method_entry = self.table_method(method, index, self.context.gc_context)?;
         let scope = self.scope();
 
@@ -1387,7 +1883,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcClass>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let base_value = self.context.avm2.pop();
         let base_class = match base_value {
             Value::Object(o) => Some(o),
@@ -1408,7 +1904,40 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_new_array(&mut self, num_args: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_new_catch(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcException>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let body: Result<_, Error<'gc>> = method
+            .body()
+            .ok_or_else(|| "Cannot create catch scope in a method without body".into());
+        let exception: Result<_, Error<'gc>> = body?
+            .exceptions
+            .get(index.0 as usize)
+            .ok_or_else(|| format!("Exception handler {} does not exist", index.0).into());
+        let variable_name = exception?.variable_name.0;
+
+        // Catch variables are named by multiname, not by string.
+        let name = if variable_name == 0 {
+            QName::dynamic_name("")
+        } else {
+            QName::from_abc_multiname(
+                method.translation_unit(),
+                Index::new(variable_name),
+                self.context.gc_context,
+            )?
+        };
+
+        let mut catch_scope = ScriptObject::bare_object(self.context.gc_context);
+        catch_scope.install_slot(self.context.gc_context, name, 1, Value::Undefined);
+
+        self.context.avm2.push(catch_scope);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_new_array(&mut self, num_args: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(num_args);
         let array = ArrayStorage::from_args(&args[..]);
         let array_obj = ArrayObject::from_array(
@@ -1422,11 +1951,25 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_coerce_a(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_coerce(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop();
+        let type_object = self.resolve_type(method, index)?;
+        let coerced = self.coerce_to_type(value, type_object)?;
+
+        self.context.avm2.push(coerced);
+
         Ok(FrameControl::Continue)
     }
 
-    fn op_coerce_s(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_coerce_a(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_coerce_s(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop();
 
         let coerced = match value {
@@ -1439,7 +1982,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_convert_b(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_convert_b(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_boolean();
 
         self.context.avm2.push(value);
@@ -1447,7 +1990,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_convert_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_convert_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_i32(self)?;
 
         self.context.avm2.push(Value::Number(value.into()));
@@ -1455,7 +1998,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_convert_d(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_convert_d(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_number(self)?;
 
         self.context.avm2.push(Value::Number(value));
@@ -1463,7 +2006,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_convert_o(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_convert_o(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_object(self)?;
 
         self.context.avm2.push(value);
@@ -1471,7 +2014,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_convert_u(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_convert_u(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_u32(self)?;
 
         self.context.avm2.push(Value::Number(value.into()));
@@ -1479,7 +2022,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_convert_s(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_convert_s(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_string(self)?;
 
         self.context.avm2.push(value);
@@ -1487,7 +2030,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_add(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_add(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1535,7 +2078,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_add_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_add_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1544,7 +2087,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_bitand(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_bitand(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1553,7 +2096,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_bitnot(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_bitnot(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
         self.context.avm2.push(!value1);
@@ -1561,7 +2104,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_bitor(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_bitor(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1570,7 +2113,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_bitxor(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_bitxor(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1579,7 +2122,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_declocal(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_declocal(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.local_register(index)?.coerce_to_number(self)?;
 
         self.set_local_register(index, value - 1.0, self.context.gc_context)?;
@@ -1587,7 +2130,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_declocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_declocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.local_register(index)?.coerce_to_i32(self)?;
 
         self.set_local_register(index, value - 1, self.context.gc_context)?;
@@ -1595,7 +2138,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_decrement(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_decrement(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_number(self)?;
 
         self.context.avm2.push(value - 1.0);
@@ -1603,7 +2146,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_decrement_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_decrement_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_i32(self)?;
 
         self.context.avm2.push(value - 1);
@@ -1611,7 +2154,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_divide(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_divide(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_number(self)?;
         let value1 = self.context.avm2.pop().coerce_to_number(self)?;
 
@@ -1620,7 +2163,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_inclocal(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_inclocal(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.local_register(index)?.coerce_to_number(self)?;
 
         self.set_local_register(index, value + 1.0, self.context.gc_context)?;
@@ -1628,7 +2171,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_inclocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_inclocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.local_register(index)?.coerce_to_i32(self)?;
 
         self.set_local_register(index, value + 1, self.context.gc_context)?;
@@ -1636,7 +2179,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_increment(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_increment(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_number(self)?;
 
         self.context.avm2.push(value + 1.0);
@@ -1644,7 +2187,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_increment_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_increment_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_i32(self)?;
 
         self.context.avm2.push(value + 1);
@@ -1652,7 +2195,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_lshift(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_lshift(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_u32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1661,7 +2204,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_modulo(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_modulo(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_number(self)?;
         let value1 = self.context.avm2.pop().coerce_to_number(self)?;
 
@@ -1670,7 +2213,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_multiply(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_multiply(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_number(self)?;
         let value1 = self.context.avm2.pop().coerce_to_number(self)?;
 
@@ -1679,7 +2222,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_multiply_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_multiply_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1688,7 +2231,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_negate(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_negate(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value1 = self.context.avm2.pop().coerce_to_number(self)?;
 
         self.context.avm2.push(-value1);
@@ -1696,7 +2239,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_negate_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_negate_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
         self.context.avm2.push(-value1);
@@ -1704,7 +2247,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_rshift(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_rshift(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_u32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1713,7 +2256,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_subtract(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_subtract(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_number(self)?;
         let value1 = self.context.avm2.pop().coerce_to_number(self)?;
 
@@ -1722,7 +2265,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_subtract_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_subtract_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1731,7 +2274,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_urshift(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_urshift(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_u32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_u32(self)?;
 
@@ -1744,17 +2287,42 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         reader.seek(offset as i64)?;
 
         Ok(FrameControl::Continue)
     }
 
+    fn op_lookup_switch(
+        &mut self,
+        default_offset: i32,
+        case_offsets: &[i32],
+        instruction_start: u64,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let index = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        // Unlike other branches, switch offsets are relative to the start of
+        // the instruction.
+        let offset = if index >= 0 {
+            case_offsets
+                .get(index as usize)
+                .copied()
+                .unwrap_or(default_offset)
+        } else {
+            default_offset
+        };
+
+        reader.seek_absolute((instruction_start as i64 + offset as i64) as u64)?;
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_if_true(
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_boolean();
 
         if value {
@@ -1768,7 +2336,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_boolean();
 
         if !value {
@@ -1782,7 +2350,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1797,7 +2365,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1812,7 +2380,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1827,7 +2395,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1842,7 +2410,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1857,7 +2425,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1872,7 +2440,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1887,7 +2455,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1902,7 +2470,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1917,7 +2485,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1932,7 +2500,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1947,7 +2515,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         offset: i32,
         reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1958,7 +2526,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_strict_equals(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_strict_equals(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1967,7 +2535,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_equals(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_equals(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1978,7 +2546,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_greater_equals(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_greater_equals(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1989,7 +2557,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_greater_than(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_greater_than(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -2000,7 +2568,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_less_equals(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_less_equals(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -2011,7 +2579,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_less_than(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_less_than(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -2022,7 +2590,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_not(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_not(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_boolean();
 
         self.context.avm2.push(!value);
@@ -2030,13 +2598,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_has_next(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_has_next(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let cur_index = self.context.avm2.pop().coerce_to_u32(self)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
         let next_index = cur_index + 1;
 
-        if object.get_enumerant_name(next_index).is_some() {
+        if object.has_enumerant(next_index) {
             self.context.avm2.push(next_index);
         } else {
             self.context.avm2.push(0.0);
@@ -2049,7 +2617,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         object_register: u32,
         index_register: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let cur_index = self.local_register(index_register)?.coerce_to_u32(self)?;
         let mut object = Some(
             self.local_register(object_register)?
@@ -2059,7 +2627,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let mut next_index = cur_index + 1;
 
         while let Some(cur_object) = object {
-            if cur_object.get_enumerant_name(next_index).is_none() {
+            if !cur_object.has_enumerant(next_index) {
                 next_index = 1;
                 object = cur_object.proto();
             } else {
@@ -2082,29 +2650,22 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_next_name(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_next_name(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let cur_index = self.context.avm2.pop().coerce_to_number(self)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let name = object
-            .get_enumerant_name(cur_index as u32)
-            .map(|n| n.local_name().into());
+        let name = object.get_enumerant_key(cur_index as u32, self.context.gc_context);
 
         self.context.avm2.push(name.unwrap_or(Value::Undefined));
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_next_value(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_next_value(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let cur_index = self.context.avm2.pop().coerce_to_number(self)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let name = object.get_enumerant_name(cur_index as u32);
-        let value = if let Some(name) = name {
-            object.get_property(object, &name, self)?
-        } else {
-            Value::Undefined
-        };
+        let value = object.get_enumerant_value(cur_index as u32, self)?;
 
         self.context.avm2.push(value);
 
@@ -2115,7 +2676,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         type_name_index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_object(self)?;
 
         let type_name =
@@ -2140,7 +2701,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_is_type_late(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_is_type_late(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let type_object = self.context.avm2.pop().coerce_to_object(self)?;
         let value = self.context.avm2.pop().coerce_to_object(self)?;
 
@@ -2151,7 +2712,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_instance_of(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_instance_of(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let type_object = self.context.avm2.pop().coerce_to_object(self)?;
         let value = self.context.avm2.pop().coerce_to_object(self)?;
 
@@ -2162,6 +2723,253 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_type_of(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop();
+
+        let type_name = match value {
+            Value::Undefined => "undefined",
+            Value::Null => "object",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) | Value::Integer(_) | Value::Unsigned(_) => "number",
+            Value::String(_) => "string",
+            Value::Object(o) if o.as_executable().is_some() && o.as_class().is_none() => "function",
+            Value::Object(o) if e4x::is_xml_like(o) => "xml",
+            Value::Object(_) => "object",
+        };
+
+        self.context.avm2.push(type_name);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_as_type(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        type_name: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop();
+        let type_object = self.resolve_type(method, type_name)?;
+
+        let result = self.as_type(value, type_object)?;
+
+        self.context.avm2.push(result);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_as_type_late(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let type_object = self.context.avm2.pop().coerce_to_object(self)?;
+        let value = self.context.avm2.pop();
+
+        let result = self.as_type(value, type_object)?;
+
+        self.context.avm2.push(result);
+
+        Ok(FrameControl::Continue)
+    }
+
+    /// Implements the `as` operator: yields the value if it is an instance of
+    /// the given type, and `null` otherwise.
+    fn as_type(
+        &mut self,
+        value: Value<'gc>,
+        type_object: Object<'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        match value {
+            Value::Undefined | Value::Null => Ok(Value::Null),
+            value => {
+                let object = value.coerce_to_object(self)?;
+
+                if object.is_instance_of(self, type_object, true)? {
+                    Ok(value)
+                } else {
+                    Ok(Value::Null)
+                }
+            }
+        }
+    }
+
+    fn op_check_filter(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self
+            .context
+            .avm2
+            .stack
+            .last()
+            .cloned()
+            .unwrap_or(Value::Undefined);
+
+        // Only XML and XMLList values may be filtered; the value is left on
+        // the stack for the filter loop.
+        match value {
+            Value::Object(o) if e4x::is_xml_like(o) => Ok(FrameControl::Continue),
+            _ => Err(Error::avm_error(
+                ErrorClass::TypeError,
+                1123,
+                format!(
+                    "Filter operator not supported on type {}.",
+                    self.type_name_of(&value)
+                ),
+            )),
+        }
+    }
+
+    fn op_dxns(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<String>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let uri = self.pool_string(&method, index, self.context.gc_context)?;
+
+        self.default_xml_namespace = Some(Namespace::Namespace(uri));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_dxns_late(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let uri = self.context.avm2.pop().coerce_to_string(self)?;
+
+        self.default_xml_namespace = Some(Namespace::Namespace(uri));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_get_descendants(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let multiname = self.pool_multiname(method, index)?;
+        let object = self.context.avm2.pop();
+
+        // The descendants operator is only defined on XML and XMLList values.
+        let descendants = match object {
+            Value::Object(o) => {
+                let local_name = multiname.local_name();
+                let local_name = local_name.as_deref().unwrap_or("*");
+
+                e4x::get_descendants(self, o, local_name, multiname.is_attribute())
+            }
+            _ => None,
+        };
+
+        match descendants {
+            Some(descendants) => {
+                self.context.avm2.push(descendants);
+
+                Ok(FrameControl::Continue)
+            }
+            None => Err(Error::avm_error(
+                ErrorClass::TypeError,
+                1016,
+                format!(
+                    "Descendants operator (..) not supported on type {}.",
+                    self.type_name_of(&object)
+                ),
+            )),
+        }
+    }
+
+    fn op_apply_type(&mut self, num_types: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let mut params = Vec::with_capacity(num_types as usize);
+        for _ in 0..num_types {
+            params.push(match self.context.avm2.pop() {
+                Value::Undefined | Value::Null => None,
+                value => Some(value.coerce_to_object(self)?),
+            });
+        }
+        params.reverse();
+
+        let base = self.context.avm2.pop().coerce_to_object(self)?;
+        let class = self.apply_type(base, &params)?;
+
+        self.context.avm2.push(class);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_esc_xelem(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop();
+        let escaped = match value {
+            Value::Object(o) if e4x::is_xml_like(o) => e4x::nodes_of(o)
+                .unwrap_or_default()
+                .iter()
+                .map(|node| node.to_xml_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            value => e4x::escape_element_value(&value.coerce_to_string(self)?),
+        };
+
+        self.context
+            .avm2
+            .push(AvmString::new(self.context.gc_context, escaped));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_esc_xattr(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop().coerce_to_string(self)?;
+        let escaped = e4x::escape_attribute_value(&value);
+
+        self.context
+            .avm2
+            .push(AvmString::new(self.context.gc_context, escaped));
+
+        Ok(FrameControl::Continue)
+    }
+
+    /// Check if a late-bound property access is indexing a `Dictionary` with
+    /// an object key.
+    ///
+    /// The key is expected on top of the stack with the dictionary beneath
+    /// it. The stack is left untouched; the caller is responsible for popping
+    /// the dictionary and key if it uses them.
+    fn peek_dictionary_key(
+        &self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: &Index<AbcMultiname>,
+    ) -> Option<(DictionaryObject<'gc>, Object<'gc>)> {
+        let abc = method.translation_unit().abc();
+        let abc_multiname = abc
+            .constant_pool
+            .multinames
+            .get((index.0 as usize).checked_sub(1)?)?;
+
+        if !matches!(
+            abc_multiname,
+            AbcMultiname::MultinameL { .. } | AbcMultiname::MultinameLA { .. }
+        ) {
+            return None;
+        }
+
+        let stack = &self.context.avm2.stack;
+        let key_index = stack.len().checked_sub(1)?;
+        let object_index = key_index.checked_sub(1)?;
+
+        match (stack.get(object_index)?, stack.get(key_index)?) {
+            (Value::Object(object), Value::Object(key)) => {
+                Some((object.as_dictionary_object()?, *key))
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the name of the type of a value, for use in error messages.
+    fn type_name_of(&self, value: &Value<'gc>) -> String {
+        match value {
+            Value::Undefined => "undefined".to_string(),
+            Value::Null => "null".to_string(),
+            Value::Bool(_) => "Boolean".to_string(),
+            Value::Number(_) => "Number".to_string(),
+            Value::Integer(_) => "int".to_string(),
+            Value::Unsigned(_) => "uint".to_string(),
+            Value::String(_) => "String".to_string(),
+            Value::Object(o) => o
+                .as_proto_class()
+                .map(|c| c.read().name().local_name().to_string())
+                .unwrap_or_else(|| "Object".to_string()),
+        }
+    }
+
     #[allow(unused_variables)]
     #[cfg(avm_debug)]
     fn op_debug(
@@ -2170,7 +2978,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         is_local_register: bool,
         register_name: Index<String>,
         register: u8,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         if is_local_register {
             let register_name = self.pool_string(method, register_name)?;
             let value = self.local_register(register as u32)?;
@@ -2191,7 +2999,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         is_local_register: bool,
         register_name: Index<String>,
         register: u8,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         Ok(FrameControl::Continue)
     }
 
@@ -2201,7 +3009,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         file_name: Index<String>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let file_name = self.pool_string(method, file_name)?;
 
         avm_debug!(self.avm2(), "File: {}", file_name);
@@ -2215,12 +3023,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         file_name: Index<String>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         Ok(FrameControl::Continue)
     }
 
     #[allow(unused_variables)]
-    fn op_debug_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_debug_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         avm_debug!(self.avm2(), "Line: {}", line_num);
 
         Ok(FrameControl::Continue)
@@ -0,0 +1,229 @@
+//! BitmapData support types
+
+use crate::backend::render::{BitmapHandle, RenderBackend};
+use gc_arena::Collect;
+use std::cell::Cell;
+
+/// The pixel storage portion of a `BitmapData`.
+///
+/// Pixels are stored row-major as unmultiplied 32-bit ARGB values. Opaque
+/// bitmaps always have an alpha of `0xFF`.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct BitmapDataStorage {
+    /// The width of the bitmap, in pixels.
+    width: u32,
+
+    /// The height of the bitmap, in pixels.
+    height: u32,
+
+    /// Whether this bitmap supports per-pixel transparency.
+    transparent: bool,
+
+    /// The pixels of the bitmap.
+    pixels: Vec<u32>,
+
+    /// Whether `dispose` has been called on this bitmap.
+    disposed: bool,
+
+    /// The renderer's copy of these pixels, if one has been uploaded.
+    bitmap_handle: Cell<Option<BitmapHandle>>,
+
+    /// Whether the pixels have changed since they were last uploaded.
+    dirty: Cell<bool>,
+}
+
+impl BitmapDataStorage {
+    /// Construct a bitmap filled with a single color.
+    pub fn new(width: u32, height: u32, transparent: bool, fill_color: u32) -> Self {
+        let fill_color = Self::with_transparency(transparent, fill_color);
+
+        Self {
+            width,
+            height,
+            transparent,
+            pixels: vec![fill_color; width as usize * height as usize],
+            disposed: false,
+            bitmap_handle: Cell::new(None),
+            dirty: Cell::new(true),
+        }
+    }
+
+    /// Force a color to be opaque if the bitmap does not support
+    /// transparency.
+    fn with_transparency(transparent: bool, color: u32) -> u32 {
+        if transparent {
+            color
+        } else {
+            color | 0xFF00_0000
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn transparent(&self) -> bool {
+        self.transparent
+    }
+
+    pub fn disposed(&self) -> bool {
+        self.disposed
+    }
+
+    /// Free the pixels of this bitmap.
+    ///
+    /// Disposed bitmaps have no size and cannot be drawn.
+    pub fn dispose(&mut self) {
+        self.width = 0;
+        self.height = 0;
+        self.pixels = Vec::new();
+        self.disposed = true;
+        self.dirty.set(true);
+    }
+
+    /// Find the index of a pixel, if it lies within the bitmap.
+    fn index_of(&self, x: i32, y: i32) -> Option<usize> {
+        if x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Read the ARGB value of a pixel.
+    ///
+    /// Pixels outside of the bitmap read as zero.
+    pub fn get_pixel32(&self, x: i32, y: i32) -> u32 {
+        self.index_of(x, y)
+            .map(|index| self.pixels[index])
+            .unwrap_or(0)
+    }
+
+    /// Read the RGB value of a pixel, ignoring its alpha.
+    pub fn get_pixel(&self, x: i32, y: i32) -> u32 {
+        self.get_pixel32(x, y) & 0x00FF_FFFF
+    }
+
+    /// Write the ARGB value of a pixel.
+    ///
+    /// Writes outside of the bitmap are ignored.
+    pub fn set_pixel32(&mut self, x: i32, y: i32, color: u32) {
+        if let Some(index) = self.index_of(x, y) {
+            self.pixels[index] = Self::with_transparency(self.transparent, color);
+            self.dirty.set(true);
+        }
+    }
+
+    /// Write the RGB value of a pixel, keeping its existing alpha.
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: u32) {
+        if let Some(index) = self.index_of(x, y) {
+            let alpha = self.pixels[index] & 0xFF00_0000;
+
+            self.pixels[index] = alpha | (color & 0x00FF_FFFF);
+            self.dirty.set(true);
+        }
+    }
+
+    /// Fill a rectangle of the bitmap with a single ARGB color.
+    ///
+    /// The rectangle is clipped to the bounds of the bitmap.
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: u32) {
+        let color = Self::with_transparency(self.transparent, color);
+        let x_min = x.max(0) as u32;
+        let y_min = y.max(0) as u32;
+        let x_max = (x.saturating_add(width).max(0) as u32).min(self.width);
+        let y_max = (y.saturating_add(height).max(0) as u32).min(self.height);
+
+        for y in y_min..y_max {
+            for x in x_min..x_max {
+                self.pixels[y as usize * self.width as usize + x as usize] = color;
+            }
+        }
+
+        self.dirty.set(true);
+    }
+
+    /// Convert the pixels of this bitmap to RGBA bytes, as accepted by the
+    /// render backend.
+    fn rgba(&self) -> Vec<u8> {
+        let mut rgba = Vec::with_capacity(self.pixels.len() * 4);
+
+        for pixel in self.pixels.iter() {
+            let [a, r, g, b] = pixel.to_be_bytes();
+
+            rgba.extend_from_slice(&[r, g, b, a]);
+        }
+
+        rgba
+    }
+
+    /// Obtain a handle to these pixels in the render backend.
+    ///
+    /// The pixels are uploaded again if they have changed since the last
+    /// upload. Disposed and empty bitmaps have no handle.
+    pub fn bitmap_handle(&self, renderer: &mut dyn RenderBackend) -> Option<BitmapHandle> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+
+        if self.dirty.get() {
+            let result = match self.bitmap_handle.get() {
+                Some(handle) => {
+                    renderer.update_texture(handle, self.width, self.height, self.rgba())
+                }
+                None => renderer.register_bitmap_raw(self.width, self.height, self.rgba()),
+            };
+
+            match result {
+                Ok(handle) => {
+                    self.bitmap_handle.set(Some(handle));
+                    self.dirty.set(false);
+                }
+                Err(e) => log::error!("Could not upload BitmapData pixels: {}", e),
+            }
+        }
+
+        self.bitmap_handle.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opaque_bitmaps_ignore_alpha() {
+        let mut storage = BitmapDataStorage::new(2, 2, false, 0x0012_3456);
+
+        assert_eq!(storage.get_pixel32(0, 0), 0xFF12_3456);
+
+        storage.set_pixel32(1, 1, 0x80AB_CDEF);
+        assert_eq!(storage.get_pixel32(1, 1), 0xFFAB_CDEF);
+        assert_eq!(storage.get_pixel(1, 1), 0x00AB_CDEF);
+    }
+
+    #[test]
+    fn set_pixel_keeps_alpha() {
+        let mut storage = BitmapDataStorage::new(2, 2, true, 0x8000_0000);
+
+        storage.set_pixel(0, 1, 0xFF11_2233);
+        assert_eq!(storage.get_pixel32(0, 1), 0x8011_2233);
+        assert_eq!(storage.get_pixel32(5, 5), 0);
+    }
+
+    #[test]
+    fn fill_rect_is_clipped() {
+        let mut storage = BitmapDataStorage::new(3, 3, true, 0);
+
+        storage.fill_rect(-1, 1, 3, 10, 0xFF00_FF00);
+        assert_eq!(storage.get_pixel32(0, 0), 0);
+        assert_eq!(storage.get_pixel32(0, 1), 0xFF00_FF00);
+        assert_eq!(storage.get_pixel32(1, 2), 0xFF00_FF00);
+        assert_eq!(storage.get_pixel32(2, 2), 0);
+    }
+}
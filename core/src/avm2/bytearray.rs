@@ -0,0 +1,423 @@
+//! ByteArray support types
+
+use crate::avm2::{Error, ErrorClass};
+use flate2::read::{DeflateDecoder, DeflateEncoder, ZlibDecoder, ZlibEncoder};
+use flate2::Compression;
+use gc_arena::Collect;
+use std::io::Read;
+
+/// The byte order used when reading or writing multi-byte values.
+#[derive(Clone, Collect, Copy, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum Endian {
+    Big,
+    Little,
+}
+
+/// The compression algorithms supported by `ByteArray.compress`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompressionAlgorithm {
+    Zlib,
+    Deflate,
+}
+
+impl CompressionAlgorithm {
+    /// Parse a compression algorithm name, as found in
+    /// `flash.utils.CompressionAlgorithm`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "zlib" => Some(CompressionAlgorithm::Zlib),
+            "deflate" => Some(CompressionAlgorithm::Deflate),
+            _ => None,
+        }
+    }
+}
+
+/// The byte storage portion of a `ByteArray`.
+///
+/// All reads and writes happen at the current position, which is advanced
+/// past the bytes that were read or written. Writing past the end of the
+/// array extends it.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct ByteArrayStorage {
+    /// The bytes in the array.
+    bytes: Vec<u8>,
+
+    /// The position that the next read or write occurs at.
+    position: usize,
+
+    /// The byte order of multi-byte values.
+    endian: Endian,
+}
+
+impl Default for ByteArrayStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ByteArrayStorage {
+    /// Construct an empty, big-endian byte array.
+    pub fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            position: 0,
+            endian: Endian::Big,
+        }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn length(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Change the length of the array, truncating it or padding it with
+    /// zeroes.
+    ///
+    /// The position is moved to the end of the array if it would otherwise
+    /// lie beyond it.
+    pub fn set_length(&mut self, length: usize) {
+        self.bytes.resize(length, 0);
+        self.position = self.position.min(length);
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    /// The number of bytes that can be read from the current position.
+    pub fn bytes_available(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    pub fn set_endian(&mut self, endian: Endian) {
+        self.endian = endian;
+    }
+
+    /// Remove all bytes from the array and reset the position.
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.position = 0;
+    }
+
+    /// Retrieve a single byte by index, as with `bytes[index]`.
+    pub fn get(&self, index: usize) -> Option<u8> {
+        self.bytes.get(index).copied()
+    }
+
+    /// Set a single byte by index, as with `bytes[index] = value`.
+    ///
+    /// The array is extended with zeroes if the index is past the end of it.
+    pub fn set(&mut self, index: usize, value: u8) {
+        if index >= self.bytes.len() {
+            self.bytes.resize(index + 1, 0);
+        }
+
+        self.bytes[index] = value;
+    }
+
+    /// Read a number of bytes from the current position.
+    pub fn read_bytes<'gc>(&mut self, length: usize) -> Result<&[u8], Error<'gc>> {
+        if length > self.bytes_available() {
+            return Err(Error::avm_error(
+                ErrorClass::EOFError,
+                2030,
+                "End of file was encountered.",
+            ));
+        }
+
+        let start = self.position;
+        self.position += length;
+
+        Ok(&self.bytes[start..self.position])
+    }
+
+    /// Write a number of bytes at the current position, overwriting existing
+    /// bytes and extending the array as needed.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        let end = self.position + bytes.len();
+
+        if end > self.bytes.len() {
+            self.bytes.resize(end, 0);
+        }
+
+        self.bytes[self.position..end].copy_from_slice(bytes);
+        self.position = end;
+    }
+
+    /// Read a fixed number of bytes into an array.
+    fn read_array<'gc, A: AsMut<[u8]> + Default>(&mut self) -> Result<A, Error<'gc>> {
+        let mut array = A::default();
+        let length = array.as_mut().len();
+
+        array.as_mut().copy_from_slice(self.read_bytes(length)?);
+
+        Ok(array)
+    }
+
+    pub fn read_boolean<'gc>(&mut self) -> Result<bool, Error<'gc>> {
+        Ok(self.read_unsigned_byte()? != 0)
+    }
+
+    pub fn read_byte<'gc>(&mut self) -> Result<i8, Error<'gc>> {
+        Ok(self.read_unsigned_byte()? as i8)
+    }
+
+    pub fn read_unsigned_byte<'gc>(&mut self) -> Result<u8, Error<'gc>> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn read_short<'gc>(&mut self) -> Result<i16, Error<'gc>> {
+        let bytes = self.read_array()?;
+
+        Ok(match self.endian {
+            Endian::Big => i16::from_be_bytes(bytes),
+            Endian::Little => i16::from_le_bytes(bytes),
+        })
+    }
+
+    pub fn read_unsigned_short<'gc>(&mut self) -> Result<u16, Error<'gc>> {
+        let bytes = self.read_array()?;
+
+        Ok(match self.endian {
+            Endian::Big => u16::from_be_bytes(bytes),
+            Endian::Little => u16::from_le_bytes(bytes),
+        })
+    }
+
+    pub fn read_int<'gc>(&mut self) -> Result<i32, Error<'gc>> {
+        let bytes = self.read_array()?;
+
+        Ok(match self.endian {
+            Endian::Big => i32::from_be_bytes(bytes),
+            Endian::Little => i32::from_le_bytes(bytes),
+        })
+    }
+
+    pub fn read_unsigned_int<'gc>(&mut self) -> Result<u32, Error<'gc>> {
+        let bytes = self.read_array()?;
+
+        Ok(match self.endian {
+            Endian::Big => u32::from_be_bytes(bytes),
+            Endian::Little => u32::from_le_bytes(bytes),
+        })
+    }
+
+    pub fn read_float<'gc>(&mut self) -> Result<f32, Error<'gc>> {
+        let bytes = self.read_array()?;
+
+        Ok(match self.endian {
+            Endian::Big => f32::from_be_bytes(bytes),
+            Endian::Little => f32::from_le_bytes(bytes),
+        })
+    }
+
+    pub fn read_double<'gc>(&mut self) -> Result<f64, Error<'gc>> {
+        let bytes = self.read_array()?;
+
+        Ok(match self.endian {
+            Endian::Big => f64::from_be_bytes(bytes),
+            Endian::Little => f64::from_le_bytes(bytes),
+        })
+    }
+
+    /// Read a string of a given length in bytes.
+    ///
+    /// Invalid UTF-8 sequences are replaced, and a leading byte order mark is
+    /// skipped.
+    pub fn read_utf_bytes<'gc>(&mut self, length: usize) -> Result<String, Error<'gc>> {
+        let bytes = self.read_bytes(length)?;
+        let bytes = if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+            &bytes[3..]
+        } else {
+            bytes
+        };
+        let string = String::from_utf8_lossy(bytes);
+
+        // Strings end at the first null byte.
+        Ok(string.split('\0').next().unwrap_or("").to_string())
+    }
+
+    /// Read a string prefixed with its length as an unsigned short.
+    pub fn read_utf<'gc>(&mut self) -> Result<String, Error<'gc>> {
+        let length = self.read_unsigned_short()?;
+
+        self.read_utf_bytes(length as usize)
+    }
+
+    pub fn write_boolean(&mut self, value: bool) {
+        self.write_bytes(&[value as u8]);
+    }
+
+    pub fn write_byte(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    pub fn write_short(&mut self, value: u16) {
+        match self.endian {
+            Endian::Big => self.write_bytes(&value.to_be_bytes()),
+            Endian::Little => self.write_bytes(&value.to_le_bytes()),
+        }
+    }
+
+    pub fn write_int(&mut self, value: u32) {
+        match self.endian {
+            Endian::Big => self.write_bytes(&value.to_be_bytes()),
+            Endian::Little => self.write_bytes(&value.to_le_bytes()),
+        }
+    }
+
+    pub fn write_float(&mut self, value: f32) {
+        match self.endian {
+            Endian::Big => self.write_bytes(&value.to_be_bytes()),
+            Endian::Little => self.write_bytes(&value.to_le_bytes()),
+        }
+    }
+
+    pub fn write_double(&mut self, value: f64) {
+        match self.endian {
+            Endian::Big => self.write_bytes(&value.to_be_bytes()),
+            Endian::Little => self.write_bytes(&value.to_le_bytes()),
+        }
+    }
+
+    pub fn write_utf_bytes(&mut self, value: &str) {
+        self.write_bytes(value.as_bytes());
+    }
+
+    /// Write a string prefixed with its length as an unsigned short.
+    pub fn write_utf<'gc>(&mut self, value: &str) -> Result<(), Error<'gc>> {
+        if value.len() > 0xFFFF {
+            return Err(Error::avm_error(
+                ErrorClass::RangeError,
+                2006,
+                "The supplied index is out of bounds.",
+            ));
+        }
+
+        self.write_short(value.len() as u16);
+        self.write_utf_bytes(value);
+
+        Ok(())
+    }
+
+    /// Compress the entire contents of the array, leaving the position at the
+    /// end of the compressed data.
+    pub fn compress<'gc>(&mut self, algorithm: CompressionAlgorithm) -> Result<(), Error<'gc>> {
+        let mut compressed = Vec::new();
+
+        match algorithm {
+            CompressionAlgorithm::Zlib => {
+                ZlibEncoder::new(&self.bytes[..], Compression::default())
+                    .read_to_end(&mut compressed)?;
+            }
+            CompressionAlgorithm::Deflate => {
+                DeflateEncoder::new(&self.bytes[..], Compression::default())
+                    .read_to_end(&mut compressed)?;
+            }
+        }
+
+        self.position = compressed.len();
+        self.bytes = compressed;
+
+        Ok(())
+    }
+
+    /// Decompress the entire contents of the array, resetting the position
+    /// to the start of the decompressed data.
+    ///
+    /// The array is left untouched if its contents cannot be decompressed.
+    pub fn uncompress<'gc>(&mut self, algorithm: CompressionAlgorithm) -> Result<(), Error<'gc>> {
+        let mut uncompressed = Vec::new();
+        let result = match algorithm {
+            CompressionAlgorithm::Zlib => {
+                ZlibDecoder::new(&self.bytes[..]).read_to_end(&mut uncompressed)
+            }
+            CompressionAlgorithm::Deflate => {
+                DeflateDecoder::new(&self.bytes[..]).read_to_end(&mut uncompressed)
+            }
+        };
+
+        if result.is_err() {
+            return Err(Error::avm_error(
+                ErrorClass::IOError,
+                2058,
+                "There was an error decompressing the data.",
+            ));
+        }
+
+        self.position = 0;
+        self.bytes = uncompressed;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn write_then_read_numbers() {
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_short(0x1234);
+        storage.write_int(0xDEAD_BEEF);
+        storage.set_endian(Endian::Little);
+        storage.write_short(0x1234);
+        storage.write_double(1.5);
+
+        assert_eq!(
+            &storage.bytes()[..8],
+            &[0x12, 0x34, 0xDE, 0xAD, 0xBE, 0xEF, 0x34, 0x12]
+        );
+
+        storage.set_position(0);
+        storage.set_endian(Endian::Big);
+        assert_eq!(storage.read_unsigned_short().unwrap(), 0x1234);
+        assert_eq!(storage.read_int().unwrap(), 0xDEAD_BEEFu32 as i32);
+        storage.set_endian(Endian::Little);
+        assert_eq!(storage.read_short().unwrap(), 0x1234);
+        assert_eq!(storage.read_double().unwrap(), 1.5);
+        assert_eq!(storage.bytes_available(), 0);
+        assert!(storage.read_byte().is_err());
+    }
+
+    #[test]
+    fn utf_strings() {
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_utf("h\u{e9}llo").unwrap();
+        assert_eq!(storage.length(), 8);
+
+        storage.set_position(0);
+        assert_eq!(storage.read_utf().unwrap(), "h\u{e9}llo");
+    }
+
+    #[test]
+    fn compression_round_trip() {
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_utf_bytes("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+        storage.compress(CompressionAlgorithm::Zlib).unwrap();
+        assert!(storage.length() < 40);
+
+        storage.uncompress(CompressionAlgorithm::Zlib).unwrap();
+        assert_eq!(storage.length(), 40);
+        assert_eq!(storage.position(), 0);
+        assert!(storage.uncompress(CompressionAlgorithm::Zlib).is_err());
+    }
+}
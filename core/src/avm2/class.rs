@@ -81,7 +81,7 @@ fn do_trait_lookup<'gc>(
     name: &QName<'gc>,
     known_traits: &mut Vec<Trait<'gc>>,
     all_traits: &[Trait<'gc>],
-) -> Result<(), Error> {
+) -> Result<(), Error<'gc>> {
     for trait_entry in all_traits {
         if name == trait_entry.name() {
             for known_trait in known_traits.iter() {
@@ -159,15 +159,15 @@ impl<'gc> Class<'gc> {
         unit: TranslationUnit<'gc>,
         class_index: u32,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<GcCell<'gc, Self>, Error> {
+    ) -> Result<GcCell<'gc, Self>, Error<'gc>> {
         let abc = unit.abc();
-        let abc_class: Result<&AbcClass, Error> = abc
+        let abc_class: Result<&AbcClass, Error<'gc>> = abc
             .classes
             .get(class_index as usize)
             .ok_or_else(|| "LoadError: Class index not valid".into());
         let abc_class = abc_class?;
 
-        let abc_instance: Result<&AbcInstance, Error> = abc
+        let abc_instance: Result<&AbcInstance, Error<'gc>> = abc
             .instances
             .get(class_index as usize)
             .ok_or_else(|| "LoadError: Instance index not valid".into());
@@ -244,7 +244,7 @@ impl<'gc> Class<'gc> {
         class_index: u32,
         avm2: &mut Avm2<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if self.traits_loaded {
             return Ok(());
         }
@@ -252,13 +252,13 @@ impl<'gc> Class<'gc> {
         self.traits_loaded = true;
 
         let abc = unit.abc();
-        let abc_class: Result<&AbcClass, Error> = abc
+        let abc_class: Result<&AbcClass, Error<'gc>> = abc
             .classes
             .get(class_index as usize)
             .ok_or_else(|| "LoadError: Class index not valid".into());
         let abc_class = abc_class?;
 
-        let abc_instance: Result<&AbcInstance, Error> = abc
+        let abc_instance: Result<&AbcInstance, Error<'gc>> = abc
             .instances
             .get(class_index as usize)
             .ok_or_else(|| "LoadError: Instance index not valid".into());
@@ -307,7 +307,7 @@ impl<'gc> Class<'gc> {
         &self,
         name: &QName<'gc>,
         known_traits: &mut Vec<Trait<'gc>>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         do_trait_lookup(name, known_traits, &self.class_traits)
     }
 
@@ -360,7 +360,7 @@ impl<'gc> Class<'gc> {
         &self,
         name: &QName<'gc>,
         known_traits: &mut Vec<Trait<'gc>>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         do_trait_lookup(name, known_traits, &self.instance_traits)
     }
 
@@ -5,7 +5,7 @@ use crate::avm2::names::{Multiname, QName};
 use crate::avm2::object::TObject;
 use crate::avm2::script::Script;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Error, ErrorClass};
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::collections::HashMap;
 
@@ -78,7 +78,7 @@ impl<'gc> Domain<'gc> {
     pub fn get_defining_script(
         self,
         multiname: &Multiname<'gc>,
-    ) -> Result<Option<(QName<'gc>, Script<'gc>)>, Error> {
+    ) -> Result<Option<(QName<'gc>, Script<'gc>)>, Error<'gc>> {
         let read = self.0.read();
 
         for ns in multiname.namespace_set() {
@@ -115,7 +115,7 @@ impl<'gc> Domain<'gc> {
         self,
         activation: &mut Activation<'_, 'gc, '_>,
         name: QName<'gc>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         let (name, mut script) = self
             .get_defining_script(&name.clone().into())?
             .ok_or_else(|| format!("MovieClip Symbol {} does not exist", name.local_name()))?;
@@ -133,13 +133,13 @@ impl<'gc> Domain<'gc> {
         name: QName<'gc>,
         script: Script<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if self.has_definition(name.clone()) {
-            return Err(format!(
-                "VerifyError: Attempted to redefine existing name {}",
-                name.local_name()
-            )
-            .into());
+            return Err(Error::avm_error(
+                ErrorClass::VerifyError,
+                0,
+                format!("Attempted to redefine existing name {}", name.local_name()),
+            ));
         }
 
         self.0.write(mc).defs.insert(name, script);
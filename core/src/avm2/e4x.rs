@@ -0,0 +1,695 @@
+//! E4X (ECMAScript for XML) support
+//!
+//! AVM2's `XML` and `XMLList` classes are implemented on top of the same XML
+//! tree that AVM1's `XMLNode` uses. E4X discards comments and whitespace-only
+//! text when parsing, and attributes are values in their own right, so this
+//! module wraps tree nodes in an `E4XNode` that can also refer to a single
+//! attribute of an element.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject, XmlListObject, XmlObject};
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use crate::xml::{XMLDocument, XMLName, XMLNode};
+use gc_arena::{Collect, MutationContext};
+
+/// The number of spaces each level of nesting is indented by when pretty
+/// printing XML.
+const INDENT: usize = 2;
+
+/// A single E4X value: either a node in an XML tree, or an attribute of an
+/// element node.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum E4XNode<'gc> {
+    Node(XMLNode<'gc>),
+    Attribute(XMLNode<'gc>, XMLName),
+}
+
+impl<'gc> E4XNode<'gc> {
+    /// Parse a string of XML into a list of E4X nodes.
+    ///
+    /// Comments and whitespace-only text are discarded, and the remaining
+    /// text is trimmed. Strings that do not contain markup yield a single
+    /// text node.
+    pub fn parse(mc: MutationContext<'gc, '_>, data: &str) -> Result<Vec<Self>, Error<'gc>> {
+        let document = XMLDocument::new(mc);
+        let mut root = document.as_node();
+
+        root.replace_with_str(mc, data, true)?;
+        normalize(mc, root)?;
+
+        Ok(root
+            .children()
+            .map(|children| children.map(E4XNode::Node).collect())
+            .unwrap_or_else(Vec::new))
+    }
+
+    /// Construct a free-standing text node.
+    pub fn new_text(mc: MutationContext<'gc, '_>, text: &str) -> Self {
+        let document = XMLDocument::new(mc);
+
+        E4XNode::Node(XMLNode::new_text(mc, text, document))
+    }
+
+    /// Check if two values refer to the same node or attribute.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (E4XNode::Node(a), E4XNode::Node(b)) => a.ptr_eq(*b),
+            (E4XNode::Attribute(a, a_name), E4XNode::Attribute(b, b_name)) => {
+                a.ptr_eq(*b) && a_name == b_name
+            }
+            _ => false,
+        }
+    }
+
+    /// The underlying tree node, if this is not an attribute.
+    pub fn node(&self) -> Option<XMLNode<'gc>> {
+        match self {
+            E4XNode::Node(node) => Some(*node),
+            E4XNode::Attribute(..) => None,
+        }
+    }
+
+    /// The qualified name of this element or attribute.
+    pub fn name(&self) -> Option<XMLName> {
+        match self {
+            E4XNode::Node(node) => node.tag_name(),
+            E4XNode::Attribute(_, name) => Some(name.clone()),
+        }
+    }
+
+    /// The kind of node this is, as yielded by `XML.nodeKind`.
+    pub fn node_kind(&self) -> &'static str {
+        match self {
+            E4XNode::Node(node) if node.is_element() => "element",
+            E4XNode::Node(node) if node.is_comment() => "comment",
+            E4XNode::Node(_) => "text",
+            E4XNode::Attribute(..) => "attribute",
+        }
+    }
+
+    pub fn is_element(&self) -> bool {
+        matches!(self, E4XNode::Node(node) if node.is_element())
+    }
+
+    pub fn is_text(&self) -> bool {
+        matches!(self, E4XNode::Node(node) if node.is_text())
+    }
+
+    /// Check if this is an element or attribute with the given local name.
+    ///
+    /// The name `*` matches any element or attribute.
+    pub fn matches_name(&self, name: &str) -> bool {
+        match self.name() {
+            Some(my_name) => name == "*" || my_name.local_name() == name,
+            None => false,
+        }
+    }
+
+    /// The parent element of this node or attribute.
+    ///
+    /// The top-level nodes of a parsed string have no parent.
+    pub fn parent(&self) -> Option<Self> {
+        match self {
+            E4XNode::Node(node) => node
+                .parent()
+                .ok()
+                .flatten()
+                .filter(|parent| parent.is_element())
+                .map(E4XNode::Node),
+            E4XNode::Attribute(node, _) => Some(E4XNode::Node(*node)),
+        }
+    }
+
+    /// All children of this node.
+    pub fn children(&self) -> Vec<Self> {
+        match self {
+            E4XNode::Node(node) => node
+                .children()
+                .map(|children| children.map(E4XNode::Node).collect())
+                .unwrap_or_else(Vec::new),
+            E4XNode::Attribute(..) => Vec::new(),
+        }
+    }
+
+    /// All attributes of this node, in name order.
+    pub fn attributes(&self) -> Vec<Self> {
+        match self {
+            E4XNode::Node(node) => node
+                .attribute_keys()
+                .iter()
+                .map(|key| E4XNode::Attribute(*node, XMLName::from_str(key)))
+                .collect(),
+            E4XNode::Attribute(..) => Vec::new(),
+        }
+    }
+
+    /// All child elements with a given name, or all attributes with a given
+    /// name if `is_attribute` is set.
+    pub fn child_named(&self, name: &str, is_attribute: bool) -> Vec<Self> {
+        if is_attribute {
+            self.attributes()
+                .into_iter()
+                .filter(|a| a.matches_name(name))
+                .collect()
+        } else if name == "*" {
+            self.children()
+        } else {
+            self.children()
+                .into_iter()
+                .filter(|c| c.matches_name(name))
+                .collect()
+        }
+    }
+
+    /// All descendant elements (or attributes of descendant elements) with a
+    /// given name, in document order.
+    pub fn descendants(&self, name: &str, is_attribute: bool, out: &mut Vec<Self>) {
+        for child in self.children() {
+            if is_attribute {
+                out.extend(child.child_named(name, true));
+            } else if name == "*" || child.matches_name(name) {
+                out.push(child.clone());
+            }
+
+            child.descendants(name, is_attribute, out);
+        }
+    }
+
+    /// The text content of this node.
+    ///
+    /// Elements yield the text of all of their descendants concatenated
+    /// together.
+    pub fn text(&self) -> String {
+        match self {
+            E4XNode::Node(node) if node.is_element() => {
+                let mut text = String::new();
+
+                for child in self.children() {
+                    if !child.node().map(|c| c.is_comment()).unwrap_or(false) {
+                        text.push_str(&child.text());
+                    }
+                }
+
+                text
+            }
+            E4XNode::Node(node) => node.node_value().unwrap_or_default(),
+            E4XNode::Attribute(node, name) => node.attribute_value(name).unwrap_or_default(),
+        }
+    }
+
+    /// Check if this node has simple content: that is, it is not an element
+    /// or it is an element with no child elements.
+    pub fn has_simple_content(&self) -> bool {
+        match self {
+            E4XNode::Node(node) if node.is_element() => {
+                !self.children().iter().any(|c| c.is_element())
+            }
+            E4XNode::Node(node) => !node.is_comment(),
+            E4XNode::Attribute(..) => true,
+        }
+    }
+
+    /// Convert this node to a string, as with `XML.toString`.
+    ///
+    /// Nodes with simple content yield their text; all other nodes are
+    /// printed as XML.
+    pub fn to_simple_string(&self) -> String {
+        if self.has_simple_content() {
+            self.text()
+        } else {
+            self.to_xml_string()
+        }
+    }
+
+    /// Print this node as pretty-printed XML, as with `XML.toXMLString`.
+    pub fn to_xml_string(&self) -> String {
+        let mut out = String::new();
+
+        self.write_xml(&mut out, 0);
+
+        out
+    }
+
+    fn write_xml(&self, out: &mut String, indent: usize) {
+        out.push_str(&" ".repeat(indent));
+
+        let node = match self {
+            E4XNode::Node(node) => *node,
+            E4XNode::Attribute(..) => {
+                out.push_str(&escape_attribute_value(&self.text()));
+                return;
+            }
+        };
+
+        if node.is_text() {
+            out.push_str(&escape_element_value(&self.text()));
+            return;
+        }
+
+        if node.is_comment() {
+            out.push_str("<!--");
+            out.push_str(&node.node_value().unwrap_or_default());
+            out.push_str("-->");
+            return;
+        }
+
+        let name = node
+            .tag_name()
+            .map(|n| n.node_name().to_string())
+            .unwrap_or_default();
+
+        out.push('<');
+        out.push_str(&name);
+
+        for key in node.attribute_keys() {
+            let value = node
+                .attribute_value(&XMLName::from_str(&key))
+                .unwrap_or_default();
+
+            out.push(' ');
+            out.push_str(&key);
+            out.push_str("=\"");
+            out.push_str(&escape_attribute_value(&value));
+            out.push('"');
+        }
+
+        let children = self.children();
+
+        if children.is_empty() {
+            out.push_str("/>");
+            return;
+        }
+
+        out.push('>');
+
+        if children.len() == 1 && children[0].is_text() {
+            out.push_str(&escape_element_value(&children[0].text()));
+        } else {
+            for child in children {
+                out.push('\n');
+                child.write_xml(out, indent + INDENT);
+            }
+
+            out.push('\n');
+            out.push_str(&" ".repeat(indent));
+        }
+
+        out.push_str("</");
+        out.push_str(&name);
+        out.push('>');
+    }
+
+    /// Create a copy of this node and all of its children, detached from any
+    /// tree.
+    ///
+    /// Copying an attribute yields a text node of the attribute's value.
+    pub fn deep_copy(&self, mc: MutationContext<'gc, '_>) -> Self {
+        match self {
+            E4XNode::Node(node) => E4XNode::Node(node.duplicate(mc, true)),
+            E4XNode::Attribute(..) => E4XNode::new_text(mc, &self.text()),
+        }
+    }
+
+    /// Append a child to this element.
+    ///
+    /// Nodes that are already part of a tree are copied first, and attributes
+    /// are appended as text.
+    pub fn append_child(
+        &self,
+        mc: MutationContext<'gc, '_>,
+        child: &Self,
+    ) -> Result<(), Error<'gc>> {
+        if let E4XNode::Node(mut node) = self {
+            let child = match (child, child.parent()) {
+                (E4XNode::Node(child), None) => *child,
+                _ => match child.deep_copy(mc) {
+                    E4XNode::Node(child) => child,
+                    E4XNode::Attribute(..) => unreachable!(),
+                },
+            };
+
+            node.append_child(mc, child)?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace the contents of this element or attribute with text.
+    pub fn set_text(&self, mc: MutationContext<'gc, '_>, text: &str) -> Result<(), Error<'gc>> {
+        match self {
+            E4XNode::Node(node) if node.is_element() => {
+                let mut node = *node;
+
+                for child in self.children() {
+                    if let E4XNode::Node(child) = child {
+                        node.remove_child(mc, child)?;
+                    }
+                }
+
+                let text_node = XMLNode::new_text(mc, text, node.document());
+                node.append_child(mc, text_node)?;
+            }
+            E4XNode::Attribute(node, name) => node.set_attribute_value(mc, name, text),
+            E4XNode::Node(_) => {}
+        }
+
+        Ok(())
+    }
+
+    /// Set an attribute of this element.
+    pub fn set_attribute(&self, mc: MutationContext<'gc, '_>, name: &str, value: &str) {
+        if let E4XNode::Node(node) = self {
+            node.set_attribute_value(mc, &XMLName::from_str(name), value);
+        }
+    }
+
+    /// Remove all child elements or attributes with a given name.
+    pub fn delete_named(
+        &self,
+        mc: MutationContext<'gc, '_>,
+        name: &str,
+        is_attribute: bool,
+    ) -> Result<(), Error<'gc>> {
+        if let E4XNode::Node(mut node) = self {
+            for target in self.child_named(name, is_attribute) {
+                match target {
+                    E4XNode::Node(child) => node.remove_child(mc, child)?,
+                    E4XNode::Attribute(_, name) => node.delete_attribute(mc, &name),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Append a new element with the given name and text content, as happens
+    /// when assigning to a child element that does not yet exist.
+    pub fn append_text_element(
+        &self,
+        mc: MutationContext<'gc, '_>,
+        name: &str,
+        text: &str,
+    ) -> Result<(), Error<'gc>> {
+        if let E4XNode::Node(mut node) = self {
+            let mut element = XMLNode::new_element(mc, name, node.document());
+            let text_node = XMLNode::new_text(mc, text, node.document());
+
+            element.append_child(mc, text_node)?;
+            node.append_child(mc, element)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Retrieve the nodes that an `XML` or `XMLList` object refers to.
+///
+/// An `XML` object is treated as a list of one node. Objects of any other
+/// class yield `None`.
+pub fn nodes_of(object: Object<'_>) -> Option<Vec<E4XNode<'_>>> {
+    if let Some(node) = object.as_xml() {
+        Some(vec![node])
+    } else {
+        object.as_xml_list().as_deref().cloned()
+    }
+}
+
+/// Check if an object is an `XML` or `XMLList`.
+pub fn is_xml_like(object: Object<'_>) -> bool {
+    object.as_xml().is_some() || object.as_xml_list().is_some()
+}
+
+/// Wrap a node in a new `XML` object.
+pub fn new_xml<'gc>(activation: &mut Activation<'_, 'gc, '_>, node: E4XNode<'gc>) -> Object<'gc> {
+    let proto = activation.context.avm2.prototypes().xml;
+
+    XmlObject::from_node(activation.context.gc_context, proto, node)
+}
+
+/// Wrap a list of nodes in a new `XMLList` object.
+pub fn new_xml_list<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    nodes: Vec<E4XNode<'gc>>,
+) -> Object<'gc> {
+    let proto = activation.context.avm2.prototypes().xml_list;
+
+    XmlListObject::from_nodes(activation.context.gc_context, proto, nodes)
+}
+
+/// Parse a property name as an index into an XML list.
+fn index_of(name: &str, is_attribute: bool) -> Option<usize> {
+    if is_attribute {
+        None
+    } else {
+        name.parse::<usize>().ok()
+    }
+}
+
+/// Check if an `XML` or `XMLList` object has a given child, attribute or
+/// index.
+pub fn has_property(object: Object<'_>, name: &str, is_attribute: bool) -> bool {
+    let nodes = match nodes_of(object) {
+        Some(nodes) => nodes,
+        None => return false,
+    };
+
+    if let Some(index) = index_of(name, is_attribute) {
+        return index < nodes.len();
+    }
+
+    nodes
+        .iter()
+        .any(|node| !node.child_named(name, is_attribute).is_empty())
+}
+
+/// Retrieve a child, attribute or index of an `XML` or `XMLList` object.
+///
+/// Named lookups yield an `XMLList` of every matching child or attribute of
+/// every node in the list; index lookups yield a single `XML` value. Objects
+/// that are not XML yield `None`.
+pub fn get_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    name: &str,
+    is_attribute: bool,
+) -> Option<Value<'gc>> {
+    let nodes = nodes_of(object)?;
+
+    if let Some(index) = index_of(name, is_attribute) {
+        return Some(match nodes.get(index) {
+            Some(_) if object.as_xml().is_some() => object.into(),
+            Some(node) => new_xml(activation, node.clone()).into(),
+            None => Value::Undefined,
+        });
+    }
+
+    let mut matches = Vec::new();
+    for node in nodes.iter() {
+        matches.extend(node.child_named(name, is_attribute));
+    }
+
+    Some(new_xml_list(activation, matches).into())
+}
+
+/// Retrieve the descendants of an `XML` or `XMLList` object with a given
+/// name, as with the `..` operator.
+pub fn get_descendants<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    name: &str,
+    is_attribute: bool,
+) -> Option<Value<'gc>> {
+    let nodes = nodes_of(object)?;
+    let mut matches = Vec::new();
+
+    for node in nodes.iter() {
+        node.descendants(name, is_attribute, &mut matches);
+    }
+
+    Some(new_xml_list(activation, matches).into())
+}
+
+/// Assign to a child, attribute or index of an `XML` or `XMLList` object.
+///
+/// Assigning to a child replaces its content with the given value, creating
+/// the child if it does not exist yet. Assigning to an index of an `XMLList`
+/// replaces that item, or appends to the list if the index is past its end.
+///
+/// Returns `false` if the object is not XML.
+pub fn set_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    name: &str,
+    is_attribute: bool,
+    value: Value<'gc>,
+) -> Result<bool, Error<'gc>> {
+    let mc = activation.context.gc_context;
+    let nodes = match nodes_of(object) {
+        Some(nodes) => nodes,
+        None => return Ok(false),
+    };
+    let value_nodes = match &value {
+        Value::Object(o) => nodes_of(*o),
+        _ => None,
+    };
+
+    if let Some(index) = index_of(name, is_attribute) {
+        if object.as_xml().is_some() {
+            return Err(Error::avm_error(
+                ErrorClass::TypeError,
+                1087,
+                "Assignment to indexed XML is not allowed.",
+            ));
+        }
+
+        let new_node = match value_nodes.and_then(|n| n.into_iter().next()) {
+            Some(node) => node,
+            None => E4XNode::new_text(mc, &value.coerce_to_string(activation)?),
+        };
+
+        if let Some(mut list) = object.as_xml_list_mut(mc) {
+            if index < list.len() {
+                list[index] = new_node;
+            } else {
+                list.push(new_node);
+            }
+        }
+
+        return Ok(true);
+    }
+
+    let target = match nodes.as_slice() {
+        [] => return Ok(true),
+        [target] => target.clone(),
+        _ => {
+            return Err(Error::avm_error(
+                ErrorClass::TypeError,
+                1089,
+                "Assignment to lists with more than one item is not supported.",
+            ))
+        }
+    };
+
+    if is_attribute {
+        let text = value.coerce_to_string(activation)?;
+
+        target.set_attribute(mc, name, &text);
+    } else if let Some(value_nodes) = value_nodes {
+        target.delete_named(mc, name, false)?;
+
+        for node in value_nodes {
+            target.append_child(mc, &node)?;
+        }
+    } else {
+        let text = value.coerce_to_string(activation)?;
+        let existing = target.child_named(name, false);
+
+        if let Some(first) = existing.first() {
+            first.set_text(mc, &text)?;
+        } else {
+            target.append_text_element(mc, name, &text)?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// Delete a child, attribute or index of an `XML` or `XMLList` object.
+///
+/// Returns `None` if the object is not XML.
+pub fn delete_property<'gc>(
+    mc: MutationContext<'gc, '_>,
+    object: Object<'gc>,
+    name: &str,
+    is_attribute: bool,
+) -> Result<Option<bool>, Error<'gc>> {
+    let nodes = match nodes_of(object) {
+        Some(nodes) => nodes,
+        None => return Ok(None),
+    };
+
+    if let Some(index) = index_of(name, is_attribute) {
+        if let Some(mut list) = object.as_xml_list_mut(mc) {
+            if index < list.len() {
+                list.remove(index);
+            }
+        }
+
+        return Ok(Some(true));
+    }
+
+    for node in nodes {
+        node.delete_named(mc, name, is_attribute)?;
+    }
+
+    Ok(Some(true))
+}
+
+/// Remove comments and whitespace-only text from a freshly parsed tree, and
+/// trim the text that remains.
+fn normalize<'gc>(mc: MutationContext<'gc, '_>, mut node: XMLNode<'gc>) -> Result<(), Error<'gc>> {
+    let children: Vec<XMLNode<'gc>> = node
+        .children()
+        .map(|children| children.collect())
+        .unwrap_or_else(Vec::new);
+
+    for child in children {
+        if child.is_comment() || child.is_doctype() {
+            node.remove_child(mc, child)?;
+        } else if child.is_text() {
+            let text = child.node_value().unwrap_or_default();
+            let trimmed = text.trim();
+
+            if trimmed.is_empty() {
+                node.remove_child(mc, child)?;
+            } else if trimmed.len() != text.len() {
+                let position = node.child_position(child).unwrap_or(0);
+                let replacement = XMLNode::new_text(mc, trimmed, node.document());
+
+                node.remove_child(mc, child)?;
+                node.insert_child(mc, position, replacement)?;
+            }
+        } else if child.is_element() {
+            normalize(mc, child)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Escape text for use as element content.
+pub fn escape_element_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Escape text for use as an attribute value.
+pub fn escape_attribute_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '"' => out.push_str("&quot;"),
+            '\n' => out.push_str("&#xA;"),
+            '\r' => out.push_str("&#xD;"),
+            '\t' => out.push_str("&#x9;"),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
@@ -0,0 +1,113 @@
+use crate::avm2::Value;
+use std::fmt;
+
+/// An error raised while running AVM2 code.
+#[derive(Debug)]
+pub enum Error<'gc> {
+    /// A value thrown by ActionScript code with the `throw` instruction.
+    ThrownValue(Value<'gc>),
+
+    /// An error raised by the interpreter or by a native function that
+    /// corresponds to an ActionScript error class.
+    ///
+    /// These become instances of `class` when they reach a script's exception
+    /// handler, and can be caught like any other thrown value.
+    AvmError {
+        /// The ActionScript class of the error.
+        class: ErrorClass,
+
+        /// The Flash Player error ID, or zero if the error has none.
+        id: u32,
+
+        /// The error message, without the error ID.
+        message: String,
+    },
+
+    /// An internal error that cannot be caught by scripts.
+    RustError(Box<dyn std::error::Error>),
+}
+
+impl<'gc> Error<'gc> {
+    /// Construct an error that scripts can catch as an instance of `class`.
+    pub fn avm_error(class: ErrorClass, id: u32, message: impl Into<String>) -> Self {
+        Error::AvmError {
+            class,
+            id,
+            message: message.into(),
+        }
+    }
+
+    /// Determine if this error is a value thrown by a script.
+    pub fn is_thrown_value(&self) -> bool {
+        matches!(self, Error::ThrownValue(_))
+    }
+}
+
+impl fmt::Display for Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ThrownValue(_) => write!(f, "A script has thrown a custom error."),
+            Error::AvmError {
+                class,
+                id: 0,
+                message,
+            } => {
+                write!(f, "{}: {}", class.name(), message)
+            }
+            Error::AvmError { class, id, message } => {
+                write!(f, "{}: Error #{}: {}", class.name(), id, message)
+            }
+            Error::RustError(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl<'gc, E> From<E> for Error<'gc>
+where
+    Box<dyn std::error::Error>: From<E>,
+{
+    fn from(error: E) -> Self {
+        Error::RustError(error.into())
+    }
+}
+
+/// The ActionScript error classes that the VM raises errors as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorClass {
+    ArgumentError,
+    RangeError,
+    ReferenceError,
+    SyntaxError,
+    TypeError,
+    VerifyError,
+    EOFError,
+    IOError,
+    IllegalOperationError,
+}
+
+impl ErrorClass {
+    /// The local name of the error class.
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorClass::ArgumentError => "ArgumentError",
+            ErrorClass::RangeError => "RangeError",
+            ErrorClass::ReferenceError => "ReferenceError",
+            ErrorClass::SyntaxError => "SyntaxError",
+            ErrorClass::TypeError => "TypeError",
+            ErrorClass::VerifyError => "VerifyError",
+            ErrorClass::EOFError => "EOFError",
+            ErrorClass::IOError => "IOError",
+            ErrorClass::IllegalOperationError => "IllegalOperationError",
+        }
+    }
+
+    /// The package that the error class is defined in.
+    pub fn package(self) -> &'static str {
+        match self {
+            ErrorClass::EOFError | ErrorClass::IOError | ErrorClass::IllegalOperationError => {
+                "flash.errors"
+            }
+            _ => "",
+        }
+    }
+}
@@ -0,0 +1,467 @@
+//! Core event structure
+
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
+use fnv::FnvHashMap;
+use gc_arena::Collect;
+use std::collections::BTreeMap;
+
+/// Which phase of event dispatch is currently occurring.
+#[derive(Copy, Clone, Collect, Debug, PartialEq, Eq)]
+#[collect(no_drop)]
+pub enum EventPhase {
+    /// The event has not yet been fired on the target and is propagating from
+    /// the stage to the target.
+    Capturing,
+
+    /// The event is currently firing on the target.
+    AtTarget,
+
+    /// The event has already fired on the target and is propagating from the
+    /// target to the stage.
+    Bubbling,
+}
+
+impl From<EventPhase> for u32 {
+    fn from(phase: EventPhase) -> u32 {
+        match phase {
+            EventPhase::Capturing => 1,
+            EventPhase::AtTarget => 2,
+            EventPhase::Bubbling => 3,
+        }
+    }
+}
+
+/// How this event is allowed to propagate.
+#[derive(Copy, Clone, Collect, Debug, PartialEq, Eq)]
+#[collect(no_drop)]
+pub enum PropagationMode {
+    /// Propagate events normally.
+    AllowPropagation,
+
+    /// Stop capturing or bubbling events.
+    StopPropagation,
+
+    /// Stop running event handlers altogether.
+    StopImmediatePropagation,
+}
+
+/// Represents data fields of an event that can be fired on an object that
+/// implements `IEventDispatcher`.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct Event<'gc> {
+    /// Whether or not the event "bubbles" - fires on its parents after it
+    /// fires on the child.
+    bubbles: bool,
+
+    /// Whether or not the event has a default response that an event handler
+    /// can request to not occur.
+    cancelable: bool,
+
+    /// Whether or not the event's default response has been cancelled.
+    cancelled: bool,
+
+    /// Whether or not event propagation has stopped.
+    propagation: PropagationMode,
+
+    /// The object currently having its event handlers invoked.
+    current_target: Option<Object<'gc>>,
+
+    /// The current event phase.
+    event_phase: EventPhase,
+
+    /// The object this event was dispatched on.
+    target: Option<Object<'gc>>,
+
+    /// The name of the event being triggered.
+    event_type: AvmString<'gc>,
+}
+
+impl<'gc> Event<'gc> {
+    /// Construct a new event of a given type.
+    pub fn new<S>(event_type: S) -> Self
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        Event {
+            bubbles: false,
+            cancelable: false,
+            cancelled: false,
+            propagation: PropagationMode::AllowPropagation,
+            current_target: None,
+            event_phase: EventPhase::AtTarget,
+            target: None,
+            event_type: event_type.into(),
+        }
+    }
+
+    pub fn event_type(&self) -> AvmString<'gc> {
+        self.event_type
+    }
+
+    pub fn set_event_type<S>(&mut self, event_type: S)
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        self.event_type = event_type.into();
+    }
+
+    pub fn is_bubbling(&self) -> bool {
+        self.bubbles
+    }
+
+    pub fn set_bubbles(&mut self, bubbling: bool) {
+        self.bubbles = bubbling;
+    }
+
+    pub fn is_cancelable(&self) -> bool {
+        self.cancelable
+    }
+
+    pub fn set_cancelable(&mut self, cancelable: bool) {
+        self.cancelable = cancelable;
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Request that the event's default response not occur.
+    ///
+    /// Events that are not cancelable ignore this request.
+    pub fn cancel(&mut self) {
+        if self.cancelable {
+            self.cancelled = true;
+        }
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation != PropagationMode::AllowPropagation
+    }
+
+    pub fn stop_propagation(&mut self) {
+        if self.propagation != PropagationMode::StopImmediatePropagation {
+            self.propagation = PropagationMode::StopPropagation;
+        }
+    }
+
+    pub fn is_propagation_stopped_immediately(&self) -> bool {
+        self.propagation == PropagationMode::StopImmediatePropagation
+    }
+
+    pub fn stop_immediate_propagation(&mut self) {
+        self.propagation = PropagationMode::StopImmediatePropagation;
+    }
+
+    pub fn phase(&self) -> EventPhase {
+        self.event_phase
+    }
+
+    pub fn set_phase(&mut self, phase: EventPhase) {
+        self.event_phase = phase;
+    }
+
+    pub fn target(&self) -> Option<Object<'gc>> {
+        self.target
+    }
+
+    pub fn set_target(&mut self, target: Object<'gc>) {
+        self.target = Some(target)
+    }
+
+    pub fn current_target(&self) -> Option<Object<'gc>> {
+        self.current_target
+    }
+
+    pub fn set_current_target(&mut self, current_target: Object<'gc>) {
+        self.current_target = Some(current_target)
+    }
+}
+
+/// A set of handlers organized by event type, priority, and order added.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct DispatchList<'gc>(FnvHashMap<AvmString<'gc>, BTreeMap<i32, Vec<EventHandler<'gc>>>>);
+
+impl<'gc> DispatchList<'gc> {
+    /// Construct a new dispatch list.
+    pub fn new() -> Self {
+        Self(Default::default())
+    }
+
+    /// Get all of the event handlers for a given event type, if such a type
+    /// exists.
+    fn get_event(&self, event: AvmString<'gc>) -> Option<&BTreeMap<i32, Vec<EventHandler<'gc>>>> {
+        self.0.get(&event)
+    }
+
+    /// Get a single priority level of event handlers for a given event type,
+    /// for mutation.
+    ///
+    /// If the event type or priority level does not exist, it will be added
+    /// to the dispatch list.
+    fn get_event_priority_mut(
+        &mut self,
+        event: AvmString<'gc>,
+        priority: i32,
+    ) -> &mut Vec<EventHandler<'gc>> {
+        self.0
+            .entry(event)
+            .or_insert_with(BTreeMap::new)
+            .entry(priority)
+            .or_insert_with(Vec::new)
+    }
+
+    /// Add an event handler to this dispatch list.
+    ///
+    /// This is equivalent to `addEventListener` in AS3. The event handler
+    /// will be added to the end of the priority level it is specified in.
+    ///
+    /// Attempts to add the same handler to the same event type and capture
+    /// mode more than once are ignored.
+    pub fn add_event_listener(
+        &mut self,
+        event: impl Into<AvmString<'gc>>,
+        priority: i32,
+        handler: Object<'gc>,
+        use_capture: bool,
+    ) {
+        let event = event.into();
+        let new_handler = EventHandler::new(handler, use_capture);
+
+        if let Some(event_sheaf) = self.get_event(event) {
+            if event_sheaf
+                .values()
+                .any(|set| set.iter().any(|h| h == &new_handler))
+            {
+                return;
+            }
+        }
+
+        self.get_event_priority_mut(event, priority)
+            .push(new_handler);
+    }
+
+    /// Remove an event handler from this dispatch list.
+    ///
+    /// This is equivalent to `removeEventListener` in AS3. Handlers are only
+    /// removed if they were registered with the same capture mode.
+    pub fn remove_event_listener(
+        &mut self,
+        event: impl Into<AvmString<'gc>>,
+        handler: Object<'gc>,
+        use_capture: bool,
+    ) {
+        let old_handler = EventHandler::new(handler, use_capture);
+
+        if let Some(sheaf) = self.0.get_mut(&event.into()) {
+            for set in sheaf.values_mut() {
+                set.retain(|h| h != &old_handler);
+            }
+        }
+    }
+
+    /// Determine if there are any event listeners for a given event type.
+    pub fn has_event_listener(&self, event: impl Into<AvmString<'gc>>) -> bool {
+        if let Some(sheaf) = self.get_event(event.into()) {
+            sheaf.values().any(|set| !set.is_empty())
+        } else {
+            false
+        }
+    }
+
+    /// Yield the event handlers on this dispatch list for a given event, in
+    /// the order they should be called.
+    ///
+    /// Handlers are yielded in descending priority order, and then in the
+    /// order they were added. Only handlers registered for the given capture
+    /// mode are yielded.
+    pub fn iter_event_handlers<'a>(
+        &'a self,
+        event: impl Into<AvmString<'gc>>,
+        use_capture: bool,
+    ) -> impl 'a + Iterator<Item = Object<'gc>> {
+        self.get_event(event.into())
+            .into_iter()
+            .flat_map(|sheaf| sheaf.values().rev())
+            .flatten()
+            .filter(move |eh| eh.use_capture == use_capture)
+            .map(|eh| eh.handler)
+    }
+}
+
+impl<'gc> Default for DispatchList<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single instance of an event handler.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+struct EventHandler<'gc> {
+    /// The event handler to call.
+    handler: Object<'gc>,
+
+    /// Indicates if this handler should only be called for capturing events
+    /// (when `true`), or if it should only be called for bubbling and
+    /// at-target events (when `false`).
+    use_capture: bool,
+}
+
+impl<'gc> EventHandler<'gc> {
+    fn new(handler: Object<'gc>, use_capture: bool) -> Self {
+        Self {
+            handler,
+            use_capture,
+        }
+    }
+}
+
+impl<'gc> PartialEq for EventHandler<'gc> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.use_capture == rhs.use_capture && Object::ptr_eq(self.handler, rhs.handler)
+    }
+}
+
+impl<'gc> Eq for EventHandler<'gc> {}
+
+/// Retrieve the parent of a given `EventDispatcher`.
+///
+/// `EventDispatcher` does not provide a generic way for its subclasses to
+/// indicate ancestry. Instead, only specific event targets provide a hierarchy
+/// to traverse. If no hierarchy is available, this returns `None`, as if the
+/// target had no parent.
+pub fn parent_of(target: Object<'_>) -> Option<Object<'_>> {
+    if let Some(dobj) = target.as_display_object() {
+        if let Some(dparent) = dobj.parent() {
+            if let Value::Object(parent) = dparent.object2() {
+                return Some(parent);
+            }
+        }
+    }
+
+    None
+}
+
+/// Call all of the event handlers on a given target.
+///
+/// The `target` is the current target of the `event`. `event` must be a valid
+/// `EventObject`, or this function will panic. You must have already set the
+/// event's phase to match what targets you are dispatching to, or you will
+/// call the wrong handlers.
+pub fn dispatch_event_to_target<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut target: Object<'gc>,
+    event: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let dispatch_list = match target.get_property(target, &dispatch_list_name(), activation)? {
+        Value::Object(dispatch_list) => dispatch_list,
+        // Objects with no dispatch list act as if they had an empty one
+        _ => return Ok(()),
+    };
+
+    let mut evtmut = event.as_event_mut(activation.context.gc_context).unwrap();
+    let name = evtmut.event_type();
+    let use_capture = evtmut.phase() == EventPhase::Capturing;
+
+    evtmut.set_current_target(target);
+
+    drop(evtmut);
+
+    let handlers: Vec<Object<'gc>> = dispatch_list
+        .as_dispatch()
+        .ok_or_else(|| Error::from("Internal dispatch list is missing during dispatch!"))?
+        .iter_event_handlers(name, use_capture)
+        .collect();
+
+    for handler in handlers.iter() {
+        if event
+            .as_event()
+            .unwrap()
+            .is_propagation_stopped_immediately()
+        {
+            break;
+        }
+
+        handler.call(None, &[event.into()], activation, None)?;
+    }
+
+    Ok(())
+}
+
+/// Dispatch an event on an object.
+///
+/// The event is first captured by each of the target's ancestors, starting
+/// from the root of the display hierarchy; then fired on the target itself;
+/// and then, if it bubbles, fired on each ancestor again in reverse order.
+///
+/// This function returns if the event was cancelled or not.
+pub fn dispatch_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    event: Object<'gc>,
+) -> Result<bool, Error<'gc>> {
+    let target = this;
+
+    let mut ancestor_list = Vec::new();
+    let mut parent = parent_of(target);
+    while let Some(par) = parent {
+        ancestor_list.push(par);
+        parent = parent_of(par);
+    }
+
+    let mut evtmut = event.as_event_mut(activation.context.gc_context).unwrap();
+
+    evtmut.set_phase(EventPhase::Capturing);
+    evtmut.set_target(target);
+
+    drop(evtmut);
+
+    for ancestor in ancestor_list.iter().rev() {
+        if event.as_event().unwrap().is_propagation_stopped() {
+            break;
+        }
+
+        dispatch_event_to_target(activation, *ancestor, event)?;
+    }
+
+    event
+        .as_event_mut(activation.context.gc_context)
+        .unwrap()
+        .set_phase(EventPhase::AtTarget);
+
+    if !event.as_event().unwrap().is_propagation_stopped() {
+        dispatch_event_to_target(activation, target, event)?;
+    }
+
+    event
+        .as_event_mut(activation.context.gc_context)
+        .unwrap()
+        .set_phase(EventPhase::Bubbling);
+
+    if event.as_event().unwrap().is_bubbling() {
+        for ancestor in ancestor_list.iter() {
+            if event.as_event().unwrap().is_propagation_stopped() {
+                break;
+            }
+
+            dispatch_event_to_target(activation, *ancestor, event)?;
+        }
+    }
+
+    let cancelled = event.as_event().unwrap().is_cancelled();
+
+    Ok(cancelled)
+}
+
+/// The name of the hidden property that holds an object's `DispatchList`.
+pub fn dispatch_list_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::Private("ruffle".into()), "dispatch_list")
+}
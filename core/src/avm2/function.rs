@@ -83,7 +83,7 @@ impl<'gc> Executable<'gc> {
         arguments: &[Value<'gc>],
         activation: &mut Activation<'_, 'gc, '_>,
         base_proto: Option<Object<'gc>>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         match self {
             Executable::Native(nf, receiver) => {
                 nf(activation, receiver.or(unbound_reciever), arguments)
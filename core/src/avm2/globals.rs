@@ -6,8 +6,9 @@ use crate::avm2::domain::Domain;
 use crate::avm2::method::NativeMethod;
 use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{
-    implicit_deriver, ArrayObject, DomainObject, FunctionObject, NamespaceObject, Object,
-    PrimitiveObject, ScriptObject, StageObject, TObject,
+    implicit_deriver, ArrayObject, BitmapDataObject, ByteArrayObject, DateObject, DictionaryObject,
+    DomainObject, EventObject, FunctionObject, NamespaceObject, Object, PrimitiveObject,
+    RegExpObject, ScriptObject, StageObject, TObject, VectorObject, XmlListObject, XmlObject,
 };
 use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
@@ -20,22 +21,29 @@ use std::f64::NAN;
 mod array;
 mod boolean;
 mod class;
+mod date;
+mod error;
 mod flash;
 mod function;
 mod global_scope;
 mod int;
+mod json;
 mod math;
 mod namespace;
 mod number;
 mod object;
+mod regexp;
 mod string;
 mod r#uint;
+pub mod vector;
+mod xml;
+mod xml_list;
 
 fn trace<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     let mut message = String::new();
     if !args.is_empty() {
         message.push_str(&args[0].clone().coerce_to_string(activation)?);
@@ -65,10 +73,30 @@ pub struct SystemPrototypes<'gc> {
     pub uint: Object<'gc>,
     pub namespace: Object<'gc>,
     pub array: Object<'gc>,
+    pub vector: Object<'gc>,
+    pub regexp: Object<'gc>,
+    pub date: Object<'gc>,
+    pub xml: Object<'gc>,
+    pub xml_list: Object<'gc>,
+    pub dictionary: Object<'gc>,
+    pub bytearray: Object<'gc>,
     pub movieclip: Object<'gc>,
     pub framelabel: Object<'gc>,
     pub scene: Object<'gc>,
     pub application_domain: Object<'gc>,
+    pub display_object: Object<'gc>,
+    pub shape: Object<'gc>,
+    pub graphics: Object<'gc>,
+    pub bitmap: Object<'gc>,
+    pub bitmapdata: Object<'gc>,
+    pub simplebutton: Object<'gc>,
+    pub stage: Object<'gc>,
+    pub loader: Object<'gc>,
+    pub loaderinfo: Object<'gc>,
+    pub textfield: Object<'gc>,
+    pub event: Object<'gc>,
+    pub mouse_event: Object<'gc>,
+    pub keyboard_event: Object<'gc>,
 }
 
 impl<'gc> SystemPrototypes<'gc> {
@@ -97,10 +125,30 @@ impl<'gc> SystemPrototypes<'gc> {
             uint: empty,
             namespace: empty,
             array: empty,
+            vector: empty,
+            regexp: empty,
+            date: empty,
+            xml: empty,
+            xml_list: empty,
+            dictionary: empty,
+            bytearray: empty,
             movieclip: empty,
             framelabel: empty,
             scene: empty,
             application_domain: empty,
+            display_object: empty,
+            shape: empty,
+            graphics: empty,
+            bitmap: empty,
+            bitmapdata: empty,
+            simplebutton: empty,
+            stage: empty,
+            loader: empty,
+            loaderinfo: empty,
+            textfield: empty,
+            event: empty,
+            mouse_event: empty,
+            keyboard_event: empty,
         }
     }
 }
@@ -114,7 +162,7 @@ fn function<'gc>(
     fn_proto: Object<'gc>,
     mut domain: Domain<'gc>,
     script: Script<'gc>,
-) -> Result<(), Error> {
+) -> Result<(), Error<'gc>> {
     let name = QName::new(Namespace::package(package), name);
     let as3fn = FunctionObject::from_builtin(mc, nf, fn_proto).into();
     domain.export_definition(name.clone(), script, mc)?;
@@ -138,7 +186,7 @@ fn dynamic_class<'gc>(
     class: GcCell<'gc, Class<'gc>>,
     mut domain: Domain<'gc>,
     script: Script<'gc>,
-) -> Result<(), Error> {
+) -> Result<(), Error<'gc>> {
     let name = class.read().name().clone();
 
     script
@@ -160,14 +208,14 @@ fn class<'gc, Deriver>(
     custom_derive: Deriver,
     mut domain: Domain<'gc>,
     script: Script<'gc>,
-) -> Result<Object<'gc>, Error>
+) -> Result<Object<'gc>, Error<'gc>>
 where
     Deriver: FnOnce(
         Object<'gc>,
         &mut Activation<'_, 'gc, '_>,
         GcCell<'gc, Class<'gc>>,
         Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error>,
+    ) -> Result<Object<'gc>, Error<'gc>>,
 {
     let mut global = script.init().1;
     let global_scope = Scope::push_scope(global.get_scope(), global, activation.context.gc_context);
@@ -178,7 +226,7 @@ where
             .resolve_multiname(sc_name)?
             .unwrap_or_else(|| QName::dynamic_name("Object"));
 
-        let super_class: Result<Object<'gc>, Error> = global
+        let super_class: Result<Object<'gc>, Error<'gc>> = global
             .get_property(global, &super_name, activation)?
             .coerce_to_object(activation)
             .map_err(|_e| {
@@ -223,7 +271,7 @@ fn primitive_deriver<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     class: GcCell<'gc, Class<'gc>>,
     scope: Option<GcCell<'gc, Scope<'gc>>>,
-) -> Result<Object<'gc>, Error> {
+) -> Result<Object<'gc>, Error<'gc>> {
     PrimitiveObject::derive(base_proto, activation.context.gc_context, class, scope)
 }
 
@@ -232,7 +280,7 @@ fn namespace_deriver<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     class: GcCell<'gc, Class<'gc>>,
     scope: Option<GcCell<'gc, Scope<'gc>>>,
-) -> Result<Object<'gc>, Error> {
+) -> Result<Object<'gc>, Error<'gc>> {
     NamespaceObject::derive(base_proto, activation.context.gc_context, class, scope)
 }
 
@@ -241,25 +289,113 @@ fn array_deriver<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     class: GcCell<'gc, Class<'gc>>,
     scope: Option<GcCell<'gc, Scope<'gc>>>,
-) -> Result<Object<'gc>, Error> {
+) -> Result<Object<'gc>, Error<'gc>> {
     ArrayObject::derive(base_proto, activation.context.gc_context, class, scope)
 }
 
+fn vector_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    VectorObject::derive(
+        base_proto,
+        activation.context.gc_context,
+        class,
+        scope,
+        None,
+        Value::Undefined,
+    )
+}
+
+fn regexp_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    RegExpObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
+fn date_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    DateObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
+fn xml_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    XmlObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
+fn xml_list_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    XmlListObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
+fn dictionary_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    DictionaryObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
+fn bytearray_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    ByteArrayObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
+fn bitmapdata_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    BitmapDataObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
 fn stage_deriver<'gc>(
     base_proto: Object<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
     class: GcCell<'gc, Class<'gc>>,
     scope: Option<GcCell<'gc, Scope<'gc>>>,
-) -> Result<Object<'gc>, Error> {
+) -> Result<Object<'gc>, Error<'gc>> {
     StageObject::derive(base_proto, activation.context.gc_context, class, scope)
 }
 
+fn event_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    EventObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
 fn appdomain_deriver<'gc>(
     base_proto: Object<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
     class: GcCell<'gc, Class<'gc>>,
     scope: Option<GcCell<'gc, Scope<'gc>>>,
-) -> Result<Object<'gc>, Error> {
+) -> Result<Object<'gc>, Error<'gc>> {
     let domain = scope
         .unwrap()
         .read()
@@ -284,7 +420,7 @@ fn constant<'gc>(
     value: Value<'gc>,
     mut domain: Domain<'gc>,
     script: Script<'gc>,
-) -> Result<(), Error> {
+) -> Result<(), Error<'gc>> {
     let name = QName::new(Namespace::package(package), name);
     domain.export_definition(name.clone(), script, mc)?;
     script.init().1.install_const(mc, name, 0, value);
@@ -301,7 +437,7 @@ fn constant<'gc>(
 pub fn load_player_globals<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     domain: Domain<'gc>,
-) -> Result<(), Error> {
+) -> Result<(), Error<'gc>> {
     let mc = activation.context.gc_context;
     let gs = DomainObject::from_domain(mc, None, domain);
     let script = Script::empty_script(mc, gs);
@@ -391,6 +527,43 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    sp.regexp = class(
+        activation,
+        regexp::create_class(mc),
+        regexp_deriver,
+        domain,
+        script,
+    )?;
+    sp.date = class(
+        activation,
+        date::create_class(mc),
+        date_deriver,
+        domain,
+        script,
+    )?;
+    sp.xml = class(
+        activation,
+        xml::create_class(mc),
+        xml_deriver,
+        domain,
+        script,
+    )?;
+    sp.xml_list = class(
+        activation,
+        xml_list::create_class(mc),
+        xml_list_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `__AS3__.vec`
+    sp.vector = class(
+        activation,
+        vector::create_class(mc),
+        vector_deriver,
+        domain,
+        script,
+    )?;
 
     // At this point we have to hide the fact that we had to create the player
     // globals scope *before* the `Object` class
@@ -404,6 +577,33 @@ pub fn load_player_globals<'gc>(
     constant(mc, "", "NaN", NAN.into(), domain, script)?;
     constant(mc, "", "Infinity", f64::INFINITY.into(), domain, script)?;
 
+    let mut error_proto = class(
+        activation,
+        error::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    error_proto.install_dynamic_property(
+        mc,
+        QName::new(Namespace::public_namespace(), "name"),
+        "Error".into(),
+    )?;
+    for (package, name, super_package, super_name) in error::SUBCLASSES {
+        let mut proto = class(
+            activation,
+            error::create_subclass(mc, package, name, super_package, super_name),
+            implicit_deriver,
+            domain,
+            script,
+        )?;
+        proto.install_dynamic_property(
+            mc,
+            QName::new(Namespace::public_namespace(), "name"),
+            (*name).into(),
+        )?;
+    }
+
     class(
         activation,
         math::create_class(mc),
@@ -411,6 +611,55 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    class(
+        activation,
+        json::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.utils`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .dictionary = class(
+        activation,
+        flash::utils::dictionary::create_class(mc),
+        dictionary_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .bytearray = class(
+        activation,
+        flash::utils::bytearray::create_class(mc),
+        bytearray_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::utils::endian::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::utils::compression_algorithm::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
 
     // package `flash.system`
     activation
@@ -442,9 +691,68 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .event = class(
+        activation,
+        flash::events::event::create_class(mc),
+        event_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .mouse_event = class(
+        activation,
+        flash::events::mouseevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .keyboard_event = class(
+        activation,
+        flash::events::keyboardevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::events::timerevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::events::progressevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
 
     // package `flash.display`
-    class(
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .display_object = class(
         activation,
         flash::display::displayobject::create_class(mc),
         stage_deriver,
@@ -511,6 +819,125 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .shape = class(
+        activation,
+        flash::display::shape::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .graphics = class(
+        activation,
+        flash::display::graphics::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .bitmap = class(
+        activation,
+        flash::display::bitmap::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .bitmapdata = class(
+        activation,
+        flash::display::bitmapdata::create_class(mc),
+        bitmapdata_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .simplebutton = class(
+        activation,
+        flash::display::simplebutton::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .stage = class(
+        activation,
+        flash::display::stage::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .loader = class(
+        activation,
+        flash::display::loader::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .loaderinfo = class(
+        activation,
+        flash::display::loaderinfo::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.text`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .textfield = class(
+        activation,
+        flash::text::textfield::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
 
     Ok(())
 }
@@ -20,7 +20,7 @@ pub fn instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
             if args.len() == 1 {
@@ -52,7 +52,7 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
@@ -61,7 +61,7 @@ pub fn length<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(array) = this.as_array_storage() {
             return Ok(array.length().into());
@@ -75,7 +75,7 @@ pub fn length<'gc>(
 pub fn build_array<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     array: ArrayStorage<'gc>,
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(ArrayObject::from_array(
         array,
         activation
@@ -96,7 +96,7 @@ pub fn concat<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     let mut base_array = this
         .and_then(|this| this.as_array_storage().map(|a| a.clone()))
         .unwrap_or_else(|| ArrayStorage::new(0));
@@ -118,7 +118,7 @@ fn resolve_array_hole<'gc>(
     this: Object<'gc>,
     i: usize,
     item: Option<Value<'gc>>,
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     item.map(Ok).unwrap_or_else(|| {
         this.proto()
             .map(|mut p| {
@@ -140,9 +140,12 @@ pub fn join_inner<'gc, 'a, 'ctxt, C>(
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
     mut conv: C,
-) -> Result<Value<'gc>, Error>
+) -> Result<Value<'gc>, Error<'gc>>
 where
-    C: for<'b> FnMut(Value<'gc>, &'b mut Activation<'a, 'gc, 'ctxt>) -> Result<Value<'gc>, Error>,
+    C: for<'b> FnMut(
+        Value<'gc>,
+        &'b mut Activation<'a, 'gc, 'ctxt>,
+    ) -> Result<Value<'gc>, Error<'gc>>,
 {
     let mut separator = args.get(0).cloned().unwrap_or(Value::Undefined);
     if separator == Value::Undefined {
@@ -184,7 +187,7 @@ pub fn join<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     join_inner(activation, this, args, |v, _act| Ok(v))
 }
 
@@ -193,7 +196,7 @@ pub fn to_string<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     join_inner(activation, this, &[",".into()], |v, _act| Ok(v))
 }
 
@@ -202,7 +205,7 @@ pub fn to_locale_string<'gc>(
     act: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     join_inner(act, this, &[",".into()], |v, activation| {
         let mut o = v.coerce_to_object(activation)?;
 
@@ -222,7 +225,7 @@ pub fn value_of<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     join_inner(activation, this, &[",".into()], |v, _act| Ok(v))
 }
 
@@ -244,7 +247,7 @@ pub fn value_of<'gc>(
 /// mutate the array under iteration. Normally, holding an `Iterator` on the
 /// array while this happens would cause a panic; this code exists to prevent
 /// that.
-struct ArrayIter<'gc> {
+pub struct ArrayIter<'gc> {
     array_object: Object<'gc>,
     index: u32,
     length: u32,
@@ -255,7 +258,7 @@ impl<'gc> ArrayIter<'gc> {
     pub fn new(
         activation: &mut Activation<'_, 'gc, '_>,
         mut array_object: Object<'gc>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, Error<'gc>> {
         let length = array_object
             .get_property(
                 array_object,
@@ -275,10 +278,10 @@ impl<'gc> ArrayIter<'gc> {
     ///
     /// Since this isn't a real iterator, this comes pre-enumerated; it yields
     /// a pair of the index and then the value.
-    fn next(
+    pub fn next(
         &mut self,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Option<Result<(u32, Value<'gc>), Error>> {
+    ) -> Option<Result<(u32, Value<'gc>), Error<'gc>>> {
         if self.index < self.length {
             let i = self.index;
 
@@ -307,7 +310,7 @@ pub fn for_each<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let callback = args
             .get(0)
@@ -342,7 +345,7 @@ pub fn map<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let callback = args
             .get(0)
@@ -381,7 +384,7 @@ pub fn filter<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let callback = args
             .get(0)
@@ -424,7 +427,7 @@ pub fn every<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let callback = args
             .get(0)
@@ -464,7 +467,7 @@ pub fn some<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let callback = args
             .get(0)
@@ -504,7 +507,7 @@ pub fn index_of<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(array) = this.as_array_storage() {
             let search_val = args.get(0).cloned().unwrap_or(Value::Undefined);
@@ -533,7 +536,7 @@ pub fn last_index_of<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(array) = this.as_array_storage() {
             let search_val = args.get(0).cloned().unwrap_or(Value::Undefined);
@@ -562,7 +565,7 @@ pub fn pop<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
             return Ok(array.pop());
@@ -577,7 +580,7 @@ pub fn push<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
             for arg in args {
@@ -593,7 +596,7 @@ pub fn reverse<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
             let mut last_non_hole_index = None;
@@ -631,7 +634,7 @@ pub fn shift<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
             return Ok(array.shift());
@@ -646,7 +649,7 @@ pub fn unshift<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
             for arg in args.iter().rev() {
@@ -663,7 +666,7 @@ pub fn resolve_index<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     index: Value<'gc>,
     length: usize,
-) -> Result<usize, Error> {
+) -> Result<usize, Error<'gc>> {
     let index = index.coerce_to_i32(activation)?;
 
     Ok(if index < 0 {
@@ -678,7 +681,7 @@ pub fn slice<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let array_length = this.as_array_storage().map(|a| a.length());
 
@@ -719,7 +722,7 @@ pub fn splice<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let array_length = this.as_array_storage().map(|a| a.length());
 
@@ -777,7 +780,7 @@ pub fn splice<'gc>(
 ///
 /// These are provided as a number by the VM and converted into an enumset.
 #[derive(EnumSetType)]
-enum SortOptions {
+pub enum SortOptions {
     /// Request case-insensitive string value sort.
     CaseInsensitive,
 
@@ -796,9 +799,13 @@ enum SortOptions {
 
 /// Identity closure shim which exists purely to decorate closure types with
 /// the HRTB necessary to accept an activation.
-fn constrain<'a, 'gc, 'ctxt, F>(f: F) -> F
+pub fn constrain<'a, 'gc, 'ctxt, F>(f: F) -> F
 where
-    F: FnMut(&mut Activation<'a, 'gc, 'ctxt>, Value<'gc>, Value<'gc>) -> Result<Ordering, Error>,
+    F: FnMut(
+        &mut Activation<'a, 'gc, 'ctxt>,
+        Value<'gc>,
+        Value<'gc>,
+    ) -> Result<Ordering, Error<'gc>>,
 {
     f
 }
@@ -816,14 +823,18 @@ where
 /// this case, you should cancel the in-place sorting operation and return 0 to
 /// the caller. In the event that this function yields a runtime error, the
 /// contents of the `values` array will be sorted in a random order.
-fn sort_inner<'a, 'gc, 'ctxt, C>(
+pub fn sort_inner<'a, 'gc, 'ctxt, C>(
     activation: &mut Activation<'a, 'gc, 'ctxt>,
     values: &mut [(usize, Value<'gc>)],
     options: EnumSet<SortOptions>,
     mut sort_func: C,
-) -> Result<bool, Error>
+) -> Result<bool, Error<'gc>>
 where
-    C: FnMut(&mut Activation<'a, 'gc, 'ctxt>, Value<'gc>, Value<'gc>) -> Result<Ordering, Error>,
+    C: FnMut(
+        &mut Activation<'a, 'gc, 'ctxt>,
+        Value<'gc>,
+        Value<'gc>,
+    ) -> Result<Ordering, Error<'gc>>,
 {
     let mut unique_sort_satisfied = true;
     let mut error_signal = Ok(());
@@ -860,33 +871,33 @@ where
     Ok(!options.contains(SortOptions::UniqueSort) || unique_sort_satisfied)
 }
 
-fn compare_string_case_sensitive<'gc>(
+pub fn compare_string_case_sensitive<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     a: Value<'gc>,
     b: Value<'gc>,
-) -> Result<Ordering, Error> {
+) -> Result<Ordering, Error<'gc>> {
     let string_a = a.coerce_to_string(activation)?;
     let string_b = b.coerce_to_string(activation)?;
 
     Ok(string_a.cmp(&string_b))
 }
 
-fn compare_string_case_insensitive<'gc>(
+pub fn compare_string_case_insensitive<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     a: Value<'gc>,
     b: Value<'gc>,
-) -> Result<Ordering, Error> {
+) -> Result<Ordering, Error<'gc>> {
     let string_a = a.coerce_to_string(activation)?.to_lowercase();
     let string_b = b.coerce_to_string(activation)?.to_lowercase();
 
     Ok(string_a.cmp(&string_b))
 }
 
-fn compare_numeric<'gc>(
+pub fn compare_numeric<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     a: Value<'gc>,
     b: Value<'gc>,
-) -> Result<Ordering, Error> {
+) -> Result<Ordering, Error<'gc>> {
     let num_a = a.coerce_to_number(activation)?;
     let num_b = b.coerce_to_number(activation)?;
 
@@ -908,7 +919,7 @@ fn sort_postprocess<'gc>(
     options: EnumSet<SortOptions>,
     unique_satisfied: bool,
     values: Vec<(usize, Value<'gc>)>,
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if unique_satisfied {
         if options.contains(SortOptions::ReturnIndexedArray) {
             return build_array(
@@ -950,7 +961,7 @@ fn sort_postprocess<'gc>(
 fn extract_array_values<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     value: Value<'gc>,
-) -> Result<Option<Vec<Value<'gc>>>, Error> {
+) -> Result<Option<Vec<Value<'gc>>>, Error<'gc>> {
     let object = value.coerce_to_object(activation).ok();
     let holey_vec = if let Some(object) = object {
         if let Some(field_array) = object.as_array_storage() {
@@ -986,7 +997,7 @@ pub fn sort<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let (compare_fnc, options) = if args.len() > 1 {
             (
@@ -1072,7 +1083,7 @@ pub fn sort<'gc>(
 fn extract_maybe_array_values<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     value: Value<'gc>,
-) -> Result<Vec<Value<'gc>>, Error> {
+) -> Result<Vec<Value<'gc>>, Error<'gc>> {
     Ok(extract_array_values(activation, value.clone())?.unwrap_or_else(|| vec![value]))
 }
 
@@ -1085,7 +1096,7 @@ fn extract_maybe_array_values<'gc>(
 fn extract_maybe_array_strings<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     value: Value<'gc>,
-) -> Result<Vec<AvmString<'gc>>, Error> {
+) -> Result<Vec<AvmString<'gc>>, Error<'gc>> {
     let mut out = Vec::new();
 
     for value in extract_maybe_array_values(activation, value)? {
@@ -1104,7 +1115,7 @@ fn extract_maybe_array_strings<'gc>(
 fn extract_maybe_array_enumsets<'gc, E>(
     activation: &mut Activation<'_, 'gc, '_>,
     value: Value<'gc>,
-) -> Result<Vec<EnumSet<E>>, Error>
+) -> Result<Vec<EnumSet<E>>, Error<'gc>>
 where
     E: EnumSetType,
 {
@@ -1122,7 +1133,7 @@ pub fn sort_on<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(field_names_value) = args.get(0).cloned() {
             let field_names = extract_maybe_array_strings(activation, field_names_value)?;
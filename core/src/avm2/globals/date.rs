@@ -0,0 +1,898 @@
+//! `Date` impl
+//!
+//! Dates are stored as a number of milliseconds since the epoch, as in
+//! ECMAScript, and all calendar calculations are done on that value rather
+//! than through `chrono`, since AS3 dates may lie far outside the range that
+//! `chrono` supports and may also be `NaN`.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+const MS_PER_DAY: f64 = 86_400_000.0;
+const MS_PER_HOUR: f64 = 3_600_000.0;
+const MS_PER_MINUTE: f64 = 60_000.0;
+const MS_PER_SECOND: f64 = 1_000.0;
+
+const DAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// The first day of each month in a common year.
+const MONTH_STARTS: [f64; 13] = [
+    0.0, 31.0, 59.0, 90.0, 120.0, 151.0, 181.0, 212.0, 243.0, 273.0, 304.0, 334.0, 365.0,
+];
+
+/// The calendar components of a time value, in the order that the `set`
+/// methods accept them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Component {
+    FullYear = 0,
+    Month = 1,
+    Date = 2,
+    Hours = 3,
+    Minutes = 4,
+    Seconds = 5,
+    Milliseconds = 6,
+}
+
+fn day(t: f64) -> f64 {
+    (t / MS_PER_DAY).floor()
+}
+
+fn time_within_day(t: f64) -> f64 {
+    t.rem_euclid(MS_PER_DAY)
+}
+
+fn days_in_year(y: f64) -> f64 {
+    if y % 4.0 != 0.0 {
+        365.0
+    } else if y % 100.0 != 0.0 {
+        366.0
+    } else if y % 400.0 != 0.0 {
+        365.0
+    } else {
+        366.0
+    }
+}
+
+fn day_from_year(y: f64) -> f64 {
+    365.0 * (y - 1970.0) + ((y - 1969.0) / 4.0).floor() - ((y - 1901.0) / 100.0).floor()
+        + ((y - 1601.0) / 400.0).floor()
+}
+
+fn time_from_year(y: f64) -> f64 {
+    MS_PER_DAY * day_from_year(y)
+}
+
+fn year_from_time(t: f64) -> f64 {
+    let mut y = (t / (MS_PER_DAY * 365.2425)).floor() + 1970.0;
+
+    while time_from_year(y) > t {
+        y -= 1.0;
+    }
+
+    while time_from_year(y + 1.0) <= t {
+        y += 1.0;
+    }
+
+    y
+}
+
+/// The first day of a month, relative to the start of the year.
+fn month_start(month: usize, leap: bool) -> f64 {
+    if leap && month >= 2 {
+        MONTH_STARTS[month] + 1.0
+    } else {
+        MONTH_STARTS[month]
+    }
+}
+
+#[allow(clippy::float_cmp)]
+fn month_from_time(t: f64) -> f64 {
+    let year = year_from_time(t);
+    let leap = days_in_year(year) == 366.0;
+    let day_in_year = day(t) - day_from_year(year);
+
+    (0..12)
+        .rev()
+        .find(|m| day_in_year >= month_start(*m, leap))
+        .unwrap_or(0) as f64
+}
+
+#[allow(clippy::float_cmp)]
+fn date_from_time(t: f64) -> f64 {
+    let year = year_from_time(t);
+    let leap = days_in_year(year) == 366.0;
+    let day_in_year = day(t) - day_from_year(year);
+
+    day_in_year - month_start(month_from_time(t) as usize, leap) + 1.0
+}
+
+fn week_day(t: f64) -> f64 {
+    (day(t) + 4.0).rem_euclid(7.0)
+}
+
+fn make_time(hour: f64, min: f64, sec: f64, ms: f64) -> f64 {
+    if !hour.is_finite() || !min.is_finite() || !sec.is_finite() || !ms.is_finite() {
+        return f64::NAN;
+    }
+
+    hour.trunc() * MS_PER_HOUR
+        + min.trunc() * MS_PER_MINUTE
+        + sec.trunc() * MS_PER_SECOND
+        + ms.trunc()
+}
+
+#[allow(clippy::float_cmp)]
+fn make_day(year: f64, month: f64, date: f64) -> f64 {
+    if !year.is_finite() || !month.is_finite() || !date.is_finite() {
+        return f64::NAN;
+    }
+
+    let year = year.trunc() + (month.trunc() / 12.0).floor();
+    let month = month.trunc().rem_euclid(12.0) as usize;
+    let leap = days_in_year(year) == 366.0;
+
+    day_from_year(year) + month_start(month, leap) + date.trunc() - 1.0
+}
+
+fn make_date(day: f64, time: f64) -> f64 {
+    if !day.is_finite() || !time.is_finite() {
+        return f64::NAN;
+    }
+
+    day * MS_PER_DAY + time
+}
+
+fn time_clip(time: f64) -> f64 {
+    if !time.is_finite() || time.abs() > 8.64e15 {
+        f64::NAN
+    } else {
+        time.trunc() + 0.0
+    }
+}
+
+/// The offset of local time from UTC, in milliseconds.
+fn local_tza(activation: &mut Activation<'_, '_, '_>) -> f64 {
+    activation.context.locale.get_timezone().local_minus_utc() as f64 * MS_PER_SECOND
+}
+
+/// Split a time value into its calendar components.
+fn decompose(t: f64) -> [f64; 7] {
+    let time = time_within_day(t);
+
+    [
+        year_from_time(t),
+        month_from_time(t),
+        date_from_time(t),
+        (time / MS_PER_HOUR).floor(),
+        (time / MS_PER_MINUTE).floor() % 60.0,
+        (time / MS_PER_SECOND).floor() % 60.0,
+        time % MS_PER_SECOND,
+    ]
+}
+
+/// Join calendar components back into a time value.
+fn compose(c: &[f64; 7]) -> f64 {
+    make_date(
+        make_day(c[0], c[1], c[2]),
+        make_time(c[3], c[4], c[5], c[6]),
+    )
+}
+
+/// Build a UTC time value out of constructor-style arguments.
+///
+/// Two-digit years are taken to be in the twentieth century.
+fn time_from_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<f64, Error<'gc>> {
+    let mut components = [f64::NAN, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+
+    for (i, arg) in args.iter().take(7).enumerate() {
+        components[i] = arg.clone().coerce_to_number(activation)?;
+    }
+
+    let year = components[0];
+    if year.is_finite() && (0.0..=99.0).contains(&year.trunc()) {
+        components[0] = 1900.0 + year.trunc();
+    }
+
+    Ok(compose(&components))
+}
+
+/// Format a timezone offset in milliseconds as `+hhmm`.
+fn format_offset(tza: f64) -> String {
+    let minutes = (tza / MS_PER_MINUTE) as i64;
+    let sign = if minutes < 0 { '-' } else { '+' };
+
+    format!("{}{:02}{:02}", sign, minutes.abs() / 60, minutes.abs() % 60)
+}
+
+fn format_date(t: f64) -> String {
+    format!(
+        "{} {} {} {}",
+        DAY_NAMES[week_day(t) as usize],
+        MONTH_NAMES[month_from_time(t) as usize],
+        date_from_time(t),
+        year_from_time(t)
+    )
+}
+
+fn format_time(t: f64) -> String {
+    let c = decompose(t);
+
+    format!("{:02}:{:02}:{:02}", c[3], c[4], c[5])
+}
+
+/// Format a local time value with its timezone offset, as `Date.toString`
+/// does.
+fn format_full(t: f64, tza: f64) -> String {
+    format!(
+        "{} {} {} {} GMT{} {}",
+        DAY_NAMES[week_day(t) as usize],
+        MONTH_NAMES[month_from_time(t) as usize],
+        date_from_time(t),
+        format_time(t),
+        format_offset(tza),
+        year_from_time(t)
+    )
+}
+
+fn format_locale_time(t: f64) -> String {
+    let c = decompose(t);
+    let (hour, meridian) = match c[3] as u32 {
+        0 => (12, "AM"),
+        h @ 1..=11 => (h, "AM"),
+        12 => (12, "PM"),
+        h => (h - 12, "PM"),
+    };
+
+    format!("{:02}:{:02}:{:02} {}", hour, c[4], c[5], meridian)
+}
+
+/// Parse a date string, as with `Date.parse`.
+///
+/// This accepts the formats that `Date.toString` and `Date.toUTCString`
+/// produce, as well as `MM/DD/YYYY` dates, with an optional time and
+/// `GMT+hhmm` or `UTC` timezone in any order. Strings without a timezone are
+/// taken to be local time.
+#[allow(clippy::float_cmp)]
+fn parse_date(activation: &mut Activation<'_, '_, '_>, string: &str) -> f64 {
+    let mut year = None;
+    let mut month = None;
+    let mut date = None;
+    let mut time = [0.0, 0.0, 0.0];
+    let mut tza = None;
+    let mut pm = None;
+
+    for token in string.split(|c: char| c.is_whitespace() || c == ',') {
+        if token.is_empty() {
+            continue;
+        }
+
+        let upper = token.to_ascii_uppercase();
+
+        if let Some(m) = MONTH_NAMES
+            .iter()
+            .position(|m| upper.starts_with(&m.to_ascii_uppercase()))
+        {
+            month = Some(m as f64);
+        } else if DAY_NAMES
+            .iter()
+            .any(|d| upper.starts_with(&d.to_ascii_uppercase()))
+        {
+            continue;
+        } else if upper == "AM" || upper == "PM" {
+            pm = Some(upper == "PM");
+        } else if upper.starts_with("GMT") || upper.starts_with("UTC") {
+            let offset = &upper[3..];
+            let (sign, digits) = match offset.chars().next() {
+                Some('+') => (1.0, &offset[1..]),
+                Some('-') => (-1.0, &offset[1..]),
+                Some(_) => return f64::NAN,
+                None => (1.0, ""),
+            };
+
+            if digits.is_empty() {
+                tza = Some(0.0);
+            } else if let Ok(hhmm) = digits.parse::<u32>() {
+                let minutes = (hhmm / 100) * 60 + hhmm % 100;
+                tza = Some(sign * minutes as f64 * MS_PER_MINUTE);
+            } else {
+                return f64::NAN;
+            }
+        } else if token.contains(':') {
+            for (i, part) in token.split(':').take(3).enumerate() {
+                match part.parse::<f64>() {
+                    Ok(value) => time[i] = value,
+                    Err(_) => return f64::NAN,
+                }
+            }
+        } else if token.contains('/') {
+            let parts: Vec<Result<f64, _>> = token.split('/').map(|p| p.parse::<f64>()).collect();
+
+            match parts.as_slice() {
+                [Ok(m), Ok(d), Ok(y)] => {
+                    month = Some(m - 1.0);
+                    date = Some(*d);
+                    year = Some(*y);
+                }
+                _ => return f64::NAN,
+            }
+        } else if let Ok(value) = token.parse::<f64>() {
+            if date.is_none() && value <= 31.0 && token.len() <= 2 {
+                date = Some(value);
+            } else {
+                year = Some(value);
+            }
+        } else {
+            return f64::NAN;
+        }
+    }
+
+    let (year, month, date) = match (year, month, date) {
+        (Some(year), Some(month), Some(date)) => (year, month, date),
+        _ => return f64::NAN,
+    };
+
+    let mut hours = time[0];
+    match pm {
+        Some(true) if hours < 12.0 => hours += 12.0,
+        Some(false) if hours == 12.0 => hours = 0.0,
+        _ => {}
+    }
+
+    let local = make_date(
+        make_day(year, month, date),
+        make_time(hours, time[1], time[2], 0.0),
+    );
+    let tza = tza.unwrap_or_else(|| local_tza(activation));
+
+    time_clip(local - tza)
+}
+
+/// Retrieve the time value of a `Date`.
+fn time_of(this: Option<Object<'_>>) -> Option<f64> {
+    this.and_then(|this| this.as_date())
+}
+
+/// Implements `Date`'s instance initializer.
+///
+/// When called as a function, this instead returns the current time as a
+/// string.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let now = activation
+        .context
+        .locale
+        .get_current_date_time()
+        .timestamp_millis() as f64;
+
+    let this = match this {
+        Some(this) => this,
+        None => {
+            let tza = local_tza(activation);
+
+            return Ok(
+                AvmString::new(activation.context.gc_context, format_full(now + tza, tza)).into(),
+            );
+        }
+    };
+
+    let time = match args {
+        [] => now,
+        [Value::Object(o)] if o.as_date().is_some() => o.as_date().unwrap(),
+        [value @ Value::String(_)] => {
+            let string = value.clone().coerce_to_string(activation)?;
+
+            parse_date(activation, &string)
+        }
+        [value] => time_clip(value.clone().coerce_to_number(activation)?),
+        args => {
+            let local = time_from_args(activation, args)?;
+
+            time_clip(local - local_tza(activation))
+        }
+    };
+
+    this.set_date(activation.context.gc_context, time);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Date`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Date.UTC`
+pub fn utc<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(time_clip(time_from_args(activation, args)?).into())
+}
+
+/// Implements `Date.parse`
+pub fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let string = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    Ok(parse_date(activation, &string).into())
+}
+
+/// Implements `Date.time` and `Date.getTime`
+pub fn get_time<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(time_of(this).map(Value::from).unwrap_or(Value::Undefined))
+}
+
+/// Implements `Date.time`'s setter and `Date.setTime`
+pub fn set_time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let time = time_clip(
+            args.get(0)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_number(activation)?,
+        );
+
+        this.set_date(activation.context.gc_context, time);
+
+        return Ok(time.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Date.timezoneOffset` and `Date.getTimezoneOffset`
+pub fn get_timezone_offset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match time_of(this) {
+        Some(t) if t.is_nan() => Ok(f64::NAN.into()),
+        Some(_) => Ok((-local_tza(activation) / MS_PER_MINUTE).into()),
+        None => Ok(Value::Undefined),
+    }
+}
+
+/// Retrieve a calendar component of a date, in local time or UTC.
+fn get_component<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    component: Component,
+    is_utc: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let t = match time_of(this) {
+        Some(t) if t.is_nan() => return Ok(f64::NAN.into()),
+        Some(t) if is_utc => t,
+        Some(t) => t + local_tza(activation),
+        None => return Ok(Value::Undefined),
+    };
+
+    Ok(decompose(t)[component as usize].into())
+}
+
+/// Replace calendar components of a date, in local time or UTC.
+///
+/// The arguments replace consecutive components, starting at `first`.
+fn set_components<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+    first: Component,
+    max_args: usize,
+    is_utc: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Undefined),
+    };
+    let t = match this.as_date() {
+        Some(t) => t,
+        None => return Ok(Value::Undefined),
+    };
+    let tza = if is_utc { 0.0 } else { local_tza(activation) };
+
+    // Setting the year of an invalid date starts from the epoch; any other
+    // component leaves it invalid.
+    let t = if t.is_nan() && first == Component::FullYear {
+        0.0
+    } else {
+        t + tza
+    };
+
+    let mut components = decompose(t);
+    let first = first as usize;
+
+    if args.is_empty() {
+        components[first] = f64::NAN;
+    }
+
+    for (i, arg) in args.iter().take(max_args).enumerate() {
+        components[first + i] = arg.clone().coerce_to_number(activation)?;
+    }
+
+    let time = time_clip(compose(&components) - tza);
+
+    this.set_date(activation.context.gc_context, time);
+
+    Ok(time.into())
+}
+
+/// Define getters and setters for a calendar component, in local time and
+/// UTC.
+macro_rules! component_accessors {
+    ($component:ident, $max_args:literal, $get:ident, $set:ident, $get_utc:ident, $set_utc:ident) => {
+        pub fn $get<'gc>(
+            activation: &mut Activation<'_, 'gc, '_>,
+            this: Option<Object<'gc>>,
+            _args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error<'gc>> {
+            get_component(activation, this, Component::$component, false)
+        }
+
+        pub fn $set<'gc>(
+            activation: &mut Activation<'_, 'gc, '_>,
+            this: Option<Object<'gc>>,
+            args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error<'gc>> {
+            set_components(
+                activation,
+                this,
+                args,
+                Component::$component,
+                $max_args,
+                false,
+            )
+        }
+
+        pub fn $get_utc<'gc>(
+            activation: &mut Activation<'_, 'gc, '_>,
+            this: Option<Object<'gc>>,
+            _args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error<'gc>> {
+            get_component(activation, this, Component::$component, true)
+        }
+
+        pub fn $set_utc<'gc>(
+            activation: &mut Activation<'_, 'gc, '_>,
+            this: Option<Object<'gc>>,
+            args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error<'gc>> {
+            set_components(
+                activation,
+                this,
+                args,
+                Component::$component,
+                $max_args,
+                true,
+            )
+        }
+    };
+}
+
+component_accessors!(
+    FullYear,
+    3,
+    get_full_year,
+    set_full_year,
+    get_utc_full_year,
+    set_utc_full_year
+);
+component_accessors!(Month, 2, get_month, set_month, get_utc_month, set_utc_month);
+component_accessors!(Date, 1, get_date, set_date, get_utc_date, set_utc_date);
+component_accessors!(Hours, 4, get_hours, set_hours, get_utc_hours, set_utc_hours);
+component_accessors!(
+    Minutes,
+    3,
+    get_minutes,
+    set_minutes,
+    get_utc_minutes,
+    set_utc_minutes
+);
+component_accessors!(
+    Seconds,
+    2,
+    get_seconds,
+    set_seconds,
+    get_utc_seconds,
+    set_utc_seconds
+);
+component_accessors!(
+    Milliseconds,
+    1,
+    get_milliseconds,
+    set_milliseconds,
+    get_utc_milliseconds,
+    set_utc_milliseconds
+);
+
+/// Implements `Date.day` and `Date.getDay`
+pub fn get_day<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match time_of(this) {
+        Some(t) if t.is_nan() => Ok(f64::NAN.into()),
+        Some(t) => Ok(week_day(t + local_tza(activation)).into()),
+        None => Ok(Value::Undefined),
+    }
+}
+
+/// Implements `Date.dayUTC` and `Date.getUTCDay`
+pub fn get_utc_day<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match time_of(this) {
+        Some(t) if t.is_nan() => Ok(f64::NAN.into()),
+        Some(t) => Ok(week_day(t).into()),
+        None => Ok(Value::Undefined),
+    }
+}
+
+/// Format a date with a given formatting function, in local time or UTC.
+fn format_with<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    is_utc: bool,
+    format: impl FnOnce(f64, f64) -> String,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let t = match time_of(this) {
+        Some(t) if t.is_nan() => return Ok("Invalid Date".into()),
+        Some(t) => t,
+        None => return Ok(Value::Undefined),
+    };
+    let tza = if is_utc { 0.0 } else { local_tza(activation) };
+
+    Ok(AvmString::new(activation.context.gc_context, format(t + tza, tza)).into())
+}
+
+/// Implements `Date.toString`
+///
+/// For example, `Sat Feb 3 04:05:06 GMT+0545 2001`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_with(activation, this, false, format_full)
+}
+
+/// Implements `Date.toUTCString`
+///
+/// For example, `Fri Feb 2 22:20:06 2001 UTC`.
+pub fn to_utc_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_with(activation, this, true, |t, _| {
+        format!(
+            "{} {} {} {} {} UTC",
+            DAY_NAMES[week_day(t) as usize],
+            MONTH_NAMES[month_from_time(t) as usize],
+            date_from_time(t),
+            format_time(t),
+            year_from_time(t)
+        )
+    })
+}
+
+/// Implements `Date.toDateString`
+pub fn to_date_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_with(activation, this, false, |t, _| format_date(t))
+}
+
+/// Implements `Date.toTimeString`
+pub fn to_time_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_with(activation, this, false, |t, tza| {
+        format!("{} GMT{}", format_time(t), format_offset(tza))
+    })
+}
+
+/// Implements `Date.toLocaleString`
+pub fn to_locale_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_with(activation, this, false, |t, _| {
+        format!("{} {}", format_date(t), format_locale_time(t))
+    })
+}
+
+/// Implements `Date.toLocaleDateString`
+pub fn to_locale_date_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    to_date_string(activation, this, args)
+}
+
+/// Implements `Date.toLocaleTimeString`
+pub fn to_locale_time_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_with(activation, this, false, |t, _| format_locale_time(t))
+}
+
+/// Construct `Date`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public_namespace(), "Date"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "UTC"),
+        Method::from_builtin(utc),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "parse"),
+        Method::from_builtin(parse),
+    ));
+
+    // Accessor properties, each of which has a getter and setter.
+    for (name, getter, setter) in [
+        (
+            "time",
+            get_time as NativeMethod<'gc>,
+            set_time as NativeMethod<'gc>,
+        ),
+        ("fullYear", get_full_year, set_full_year),
+        ("month", get_month, set_month),
+        ("date", get_date, set_date),
+        ("hours", get_hours, set_hours),
+        ("minutes", get_minutes, set_minutes),
+        ("seconds", get_seconds, set_seconds),
+        ("milliseconds", get_milliseconds, set_milliseconds),
+        ("fullYearUTC", get_utc_full_year, set_utc_full_year),
+        ("monthUTC", get_utc_month, set_utc_month),
+        ("dateUTC", get_utc_date, set_utc_date),
+        ("hoursUTC", get_utc_hours, set_utc_hours),
+        ("minutesUTC", get_utc_minutes, set_utc_minutes),
+        ("secondsUTC", get_utc_seconds, set_utc_seconds),
+        (
+            "millisecondsUTC",
+            get_utc_milliseconds,
+            set_utc_milliseconds,
+        ),
+    ]
+    .iter()
+    {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), *name),
+            Method::from_builtin(*getter),
+        ));
+        write.define_instance_trait(Trait::from_setter(
+            QName::new(Namespace::public_namespace(), *name),
+            Method::from_builtin(*setter),
+        ));
+    }
+
+    for (name, getter) in [
+        ("day", get_day as NativeMethod<'gc>),
+        ("dayUTC", get_utc_day),
+        ("timezoneOffset", get_timezone_offset),
+    ]
+    .iter()
+    {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), *name),
+            Method::from_builtin(*getter),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    for (name, method) in [
+        ("getTime", get_time as NativeMethod<'gc>),
+        ("setTime", set_time),
+        ("valueOf", get_time),
+        ("getTimezoneOffset", get_timezone_offset),
+        ("getFullYear", get_full_year),
+        ("getMonth", get_month),
+        ("getDate", get_date),
+        ("getDay", get_day),
+        ("getHours", get_hours),
+        ("getMinutes", get_minutes),
+        ("getSeconds", get_seconds),
+        ("getMilliseconds", get_milliseconds),
+        ("getUTCFullYear", get_utc_full_year),
+        ("getUTCMonth", get_utc_month),
+        ("getUTCDate", get_utc_date),
+        ("getUTCDay", get_utc_day),
+        ("getUTCHours", get_utc_hours),
+        ("getUTCMinutes", get_utc_minutes),
+        ("getUTCSeconds", get_utc_seconds),
+        ("getUTCMilliseconds", get_utc_milliseconds),
+        ("setFullYear", set_full_year),
+        ("setMonth", set_month),
+        ("setDate", set_date),
+        ("setHours", set_hours),
+        ("setMinutes", set_minutes),
+        ("setSeconds", set_seconds),
+        ("setMilliseconds", set_milliseconds),
+        ("setUTCFullYear", set_utc_full_year),
+        ("setUTCMonth", set_utc_month),
+        ("setUTCDate", set_utc_date),
+        ("setUTCHours", set_utc_hours),
+        ("setUTCMinutes", set_utc_minutes),
+        ("setUTCSeconds", set_utc_seconds),
+        ("setUTCMilliseconds", set_utc_milliseconds),
+        ("toDateString", to_date_string),
+        ("toTimeString", to_time_string),
+        ("toUTCString", to_utc_string),
+        ("toLocaleString", to_locale_string),
+        ("toLocaleDateString", to_locale_date_string),
+        ("toLocaleTimeString", to_locale_time_string),
+    ]
+    .iter()
+    {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::as3_namespace(), *name),
+            Method::from_builtin(*method),
+        ));
+    }
+
+    drop(write);
+
+    class
+}
@@ -0,0 +1,192 @@
+//! `Error` and its subclasses
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The builtin subclasses of `Error`, as `(package, name, superclass package,
+/// superclass name)`.
+pub const SUBCLASSES: &[(&str, &str, &str, &str)] = &[
+    ("", "ArgumentError", "", "Error"),
+    ("", "DefinitionError", "", "Error"),
+    ("", "EvalError", "", "Error"),
+    ("", "RangeError", "", "Error"),
+    ("", "ReferenceError", "", "Error"),
+    ("", "SecurityError", "", "Error"),
+    ("", "SyntaxError", "", "Error"),
+    ("", "TypeError", "", "Error"),
+    ("", "URIError", "", "Error"),
+    ("", "VerifyError", "", "Error"),
+    ("flash.errors", "IOError", "", "Error"),
+    ("flash.errors", "EOFError", "flash.errors", "IOError"),
+    ("flash.errors", "IllegalOperationError", "", "Error"),
+];
+
+/// The name of the private slot holding an error's ID.
+fn error_id_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::Private("".into()), "_errorID")
+}
+
+/// Implements `Error`'s instance initializer.
+///
+/// All subclasses of `Error` share this initializer, as they differ only in
+/// the `name` on their prototype.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        let message = match args.get(0) {
+            Some(Value::Undefined) | None => "".into(),
+            Some(message) => message.clone().coerce_to_string(activation)?.into(),
+        };
+        this.set_property(
+            this,
+            &QName::new(Namespace::public_namespace(), "message"),
+            message,
+            activation,
+        )?;
+
+        let error_id = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+        this.set_property(this, &error_id_name(), error_id.into(), activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.errorID`.
+pub fn error_id<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &error_id_name(), activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.getStackTrace`.
+///
+/// Stack traces are only available in debugger versions of Flash Player, so
+/// this always returns `null`.
+pub fn get_stack_trace<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Null)
+}
+
+/// Implements `Error.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        let name = this
+            .get_property(
+                this,
+                &QName::new(Namespace::public_namespace(), "name"),
+                activation,
+            )?
+            .coerce_to_string(activation)?;
+        let message = this
+            .get_property(
+                this,
+                &QName::new(Namespace::public_namespace(), "message"),
+                activation,
+            )?
+            .coerce_to_string(activation)?;
+
+        if message.is_empty() {
+            return Ok(name.into());
+        }
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("{}: {}", name, message),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Error`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public_namespace(), "Error"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public_namespace(), "message"),
+        Multiname::from(QName::new(Namespace::public_namespace(), "String")),
+        Some("".into()),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        error_id_name(),
+        Multiname::from(QName::new(Namespace::public_namespace(), "int")),
+        Some(0.into()),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "errorID"),
+        Method::from_builtin(error_id),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "getStackTrace"),
+        Method::from_builtin(get_stack_trace),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+    drop(write);
+
+    class
+}
+
+/// Construct the class of one of the builtin subclasses of `Error`.
+pub fn create_subclass<'gc>(
+    mc: MutationContext<'gc, '_>,
+    package: &'static str,
+    name: &'static str,
+    super_package: &'static str,
+    super_name: &'static str,
+) -> GcCell<'gc, Class<'gc>> {
+    Class::new(
+        QName::new(Namespace::package(package), name),
+        Some(QName::new(Namespace::package(super_package), super_name).into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    )
+}
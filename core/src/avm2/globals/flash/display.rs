@@ -1,9 +1,17 @@
 //! `flash.display` namespace
 
+pub mod bitmap;
+pub mod bitmapdata;
 pub mod displayobject;
 pub mod displayobjectcontainer;
 pub mod framelabel;
+pub mod graphics;
 pub mod interactiveobject;
+pub mod loader;
+pub mod loaderinfo;
 pub mod movieclip;
 pub mod scene;
+pub mod shape;
+pub mod simplebutton;
 pub mod sprite;
+pub mod stage;
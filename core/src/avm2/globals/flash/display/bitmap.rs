@@ -0,0 +1,124 @@
+//! `flash.display.Bitmap` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use crate::display_object::{Bitmap, TDisplayObject};
+use gc_arena::{GcCell, MutationContext};
+
+/// Coerce a value into a `BitmapData` object, or `None` for `null`.
+fn bitmap_data_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<Option<Object<'gc>>, Error<'gc>> {
+    match value {
+        Value::Undefined | Value::Null => Ok(None),
+        value => {
+            let bitmap_data = value.coerce_to_object(activation)?;
+
+            if bitmap_data.as_bitmap_data().is_none() {
+                return Err(Error::avm_error(
+                    ErrorClass::TypeError,
+                    1034,
+                    "Type Coercion failed: cannot convert value to flash.display.BitmapData.",
+                ));
+            }
+
+            Ok(Some(bitmap_data))
+        }
+    }
+}
+
+/// Implements `flash.display.Bitmap`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if this.as_display_object().is_none() {
+            let bitmap_data =
+                bitmap_data_arg(activation, args.get(0).cloned().unwrap_or(Value::Null))?;
+            let new_do = Bitmap::new_with_avm2(activation.context.gc_context, this, bitmap_data);
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.Bitmap`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `bitmapData`'s getter.
+///
+/// Bitmaps placed from a bitmap character have no `BitmapData` until one is
+/// assigned.
+pub fn bitmap_data<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_bitmap())
+    {
+        return Ok(bitmap.bitmap_data().map(Value::from).unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bitmapData`'s setter.
+pub fn set_bitmap_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_bitmap())
+    {
+        let bitmap_data = bitmap_data_arg(activation, args.get(0).cloned().unwrap_or(Value::Null))?;
+
+        bitmap.set_bitmap_data(activation.context.gc_context, bitmap_data);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Bitmap`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Bitmap"),
+        Some(QName::new(Namespace::package("flash.display"), "DisplayObject").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "bitmapData"),
+        Method::from_builtin(bitmap_data),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "bitmapData"),
+        Method::from_builtin(set_bitmap_data),
+    ));
+
+    class
+}
@@ -0,0 +1,360 @@
+//! `flash.display.BitmapData` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bitmapdata::BitmapDataStorage;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use gc_arena::{GcCell, MutationContext};
+
+/// The largest width or height of a bitmap.
+const MAX_DIMENSION: i32 = 8191;
+
+/// The largest number of pixels in a bitmap.
+const MAX_PIXELS: i32 = 16_777_215;
+
+/// Implements `flash.display.BitmapData`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let width = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let height = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let transparent = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Bool(true))
+            .coerce_to_boolean();
+        let fill_color = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| 0xFFFF_FFFFu32.into())
+            .coerce_to_u32(activation)?;
+
+        if width <= 0
+            || height <= 0
+            || width > MAX_DIMENSION
+            || height > MAX_DIMENSION
+            || width * height > MAX_PIXELS
+        {
+            return Err(Error::avm_error(
+                ErrorClass::ArgumentError,
+                2015,
+                "Invalid BitmapData.",
+            ));
+        }
+
+        if let Some(mut storage) = this.as_bitmap_data_mut(activation.context.gc_context) {
+            *storage = BitmapDataStorage::new(width as u32, height as u32, transparent, fill_color);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.BitmapData`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Check that a `BitmapData` object is usable, raising an error if it has
+/// been disposed.
+fn check_valid(this: Object<'_>) -> Result<(), Error<'_>> {
+    match this.as_bitmap_data() {
+        Some(storage) if !storage.disposed() => Ok(()),
+        _ => Err(Error::avm_error(
+            ErrorClass::ArgumentError,
+            2015,
+            "Invalid BitmapData.",
+        )),
+    }
+}
+
+/// Coerce a pair of arguments into pixel coordinates.
+fn point_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(i32, i32), Error<'gc>> {
+    let x = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let y = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    Ok((x, y))
+}
+
+/// Implements `width`.
+pub fn width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        check_valid(this)?;
+
+        if let Some(storage) = this.as_bitmap_data() {
+            return Ok((storage.width() as i32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `height`.
+pub fn height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        check_valid(this)?;
+
+        if let Some(storage) = this.as_bitmap_data() {
+            return Ok((storage.height() as i32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `transparent`.
+pub fn transparent<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        check_valid(this)?;
+
+        if let Some(storage) = this.as_bitmap_data() {
+            return Ok(storage.transparent().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.getPixel`.
+pub fn get_pixel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        check_valid(this)?;
+
+        let (x, y) = point_args(activation, args)?;
+
+        if let Some(storage) = this.as_bitmap_data() {
+            return Ok(storage.get_pixel(x, y).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.getPixel32`.
+pub fn get_pixel32<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        check_valid(this)?;
+
+        let (x, y) = point_args(activation, args)?;
+
+        if let Some(storage) = this.as_bitmap_data() {
+            return Ok(storage.get_pixel32(x, y).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.setPixel`.
+pub fn set_pixel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        check_valid(this)?;
+
+        let (x, y) = point_args(activation, args)?;
+        let color = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut storage) = this.as_bitmap_data_mut(activation.context.gc_context) {
+            storage.set_pixel(x, y, color);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.setPixel32`.
+pub fn set_pixel32<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        check_valid(this)?;
+
+        let (x, y) = point_args(activation, args)?;
+        let color = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut storage) = this.as_bitmap_data_mut(activation.context.gc_context) {
+            storage.set_pixel32(x, y, color);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.fillRect`.
+///
+/// The rectangle may be any object with `x`, `y`, `width` and `height`
+/// properties.
+pub fn fill_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        check_valid(this)?;
+
+        let mut rect = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => {
+                return Err(Error::avm_error(
+                    ErrorClass::TypeError,
+                    2007,
+                    "Parameter rect must be non-null.",
+                ))
+            }
+            rect => rect.coerce_to_object(activation)?,
+        };
+        let mut bounds = [0; 4];
+        for (bound, name) in bounds.iter_mut().zip(&["x", "y", "width", "height"]) {
+            *bound = rect
+                .get_property(
+                    rect,
+                    &QName::new(Namespace::public_namespace(), *name),
+                    activation,
+                )?
+                .coerce_to_i32(activation)?;
+        }
+        let color = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut storage) = this.as_bitmap_data_mut(activation.context.gc_context) {
+            let [x, y, width, height] = bounds;
+
+            storage.fill_rect(x, y, width, height, color);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.dispose`.
+pub fn dispose<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut storage) = this.as_bitmap_data_mut(activation.context.gc_context) {
+            storage.dispose();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `BitmapData`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "BitmapData"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "width"),
+        Method::from_builtin(width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "height"),
+        Method::from_builtin(height),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "transparent"),
+        Method::from_builtin(transparent),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "getPixel"),
+        Method::from_builtin(get_pixel),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "getPixel32"),
+        Method::from_builtin(get_pixel32),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "setPixel"),
+        Method::from_builtin(set_pixel),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "setPixel32"),
+        Method::from_builtin(set_pixel32),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "fillRect"),
+        Method::from_builtin(fill_rect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "dispose"),
+        Method::from_builtin(dispose),
+    ));
+
+    class
+}
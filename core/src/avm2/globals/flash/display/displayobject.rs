@@ -2,11 +2,16 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
+use crate::avm2::globals::flash::display::stage;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Error, ErrorClass};
+use crate::display_object::{blend_mode_from_name, blend_mode_name, DisplayObject, TDisplayObject};
+use crate::types::{Degrees, Percent};
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `flash.display.DisplayObject`'s instance constructor.
@@ -14,7 +19,7 @@ pub fn instance_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
@@ -23,17 +28,590 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `blendMode`'s getter.
+pub fn blend_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let name = blend_mode_name(dobj.blend_mode());
+        return Ok(AvmString::new(activation.context.gc_context, name).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `blendMode`'s setter.
+pub fn set_blend_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if let Some(blend_mode) = blend_mode_from_name(&name) {
+            dobj.set_blend_mode(activation.context.gc_context, blend_mode);
+        } else {
+            return Err(Error::avm_error(
+                ErrorClass::ArgumentError,
+                2008,
+                "Parameter blendMode must be one of the accepted values.",
+            ));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `x`'s getter.
+pub fn x<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.x().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `x`'s setter.
+pub fn set_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_x = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        dobj.set_x(activation.context.gc_context, new_x);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `y`'s getter.
+pub fn y<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.y().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `y`'s setter.
+pub fn set_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_y = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        dobj.set_y(activation.context.gc_context, new_y);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `width`'s getter.
+pub fn width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.width().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `width`'s setter.
+pub fn set_width<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_width = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        if new_width >= 0.0 {
+            dobj.set_width(activation.context.gc_context, new_width);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `height`'s getter.
+pub fn height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.height().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `height`'s setter.
+pub fn set_height<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_height = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        if new_height >= 0.0 {
+            dobj.set_height(activation.context.gc_context, new_height);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scaleX`'s getter.
+///
+/// AVM2 scales are unit proportions, unlike AVM1's percentages.
+pub fn scale_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj
+            .scale_x(activation.context.gc_context)
+            .into_unit()
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scaleX`'s setter.
+pub fn set_scale_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_scale = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        dobj.set_scale_x(activation.context.gc_context, Percent::from_unit(new_scale));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scaleY`'s getter.
+pub fn scale_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj
+            .scale_y(activation.context.gc_context)
+            .into_unit()
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scaleY`'s setter.
+pub fn set_scale_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_scale = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        dobj.set_scale_y(activation.context.gc_context, Percent::from_unit(new_scale));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `rotation`'s getter.
+pub fn rotation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let rotation: f64 = dobj.rotation(activation.context.gc_context).into();
+
+        return Ok(rotation.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `rotation`'s setter.
+///
+/// Rotations are wrapped into the range -180 to 180 degrees.
+pub fn set_rotation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let mut degrees = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?
+            % 360.0;
+
+        if degrees > 180.0 {
+            degrees -= 360.0;
+        } else if degrees < -180.0 {
+            degrees += 360.0;
+        }
+
+        dobj.set_rotation(activation.context.gc_context, Degrees::from(degrees));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `alpha`'s getter.
+pub fn alpha<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.alpha().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `alpha`'s setter.
+pub fn set_alpha<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_alpha = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        dobj.set_alpha(activation.context.gc_context, new_alpha);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `visible`'s getter.
+pub fn visible<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.visible().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `visible`'s setter.
+pub fn set_visible<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_visible = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        dobj.set_visible(activation.context.gc_context, new_visible);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `name`'s getter.
+pub fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(AvmString::new(activation.context.gc_context, dobj.name().to_string()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `name`'s setter.
+///
+/// Timeline-placed objects cannot be renamed.
+pub fn set_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if dobj.place_frame() != 0 {
+            return Err(Error::avm_error(
+                ErrorClass::IllegalOperationError,
+                2078,
+                "The name property of a Timeline-placed object cannot be modified.",
+            ));
+        }
+
+        let new_name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        dobj.set_name(activation.context.gc_context, &new_name);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `parent`.
+pub fn parent<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj
+            .parent()
+            .map(|parent| parent.object2())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Find the top-most ancestor of a display object, if it is on the stage.
+fn stage_root<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    dobj: DisplayObject<'gc>,
+) -> Option<DisplayObject<'gc>> {
+    let mut root = dobj;
+    while let Some(parent) = root.parent() {
+        root = parent;
+    }
+
+    if activation
+        .context
+        .levels
+        .values()
+        .any(|level| DisplayObject::ptr_eq(*level, root))
+    {
+        Some(root)
+    } else {
+        None
+    }
+}
+
+/// Implements `root`.
+///
+/// Objects that are not on the stage have no root.
+pub fn root<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(stage_root(activation, dobj)
+            .map(|root| root.object2())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stage`.
+///
+/// Objects that are not on the stage have no stage.
+pub fn stage<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if stage_root(activation, dobj).is_some() {
+            return Ok(stage::stage_object(activation)?.into());
+        }
+
+        return Ok(Value::Null);
+    }
+
     Ok(Value::Undefined)
 }
 
 /// Construct `DisplayObject`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(Namespace::package("flash.display"), "DisplayObject"),
         Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "blendMode"),
+        Method::from_builtin(blend_mode),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "blendMode"),
+        Method::from_builtin(set_blend_mode),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "x"),
+        Method::from_builtin(x),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "x"),
+        Method::from_builtin(set_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "y"),
+        Method::from_builtin(y),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "y"),
+        Method::from_builtin(set_y),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "width"),
+        Method::from_builtin(width),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "width"),
+        Method::from_builtin(set_width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "height"),
+        Method::from_builtin(height),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "height"),
+        Method::from_builtin(set_height),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "scaleX"),
+        Method::from_builtin(scale_x),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "scaleX"),
+        Method::from_builtin(set_scale_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "scaleY"),
+        Method::from_builtin(scale_y),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "scaleY"),
+        Method::from_builtin(set_scale_y),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "rotation"),
+        Method::from_builtin(rotation),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "rotation"),
+        Method::from_builtin(set_rotation),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "alpha"),
+        Method::from_builtin(alpha),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "alpha"),
+        Method::from_builtin(set_alpha),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "visible"),
+        Method::from_builtin(visible),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "visible"),
+        Method::from_builtin(set_visible),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "name"),
+        Method::from_builtin(name),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "name"),
+        Method::from_builtin(set_name),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "parent"),
+        Method::from_builtin(parent),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "root"),
+        Method::from_builtin(root),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "stage"),
+        Method::from_builtin(stage),
+    ));
+
+    class
 }
@@ -4,17 +4,314 @@ use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Error, ErrorClass};
+use crate::context::UpdateContext;
+use crate::display_object::{
+    dispatch_added_event, dispatch_removed_event, DisplayObject, MovieClip, TDisplayObject,
+};
 use gc_arena::{GcCell, MutationContext};
 
+/// The display list that a container method operates on.
+///
+/// The player has no display object for the stage itself. Instead, the
+/// children of the stage are the player's levels.
+#[derive(Clone, Copy)]
+enum Container<'gc> {
+    Clip(MovieClip<'gc>),
+    Stage,
+}
+
+impl<'gc> Container<'gc> {
+    /// Get the container that a `DisplayObjectContainer` method was called on.
+    fn from_this(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+    ) -> Option<Self> {
+        let this = this?;
+
+        if let Some(stage) = activation.context.avm2.stage_object {
+            if Object::ptr_eq(stage, this) {
+                return Some(Self::Stage);
+            }
+        }
+
+        this.as_display_object()
+            .and_then(|dobj| dobj.as_movie_clip())
+            .map(Self::Clip)
+    }
+
+    /// Get the container that currently holds a display object, if any.
+    fn holding(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        child: DisplayObject<'gc>,
+    ) -> Option<Self> {
+        match child.parent() {
+            Some(parent) => parent.as_movie_clip().map(Self::Clip),
+            None if context
+                .levels
+                .values()
+                .any(|level| DisplayObject::ptr_eq(*level, child)) =>
+            {
+                Some(Self::Stage)
+            }
+            None => None,
+        }
+    }
+
+    fn is_same(self, other: Self) -> bool {
+        match (self, other) {
+            (Self::Clip(a), Self::Clip(b)) => DisplayObject::ptr_eq(a.into(), b.into()),
+            (Self::Stage, Self::Stage) => true,
+            _ => false,
+        }
+    }
+
+    fn num_children(self, context: &mut UpdateContext<'_, 'gc, '_>) -> usize {
+        match self {
+            Self::Clip(mc) => mc.num_children(),
+            Self::Stage => context.levels.len(),
+        }
+    }
+
+    fn child_at(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        index: usize,
+    ) -> Option<DisplayObject<'gc>> {
+        match self {
+            Self::Clip(mc) => mc.child_by_index(index),
+            Self::Stage => context.levels.values().nth(index).copied(),
+        }
+    }
+
+    fn index_of(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        child: DisplayObject<'gc>,
+    ) -> Option<usize> {
+        match self {
+            Self::Clip(mc) => mc.child_index(child),
+            Self::Stage => context
+                .levels
+                .values()
+                .position(|level| DisplayObject::ptr_eq(*level, child)),
+        }
+    }
+
+    /// Insert a parentless child at a given index, moving any children at or
+    /// above it up by one.
+    fn insert_at(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        child: DisplayObject<'gc>,
+        index: usize,
+    ) {
+        match self {
+            Self::Clip(mut mc) => mc.insert_child_at_index(context, child, index),
+            Self::Stage => {
+                // The root movie must stay on level 0, so nothing may be
+                // inserted beneath it.
+                let level = if let Some(level) = context.levels.keys().nth(index).copied() {
+                    let level = level.max(1);
+                    let above: Vec<(u32, DisplayObject<'gc>)> = context
+                        .levels
+                        .range(level..)
+                        .map(|(level, child)| (*level, *child))
+                        .collect();
+
+                    for (level, _) in above.iter() {
+                        context.levels.remove(level);
+                    }
+
+                    for (level, above_child) in above {
+                        above_child.set_depth(context.gc_context, (level + 1) as i32);
+                        context.levels.insert(level + 1, above_child);
+                    }
+
+                    level
+                } else {
+                    context
+                        .levels
+                        .keys()
+                        .copied()
+                        .rev()
+                        .next()
+                        .map(|level| level + 1)
+                        .unwrap_or(0)
+                };
+
+                child.set_depth(context.gc_context, level as i32);
+                child.set_place_frame(context.gc_context, 0);
+                child.set_removed(context.gc_context, false);
+                context.levels.insert(level, child);
+            }
+        }
+    }
+
+    /// Remove a child from this container without unloading it.
+    ///
+    /// The root movie cannot be removed from the stage, as the player
+    /// requires a level 0 to exist.
+    fn remove(self, context: &mut UpdateContext<'_, 'gc, '_>, child: DisplayObject<'gc>) {
+        match self {
+            Self::Clip(mut mc) => mc.remove_child_from_avm2(context, child),
+            Self::Stage => {
+                let level = context
+                    .levels
+                    .iter()
+                    .find(|(_, level)| DisplayObject::ptr_eq(**level, child))
+                    .map(|(level, _)| *level);
+
+                match level {
+                    Some(0) => {
+                        log::warn!("Removing the root movie from the stage is not supported")
+                    }
+                    Some(level) => {
+                        context.levels.remove(&level);
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    /// Check if a display object is this container or one of its
+    /// descendants.
+    fn contains(self, context: &mut UpdateContext<'_, 'gc, '_>, child: DisplayObject<'gc>) -> bool {
+        let mut ancestor = Some(child);
+
+        while let Some(dobj) = ancestor {
+            if let Self::Clip(mc) = self {
+                if DisplayObject::ptr_eq(dobj, mc.into()) {
+                    return true;
+                }
+            }
+
+            if dobj.parent().is_none() {
+                return matches!(self, Self::Stage)
+                    && context
+                        .levels
+                        .values()
+                        .any(|level| DisplayObject::ptr_eq(*level, dobj));
+            }
+
+            ancestor = dobj.parent();
+        }
+
+        false
+    }
+}
+
+/// Coerce an argument into the display object it refers to.
+fn display_object_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<DisplayObject<'gc>, Error<'gc>> {
+    match args.get(index).cloned().unwrap_or(Value::Undefined) {
+        Value::Undefined | Value::Null => Err(Error::avm_error(
+            ErrorClass::TypeError,
+            2007,
+            "Parameter child must be non-null.",
+        )),
+        value => value
+            .coerce_to_object(activation)?
+            .as_display_object()
+            .ok_or_else(|| {
+                Error::avm_error(
+                    ErrorClass::TypeError,
+                    1034,
+                    "Type Coercion failed: cannot convert value to flash.display.DisplayObject.",
+                )
+            }),
+    }
+}
+
+/// Coerce an argument into a child index, checking that it lies within
+/// `0..=max`.
+fn index_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    max: usize,
+) -> Result<usize, Error<'gc>> {
+    let child_index = args
+        .get(index)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    if child_index < 0 || child_index as usize > max {
+        return Err(Error::avm_error(
+            ErrorClass::RangeError,
+            2006,
+            "The supplied index is out of bounds.",
+        ));
+    }
+
+    Ok(child_index as usize)
+}
+
+/// Add a child to a container at a given index.
+///
+/// The child is first removed from wherever it currently is on the display
+/// list, dispatching the usual removal events.
+fn add_child_at_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    container: Container<'gc>,
+    child: DisplayObject<'gc>,
+    mut index: usize,
+) -> Result<(), Error<'gc>> {
+    if let (Container::Clip(mc), Some(child)) = (container, child.as_movie_clip()) {
+        if Container::Clip(child).contains(&mut activation.context, mc.into()) {
+            return Err(Error::avm_error(ErrorClass::ArgumentError, 2150, "An object cannot be added as a child of itself or one of its children (or children's children, etc.)."));
+        }
+    }
+
+    if let Some(old_container) = Container::holding(&mut activation.context, child) {
+        if old_container.is_same(container) {
+            // Moving a child within the same container shifts the children
+            // after it down by one.
+            if let Some(old_index) = old_container.index_of(&mut activation.context, child) {
+                if old_index < index {
+                    index -= 1;
+                }
+            }
+        }
+
+        dispatch_removed_event(child, &mut activation.context);
+        old_container.remove(&mut activation.context, child);
+
+        if Container::holding(&mut activation.context, child).is_some() {
+            return Ok(());
+        }
+    }
+
+    container.insert_at(&mut activation.context, child, index);
+    dispatch_added_event(child, &mut activation.context);
+
+    Ok(())
+}
+
+/// Remove a child from a container, dispatching removal events.
+fn remove_child_with_events<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    container: Container<'gc>,
+    child: DisplayObject<'gc>,
+) {
+    dispatch_removed_event(child, &mut activation.context);
+    container.remove(&mut activation.context, child);
+}
+
 /// Implements `flash.display.DisplayObjectContainer`'s instance constructor.
 pub fn instance_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
@@ -23,13 +320,313 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `numChildren`.
+pub fn num_children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(container) = Container::from_this(activation, this) {
+        return Ok((container.num_children(&mut activation.context) as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `addChild`.
+pub fn add_child<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(container) = Container::from_this(activation, this) {
+        let child = display_object_arg(activation, args, 0)?;
+        let index = container.num_children(&mut activation.context);
+
+        add_child_at_index(activation, container, child, index)?;
+
+        return Ok(child.object2());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `addChildAt`.
+pub fn add_child_at<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(container) = Container::from_this(activation, this) {
+        let child = display_object_arg(activation, args, 0)?;
+        let num_children = container.num_children(&mut activation.context);
+        let index = index_arg(activation, args, 1, num_children)?;
+
+        add_child_at_index(activation, container, child, index)?;
+
+        return Ok(child.object2());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `removeChild`.
+pub fn remove_child<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(container) = Container::from_this(activation, this) {
+        let child = display_object_arg(activation, args, 0)?;
+
+        if container.index_of(&mut activation.context, child).is_none() {
+            return Err(Error::avm_error(
+                ErrorClass::ArgumentError,
+                2025,
+                "The supplied DisplayObject must be a child of the caller.",
+            ));
+        }
+
+        remove_child_with_events(activation, container, child);
+
+        return Ok(child.object2());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `removeChildAt`.
+pub fn remove_child_at<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(container) = Container::from_this(activation, this) {
+        let index = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let child = if index >= 0 {
+            container.child_at(&mut activation.context, index as usize)
+        } else {
+            None
+        };
+
+        if let Some(child) = child {
+            remove_child_with_events(activation, container, child);
+
+            return Ok(child.object2());
+        }
+
+        return Err(Error::avm_error(
+            ErrorClass::RangeError,
+            2006,
+            "The supplied index is out of bounds.",
+        ));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `getChildAt`.
+pub fn get_child_at<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(container) = Container::from_this(activation, this) {
+        let index = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let child = if index >= 0 {
+            container.child_at(&mut activation.context, index as usize)
+        } else {
+            None
+        };
+
+        return match child {
+            Some(child) => Ok(child.object2()),
+            None => Err(Error::avm_error(
+                ErrorClass::RangeError,
+                2006,
+                "The supplied index is out of bounds.",
+            )),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `getChildByName`.
+pub fn get_child_by_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(container) = Container::from_this(activation, this) {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        for index in 0..container.num_children(&mut activation.context) {
+            if let Some(child) = container.child_at(&mut activation.context, index) {
+                if *child.name() == *name {
+                    return Ok(child.object2());
+                }
+            }
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `getChildIndex`.
+pub fn get_child_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(container) = Container::from_this(activation, this) {
+        let child = display_object_arg(activation, args, 0)?;
+
+        return match container.index_of(&mut activation.context, child) {
+            Some(index) => Ok((index as u32).into()),
+            None => Err(Error::avm_error(
+                ErrorClass::ArgumentError,
+                2025,
+                "The supplied DisplayObject must be a child of the caller.",
+            )),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `setChildIndex`.
+///
+/// Unlike removing and re-adding the child, this dispatches no events.
+pub fn set_child_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(container) = Container::from_this(activation, this) {
+        let child = display_object_arg(activation, args, 0)?;
+
+        if container.index_of(&mut activation.context, child).is_none() {
+            return Err(Error::avm_error(
+                ErrorClass::ArgumentError,
+                2025,
+                "The supplied DisplayObject must be a child of the caller.",
+            ));
+        }
+
+        let max = container.num_children(&mut activation.context) - 1;
+        let index = index_arg(activation, args, 1, max)?;
+
+        container.remove(&mut activation.context, child);
+
+        if container.index_of(&mut activation.context, child).is_none() {
+            container.insert_at(&mut activation.context, child, index);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `contains`.
+pub fn contains<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(container) = Container::from_this(activation, this) {
+        let child = display_object_arg(activation, args, 0)?;
+
+        return Ok(container.contains(&mut activation.context, child).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `tabChildren`'s getter.
+pub fn tab_children<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.tab_children().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `tabChildren`'s setter.
+pub fn set_tab_children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let tab_children = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        dobj.set_tab_children(activation.context.gc_context, tab_children);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `mouseChildren`'s getter.
+pub fn mouse_children<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.mouse_children().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `mouseChildren`'s setter.
+pub fn set_mouse_children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let mouse_children = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        dobj.set_mouse_children(activation.context.gc_context, mouse_children);
+    }
+
     Ok(Value::Undefined)
 }
 
 /// Construct `DisplayObjectContainer`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(
             Namespace::package("flash.display"),
             "DisplayObjectContainer",
@@ -38,5 +635,66 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "numChildren"),
+        Method::from_builtin(num_children),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "addChild"),
+        Method::from_builtin(add_child),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "addChildAt"),
+        Method::from_builtin(add_child_at),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "removeChild"),
+        Method::from_builtin(remove_child),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "removeChildAt"),
+        Method::from_builtin(remove_child_at),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "getChildAt"),
+        Method::from_builtin(get_child_at),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "getChildByName"),
+        Method::from_builtin(get_child_by_name),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "getChildIndex"),
+        Method::from_builtin(get_child_index),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "setChildIndex"),
+        Method::from_builtin(set_child_index),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "contains"),
+        Method::from_builtin(contains),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "tabChildren"),
+        Method::from_builtin(tab_children),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "tabChildren"),
+        Method::from_builtin(set_tab_children),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "mouseChildren"),
+        Method::from_builtin(mouse_children),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "mouseChildren"),
+        Method::from_builtin(set_mouse_children),
+    ));
+
+    class
 }
@@ -0,0 +1,454 @@
+//! `flash.display.Graphics` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, StageObject, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::shape_utils::DrawCommand;
+use gc_arena::{GcCell, MutationContext};
+use std::f64::consts::FRAC_PI_4;
+use swf::{Color, FillStyle, LineCapStyle, LineJoinStyle, LineStyle, Twips};
+
+/// The name of the hidden property that caches a display object's `Graphics`.
+fn graphics_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::Private("ruffle".into()), "graphics")
+}
+
+/// Get the `Graphics` object for a display object's drawing, creating it if
+/// necessary.
+///
+/// The same object is returned every time, as scripts expect.
+pub fn graphics_for<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    dobj: DisplayObject<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match this.get_property(this, &graphics_name(), activation)? {
+        Value::Object(graphics) => Ok(graphics.into()),
+        _ => {
+            let graphics_proto = activation.context.avm2.prototypes().graphics;
+            let graphics: Object<'gc> = StageObject::for_display_object(
+                activation.context.gc_context,
+                dobj,
+                graphics_proto,
+            )
+            .into();
+
+            this.install_dynamic_property(
+                activation.context.gc_context,
+                graphics_name(),
+                graphics.into(),
+            )?;
+
+            Ok(graphics.into())
+        }
+    }
+}
+
+/// Implements `flash.display.Graphics`'s instance constructor.
+///
+/// `Graphics` objects are only created by their display objects.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Err(Error::avm_error(
+        ErrorClass::ArgumentError,
+        2012,
+        "Graphics class cannot be instantiated.",
+    ))
+}
+
+/// Implements `flash.display.Graphics`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Coerce an argument into a number, treating a missing argument as zero.
+fn number_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<f64, Error<'gc>> {
+    match args.get(index) {
+        Some(value) => value.coerce_to_number(activation),
+        None => Ok(0.0),
+    }
+}
+
+/// Convert an AVM2 `0xRRGGBB` color and unit alpha into a color.
+fn color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    rgb: Option<&Value<'gc>>,
+    alpha: Option<&Value<'gc>>,
+) -> Result<Color, Error<'gc>> {
+    let rgb = match rgb {
+        Some(rgb) => rgb.coerce_to_u32(activation)?,
+        None => 0,
+    };
+    let alpha = match alpha {
+        Some(alpha) => alpha.coerce_to_number(activation)?.min(1.0).max(0.0),
+        None => 1.0,
+    };
+
+    Ok(Color::from_rgb(rgb, (alpha * 255.0) as u8))
+}
+
+/// Issue a series of drawing commands to a display object's drawing.
+fn draw<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    commands: impl IntoIterator<Item = DrawCommand>,
+) {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if let Some(mut drawing) = dobj.as_drawing(activation.context.gc_context) {
+            for command in commands {
+                drawing.draw_command(command);
+            }
+        }
+    }
+}
+
+fn move_to_command(x: f64, y: f64) -> DrawCommand {
+    DrawCommand::MoveTo {
+        x: Twips::from_pixels(x),
+        y: Twips::from_pixels(y),
+    }
+}
+
+fn line_to_command(x: f64, y: f64) -> DrawCommand {
+    DrawCommand::LineTo {
+        x: Twips::from_pixels(x),
+        y: Twips::from_pixels(y),
+    }
+}
+
+fn curve_to_command(x1: f64, y1: f64, x2: f64, y2: f64) -> DrawCommand {
+    DrawCommand::CurveTo {
+        x1: Twips::from_pixels(x1),
+        y1: Twips::from_pixels(y1),
+        x2: Twips::from_pixels(x2),
+        y2: Twips::from_pixels(y2),
+    }
+}
+
+/// Implements `Graphics.beginFill`.
+pub fn begin_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let color = color(activation, args.get(0), args.get(1))?;
+
+        if let Some(mut drawing) = dobj.as_drawing(activation.context.gc_context) {
+            drawing.set_fill_style(Some(FillStyle::Color(color)));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.endFill`.
+pub fn end_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if let Some(mut drawing) = dobj.as_drawing(activation.context.gc_context) {
+            drawing.set_fill_style(None);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineStyle`.
+///
+/// Omitting the thickness, or passing `NaN`, removes the line style.
+pub fn line_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let thickness = match args.get(0) {
+            Some(Value::Undefined) | Some(Value::Null) | None => f64::NAN,
+            Some(thickness) => thickness.coerce_to_number(activation)?,
+        };
+
+        let style = if thickness.is_nan() {
+            None
+        } else {
+            let width = Twips::from_pixels(thickness.min(255.0).max(0.0));
+            let color = color(activation, args.get(1), args.get(2))?;
+            let is_pixel_hinted = args
+                .get(3)
+                .map_or(false, |hinted| hinted.coerce_to_boolean());
+            let (allow_scale_x, allow_scale_y) = match args.get(4) {
+                Some(Value::Undefined) | Some(Value::Null) | None => (true, true),
+                Some(mode) => match mode.coerce_to_string(activation)?.as_str() {
+                    "vertical" => (true, false),
+                    "horizontal" => (false, true),
+                    "none" => (false, false),
+                    _ => (true, true),
+                },
+            };
+            let cap_style = match args.get(5) {
+                Some(Value::Undefined) | Some(Value::Null) | None => LineCapStyle::Round,
+                Some(caps) => match caps.coerce_to_string(activation)?.as_str() {
+                    "square" => LineCapStyle::Square,
+                    "none" => LineCapStyle::None,
+                    _ => LineCapStyle::Round,
+                },
+            };
+            let join_style = match args.get(6) {
+                Some(Value::Undefined) | Some(Value::Null) | None => LineJoinStyle::Round,
+                Some(joints) => match joints.coerce_to_string(activation)?.as_str() {
+                    "miter" => {
+                        let limit = match args.get(7) {
+                            Some(limit) => limit.coerce_to_number(activation)?,
+                            None => 3.0,
+                        };
+
+                        LineJoinStyle::Miter(limit.max(1.0).min(255.0) as f32)
+                    }
+                    "bevel" => LineJoinStyle::Bevel,
+                    _ => LineJoinStyle::Round,
+                },
+            };
+
+            Some(LineStyle {
+                width,
+                color,
+                start_cap: cap_style,
+                end_cap: cap_style,
+                join_style,
+                fill_style: None,
+                allow_scale_x,
+                allow_scale_y,
+                is_pixel_hinted,
+                allow_close: false,
+            })
+        };
+
+        if let Some(mut drawing) = dobj.as_drawing(activation.context.gc_context) {
+            drawing.set_line_style(style);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if let Some(mut drawing) = dobj.as_drawing(activation.context.gc_context) {
+            drawing.clear();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.moveTo`.
+pub fn move_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let x = number_arg(activation, args, 0)?;
+    let y = number_arg(activation, args, 1)?;
+
+    draw(activation, this, vec![move_to_command(x, y)]);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineTo`.
+pub fn line_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let x = number_arg(activation, args, 0)?;
+    let y = number_arg(activation, args, 1)?;
+
+    draw(activation, this, vec![line_to_command(x, y)]);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.curveTo`.
+pub fn curve_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let x1 = number_arg(activation, args, 0)?;
+    let y1 = number_arg(activation, args, 1)?;
+    let x2 = number_arg(activation, args, 2)?;
+    let y2 = number_arg(activation, args, 3)?;
+
+    draw(activation, this, vec![curve_to_command(x1, y1, x2, y2)]);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawRect`.
+pub fn draw_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let x = number_arg(activation, args, 0)?;
+    let y = number_arg(activation, args, 1)?;
+    let width = number_arg(activation, args, 2)?;
+    let height = number_arg(activation, args, 3)?;
+
+    draw(
+        activation,
+        this,
+        vec![
+            move_to_command(x, y),
+            line_to_command(x + width, y),
+            line_to_command(x + width, y + height),
+            line_to_command(x, y + height),
+            line_to_command(x, y),
+        ],
+    );
+
+    Ok(Value::Undefined)
+}
+
+/// Build the commands for an ellipse centered on a point.
+///
+/// The ellipse is approximated with eight quadratic curves, one per octant.
+fn ellipse_commands(x: f64, y: f64, radius_x: f64, radius_y: f64) -> Vec<DrawCommand> {
+    // The control points of each curve lie on the tangents at its ends, which
+    // meet at this distance from the center.
+    let control_scale = 1.0 / (FRAC_PI_4 / 2.0).cos();
+    let mut commands = vec![move_to_command(x + radius_x, y)];
+
+    for octant in 1..=8 {
+        let angle = FRAC_PI_4 * octant as f64;
+        let control_angle = angle - FRAC_PI_4 / 2.0;
+
+        commands.push(curve_to_command(
+            x + radius_x * control_scale * control_angle.cos(),
+            y + radius_y * control_scale * control_angle.sin(),
+            x + radius_x * angle.cos(),
+            y + radius_y * angle.sin(),
+        ));
+    }
+
+    commands
+}
+
+/// Implements `Graphics.drawCircle`.
+pub fn draw_circle<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let x = number_arg(activation, args, 0)?;
+    let y = number_arg(activation, args, 1)?;
+    let radius = number_arg(activation, args, 2)?;
+
+    draw(activation, this, ellipse_commands(x, y, radius, radius));
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawEllipse`.
+///
+/// Unlike `drawCircle`, the ellipse is positioned by its top-left corner.
+pub fn draw_ellipse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let x = number_arg(activation, args, 0)?;
+    let y = number_arg(activation, args, 1)?;
+    let width = number_arg(activation, args, 2)?;
+    let height = number_arg(activation, args, 3)?;
+
+    draw(
+        activation,
+        this,
+        ellipse_commands(x + width / 2.0, y + height / 2.0, width / 2.0, height / 2.0),
+    );
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Graphics`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Graphics"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "beginFill"),
+        Method::from_builtin(begin_fill),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "clear"),
+        Method::from_builtin(clear),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "curveTo"),
+        Method::from_builtin(curve_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "drawCircle"),
+        Method::from_builtin(draw_circle),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "drawEllipse"),
+        Method::from_builtin(draw_ellipse),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "drawRect"),
+        Method::from_builtin(draw_rect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "endFill"),
+        Method::from_builtin(end_fill),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "lineStyle"),
+        Method::from_builtin(line_style),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "lineTo"),
+        Method::from_builtin(line_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "moveTo"),
+        Method::from_builtin(move_to),
+    ));
+
+    class
+}
@@ -0,0 +1,220 @@
+//! `flash.display.Loader` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::display::loaderinfo;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use crate::backend::navigator::RequestOptions;
+use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
+use crate::tag_utils::{SwfMovie, SwfSlice};
+use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+
+/// The name of the hidden property that holds a loader's `LoaderInfo`.
+fn content_loader_info_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::Private("ruffle".into()), "contentLoaderInfo")
+}
+
+/// Implements `flash.display.Loader`'s instance constructor.
+///
+/// Loaders are represented by movie clips, which hold the loaded content as
+/// their only child.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        if this.as_display_object().is_none() {
+            let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+            let new_do = MovieClip::new(SwfSlice::empty(movie), activation.context.gc_context);
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+            new_do.set_object2(activation.context.gc_context, this);
+        }
+
+        let loader_info = loaderinfo::create_loader_info(activation, this)?;
+
+        this.install_dynamic_property(
+            activation.context.gc_context,
+            content_loader_info_name(),
+            loader_info.into(),
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.Loader`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Get the clip that holds a loader's content.
+fn loader_clip(this: Option<Object<'_>>) -> Option<MovieClip<'_>> {
+    this.and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+}
+
+/// Get the content currently held by a loader, if any.
+pub fn loader_content(loader: Object<'_>) -> Option<DisplayObject<'_>> {
+    loader_clip(Some(loader)).and_then(|clip| clip.child_by_index(0))
+}
+
+/// Remove a loader's content, if it has any.
+fn remove_content<'gc>(activation: &mut Activation<'_, 'gc, '_>, mut clip: MovieClip<'gc>) {
+    while let Some(content) = clip.child_by_index(0) {
+        clip.remove_child_from_avm2(&mut activation.context, content);
+        content.unload(&mut activation.context);
+    }
+}
+
+/// Implements `Loader.load`.
+///
+/// The request may be a `URLRequest`-like object with a `url` property, or a
+/// URL string.
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let (Some(mut this), Some(mut clip)) = (this, loader_clip(this)) {
+        let url = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => {
+                return Err(Error::avm_error(
+                    ErrorClass::TypeError,
+                    2007,
+                    "Parameter request must be non-null.",
+                ))
+            }
+            Value::Object(mut request) => request
+                .get_property(
+                    request,
+                    &QName::new(Namespace::public_namespace(), "url"),
+                    activation,
+                )?
+                .coerce_to_string(activation)?,
+            url => url.coerce_to_string(activation)?,
+        };
+        let loader_info = this
+            .get_property(this, &content_loader_info_name(), activation)?
+            .coerce_to_object(activation)?;
+
+        remove_content(activation, clip);
+
+        let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+        let content = MovieClip::new(SwfSlice::empty(movie), activation.context.gc_context);
+
+        clip.insert_child_at_index(&mut activation.context, content.into(), 0);
+
+        let fetch = activation
+            .context
+            .navigator
+            .fetch(&url, RequestOptions::get());
+        let process = activation.context.load_manager.load_movie_into_clip(
+            activation.context.player.clone().unwrap(),
+            content.into(),
+            fetch,
+            url.to_string(),
+            None,
+            Some(loader_info),
+        );
+
+        activation.context.navigator.spawn_future(process);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Loader.unload`.
+pub fn unload<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(clip) = loader_clip(this) {
+        remove_content(activation, clip);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `content`.
+///
+/// Content without an AVM2 object, such as an AVM1 movie, reads as `null`.
+pub fn content<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        return Ok(
+            match loader_content(this).map(|content| content.object2()) {
+                Some(Value::Object(content)) => content.into(),
+                _ => Value::Null,
+            },
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `contentLoaderInfo`.
+pub fn content_loader_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &content_loader_info_name(), activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Loader`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Loader"),
+        Some(
+            QName::new(
+                Namespace::package("flash.display"),
+                "DisplayObjectContainer",
+            )
+            .into(),
+        ),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "content"),
+        Method::from_builtin(content),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "contentLoaderInfo"),
+        Method::from_builtin(content_loader_info),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "load"),
+        Method::from_builtin(load),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "unload"),
+        Method::from_builtin(unload),
+    ));
+
+    class
+}
@@ -0,0 +1,145 @@
+//! `flash.display.LoaderInfo` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::display::loader::loader_content;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use crate::display_object::TDisplayObject;
+use gc_arena::{GcCell, MutationContext};
+
+/// The name of the hidden property that holds a `LoaderInfo`'s `Loader`.
+fn loader_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::Private("ruffle".into()), "loader")
+}
+
+/// Create the `LoaderInfo` for a `Loader`.
+pub fn create_loader_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    loader: Object<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let loaderinfo_proto = activation.context.avm2.prototypes().loaderinfo;
+    let mut loader_info = loaderinfo_proto.construct(activation, &[])?;
+
+    loader_info.install_dynamic_property(
+        activation.context.gc_context,
+        loader_name(),
+        loader.into(),
+    )?;
+
+    Ok(loader_info)
+}
+
+/// Implements `flash.display.LoaderInfo`'s instance constructor.
+///
+/// `LoaderInfo` objects are only created by their `Loader`.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Err(Error::avm_error(
+        ErrorClass::ArgumentError,
+        2012,
+        "LoaderInfo class cannot be instantiated.",
+    ))
+}
+
+/// Implements `flash.display.LoaderInfo`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `loader`.
+pub fn loader<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &loader_name(), activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `content`.
+pub fn content<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        if let Value::Object(loader) = this.get_property(this, &loader_name(), activation)? {
+            return Ok(
+                match loader_content(loader).map(|content| content.object2()) {
+                    Some(Value::Object(content)) => content.into(),
+                    _ => Value::Null,
+                },
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `url`.
+///
+/// This is `null` until the loaded movie has arrived.
+pub fn url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        if let Value::Object(loader) = this.get_property(this, &loader_name(), activation)? {
+            let url = loader_content(loader)
+                .and_then(|content| content.movie())
+                .and_then(|movie| movie.url().map(|url| url.to_string()));
+
+            return Ok(match url {
+                Some(url) => AvmString::new(activation.context.gc_context, url).into(),
+                None => Value::Null,
+            });
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `LoaderInfo`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "LoaderInfo"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "content"),
+        Method::from_builtin(content),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "loader"),
+        Method::from_builtin(loader),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "url"),
+        Method::from_builtin(url),
+    ));
+
+    class
+}
@@ -0,0 +1,63 @@
+//! `flash.display.SimpleButton` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{Button, TDisplayObject};
+use crate::tag_utils::SwfSlice;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.SimpleButton`'s instance constructor.
+///
+/// Buttons created by script have no states of their own.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if this.as_display_object().is_none() {
+            let swf_button = swf::Button {
+                id: 0,
+                is_track_as_menu: false,
+                records: Vec::new(),
+                actions: Vec::new(),
+            };
+            let new_do = Button::from_swf_tag(
+                &swf_button,
+                &SwfSlice::empty(activation.context.swf.clone()),
+                &activation.context.library,
+                activation.context.gc_context,
+            );
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+            new_do.set_object2(activation.context.gc_context, this);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.SimpleButton`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `SimpleButton`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    Class::new(
+        QName::new(Namespace::package("flash.display"), "SimpleButton"),
+        Some(QName::new(Namespace::package("flash.display"), "InteractiveObject").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    )
+}
@@ -0,0 +1,117 @@
+//! `flash.display.Stage` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.Stage`'s instance constructor.
+///
+/// There is only ever one stage, which is created by the player.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Err(Error::avm_error(
+        ErrorClass::ArgumentError,
+        2012,
+        "Stage class cannot be instantiated.",
+    ))
+}
+
+/// Implements `flash.display.Stage`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Get the stage object, creating it if it does not yet exist.
+///
+/// The stage has no display object of its own: the display list methods it
+/// inherits from `DisplayObjectContainer` operate on the player's levels.
+pub fn stage_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    if let Some(stage) = activation.context.avm2.stage_object {
+        return Ok(stage);
+    }
+
+    let stage_proto = activation.context.avm2.prototypes().stage;
+    let stage = stage_proto.construct(activation, &[])?;
+
+    activation.context.avm2.stage_object = Some(stage);
+
+    Ok(stage)
+}
+
+/// Implements `stageWidth`.
+pub fn stage_width<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((activation.context.stage_size.0.to_pixels() as i32).into())
+}
+
+/// Implements `stageHeight`.
+pub fn stage_height<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((activation.context.stage_size.1.to_pixels() as i32).into())
+}
+
+/// Implements `frameRate`.
+pub fn frame_rate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let frame_rate: f64 = activation.context.swf.header().frame_rate.into();
+
+    Ok(frame_rate.into())
+}
+
+/// Construct `Stage`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Stage"),
+        Some(
+            QName::new(
+                Namespace::package("flash.display"),
+                "DisplayObjectContainer",
+            )
+            .into(),
+        ),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "stageWidth"),
+        Method::from_builtin(stage_width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "stageHeight"),
+        Method::from_builtin(stage_height),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "frameRate"),
+        Method::from_builtin(frame_rate),
+    ));
+
+    class
+}
@@ -1,4 +1,9 @@
 //! `flash.events` namespace
 
+pub mod event;
 pub mod eventdispatcher;
 pub mod ieventdispatcher;
+pub mod keyboardevent;
+pub mod mouseevent;
+pub mod progressevent;
+pub mod timerevent;
@@ -0,0 +1,538 @@
+//! `flash.events.Event` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::Event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.Event`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let bubbles = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let cancelable = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.set_event_type(event_type);
+            evt.set_bubbles(bubbles);
+            evt.set_cancelable(cancelable);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.Event`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `bubbles` property's getter
+pub fn bubbles<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            return Ok(evt.is_bubbling().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `cancelable` property's getter
+pub fn cancelable<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            return Ok(evt.is_cancelable().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `type` property's getter
+pub fn get_type<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            return Ok(evt.event_type().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `target` property's getter
+pub fn target<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            return Ok(evt.target().map(|o| o.into()).unwrap_or(Value::Null));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `currentTarget` property's getter
+pub fn current_target<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            return Ok(evt
+                .current_target()
+                .map(|o| o.into())
+                .unwrap_or(Value::Null));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `eventPhase` property's getter
+pub fn event_phase<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            let event_phase: u32 = evt.phase().into();
+            return Ok(event_phase.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Copy the base event fields of `this` onto a new instance of the same
+/// class.
+///
+/// The new event has not been dispatched, and has no target or propagation
+/// state.
+pub fn clone_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<Option<Object<'gc>>, Error<'gc>> {
+    let (event_type, bubbles, cancelable) = if let Some(evt) = this.as_event() {
+        (evt.event_type(), evt.is_bubbling(), evt.is_cancelable())
+    } else {
+        return Ok(None);
+    };
+
+    if let Some(proto) = this.proto() {
+        let new_event = proto.construct(activation, &[])?;
+
+        if let Some(mut evt) = new_event.as_event_mut(activation.context.gc_context) {
+            *evt = Event::new(event_type);
+            evt.set_bubbles(bubbles);
+            evt.set_cancelable(cancelable);
+        }
+
+        return Ok(Some(new_event));
+    }
+
+    Ok(None)
+}
+
+/// Implements `clone`
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(new_event) = clone_event(activation, this)? {
+            return Ok(new_event.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `formatToString`
+pub fn format_to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        let class_name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let mut stringified_params = format!("[{}", class_name);
+
+        for param_name in args.iter().skip(1) {
+            let param_name = param_name.clone().coerce_to_string(activation)?;
+            let param_value = this.get_property(
+                this,
+                &QName::new(Namespace::public_namespace(), param_name),
+                activation,
+            )?;
+
+            let param_value = match param_value {
+                Value::String(s) => format!("\"{}\"", s),
+                v => v.coerce_to_string(activation)?.to_string(),
+            };
+
+            stringified_params.push(' ');
+            stringified_params.push_str(&param_name);
+            stringified_params.push('=');
+            stringified_params.push_str(&param_value);
+        }
+
+        stringified_params.push(']');
+
+        return Ok(AvmString::new(activation.context.gc_context, stringified_params).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `isDefaultPrevented`
+pub fn is_default_prevented<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            return Ok(evt.is_cancelled().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `preventDefault`
+pub fn prevent_default<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.cancel();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stopPropagation`
+pub fn stop_propagation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.stop_propagation();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stopImmediatePropagation`
+pub fn stop_immediate_propagation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.stop_immediate_propagation();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let class_name = this
+            .proto()
+            .and_then(|proto| proto.as_proto_class())
+            .map(|c| c.read().name().local_name())
+            .unwrap_or_else(|| "Event".into());
+
+        return format_to_string(
+            activation,
+            Some(this),
+            &[
+                class_name.into(),
+                "type".into(),
+                "bubbles".into(),
+                "cancelable".into(),
+                "eventPhase".into(),
+            ],
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `updateAfterEvent`, which is shared by several event
+/// subclasses.
+pub fn update_after_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    *activation.context.needs_render = true;
+
+    Ok(Value::Undefined)
+}
+
+/// A field stored on instances of an event subclass, given as its name, the
+/// package of its type, and the name of its type.
+pub type EventField = (&'static str, &'static str, &'static str);
+
+/// Define a set of public fields on instances of an event subclass.
+pub fn define_event_fields<'gc>(
+    class: GcCell<'gc, Class<'gc>>,
+    mc: MutationContext<'gc, '_>,
+    fields: &[EventField],
+) {
+    let mut write = class.write(mc);
+
+    for (field, type_package, type_name) in fields {
+        let default_value = match (*type_package, *type_name) {
+            ("", "Number") => Value::Number(0.0),
+            ("", "int") => Value::Integer(0),
+            ("", "uint") => Value::Unsigned(0),
+            ("", "Boolean") => Value::Bool(false),
+            _ => Value::Null,
+        };
+
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), *field),
+            Multiname::from(QName::new(Namespace::package(*type_package), *type_name)),
+            Some(default_value),
+        ));
+    }
+}
+
+/// Initialize the fields of an event subclass from constructor arguments.
+///
+/// Each argument is assigned to the field in the same position; fields
+/// without a corresponding argument keep their default values.
+pub fn init_event_fields<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+    fields: &[EventField],
+) -> Result<(), Error<'gc>> {
+    if let Some(mut this) = this {
+        for ((field, _, _), value) in fields.iter().zip(args.iter()) {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public_namespace(), *field),
+                value.clone(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Clone an instance of an event subclass, including its fields.
+pub fn clone_event_with_fields<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    fields: &[EventField],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        if let Some(mut new_event) = clone_event(activation, this)? {
+            for (field, _, _) in fields {
+                let name = QName::new(Namespace::public_namespace(), *field);
+                let value = this.get_property(this, &name, activation)?;
+
+                new_event.set_property(new_event, &name, value, activation)?;
+            }
+
+            return Ok(new_event.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Define a set of string constants naming event types on an event class.
+pub fn define_event_types<'gc>(
+    class: GcCell<'gc, Class<'gc>>,
+    mc: MutationContext<'gc, '_>,
+    event_types: &[(&'static str, &'static str)],
+) {
+    let mut write = class.write(mc);
+
+    for (const_name, event_type) in event_types {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public_namespace(), *const_name),
+            Multiname::from(QName::new(Namespace::public_namespace(), "String")),
+            Some((*event_type).into()),
+        ));
+    }
+}
+
+/// Construct `Event`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "Event"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "bubbles"),
+        Method::from_builtin(bubbles),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "cancelable"),
+        Method::from_builtin(cancelable),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "type"),
+        Method::from_builtin(get_type),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "target"),
+        Method::from_builtin(target),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "currentTarget"),
+        Method::from_builtin(current_target),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "eventPhase"),
+        Method::from_builtin(event_phase),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "formatToString"),
+        Method::from_builtin(format_to_string),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "isDefaultPrevented"),
+        Method::from_builtin(is_default_prevented),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "preventDefault"),
+        Method::from_builtin(prevent_default),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "stopPropagation"),
+        Method::from_builtin(stop_propagation),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "stopImmediatePropagation"),
+        Method::from_builtin(stop_immediate_propagation),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    drop(write);
+
+    define_event_types(
+        class,
+        mc,
+        &[
+            ("ACTIVATE", "activate"),
+            ("ADDED", "added"),
+            ("ADDED_TO_STAGE", "addedToStage"),
+            ("CANCEL", "cancel"),
+            ("CHANGE", "change"),
+            ("CLEAR", "clear"),
+            ("CLOSE", "close"),
+            ("COMPLETE", "complete"),
+            ("CONNECT", "connect"),
+            ("COPY", "copy"),
+            ("CUT", "cut"),
+            ("DEACTIVATE", "deactivate"),
+            ("ENTER_FRAME", "enterFrame"),
+            ("EXIT_FRAME", "exitFrame"),
+            ("FRAME_CONSTRUCTED", "frameConstructed"),
+            ("FULLSCREEN", "fullScreen"),
+            ("ID3", "id3"),
+            ("INIT", "init"),
+            ("MOUSE_LEAVE", "mouseLeave"),
+            ("OPEN", "open"),
+            ("PASTE", "paste"),
+            ("REMOVED", "removed"),
+            ("REMOVED_FROM_STAGE", "removedFromStage"),
+            ("RENDER", "render"),
+            ("RESIZE", "resize"),
+            ("SCROLL", "scroll"),
+            ("SELECT", "select"),
+            ("SELECT_ALL", "selectAll"),
+            ("SOUND_COMPLETE", "soundComplete"),
+            ("TAB_CHILDREN_CHANGE", "tabChildrenChange"),
+            ("TAB_ENABLED_CHANGE", "tabEnabledChange"),
+            ("TAB_INDEX_CHANGE", "tabIndexChange"),
+            ("UNLOAD", "unload"),
+        ],
+    );
+
+    class
+}
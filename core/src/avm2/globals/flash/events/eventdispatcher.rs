@@ -2,19 +2,25 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
+use crate::avm2::events::{dispatch_event, dispatch_list_name, parent_of};
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{DispatchObject, Object, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Avm2, Error};
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `flash.events.EventDispatcher`'s instance constructor.
 pub fn instance_init<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        dispatch_list(activation, this)?;
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -23,7 +29,219 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Get an object's dispatch list, lazily initializing it if necessary.
+///
+/// Display objects constructed by the player do not run the
+/// `EventDispatcher` constructor, so their dispatch list is created the first
+/// time it is needed.
+fn dispatch_list<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    match this.get_property(this, &dispatch_list_name(), activation)? {
+        Value::Object(o) => Ok(o),
+        _ => {
+            let dispatch_list = DispatchObject::empty_list(activation.context.gc_context);
+            this.install_dynamic_property(
+                activation.context.gc_context,
+                dispatch_list_name(),
+                dispatch_list.into(),
+            )?;
+
+            Ok(dispatch_list)
+        }
+    }
+}
+
+/// Implements `EventDispatcher.addEventListener`.
+pub fn add_event_listener<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let dispatch_list = dispatch_list(activation, this)?;
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let listener = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let use_capture = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let priority = args
+            .get(3)
+            .cloned()
+            .unwrap_or(Value::Integer(0))
+            .coerce_to_i32(activation)?;
+
+        //TODO: If we ever get weak GC references, we should respect `useWeakReference`.
+        dispatch_list
+            .as_dispatch_mut(activation.context.gc_context)
+            .ok_or_else(|| Error::from("Internal dispatch list is missing!"))?
+            .add_event_listener(event_type, priority, listener, use_capture);
+
+        Avm2::register_broadcast_listener(&mut activation.context, this, event_type);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EventDispatcher.removeEventListener`.
+pub fn remove_event_listener<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let dispatch_list = dispatch_list(activation, this)?;
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let listener = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let use_capture = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+
+        let mut dispatch = dispatch_list
+            .as_dispatch_mut(activation.context.gc_context)
+            .ok_or_else(|| Error::from("Internal dispatch list is missing!"))?;
+        dispatch.remove_event_listener(event_type, listener, use_capture);
+        let has_remaining = dispatch.has_event_listener(event_type);
+        drop(dispatch);
+
+        if !has_remaining {
+            Avm2::unregister_broadcast_listener(&mut activation.context, this, event_type);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EventDispatcher.hasEventListener`.
+pub fn has_event_listener<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let dispatch_list = dispatch_list(activation, this)?;
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        return Ok(dispatch_list
+            .as_dispatch()
+            .ok_or_else(|| Error::from("Internal dispatch list is missing!"))?
+            .has_event_listener(event_type)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EventDispatcher.willTrigger`.
+///
+/// This checks both this object and all of its ancestors, since any of them
+/// may receive the event during the capture or bubble phases.
+pub fn will_trigger<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        let mut target = Some(this);
+        while let Some(object) = target {
+            let dispatch_list = dispatch_list(activation, object)?;
+            if dispatch_list
+                .as_dispatch()
+                .ok_or_else(|| Error::from("Internal dispatch list is missing!"))?
+                .has_event_listener(event_type)
+            {
+                return Ok(true.into());
+            }
+
+            target = parent_of(object);
+        }
+
+        return Ok(false.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EventDispatcher.dispatchEvent`.
+///
+/// Events that have already been dispatched are cloned before being
+/// dispatched again, as Flash Player does.
+pub fn dispatch_event_method<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let mut event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let already_dispatched = event
+            .as_event()
+            .ok_or_else(|| Error::from("Dispatched Events must be subclasses of Event."))?
+            .target()
+            .is_some();
+
+        if already_dispatched {
+            let clone = event
+                .get_property(
+                    event,
+                    &QName::new(Namespace::public_namespace(), "clone"),
+                    activation,
+                )?
+                .coerce_to_object(activation)?;
+
+            event = clone
+                .call(Some(event), &[], activation, None)?
+                .coerce_to_object(activation)?;
+
+            if event.as_event().is_none() {
+                return Err("Event.clone must return an instance of Event.".into());
+            }
+        }
+
+        let cancelled = dispatch_event(activation, this, event)?;
+
+        return Ok((!cancelled).into());
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -37,9 +255,32 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         mc,
     );
 
-    class
-        .write(mc)
-        .implements(QName::new(Namespace::package("flash.events"), "IEventDispatcher").into());
+    let mut write = class.write(mc);
+
+    write.implements(QName::new(Namespace::package("flash.events"), "IEventDispatcher").into());
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "addEventListener"),
+        Method::from_builtin(add_event_listener),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "removeEventListener"),
+        Method::from_builtin(remove_event_listener),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "hasEventListener"),
+        Method::from_builtin(has_event_listener),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "willTrigger"),
+        Method::from_builtin(will_trigger),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "dispatchEvent"),
+        Method::from_builtin(dispatch_event_method),
+    ));
+
+    drop(write);
 
     class
 }
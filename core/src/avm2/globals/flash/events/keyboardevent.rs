@@ -0,0 +1,91 @@
+//! `flash.events.KeyboardEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::event::{
+    self, clone_event_with_fields, define_event_fields, define_event_types, init_event_fields,
+    update_after_event, EventField,
+};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The names and types of the fields stored on each `KeyboardEvent`, in the
+/// order they are accepted by the constructor.
+const FIELDS: [EventField; 6] = [
+    ("charCode", "", "uint"),
+    ("keyCode", "", "uint"),
+    ("keyLocation", "", "uint"),
+    ("ctrlKey", "", "Boolean"),
+    ("altKey", "", "Boolean"),
+    ("shiftKey", "", "Boolean"),
+];
+
+/// Implements `flash.events.KeyboardEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let event_args = [
+        args.get(0).cloned().unwrap_or(Value::Undefined),
+        args.get(1).cloned().unwrap_or(Value::Bool(true)),
+        args.get(2).cloned().unwrap_or(Value::Bool(false)),
+    ];
+    event::instance_init(activation, this, &event_args)?;
+    init_event_fields(activation, this, args.get(3..).unwrap_or(&[]), &FIELDS)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.KeyboardEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    clone_event_with_fields(activation, this, &FIELDS)
+}
+
+/// Construct `KeyboardEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "KeyboardEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    define_event_fields(class, mc, &FIELDS);
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "updateAfterEvent"),
+        Method::from_builtin(update_after_event),
+    ));
+
+    drop(write);
+
+    define_event_types(class, mc, &[("KEY_DOWN", "keyDown"), ("KEY_UP", "keyUp")]);
+
+    class
+}
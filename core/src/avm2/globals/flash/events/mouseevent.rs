@@ -0,0 +1,180 @@
+//! `flash.events.MouseEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::event::{
+    self, clone_event_with_fields, define_event_fields, define_event_types, init_event_fields,
+    update_after_event, EventField,
+};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
+use crate::prelude::Twips;
+use gc_arena::{GcCell, MutationContext};
+
+/// The names and types of the fields stored on each `MouseEvent`, in the
+/// order they are accepted by the constructor.
+const FIELDS: [EventField; 8] = [
+    ("localX", "", "Number"),
+    ("localY", "", "Number"),
+    ("relatedObject", "flash.display", "InteractiveObject"),
+    ("ctrlKey", "", "Boolean"),
+    ("altKey", "", "Boolean"),
+    ("shiftKey", "", "Boolean"),
+    ("buttonDown", "", "Boolean"),
+    ("delta", "", "int"),
+];
+
+/// Implements `flash.events.MouseEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bubbles = args.get(1).cloned().unwrap_or(Value::Bool(true));
+    let event_args = [
+        args.get(0).cloned().unwrap_or(Value::Undefined),
+        bubbles,
+        args.get(2).cloned().unwrap_or(Value::Bool(false)),
+    ];
+    event::instance_init(activation, this, &event_args)?;
+    init_event_fields(activation, this, args.get(3..).unwrap_or(&[]), &FIELDS)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.MouseEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Convert this event's local coordinates into stage coordinates.
+fn stage_position<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<(f64, f64), Error<'gc>> {
+    let local_x = this
+        .get_property(
+            this,
+            &QName::new(Namespace::public_namespace(), "localX"),
+            activation,
+        )?
+        .coerce_to_number(activation)?;
+    let local_y = this
+        .get_property(
+            this,
+            &QName::new(Namespace::public_namespace(), "localY"),
+            activation,
+        )?
+        .coerce_to_number(activation)?;
+
+    let target = this
+        .as_event()
+        .and_then(|evt| evt.target())
+        .and_then(|t| t.as_display_object());
+
+    if let Some(target) = target {
+        let (stage_x, stage_y) =
+            target.local_to_global((Twips::from_pixels(local_x), Twips::from_pixels(local_y)));
+
+        return Ok((stage_x.to_pixels(), stage_y.to_pixels()));
+    }
+
+    Ok((local_x, local_y))
+}
+
+/// Implements `stageX`'s getter.
+pub fn stage_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        return Ok(stage_position(activation, this)?.0.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stageY`'s getter.
+pub fn stage_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        return Ok(stage_position(activation, this)?.1.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    clone_event_with_fields(activation, this, &FIELDS)
+}
+
+/// Construct `MouseEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "MouseEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    define_event_fields(class, mc, &FIELDS);
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "stageX"),
+        Method::from_builtin(stage_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "stageY"),
+        Method::from_builtin(stage_y),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "updateAfterEvent"),
+        Method::from_builtin(update_after_event),
+    ));
+
+    drop(write);
+
+    define_event_types(
+        class,
+        mc,
+        &[
+            ("CLICK", "click"),
+            ("DOUBLE_CLICK", "doubleClick"),
+            ("MOUSE_DOWN", "mouseDown"),
+            ("MOUSE_MOVE", "mouseMove"),
+            ("MOUSE_OUT", "mouseOut"),
+            ("MOUSE_OVER", "mouseOver"),
+            ("MOUSE_UP", "mouseUp"),
+            ("MOUSE_WHEEL", "mouseWheel"),
+            ("ROLL_OUT", "rollOut"),
+            ("ROLL_OVER", "rollOver"),
+        ],
+    );
+
+    class
+}
@@ -0,0 +1,588 @@
+//! `flash.text.TextField` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use crate::display_object::{AutoSizeMode, EditText, TDisplayObject};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.text.TextField`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if this.as_display_object().is_none() {
+            let movie = activation.context.swf.clone();
+            let new_do = EditText::new(&mut activation.context, movie, 0.0, 0.0, 100.0, 100.0);
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+            new_do.set_object2(activation.context.gc_context, this);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.TextField`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Get the text field that a `TextField` method was called on.
+fn edit_text(this: Option<Object<'_>>) -> Option<EditText<'_>> {
+    this.and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+}
+
+/// Implements `text`'s getter.
+pub fn text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        return Ok(AvmString::new(activation.context.gc_context, this.text()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `text`'s setter.
+pub fn set_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let text = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        this.set_text(text.to_string(), &mut activation.context)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `htmlText`'s getter.
+pub fn html_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let html_text = this.html_text(&mut activation.context)?;
+
+        return Ok(AvmString::new(activation.context.gc_context, html_text).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `htmlText`'s setter.
+pub fn set_html_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let html_text = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        // Unlike AVM1, setting HTML text always switches the field into HTML
+        // mode.
+        this.set_is_html(&mut activation.context, true);
+        this.set_html_text(html_text.to_string(), &mut activation.context)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `multiline`'s getter.
+pub fn multiline<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        return Ok(this.is_multiline().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `multiline`'s setter.
+pub fn set_multiline<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let is_multiline = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        this.set_multiline(is_multiline, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `selectable`'s getter.
+pub fn selectable<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        return Ok(this.is_selectable().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `selectable`'s setter.
+pub fn set_selectable<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let is_selectable = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        this.set_selectable(is_selectable, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `wordWrap`'s getter.
+pub fn word_wrap<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        return Ok(this.is_word_wrap().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `wordWrap`'s setter.
+pub fn set_word_wrap<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let is_word_wrap = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        this.set_word_wrap(is_word_wrap, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `border`'s getter.
+pub fn border<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        return Ok(this.has_border().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `border`'s setter.
+pub fn set_border<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let has_border = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        this.set_has_border(activation.context.gc_context, has_border);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `displayAsPassword`'s getter.
+pub fn display_as_password<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        return Ok(this.is_password().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `displayAsPassword`'s setter.
+pub fn set_display_as_password<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let is_password = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        this.set_password(is_password, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `mouseWheelEnabled`'s getter.
+pub fn mouse_wheel_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        return Ok(this.is_mouse_wheel_enabled().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `mouseWheelEnabled`'s setter.
+pub fn set_mouse_wheel_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let is_enabled = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        this.set_mouse_wheel_enabled(is_enabled, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `maxChars`'s getter.
+pub fn max_chars<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        return Ok(this.max_chars().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `maxChars`'s setter.
+pub fn set_max_chars<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let max_chars = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        this.set_max_chars(max_chars, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `type`'s getter.
+pub fn field_type<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        return if this.is_editable() {
+            Ok("input".into())
+        } else {
+            Ok("dynamic".into())
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `type`'s setter.
+pub fn set_field_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let field_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        match field_type.as_str() {
+            "input" => this.set_editable(true, activation.context.gc_context),
+            "dynamic" => this.set_editable(false, activation.context.gc_context),
+            _ => {
+                return Err(Error::avm_error(
+                    ErrorClass::ArgumentError,
+                    2008,
+                    "Parameter type must be one of the accepted values.",
+                ))
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `autoSize`'s getter.
+pub fn auto_size<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let auto_size = match this.autosize() {
+            AutoSizeMode::None => "none",
+            AutoSizeMode::Left => "left",
+            AutoSizeMode::Center => "center",
+            AutoSizeMode::Right => "right",
+        };
+
+        return Ok(auto_size.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `autoSize`'s setter.
+pub fn set_auto_size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let auto_size = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let auto_size = match auto_size.as_str() {
+            "none" => AutoSizeMode::None,
+            "left" => AutoSizeMode::Left,
+            "center" => AutoSizeMode::Center,
+            "right" => AutoSizeMode::Right,
+            _ => {
+                return Err(Error::avm_error(
+                    ErrorClass::ArgumentError,
+                    2008,
+                    "Parameter autoSize must be one of the accepted values.",
+                ))
+            }
+        };
+
+        this.set_autosize(auto_size, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `length`.
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        return Ok(this.text_length().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.appendText`.
+pub fn append_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = edit_text(this) {
+        let new_text = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let existing_length = this.text_length();
+
+        this.replace_text(
+            existing_length,
+            existing_length,
+            &new_text,
+            &mut activation.context,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `TextField`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text"), "TextField"),
+        Some(QName::new(Namespace::package("flash.display"), "InteractiveObject").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "text"),
+        Method::from_builtin(text),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "text"),
+        Method::from_builtin(set_text),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "htmlText"),
+        Method::from_builtin(html_text),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "htmlText"),
+        Method::from_builtin(set_html_text),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "multiline"),
+        Method::from_builtin(multiline),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "multiline"),
+        Method::from_builtin(set_multiline),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "selectable"),
+        Method::from_builtin(selectable),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "selectable"),
+        Method::from_builtin(set_selectable),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "wordWrap"),
+        Method::from_builtin(word_wrap),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "wordWrap"),
+        Method::from_builtin(set_word_wrap),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "border"),
+        Method::from_builtin(border),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "border"),
+        Method::from_builtin(set_border),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "displayAsPassword"),
+        Method::from_builtin(display_as_password),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "displayAsPassword"),
+        Method::from_builtin(set_display_as_password),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "mouseWheelEnabled"),
+        Method::from_builtin(mouse_wheel_enabled),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "mouseWheelEnabled"),
+        Method::from_builtin(set_mouse_wheel_enabled),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "maxChars"),
+        Method::from_builtin(max_chars),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "maxChars"),
+        Method::from_builtin(set_max_chars),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "type"),
+        Method::from_builtin(field_type),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "type"),
+        Method::from_builtin(set_field_type),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "autoSize"),
+        Method::from_builtin(auto_size),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::package(""), "autoSize"),
+        Method::from_builtin(set_auto_size),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::package(""), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::package(""), "appendText"),
+        Method::from_builtin(append_text),
+    ));
+
+    class
+}
@@ -0,0 +1,6 @@
+//! `flash.utils` namespace
+
+pub mod bytearray;
+pub mod compression_algorithm;
+pub mod dictionary;
+pub mod endian;
@@ -0,0 +1,812 @@
+//! `flash.utils.ByteArray` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::{CompressionAlgorithm, Endian};
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.utils.ByteArray`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.utils.ByteArray`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Coerce a compression algorithm argument, defaulting to zlib.
+fn compression_algorithm<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<CompressionAlgorithm, Error<'gc>> {
+    match args.get(0) {
+        None | Some(Value::Undefined) | Some(Value::Null) => Ok(CompressionAlgorithm::Zlib),
+        Some(algorithm) => {
+            let name = algorithm.clone().coerce_to_string(activation)?;
+
+            CompressionAlgorithm::from_name(&name).ok_or_else(|| {
+                Error::avm_error(
+                    ErrorClass::ArgumentError,
+                    2008,
+                    "Parameter algorithm must be one of the accepted values.",
+                )
+            })
+        }
+    }
+}
+
+/// Coerce the numeric argument at `index`, if present.
+fn number_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<f64, Error<'gc>> {
+    args.get(index)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)
+}
+
+/// Coerce the string argument at `index`, if present.
+fn string_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<AvmString<'gc>, Error<'gc>> {
+    args.get(index)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)
+}
+
+/// Implements `ByteArray.length`'s getter.
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bytes) = this.and_then(|this| this.as_bytearray().map(|b| b.length())) {
+        return Ok((bytes as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.length`'s setter.
+pub fn set_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let length = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.set_length(length as usize);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.position`'s getter.
+pub fn position<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(position) = this.and_then(|this| this.as_bytearray().map(|b| b.position())) {
+        return Ok((position as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.position`'s setter.
+pub fn set_position<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let position = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.set_position(position as usize);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.bytesAvailable`'s getter.
+pub fn bytes_available<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(available) = this.and_then(|this| this.as_bytearray().map(|b| b.bytes_available()))
+    {
+        return Ok((available as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.endian`'s getter.
+pub fn endian<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(endian) = this.and_then(|this| this.as_bytearray().map(|b| b.endian())) {
+        return Ok(match endian {
+            Endian::Big => "bigEndian".into(),
+            Endian::Little => "littleEndian".into(),
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.endian`'s setter.
+pub fn set_endian<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let endian = match string_arg(activation, args, 0)?.as_str() {
+            "bigEndian" => Endian::Big,
+            "littleEndian" => Endian::Little,
+            _ => {
+                return Err(Error::avm_error(
+                    ErrorClass::ArgumentError,
+                    2008,
+                    "Parameter type must be one of the accepted values.",
+                ))
+            }
+        };
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.set_endian(endian);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.clear();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.compress`.
+pub fn compress<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let algorithm = compression_algorithm(activation, args)?;
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.compress(algorithm)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.uncompress`.
+pub fn uncompress<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let algorithm = compression_algorithm(activation, args)?;
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.uncompress(algorithm)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.deflate`.
+pub fn deflate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    compress(activation, this, &["deflate".into()])
+}
+
+/// Implements `ByteArray.inflate`.
+pub fn inflate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    uncompress(activation, this, &["deflate".into()])
+}
+
+/// Implements `ByteArray.toString`.
+///
+/// The bytes are decoded as UTF-8, skipping a leading byte order mark.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(bytes) = this.as_bytearray() {
+            let bytes = bytes.bytes();
+            let bytes = if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+                &bytes[3..]
+            } else {
+                bytes
+            };
+
+            return Ok(AvmString::new(
+                activation.context.gc_context,
+                String::from_utf8_lossy(bytes).into_owned(),
+            )
+            .into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readBytes`.
+///
+/// Bytes are read from this array into the given array, starting at
+/// `offset`. A `length` of zero reads every available byte.
+pub fn read_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let target = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let offset = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)? as usize;
+        let length = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)? as usize;
+
+        let data = if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            let length = if length == 0 {
+                bytes.bytes_available()
+            } else {
+                length
+            };
+
+            bytes.read_bytes(length)?.to_vec()
+        } else {
+            return Ok(Value::Undefined);
+        };
+
+        if let Some(mut target) = target.as_bytearray_mut(activation.context.gc_context) {
+            let position = target.position();
+
+            target.set_position(offset);
+            target.write_bytes(&data);
+            target.set_position(position);
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeBytes`.
+///
+/// Bytes are copied from the given array, starting at `offset`. A `length` of
+/// zero copies everything after the offset.
+pub fn write_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let source = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let offset = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)? as usize;
+        let length = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)? as usize;
+
+        let data = if let Some(source) = source.as_bytearray() {
+            let source = source.bytes();
+
+            if offset > source.len() {
+                return Err(Error::avm_error(
+                    ErrorClass::RangeError,
+                    2006,
+                    "The supplied index is out of bounds.",
+                ));
+            }
+
+            let end = if length == 0 {
+                source.len()
+            } else {
+                offset + length
+            };
+
+            if end > source.len() {
+                return Err(Error::avm_error(
+                    ErrorClass::RangeError,
+                    2006,
+                    "The supplied index is out of bounds.",
+                ));
+            }
+
+            source[offset..end].to_vec()
+        } else {
+            return Ok(Value::Undefined);
+        };
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.write_bytes(&data);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Define a `ByteArray` read method in terms of a `ByteArrayStorage` read
+/// method and a conversion of its result into a `Value`.
+macro_rules! read_method {
+    ($(#[$attr:meta])* $name:ident, $storage_method:ident, $convert:expr) => {
+        $(#[$attr])*
+        pub fn $name<'gc>(
+            activation: &mut Activation<'_, 'gc, '_>,
+            this: Option<Object<'gc>>,
+            _args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error<'gc>> {
+            if let Some(this) = this {
+                if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+                    let value = bytes.$storage_method()?;
+
+                    #[allow(clippy::redundant_closure_call)]
+                    return Ok(($convert)(value));
+                }
+            }
+
+            Ok(Value::Undefined)
+        }
+    };
+}
+
+read_method!(
+    /// Implements `ByteArray.readBoolean`.
+    read_boolean,
+    read_boolean,
+    |v: bool| v.into()
+);
+read_method!(
+    /// Implements `ByteArray.readByte`.
+    read_byte,
+    read_byte,
+    |v: i8| (v as i32).into()
+);
+read_method!(
+    /// Implements `ByteArray.readUnsignedByte`.
+    read_unsigned_byte,
+    read_unsigned_byte,
+    |v: u8| (v as u32).into()
+);
+read_method!(
+    /// Implements `ByteArray.readShort`.
+    read_short,
+    read_short,
+    |v: i16| (v as i32).into()
+);
+read_method!(
+    /// Implements `ByteArray.readUnsignedShort`.
+    read_unsigned_short,
+    read_unsigned_short,
+    |v: u16| (v as u32).into()
+);
+read_method!(
+    /// Implements `ByteArray.readInt`.
+    read_int,
+    read_int,
+    |v: i32| v.into()
+);
+read_method!(
+    /// Implements `ByteArray.readUnsignedInt`.
+    read_unsigned_int,
+    read_unsigned_int,
+    |v: u32| v.into()
+);
+read_method!(
+    /// Implements `ByteArray.readFloat`.
+    read_float,
+    read_float,
+    |v: f32| (v as f64).into()
+);
+read_method!(
+    /// Implements `ByteArray.readDouble`.
+    read_double,
+    read_double,
+    |v: f64| v.into()
+);
+
+/// Implements `ByteArray.writeBoolean`.
+pub fn write_boolean<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.write_boolean(value);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeByte`.
+pub fn write_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.write_byte(value as u8);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeShort`.
+pub fn write_short<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.write_short(value as u16);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeInt`.
+pub fn write_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.write_int(value as u32);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeUnsignedInt`.
+pub fn write_unsigned_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.write_int(value);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeFloat`.
+pub fn write_float<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let value = number_arg(activation, args, 0)?;
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.write_float(value as f32);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeDouble`.
+pub fn write_double<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let value = number_arg(activation, args, 0)?;
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.write_double(value);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUTF`.
+pub fn read_utf<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            let string = bytes.read_utf()?;
+
+            return Ok(AvmString::new(activation.context.gc_context, string).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUTFBytes`.
+pub fn read_utf_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let length = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            let string = bytes.read_utf_bytes(length as usize)?;
+
+            return Ok(AvmString::new(activation.context.gc_context, string).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeUTF`.
+pub fn write_utf<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let string = string_arg(activation, args, 0)?;
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.write_utf(&string)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeUTFBytes`.
+pub fn write_utf_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let string = string_arg(activation, args, 0)?;
+
+        if let Some(mut bytes) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytes.write_utf_bytes(&string);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readMultiByte`.
+///
+/// Only UTF-8 is supported; other character sets are decoded as UTF-8.
+pub fn read_multi_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    read_utf_bytes(activation, this, &args[..args.len().min(1)])
+}
+
+/// Implements `ByteArray.writeMultiByte`.
+///
+/// Only UTF-8 is supported; other character sets are encoded as UTF-8.
+pub fn write_multi_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    write_utf_bytes(activation, this, &args[..args.len().min(1)])
+}
+
+/// Construct `ByteArray`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "ByteArray"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::Sealed.into());
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "length"),
+        Method::from_builtin(set_length),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "position"),
+        Method::from_builtin(position),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "position"),
+        Method::from_builtin(set_position),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "bytesAvailable"),
+        Method::from_builtin(bytes_available),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "endian"),
+        Method::from_builtin(endian),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "endian"),
+        Method::from_builtin(set_endian),
+    ));
+
+    for (name, method) in [
+        ("clear", clear as NativeMethod<'gc>),
+        ("compress", compress),
+        ("uncompress", uncompress),
+        ("deflate", deflate),
+        ("inflate", inflate),
+        ("toString", to_string),
+        ("readBytes", read_bytes),
+        ("writeBytes", write_bytes),
+        ("readBoolean", read_boolean),
+        ("readByte", read_byte),
+        ("readUnsignedByte", read_unsigned_byte),
+        ("readShort", read_short),
+        ("readUnsignedShort", read_unsigned_short),
+        ("readInt", read_int),
+        ("readUnsignedInt", read_unsigned_int),
+        ("readFloat", read_float),
+        ("readDouble", read_double),
+        ("readUTF", read_utf),
+        ("readUTFBytes", read_utf_bytes),
+        ("readMultiByte", read_multi_byte),
+        ("writeBoolean", write_boolean),
+        ("writeByte", write_byte),
+        ("writeShort", write_short),
+        ("writeInt", write_int),
+        ("writeUnsignedInt", write_unsigned_int),
+        ("writeFloat", write_float),
+        ("writeDouble", write_double),
+        ("writeUTF", write_utf),
+        ("writeUTFBytes", write_utf_bytes),
+        ("writeMultiByte", write_multi_byte),
+    ]
+    .iter()
+    {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), *name),
+            Method::from_builtin(*method),
+        ));
+    }
+
+    drop(write);
+
+    class
+}
@@ -0,0 +1,63 @@
+//! `flash.utils.Endian` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.utils.Endian`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Err(Error::avm_error(
+        ErrorClass::ArgumentError,
+        2012,
+        "Endian class cannot be instantiated.",
+    ))
+}
+
+/// Implements `flash.utils.Endian`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `Endian`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "Endian"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::Final | ClassAttributes::Sealed);
+
+    for (name, value) in &[
+        ("BIG_ENDIAN", "bigEndian"),
+        ("LITTLE_ENDIAN", "littleEndian"),
+    ] {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public_namespace(), *name),
+            Multiname::from(QName::new(Namespace::public_namespace(), "String")),
+            Some((*value).into()),
+        ));
+    }
+
+    drop(write);
+
+    class
+}
@@ -0,0 +1,413 @@
+//! `JSON` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::array::build_array;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use gc_arena::{GcCell, MutationContext};
+use json::JsonValue;
+
+/// Implements `JSON`'s instance initializer.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Err(Error::avm_error(
+        ErrorClass::ArgumentError,
+        2012,
+        "JSON class cannot be instantiated.",
+    ))
+}
+
+/// Implements `JSON`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Convert a parsed JSON value into an AVM2 value.
+fn deserialize<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    json: &JsonValue,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match json {
+        JsonValue::Null => Value::Null,
+        JsonValue::Boolean(b) => (*b).into(),
+        JsonValue::Number(n) => f64::from(*n).into(),
+        JsonValue::Short(s) => AvmString::new(activation.context.gc_context, s.as_str()).into(),
+        JsonValue::String(s) => AvmString::new(activation.context.gc_context, s.as_str()).into(),
+        JsonValue::Array(values) => {
+            let mut storage = ArrayStorage::new(0);
+
+            for value in values {
+                storage.push(deserialize(activation, value)?);
+            }
+
+            build_array(activation, storage)?
+        }
+        JsonValue::Object(entries) => {
+            let object_proto = activation.context.avm2.prototypes().object;
+            let mut object = ScriptObject::object(activation.context.gc_context, object_proto);
+
+            for (key, value) in entries.iter() {
+                let value = deserialize(activation, value)?;
+
+                object.set_property(
+                    object,
+                    &QName::dynamic_name(AvmString::new(activation.context.gc_context, key)),
+                    value,
+                    activation,
+                )?;
+            }
+
+            object.into()
+        }
+    })
+}
+
+/// Run a reviver function over a freshly parsed value, bottom-up.
+fn revive<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    reviver: Object<'gc>,
+    mut holder: Object<'gc>,
+    key: AvmString<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = QName::dynamic_name(key);
+    let value = holder.get_property(holder, &name, activation)?;
+
+    if let Value::Object(mut object) = value {
+        let mut keys = Vec::new();
+        let mut index = 1;
+
+        while let Some(name) = object.get_enumerant_name(index) {
+            keys.push(name);
+            index += 1;
+        }
+
+        for name in keys {
+            let new_value = revive(activation, reviver, object, name.local_name())?;
+
+            if matches!(new_value, Value::Undefined) {
+                object.delete_property(activation.context.gc_context, &name);
+            } else {
+                object.set_property(object, &name, new_value, activation)?;
+            }
+        }
+    }
+
+    reviver.call(Some(holder), &[key.into(), value], activation, None)
+}
+
+/// Implements `JSON.parse`
+pub fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let json = json::parse(&text).map_err(|_| {
+        Error::avm_error(ErrorClass::SyntaxError, 1132, "Invalid JSON parse input.")
+    })?;
+    let value = deserialize(activation, &json)?;
+
+    match args.get(1) {
+        Some(Value::Object(reviver)) => {
+            let object_proto = activation.context.avm2.prototypes().object;
+            let mut holder = ScriptObject::object(activation.context.gc_context, object_proto);
+
+            holder.set_property(holder, &QName::dynamic_name(""), value, activation)?;
+
+            revive(activation, *reviver, holder, "".into())
+        }
+        _ => Ok(value),
+    }
+}
+
+/// Quote a string for inclusion in JSON output.
+fn quote(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+
+    quoted.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\u{8}' => quoted.push_str("\\b"),
+            '\u{c}' => quoted.push_str("\\f"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+/// The state of a `JSON.stringify` call.
+struct Serializer<'gc> {
+    /// A function that replaces values before they are serialized.
+    replacer_function: Option<Object<'gc>>,
+
+    /// The keys that are allowed to be serialized, if restricted.
+    allowed_keys: Option<Vec<String>>,
+
+    /// The string each level of nesting is indented by.
+    gap: String,
+
+    /// The objects currently being serialized, used to detect cycles.
+    stack: Vec<Object<'gc>>,
+}
+
+impl<'gc> Serializer<'gc> {
+    /// Serialize a property of `holder`, returning `None` if it should be
+    /// omitted.
+    fn serialize_property(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        holder: Object<'gc>,
+        key: AvmString<'gc>,
+        mut value: Value<'gc>,
+        indent: &str,
+    ) -> Result<Option<String>, Error<'gc>> {
+        if let Value::Object(mut object) = value {
+            let to_json =
+                object.get_property(object, &QName::dynamic_name("toJSON"), activation)?;
+
+            if let Value::Object(to_json) = to_json {
+                if to_json.as_executable().is_some() {
+                    value = to_json.call(Some(object), &[key.into()], activation, None)?;
+                }
+            }
+        }
+
+        if let Some(replacer) = self.replacer_function {
+            value = replacer.call(Some(holder), &[key.into(), value], activation, None)?;
+        }
+
+        Ok(match value {
+            Value::Undefined => None,
+            Value::Null => Some("null".to_string()),
+            Value::Bool(b) => Some(b.to_string()),
+            Value::Number(_) | Value::Integer(_) | Value::Unsigned(_) => {
+                let number = value.coerce_to_number(activation)?;
+
+                if number.is_finite() {
+                    Some(value.coerce_to_string(activation)?.to_string())
+                } else {
+                    Some("null".to_string())
+                }
+            }
+            Value::String(s) => Some(quote(&s)),
+            Value::Object(object) => {
+                if object.as_executable().is_some() {
+                    None
+                } else if self.stack.iter().any(|o| Object::ptr_eq(*o, object)) {
+                    return Err(Error::avm_error(
+                        ErrorClass::TypeError,
+                        1129,
+                        "Cyclic structure cannot be converted to JSON string.",
+                    ));
+                } else {
+                    self.stack.push(object);
+
+                    let result = if object.as_array_storage().is_some()
+                        || object.as_vector_storage().is_some()
+                    {
+                        self.serialize_array(activation, object, indent)
+                    } else {
+                        self.serialize_object(activation, object, indent)
+                    };
+
+                    self.stack.pop();
+
+                    Some(result?)
+                }
+            }
+        })
+    }
+
+    /// Join serialized members into a bracketed list, indenting if requested.
+    fn join(&self, members: Vec<String>, open: char, close: char, indent: &str) -> String {
+        if members.is_empty() {
+            format!("{}{}", open, close)
+        } else if self.gap.is_empty() {
+            format!("{}{}{}", open, members.join(","), close)
+        } else {
+            let inner = format!("{}{}", indent, self.gap);
+
+            format!(
+                "{}\n{}{}\n{}{}",
+                open,
+                inner,
+                members.join(&format!(",\n{}", inner)),
+                indent,
+                close
+            )
+        }
+    }
+
+    fn serialize_array(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut array: Object<'gc>,
+        indent: &str,
+    ) -> Result<String, Error<'gc>> {
+        let length = array
+            .get_property(
+                array,
+                &QName::new(Namespace::public_namespace(), "length"),
+                activation,
+            )?
+            .coerce_to_u32(activation)?;
+        let inner = format!("{}{}", indent, self.gap);
+        let mut members = Vec::with_capacity(length as usize);
+
+        for i in 0..length {
+            let key = AvmString::new(activation.context.gc_context, i.to_string());
+            let value = array.get_property(array, &QName::dynamic_name(key), activation)?;
+            let member = self.serialize_property(activation, array, key, value, &inner)?;
+
+            members.push(member.unwrap_or_else(|| "null".to_string()));
+        }
+
+        Ok(self.join(members, '[', ']', indent))
+    }
+
+    fn serialize_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+        indent: &str,
+    ) -> Result<String, Error<'gc>> {
+        let inner = format!("{}{}", indent, self.gap);
+        let separator = if self.gap.is_empty() { ":" } else { ": " };
+        let mut names = Vec::new();
+        let mut index = 1;
+
+        while let Some(name) = object.get_enumerant_name(index) {
+            names.push(name);
+            index += 1;
+        }
+
+        let mut members = Vec::with_capacity(names.len());
+
+        for name in names {
+            let key = name.local_name();
+
+            if let Some(allowed_keys) = &self.allowed_keys {
+                if !allowed_keys.iter().any(|k| k.as_str() == key.as_str()) {
+                    continue;
+                }
+            }
+
+            let value = object.get_property(object, &name, activation)?;
+
+            if let Some(member) = self.serialize_property(activation, object, key, value, &inner)? {
+                members.push(format!("{}{}{}", quote(&key), separator, member));
+            }
+        }
+
+        Ok(self.join(members, '{', '}', indent))
+    }
+}
+
+/// Implements `JSON.stringify`
+pub fn stringify<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let mut replacer_function = None;
+    let mut allowed_keys = None;
+
+    if let Some(Value::Object(replacer)) = args.get(1) {
+        if replacer.as_executable().is_some() {
+            replacer_function = Some(*replacer);
+        } else if let Some(storage) = replacer.as_array_storage() {
+            let values: Vec<Value<'gc>> = storage.iter().flatten().collect();
+            drop(storage);
+
+            let mut keys = Vec::with_capacity(values.len());
+            for value in values {
+                keys.push(value.coerce_to_string(activation)?.to_string());
+            }
+
+            allowed_keys = Some(keys);
+        } else {
+            return Err(Error::avm_error(ErrorClass::TypeError, 1131, "Replacer argument to JSON stringifier must be an array or a two parameter function."));
+        }
+    }
+
+    let gap = match args.get(2).cloned().unwrap_or(Value::Undefined) {
+        Value::Undefined | Value::Null => String::new(),
+        space @ Value::String(_) => space
+            .coerce_to_string(activation)?
+            .chars()
+            .take(10)
+            .collect(),
+        space => " ".repeat(space.coerce_to_i32(activation)?.max(0).min(10) as usize),
+    };
+
+    let object_proto = activation.context.avm2.prototypes().object;
+    let mut holder = ScriptObject::object(activation.context.gc_context, object_proto);
+    holder.set_property(holder, &QName::dynamic_name(""), value.clone(), activation)?;
+
+    let mut serializer = Serializer {
+        replacer_function,
+        allowed_keys,
+        gap,
+        stack: Vec::new(),
+    };
+
+    match serializer.serialize_property(activation, holder, "".into(), value, "")? {
+        Some(string) => Ok(AvmString::new(activation.context.gc_context, string).into()),
+        None => Ok(Value::Undefined),
+    }
+}
+
+/// Construct `JSON`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public_namespace(), "JSON"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::Final | ClassAttributes::Sealed);
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "parse"),
+        Method::from_builtin(parse),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "stringify"),
+        Method::from_builtin(stringify),
+    ));
+
+    drop(write);
+
+    class
+}
@@ -15,7 +15,7 @@ pub fn instance_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
@@ -24,7 +24,7 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
@@ -33,7 +33,7 @@ fn to_string<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(this
         .map(|t| t.to_string(activation.context.gc_context))
         .unwrap_or(Ok(Value::Undefined))?)
@@ -44,7 +44,7 @@ fn to_locale_string<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(this
         .map(|t| t.to_locale_string(activation.context.gc_context))
         .unwrap_or(Ok(Value::Undefined))?)
@@ -55,7 +55,7 @@ fn value_of<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(this
         .map(|t| t.value_of(activation.context.gc_context))
         .unwrap_or(Ok(Value::Undefined))?)
@@ -66,10 +66,12 @@ pub fn has_own_property<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
-    let this: Result<Object<'gc>, Error> = this.ok_or_else(|| "No valid this parameter".into());
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this: Result<Object<'gc>, Error<'gc>> =
+        this.ok_or_else(|| "No valid this parameter".into());
     let this = this?;
-    let name: Result<&Value<'gc>, Error> = args.get(0).ok_or_else(|| "No name specified".into());
+    let name: Result<&Value<'gc>, Error<'gc>> =
+        args.get(0).ok_or_else(|| "No name specified".into());
     let name = name?.coerce_to_string(activation)?;
 
     if let Some(ns) = this.resolve_any(name)? {
@@ -87,8 +89,8 @@ pub fn is_prototype_of<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
-    let search_proto: Result<Object<'gc>, Error> =
+) -> Result<Value<'gc>, Error<'gc>> {
+    let search_proto: Result<Object<'gc>, Error<'gc>> =
         this.ok_or_else(|| "No valid this parameter".into());
     let search_proto = search_proto?;
     let mut target_proto = args.get(0).cloned().unwrap_or(Value::Undefined);
@@ -109,10 +111,12 @@ pub fn property_is_enumerable<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
-    let this: Result<Object<'gc>, Error> = this.ok_or_else(|| "No valid this parameter".into());
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this: Result<Object<'gc>, Error<'gc>> =
+        this.ok_or_else(|| "No valid this parameter".into());
     let this = this?;
-    let name: Result<&Value<'gc>, Error> = args.get(0).ok_or_else(|| "No name specified".into());
+    let name: Result<&Value<'gc>, Error<'gc>> =
+        args.get(0).ok_or_else(|| "No name specified".into());
     let name = name?.coerce_to_string(activation)?;
 
     if let Some(ns) = this.resolve_any(name)? {
@@ -130,10 +134,12 @@ pub fn set_property_is_enumerable<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
-    let this: Result<Object<'gc>, Error> = this.ok_or_else(|| "No valid this parameter".into());
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this: Result<Object<'gc>, Error<'gc>> =
+        this.ok_or_else(|| "No valid this parameter".into());
     let this = this?;
-    let name: Result<&Value<'gc>, Error> = args.get(0).ok_or_else(|| "No name specified".into());
+    let name: Result<&Value<'gc>, Error<'gc>> =
+        args.get(0).ok_or_else(|| "No name specified".into());
     let name = name?.coerce_to_string(activation)?;
 
     if let Some(Value::Bool(is_enum)) = args.get(1) {
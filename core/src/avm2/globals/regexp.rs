@@ -0,0 +1,331 @@
+//! `RegExp` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::globals::array::build_array;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::regexp::{RegExp, RegExpFlag};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `RegExp`'s instance initializer.
+///
+/// If the pattern is itself a `RegExp`, its source and flags are copied; in
+/// that case, providing flags is an error.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let pattern = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let flags = args.get(1).cloned().unwrap_or(Value::Undefined);
+
+        let existing = match &pattern {
+            Value::Object(o) => o
+                .as_regexp()
+                .map(|regexp| (regexp.source(), regexp.flags())),
+            _ => None,
+        };
+
+        let (source, flags) = if let Some((source, existing_flags)) = existing {
+            if flags != Value::Undefined {
+                return Err(Error::avm_error(
+                    ErrorClass::TypeError,
+                    1100,
+                    "Cannot supply flags when constructing one RegExp from another.",
+                ));
+            }
+
+            (source, existing_flags)
+        } else {
+            let source = match pattern {
+                Value::Undefined => "".into(),
+                pattern => pattern.coerce_to_string(activation)?,
+            };
+            let flags = match flags {
+                Value::Undefined => "".into(),
+                flags => flags.coerce_to_string(activation)?,
+            };
+
+            (source, RegExpFlag::parse(&flags))
+        };
+
+        let regexp = RegExp::new(source, flags)
+            .map_err(|e| Error::avm_error(ErrorClass::SyntaxError, 1100, e))?;
+
+        if let Some(mut this_regexp) = this.as_regexp_mut(activation.context.gc_context) {
+            *this_regexp = regexp;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.source`
+pub fn source<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(regexp) = this.as_regexp() {
+            return Ok(regexp.source().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Define a getter that reports whether a `RegExp` has a given flag set.
+macro_rules! flag_getter {
+    ($(#[$attr:meta])* $name:ident, $flag:ident) => {
+        $(#[$attr])*
+        pub fn $name<'gc>(
+            _activation: &mut Activation<'_, 'gc, '_>,
+            this: Option<Object<'gc>>,
+            _args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error<'gc>> {
+            if let Some(this) = this {
+                if let Some(regexp) = this.as_regexp() {
+                    return Ok(regexp.flags().contains(RegExpFlag::$flag).into());
+                }
+            }
+
+            Ok(Value::Undefined)
+        }
+    };
+}
+
+flag_getter!(
+    /// Implements `RegExp.global`
+    global,
+    Global
+);
+flag_getter!(
+    /// Implements `RegExp.ignoreCase`
+    ignore_case,
+    IgnoreCase
+);
+flag_getter!(
+    /// Implements `RegExp.multiline`
+    multiline,
+    Multiline
+);
+flag_getter!(
+    /// Implements `RegExp.dotall`
+    dotall,
+    DotAll
+);
+flag_getter!(
+    /// Implements `RegExp.extended`
+    extended,
+    Extended
+);
+
+/// Implements `RegExp.lastIndex`'s getter
+pub fn last_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(regexp) = this.as_regexp() {
+            return Ok((regexp.last_index() as i32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.lastIndex`'s setter
+pub fn set_last_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let last_index = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?
+            .max(0);
+
+        if let Some(mut regexp) = this.as_regexp_mut(activation.context.gc_context) {
+            regexp.set_last_index(last_index as usize);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.exec`
+///
+/// Successful matches yield an array of the match and its captures, with
+/// `index` and `input` properties. Failed matches yield `null`.
+pub fn exec<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let input = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let chars: Vec<char> = input.chars().collect();
+
+        let result = match this.as_regexp_mut(activation.context.gc_context) {
+            Some(mut regexp) => regexp.exec(&chars),
+            None => return Ok(Value::Undefined),
+        };
+
+        let result = match result {
+            Some(result) => result,
+            None => return Ok(Value::Null),
+        };
+
+        let mut storage = ArrayStorage::new(0);
+        for capture in result.captures.iter() {
+            storage.push(match capture {
+                Some((start, end)) => AvmString::new(
+                    activation.context.gc_context,
+                    chars[*start..*end].iter().collect::<String>(),
+                )
+                .into(),
+                None => Value::Undefined,
+            });
+        }
+
+        let mut array = build_array(activation, storage)?.coerce_to_object(activation)?;
+        array.set_property(
+            array,
+            &QName::new(Namespace::public_namespace(), "index"),
+            (result.start() as u32).into(),
+            activation,
+        )?;
+        array.set_property(
+            array,
+            &QName::new(Namespace::public_namespace(), "input"),
+            input.into(),
+            activation,
+        )?;
+
+        return Ok(array.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.test`
+pub fn test<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let input = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let chars: Vec<char> = input.chars().collect();
+
+        if let Some(mut regexp) = this.as_regexp_mut(activation.context.gc_context) {
+            return Ok(regexp.exec(&chars).is_some().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.toString`
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(regexp) = this.as_regexp() {
+            return Ok(AvmString::new(
+                activation.context.gc_context,
+                format!(
+                    "/{}/{}",
+                    regexp.source(),
+                    RegExpFlag::flags_to_string(regexp.flags())
+                ),
+            )
+            .into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `RegExp`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public_namespace(), "RegExp"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for (name, getter) in [
+        ("source", source as NativeMethod<'gc>),
+        ("global", global),
+        ("ignoreCase", ignore_case),
+        ("multiline", multiline),
+        ("dotall", dotall),
+        ("extended", extended),
+        ("lastIndex", last_index),
+    ]
+    .iter()
+    {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), *name),
+            Method::from_builtin(*getter),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "lastIndex"),
+        Method::from_builtin(set_last_index),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "exec"),
+        Method::from_builtin(exec),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "test"),
+        Method::from_builtin(test),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    drop(write);
+
+    class
+}
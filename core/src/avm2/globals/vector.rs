@@ -0,0 +1,914 @@
+//! `Vector` class
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::array::{
+    compare_numeric, compare_string_case_insensitive, compare_string_case_sensitive, constrain,
+    resolve_index, sort_inner, ArrayIter, SortOptions,
+};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{FunctionObject, Object, TObject, VectorObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use enumset::EnumSet;
+use gc_arena::{GcCell, MutationContext};
+use std::cmp::{min, Ordering};
+
+/// Implements `Vector`'s instance initializer.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let length = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)?;
+        let is_fixed = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            vector.set_length(length as usize)?;
+            vector.set_is_fixed(is_fixed);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Determine the value that new elements of a vector of the given type take.
+fn default_value_of<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value_type: Option<Object<'gc>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut value_type = match value_type {
+        Some(value_type) => value_type,
+        None => return Ok(Value::Undefined),
+    };
+    let type_proto = value_type
+        .get_property(value_type, &QName::dynamic_name("prototype"), activation)?
+        .coerce_to_object(activation)?;
+    let prototypes = activation.context.avm2.prototypes().clone();
+
+    Ok(if Object::ptr_eq(type_proto, prototypes.int) {
+        Value::Integer(0)
+    } else if Object::ptr_eq(type_proto, prototypes.uint) {
+        Value::Unsigned(0)
+    } else if Object::ptr_eq(type_proto, prototypes.number) {
+        Value::Number(0.0)
+    } else if Object::ptr_eq(type_proto, prototypes.boolean) {
+        false.into()
+    } else {
+        Value::Null
+    })
+}
+
+/// Retrieve the specialization of `Vector` for a given value type, creating
+/// it if it does not exist yet.
+///
+/// This implements the `applytype` opcode for vectors. A value type of `None`
+/// yields `Vector.<*>`.
+pub fn specialize<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    vector_class: Object<'gc>,
+    value_type: Option<Object<'gc>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let existing = activation
+        .context
+        .avm2
+        .vector_classes
+        .iter()
+        .find(|(t, _)| match (t, value_type) {
+            (Some(t), Some(value_type)) => Object::ptr_eq(*t, value_type),
+            (None, None) => true,
+            _ => false,
+        })
+        .map(|(_, class)| *class);
+
+    if let Some(existing) = existing {
+        return Ok(existing);
+    }
+
+    let mc = activation.context.gc_context;
+    let type_name = value_type
+        .and_then(|t| t.as_class())
+        .map(|c| c.read().name().local_name().to_string())
+        .unwrap_or_else(|| "*".to_string());
+    let class = Class::new(
+        QName::new(
+            Namespace::package("__AS3__.vec"),
+            AvmString::new(mc, format!("Vector.<{}>", type_name)),
+        ),
+        Some(QName::new(Namespace::package("__AS3__.vec"), "Vector").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    class
+        .write(mc)
+        .set_attributes(ClassAttributes::Final.into());
+
+    let default_value = default_value_of(activation, value_type)?;
+    let scope = vector_class.get_scope();
+    let (constr, _cinit) = FunctionObject::from_class_with_deriver(
+        activation,
+        class,
+        Some(vector_class),
+        scope,
+        |base_proto, activation, class, scope| {
+            VectorObject::derive(
+                base_proto,
+                activation.context.gc_context,
+                class,
+                scope,
+                value_type,
+                default_value,
+            )
+        },
+    )?;
+
+    activation
+        .context
+        .avm2
+        .vector_classes
+        .push((value_type, constr));
+
+    Ok(constr)
+}
+
+/// Coerce a value to the type of values a vector holds.
+fn coerce_to_value_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    value: Value<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value_type = this.as_vector_storage().and_then(|v| v.value_type());
+
+    if let Some(value_type) = value_type {
+        activation.coerce_to_type(value, value_type)
+    } else {
+        Ok(value)
+    }
+}
+
+/// Bundle new vector storage of the same type as `this` in an `Object`.
+fn build_vector<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    values: Vec<Value<'gc>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let vector_proto: Result<Object<'gc>, Error<'gc>> = this
+        .proto()
+        .ok_or_else(|| "Vector instance has no prototype".into());
+    let mut vector = this
+        .as_vector_storage()
+        .map(|v| v.new_of_same_type())
+        .ok_or("Cannot build a vector from a non-vector")?;
+
+    vector.replace_storage(values);
+
+    Ok(VectorObject::from_vector(vector, vector_proto?, activation.context.gc_context).into())
+}
+
+/// Implements `Vector.length`'s getter
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(vector) = this.as_vector_storage() {
+            return Ok((vector.length() as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.length`'s setter
+pub fn set_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let new_length = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            vector.set_length(new_length as usize)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.fixed`'s getter
+pub fn fixed<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(vector) = this.as_vector_storage() {
+            return Ok(vector.is_fixed().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.fixed`'s setter
+pub fn set_fixed<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let is_fixed = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            vector.set_is_fixed(is_fixed);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.concat`
+pub fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let mut values: Vec<Value<'gc>> = match this.as_vector_storage() {
+            Some(vector) => vector.iter().collect(),
+            None => return Ok(Value::Undefined),
+        };
+
+        for arg in args {
+            let other = arg.coerce_to_object(activation)?;
+            let other_values: Vec<Value<'gc>> = match other.as_vector_storage() {
+                Some(vector) => vector.iter().collect(),
+                None => {
+                    return Err(Error::avm_error(
+                        ErrorClass::TypeError,
+                        1034,
+                        format!(
+                            "Type Coercion failed: cannot convert {} to Vector.",
+                            arg.coerce_to_string(activation)?
+                        ),
+                    ))
+                }
+            };
+
+            for value in other_values {
+                values.push(coerce_to_value_type(activation, this, value)?);
+            }
+        }
+
+        return build_vector(activation, this, values);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.join`
+pub fn join<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let mut separator = args.get(0).cloned().unwrap_or(Value::Undefined);
+        if separator == Value::Undefined {
+            separator = ",".into();
+        }
+
+        let values: Vec<Value<'gc>> = match this.as_vector_storage() {
+            Some(vector) => vector.iter().collect(),
+            None => return Ok(Value::Undefined),
+        };
+        let string_separator = separator.coerce_to_string(activation)?;
+        let mut accum = Vec::with_capacity(values.len());
+
+        for value in values {
+            accum.push(value.coerce_to_string(activation)?.to_string());
+        }
+
+        return Ok(
+            AvmString::new(activation.context.gc_context, accum.join(&string_separator)).into(),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.toString`
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    join(activation, this, &[",".into()])
+}
+
+/// Implements `Vector.push`
+pub fn push<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        for arg in args {
+            let value = coerce_to_value_type(activation, this, arg.clone())?;
+
+            if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+                vector.push(value)?;
+            }
+        }
+
+        if let Some(vector) = this.as_vector_storage() {
+            return Ok((vector.length() as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.pop`
+pub fn pop<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            return vector.pop();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.shift`
+pub fn shift<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            return vector.shift();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.unshift`
+pub fn unshift<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        for arg in args.iter().rev() {
+            let value = coerce_to_value_type(activation, this, arg.clone())?;
+
+            if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+                vector.unshift(value)?;
+            }
+        }
+
+        if let Some(vector) = this.as_vector_storage() {
+            return Ok((vector.length() as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.reverse`
+pub fn reverse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            vector.reverse();
+        }
+
+        return Ok(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.indexOf`
+pub fn index_of<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let search_val = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let length = this.as_vector_storage().map(|v| v.length()).unwrap_or(0);
+        let from = resolve_index(
+            activation,
+            args.get(1).cloned().unwrap_or_else(|| 0.into()),
+            length,
+        )?;
+
+        if let Some(vector) = this.as_vector_storage() {
+            for (i, val) in vector.iter().enumerate() {
+                if i >= from && val == search_val {
+                    return Ok(i.into());
+                }
+            }
+        }
+
+        return Ok((-1).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.lastIndexOf`
+pub fn last_index_of<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let search_val = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let length = this.as_vector_storage().map(|v| v.length()).unwrap_or(0);
+        let from = resolve_index(
+            activation,
+            args.get(1).cloned().unwrap_or_else(|| i32::MAX.into()),
+            length,
+        )?;
+
+        if let Some(vector) = this.as_vector_storage() {
+            for (i, val) in vector.iter().enumerate().rev() {
+                if i <= from && val == search_val {
+                    return Ok(i.into());
+                }
+            }
+        }
+
+        return Ok((-1).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.slice`
+pub fn slice<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let values: Vec<Value<'gc>> = match this.as_vector_storage() {
+            Some(vector) => vector.iter().collect(),
+            None => return Ok(Value::Undefined),
+        };
+        let start = resolve_index(
+            activation,
+            args.get(0).cloned().unwrap_or_else(|| 0.into()),
+            values.len(),
+        )?;
+        let end = resolve_index(
+            activation,
+            args.get(1).cloned().unwrap_or_else(|| 0xFFFFFF.into()),
+            values.len(),
+        )?;
+        let end = min(end, values.len());
+        let sliced = if start < end {
+            values[start..end].to_vec()
+        } else {
+            Vec::new()
+        };
+
+        return build_vector(activation, this, sliced);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.splice`
+pub fn splice<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let length = match this.as_vector_storage() {
+            Some(vector) => vector.length(),
+            None => return Ok(Value::Undefined),
+        };
+        let start = min(
+            resolve_index(
+                activation,
+                args.get(0).cloned().unwrap_or_else(|| 0.into()),
+                length,
+            )?,
+            length,
+        );
+        let delete_count = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| (length as u32).into())
+            .coerce_to_i32(activation)?
+            .max(0) as usize;
+        let delete_count = min(delete_count, length - start);
+        let mut replacements = Vec::new();
+
+        for arg in args.iter().skip(2) {
+            replacements.push(coerce_to_value_type(activation, this, arg.clone())?);
+        }
+
+        let removed = match this.as_vector_storage_mut(activation.context.gc_context) {
+            Some(mut vector) => vector.splice(start, delete_count, replacements)?,
+            None => return Ok(Value::Undefined),
+        };
+
+        return build_vector(activation, this, removed);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.sort`
+///
+/// The sole argument may either be a comparison function or a set of
+/// `Array` sort options.
+pub fn sort<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let sort_arg = args.get(0).cloned().unwrap_or_else(|| 0.into());
+        let compare_fnc = match sort_arg {
+            Value::Object(o) if o.as_executable().is_some() => Some(o),
+            _ => None,
+        };
+        let options = if compare_fnc.is_some() {
+            EnumSet::empty()
+        } else {
+            sort_arg.coerce_to_enumset(activation)?
+        };
+
+        let mut values: Vec<(usize, Value<'gc>)> = match this.as_vector_storage() {
+            Some(vector) => vector.iter().enumerate().collect(),
+            None => return Ok(Value::Undefined),
+        };
+
+        if let Some(v) = compare_fnc {
+            sort_inner(
+                activation,
+                &mut values,
+                options,
+                constrain(|activation, a, b| {
+                    let order = v
+                        .call(None, &[a, b], activation, None)?
+                        .coerce_to_number(activation)?;
+
+                    if order > 0.0 {
+                        Ok(Ordering::Greater)
+                    } else if order < 0.0 {
+                        Ok(Ordering::Less)
+                    } else {
+                        Ok(Ordering::Equal)
+                    }
+                }),
+            )?;
+        } else if options.contains(SortOptions::Numeric) {
+            sort_inner(activation, &mut values, options, compare_numeric)?;
+        } else if options.contains(SortOptions::CaseInsensitive) {
+            sort_inner(
+                activation,
+                &mut values,
+                options,
+                compare_string_case_insensitive,
+            )?;
+        } else {
+            sort_inner(
+                activation,
+                &mut values,
+                options,
+                compare_string_case_sensitive,
+            )?;
+        }
+
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            vector.replace_storage(values.into_iter().map(|(_, v)| v).collect());
+        }
+
+        return Ok(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.forEach`
+pub fn for_each<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let callback = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let receiver = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Null)
+            .coerce_to_object(activation)
+            .ok();
+        let mut iter = ArrayIter::new(activation, this)?;
+
+        while let Some(r) = iter.next(activation) {
+            let (i, item) = r?;
+
+            callback.call(
+                receiver,
+                &[item, i.into(), this.into()],
+                activation,
+                receiver.and_then(|r| r.proto()),
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.map`
+///
+/// The resulting vector has the same type as this one.
+pub fn map<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let callback = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let receiver = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Null)
+            .coerce_to_object(activation)
+            .ok();
+        let mut new_values = Vec::new();
+        let mut iter = ArrayIter::new(activation, this)?;
+
+        while let Some(r) = iter.next(activation) {
+            let (i, item) = r?;
+            let new_item = callback.call(
+                receiver,
+                &[item, i.into(), this.into()],
+                activation,
+                receiver.and_then(|r| r.proto()),
+            )?;
+
+            new_values.push(coerce_to_value_type(activation, this, new_item)?);
+        }
+
+        return build_vector(activation, this, new_values);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.filter`
+pub fn filter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let callback = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let receiver = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Null)
+            .coerce_to_object(activation)
+            .ok();
+        let mut new_values = Vec::new();
+        let mut iter = ArrayIter::new(activation, this)?;
+
+        while let Some(r) = iter.next(activation) {
+            let (i, item) = r?;
+            let is_allowed = callback
+                .call(
+                    receiver,
+                    &[item.clone(), i.into(), this.into()],
+                    activation,
+                    receiver.and_then(|r| r.proto()),
+                )?
+                .coerce_to_boolean();
+
+            if is_allowed {
+                new_values.push(item);
+            }
+        }
+
+        return build_vector(activation, this, new_values);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.every`
+pub fn every<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let callback = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let receiver = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Null)
+            .coerce_to_object(activation)
+            .ok();
+        let mut iter = ArrayIter::new(activation, this)?;
+
+        while let Some(r) = iter.next(activation) {
+            let (i, item) = r?;
+            let result = callback
+                .call(
+                    receiver,
+                    &[item, i.into(), this.into()],
+                    activation,
+                    receiver.and_then(|r| r.proto()),
+                )?
+                .coerce_to_boolean();
+
+            if !result {
+                return Ok(false.into());
+            }
+        }
+
+        return Ok(true.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.some`
+pub fn some<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let callback = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let receiver = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Null)
+            .coerce_to_object(activation)
+            .ok();
+        let mut iter = ArrayIter::new(activation, this)?;
+
+        while let Some(r) = iter.next(activation) {
+            let (i, item) = r?;
+            let result = callback
+                .call(
+                    receiver,
+                    &[item, i.into(), this.into()],
+                    activation,
+                    receiver.and_then(|r| r.proto()),
+                )?
+                .coerce_to_boolean();
+
+            if result {
+                return Ok(true.into());
+            }
+        }
+
+        return Ok(false.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Vector`'s class.
+///
+/// This is the unspecialized `Vector` class; each specialization created by
+/// `specialize` is a subclass of it.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("__AS3__.vec"), "Vector"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::Sealed.into());
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "length"),
+        Method::from_builtin(set_length),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "fixed"),
+        Method::from_builtin(fixed),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "fixed"),
+        Method::from_builtin(set_fixed),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    for (name, method) in [
+        ("concat", concat as NativeMethod<'gc>),
+        ("join", join),
+        ("push", push),
+        ("pop", pop),
+        ("shift", shift),
+        ("unshift", unshift),
+        ("reverse", reverse),
+        ("indexOf", index_of),
+        ("lastIndexOf", last_index_of),
+        ("slice", slice),
+        ("splice", splice),
+        ("sort", sort),
+        ("forEach", for_each),
+        ("map", map),
+        ("filter", filter),
+        ("every", every),
+        ("some", some),
+    ]
+    .iter()
+    {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::as3_namespace(), *name),
+            Method::from_builtin(*method),
+        ));
+    }
+
+    drop(write);
+
+    class
+}
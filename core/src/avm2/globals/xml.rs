@@ -0,0 +1,475 @@
+//! `XML` impl
+//!
+//! Most of the methods defined here apply equally to `XML` and `XMLList`
+//! objects, treating an `XML` object as a list of one node; `XMLList`'s class
+//! reuses them.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::e4x::{self, E4XNode};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use gc_arena::{GcCell, MutationContext};
+
+/// Convert a value into a list of E4X nodes, as `XML` and `XMLList`'s
+/// constructors do.
+///
+/// Existing XML values are copied; everything else is parsed as markup.
+pub fn nodes_from_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<Vec<E4XNode<'gc>>, Error<'gc>> {
+    let mc = activation.context.gc_context;
+
+    match value {
+        Value::Undefined | Value::Null => Ok(Vec::new()),
+        Value::Object(o) if e4x::is_xml_like(o) => Ok(e4x::nodes_of(o)
+            .unwrap_or_default()
+            .iter()
+            .map(|node| node.deep_copy(mc))
+            .collect()),
+        value => {
+            let markup = value.coerce_to_string(activation)?;
+
+            E4XNode::parse(mc, &markup)
+        }
+    }
+}
+
+/// Retrieve the nodes `this` refers to, or an empty list.
+fn this_nodes(this: Option<Object<'_>>) -> Vec<E4XNode<'_>> {
+    this.and_then(e4x::nodes_of).unwrap_or_default()
+}
+
+/// Coerce a name argument to a string, defaulting to `*`.
+fn name_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<AvmString<'gc>, Error<'gc>> {
+    match args.get(0) {
+        None | Some(Value::Undefined) => Ok("*".into()),
+        Some(name) => name.clone().coerce_to_string(activation),
+    }
+}
+
+/// Implements `XML`'s instance initializer.
+///
+/// When called as a function, this instead converts its argument to `XML`.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    if this.is_none() {
+        if let Value::Object(o) = value {
+            if o.as_xml().is_some() {
+                return Ok(value);
+            }
+        }
+    }
+
+    let mut nodes = nodes_from_value(activation, value)?;
+    let node = match nodes.len() {
+        0 => E4XNode::new_text(activation.context.gc_context, ""),
+        1 => nodes.remove(0),
+        _ => {
+            return Err(Error::avm_error(
+                ErrorClass::TypeError,
+                1088,
+                "The markup in the document following the root element must be well-formed.",
+            ))
+        }
+    };
+
+    match this {
+        Some(this) => {
+            this.set_xml(activation.context.gc_context, node);
+
+            Ok(Value::Undefined)
+        }
+        None => Ok(e4x::new_xml(activation, node).into()),
+    }
+}
+
+/// Implements `XML`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.toString` and `XMLList.toString`
+///
+/// Values with simple content yield their text, and all others are printed
+/// as markup.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let nodes = this_nodes(this);
+    let string = if list_has_simple_content(&nodes) {
+        nodes
+            .iter()
+            .filter(|node| node.node_kind() != "comment")
+            .map(|node| node.to_simple_string())
+            .collect::<String>()
+    } else {
+        nodes
+            .iter()
+            .map(|node| node.to_xml_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    Ok(AvmString::new(activation.context.gc_context, string).into())
+}
+
+/// Implements `XML.toXMLString` and `XMLList.toXMLString`
+pub fn to_xml_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let string = this_nodes(this)
+        .iter()
+        .map(|node| node.to_xml_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok(AvmString::new(activation.context.gc_context, string).into())
+}
+
+/// Check if a list of nodes has simple content.
+fn list_has_simple_content(nodes: &[E4XNode<'_>]) -> bool {
+    match nodes {
+        [node] => node.has_simple_content(),
+        nodes => !nodes.iter().any(|node| node.is_element()),
+    }
+}
+
+/// Implements `XML.hasSimpleContent` and `XMLList.hasSimpleContent`
+pub fn has_simple_content<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(list_has_simple_content(&this_nodes(this)).into())
+}
+
+/// Implements `XML.hasComplexContent` and `XMLList.hasComplexContent`
+pub fn has_complex_content<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let nodes = this_nodes(this);
+    let complex = match nodes.as_slice() {
+        [node] => node.is_element() && !node.has_simple_content(),
+        nodes => nodes.iter().any(|node| node.is_element()),
+    };
+
+    Ok(complex.into())
+}
+
+/// Implements `XML.length` and `XMLList.length`
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((this_nodes(this).len() as u32).into())
+}
+
+/// Implements `XML.name`
+///
+/// TODO: This should yield a `QName` object, but only the local name is
+/// supported.
+pub fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(name) = this_nodes(this).first().and_then(|node| node.name()) {
+        return Ok(AvmString::new(activation.context.gc_context, name.node_name()).into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `XML.localName`
+pub fn local_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(name) = this_nodes(this).first().and_then(|node| node.name()) {
+        return Ok(AvmString::new(activation.context.gc_context, name.local_name()).into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `XML.nodeKind`
+pub fn node_kind<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(node) = this_nodes(this).first() {
+        return Ok(node.node_kind().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.parent` and `XMLList.parent`
+///
+/// Lists only have a parent if all of their nodes share it.
+pub fn parent<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let nodes = this_nodes(this);
+    let parent = match nodes.first().and_then(|node| node.parent()) {
+        Some(parent) => parent,
+        None => return Ok(Value::Undefined),
+    };
+
+    let shared = nodes.iter().all(|node| {
+        node.parent()
+            .map(|other| other.ptr_eq(&parent))
+            .unwrap_or(false)
+    });
+
+    if shared {
+        Ok(e4x::new_xml(activation, parent).into())
+    } else {
+        Ok(Value::Undefined)
+    }
+}
+
+/// Implements `XML.child` and `XMLList.child`
+///
+/// Numeric names select a child by index rather than by name.
+pub fn child<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = name_arg(activation, args)?;
+    let mut matches = Vec::new();
+
+    for node in this_nodes(this) {
+        if let Ok(index) = name.parse::<usize>() {
+            matches.extend(node.children().into_iter().nth(index));
+        } else {
+            matches.extend(node.child_named(&name, false));
+        }
+    }
+
+    Ok(e4x::new_xml_list(activation, matches).into())
+}
+
+/// Implements `XML.children` and `XMLList.children`
+pub fn children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matches = this_nodes(this)
+        .iter()
+        .flat_map(|node| node.children())
+        .collect();
+
+    Ok(e4x::new_xml_list(activation, matches).into())
+}
+
+/// Implements `XML.elements` and `XMLList.elements`
+pub fn elements<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = name_arg(activation, args)?;
+    let matches = this_nodes(this)
+        .iter()
+        .flat_map(|node| node.child_named(&name, false))
+        .filter(|node| node.is_element())
+        .collect();
+
+    Ok(e4x::new_xml_list(activation, matches).into())
+}
+
+/// Implements `XML.attribute` and `XMLList.attribute`
+pub fn attribute<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = name_arg(activation, args)?;
+    let matches = this_nodes(this)
+        .iter()
+        .flat_map(|node| node.child_named(&name, true))
+        .collect();
+
+    Ok(e4x::new_xml_list(activation, matches).into())
+}
+
+/// Implements `XML.attributes` and `XMLList.attributes`
+pub fn attributes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matches = this_nodes(this)
+        .iter()
+        .flat_map(|node| node.attributes())
+        .collect();
+
+    Ok(e4x::new_xml_list(activation, matches).into())
+}
+
+/// Implements `XML.descendants` and `XMLList.descendants`
+pub fn descendants<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = name_arg(activation, args)?;
+    let mut matches = Vec::new();
+
+    for node in this_nodes(this) {
+        node.descendants(&name, false, &mut matches);
+    }
+
+    Ok(e4x::new_xml_list(activation, matches).into())
+}
+
+/// Implements `XML.text` and `XMLList.text`
+pub fn text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matches = this_nodes(this)
+        .iter()
+        .flat_map(|node| node.children())
+        .filter(|node| node.is_text())
+        .collect();
+
+    Ok(e4x::new_xml_list(activation, matches).into())
+}
+
+/// Implements `XML.copy` and `XMLList.copy`
+pub fn copy<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mc = activation.context.gc_context;
+    let mut copies: Vec<E4XNode<'gc>> = this_nodes(this)
+        .iter()
+        .map(|node| node.deep_copy(mc))
+        .collect();
+
+    match this {
+        Some(this) if this.as_xml().is_some() && copies.len() == 1 => {
+            Ok(e4x::new_xml(activation, copies.remove(0)).into())
+        }
+        _ => Ok(e4x::new_xml_list(activation, copies).into()),
+    }
+}
+
+/// Implements `XML.appendChild`
+///
+/// Values that are not XML are appended as text.
+pub fn append_child<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(node) = this.as_xml() {
+            let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+            let children = match &value {
+                Value::Object(o) if e4x::is_xml_like(*o) => e4x::nodes_of(*o).unwrap_or_default(),
+                _ => {
+                    let text = value.coerce_to_string(activation)?;
+
+                    vec![E4XNode::new_text(activation.context.gc_context, &text)]
+                }
+            };
+
+            for child in children {
+                node.append_child(activation.context.gc_context, &child)?;
+            }
+
+            return Ok(this.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `XML`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public_namespace(), "XML"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::Final.into());
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    for (name, method) in [
+        ("toString", to_string as NativeMethod<'gc>),
+        ("toXMLString", to_xml_string),
+        ("hasSimpleContent", has_simple_content),
+        ("hasComplexContent", has_complex_content),
+        ("length", length),
+        ("name", name),
+        ("localName", local_name),
+        ("nodeKind", node_kind),
+        ("parent", parent),
+        ("child", child),
+        ("children", children),
+        ("elements", elements),
+        ("attribute", attribute),
+        ("attributes", attributes),
+        ("descendants", descendants),
+        ("text", text),
+        ("copy", copy),
+        ("appendChild", append_child),
+    ]
+    .iter()
+    {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::as3_namespace(), *name),
+            Method::from_builtin(*method),
+        ));
+    }
+
+    drop(write);
+
+    class
+}
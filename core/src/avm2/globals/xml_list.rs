@@ -0,0 +1,103 @@
+//! `XMLList` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::e4x;
+use crate::avm2::globals::xml::{self, nodes_from_value};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `XMLList`'s instance initializer.
+///
+/// When called as a function, this instead converts its argument to
+/// `XMLList`.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    if this.is_none() {
+        if let Value::Object(o) = value {
+            if o.as_xml_list().is_some() {
+                return Ok(value);
+            }
+        }
+    }
+
+    let nodes = nodes_from_value(activation, value)?;
+
+    match this {
+        Some(this) => {
+            if let Some(mut list) = this.as_xml_list_mut(activation.context.gc_context) {
+                *list = nodes;
+            }
+
+            Ok(Value::Undefined)
+        }
+        None => Ok(e4x::new_xml_list(activation, nodes).into()),
+    }
+}
+
+/// Implements `XMLList`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `XMLList`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public_namespace(), "XMLList"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::Final.into());
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(xml::to_string),
+    ));
+
+    for (name, method) in [
+        ("toString", xml::to_string as NativeMethod<'gc>),
+        ("toXMLString", xml::to_xml_string),
+        ("hasSimpleContent", xml::has_simple_content),
+        ("hasComplexContent", xml::has_complex_content),
+        ("length", xml::length),
+        ("parent", xml::parent),
+        ("child", xml::child),
+        ("children", xml::children),
+        ("elements", xml::elements),
+        ("attribute", xml::attribute),
+        ("attributes", xml::attributes),
+        ("descendants", xml::descendants),
+        ("text", xml::text),
+        ("copy", xml::copy),
+    ]
+    .iter()
+    {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::as3_namespace(), *name),
+            Method::from_builtin(*method),
+        ));
+    }
+
+    drop(write);
+
+    class
+}
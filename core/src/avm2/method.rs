@@ -29,7 +29,7 @@ pub type NativeMethod<'gc> = fn(
     &mut Activation<'_, 'gc, '_>,
     Option<Object<'gc>>,
     &[Value<'gc>],
-) -> Result<Value<'gc>, Error>;
+) -> Result<Value<'gc>, Error<'gc>>;
 
 /// Represents a reference to an AVM2 method and body.
 #[derive(Collect, Clone, Debug)]
@@ -168,7 +168,7 @@ impl<'gc> Method<'gc> {
     /// Access the bytecode of this method.
     ///
     /// This function returns `Err` if there is no bytecode for this method.
-    pub fn into_bytecode(self) -> Result<Gc<'gc, BytecodeMethod<'gc>>, Error> {
+    pub fn into_bytecode(self) -> Result<Gc<'gc, BytecodeMethod<'gc>>, Error<'gc>> {
         match self {
             Method::Native(_) => {
                 Err("Attempted to unwrap a native method as a user-defined one".into())
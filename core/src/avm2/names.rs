@@ -30,14 +30,14 @@ impl<'gc> Namespace<'gc> {
         translation_unit: TranslationUnit<'gc>,
         namespace_index: Index<AbcNamespace>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, Error<'gc>> {
         if namespace_index.0 == 0 {
             return Ok(Self::Any);
         }
 
         let actual_index = namespace_index.0 as usize - 1;
         let abc = translation_unit.abc();
-        let abc_namespace: Result<_, Error> = abc
+        let abc_namespace: Result<_, Error<'gc>> = abc
             .constant_pool
             .namespaces
             .get(actual_index)
@@ -146,14 +146,14 @@ impl<'gc> QName<'gc> {
         translation_unit: TranslationUnit<'gc>,
         multiname_index: Index<AbcMultiname>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, Error<'gc>> {
         if multiname_index.0 == 0 {
             return Err("Attempted to load a trait name of index zero".into());
         }
 
         let actual_index = multiname_index.0 as usize - 1;
         let abc = translation_unit.abc();
-        let abc_multiname: Result<_, Error> = abc
+        let abc_multiname: Result<_, Error<'gc>> = abc
             .constant_pool
             .multinames
             .get(actual_index)
@@ -207,6 +207,13 @@ impl<'gc> QName<'gc> {
 pub struct Multiname<'gc> {
     ns: Vec<Namespace<'gc>>,
     name: Option<AvmString<'gc>>,
+
+    /// The type parameters applied to this name, such as the `int` in
+    /// `Vector.<int>`.
+    params: Vec<Multiname<'gc>>,
+
+    /// Whether or not this name refers to XML attributes.
+    is_attribute: bool,
 }
 
 impl<'gc> Multiname<'gc> {
@@ -216,7 +223,7 @@ impl<'gc> Multiname<'gc> {
         translation_unit: TranslationUnit<'gc>,
         namespace_set_index: Index<AbcNamespaceSet>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Vec<Namespace<'gc>>, Error> {
+    ) -> Result<Vec<Namespace<'gc>>, Error<'gc>> {
         if namespace_set_index.0 == 0 {
             //TODO: What is namespace set zero?
             return Ok(vec![]);
@@ -224,7 +231,7 @@ impl<'gc> Multiname<'gc> {
 
         let actual_index = namespace_set_index.0 as usize - 1;
         let abc = translation_unit.abc();
-        let ns_set: Result<_, Error> = abc
+        let ns_set: Result<_, Error<'gc>> = abc
             .constant_pool
             .namespace_sets
             .get(actual_index)
@@ -250,19 +257,22 @@ impl<'gc> Multiname<'gc> {
         translation_unit: TranslationUnit<'gc>,
         multiname_index: Index<AbcMultiname>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Self, Error> {
-        let actual_index: Result<usize, Error> = (multiname_index.0 as usize)
+    ) -> Result<Self, Error<'gc>> {
+        let actual_index: Result<usize, Error<'gc>> = (multiname_index.0 as usize)
             .checked_sub(1)
             .ok_or_else(|| "Attempted to resolve a multiname at index zero. This is a bug.".into());
         let actual_index = actual_index?;
         let abc = translation_unit.abc();
-        let abc_multiname: Result<_, Error> = abc
+        let abc_multiname: Result<_, Error<'gc>> = abc
             .constant_pool
             .multinames
             .get(actual_index)
             .ok_or_else(|| format!("Unknown multiname constant {}", multiname_index.0).into());
 
-        Ok(match abc_multiname? {
+        let abc_multiname = abc_multiname?;
+        let is_attribute = Self::is_abc_attribute(abc_multiname);
+
+        Ok(match abc_multiname {
             AbcMultiname::QName { namespace, name } | AbcMultiname::QNameA { namespace, name } => {
                 Self {
                     ns: vec![Namespace::from_abc_namespace(
@@ -272,6 +282,8 @@ impl<'gc> Multiname<'gc> {
                     )?],
                     name: translation_unit
                         .pool_string_option(name.0, activation.context.gc_context)?,
+                    params: Vec::new(),
+                    is_attribute,
                 }
             }
             AbcMultiname::RTQName { name } | AbcMultiname::RTQNameA { name } => {
@@ -280,6 +292,8 @@ impl<'gc> Multiname<'gc> {
                     ns: vec![ns],
                     name: translation_unit
                         .pool_string_option(name.0, activation.context.gc_context)?,
+                    params: Vec::new(),
+                    is_attribute,
                 }
             }
             AbcMultiname::RTQNameL | AbcMultiname::RTQNameLA => {
@@ -288,6 +302,8 @@ impl<'gc> Multiname<'gc> {
                 Self {
                     ns: vec![ns],
                     name: Some(name),
+                    params: Vec::new(),
+                    is_attribute,
                 }
             }
             AbcMultiname::Multiname {
@@ -304,6 +320,8 @@ impl<'gc> Multiname<'gc> {
                     activation.context.gc_context,
                 )?,
                 name: translation_unit.pool_string_option(name.0, activation.context.gc_context)?,
+                params: Vec::new(),
+                is_attribute,
             },
             AbcMultiname::MultinameL { namespace_set }
             | AbcMultiname::MultinameLA { namespace_set } => {
@@ -315,11 +333,30 @@ impl<'gc> Multiname<'gc> {
                         activation.context.gc_context,
                     )?,
                     name: Some(name),
+                    params: Vec::new(),
+                    is_attribute,
                 }
             }
+            AbcMultiname::TypeName { .. } => Self::from_abc_multiname_static(
+                translation_unit,
+                multiname_index,
+                activation.context.gc_context,
+            )?,
         })
     }
 
+    /// Determine if an ABC multiname refers to XML attributes.
+    fn is_abc_attribute(abc_multiname: &AbcMultiname) -> bool {
+        matches!(
+            abc_multiname,
+            AbcMultiname::QNameA { .. }
+                | AbcMultiname::RTQNameA { .. }
+                | AbcMultiname::RTQNameLA
+                | AbcMultiname::MultinameA { .. }
+                | AbcMultiname::MultinameLA { .. }
+        )
+    }
+
     /// Read a static multiname from the ABC constant pool
     ///
     /// This function prohibits the use of runtime-qualified and late-bound
@@ -328,20 +365,23 @@ impl<'gc> Multiname<'gc> {
         translation_unit: TranslationUnit<'gc>,
         multiname_index: Index<AbcMultiname>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Self, Error> {
-        let actual_index: Result<usize, Error> =
+    ) -> Result<Self, Error<'gc>> {
+        let actual_index: Result<usize, Error<'gc>> =
             (multiname_index.0 as usize).checked_sub(1).ok_or_else(|| {
                 "Attempted to resolve a (static) multiname at index zero. This is a bug.".into()
             });
         let actual_index = actual_index?;
         let abc = translation_unit.abc();
-        let abc_multiname: Result<_, Error> = abc
+        let abc_multiname: Result<_, Error<'gc>> = abc
             .constant_pool
             .multinames
             .get(actual_index)
             .ok_or_else(|| format!("Unknown multiname constant {}", multiname_index.0).into());
 
-        Ok(match abc_multiname? {
+        let abc_multiname = abc_multiname?;
+        let is_attribute = Self::is_abc_attribute(abc_multiname);
+
+        Ok(match abc_multiname {
             AbcMultiname::QName { namespace, name } | AbcMultiname::QNameA { namespace, name } => {
                 Self {
                     ns: vec![Namespace::from_abc_namespace(
@@ -350,6 +390,8 @@ impl<'gc> Multiname<'gc> {
                         mc,
                     )?],
                     name: translation_unit.pool_string_option(name.0, mc)?,
+                    params: Vec::new(),
+                    is_attribute,
                 }
             }
             AbcMultiname::Multiname {
@@ -362,7 +404,29 @@ impl<'gc> Multiname<'gc> {
             } => Self {
                 ns: Self::abc_namespace_set(translation_unit, namespace_set.clone(), mc)?,
                 name: translation_unit.pool_string_option(name.0, mc)?,
+                params: Vec::new(),
+                is_attribute,
             },
+            AbcMultiname::TypeName {
+                base_type,
+                parameters,
+            } => {
+                let mut base =
+                    Self::from_abc_multiname_static(translation_unit, base_type.clone(), mc)?;
+
+                for parameter in parameters {
+                    // Parameter zero is the any type, as in `Vector.<*>`.
+                    let parameter = if parameter.0 == 0 {
+                        Self::any()
+                    } else {
+                        Self::from_abc_multiname_static(translation_unit, parameter.clone(), mc)?
+                    };
+
+                    base.params.push(parameter);
+                }
+
+                base
+            }
             _ => return Err(format!("Multiname {} is not static", multiname_index.0).into()),
         })
     }
@@ -372,6 +436,8 @@ impl<'gc> Multiname<'gc> {
         Self {
             ns: vec![Namespace::Any],
             name: None,
+            params: Vec::new(),
+            is_attribute: false,
         }
     }
 
@@ -383,6 +449,16 @@ impl<'gc> Multiname<'gc> {
         self.name
     }
 
+    /// The type parameters of this name, if it names a parameterized type.
+    pub fn params(&self) -> &[Multiname<'gc>] {
+        &self.params
+    }
+
+    /// Indicates if this name refers to XML attributes (e.g. `@id`).
+    pub fn is_attribute(&self) -> bool {
+        self.is_attribute
+    }
+
     pub fn includes_dynamic_namespace(&self) -> bool {
         for ns in self.ns.iter() {
             if ns.is_dynamic() {
@@ -399,6 +475,8 @@ impl<'gc> From<QName<'gc>> for Multiname<'gc> {
         Self {
             ns: vec![q.ns],
             name: Some(q.name),
+            params: Vec::new(),
+            is_attribute: false,
         }
     }
 }
@@ -2,15 +2,21 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::array::ArrayStorage;
+use crate::avm2::bitmapdata::BitmapDataStorage;
+use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::class::Class;
 use crate::avm2::domain::Domain;
+use crate::avm2::e4x::E4XNode;
+use crate::avm2::events::{DispatchList, Event};
 use crate::avm2::function::Executable;
 use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::regexp::RegExp;
 use crate::avm2::scope::Scope;
 use crate::avm2::string::AvmString;
 use crate::avm2::traits::{Trait, TraitKind};
 use crate::avm2::value::{Hint, Value};
-use crate::avm2::Error;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Error, ErrorClass};
 use crate::display_object::DisplayObject;
 use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_macros::enum_trait_object;
@@ -18,21 +24,41 @@ use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
 
 mod array_object;
+mod bitmapdata_object;
+mod bytearray_object;
 mod custom_object;
+mod date_object;
+mod dictionary_object;
+mod dispatch_object;
 mod domain_object;
+mod event_object;
 mod function_object;
 mod namespace_object;
 mod primitive_object;
+mod regexp_object;
 mod script_object;
 mod stage_object;
+mod vector_object;
+mod xml_list_object;
+mod xml_object;
 
 pub use crate::avm2::object::array_object::ArrayObject;
+pub use crate::avm2::object::bitmapdata_object::BitmapDataObject;
+pub use crate::avm2::object::bytearray_object::ByteArrayObject;
+pub use crate::avm2::object::date_object::DateObject;
+pub use crate::avm2::object::dictionary_object::DictionaryObject;
+pub use crate::avm2::object::dispatch_object::DispatchObject;
 pub use crate::avm2::object::domain_object::DomainObject;
+pub use crate::avm2::object::event_object::EventObject;
 pub use crate::avm2::object::function_object::{implicit_deriver, FunctionObject};
 pub use crate::avm2::object::namespace_object::NamespaceObject;
 pub use crate::avm2::object::primitive_object::PrimitiveObject;
+pub use crate::avm2::object::regexp_object::RegExpObject;
 pub use crate::avm2::object::script_object::ScriptObject;
 pub use crate::avm2::object::stage_object::StageObject;
+pub use crate::avm2::object::vector_object::VectorObject;
+pub use crate::avm2::object::xml_list_object::XmlListObject;
+pub use crate::avm2::object::xml_object::XmlObject;
 
 /// Represents an object that can be directly interacted with by the AVM2
 /// runtime.
@@ -47,6 +73,16 @@ pub use crate::avm2::object::stage_object::StageObject;
         ArrayObject(ArrayObject<'gc>),
         StageObject(StageObject<'gc>),
         DomainObject(DomainObject<'gc>),
+        EventObject(EventObject<'gc>),
+        DispatchObject(DispatchObject<'gc>),
+        VectorObject(VectorObject<'gc>),
+        DictionaryObject(DictionaryObject<'gc>),
+        ByteArrayObject(ByteArrayObject<'gc>),
+        RegExpObject(RegExpObject<'gc>),
+        DateObject(DateObject<'gc>),
+        XmlObject(XmlObject<'gc>),
+        XmlListObject(XmlListObject<'gc>),
+        BitmapDataObject(BitmapDataObject<'gc>),
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -57,7 +93,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         receiver: Object<'gc>,
         name: &QName<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Value<'gc>, Error>;
+    ) -> Result<Value<'gc>, Error<'gc>>;
 
     /// Retrieve a property by its QName.
     fn get_property(
@@ -65,7 +101,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         receiver: Object<'gc>,
         name: &QName<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         if !self.has_instantiated_property(name) {
             for abc_trait in self.get_trait(name)? {
                 self.install_trait(activation, abc_trait, receiver)?;
@@ -89,7 +125,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     ///
     /// This function returns `None` for non-trait properties, such as actually
     /// defined prototype methods for ES3-style classes.
-    fn get_base_proto(self, name: &QName<'gc>) -> Result<Option<Object<'gc>>, Error> {
+    fn get_base_proto(self, name: &QName<'gc>) -> Result<Option<Object<'gc>>, Error<'gc>> {
         if self.provides_trait(name)? {
             return Ok(Some(self.into()));
         }
@@ -108,7 +144,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Set a property by its QName.
     fn set_property(
@@ -117,7 +153,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if !self.has_instantiated_property(name) {
             for abc_trait in self.get_trait(name)? {
                 self.install_trait(activation, abc_trait, receiver)?;
@@ -150,7 +186,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Init a property by its QName.
     fn init_property(
@@ -159,7 +195,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if !self.has_instantiated_property(name) {
             for abc_trait in self.get_trait(name)? {
                 self.install_trait(activation, abc_trait, receiver)?;
@@ -186,7 +222,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     }
 
     /// Retrieve a slot by its index.
-    fn get_slot(self, id: u32) -> Result<Value<'gc>, Error>;
+    fn get_slot(self, id: u32) -> Result<Value<'gc>, Error<'gc>>;
 
     /// Set a slot by its index.
     fn set_slot(
@@ -194,7 +230,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         id: u32,
         value: Value<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Initialize a slot by its index.
     fn init_slot(
@@ -202,7 +238,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         id: u32,
         value: Value<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Retrieve a method by its index.
     fn get_method(self, id: u32) -> Option<Object<'gc>>;
@@ -212,7 +248,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This function returns `None` if no such trait exists, or the object
     /// does not have traits. It returns `Err` if *any* trait in the object is
     /// malformed in some way.
-    fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error>;
+    fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error<'gc>>;
 
     /// Populate a list of traits that this object provides.
     ///
@@ -224,7 +260,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         &self,
         name: &QName<'gc>,
         known_traits: &mut Vec<Trait<'gc>>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Retrieves the scope chain of the object at time of its creation.
     ///
@@ -236,7 +272,10 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
 
     /// Resolve a multiname into a single QName, if any of the namespaces
     /// match.
-    fn resolve_multiname(self, multiname: &Multiname<'gc>) -> Result<Option<QName<'gc>>, Error> {
+    fn resolve_multiname(
+        self,
+        multiname: &Multiname<'gc>,
+    ) -> Result<Option<QName<'gc>>, Error<'gc>> {
         for ns in multiname.namespace_set() {
             if ns.is_any() {
                 if let Some(name) = multiname.local_name() {
@@ -270,18 +309,20 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// Trait names will be resolve on class constructors and object instances,
     /// but not prototypes. If you want to search a prototype's provided traits
     /// you must walk the prototype chain using `resolve_any_trait`.
-    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error>;
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error<'gc>>;
 
     /// Given a local name of a trait, find the namespace it resides in, if any.
     ///
     /// This function only works for names which are trait properties, not
     /// dynamic or prototype properties. Furthermore, instance prototypes *will*
     /// resolve trait names here, contrary to their behavior in `resolve_any.`
-    fn resolve_any_trait(self, local_name: AvmString<'gc>)
-        -> Result<Option<Namespace<'gc>>, Error>;
+    fn resolve_any_trait(
+        self,
+        local_name: AvmString<'gc>,
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>>;
 
     /// Indicates whether or not a property exists on an object.
-    fn has_property(self, name: &QName<'gc>) -> Result<bool, Error> {
+    fn has_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         if self.has_own_property(name)? {
             Ok(true)
         } else if let Some(proto) = self.proto() {
@@ -293,15 +334,15 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
 
     /// Indicates whether or not a property or trait exists on an object and is
     /// not part of the prototype chain.
-    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error>;
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>>;
 
     /// Returns true if an object has one or more traits of a given name.
-    fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error>;
+    fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error<'gc>>;
 
     /// Returns true if an object is part of a class that defines a trait of a
     /// given name on itself (as opposed to merely inheriting a superclass
     /// trait.)
-    fn provides_trait(self, name: &QName<'gc>) -> Result<bool, Error>;
+    fn provides_trait(self, name: &QName<'gc>) -> Result<bool, Error<'gc>>;
 
     /// Indicates whether or not a property or *instantiated* trait exists on
     /// an object and is not part of the prototype chain.
@@ -355,6 +396,36 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// mechanism.
     fn get_enumerant_name(&self, index: u32) -> Option<QName<'gc>>;
 
+    /// Determine if an enumerant exists at a given index.
+    ///
+    /// Objects whose enumerants are not all named by a `QName` (such as
+    /// vector indices or dictionary object keys) should override this along
+    /// with `get_enumerant_key` and `get_enumerant_value`.
+    fn has_enumerant(&self, index: u32) -> bool {
+        self.get_enumerant_name(index).is_some()
+    }
+
+    /// Retrieve the key of a given enumerant, as yielded by `for..in`.
+    fn get_enumerant_key(&self, index: u32, _mc: MutationContext<'gc, '_>) -> Option<Value<'gc>> {
+        self.get_enumerant_name(index)
+            .map(|name| name.local_name().into())
+    }
+
+    /// Retrieve the value of a given enumerant, as yielded by `for each..in`.
+    fn get_enumerant_value(
+        &mut self,
+        index: u32,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        if let Some(name) = self.get_enumerant_name(index) {
+            let receiver: Object<'gc> = (*self).into();
+
+            self.get_property(receiver, &name, activation)
+        } else {
+            Ok(Value::Undefined)
+        }
+    }
+
     /// Determine if a property is currently enumerable.
     ///
     /// Properties that do not exist are also not enumerable.
@@ -366,7 +437,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         mc: MutationContext<'gc, '_>,
         name: &QName<'gc>,
         is_enumerable: bool,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Install a method (or any other non-slot value) on an object.
     fn install_method(
@@ -384,7 +455,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Install a setter method on an object property.
     fn install_setter(
@@ -393,7 +464,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Install a dynamic or built-in value property on an object.
     fn install_dynamic_property(
@@ -401,7 +472,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         mc: MutationContext<'gc, '_>,
         name: QName<'gc>,
         value: Value<'gc>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Install a slot on an object property.
     fn install_slot(
@@ -442,7 +513,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         activation: &mut Activation<'_, 'gc, '_>,
         trait_entry: Trait<'gc>,
         receiver: Object<'gc>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         self.install_foreign_trait(activation, trait_entry, self.get_scope(), receiver)
     }
 
@@ -456,7 +527,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         trait_entry: Trait<'gc>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
         receiver: Object<'gc>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         let fn_proto = activation.avm2().prototypes().function;
         let trait_name = trait_entry.name().clone();
         avm_debug!(
@@ -634,8 +705,12 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         _arguments: &[Value<'gc>],
         _activation: &mut Activation<'_, 'gc, '_>,
         _base_proto: Option<Object<'gc>>,
-    ) -> Result<Value<'gc>, Error> {
-        Err("Object is not callable".into())
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        Err(Error::avm_error(
+            ErrorClass::TypeError,
+            1006,
+            "value is not a function.",
+        ))
     }
 
     /// Construct a host object of some kind and return its cell.
@@ -659,7 +734,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
         args: &[Value<'gc>],
-    ) -> Result<Object<'gc>, Error>;
+    ) -> Result<Object<'gc>, Error<'gc>>;
 
     /// Construct a host object prototype of some kind and return it.
     ///
@@ -675,7 +750,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         activation: &mut Activation<'_, 'gc, '_>,
         class: GcCell<'gc, Class<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error>;
+    ) -> Result<Object<'gc>, Error<'gc>>;
 
     /// Determine the type of primitive coercion this object would prefer, in
     /// the case that there is no obvious reason to prefer one type over the
@@ -695,7 +770,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// coercions happen by defining `toString` in a downstream class or
     /// prototype; this is then picked up by the VM runtime when doing
     /// coercions.
-    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         let class_name = self
             .as_proto_class()
             .map(|c| c.read().name().local_name())
@@ -712,7 +787,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// generate a debug-style string based on the name of the class this
     /// object is, in the format of `[object Class]` (where `Class` is the name
     /// of the class that created this object).
-    fn to_locale_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn to_locale_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         let class_name = self
             .as_proto_class()
             .map(|c| c.read().name().local_name())
@@ -726,7 +801,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     ///
     /// `valueOf` is a method used to request an object be coerced to a
     /// primitive value. Typically, this would be a number of some kind.
-    fn value_of(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error>;
+    fn value_of(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>>;
 
     /// Enumerate all interfaces implemented by this object.
     fn interfaces(&self) -> Vec<Object<'gc>>;
@@ -746,7 +821,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         activation: &mut Activation<'_, 'gc, '_>,
         mut constructor: Object<'gc>,
         check_interfaces: bool,
-    ) -> Result<bool, Error> {
+    ) -> Result<bool, Error<'gc>> {
         let type_proto = constructor
             .get_property(constructor, &QName::dynamic_name("prototype"), activation)?
             .coerce_to_object(activation)?;
@@ -764,7 +839,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         &self,
         type_proto: Object<'gc>,
         check_interfaces: bool,
-    ) -> Result<bool, Error> {
+    ) -> Result<bool, Error<'gc>> {
         let mut my_proto = self.proto();
 
         //TODO: Is it a verification error to do `obj instanceof bare_object`?
@@ -848,6 +923,103 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     fn as_application_domain(&self) -> Option<Domain<'gc>> {
         None
     }
+
+    /// Unwrap this object as an event.
+    fn as_event(&self) -> Option<Ref<Event<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as a mutable event.
+    fn as_event_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<Event<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as a list of event handlers.
+    fn as_dispatch(&self) -> Option<Ref<DispatchList<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as a mutable list of event handlers.
+    fn as_dispatch_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<DispatchList<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as vector storage.
+    fn as_vector_storage(&self) -> Option<Ref<VectorStorage<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as mutable vector storage.
+    fn as_vector_storage_mut(
+        &self,
+        _mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<VectorStorage<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as a dictionary.
+    fn as_dictionary_object(self) -> Option<DictionaryObject<'gc>> {
+        None
+    }
+
+    /// Unwrap this object as byte array storage.
+    fn as_bytearray(&self) -> Option<Ref<ByteArrayStorage>> {
+        None
+    }
+
+    /// Unwrap this object as mutable byte array storage.
+    fn as_bytearray_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<ByteArrayStorage>> {
+        None
+    }
+
+    /// Unwrap this object as bitmap pixel storage.
+    fn as_bitmap_data(&self) -> Option<Ref<BitmapDataStorage>> {
+        None
+    }
+
+    /// Unwrap this object as mutable bitmap pixel storage.
+    fn as_bitmap_data_mut(
+        &self,
+        _mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<BitmapDataStorage>> {
+        None
+    }
+
+    /// Unwrap this object as a regular expression.
+    fn as_regexp(&self) -> Option<Ref<RegExp<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as a mutable regular expression.
+    fn as_regexp_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<RegExp<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as a date, yielding its time value.
+    fn as_date(&self) -> Option<f64> {
+        None
+    }
+
+    /// Change the time value of this object, if it is a date.
+    fn set_date(&self, _mc: MutationContext<'gc, '_>, _time: f64) {}
+
+    /// Unwrap this object as an XML node.
+    fn as_xml(&self) -> Option<E4XNode<'gc>> {
+        None
+    }
+
+    /// Change the node this object refers to, if it is an XML object.
+    fn set_xml(&self, _mc: MutationContext<'gc, '_>, _node: E4XNode<'gc>) {}
+
+    /// Unwrap this object as a list of XML nodes.
+    fn as_xml_list(&self) -> Option<Ref<Vec<E4XNode<'gc>>>> {
+        None
+    }
+
+    /// Unwrap this object as a mutable list of XML nodes.
+    fn as_xml_list_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<Vec<E4XNode<'gc>>>> {
+        None
+    }
 }
 
 pub enum ObjectPtr {}
@@ -0,0 +1,111 @@
+//! Object representation for bitmap data
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bitmapdata::BitmapDataStorage;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which stores the pixels of a bitmap.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct BitmapDataObject<'gc>(GcCell<'gc, BitmapDataObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct BitmapDataObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The pixels of this bitmap.
+    storage: BitmapDataStorage,
+}
+
+impl<'gc> BitmapDataObject<'gc> {
+    /// Construct a bitmap data subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(BitmapDataObject(GcCell::allocate(
+            mc,
+            BitmapDataObjectData {
+                base,
+                storage: BitmapDataStorage::new(0, 0, true, 0),
+            },
+        ))
+        .into())
+    }
+
+    /// Construct a bitmap data object with the given pixels.
+    pub fn from_storage(
+        mc: MutationContext<'gc, '_>,
+        base_proto: Object<'gc>,
+        storage: BitmapDataStorage,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(Some(base_proto), ScriptObjectClass::NoClass);
+
+        BitmapDataObject(GcCell::allocate(mc, BitmapDataObjectData { base, storage })).into()
+    }
+}
+
+impl<'gc> TObject<'gc> for BitmapDataObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_bitmap_data(&self) -> Option<Ref<BitmapDataStorage>> {
+        Some(Ref::map(self.0.read(), |bdod| &bdod.storage))
+    }
+
+    fn as_bitmap_data_mut(
+        &self,
+        mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<BitmapDataStorage>> {
+        Some(RefMut::map(self.0.write(mc), |bdod| &mut bdod.storage))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::BitmapDataObject(*self);
+
+        Ok(Self::from_storage(
+            activation.context.gc_context,
+            this,
+            BitmapDataStorage::new(0, 0, true, 0),
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::BitmapDataObject(*self);
+
+        Self::derive(this, activation.context.gc_context, class, scope)
+    }
+}
@@ -8,7 +8,7 @@ macro_rules! impl_avm2_custom_object_properties {
             receiver: Object<'gc>,
             name: &QName<'gc>,
             activation: &mut Activation<'_, 'gc, '_>,
-        ) -> Result<Value<'gc>, Error> {
+        ) -> Result<Value<'gc>, Error<'gc>> {
             let read = self.0.read();
             let rv = read.$field.get_property_local(receiver, name, activation)?;
 
@@ -23,7 +23,7 @@ macro_rules! impl_avm2_custom_object_properties {
             name: &QName<'gc>,
             value: Value<'gc>,
             activation: &mut Activation<'_, 'gc, '_>,
-        ) -> Result<(), Error> {
+        ) -> Result<(), Error<'gc>> {
             let mut write = self.0.write(activation.context.gc_context);
             let rv = write
                 .$field
@@ -42,7 +42,7 @@ macro_rules! impl_avm2_custom_object_properties {
             name: &QName<'gc>,
             value: Value<'gc>,
             activation: &mut Activation<'_, 'gc, '_>,
-        ) -> Result<(), Error> {
+        ) -> Result<(), Error<'gc>> {
             let mut write = self.0.write(activation.context.gc_context);
             let rv = write
                 .$field
@@ -74,18 +74,21 @@ macro_rules! impl_avm2_custom_object_properties {
             self.0.write(gc_context).$field.delete_property(multiname)
         }
 
-        fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error> {
+        fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
             self.0.read().$field.has_own_property(name)
         }
 
-        fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
+        fn resolve_any(
+            self,
+            local_name: AvmString<'gc>,
+        ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
             self.0.read().$field.resolve_any(local_name)
         }
 
         fn resolve_any_trait(
             self,
             local_name: AvmString<'gc>,
-        ) -> Result<Option<Namespace<'gc>>, Error> {
+        ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
             self.0.read().$field.resolve_any_trait(local_name)
         }
     };
@@ -94,7 +97,7 @@ macro_rules! impl_avm2_custom_object_properties {
 #[macro_export]
 macro_rules! impl_avm2_custom_object {
     ($field:ident) => {
-        fn get_slot(self, id: u32) -> Result<Value<'gc>, Error> {
+        fn get_slot(self, id: u32) -> Result<Value<'gc>, Error<'gc>> {
             self.0.read().$field.get_slot(id)
         }
 
@@ -103,7 +106,7 @@ macro_rules! impl_avm2_custom_object {
             id: u32,
             value: Value<'gc>,
             mc: MutationContext<'gc, '_>,
-        ) -> Result<(), Error> {
+        ) -> Result<(), Error<'gc>> {
             self.0.write(mc).$field.set_slot(id, value, mc)
         }
 
@@ -112,7 +115,7 @@ macro_rules! impl_avm2_custom_object {
             id: u32,
             value: Value<'gc>,
             mc: MutationContext<'gc, '_>,
-        ) -> Result<(), Error> {
+        ) -> Result<(), Error<'gc>> {
             self.0.write(mc).$field.init_slot(id, value, mc)
         }
 
@@ -120,7 +123,7 @@ macro_rules! impl_avm2_custom_object {
             self.0.read().$field.get_method(id)
         }
 
-        fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error> {
+        fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error<'gc>> {
             self.0.read().$field.get_trait(name)
         }
 
@@ -128,7 +131,7 @@ macro_rules! impl_avm2_custom_object {
             &self,
             name: &QName<'gc>,
             known_traits: &mut Vec<Trait<'gc>>,
-        ) -> Result<(), Error> {
+        ) -> Result<(), Error<'gc>> {
             self.0.read().$field.get_provided_trait(name, known_traits)
         }
 
@@ -136,11 +139,11 @@ macro_rules! impl_avm2_custom_object {
             self.0.read().$field.get_scope()
         }
 
-        fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error> {
+        fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
             self.0.read().$field.has_trait(name)
         }
 
-        fn provides_trait(self, name: &QName<'gc>) -> Result<bool, Error> {
+        fn provides_trait(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
             self.0.read().$field.provides_trait(name)
         }
 
@@ -177,7 +180,7 @@ macro_rules! impl_avm2_custom_object {
             mc: MutationContext<'gc, '_>,
             name: &QName<'gc>,
             is_enumerable: bool,
-        ) -> Result<(), Error> {
+        ) -> Result<(), Error<'gc>> {
             self.0
                 .write(mc)
                 .$field
@@ -211,7 +214,7 @@ macro_rules! impl_avm2_custom_object {
             name: QName<'gc>,
             disp_id: u32,
             function: Object<'gc>,
-        ) -> Result<(), Error> {
+        ) -> Result<(), Error<'gc>> {
             self.0
                 .write(mc)
                 .$field
@@ -224,7 +227,7 @@ macro_rules! impl_avm2_custom_object {
             name: QName<'gc>,
             disp_id: u32,
             function: Object<'gc>,
-        ) -> Result<(), Error> {
+        ) -> Result<(), Error<'gc>> {
             self.0
                 .write(mc)
                 .$field
@@ -236,7 +239,7 @@ macro_rules! impl_avm2_custom_object {
             mc: MutationContext<'gc, '_>,
             name: QName<'gc>,
             value: Value<'gc>,
-        ) -> Result<(), Error> {
+        ) -> Result<(), Error<'gc>> {
             self.0
                 .write(mc)
                 .$field
@@ -0,0 +1,112 @@
+//! Object representation for dates
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::{Hint, Value};
+use crate::avm2::Error;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// An Object which represents a point in time.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct DateObject<'gc>(GcCell<'gc, DateObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct DateObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The number of milliseconds since midnight, January 1st 1970 UTC.
+    ///
+    /// Invalid dates hold `NaN`.
+    time: f64,
+}
+
+impl<'gc> DateObject<'gc> {
+    /// Construct a date subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(DateObject(GcCell::allocate(
+            mc,
+            DateObjectData {
+                base,
+                time: f64::NAN,
+            },
+        ))
+        .into())
+    }
+
+    /// Construct a date with a given time value.
+    pub fn from_time(
+        mc: MutationContext<'gc, '_>,
+        base_proto: Object<'gc>,
+        time: f64,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(Some(base_proto), ScriptObjectClass::NoClass);
+
+        DateObject(GcCell::allocate(mc, DateObjectData { base, time })).into()
+    }
+}
+
+impl<'gc> TObject<'gc> for DateObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn default_hint(&self) -> Hint {
+        Hint::String
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(self.0.read().time.into())
+    }
+
+    fn as_date(&self) -> Option<f64> {
+        Some(self.0.read().time)
+    }
+
+    fn set_date(&self, mc: MutationContext<'gc, '_>, time: f64) {
+        self.0.write(mc).time = time;
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::DateObject(*self);
+
+        Ok(Self::from_time(
+            activation.context.gc_context,
+            this,
+            f64::NAN,
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::DateObject(*self);
+
+        Self::derive(this, activation.context.gc_context, class, scope)
+    }
+}
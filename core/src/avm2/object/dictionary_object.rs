@@ -0,0 +1,195 @@
+//! Object representation for dictionaries
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// An Object which can use other objects as property keys.
+///
+/// Keys that are not objects are stored as ordinary dynamic properties.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct DictionaryObject<'gc>(GcCell<'gc, DictionaryObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct DictionaryObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// Values keyed by object identity, in insertion order.
+    ///
+    /// TODO: Weak keys (`new Dictionary(true)`) are currently held strongly,
+    /// as the garbage collector has no support for weak references.
+    object_entries: Vec<(Object<'gc>, Value<'gc>)>,
+}
+
+impl<'gc> DictionaryObject<'gc> {
+    /// Construct a dictionary subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(DictionaryObject(GcCell::allocate(
+            mc,
+            DictionaryObjectData {
+                base,
+                object_entries: Vec::new(),
+            },
+        ))
+        .into())
+    }
+
+    fn position_of(self, key: Object<'gc>) -> Option<usize> {
+        self.0
+            .read()
+            .object_entries
+            .iter()
+            .position(|(k, _)| Object::ptr_eq(*k, key))
+    }
+
+    /// Retrieve a value keyed by an object.
+    pub fn get_property_by_object(self, key: Object<'gc>) -> Value<'gc> {
+        self.position_of(key)
+            .map(|i| self.0.read().object_entries[i].1.clone())
+            .unwrap_or(Value::Undefined)
+    }
+
+    /// Set a value keyed by an object.
+    pub fn set_property_by_object(
+        self,
+        mc: MutationContext<'gc, '_>,
+        key: Object<'gc>,
+        value: Value<'gc>,
+    ) {
+        if let Some(i) = self.position_of(key) {
+            self.0.write(mc).object_entries[i].1 = value;
+        } else {
+            self.0.write(mc).object_entries.push((key, value));
+        }
+    }
+
+    /// Delete a value keyed by an object.
+    pub fn delete_property_by_object(self, mc: MutationContext<'gc, '_>, key: Object<'gc>) -> bool {
+        if let Some(i) = self.position_of(key) {
+            self.0.write(mc).object_entries.remove(i);
+        }
+
+        true
+    }
+
+    /// Check if a value is keyed by an object.
+    pub fn has_property_by_object(self, key: Object<'gc>) -> bool {
+        self.position_of(key).is_some()
+    }
+}
+
+impl<'gc> TObject<'gc> for DictionaryObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn has_enumerant(&self, index: u32) -> bool {
+        let read = self.0.read();
+        let length = read.object_entries.len() as u32;
+
+        if index == 0 {
+            false
+        } else if index <= length {
+            true
+        } else {
+            read.base.get_enumerant_name(index - length).is_some()
+        }
+    }
+
+    fn get_enumerant_key(&self, index: u32, _mc: MutationContext<'gc, '_>) -> Option<Value<'gc>> {
+        let read = self.0.read();
+        let length = read.object_entries.len() as u32;
+
+        if index == 0 {
+            None
+        } else if index <= length {
+            Some(read.object_entries[index as usize - 1].0.into())
+        } else {
+            read.base
+                .get_enumerant_name(index - length)
+                .map(|name| name.local_name().into())
+        }
+    }
+
+    fn get_enumerant_value(
+        &mut self,
+        index: u32,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let length = self.0.read().object_entries.len() as u32;
+
+        if index == 0 {
+            Ok(Value::Undefined)
+        } else if index <= length {
+            Ok(self.0.read().object_entries[index as usize - 1].1.clone())
+        } else {
+            let name = self.0.read().base.get_enumerant_name(index - length);
+
+            if let Some(name) = name {
+                let receiver: Object<'gc> = (*self).into();
+
+                self.get_property(receiver, &name, activation)
+            } else {
+                Ok(Value::Undefined)
+            }
+        }
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_dictionary_object(self) -> Option<DictionaryObject<'gc>> {
+        Some(self)
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::DictionaryObject(*self);
+        let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
+
+        Ok(DictionaryObject(GcCell::allocate(
+            activation.context.gc_context,
+            DictionaryObjectData {
+                base,
+                object_entries: Vec::new(),
+            },
+        ))
+        .into())
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::DictionaryObject(*self);
+
+        Self::derive(this, activation.context.gc_context, class, scope)
+    }
+}
@@ -0,0 +1,87 @@
+//! Object representation for the dispatch lists of event dispatchers
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::DispatchList;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// Internal representation of dispatch lists as generated by `EventDispatcher`.
+///
+/// This object is not intended to be constructed, subclassed, or otherwise
+/// interacted with by user code. It exists solely to hold event handlers
+/// attached to other objects.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct DispatchObject<'gc>(GcCell<'gc, DispatchObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct DispatchObjectData<'gc> {
+    /// The base script object.
+    base: ScriptObjectData<'gc>,
+
+    /// The dispatch list this object holds.
+    dispatch: DispatchList<'gc>,
+}
+
+impl<'gc> DispatchObject<'gc> {
+    /// Construct an empty dispatch list.
+    pub fn empty_list(mc: MutationContext<'gc, '_>) -> Object<'gc> {
+        // TODO: Dispatch lists currently inherit from nothing, as they are
+        // never exposed to user code.
+        let base = ScriptObjectData::base_new(None, ScriptObjectClass::NoClass);
+
+        DispatchObject(GcCell::allocate(
+            mc,
+            DispatchObjectData {
+                base,
+                dispatch: DispatchList::new(),
+            },
+        ))
+        .into()
+    }
+}
+
+impl<'gc> TObject<'gc> for DispatchObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn construct(
+        &self,
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        Err("Cannot construct internal event dispatcher structures.".into())
+    }
+
+    fn derive(
+        &self,
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _class: GcCell<'gc, Class<'gc>>,
+        _scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        Err("Cannot subclass internal event dispatcher structures.".into())
+    }
+
+    fn as_dispatch(&self) -> Option<Ref<DispatchList<'gc>>> {
+        Some(Ref::map(self.0.read(), |od| &od.dispatch))
+    }
+
+    fn as_dispatch_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<DispatchList<'gc>>> {
+        Some(RefMut::map(self.0.write(mc), |od| &mut od.dispatch))
+    }
+}
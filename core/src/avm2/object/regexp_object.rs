@@ -0,0 +1,102 @@
+//! Object representation for regular expressions
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::regexp::RegExp;
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use enumset::EnumSet;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which holds a compiled regular expression.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct RegExpObject<'gc>(GcCell<'gc, RegExpObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct RegExpObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The regular expression this object holds.
+    regexp: RegExp<'gc>,
+}
+
+impl<'gc> RegExpObject<'gc> {
+    /// Construct a regular expression subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(RegExpObject(GcCell::allocate(
+            mc,
+            RegExpObjectData {
+                base,
+                regexp: RegExp::new("".into(), EnumSet::empty())?,
+            },
+        ))
+        .into())
+    }
+}
+
+impl<'gc> TObject<'gc> for RegExpObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_regexp(&self) -> Option<Ref<RegExp<'gc>>> {
+        Some(Ref::map(self.0.read(), |rod| &rod.regexp))
+    }
+
+    fn as_regexp_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<RegExp<'gc>>> {
+        Some(RefMut::map(self.0.write(mc), |rod| &mut rod.regexp))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::RegExpObject(*self);
+        let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
+
+        Ok(RegExpObject(GcCell::allocate(
+            activation.context.gc_context,
+            RegExpObjectData {
+                base,
+                regexp: RegExp::new("".into(), EnumSet::empty())?,
+            },
+        ))
+        .into())
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::RegExpObject(*self);
+
+        Self::derive(this, activation.context.gc_context, class, scope)
+    }
+}
@@ -80,7 +80,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         receiver: Object<'gc>,
         name: &QName<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         let rv = self
             .0
             .read()
@@ -95,7 +95,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let rv = self
             .0
             .write(activation.context.gc_context)
@@ -112,7 +112,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let rv = self
             .0
             .write(activation.context.gc_context)
@@ -135,7 +135,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         self.0.write(gc_context).delete_property(name)
     }
 
-    fn get_slot(self, id: u32) -> Result<Value<'gc>, Error> {
+    fn get_slot(self, id: u32) -> Result<Value<'gc>, Error<'gc>> {
         self.0.read().get_slot(id)
     }
 
@@ -144,7 +144,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         id: u32,
         value: Value<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.write(mc).set_slot(id, value, mc)
     }
 
@@ -153,7 +153,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         id: u32,
         value: Value<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.write(mc).init_slot(id, value, mc)
     }
 
@@ -161,7 +161,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         self.0.read().get_method(id)
     }
 
-    fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error> {
+    fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error<'gc>> {
         self.0.read().get_trait(name)
     }
 
@@ -169,7 +169,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         &self,
         name: &QName<'gc>,
         known_traits: &mut Vec<Trait<'gc>>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.read().get_provided_trait(name, known_traits)
     }
 
@@ -177,26 +177,26 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         self.0.read().get_scope()
     }
 
-    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
         self.0.read().resolve_any(local_name)
     }
 
     fn resolve_any_trait(
         self,
         local_name: AvmString<'gc>,
-    ) -> Result<Option<Namespace<'gc>>, Error> {
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
         self.0.read().resolve_any_trait(local_name)
     }
 
-    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error> {
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         self.0.read().has_own_property(name)
     }
 
-    fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error> {
+    fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         self.0.read().has_trait(name)
     }
 
-    fn provides_trait(self, name: &QName<'gc>) -> Result<bool, Error> {
+    fn provides_trait(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         self.0.read().provides_trait(name)
     }
 
@@ -233,7 +233,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         mc: MutationContext<'gc, '_>,
         name: &QName<'gc>,
         is_enumerable: bool,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0
             .write(mc)
             .set_local_property_is_enumerable(name, is_enumerable)
@@ -247,7 +247,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
         _args: &[Value<'gc>],
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::ScriptObject(*self);
         Ok(ScriptObject::object(activation.context.gc_context, this))
     }
@@ -257,7 +257,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
         class: GcCell<'gc, Class<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::ScriptObject(*self);
         Ok(ScriptObject::prototype(
             activation.context.gc_context,
@@ -267,7 +267,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         ))
     }
 
-    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         if let Some(class) = self.as_proto_class() {
             Ok(AvmString::new(mc, format!("[object {}]", class.read().name().local_name())).into())
         } else {
@@ -275,7 +275,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         }
     }
 
-    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         Ok(Value::Object(Object::from(*self)))
     }
 
@@ -295,7 +295,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.write(mc).install_getter(name, disp_id, function)
     }
 
@@ -305,7 +305,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.write(mc).install_setter(name, disp_id, function)
     }
 
@@ -314,7 +314,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         mc: MutationContext<'gc, '_>,
         name: QName<'gc>,
         value: Value<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.write(mc).install_dynamic_property(name, value)
     }
 
@@ -426,7 +426,7 @@ impl<'gc> ScriptObjectData<'gc> {
         receiver: Object<'gc>,
         name: &QName<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<ReturnValue<'gc>, Error> {
+    ) -> Result<ReturnValue<'gc>, Error<'gc>> {
         let prop = self.values.get(name);
 
         if let Some(prop) = prop {
@@ -442,7 +442,7 @@ impl<'gc> ScriptObjectData<'gc> {
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<ReturnValue<'gc>, Error> {
+    ) -> Result<ReturnValue<'gc>, Error<'gc>> {
         let slot_id = if let Some(prop) = self.values.get(name) {
             if let Some(slot_id) = prop.slot_id() {
                 Some(slot_id)
@@ -476,7 +476,7 @@ impl<'gc> ScriptObjectData<'gc> {
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<ReturnValue<'gc>, Error> {
+    ) -> Result<ReturnValue<'gc>, Error<'gc>> {
         if let Some(prop) = self.values.get_mut(name) {
             if let Some(slot_id) = prop.slot_id() {
                 self.init_slot(slot_id, value, activation.context.gc_context)?;
@@ -515,7 +515,7 @@ impl<'gc> ScriptObjectData<'gc> {
         can_delete
     }
 
-    pub fn get_slot(&self, id: u32) -> Result<Value<'gc>, Error> {
+    pub fn get_slot(&self, id: u32) -> Result<Value<'gc>, Error<'gc>> {
         //TODO: slot inheritance, I think?
         self.slots
             .get(id as usize)
@@ -530,7 +530,7 @@ impl<'gc> ScriptObjectData<'gc> {
         id: u32,
         value: Value<'gc>,
         _mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if let Some(slot) = self.slots.get_mut(id as usize) {
             slot.set(value)
         } else {
@@ -544,7 +544,7 @@ impl<'gc> ScriptObjectData<'gc> {
         id: u32,
         value: Value<'gc>,
         _mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if let Some(slot) = self.slots.get_mut(id as usize) {
             slot.init(value)
         } else {
@@ -557,7 +557,7 @@ impl<'gc> ScriptObjectData<'gc> {
         self.methods.get(id as usize).and_then(|v| *v)
     }
 
-    pub fn get_trait(&self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error> {
+    pub fn get_trait(&self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error<'gc>> {
         match &self.class {
             //Class constructors have local traits only.
             ScriptObjectClass::ClassConstructor(..) => {
@@ -596,7 +596,7 @@ impl<'gc> ScriptObjectData<'gc> {
         &self,
         name: &QName<'gc>,
         known_traits: &mut Vec<Trait<'gc>>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         match &self.class {
             ScriptObjectClass::ClassConstructor(class, ..) => {
                 class.read().lookup_class_traits(name, known_traits)
@@ -608,7 +608,7 @@ impl<'gc> ScriptObjectData<'gc> {
         }
     }
 
-    pub fn has_trait(&self, name: &QName<'gc>) -> Result<bool, Error> {
+    pub fn has_trait(&self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         match &self.class {
             //Class constructors have local traits only.
             ScriptObjectClass::ClassConstructor(..) => self.provides_trait(name),
@@ -635,7 +635,7 @@ impl<'gc> ScriptObjectData<'gc> {
         }
     }
 
-    pub fn provides_trait(&self, name: &QName<'gc>) -> Result<bool, Error> {
+    pub fn provides_trait(&self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         match &self.class {
             ScriptObjectClass::ClassConstructor(class, ..) => {
                 Ok(class.read().has_class_trait(name))
@@ -655,7 +655,10 @@ impl<'gc> ScriptObjectData<'gc> {
         }
     }
 
-    pub fn resolve_any(&self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
+    pub fn resolve_any(
+        &self,
+        local_name: AvmString<'gc>,
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
         for (key, _value) in self.values.iter() {
             if key.local_name() == local_name {
                 return Ok(Some(key.namespace().clone()));
@@ -682,7 +685,7 @@ impl<'gc> ScriptObjectData<'gc> {
     pub fn resolve_any_trait(
         &self,
         local_name: AvmString<'gc>,
-    ) -> Result<Option<Namespace<'gc>>, Error> {
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
         if let Some(proto) = self.proto {
             let proto_trait_name = proto.resolve_any_trait(local_name)?;
             if let Some(ns) = proto_trait_name {
@@ -701,7 +704,7 @@ impl<'gc> ScriptObjectData<'gc> {
         }
     }
 
-    pub fn has_own_property(&self, name: &QName<'gc>) -> Result<bool, Error> {
+    pub fn has_own_property(&self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         Ok(self.values.get(name).is_some() || self.has_trait(name)?)
     }
 
@@ -751,7 +754,7 @@ impl<'gc> ScriptObjectData<'gc> {
         &mut self,
         name: &QName<'gc>,
         is_enumerable: bool,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if is_enumerable && self.values.contains_key(name) && !self.enumerants.contains(name) {
             // Traits are never enumerable
             if self.has_trait(name)? {
@@ -803,8 +806,8 @@ impl<'gc> ScriptObjectData<'gc> {
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error> {
-        let executable: Result<Executable<'gc>, Error> = function
+    ) -> Result<(), Error<'gc>> {
+        let executable: Result<Executable<'gc>, Error<'gc>> = function
             .as_executable()
             .ok_or_else(|| "Attempted to install getter without a valid method".into());
         let executable = executable?;
@@ -838,8 +841,8 @@ impl<'gc> ScriptObjectData<'gc> {
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error> {
-        let executable: Result<Executable<'gc>, Error> = function
+    ) -> Result<(), Error<'gc>> {
+        let executable: Result<Executable<'gc>, Error<'gc>> = function
             .as_executable()
             .ok_or_else(|| "Attempted to install setter without a valid method".into());
         let executable = executable?;
@@ -867,7 +870,7 @@ impl<'gc> ScriptObjectData<'gc> {
         &mut self,
         name: QName<'gc>,
         value: Value<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.values
             .insert(name, Property::new_dynamic_property(value));
 
@@ -49,7 +49,7 @@ impl<'gc> StageObject<'gc> {
         mc: MutationContext<'gc, '_>,
         class: GcCell<'gc, Class<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let base = ScriptObjectData::base_new(
             Some(base_proto),
             ScriptObjectClass::InstancePrototype(class, scope),
@@ -72,7 +72,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         reciever: Object<'gc>,
         name: &QName<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         let read = self.0.read();
         let rv = read.base.get_property_local(reciever, name, activation)?;
 
@@ -87,7 +87,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let mut write = self.0.write(activation.context.gc_context);
         let rv = write
             .base
@@ -106,7 +106,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let mut write = self.0.write(activation.context.gc_context);
         let rv = write
             .base
@@ -135,7 +135,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         self.0.write(gc_context).base.delete_property(multiname)
     }
 
-    fn get_slot(self, id: u32) -> Result<Value<'gc>, Error> {
+    fn get_slot(self, id: u32) -> Result<Value<'gc>, Error<'gc>> {
         self.0.read().base.get_slot(id)
     }
 
@@ -144,7 +144,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         id: u32,
         value: Value<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.write(mc).base.set_slot(id, value, mc)
     }
 
@@ -153,7 +153,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         id: u32,
         value: Value<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.write(mc).base.init_slot(id, value, mc)
     }
 
@@ -161,7 +161,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         self.0.read().base.get_method(id)
     }
 
-    fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error> {
+    fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error<'gc>> {
         self.0.read().base.get_trait(name)
     }
 
@@ -169,7 +169,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         &self,
         name: &QName<'gc>,
         known_traits: &mut Vec<Trait<'gc>>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.read().base.get_provided_trait(name, known_traits)
     }
 
@@ -177,26 +177,26 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         self.0.read().base.get_scope()
     }
 
-    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
         self.0.read().base.resolve_any(local_name)
     }
 
     fn resolve_any_trait(
         self,
         local_name: AvmString<'gc>,
-    ) -> Result<Option<Namespace<'gc>>, Error> {
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
         self.0.read().base.resolve_any_trait(local_name)
     }
 
-    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error> {
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         self.0.read().base.has_own_property(name)
     }
 
-    fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error> {
+    fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         self.0.read().base.has_trait(name)
     }
 
-    fn provides_trait(self, name: &QName<'gc>) -> Result<bool, Error> {
+    fn provides_trait(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         self.0.read().base.provides_trait(name)
     }
 
@@ -233,7 +233,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         mc: MutationContext<'gc, '_>,
         name: &QName<'gc>,
         is_enumerable: bool,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0
             .write(mc)
             .base
@@ -266,7 +266,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         _arguments: &[Value<'gc>],
         _activation: &mut Activation<'_, 'gc, '_>,
         _base_proto: Option<Object<'gc>>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         Err("Not a callable function!".into())
     }
 
@@ -274,7 +274,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
         _args: &[Value<'gc>],
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::StageObject(*self);
         let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
 
@@ -293,7 +293,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
         class: GcCell<'gc, Class<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::StageObject(*self);
         let base = ScriptObjectData::base_new(
             Some(this),
@@ -310,11 +310,11 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         .into())
     }
 
-    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         Ok("todo: movieclip values".into())
     }
 
-    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         Ok(Value::Object(Object::from(*self)))
     }
 
@@ -337,7 +337,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0
             .write(mc)
             .base
@@ -350,7 +350,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0
             .write(mc)
             .base
@@ -362,7 +362,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         mc: MutationContext<'gc, '_>,
         name: QName<'gc>,
         value: Value<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.write(mc).base.install_dynamic_property(name, value)
     }
 
@@ -0,0 +1,398 @@
+//! Vector storage object
+
+use crate::avm1::AvmString;
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which stores typed properties in vector storage
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct VectorObject<'gc>(GcCell<'gc, VectorObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct VectorObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// Vector-structured properties
+    vector: VectorStorage<'gc>,
+}
+
+impl<'gc> VectorObject<'gc> {
+    /// Wrap existing vector storage in an object.
+    ///
+    /// The given prototype should be that of the `Vector` specialization for
+    /// the storage's value type.
+    pub fn from_vector(
+        vector: VectorStorage<'gc>,
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(Some(base_proto), ScriptObjectClass::NoClass);
+
+        VectorObject(GcCell::allocate(mc, VectorObjectData { base, vector })).into()
+    }
+
+    /// Construct a vector subclass.
+    ///
+    /// Each specialization of `Vector` is its own subclass; `value_type` and
+    /// `default_value` are inherited by all vectors constructed from it.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+        value_type: Option<Object<'gc>>,
+        default_value: Value<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(VectorObject(GcCell::allocate(
+            mc,
+            VectorObjectData {
+                base,
+                vector: VectorStorage::new(0, false, value_type, default_value),
+            },
+        ))
+        .into())
+    }
+
+    /// Parse a property name as a vector index.
+    fn index_of(name: &QName<'gc>) -> Option<usize> {
+        if name.namespace().is_public() {
+            name.local_name().parse::<usize>().ok()
+        } else {
+            None
+        }
+    }
+
+    /// Coerce a value to the type this vector holds.
+    fn coerce_value(
+        self,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let value_type = self.0.read().vector.value_type();
+
+        if let Some(value_type) = value_type {
+            activation.coerce_to_type(value, value_type)
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+impl<'gc> TObject<'gc> for VectorObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn get_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let read = self.0.read();
+
+        if let Some(index) = Self::index_of(name) {
+            return read.vector.get(index);
+        }
+
+        let rv = read.base.get_property_local(receiver, name, activation)?;
+
+        drop(read);
+
+        rv.resolve(activation)
+    }
+
+    fn get_property(
+        &mut self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        // Out-of-range reads are errors, not prototype lookups.
+        if let Some(index) = Self::index_of(name) {
+            return self.0.read().vector.get(index);
+        }
+
+        if !self.has_instantiated_property(name) {
+            for abc_trait in self.get_trait(name)? {
+                self.install_trait(activation, abc_trait, receiver)?;
+            }
+        }
+
+        let has_no_getter = self.has_own_virtual_setter(name) && !self.has_own_virtual_getter(name);
+
+        if self.has_own_property(name)? && !has_no_getter {
+            return self.get_property_local(receiver, name, activation);
+        }
+
+        if let Some(mut proto) = self.proto() {
+            return proto.get_property(receiver, name, activation);
+        }
+
+        Ok(Value::Undefined)
+    }
+
+    fn set_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        if let Some(index) = Self::index_of(name) {
+            let value = self.coerce_value(value, activation)?;
+
+            return self
+                .0
+                .write(activation.context.gc_context)
+                .vector
+                .set(index, value);
+        }
+
+        let mut write = self.0.write(activation.context.gc_context);
+        let rv = write
+            .base
+            .set_property_local(receiver, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn init_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        if let Some(index) = Self::index_of(name) {
+            let value = self.coerce_value(value, activation)?;
+
+            return self
+                .0
+                .write(activation.context.gc_context)
+                .vector
+                .set(index, value);
+        }
+
+        let mut write = self.0.write(activation.context.gc_context);
+        let rv = write
+            .base
+            .init_property_local(receiver, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn is_property_overwritable(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        name: &QName<'gc>,
+    ) -> bool {
+        self.0.write(gc_context).base.is_property_overwritable(name)
+    }
+
+    fn delete_property(&self, gc_context: MutationContext<'gc, '_>, name: &QName<'gc>) -> bool {
+        // Vector elements cannot be deleted.
+        if Self::index_of(name).is_some() {
+            return false;
+        }
+
+        self.0.write(gc_context).base.delete_property(name)
+    }
+
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
+        if let Some(index) = Self::index_of(name) {
+            return Ok(index < self.0.read().vector.length());
+        }
+
+        self.0.read().base.has_own_property(name)
+    }
+
+    fn resolve_multiname(
+        self,
+        multiname: &Multiname<'gc>,
+    ) -> Result<Option<QName<'gc>>, Error<'gc>> {
+        // Numeric names always refer to vector elements, even if they are
+        // out of range.
+        if multiname
+            .namespace_set()
+            .any(|ns| ns.is_public() || ns.is_any())
+        {
+            if let Some(name) = multiname.local_name() {
+                if name.parse::<usize>().is_ok() {
+                    return Ok(Some(QName::new(Namespace::public_namespace(), name)));
+                }
+            }
+        }
+
+        for ns in multiname.namespace_set() {
+            if ns.is_any() {
+                if let Some(name) = multiname.local_name() {
+                    let ns = self.resolve_any(name)?;
+                    return Ok(ns.map(|ns| QName::new(ns, name)));
+                } else {
+                    return Ok(None);
+                }
+            } else if let Some(name) = multiname.local_name() {
+                let qname = QName::new(ns.clone(), name);
+                if self.has_property(&qname)? {
+                    return Ok(Some(qname));
+                }
+            } else {
+                return Ok(None);
+            }
+        }
+
+        if let Some(proto) = self.proto() {
+            return Ok(proto.resolve_multiname(multiname)?);
+        }
+
+        Ok(None)
+    }
+
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
+        if let Ok(index) = local_name.parse::<usize>() {
+            if index < self.0.read().vector.length() {
+                return Ok(Some(Namespace::public_namespace()));
+            }
+        }
+
+        self.0.read().base.resolve_any(local_name)
+    }
+
+    fn resolve_any_trait(
+        self,
+        local_name: AvmString<'gc>,
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
+        self.0.read().base.resolve_any_trait(local_name)
+    }
+
+    fn has_enumerant(&self, index: u32) -> bool {
+        let read = self.0.read();
+        let length = read.vector.length() as u32;
+
+        if index == 0 {
+            false
+        } else if index <= length {
+            true
+        } else {
+            read.base.get_enumerant_name(index - length).is_some()
+        }
+    }
+
+    fn get_enumerant_key(&self, index: u32, mc: MutationContext<'gc, '_>) -> Option<Value<'gc>> {
+        let read = self.0.read();
+        let length = read.vector.length() as u32;
+
+        if index == 0 {
+            None
+        } else if index <= length {
+            Some(AvmString::new(mc, (index - 1).to_string()).into())
+        } else {
+            read.base
+                .get_enumerant_name(index - length)
+                .map(|name| name.local_name().into())
+        }
+    }
+
+    fn get_enumerant_value(
+        &mut self,
+        index: u32,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let length = self.0.read().vector.length() as u32;
+
+        if index == 0 {
+            Ok(Value::Undefined)
+        } else if index <= length {
+            self.0.read().vector.get(index as usize - 1)
+        } else {
+            let name = self.0.read().base.get_enumerant_name(index - length);
+
+            if let Some(name) = name {
+                let receiver: Object<'gc> = (*self).into();
+
+                self.get_property(receiver, &name, activation)
+            } else {
+                Ok(Value::Undefined)
+            }
+        }
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_vector_storage(&self) -> Option<Ref<VectorStorage<'gc>>> {
+        Some(Ref::map(self.0.read(), |vod| &vod.vector))
+    }
+
+    fn as_vector_storage_mut(
+        &self,
+        mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<VectorStorage<'gc>>> {
+        Some(RefMut::map(self.0.write(mc), |vod| &mut vod.vector))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::VectorObject(*self);
+        let vector = self.0.read().vector.new_of_same_type();
+
+        Ok(Self::from_vector(
+            vector,
+            this,
+            activation.context.gc_context,
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::VectorObject(*self);
+        let read = self.0.read();
+        let value_type = read.vector.value_type();
+        let default_value = read.vector.default_value();
+
+        drop(read);
+
+        Self::derive(
+            this,
+            activation.context.gc_context,
+            class,
+            scope,
+            value_type,
+            default_value,
+        )
+    }
+}
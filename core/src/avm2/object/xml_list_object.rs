@@ -0,0 +1,170 @@
+//! Object representation for E4X XML lists
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::e4x::E4XNode;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject, XmlObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::{Hint, Value};
+use crate::avm2::Error;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which represents an ordered list of XML nodes.
+///
+/// Child, attribute and index access on XML lists is handled by the E4X
+/// support in the interpreter, see `crate::avm2::e4x`.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct XmlListObject<'gc>(GcCell<'gc, XmlListObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct XmlListObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The nodes in this list.
+    nodes: Vec<E4XNode<'gc>>,
+}
+
+impl<'gc> XmlListObject<'gc> {
+    /// Construct an XML list subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(XmlListObject(GcCell::allocate(
+            mc,
+            XmlListObjectData {
+                base,
+                nodes: Vec::new(),
+            },
+        ))
+        .into())
+    }
+
+    /// Wrap a list of nodes in an XML list object.
+    pub fn from_nodes(
+        mc: MutationContext<'gc, '_>,
+        base_proto: Object<'gc>,
+        nodes: Vec<E4XNode<'gc>>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(Some(base_proto), ScriptObjectClass::NoClass);
+
+        XmlListObject(GcCell::allocate(mc, XmlListObjectData { base, nodes })).into()
+    }
+}
+
+impl<'gc> TObject<'gc> for XmlListObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn has_enumerant(&self, index: u32) -> bool {
+        let read = self.0.read();
+        let length = read.nodes.len() as u32;
+
+        if index == 0 {
+            false
+        } else if index <= length {
+            true
+        } else {
+            read.base.get_enumerant_name(index - length).is_some()
+        }
+    }
+
+    fn get_enumerant_key(&self, index: u32, mc: MutationContext<'gc, '_>) -> Option<Value<'gc>> {
+        let read = self.0.read();
+        let length = read.nodes.len() as u32;
+
+        if index == 0 {
+            None
+        } else if index <= length {
+            Some(AvmString::new(mc, (index - 1).to_string()).into())
+        } else {
+            read.base
+                .get_enumerant_name(index - length)
+                .map(|name| name.local_name().into())
+        }
+    }
+
+    fn get_enumerant_value(
+        &mut self,
+        index: u32,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let length = self.0.read().nodes.len() as u32;
+
+        if index == 0 {
+            Ok(Value::Undefined)
+        } else if index <= length {
+            let node = self.0.read().nodes[index as usize - 1].clone();
+            let xml_proto = activation.context.avm2.prototypes().xml;
+
+            Ok(XmlObject::from_node(activation.context.gc_context, xml_proto, node).into())
+        } else {
+            let name = self.0.read().base.get_enumerant_name(index - length);
+
+            if let Some(name) = name {
+                let receiver: Object<'gc> = (*self).into();
+
+                self.get_property(receiver, &name, activation)
+            } else {
+                Ok(Value::Undefined)
+            }
+        }
+    }
+
+    fn default_hint(&self) -> Hint {
+        Hint::String
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_xml_list(&self) -> Option<Ref<Vec<E4XNode<'gc>>>> {
+        Some(Ref::map(self.0.read(), |xlod| &xlod.nodes))
+    }
+
+    fn as_xml_list_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<Vec<E4XNode<'gc>>>> {
+        Some(RefMut::map(self.0.write(mc), |xlod| &mut xlod.nodes))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::XmlListObject(*self);
+
+        Ok(Self::from_nodes(
+            activation.context.gc_context,
+            this,
+            Vec::new(),
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::XmlListObject(*self);
+
+        Self::derive(this, activation.context.gc_context, class, scope)
+    }
+}
@@ -0,0 +1,111 @@
+//! Object representation for E4X XML values
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::e4x::E4XNode;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::{Hint, Value};
+use crate::avm2::Error;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// An Object which represents a single XML node or attribute.
+///
+/// Child and attribute access on XML objects is handled by the E4X support
+/// in the interpreter, see `crate::avm2::e4x`.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct XmlObject<'gc>(GcCell<'gc, XmlObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct XmlObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The node this object refers to.
+    node: E4XNode<'gc>,
+}
+
+impl<'gc> XmlObject<'gc> {
+    /// Construct an XML subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(XmlObject(GcCell::allocate(
+            mc,
+            XmlObjectData {
+                base,
+                node: E4XNode::new_text(mc, ""),
+            },
+        ))
+        .into())
+    }
+
+    /// Wrap an existing node in an XML object.
+    pub fn from_node(
+        mc: MutationContext<'gc, '_>,
+        base_proto: Object<'gc>,
+        node: E4XNode<'gc>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(Some(base_proto), ScriptObjectClass::NoClass);
+
+        XmlObject(GcCell::allocate(mc, XmlObjectData { base, node })).into()
+    }
+}
+
+impl<'gc> TObject<'gc> for XmlObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn default_hint(&self) -> Hint {
+        Hint::String
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_xml(&self) -> Option<E4XNode<'gc>> {
+        Some(self.0.read().node.clone())
+    }
+
+    fn set_xml(&self, mc: MutationContext<'gc, '_>, node: E4XNode<'gc>) {
+        self.0.write(mc).node = node;
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::XmlObject(*self);
+        let node = E4XNode::new_text(activation.context.gc_context, "");
+
+        Ok(Self::from_node(activation.context.gc_context, this, node))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::XmlObject(*self);
+
+        Self::derive(this, activation.context.gc_context, class, scope)
+    }
+}
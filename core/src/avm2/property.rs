@@ -105,7 +105,10 @@ impl<'gc> Property<'gc> {
     ///
     /// This function errors if attempting to install executables into a
     /// non-virtual property.
-    pub fn install_virtual_getter(&mut self, getter_impl: Executable<'gc>) -> Result<(), Error> {
+    pub fn install_virtual_getter(
+        &mut self,
+        getter_impl: Executable<'gc>,
+    ) -> Result<(), Error<'gc>> {
         match self {
             Property::Virtual { get, .. } => *get = Some(getter_impl),
             Property::Stored { .. } => return Err("Not a virtual property".into()),
@@ -119,7 +122,10 @@ impl<'gc> Property<'gc> {
     ///
     /// This function errors if attempting to install executables into a
     /// non-virtual property.
-    pub fn install_virtual_setter(&mut self, setter_impl: Executable<'gc>) -> Result<(), Error> {
+    pub fn install_virtual_setter(
+        &mut self,
+        setter_impl: Executable<'gc>,
+    ) -> Result<(), Error<'gc>> {
         match self {
             Property::Virtual { set, .. } => *set = Some(setter_impl),
             Property::Stored { .. } => return Err("Not a virtual property".into()),
@@ -137,7 +143,7 @@ impl<'gc> Property<'gc> {
         &self,
         this: Object<'gc>,
         base_proto: Option<Object<'gc>>,
-    ) -> Result<ReturnValue<'gc>, Error> {
+    ) -> Result<ReturnValue<'gc>, Error<'gc>> {
         match self {
             Property::Virtual { get: Some(get), .. } => Ok(ReturnValue::defer_execution(
                 *get,
@@ -163,7 +169,7 @@ impl<'gc> Property<'gc> {
         this: Object<'gc>,
         base_proto: Option<Object<'gc>>,
         new_value: impl Into<Value<'gc>>,
-    ) -> Result<ReturnValue<'gc>, Error> {
+    ) -> Result<ReturnValue<'gc>, Error<'gc>> {
         match self {
             Property::Virtual { set, .. } => {
                 if let Some(function) = set {
@@ -207,7 +213,7 @@ impl<'gc> Property<'gc> {
         this: Object<'gc>,
         base_proto: Option<Object<'gc>>,
         new_value: impl Into<Value<'gc>>,
-    ) -> Result<ReturnValue<'gc>, Error> {
+    ) -> Result<ReturnValue<'gc>, Error<'gc>> {
         match self {
             Property::Virtual { set, .. } => {
                 if let Some(function) = set {
@@ -0,0 +1,878 @@
+//! Regular expression engine for `RegExp` objects
+//!
+//! This is a backtracking matcher for the ECMAScript 3 regular expression
+//! grammar, along with the `s` (dotall) and `x` (extended) flags that AVM2
+//! adds to it. Matching operates on the characters of a string.
+
+use crate::avm2::string::AvmString;
+use enumset::{EnumSet, EnumSetType};
+use gc_arena::Collect;
+
+/// Flags that change how a regular expression matches.
+#[derive(EnumSetType, Debug)]
+pub enum RegExpFlag {
+    /// `g`: `exec` and `test` start matching from `lastIndex`.
+    Global,
+
+    /// `i`: Letters match regardless of case.
+    IgnoreCase,
+
+    /// `m`: `^` and `$` also match at line terminators.
+    Multiline,
+
+    /// `s`: `.` also matches line terminators.
+    DotAll,
+
+    /// `x`: Whitespace and `#` comments in the pattern are ignored.
+    Extended,
+}
+
+impl RegExpFlag {
+    /// Parse a flags string, such as `"gi"`.
+    ///
+    /// Unknown flag characters are ignored.
+    pub fn parse(flags: &str) -> EnumSet<RegExpFlag> {
+        let mut set = EnumSet::empty();
+
+        for c in flags.chars() {
+            match c {
+                'g' => set.insert(RegExpFlag::Global),
+                'i' => set.insert(RegExpFlag::IgnoreCase),
+                'm' => set.insert(RegExpFlag::Multiline),
+                's' => set.insert(RegExpFlag::DotAll),
+                'x' => set.insert(RegExpFlag::Extended),
+                _ => false,
+            };
+        }
+
+        set
+    }
+
+    /// Render a set of flags as a string, in canonical order.
+    pub fn flags_to_string(flags: EnumSet<RegExpFlag>) -> String {
+        let mut string = String::new();
+
+        if flags.contains(RegExpFlag::Global) {
+            string.push('g');
+        }
+        if flags.contains(RegExpFlag::IgnoreCase) {
+            string.push('i');
+        }
+        if flags.contains(RegExpFlag::Multiline) {
+            string.push('m');
+        }
+        if flags.contains(RegExpFlag::DotAll) {
+            string.push('s');
+        }
+        if flags.contains(RegExpFlag::Extended) {
+            string.push('x');
+        }
+
+        string
+    }
+}
+
+/// A single item of a character class.
+#[derive(Clone, Debug)]
+enum ClassItem {
+    Range(char, char),
+    Digit(bool),
+    Word(bool),
+    Space(bool),
+}
+
+/// A node of a parsed regular expression.
+#[derive(Clone, Debug)]
+enum Node {
+    Empty,
+    Char(char),
+    Any,
+    Class {
+        items: Vec<ClassItem>,
+        negated: bool,
+    },
+    LineStart,
+    LineEnd,
+    WordBoundary(bool),
+    Group(Box<Node>, Option<usize>),
+    Backreference(usize),
+    LookAhead(Box<Node>, bool),
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+        greedy: bool,
+    },
+}
+
+/// The result of a successful match.
+///
+/// Positions are character indices into the matched string. The first
+/// capture is always the entire match; unmatched groups are `None`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegexMatch {
+    pub captures: Vec<Option<(usize, usize)>>,
+}
+
+impl RegexMatch {
+    /// The character index the match starts at.
+    pub fn start(&self) -> usize {
+        self.captures[0].map(|(s, _)| s).unwrap_or(0)
+    }
+
+    /// The character index the match ends at.
+    pub fn end(&self) -> usize {
+        self.captures[0].map(|(_, e)| e).unwrap_or(0)
+    }
+}
+
+/// A compiled regular expression.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct Regex {
+    root: Node,
+    group_count: usize,
+    flags: EnumSet<RegExpFlag>,
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    group_count: usize,
+    extended: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Skip whitespace and comments in extended mode.
+    fn skip_extended(&mut self) {
+        if !self.extended {
+            return;
+        }
+
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.pos += 1;
+            } else if c == '#' {
+                while let Some(c) = self.peek() {
+                    self.pos += 1;
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn parse_alternation(&mut self) -> Result<Node, String> {
+        let mut alternatives = vec![self.parse_concat()?];
+
+        while self.eat('|') {
+            alternatives.push(self.parse_concat()?);
+        }
+
+        if alternatives.len() == 1 {
+            Ok(alternatives.pop().unwrap())
+        } else {
+            Ok(Node::Alternation(alternatives))
+        }
+    }
+
+    fn parse_concat(&mut self) -> Result<Node, String> {
+        let mut nodes = Vec::new();
+
+        loop {
+            self.skip_extended();
+            match self.peek() {
+                None | Some('|') | Some(')') => break,
+                _ => {
+                    let atom = self.parse_atom()?;
+                    let atom = self.parse_quantifier(atom)?;
+                    nodes.push(atom);
+                }
+            }
+        }
+
+        match nodes.len() {
+            0 => Ok(Node::Empty),
+            1 => Ok(nodes.pop().unwrap()),
+            _ => Ok(Node::Concat(nodes)),
+        }
+    }
+
+    fn parse_number(&mut self) -> Option<usize> {
+        let start = self.pos;
+
+        while let Some(c) = self.peek() {
+            if c.is_ascii_digit() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+
+        self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .ok()
+    }
+
+    fn parse_quantifier(&mut self, atom: Node) -> Result<Node, String> {
+        self.skip_extended();
+
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                let start = self.pos;
+                self.pos += 1;
+
+                if let Some(min) = self.parse_number() {
+                    let max = if self.eat(',') {
+                        self.parse_number()
+                    } else {
+                        Some(min)
+                    };
+
+                    if self.peek() == Some('}') {
+                        (min, max)
+                    } else {
+                        // Not a valid quantifier; treat the brace literally.
+                        self.pos = start;
+                        return Ok(atom);
+                    }
+                } else {
+                    self.pos = start;
+                    return Ok(atom);
+                }
+            }
+            _ => return Ok(atom),
+        };
+
+        self.pos += 1;
+
+        if let Some(max) = max {
+            if max < min {
+                return Err("numbers out of order in {} quantifier".to_string());
+            }
+        }
+
+        let greedy = !self.eat('?');
+
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+            greedy,
+        })
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, String> {
+        let c = self
+            .peek()
+            .ok_or_else(|| "unexpected end of pattern".to_string())?;
+        self.pos += 1;
+
+        match c {
+            '.' => Ok(Node::Any),
+            '^' => Ok(Node::LineStart),
+            '$' => Ok(Node::LineEnd),
+            '(' => {
+                let node = if self.eat('?') {
+                    if self.eat(':') {
+                        Node::Group(Box::new(self.parse_alternation()?), None)
+                    } else if self.eat('=') {
+                        Node::LookAhead(Box::new(self.parse_alternation()?), false)
+                    } else if self.eat('!') {
+                        Node::LookAhead(Box::new(self.parse_alternation()?), true)
+                    } else {
+                        return Err("unsupported group".to_string());
+                    }
+                } else {
+                    self.group_count += 1;
+                    let index = self.group_count;
+                    Node::Group(Box::new(self.parse_alternation()?), Some(index))
+                };
+
+                if !self.eat(')') {
+                    return Err("missing )".to_string());
+                }
+
+                Ok(node)
+            }
+            '[' => self.parse_class(),
+            '\\' => self.parse_escape(),
+            '*' | '+' | '?' => Err("nothing to repeat".to_string()),
+            c => Ok(Node::Char(c)),
+        }
+    }
+
+    /// Parse an escape that is valid both inside and outside of classes.
+    fn parse_char_escape(&mut self, c: char) -> char {
+        match c {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'f' => '\u{0C}',
+            'v' => '\u{0B}',
+            '0' => '\0',
+            'x' | 'u' => {
+                let digits = if c == 'x' { 2 } else { 4 };
+                let hex: String = self.chars[self.pos..].iter().take(digits).collect();
+
+                if hex.len() == digits {
+                    if let Some(decoded) = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(std::char::from_u32)
+                    {
+                        self.pos += digits;
+                        return decoded;
+                    }
+                }
+
+                c
+            }
+            c => c,
+        }
+    }
+
+    fn parse_escape(&mut self) -> Result<Node, String> {
+        let c = self
+            .peek()
+            .ok_or_else(|| "\\ at end of pattern".to_string())?;
+        self.pos += 1;
+
+        Ok(match c {
+            'd' => Node::Class {
+                items: vec![ClassItem::Digit(true)],
+                negated: false,
+            },
+            'D' => Node::Class {
+                items: vec![ClassItem::Digit(false)],
+                negated: false,
+            },
+            'w' => Node::Class {
+                items: vec![ClassItem::Word(true)],
+                negated: false,
+            },
+            'W' => Node::Class {
+                items: vec![ClassItem::Word(false)],
+                negated: false,
+            },
+            's' => Node::Class {
+                items: vec![ClassItem::Space(true)],
+                negated: false,
+            },
+            'S' => Node::Class {
+                items: vec![ClassItem::Space(false)],
+                negated: false,
+            },
+            'b' => Node::WordBoundary(true),
+            'B' => Node::WordBoundary(false),
+            '1'..='9' => {
+                self.pos -= 1;
+                Node::Backreference(self.parse_number().unwrap_or(0))
+            }
+            c => Node::Char(self.parse_char_escape(c)),
+        })
+    }
+
+    fn parse_class_char(&mut self) -> Result<ClassItem, String> {
+        let c = self.peek().ok_or_else(|| "missing ]".to_string())?;
+        self.pos += 1;
+
+        if c != '\\' {
+            return Ok(ClassItem::Range(c, c));
+        }
+
+        let c = self.peek().ok_or_else(|| "missing ]".to_string())?;
+        self.pos += 1;
+
+        Ok(match c {
+            'd' => ClassItem::Digit(true),
+            'D' => ClassItem::Digit(false),
+            'w' => ClassItem::Word(true),
+            'W' => ClassItem::Word(false),
+            's' => ClassItem::Space(true),
+            'S' => ClassItem::Space(false),
+            'b' => ClassItem::Range('\u{08}', '\u{08}'),
+            c => {
+                let c = self.parse_char_escape(c);
+                ClassItem::Range(c, c)
+            }
+        })
+    }
+
+    fn parse_class(&mut self) -> Result<Node, String> {
+        let negated = self.eat('^');
+        let mut items = Vec::new();
+
+        loop {
+            match self.peek() {
+                None => return Err("missing ]".to_string()),
+                Some(']') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => {
+                    let item = self.parse_class_char()?;
+
+                    if let ClassItem::Range(low, _) = item {
+                        if self.peek() == Some('-')
+                            && matches!(self.chars.get(self.pos + 1), Some(c) if *c != ']')
+                        {
+                            self.pos += 1;
+                            if let ClassItem::Range(high, _) = self.parse_class_char()? {
+                                if high < low {
+                                    return Err("range out of order in character class".to_string());
+                                }
+
+                                items.push(ClassItem::Range(low, high));
+                                continue;
+                            }
+
+                            return Err("invalid range in character class".to_string());
+                        }
+                    }
+
+                    items.push(item);
+                }
+            }
+        }
+
+        Ok(Node::Class { items, negated })
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn is_line_terminator(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Mutable state of a single match attempt.
+struct State<'a> {
+    input: &'a [char],
+    captures: Vec<Option<(usize, usize)>>,
+}
+
+type Continuation<'k, 'a> = &'k mut dyn FnMut(usize, &mut State<'a>) -> bool;
+
+impl Regex {
+    /// Compile a regular expression.
+    pub fn new(source: &str, flags: EnumSet<RegExpFlag>) -> Result<Self, String> {
+        let mut parser = Parser {
+            chars: source.chars().collect(),
+            pos: 0,
+            group_count: 0,
+            extended: flags.contains(RegExpFlag::Extended),
+        };
+
+        let root = parser.parse_alternation()?;
+
+        if parser.pos < parser.chars.len() {
+            return Err("unmatched )".to_string());
+        }
+
+        Ok(Self {
+            root,
+            group_count: parser.group_count,
+            flags,
+        })
+    }
+
+    /// The flags the expression was compiled with.
+    pub fn flags(&self) -> EnumSet<RegExpFlag> {
+        self.flags
+    }
+
+    /// Search for a match in `input`, starting at character index `start`.
+    pub fn find_at(&self, input: &[char], start: usize) -> Option<RegexMatch> {
+        let mut state = State {
+            input,
+            captures: vec![None; self.group_count + 1],
+        };
+
+        for begin in start..=input.len() {
+            for capture in state.captures.iter_mut() {
+                *capture = None;
+            }
+
+            let mut end = None;
+            let is_match = self.match_node(&self.root, begin, &mut state, &mut |pos, _| {
+                end = Some(pos);
+                true
+            });
+            if is_match {
+                state.captures[0] = end.map(|end| (begin, end));
+
+                return Some(RegexMatch {
+                    captures: state.captures,
+                });
+            }
+        }
+
+        None
+    }
+
+    fn chars_equal(&self, a: char, b: char) -> bool {
+        a == b || (self.flags.contains(RegExpFlag::IgnoreCase) && fold_case(a) == fold_case(b))
+    }
+
+    fn class_matches(&self, items: &[ClassItem], negated: bool, c: char) -> bool {
+        let ignore_case = self.flags.contains(RegExpFlag::IgnoreCase);
+        let matched = items.iter().any(|item| match item {
+            ClassItem::Range(low, high) => {
+                (*low <= c && c <= *high)
+                    || (ignore_case
+                        && c.to_lowercase()
+                            .chain(c.to_uppercase())
+                            .any(|c| *low <= c && c <= *high))
+            }
+            ClassItem::Digit(positive) => c.is_ascii_digit() == *positive,
+            ClassItem::Word(positive) => is_word_char(c) == *positive,
+            ClassItem::Space(positive) => c.is_whitespace() == *positive,
+        });
+
+        matched != negated
+    }
+
+    fn is_word_boundary(&self, input: &[char], pos: usize) -> bool {
+        let before = pos > 0 && is_word_char(input[pos - 1]);
+        let after = pos < input.len() && is_word_char(input[pos]);
+
+        before != after
+    }
+
+    fn match_node<'a>(
+        &self,
+        node: &Node,
+        pos: usize,
+        state: &mut State<'a>,
+        k: Continuation<'_, 'a>,
+    ) -> bool {
+        let input = state.input;
+        let multiline = self.flags.contains(RegExpFlag::Multiline);
+
+        match node {
+            Node::Empty => k(pos, state),
+            Node::Char(c) => {
+                pos < input.len() && self.chars_equal(input[pos], *c) && k(pos + 1, state)
+            }
+            Node::Any => {
+                pos < input.len()
+                    && (self.flags.contains(RegExpFlag::DotAll) || !is_line_terminator(input[pos]))
+                    && k(pos + 1, state)
+            }
+            Node::Class { items, negated } => {
+                pos < input.len()
+                    && self.class_matches(items, *negated, input[pos])
+                    && k(pos + 1, state)
+            }
+            Node::LineStart => {
+                (pos == 0 || (multiline && is_line_terminator(input[pos - 1]))) && k(pos, state)
+            }
+            Node::LineEnd => {
+                (pos == input.len() || (multiline && is_line_terminator(input[pos])))
+                    && k(pos, state)
+            }
+            Node::WordBoundary(expected) => {
+                self.is_word_boundary(input, pos) == *expected && k(pos, state)
+            }
+            Node::Group(inner, None) => self.match_node(inner, pos, state, k),
+            Node::Group(inner, Some(index)) => {
+                let index = *index;
+
+                self.match_node(inner, pos, state, &mut |end, state| {
+                    let old = state.captures[index];
+                    state.captures[index] = Some((pos, end));
+
+                    if k(end, state) {
+                        true
+                    } else {
+                        state.captures[index] = old;
+                        false
+                    }
+                })
+            }
+            Node::Backreference(index) => {
+                let captured = state.captures.get(*index).cloned().unwrap_or(None);
+
+                if let Some((start, end)) = captured {
+                    let length = end - start;
+
+                    if pos + length > input.len() {
+                        return false;
+                    }
+
+                    for i in 0..length {
+                        if !self.chars_equal(input[start + i], input[pos + i]) {
+                            return false;
+                        }
+                    }
+
+                    k(pos + length, state)
+                } else {
+                    k(pos, state)
+                }
+            }
+            Node::LookAhead(inner, negated) => {
+                let saved = state.captures.clone();
+                let matched = self.match_node(inner, pos, state, &mut |_, _| true);
+
+                if matched == *negated {
+                    state.captures = saved;
+                    return false;
+                }
+
+                if *negated {
+                    state.captures = saved.clone();
+                }
+
+                if k(pos, state) {
+                    true
+                } else {
+                    state.captures = saved;
+                    false
+                }
+            }
+            Node::Concat(nodes) => self.match_sequence(nodes, pos, state, k),
+            Node::Alternation(alternatives) => {
+                for alternative in alternatives {
+                    let saved = state.captures.clone();
+
+                    if self.match_node(alternative, pos, state, k) {
+                        return true;
+                    }
+
+                    state.captures = saved;
+                }
+
+                false
+            }
+            Node::Repeat {
+                node,
+                min,
+                max,
+                greedy,
+            } => self.match_repeat(node, *min, *max, *greedy, 0, pos, state, k),
+        }
+    }
+
+    fn match_sequence<'a>(
+        &self,
+        nodes: &[Node],
+        pos: usize,
+        state: &mut State<'a>,
+        k: Continuation<'_, 'a>,
+    ) -> bool {
+        if let Some((first, rest)) = nodes.split_first() {
+            self.match_node(first, pos, state, &mut |next, state| {
+                self.match_sequence(rest, next, state, k)
+            })
+        } else {
+            k(pos, state)
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn match_repeat<'a>(
+        &self,
+        node: &Node,
+        min: usize,
+        max: Option<usize>,
+        greedy: bool,
+        count: usize,
+        pos: usize,
+        state: &mut State<'a>,
+        k: Continuation<'_, 'a>,
+    ) -> bool {
+        let can_repeat = match max {
+            Some(max) => count < max,
+            None => true,
+        };
+        let can_stop = count >= min;
+
+        if !greedy && can_stop && k(pos, state) {
+            return true;
+        }
+
+        let mut repeat = |next, state: &mut State<'a>| {
+            // An empty iteration past the minimum can never make
+            // progress, so it is rejected to avoid looping forever.
+            if next == pos && count >= min {
+                return false;
+            }
+
+            self.match_repeat(node, min, max, greedy, count + 1, next, state, k)
+        };
+        if can_repeat && self.match_node(node, pos, state, &mut repeat) {
+            return true;
+        }
+
+        greedy && can_stop && k(pos, state)
+    }
+}
+
+/// The state of a `RegExp` object.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct RegExp<'gc> {
+    /// The pattern the expression was constructed from.
+    source: AvmString<'gc>,
+
+    /// The character index that global matches start from.
+    last_index: usize,
+
+    /// The compiled expression.
+    regex: Regex,
+}
+
+impl<'gc> RegExp<'gc> {
+    /// Compile a new regular expression.
+    pub fn new(source: AvmString<'gc>, flags: EnumSet<RegExpFlag>) -> Result<Self, String> {
+        Ok(Self {
+            regex: Regex::new(&source, flags)?,
+            source,
+            last_index: 0,
+        })
+    }
+
+    pub fn source(&self) -> AvmString<'gc> {
+        self.source
+    }
+
+    pub fn flags(&self) -> EnumSet<RegExpFlag> {
+        self.regex.flags()
+    }
+
+    pub fn last_index(&self) -> usize {
+        self.last_index
+    }
+
+    pub fn set_last_index(&mut self, last_index: usize) {
+        self.last_index = last_index;
+    }
+
+    /// Run the expression against a string, as with `RegExp.exec`.
+    ///
+    /// Global expressions start searching at `lastIndex` and update it to the
+    /// end of the match, or reset it to zero if there is no match. Match
+    /// positions are in characters.
+    pub fn exec(&mut self, input: &[char]) -> Option<RegexMatch> {
+        let global = self.flags().contains(RegExpFlag::Global);
+        let start = if global { self.last_index } else { 0 };
+
+        if start > input.len() {
+            self.last_index = 0;
+            return None;
+        }
+
+        let result = self.regex.find_at(input, start);
+
+        if global {
+            self.last_index = result.as_ref().map(|m| m.end()).unwrap_or(0);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(pattern: &str, flags: &str, input: &str) -> Option<Vec<Option<String>>> {
+        let regex = Regex::new(pattern, RegExpFlag::parse(flags)).unwrap();
+        let chars: Vec<char> = input.chars().collect();
+
+        regex.find_at(&chars, 0).map(|m| {
+            m.captures
+                .iter()
+                .map(|c| c.map(|(s, e)| chars[s..e].iter().collect()))
+                .collect()
+        })
+    }
+
+    fn some(values: &[&str]) -> Option<Vec<Option<String>>> {
+        Some(values.iter().map(|v| Some(v.to_string())).collect())
+    }
+
+    #[test]
+    fn literals_and_classes() {
+        assert_eq!(find("abc", "", "xxabcxx"), some(&["abc"]));
+        assert_eq!(find("a.c", "", "a\nc abc"), some(&["abc"]));
+        assert_eq!(find("a.c", "s", "a\nc"), some(&["a\nc"]));
+        assert_eq!(find("[a-c]+", "", "xxbcaz"), some(&["bca"]));
+        assert_eq!(find("[^a-c]+", "", "abxyc"), some(&["xy"]));
+        assert_eq!(
+            find("\\d+\\s\\w+", "", "is 42 apples"),
+            some(&["42 apples"])
+        );
+        assert_eq!(find("ABC", "i", "xabc"), some(&["abc"]));
+        assert_eq!(find("[A-Z]+", "i", "abc"), some(&["abc"]));
+        assert_eq!(find("\\x41\\u0042", "", "AB"), some(&["AB"]));
+        assert_eq!(find("abc", "", "ab"), None);
+    }
+
+    #[test]
+    fn quantifiers() {
+        assert_eq!(find("a{2,3}", "", "aaaa"), some(&["aaa"]));
+        assert_eq!(find("a{2}", "", "aaaa"), some(&["aa"]));
+        assert_eq!(find("<.+>", "", "<a><b>"), some(&["<a><b>"]));
+        assert_eq!(find("<.+?>", "", "<a><b>"), some(&["<a>"]));
+        assert_eq!(
+            find("(a*)*b", "", "aab").map(|c| c[0].clone()),
+            Some(Some("aab".to_string()))
+        );
+        assert_eq!(find("x{a", "", "x{a"), some(&["x{a"]));
+        assert!(Regex::new("*a", EnumSet::empty()).is_err());
+    }
+
+    #[test]
+    fn groups_and_anchors() {
+        assert_eq!(
+            find("(\\w+)@(\\w+)\\.com", "", "mail bob@example.com"),
+            some(&["bob@example.com", "bob", "example"])
+        );
+        assert_eq!(
+            find("(a)|(b)", "", "b"),
+            Some(vec![Some("b".to_string()), None, Some("b".to_string())])
+        );
+        assert_eq!(find("(?:ab)+", "", "ababx"), some(&["abab"]));
+        assert_eq!(find("(\\w)\\1", "", "abccd"), some(&["cc", "c"]));
+        assert_eq!(find("^b", "", "a\nb"), None);
+        assert_eq!(find("^b$", "m", "a\nb\nc"), some(&["b"]));
+        assert_eq!(find("\\bfoo\\b", "", "afoo foo"), some(&["foo"]));
+        assert_eq!(find("foo(?=bar)", "", "foobaz foobar"), some(&["foo"]));
+        assert_eq!(
+            find("foo(?!bar)", "", "foobar foobaz").map(|c| c.len()),
+            Some(1)
+        );
+        assert_eq!(find("a b # comment\n c", "x", "abc"), some(&["abc"]));
+    }
+}
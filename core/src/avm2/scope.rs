@@ -1,6 +1,7 @@
 //! Represents AVM2 scope chain resolution.
 
 use crate::avm2::activation::Activation;
+use crate::avm2::e4x;
 use crate::avm2::names::Multiname;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
@@ -104,6 +105,22 @@ impl<'gc> Scope<'gc> {
         self.parent
     }
 
+    /// Check if this is a `with` scope on an `XML` or `XMLList` object that
+    /// has a child or attribute of the given name.
+    ///
+    /// XML properties are not resolvable as `QName`s, so filter expressions
+    /// such as `list.(@id == 1)` need this to find their scope.
+    fn has_xml_property(&self, name: &Multiname<'gc>) -> bool {
+        if self.class != ScopeClass::With || !e4x::is_xml_like(self.values) {
+            return false;
+        }
+
+        match name.local_name() {
+            Some(local_name) => e4x::has_property(self.values, &local_name, name.is_attribute()),
+            None => false,
+        }
+    }
+
     /// Find an object that contains a given property in the scope stack.
     ///
     /// This function yields `None` if no such scope exists.
@@ -111,7 +128,11 @@ impl<'gc> Scope<'gc> {
         &self,
         name: &Multiname<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Option<Object<'gc>>, Error> {
+    ) -> Result<Option<Object<'gc>>, Error<'gc>> {
+        if self.has_xml_property(name) {
+            return Ok(Some(self.values));
+        }
+
         if let Some(qname) = self.locals().resolve_multiname(name)? {
             if self.locals().has_property(&qname)? {
                 return Ok(Some(*self.locals()));
@@ -141,7 +162,18 @@ impl<'gc> Scope<'gc> {
         &mut self,
         name: &Multiname<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Option<Value<'gc>>, Error> {
+    ) -> Result<Option<Value<'gc>>, Error<'gc>> {
+        if self.has_xml_property(name) {
+            let local_name = name.local_name().unwrap_or_default();
+
+            return Ok(e4x::get_property(
+                activation,
+                self.values,
+                &local_name,
+                name.is_attribute(),
+            ));
+        }
+
         if let Some(qname) = self.locals().resolve_multiname(name)? {
             if self.locals().has_property(&qname)? {
                 return Ok(Some(self.values.get_property(
@@ -84,7 +84,7 @@ impl<'gc> TranslationUnit<'gc> {
         self,
         method_index: u32,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Method<'gc>, Error> {
+    ) -> Result<Method<'gc>, Error<'gc>> {
         let read = self.0.read();
         if let Some(method) = read.methods.get(&method_index) {
             return Ok(method.clone());
@@ -92,7 +92,7 @@ impl<'gc> TranslationUnit<'gc> {
 
         drop(read);
 
-        let method: Result<Gc<'gc, BytecodeMethod<'gc>>, Error> =
+        let method: Result<Gc<'gc, BytecodeMethod<'gc>>, Error<'gc>> =
             BytecodeMethod::from_method_index(self, Index::new(method_index), mc)
                 .ok_or_else(|| "Method index does not exist".into());
         let method: Method<'gc> = method?.into();
@@ -111,7 +111,7 @@ impl<'gc> TranslationUnit<'gc> {
         class_index: u32,
         avm2: &mut Avm2<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<GcCell<'gc, Class<'gc>>, Error> {
+    ) -> Result<GcCell<'gc, Class<'gc>>, Error<'gc>> {
         let read = self.0.read();
         if let Some(class) = read.classes.get(&class_index) {
             return Ok(*class);
@@ -133,7 +133,7 @@ impl<'gc> TranslationUnit<'gc> {
         script_index: u32,
         avm2: &mut Avm2<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Script<'gc>, Error> {
+    ) -> Result<Script<'gc>, Error<'gc>> {
         let read = self.0.read();
         if let Some(scripts) = read.scripts.get(&script_index) {
             return Ok(*scripts);
@@ -167,7 +167,7 @@ impl<'gc> TranslationUnit<'gc> {
         self,
         string_index: u32,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Option<AvmString<'gc>>, Error> {
+    ) -> Result<Option<AvmString<'gc>>, Error<'gc>> {
         let mut write = self.0.write(mc);
         if let Some(string) = write.strings.get(&string_index) {
             return Ok(Some(*string));
@@ -202,7 +202,7 @@ impl<'gc> TranslationUnit<'gc> {
         self,
         string_index: u32,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<AvmString<'gc>, Error> {
+    ) -> Result<AvmString<'gc>, Error<'gc>> {
         Ok(self
             .pool_string_option(string_index, mc)?
             .unwrap_or_default())
@@ -271,9 +271,9 @@ impl<'gc> Script<'gc> {
         script_index: u32,
         globals: Object<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, Error<'gc>> {
         let abc = unit.abc();
-        let script: Result<&AbcScript, Error> = abc
+        let script: Result<&AbcScript, Error<'gc>> = abc
             .scripts
             .get(script_index as usize)
             .ok_or_else(|| "LoadError: Script index not valid".into());
@@ -305,7 +305,7 @@ impl<'gc> Script<'gc> {
         script_index: u32,
         avm2: &mut Avm2<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let mut write = self.0.write(mc);
 
         if write.traits_loaded {
@@ -315,7 +315,7 @@ impl<'gc> Script<'gc> {
         write.traits_loaded = true;
 
         let abc = unit.abc();
-        let script: Result<_, Error> = abc
+        let script: Result<_, Error<'gc>> = abc
             .scripts
             .get(script_index as usize)
             .ok_or_else(|| "LoadError: Script index not valid".into());
@@ -346,7 +346,7 @@ impl<'gc> Script<'gc> {
     pub fn globals(
         &mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let mut write = self.0.write(context.gc_context);
         if !write.initialized {
             write.initialized = true;
@@ -378,7 +378,7 @@ impl<'gc> Script<'gc> {
     ///
     /// This function will return an error if it is incorrectly called before
     /// traits are loaded.
-    pub fn traits<'a>(&'a self) -> Result<Ref<'a, [Trait<'gc>]>, Error> {
+    pub fn traits<'a>(&'a self) -> Result<Ref<'a, [Trait<'gc>]>, Error<'gc>> {
         let read = self.0.read();
 
         if !read.traits_loaded {
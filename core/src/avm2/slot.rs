@@ -65,7 +65,7 @@ impl<'gc> Slot<'gc> {
     }
 
     /// Write the value of this slot.
-    pub fn set(&mut self, new_value: impl Into<Value<'gc>>) -> Result<(), Error> {
+    pub fn set(&mut self, new_value: impl Into<Value<'gc>>) -> Result<(), Error<'gc>> {
         match self {
             Self::Unoccupied => Err("Cannot overwrite unoccupied slot".into()),
             Self::Occupied { value, attributes } => {
@@ -83,7 +83,7 @@ impl<'gc> Slot<'gc> {
     }
 
     /// Initialize a slot to a particular value.
-    pub fn init(&mut self, new_value: impl Into<Value<'gc>>) -> Result<(), Error> {
+    pub fn init(&mut self, new_value: impl Into<Value<'gc>>) -> Result<(), Error<'gc>> {
         match self {
             Self::Unoccupied => Err("Cannot initialize unoccupied slot".into()),
             Self::Occupied { value, .. } => {
@@ -182,7 +182,7 @@ impl<'gc> Trait<'gc> {
         abc_trait: &AbcTrait,
         avm2: &mut Avm2<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, Error<'gc>> {
         let name = QName::from_abc_multiname(unit, abc_trait.name.clone(), mc)?;
 
         Ok(match &abc_trait.kind {
@@ -6,7 +6,7 @@ use crate::avm2::names::QName;
 use crate::avm2::object::{NamespaceObject, Object, PrimitiveObject, TObject};
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::string::AvmString;
-use crate::avm2::{Avm2, Error};
+use crate::avm2::{Avm2, Error, ErrorClass};
 use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
 use enumset::{EnumSet, EnumSetType};
 use gc_arena::{Collect, MutationContext};
@@ -140,7 +140,7 @@ impl PartialEq for Value<'_> {
     }
 }
 
-pub fn abc_int(translation_unit: TranslationUnit<'_>, index: Index<i32>) -> Result<i32, Error> {
+pub fn abc_int(translation_unit: TranslationUnit<'_>, index: Index<i32>) -> Result<i32, Error<'_>> {
     if index.0 == 0 {
         return Ok(0);
     }
@@ -154,7 +154,10 @@ pub fn abc_int(translation_unit: TranslationUnit<'_>, index: Index<i32>) -> Resu
         .ok_or_else(|| format!("Unknown int constant {}", index.0).into())
 }
 
-pub fn abc_uint(translation_unit: TranslationUnit<'_>, index: Index<u32>) -> Result<u32, Error> {
+pub fn abc_uint(
+    translation_unit: TranslationUnit<'_>,
+    index: Index<u32>,
+) -> Result<u32, Error<'_>> {
     if index.0 == 0 {
         return Ok(0);
     }
@@ -168,7 +171,10 @@ pub fn abc_uint(translation_unit: TranslationUnit<'_>, index: Index<u32>) -> Res
         .ok_or_else(|| format!("Unknown uint constant {}", index.0).into())
 }
 
-pub fn abc_double(translation_unit: TranslationUnit<'_>, index: Index<f64>) -> Result<f64, Error> {
+pub fn abc_double(
+    translation_unit: TranslationUnit<'_>,
+    index: Index<f64>,
+) -> Result<f64, Error<'_>> {
     if index.0 == 0 {
         return Ok(NAN);
     }
@@ -188,7 +194,7 @@ pub fn abc_default_value<'gc>(
     default: &AbcDefaultValue,
     avm2: &mut Avm2<'gc>,
     mc: MutationContext<'gc, '_>,
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     match default {
         AbcDefaultValue::Int(i) => abc_int(translation_unit, *i).map(|v| v.into()),
         AbcDefaultValue::Uint(u) => abc_uint(translation_unit, *u).map(|v| v.into()),
@@ -214,7 +220,7 @@ pub fn abc_default_value<'gc>(
 }
 
 impl<'gc> Value<'gc> {
-    pub fn as_namespace(&self) -> Result<Ref<Namespace<'gc>>, Error> {
+    pub fn as_namespace(&self) -> Result<Ref<Namespace<'gc>>, Error<'gc>> {
         match self {
             Value::Object(ns) => ns
                 .as_namespace()
@@ -229,7 +235,7 @@ impl<'gc> Value<'gc> {
     /// object methods called. This should only be used if you specifically
     /// need the behavior of only handling actual numbers; otherwise you should
     /// use the appropriate `coerce_to_` method.
-    pub fn as_number(&self, mc: MutationContext<'gc, '_>) -> Result<f64, Error> {
+    pub fn as_number(&self, mc: MutationContext<'gc, '_>) -> Result<f64, Error<'gc>> {
         match self {
             // Methods that look for numbers in Flash Player don't seem to care
             // about user-defined `valueOf` implementations. This code upholds
@@ -289,7 +295,7 @@ impl<'gc> Value<'gc> {
         &self,
         hint: Option<Hint>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         let hint = hint.unwrap_or_else(|| match self {
             Value::Object(o) => o.default_hint(),
             _ => Hint::Number,
@@ -320,7 +326,11 @@ impl<'gc> Value<'gc> {
                     return Ok(prim);
                 }
 
-                Err("TypeError: cannot convert object to string".into())
+                Err(Error::avm_error(
+                    ErrorClass::TypeError,
+                    1050,
+                    "Cannot convert object to primitive.",
+                ))
             }
             Value::Object(o) if hint == Hint::Number => {
                 let mut prim = self.clone();
@@ -346,7 +356,11 @@ impl<'gc> Value<'gc> {
                     return Ok(prim);
                 }
 
-                Err("TypeError: cannot convert object to number".into())
+                Err(Error::avm_error(
+                    ErrorClass::TypeError,
+                    1050,
+                    "Cannot convert object to primitive.",
+                ))
             }
             _ => Ok(self.clone()),
         }
@@ -360,7 +374,10 @@ impl<'gc> Value<'gc> {
     ///
     /// Numerical conversions occur according to ECMA-262 3rd Edition's
     /// ToNumber algorithm which appears to match AVM2.
-    pub fn coerce_to_number(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<f64, Error> {
+    pub fn coerce_to_number(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<f64, Error<'gc>> {
         Ok(match self {
             Value::Undefined => f64::NAN,
             Value::Null => 0.0,
@@ -431,7 +448,10 @@ impl<'gc> Value<'gc> {
     ///
     /// Numerical conversions occur according to ECMA-262 3rd Edition's
     /// ToUint32 algorithm which appears to match AVM2.
-    pub fn coerce_to_u32(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<u32, Error> {
+    pub fn coerce_to_u32(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<u32, Error<'gc>> {
         Ok(f64_to_wrapping_u32(self.coerce_to_number(activation)?))
     }
 
@@ -442,7 +462,10 @@ impl<'gc> Value<'gc> {
     ///
     /// Numerical conversions occur according to ECMA-262 3rd Edition's
     /// ToInt32 algorithm which appears to match AVM2.
-    pub fn coerce_to_i32(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<i32, Error> {
+    pub fn coerce_to_i32(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<i32, Error<'gc>> {
         Ok(f64_to_wrapping_i32(self.coerce_to_number(activation)?))
     }
 
@@ -453,7 +476,7 @@ impl<'gc> Value<'gc> {
     pub fn coerce_to_enumset<E>(
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<EnumSet<E>, Error>
+    ) -> Result<EnumSet<E>, Error<'gc>>
     where
         E: EnumSetType,
     {
@@ -492,7 +515,7 @@ impl<'gc> Value<'gc> {
     pub fn coerce_to_string<'a>(
         &'a self,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<AvmString<'gc>, Error> {
+    ) -> Result<AvmString<'gc>, Error<'gc>> {
         Ok(match self {
             Value::Undefined => "undefined".into(),
             Value::Null => "null".into(),
@@ -547,10 +570,22 @@ impl<'gc> Value<'gc> {
     pub fn coerce_to_object(
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         match self {
-            Value::Undefined => return Err("TypeError: undefined is not an Object".into()),
-            Value::Null => return Err("TypeError: null is not an Object".into()),
+            Value::Undefined => {
+                return Err(Error::avm_error(
+                    ErrorClass::TypeError,
+                    1010,
+                    "A term is undefined and has no properties.",
+                ))
+            }
+            Value::Null => {
+                return Err(Error::avm_error(
+                    ErrorClass::TypeError,
+                    1009,
+                    "Cannot access a property or method of a null object reference.",
+                ))
+            }
             Value::Object(o) => return Ok(*o),
             _ => {}
         };
@@ -580,7 +615,7 @@ impl<'gc> Value<'gc> {
         &self,
         other: &Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<bool, Error> {
+    ) -> Result<bool, Error<'gc>> {
         match (self, other) {
             (Value::Undefined, Value::Undefined) => Ok(true),
             (Value::Null, Value::Null) => Ok(true),
@@ -671,7 +706,7 @@ impl<'gc> Value<'gc> {
         &self,
         other: &Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Option<bool>, Error> {
+    ) -> Result<Option<bool>, Error<'gc>> {
         let prim_self = self.coerce_to_primitive(Some(Hint::Number), activation)?;
         let prim_other = other.coerce_to_primitive(Some(Hint::Number), activation)?;
 
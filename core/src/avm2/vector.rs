@@ -0,0 +1,223 @@
+//! Vector support types
+
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorClass};
+use gc_arena::Collect;
+use std::cmp::Ordering;
+
+/// The vector storage portion of a vector object.
+///
+/// Vector values are always of the vector's value type; it is the caller's
+/// responsibility to coerce values before storing them. Unlike arrays,
+/// vectors have no holes: every index below the length holds a value.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct VectorStorage<'gc> {
+    /// The values stored in the vector.
+    storage: Vec<Value<'gc>>,
+
+    /// Whether or not the length of this vector may change.
+    is_fixed: bool,
+
+    /// The class of values stored in this vector.
+    ///
+    /// `None` indicates the any type, as in `Vector.<*>`.
+    value_type: Option<Object<'gc>>,
+
+    /// The value new elements take when the vector is lengthened.
+    default_value: Value<'gc>,
+}
+
+impl<'gc> VectorStorage<'gc> {
+    /// Construct new vector storage.
+    ///
+    /// The length parameter indicates how big the vector storage should start
+    /// out as. All values will be the given default value.
+    pub fn new(
+        length: usize,
+        is_fixed: bool,
+        value_type: Option<Object<'gc>>,
+        default_value: Value<'gc>,
+    ) -> Self {
+        let mut storage = Vec::new();
+
+        storage.resize(length, default_value.clone());
+
+        Self {
+            storage,
+            is_fixed,
+            value_type,
+            default_value,
+        }
+    }
+
+    /// Construct empty storage of the same type as this one.
+    pub fn new_of_same_type(&self) -> Self {
+        Self::new(0, false, self.value_type, self.default_value.clone())
+    }
+
+    /// The class of values stored in this vector, if any.
+    pub fn value_type(&self) -> Option<Object<'gc>> {
+        self.value_type
+    }
+
+    /// The value new elements take when the vector is lengthened.
+    pub fn default_value(&self) -> Value<'gc> {
+        self.default_value.clone()
+    }
+
+    /// Check if this vector has a fixed length.
+    pub fn is_fixed(&self) -> bool {
+        self.is_fixed
+    }
+
+    /// Change whether or not this vector has a fixed length.
+    pub fn set_is_fixed(&mut self, is_fixed: bool) {
+        self.is_fixed = is_fixed;
+    }
+
+    /// Get the length of the vector.
+    pub fn length(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Check that the length of the vector may be changed.
+    fn check_fixed_length(&self) -> Result<(), Error<'gc>> {
+        if self.is_fixed {
+            return Err(Error::avm_error(
+                ErrorClass::RangeError,
+                1126,
+                "Cannot change the length of a fixed Vector.",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Set the length of the vector.
+    ///
+    /// New elements take the default value of the vector's type.
+    pub fn set_length(&mut self, size: usize) -> Result<(), Error<'gc>> {
+        self.check_fixed_length()?;
+        self.storage.resize(size, self.default_value.clone());
+
+        Ok(())
+    }
+
+    /// Retrieve a value from vector storage by index.
+    ///
+    /// Out of bounds values yield a `RangeError`.
+    pub fn get(&self, item: usize) -> Result<Value<'gc>, Error<'gc>> {
+        self.storage.get(item).cloned().ok_or_else(|| {
+            Error::avm_error(
+                ErrorClass::RangeError,
+                1125,
+                format!("The index {} is out of range {}.", item, self.storage.len()),
+            )
+        })
+    }
+
+    /// Set a vector storage slot to a particular value.
+    ///
+    /// Setting the index immediately past the end of a vector appends the
+    /// value; any other out of bounds index yields a `RangeError`.
+    pub fn set(&mut self, item: usize, value: Value<'gc>) -> Result<(), Error<'gc>> {
+        match item.cmp(&self.storage.len()) {
+            Ordering::Less => self.storage[item] = value,
+            Ordering::Equal => self.push(value)?,
+            Ordering::Greater => {
+                return Err(Error::avm_error(
+                    ErrorClass::RangeError,
+                    1125,
+                    format!("The index {} is out of range {}.", item, self.storage.len()),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Push a single value onto the end of this vector.
+    pub fn push(&mut self, item: Value<'gc>) -> Result<(), Error<'gc>> {
+        self.check_fixed_length()?;
+        self.storage.push(item);
+
+        Ok(())
+    }
+
+    /// Pop a value from the back of the vector.
+    ///
+    /// Empty vectors yield the default value of the vector's type.
+    pub fn pop(&mut self) -> Result<Value<'gc>, Error<'gc>> {
+        self.check_fixed_length()?;
+
+        Ok(self
+            .storage
+            .pop()
+            .unwrap_or_else(|| self.default_value.clone()))
+    }
+
+    /// Shift a value from the front of the vector.
+    ///
+    /// Empty vectors yield the default value of the vector's type.
+    pub fn shift(&mut self) -> Result<Value<'gc>, Error<'gc>> {
+        self.check_fixed_length()?;
+
+        if !self.storage.is_empty() {
+            Ok(self.storage.remove(0))
+        } else {
+            Ok(self.default_value.clone())
+        }
+    }
+
+    /// Unshift a single value onto the start of this vector.
+    pub fn unshift(&mut self, item: Value<'gc>) -> Result<(), Error<'gc>> {
+        self.check_fixed_length()?;
+        self.storage.insert(0, item);
+
+        Ok(())
+    }
+
+    /// Reverse the order of the values in this vector.
+    pub fn reverse(&mut self) {
+        self.storage.reverse()
+    }
+
+    /// Iterate over vector values.
+    pub fn iter<'a>(
+        &'a self,
+    ) -> impl DoubleEndedIterator<Item = Value<'gc>> + ExactSizeIterator + 'a {
+        self.storage.iter().cloned()
+    }
+
+    /// Remove `delete_count` values starting at `start` and insert a set of
+    /// new values in their place, returning the values that were removed.
+    ///
+    /// The range must be within the bounds of the vector.
+    pub fn splice<I>(
+        &mut self,
+        start: usize,
+        delete_count: usize,
+        replace_with: I,
+    ) -> Result<Vec<Value<'gc>>, Error<'gc>>
+    where
+        I: IntoIterator<Item = Value<'gc>>,
+    {
+        let replace_with: Vec<Value<'gc>> = replace_with.into_iter().collect();
+
+        if replace_with.len() != delete_count {
+            self.check_fixed_length()?;
+        }
+
+        Ok(self
+            .storage
+            .splice(start..start + delete_count, replace_with)
+            .collect())
+    }
+
+    /// Replace the contents of this vector with a new list of values.
+    pub fn replace_storage(&mut self, storage: Vec<Value<'gc>>) {
+        self.storage = storage;
+    }
+}
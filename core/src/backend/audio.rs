@@ -1,3 +1,5 @@
+use crate::display_object::{DisplayObject, TDisplayObject};
+use gc_arena::Collect;
 use generational_arena::{Arena, Index};
 
 pub mod decoders;
@@ -70,10 +72,17 @@ pub trait AudioBackend {
     /// which only plays a sound if that sound is not already playing.
     fn is_sound_playing_with_handle(&mut self, handle: SoundHandle) -> bool;
 
+    /// Returns whether a sound instance or stream is still playing.
+    fn is_sound_playing(&mut self, instance: SoundInstanceHandle) -> bool;
+
     /// Get the duration of a sound in milliseconds.
     /// Returns `None` if sound is not registered.
     fn get_sound_duration(&self, sound: SoundHandle) -> Option<u32>;
 
+    /// Set the volume and channel mix of a playing sound instance or stream.
+    /// No-op if the sound is not playing.
+    fn set_sound_transform(&mut self, instance: SoundInstanceHandle, transform: SoundTransform);
+
     // TODO: Eventually remove this/move it to library.
     fn is_loading_complete(&self) -> bool {
         true
@@ -139,9 +148,15 @@ impl AudioBackend for NullAudioBackend {
         false
     }
 
+    fn is_sound_playing(&mut self, _instance: SoundInstanceHandle) -> bool {
+        false
+    }
+
     fn get_sound_duration(&self, _sound: SoundHandle) -> Option<u32> {
         None
     }
+
+    fn set_sound_transform(&mut self, _instance: SoundInstanceHandle, _transform: SoundTransform) {}
 }
 
 impl Default for NullAudioBackend {
@@ -149,3 +164,302 @@ impl Default for NullAudioBackend {
         NullAudioBackend::new()
     }
 }
+
+/// The volume and channel mix applied to a sound, as set by `Sound.setTransform`.
+///
+/// All values are percentages. The `left_to_right` coefficient is how much of
+/// the left input channel is played through the right speaker, and so on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub struct SoundTransform {
+    pub volume: i32,
+    pub left_to_left: i32,
+    pub left_to_right: i32,
+    pub right_to_left: i32,
+    pub right_to_right: i32,
+}
+
+impl SoundTransform {
+    /// The balance between the left and right speakers, from -100 (left only)
+    /// to 100 (right only).
+    pub fn pan(&self) -> i32 {
+        // Flash only looks at the direct channels when calculating the pan.
+        if self.left_to_left != 100 {
+            100 - self.left_to_left
+        } else {
+            self.right_to_right - 100
+        }
+    }
+
+    /// Sets the balance between the left and right speakers. This discards
+    /// any cross-channel mixing.
+    pub fn set_pan(&mut self, pan: i32) {
+        if pan >= 0 {
+            self.left_to_left = 100 - pan;
+            self.right_to_right = 100;
+        } else {
+            self.left_to_left = 100;
+            self.right_to_right = 100 + pan;
+        }
+        self.left_to_right = 0;
+        self.right_to_left = 0;
+    }
+
+    /// Applies a parent transform on top of this one, as when a sound plays
+    /// inside of a movie clip with its own transform.
+    pub fn concat(&mut self, parent: &SoundTransform) {
+        let (ll, lr, rl, rr) = (
+            self.left_to_left,
+            self.left_to_right,
+            self.right_to_left,
+            self.right_to_right,
+        );
+        self.left_to_left = (parent.left_to_left * ll + parent.right_to_left * lr) / 100;
+        self.left_to_right = (parent.left_to_right * ll + parent.right_to_right * lr) / 100;
+        self.right_to_left = (parent.left_to_left * rl + parent.right_to_left * rr) / 100;
+        self.right_to_right = (parent.left_to_right * rl + parent.right_to_right * rr) / 100;
+        self.volume = self.volume * parent.volume / 100;
+    }
+
+    /// The mixing matrix for this transform with the volume applied, as
+    /// `[left_to_left, left_to_right, right_to_left, right_to_right]`
+    /// multipliers.
+    pub fn matrix(&self) -> [f32; 4] {
+        let scale = self.volume as f32 / 10000.0;
+        [
+            self.left_to_left as f32 * scale,
+            self.left_to_right as f32 * scale,
+            self.right_to_left as f32 * scale,
+            self.right_to_right as f32 * scale,
+        ]
+    }
+}
+
+impl Default for SoundTransform {
+    fn default() -> Self {
+        Self {
+            volume: 100,
+            left_to_left: 100,
+            left_to_right: 0,
+            right_to_left: 0,
+            right_to_right: 100,
+        }
+    }
+}
+
+/// Keeps track of the sounds started by the movie and the display objects
+/// that own them, so that sound transforms can be applied as they change.
+pub struct AudioManager<'gc> {
+    /// The sounds that were playing as of the last update.
+    sounds: Vec<ActiveSound<'gc>>,
+
+    /// The transform applied to every sound, set by a `Sound` with no owner.
+    global_sound_transform: SoundTransform,
+
+    /// Whether any transforms changed since sounds were last updated.
+    transforms_dirty: bool,
+}
+
+/// A playing sound or stream, and the display object that started it.
+struct ActiveSound<'gc> {
+    instance: SoundInstanceHandle,
+    display_object: Option<DisplayObject<'gc>>,
+}
+
+unsafe impl<'gc> Collect for AudioManager<'gc> {
+    fn trace(&self, cc: gc_arena::CollectionContext) {
+        for sound in &self.sounds {
+            sound.display_object.trace(cc);
+        }
+    }
+}
+
+impl<'gc> AudioManager<'gc> {
+    pub fn new() -> Self {
+        Self {
+            sounds: Vec::new(),
+            global_sound_transform: Default::default(),
+            transforms_dirty: false,
+        }
+    }
+
+    /// Starts an event sound owned by the given display object.
+    pub fn start_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        sound: SoundHandle,
+        settings: &swf::SoundInfo,
+        display_object: Option<DisplayObject<'gc>>,
+    ) -> Option<SoundInstanceHandle> {
+        match audio.start_sound(sound, settings) {
+            Ok(instance) => {
+                self.add_sound(audio, instance, display_object);
+                Some(instance)
+            }
+            Err(e) => {
+                log::error!("Unable to start sound: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Starts the stream sound of the given movie clip.
+    pub fn start_stream(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        clip_frame: u16,
+        clip_data: crate::tag_utils::SwfSlice,
+        stream_info: &swf::SoundStreamHead,
+        movie_clip: DisplayObject<'gc>,
+    ) -> Option<AudioStreamHandle> {
+        match audio.start_stream(movie_clip.id(), clip_frame, clip_data, stream_info) {
+            Ok(stream) => {
+                self.add_sound(audio, stream, Some(movie_clip));
+                Some(stream)
+            }
+            Err(e) => {
+                log::error!("Unable to start stream: {}", e);
+                None
+            }
+        }
+    }
+
+    fn add_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        instance: SoundInstanceHandle,
+        display_object: Option<DisplayObject<'gc>>,
+    ) {
+        let transform = self.transform_for_display_object(display_object);
+        if transform != SoundTransform::default() {
+            audio.set_sound_transform(instance, transform);
+        }
+        self.sounds.push(ActiveSound {
+            instance,
+            display_object,
+        });
+    }
+
+    /// Stops every sound started by the given display object.
+    pub fn stop_sounds_with_display_object(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        display_object: DisplayObject<'gc>,
+    ) {
+        self.sounds.retain(|sound| {
+            if let Some(owner) = sound.display_object {
+                if DisplayObject::ptr_eq(owner, display_object) {
+                    audio.stop_sound(sound.instance);
+                    return false;
+                }
+            }
+            true
+        });
+    }
+
+    pub fn global_sound_transform(&self) -> &SoundTransform {
+        &self.global_sound_transform
+    }
+
+    pub fn set_global_sound_transform(&mut self, transform: SoundTransform) {
+        self.global_sound_transform = transform;
+        self.transforms_dirty = true;
+    }
+
+    /// Flags that a movie clip's sound transform has changed, so that playing
+    /// sounds will be updated.
+    pub fn set_sound_transforms_dirty(&mut self) {
+        self.transforms_dirty = true;
+    }
+
+    /// The final transform of a sound played by the given display object,
+    /// including the transforms of all of its ancestors.
+    pub fn transform_for_display_object(
+        &self,
+        display_object: Option<DisplayObject<'gc>>,
+    ) -> SoundTransform {
+        let mut transform = SoundTransform::default();
+        let mut node = display_object;
+        while let Some(display_object) = node {
+            if let Some(clip) = display_object.as_movie_clip() {
+                transform.concat(&clip.sound_transform());
+            }
+            node = display_object.parent();
+        }
+        transform.concat(&self.global_sound_transform);
+        transform
+    }
+
+    /// Forgets sounds that have finished playing, and reapplies transforms if
+    /// any of them changed.
+    pub fn update_sounds(&mut self, audio: &mut dyn AudioBackend) {
+        self.sounds
+            .retain(|sound| audio.is_sound_playing(sound.instance));
+
+        if self.transforms_dirty {
+            for sound in &self.sounds {
+                let transform = self.transform_for_display_object(sound.display_object);
+                audio.set_sound_transform(sound.instance, transform);
+            }
+            self.transforms_dirty = false;
+        }
+    }
+}
+
+impl Default for AudioManager<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sound_transform_pan() {
+        let mut transform = SoundTransform::default();
+        assert_eq!(transform.pan(), 0);
+
+        transform.set_pan(25);
+        assert_eq!(transform.left_to_left, 75);
+        assert_eq!(transform.right_to_right, 100);
+        assert_eq!(transform.pan(), 25);
+
+        transform.set_pan(-40);
+        assert_eq!(transform.left_to_left, 100);
+        assert_eq!(transform.right_to_right, 60);
+        assert_eq!(transform.pan(), -40);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn sound_transform_concat() {
+        // A clip that swaps its channels, inside a clip at half volume playing left only.
+        let mut transform = SoundTransform {
+            volume: 100,
+            left_to_left: 0,
+            left_to_right: 100,
+            right_to_left: 100,
+            right_to_right: 0,
+        };
+        let mut parent = SoundTransform {
+            volume: 50,
+            ..Default::default()
+        };
+        parent.set_pan(-100);
+        transform.concat(&parent);
+
+        assert_eq!(
+            transform,
+            SoundTransform {
+                volume: 50,
+                left_to_left: 0,
+                left_to_right: 0,
+                right_to_left: 100,
+                right_to_right: 0,
+            }
+        );
+        assert_eq!(transform.matrix(), [0.0, 0.0, 0.5, 0.0]);
+    }
+}
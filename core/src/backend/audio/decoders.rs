@@ -2,11 +2,15 @@
 
 mod adpcm;
 mod mp3;
+mod nellymoser;
 mod pcm;
+mod speex;
 
 pub use adpcm::AdpcmDecoder;
 pub use mp3::Mp3Decoder;
+pub use nellymoser::NellymoserDecoder;
 pub use pcm::PcmDecoder;
+pub use speex::SpeexDecoder;
 
 use crate::tag_utils::SwfSlice;
 use std::io::{Cursor, Read};
@@ -56,18 +60,26 @@ pub fn make_decoder<'a, R: 'a + Send + Read>(
             format.sample_rate.into(),
             data,
         )),
-        _ => {
-            let msg = format!(
-                "make_decoder: Unhandled audio compression {:?}",
-                format.compression
-            );
-            log::error!("{}", msg);
-            return Err(msg.into());
+        AudioCompression::Nellymoser
+        | AudioCompression::Nellymoser8Khz
+        | AudioCompression::Nellymoser16Khz => {
+            Box::new(NellymoserDecoder::new(data, effective_sample_rate(format)))
         }
+        AudioCompression::Speex => Box::new(SpeexDecoder::new(data)),
     };
     Ok(decoder)
 }
 
+/// The sample rate of the decoded audio. This differs from the sample rate in the
+/// `SoundFormat` for the 8kHz and 16kHz Nellymoser formats and Speex, which ignore it.
+pub fn effective_sample_rate(format: &SoundFormat) -> u16 {
+    match format.compression {
+        AudioCompression::Nellymoser8Khz => 8000,
+        AudioCompression::Nellymoser16Khz | AudioCompression::Speex => 16000,
+        _ => format.sample_rate,
+    }
+}
+
 /// A "stream" sound is a sound that has its data distributed across `SoundStreamBlock` tags,
 /// one per each frame of a MovieClip. The sound is synced to the MovieClip's timeline, and will
 /// stop/seek as the MovieClip stops/seeks.
@@ -178,16 +190,62 @@ impl Iterator for AdpcmStreamDecoder {
     }
 }
 
+/// Each `SoundStreamBlock` tag of a Speex stream is a separate Speex packet, which may end with
+/// padding. Therefore, we must feed the blocks to the decoder one at a time.
+pub struct SpeexStreamDecoder {
+    tag_reader: StreamTagReader,
+    decoder: SpeexDecoder<Cursor<SwfSlice>>,
+}
+
+impl SpeexStreamDecoder {
+    fn new(format: &SoundFormat, swf_data: SwfSlice) -> Self {
+        let movie = swf_data.movie.clone();
+        let mut tag_reader = StreamTagReader::new(format.compression, swf_data);
+        let audio_data = tag_reader.next().unwrap_or_else(|| SwfSlice::empty(movie));
+        let decoder = SpeexDecoder::new(Cursor::new(audio_data));
+        Self {
+            tag_reader,
+            decoder,
+        }
+    }
+}
+
+impl Decoder for SpeexStreamDecoder {
+    fn num_channels(&self) -> u8 {
+        self.decoder.num_channels()
+    }
+    fn sample_rate(&self) -> u16 {
+        self.decoder.sample_rate()
+    }
+}
+
+impl Iterator for SpeexStreamDecoder {
+    type Item = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(sample_frame) = self.decoder.next() {
+                return Some(sample_frame);
+            }
+            // Continue with the next packet, keeping the state of the decoder.
+            // Skip over any blocks that contain no frames.
+            let audio_data = self.tag_reader.next()?;
+            self.decoder.next_packet(Cursor::new(audio_data));
+        }
+    }
+}
+
 /// Makes a `StreamDecoder` for the given stream. `swf_data` should be the MovieClip's tag data.
-/// Generally this will return a `StandardStreamDecoder`, except for ADPCM streams.
+/// Generally this will return a `StandardStreamDecoder`, except for ADPCM and Speex streams.
 pub fn make_stream_decoder(
     format: &swf::SoundFormat,
     swf_data: SwfSlice,
 ) -> Result<Box<dyn Decoder + Send>, Error> {
-    let decoder: Box<dyn Decoder + Send> = if format.compression == AudioCompression::Adpcm {
-        Box::new(AdpcmStreamDecoder::new(format, swf_data))
-    } else {
-        Box::new(StandardStreamDecoder::new(format, swf_data)?)
+    let decoder: Box<dyn Decoder + Send> = match format.compression {
+        AudioCompression::Adpcm => Box::new(AdpcmStreamDecoder::new(format, swf_data)),
+        AudioCompression::Speex => Box::new(SpeexStreamDecoder::new(format, swf_data)),
+        _ => Box::new(StandardStreamDecoder::new(format, swf_data)?),
     };
     Ok(decoder)
 }
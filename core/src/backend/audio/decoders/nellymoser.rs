@@ -0,0 +1,549 @@
+//! Nellymoser Asao decoder.
+//!
+//! Nellymoser is a transform codec used by Flash for microphone audio. Each
+//! 64-byte block contains the spectral envelope of 23 frequency bands,
+//! followed by two sets of quantized MDCT coefficients that decode to 256 mono
+//! samples.
+
+use super::{Decoder, SeekableDecoder};
+use std::io::{Cursor, Read};
+
+const NELLY_BANDS: usize = 23;
+const NELLY_BLOCK_LEN: usize = 64;
+const NELLY_HEADER_BITS: usize = 116;
+const NELLY_DETAIL_BITS: i32 = 198;
+const NELLY_BUF_LEN: usize = 128;
+const NELLY_FILL_LEN: usize = 124;
+const NELLY_BIT_CAP: i32 = 6;
+const NELLY_BASE_OFF: i32 = 4228;
+const NELLY_BASE_SHIFT: i32 = 19;
+const NELLY_SAMPLES: usize = 2 * NELLY_BUF_LEN;
+
+#[allow(clippy::excessive_precision)]
+const DEQUANTIZATION_TABLE: [f32; 127] = [
+    0.0000000000,
+    -0.8472560048,
+    0.7224709988,
+    -1.5247479677,
+    -0.4531480074,
+    0.3753609955,
+    1.4717899561,
+    -1.9822579622,
+    -1.1929379702,
+    -0.5829370022,
+    -0.0693780035,
+    0.3909569979,
+    0.9069200158,
+    1.4862740040,
+    2.2215409279,
+    -2.3887761831,
+    -1.8067469597,
+    -1.4105229378,
+    -1.0773609877,
+    -0.7995010018,
+    -0.5558109879,
+    -0.3334020078,
+    -0.1324490011,
+    0.0568020009,
+    0.2548770010,
+    0.4773550034,
+    0.7386850119,
+    1.0443060398,
+    1.3954459429,
+    1.8098750114,
+    2.3918759823,
+    -2.3893830776,
+    -1.9884680510,
+    -1.7514040470,
+    -1.5643119812,
+    -1.3922129869,
+    -1.2164649963,
+    -1.0469499826,
+    -0.8905100226,
+    -0.7645580173,
+    -0.6454579830,
+    -0.5259280205,
+    -0.4059549868,
+    -0.3029719889,
+    -0.2096900046,
+    -0.1239869967,
+    -0.0479229987,
+    0.0257730000,
+    0.1001340002,
+    0.1737180054,
+    0.2585540116,
+    0.3522900045,
+    0.4569880068,
+    0.5767750144,
+    0.7003160119,
+    0.8425520062,
+    1.0093879700,
+    1.1821349859,
+    1.3534560204,
+    1.5320819616,
+    1.7332619429,
+    1.9722349644,
+    2.3978140354,
+    -2.5756309032,
+    -2.0573320389,
+    -1.8984919786,
+    -1.7727810144,
+    -1.6662600040,
+    -1.5742180347,
+    -1.4993319511,
+    -1.4316639900,
+    -1.3652280569,
+    -1.3000990152,
+    -1.2280930281,
+    -1.1588579416,
+    -1.0921250582,
+    -1.0135740042,
+    -0.9202849865,
+    -0.8287050128,
+    -0.7374889851,
+    -0.6447759867,
+    -0.5590940118,
+    -0.4857139885,
+    -0.4110319912,
+    -0.3459700048,
+    -0.2851159871,
+    -0.2341620028,
+    -0.1870580018,
+    -0.1442500055,
+    -0.1107169986,
+    -0.0739680007,
+    -0.0365610011,
+    -0.0073290002,
+    0.0203610007,
+    0.0479039997,
+    0.0751969963,
+    0.0980999991,
+    0.1220389977,
+    0.1458999962,
+    0.1694349945,
+    0.1970459968,
+    0.2252430022,
+    0.2556869984,
+    0.2870100141,
+    0.3197099864,
+    0.3525829911,
+    0.3889069855,
+    0.4334920049,
+    0.4769459963,
+    0.5204820037,
+    0.5644530058,
+    0.6122040153,
+    0.6685929894,
+    0.7341650128,
+    0.8032159805,
+    0.8784040213,
+    0.9566209912,
+    1.0397069454,
+    1.1293770075,
+    1.2211159468,
+    1.3080279827,
+    1.4024800062,
+    1.5056819916,
+    1.6227730513,
+    1.7724959850,
+    1.9430880547,
+    2.2903931141,
+];
+
+const BAND_SIZES_TABLE: [usize; NELLY_BANDS] = [
+    2, 2, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 7, 8, 9, 10, 12, 14, 15,
+];
+
+const INIT_TABLE: [u16; 64] = [
+    3134, 5342, 6870, 7792, 8569, 9185, 9744, 10191, 10631, 11061, 11434, 11770, 12116, 12513,
+    12925, 13300, 13674, 14027, 14352, 14716, 15117, 15477, 15824, 16157, 16513, 16804, 17090,
+    17401, 17679, 17948, 18238, 18520, 18764, 19078, 19298, 19589, 19909, 20186, 20441, 20716,
+    20964, 21254, 21573, 21902, 22170, 22467, 22819, 23092, 23353, 23671, 24033, 24419, 24785,
+    25128, 25495, 25876, 26290, 26717, 27151, 27637, 28147, 28702, 29350, 30139,
+];
+
+const DELTA_TABLE: [i16; 32] = [
+    -11725, -9420, -7910, -6801, -5948, -5233, -4599, -4039, -3507, -3030, -2596, -2170, -1774,
+    -1383, -1016, -660, -329, -1, 337, 696, 1085, 1512, 1962, 2433, 2968, 3569, 4314, 5165, 6258,
+    7584, 9365, 11805,
+];
+
+/// Scales the decoded coefficients so that the output is in the range of -1.0 to 1.0.
+const SCALE_BIAS: f32 = 1.0 / (32768.0 * 8.0);
+
+pub struct NellymoserDecoder<R: Read> {
+    inner: R,
+    sample_rate: u16,
+
+    /// The samples of the most recently decoded block.
+    samples: [i16; NELLY_SAMPLES],
+    sample_index: usize,
+
+    /// The second half of the previous inverse MDCT, which overlaps with the next one.
+    imdct_prev: [f32; NELLY_BUF_LEN],
+
+    /// `cos` values for the inverse MDCT.
+    imdct_table: Vec<f32>,
+
+    /// The sine window used to overlap consecutive inverse MDCTs.
+    window: [f32; NELLY_BUF_LEN],
+
+    /// State of the generator for the sign of noise-filled coefficients.
+    random_state: u32,
+}
+
+impl<R: Read> NellymoserDecoder<R> {
+    pub fn new(inner: R, sample_rate: u16) -> Self {
+        // The middle half of a 256-point inverse MDCT, as only that part is
+        // needed when the window is applied below.
+        let mut imdct_table = Vec::with_capacity(NELLY_BUF_LEN * NELLY_BUF_LEN);
+        for n in 0..NELLY_BUF_LEN {
+            let n = (n + NELLY_BUF_LEN / 2) as f64 + 0.5 + (NELLY_BUF_LEN / 2) as f64;
+            for k in 0..NELLY_BUF_LEN {
+                let theta = std::f64::consts::PI / NELLY_BUF_LEN as f64 * n * (k as f64 + 0.5);
+                imdct_table.push(theta.cos() as f32);
+            }
+        }
+
+        let mut window = [0.0; NELLY_BUF_LEN];
+        for (i, w) in window.iter_mut().enumerate() {
+            *w = ((i as f64 + 0.5) * std::f64::consts::PI / (2.0 * NELLY_BUF_LEN as f64)).sin()
+                as f32;
+        }
+
+        Self {
+            inner,
+            sample_rate,
+            samples: [0; NELLY_SAMPLES],
+            sample_index: NELLY_SAMPLES,
+            imdct_prev: [0.0; NELLY_BUF_LEN],
+            imdct_table,
+            window,
+            random_state: 0,
+        }
+    }
+
+    fn next_random_bit(&mut self) -> bool {
+        self.random_state = self
+            .random_state
+            .wrapping_mul(1_664_525)
+            .wrapping_add(1_013_904_223);
+        self.random_state & 0x8000_0000 != 0
+    }
+
+    fn decode_block(&mut self, block: &[u8; NELLY_BLOCK_LEN]) {
+        let mut buf = [0.0f32; NELLY_FILL_LEN];
+        let mut pows = [0.0f32; NELLY_FILL_LEN];
+
+        // Read the spectral envelope.
+        let mut reader = BitReader::new(block);
+        let mut val = f32::from(INIT_TABLE[reader.read(6) as usize]);
+        let band_values = (0..NELLY_BANDS).flat_map(|band| {
+            if band > 0 {
+                val += f32::from(DELTA_TABLE[reader.read(5) as usize]);
+            }
+            let pval = -(val / 2048.0).exp2() * SCALE_BIAS;
+            std::iter::repeat((val, pval)).take(BAND_SIZES_TABLE[band])
+        });
+        for ((b, p), (val, pval)) in buf.iter_mut().zip(pows.iter_mut()).zip(band_values) {
+            *b = val;
+            *p = pval;
+        }
+
+        let bits = sample_bits(&buf);
+
+        for half in 0..2 {
+            let mut reader = BitReader::new(block);
+            reader.skip(NELLY_HEADER_BITS + half * NELLY_DETAIL_BITS as usize);
+
+            let mut coefficients = [0.0f32; NELLY_BUF_LEN];
+            for j in 0..NELLY_FILL_LEN {
+                coefficients[j] = if bits[j] <= 0 {
+                    // Bands without any bits are filled with noise.
+                    let noise = std::f32::consts::FRAC_1_SQRT_2 * pows[j];
+                    if self.next_random_bit() {
+                        -noise
+                    } else {
+                        noise
+                    }
+                } else {
+                    let v = reader.read(bits[j] as usize) as usize;
+                    DEQUANTIZATION_TABLE[(1usize << bits[j]) - 1 + v] * pows[j]
+                };
+            }
+
+            let mut imdct_out = [0.0f32; NELLY_BUF_LEN];
+            for (out, cos_row) in imdct_out
+                .iter_mut()
+                .zip(self.imdct_table.chunks_exact(NELLY_BUF_LEN))
+            {
+                *out = coefficients
+                    .iter()
+                    .zip(cos_row)
+                    .map(|(x, cos)| x * cos)
+                    .sum();
+            }
+
+            // Overlap the second half of the previous transform with the first
+            // half of this one.
+            let len = NELLY_BUF_LEN / 2;
+            let out = &mut self.samples[half * NELLY_BUF_LEN..(half + 1) * NELLY_BUF_LEN];
+            for i in 0..len {
+                let j = NELLY_BUF_LEN - 1 - i;
+                let s0 = self.imdct_prev[len + i];
+                let s1 = imdct_out[len - 1 - i];
+                let wi = self.window[i];
+                let wj = self.window[j];
+                out[i] = to_i16(s0 * wj - s1 * wi);
+                out[j] = to_i16(s0 * wi + s1 * wj);
+            }
+            self.imdct_prev = imdct_out;
+        }
+    }
+}
+
+fn to_i16(sample: f32) -> i16 {
+    (sample * 32768.0).max(-32768.0).min(32767.0) as i16
+}
+
+/// Determines how many bits each coefficient is allocated from the spectral
+/// envelope, so that the total is as close to `NELLY_DETAIL_BITS` as possible.
+fn sample_bits(buf: &[f32; NELLY_FILL_LEN]) -> [i32; NELLY_FILL_LEN] {
+    fn signed_shift(i: i32, shift: i32) -> i32 {
+        if shift > 0 {
+            i.wrapping_shl(shift as u32)
+        } else {
+            i >> -shift
+        }
+    }
+
+    fn headroom(la: &mut i32) -> i32 {
+        if *la == 0 {
+            return 31;
+        }
+        let l = 30 - (31 - la.wrapping_abs().leading_zeros() as i32);
+        *la = la.wrapping_shl(l as u32);
+        l
+    }
+
+    fn sum_bits(sbuf: &[i16; NELLY_FILL_LEN], shift: i32, off: i16) -> i32 {
+        sbuf.iter()
+            .map(|&b| {
+                let b = i32::from(b) - i32::from(off);
+                let b = ((b >> (shift - 1)) + 1) >> 1;
+                b.max(0).min(NELLY_BIT_CAP)
+            })
+            .sum()
+    }
+
+    let mut max = buf.iter().fold(0, |max, &b| max.max(b as i32));
+    let mut shift = -16 + headroom(&mut max);
+
+    let mut sbuf = [0i16; NELLY_FILL_LEN];
+    let mut sum: i32 = 0;
+    for (s, &b) in sbuf.iter_mut().zip(buf.iter()) {
+        *s = signed_shift(b as i32, shift) as i16;
+        *s = ((3 * i32::from(*s)) >> 2) as i16;
+        sum += i32::from(*s);
+    }
+
+    shift += 11;
+    let shift_saved = shift;
+    sum = sum.wrapping_sub(NELLY_DETAIL_BITS.wrapping_shl(shift as u32));
+    shift += headroom(&mut sum);
+    let mut small_off = (NELLY_BASE_OFF * (sum >> 16)) >> 15;
+    shift = shift_saved - (NELLY_BASE_SHIFT + shift - 31);
+
+    small_off = signed_shift(small_off, shift);
+
+    let mut bitsum = sum_bits(&sbuf, shift_saved, small_off as i16);
+
+    if bitsum != NELLY_DETAIL_BITS {
+        let mut off = bitsum - NELLY_DETAIL_BITS;
+
+        shift = 0;
+        while off.abs() <= 16383 {
+            off *= 2;
+            shift += 1;
+        }
+
+        off = (off * NELLY_BASE_OFF) >> 15;
+        shift = shift_saved - (NELLY_BASE_SHIFT + shift - 15);
+
+        off = signed_shift(off, shift);
+
+        let mut last_off = small_off;
+        let mut last_bitsum = bitsum;
+        let mut j = 1;
+        while j < 20 {
+            last_off = small_off;
+            small_off += off;
+            last_bitsum = bitsum;
+
+            bitsum = sum_bits(&sbuf, shift_saved, small_off as i16);
+
+            if (bitsum - NELLY_DETAIL_BITS) * (last_bitsum - NELLY_DETAIL_BITS) <= 0 {
+                break;
+            }
+            j += 1;
+        }
+
+        let (mut big_off, mut big_bitsum, mut small_bitsum);
+        if bitsum > NELLY_DETAIL_BITS {
+            big_off = small_off;
+            small_off = last_off;
+            big_bitsum = bitsum;
+            small_bitsum = last_bitsum;
+        } else {
+            big_off = last_off;
+            big_bitsum = last_bitsum;
+            small_bitsum = bitsum;
+        }
+
+        // Binary search for the offset that uses the number of bits available.
+        while bitsum != NELLY_DETAIL_BITS && j <= 19 {
+            off = (big_off + small_off) >> 1;
+            bitsum = sum_bits(&sbuf, shift_saved, off as i16);
+            if bitsum > NELLY_DETAIL_BITS {
+                big_off = off;
+                big_bitsum = bitsum;
+            } else {
+                small_off = off;
+                small_bitsum = bitsum;
+            }
+            j += 1;
+        }
+
+        if (big_bitsum - NELLY_DETAIL_BITS).abs() >= (small_bitsum - NELLY_DETAIL_BITS).abs() {
+            bitsum = small_bitsum;
+        } else {
+            small_off = big_off;
+            bitsum = big_bitsum;
+        }
+    }
+
+    let mut bits = [0i32; NELLY_FILL_LEN];
+    for (bit, &s) in bits.iter_mut().zip(sbuf.iter()) {
+        let tmp = i32::from(s) - i32::from(small_off as i16);
+        let tmp = ((tmp >> (shift_saved - 1)) + 1) >> 1;
+        *bit = tmp.max(0).min(NELLY_BIT_CAP);
+    }
+
+    if bitsum > NELLY_DETAIL_BITS {
+        // Remove the excess bits from the last coefficients.
+        let mut total = 0;
+        let mut i = 0;
+        while total < NELLY_DETAIL_BITS {
+            total += bits[i];
+            i += 1;
+        }
+        bits[i - 1] -= total - NELLY_DETAIL_BITS;
+        for bit in &mut bits[i..] {
+            *bit = 0;
+        }
+    }
+
+    bits
+}
+
+/// Reads bits least significant bit first, as Nellymoser blocks are packed.
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn skip(&mut self, num_bits: usize) {
+        self.pos += num_bits;
+    }
+
+    fn read(&mut self, num_bits: usize) -> u32 {
+        let mut value = 0;
+        for i in 0..num_bits {
+            let bit = self
+                .data
+                .get(self.pos / 8)
+                .map(|byte| (byte >> (self.pos % 8)) & 1)
+                .unwrap_or(0);
+            value |= u32::from(bit) << i;
+            self.pos += 1;
+        }
+        value
+    }
+}
+
+impl<R: Read> Iterator for NellymoserDecoder<R> {
+    type Item = [i16; 2];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.sample_index >= NELLY_SAMPLES {
+            let mut block = [0u8; NELLY_BLOCK_LEN];
+            self.inner.read_exact(&mut block).ok()?;
+            self.decode_block(&block);
+            self.sample_index = 0;
+        }
+
+        let sample = self.samples[self.sample_index];
+        self.sample_index += 1;
+        Some([sample, sample])
+    }
+}
+
+impl<R: Read> Decoder for NellymoserDecoder<R> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        1
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        self.sample_rate
+    }
+}
+
+impl<R: AsRef<[u8]>> SeekableDecoder for NellymoserDecoder<Cursor<R>> {
+    fn reset(&mut self) {
+        self.inner.set_position(0);
+        self.sample_index = NELLY_SAMPLES;
+        self.imdct_prev = [0.0; NELLY_BUF_LEN];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_bits_uses_all_detail_bits() {
+        // A flat envelope spreads the available bits evenly.
+        let buf = [20000.0; NELLY_FILL_LEN];
+        let bits = sample_bits(&buf);
+        assert_eq!(bits.iter().sum::<i32>(), NELLY_DETAIL_BITS);
+        assert!(bits.iter().all(|b| (0..=NELLY_BIT_CAP).contains(b)));
+
+        // A sloped envelope gives more bits to the louder bands.
+        let mut buf = [0.0; NELLY_FILL_LEN];
+        for (i, b) in buf.iter_mut().enumerate() {
+            *b = 30000.0 - 100.0 * i as f32;
+        }
+        let bits = sample_bits(&buf);
+        assert!(bits.iter().sum::<i32>() <= NELLY_DETAIL_BITS);
+        assert!(bits[0] >= bits[NELLY_FILL_LEN - 1]);
+    }
+
+    #[test]
+    fn decode_blocks() {
+        let data = vec![0x5a; NELLY_BLOCK_LEN * 2];
+        let decoder = NellymoserDecoder::new(Cursor::new(data), 8000);
+        assert_eq!(decoder.count(), NELLY_SAMPLES * 2);
+
+        // A partial block is dropped.
+        let data = vec![0; NELLY_BLOCK_LEN + 10];
+        let decoder = NellymoserDecoder::new(Cursor::new(data), 8000);
+        assert_eq!(decoder.count(), NELLY_SAMPLES);
+    }
+}
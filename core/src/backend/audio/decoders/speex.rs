@@ -0,0 +1,1631 @@
+//! Speex decoder.
+//!
+//! Speex is a CELP speech codec used by Flash for microphone audio. Flash
+//! always uses the wideband mode, where each 20ms frame contains a narrowband
+//! frame for the lower 4kHz of the signal, followed by a layer for the high
+//! band. Each frame decodes to 320 mono samples at 16kHz.
+//!
+//! This is a port of the floating point decoder from libspeex, which is
+//! distributed under the following license:
+//!
+//! Copyright 2002-2008 Xiph.org Foundation
+//! Copyright 2002-2008 Jean-Marc Valin
+//! Copyright 2005-2007 Analog Devices Inc.
+//! Copyright 2005-2008 Commonwealth Scientific and Industrial Research
+//!                     Organisation (CSIRO)
+//! Copyright 1993, 2002, 2006 David Rowe
+//! Copyright 2003 EpicGames
+//! Copyright 1992-1994 Jutta Degener, Carsten Bormann
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions
+//! are met:
+//!
+//! - Redistributions of source code must retain the above copyright
+//!   notice, this list of conditions and the following disclaimer.
+//!
+//! - Redistributions in binary form must reproduce the above copyright
+//!   notice, this list of conditions and the following disclaimer in the
+//!   documentation and/or other materials provided with the distribution.
+//!
+//! - Neither the name of the Xiph.org Foundation nor the names of its
+//!   contributors may be used to endorse or promote products derived from
+//!   this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+//! ``AS IS'' AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+//! LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+//! A PARTICULAR PURPOSE ARE DISCLAIMED.  IN NO EVENT SHALL THE FOUNDATION OR
+//! CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+//! EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+//! PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+//! PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+//! LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+//! NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+//! SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+mod tables;
+
+use self::tables::*;
+use super::{Decoder, SeekableDecoder};
+use std::f64::consts::PI;
+use std::io::{Cursor, Read};
+
+const SAMPLE_RATE: u16 = 16000;
+const FRAME_SIZE: usize = 2 * NB_FRAME_SIZE;
+
+const NB_ORDER: usize = 10;
+const NB_FRAME_SIZE: usize = 160;
+const NB_SUBFRAME_SIZE: usize = 40;
+const NB_SUBFRAMES: usize = 4;
+const NB_PITCH_START: i32 = 17;
+const NB_PITCH_END: i32 = 144;
+const NB_LSP_MARGIN: f32 = 0.002;
+
+/// Offset of the current frame in the narrowband excitation buffer, which
+/// keeps enough history for the longest pitch period.
+const NB_EXC_OFFSET: usize = 2 * NB_PITCH_END as usize + NB_SUBFRAME_SIZE + 6;
+const NB_EXC_BUF_LEN: usize = NB_FRAME_SIZE + 2 * NB_PITCH_END as usize + NB_SUBFRAME_SIZE + 12;
+
+const SB_ORDER: usize = 8;
+const SB_SUBMODE_BITS: u32 = 3;
+const SB_LSP_MARGIN: f32 = 0.05;
+const SB_FOLDING_GAIN: f32 = 0.9;
+const QMF_ORDER: usize = 64;
+
+const VERY_SMALL: f32 = 1e-15;
+
+#[allow(clippy::excessive_precision)]
+const EXC_GAIN_QUANT_SCAL3: [f32; 8] = [
+    0.061130, 0.163546, 0.310413, 0.428220, 0.555887, 0.719055, 0.938694, 1.326874,
+];
+
+#[allow(clippy::excessive_precision)]
+const EXC_GAIN_QUANT_SCAL1: [f32; 2] = [0.70469, 1.05127];
+
+#[allow(clippy::excessive_precision)]
+const GC_QUANT_BOUND: [f32; 16] = [
+    0.97979, 1.28384, 1.68223, 2.20426, 2.88829, 3.78458, 4.95900, 6.49787, 8.51428, 11.15642,
+    14.61846, 19.15484, 25.09895, 32.88761, 43.09325, 56.46588,
+];
+
+/// Skipped when searching a narrowband decoder's input for its own frame.
+const WB_SKIP_TABLE: [u32; 8] = [0, 36, 112, 192, 352, 0, 0, 0];
+
+/// Coefficients of the quadrature mirror filter that recombines the bands.
+#[allow(clippy::excessive_precision)]
+const QMF_COEFS: [f32; QMF_ORDER] = [
+    3.596189e-05,
+    -0.0001123515,
+    -0.0001104587,
+    0.0002790277,
+    0.0002298438,
+    -0.0005953563,
+    -0.0003823631,
+    0.00113826,
+    0.0005308539,
+    -0.001986177,
+    -0.0006243724,
+    0.003235877,
+    0.0005743159,
+    -0.004989147,
+    -0.0002584767,
+    0.007367171,
+    -0.0004857935,
+    -0.01050689,
+    0.001894714,
+    0.01459396,
+    -0.004313674,
+    -0.01994365,
+    0.00828756,
+    0.02716055,
+    -0.01485397,
+    -0.03764973,
+    0.026447,
+    0.05543245,
+    -0.05095487,
+    -0.09779096,
+    0.1382363,
+    0.4600981,
+    0.4600981,
+    0.1382363,
+    -0.09779096,
+    -0.05095487,
+    0.05543245,
+    0.026447,
+    -0.03764973,
+    -0.01485397,
+    0.02716055,
+    0.00828756,
+    -0.01994365,
+    -0.004313674,
+    0.01459396,
+    0.001894714,
+    -0.01050689,
+    -0.0004857935,
+    0.007367171,
+    -0.0002584767,
+    -0.004989147,
+    0.0005743159,
+    0.003235877,
+    -0.0006243724,
+    -0.001986177,
+    0.0005308539,
+    0.00113826,
+    -0.0003823631,
+    -0.0005953563,
+    0.0002298438,
+    0.0002790277,
+    -0.0001104587,
+    -0.0001123515,
+    3.596189e-05,
+];
+
+/// Filters used to interpolate the excitation at fractional pitch offsets.
+#[allow(clippy::excessive_precision)]
+const SHIFT_FILT: [[f32; 7]; 3] = [
+    [
+        -0.011915, 0.046995, -0.152373, 0.614108, 0.614108, -0.152373, 0.046995,
+    ],
+    [
+        -0.0324855, 0.0859768, -0.2042986, 0.9640297, 0.2086420, -0.0302054, -0.0063646,
+    ],
+    [
+        -0.0063646, -0.0302054, 0.2086420, 0.9640297, -0.2042986, 0.0859768, -0.0324855,
+    ],
+];
+
+/// Decodes Speex audio.
+///
+/// The data is treated as a single Speex packet, which may hold any number of
+/// frames. Streams made of several packets are fed in with `next_packet`.
+pub struct SpeexDecoder<R: Read> {
+    inner: R,
+    bits: Option<BitReader>,
+    decoder: Box<WidebandDecoder>,
+    samples: [i16; FRAME_SIZE],
+    sample_index: usize,
+}
+
+impl<R: Read> SpeexDecoder<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            bits: None,
+            decoder: Box::new(WidebandDecoder::new()),
+            samples: [0; FRAME_SIZE],
+            sample_index: FRAME_SIZE,
+        }
+    }
+
+    /// Continue decoding with the next packet of a stream.
+    ///
+    /// The state of the decoder carries over between packets, as each one
+    /// predicts its audio from the previous frames.
+    pub fn next_packet(&mut self, inner: R) {
+        self.inner = inner;
+        self.bits = None;
+    }
+
+    /// Decode the next frame into `samples`. Returns `false` at the end of the
+    /// packet, or if the data is corrupt.
+    fn decode_frame(&mut self) -> bool {
+        let inner = &mut self.inner;
+        let bits = self.bits.get_or_insert_with(|| {
+            let mut data = Vec::new();
+            let _ = inner.read_to_end(&mut data);
+            BitReader::new(data)
+        });
+
+        let mut out = [0.0; FRAME_SIZE];
+        if self.decoder.decode(bits, &mut out).is_err() {
+            return false;
+        }
+
+        for (sample, &value) in self.samples.iter_mut().zip(out.iter()) {
+            *sample = if value > 32767.0 {
+                32767
+            } else if value < -32768.0 {
+                -32768
+            } else {
+                (0.5 + f64::from(value)).floor() as i16
+            };
+        }
+        true
+    }
+}
+
+impl<R: Read> Iterator for SpeexDecoder<R> {
+    type Item = [i16; 2];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.sample_index >= FRAME_SIZE {
+            if !self.decode_frame() {
+                return None;
+            }
+            self.sample_index = 0;
+        }
+
+        let sample = self.samples[self.sample_index];
+        self.sample_index += 1;
+        Some([sample, sample])
+    }
+}
+
+impl<R: Read> Decoder for SpeexDecoder<R> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        1
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        SAMPLE_RATE
+    }
+}
+
+impl<R: AsRef<[u8]>> SeekableDecoder for SpeexDecoder<Cursor<R>> {
+    fn reset(&mut self) {
+        self.inner.set_position(0);
+        self.bits = None;
+        self.decoder = Box::new(WidebandDecoder::new());
+        self.sample_index = FRAME_SIZE;
+    }
+}
+
+/// Why no frame could be decoded.
+#[derive(Debug, PartialEq)]
+enum FrameError {
+    /// The packet has no frames left, or a terminator ends it early.
+    EndOfPacket,
+
+    /// The packet is invalid.
+    Corrupt,
+}
+
+/// Reads bits most significant bit first, as Speex packets are packed.
+struct BitReader {
+    data: Vec<u8>,
+    pos: usize,
+    overflow: bool,
+}
+
+impl BitReader {
+    fn new(data: Vec<u8>) -> Self {
+        Self {
+            data,
+            pos: 0,
+            overflow: false,
+        }
+    }
+
+    /// The number of unread bits, or -1 if a read went past the end.
+    fn remaining(&self) -> isize {
+        if self.overflow {
+            -1
+        } else {
+            (self.data.len() * 8) as isize - self.pos as isize
+        }
+    }
+
+    fn check(&mut self, num_bits: usize) -> bool {
+        if self.pos + num_bits > self.data.len() * 8 {
+            self.overflow = true;
+        }
+        !self.overflow
+    }
+
+    fn bit(&self, pos: usize) -> u32 {
+        u32::from(self.data[pos / 8] >> (7 - pos % 8)) & 1
+    }
+
+    fn read(&mut self, num_bits: usize) -> u32 {
+        if !self.check(num_bits) {
+            return 0;
+        }
+        let mut value = 0;
+        for _ in 0..num_bits {
+            value = (value << 1) | self.bit(self.pos);
+            self.pos += 1;
+        }
+        value
+    }
+
+    fn peek(&mut self) -> u32 {
+        if !self.check(1) {
+            return 0;
+        }
+        self.bit(self.pos)
+    }
+
+    fn skip(&mut self, num_bits: usize) {
+        if self.check(num_bits) {
+            self.pos += num_bits;
+        }
+    }
+}
+
+/// How a submode quantizes its line spectral pairs.
+#[derive(Clone, Copy)]
+enum LspQuant {
+    Nb,
+    Lbr,
+    High,
+}
+
+/// How a submode predicts the excitation from the pitch.
+#[derive(Clone, Copy)]
+enum PitchQuant {
+    /// A single gain for the whole frame, at the open loop pitch.
+    Forced,
+
+    /// A 3-tap predictor for each subframe.
+    ThreeTap {
+        gain_cdbk: &'static [i8],
+        gain_bits: usize,
+        pitch_bits: usize,
+    },
+}
+
+/// How a submode quantizes the innovation.
+#[derive(Clone, Copy)]
+enum Innovation {
+    /// The high band is folded over from the low band.
+    None,
+
+    /// Random noise.
+    Noise,
+
+    /// Subvectors taken from a codebook.
+    SplitCodebook {
+        subvect_size: usize,
+        nb_subvect: usize,
+        shape_cb: &'static [i8],
+        shape_bits: usize,
+        have_sign: bool,
+    },
+}
+
+struct Submode {
+    /// The allowed deviation from the open loop pitch, or -1 to code the pitch
+    /// of each subframe separately.
+    lbr_pitch: i32,
+    forced_pitch_gain: bool,
+    have_subframe_gain: u32,
+    double_codebook: bool,
+    lsp: LspQuant,
+    pitch: PitchQuant,
+    innovation: Innovation,
+    comb_gain: f32,
+}
+
+const LTP_NB: PitchQuant = PitchQuant::ThreeTap {
+    gain_cdbk: &GAIN_CDBK_NB,
+    gain_bits: 7,
+    pitch_bits: 7,
+};
+
+const LTP_VLBR: PitchQuant = PitchQuant::ThreeTap {
+    gain_cdbk: &GAIN_CDBK_LBR,
+    gain_bits: 5,
+    pitch_bits: 0,
+};
+
+const LTP_LBR: PitchQuant = PitchQuant::ThreeTap {
+    gain_cdbk: &GAIN_CDBK_LBR,
+    gain_bits: 5,
+    pitch_bits: 7,
+};
+
+const SPLIT_CB_NB: Innovation = Innovation::SplitCodebook {
+    subvect_size: 5,
+    nb_subvect: 8,
+    shape_cb: &EXC_5_64,
+    shape_bits: 6,
+    have_sign: false,
+};
+
+const SPLIT_CB_HIGH: Innovation = Innovation::SplitCodebook {
+    subvect_size: 8,
+    nb_subvect: 5,
+    shape_cb: &HEXC_8_128,
+    shape_bits: 7,
+    have_sign: true,
+};
+
+/// The narrowband submodes, indexed by their ID. Submode 0 transmits nothing.
+const NB_SUBMODES: [Option<Submode>; 9] = [
+    None,
+    // 2150 bps "vocoder-like" mode for comfort noise.
+    Some(Submode {
+        lbr_pitch: 0,
+        forced_pitch_gain: true,
+        have_subframe_gain: 0,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        pitch: PitchQuant::Forced,
+        innovation: Innovation::Noise,
+        comb_gain: -1.0,
+    }),
+    // 5.95 kbps very low bit-rate mode.
+    Some(Submode {
+        lbr_pitch: 0,
+        forced_pitch_gain: false,
+        have_subframe_gain: 0,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        pitch: LTP_VLBR,
+        innovation: Innovation::SplitCodebook {
+            subvect_size: 10,
+            nb_subvect: 4,
+            shape_cb: &EXC_10_16,
+            shape_bits: 4,
+            have_sign: false,
+        },
+        comb_gain: 0.6,
+    }),
+    // 8 kbps low bit-rate mode.
+    Some(Submode {
+        lbr_pitch: -1,
+        forced_pitch_gain: false,
+        have_subframe_gain: 1,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        pitch: LTP_LBR,
+        innovation: Innovation::SplitCodebook {
+            subvect_size: 10,
+            nb_subvect: 4,
+            shape_cb: &EXC_10_32,
+            shape_bits: 5,
+            have_sign: false,
+        },
+        comb_gain: 0.55,
+    }),
+    // 11 kbps medium bit-rate mode.
+    Some(Submode {
+        lbr_pitch: -1,
+        forced_pitch_gain: false,
+        have_subframe_gain: 1,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        pitch: LTP_LBR,
+        innovation: Innovation::SplitCodebook {
+            subvect_size: 8,
+            nb_subvect: 5,
+            shape_cb: &EXC_8_128,
+            shape_bits: 7,
+            have_sign: false,
+        },
+        comb_gain: 0.45,
+    }),
+    // 15 kbps high bit-rate mode.
+    Some(Submode {
+        lbr_pitch: -1,
+        forced_pitch_gain: false,
+        have_subframe_gain: 3,
+        double_codebook: false,
+        lsp: LspQuant::Nb,
+        pitch: LTP_NB,
+        innovation: SPLIT_CB_NB,
+        comb_gain: 0.25,
+    }),
+    // 18.2 kbps high bit-rate mode.
+    Some(Submode {
+        lbr_pitch: -1,
+        forced_pitch_gain: false,
+        have_subframe_gain: 3,
+        double_codebook: false,
+        lsp: LspQuant::Nb,
+        pitch: LTP_NB,
+        innovation: Innovation::SplitCodebook {
+            subvect_size: 5,
+            nb_subvect: 8,
+            shape_cb: &EXC_5_256,
+            shape_bits: 8,
+            have_sign: false,
+        },
+        comb_gain: 0.15,
+    }),
+    // 24.6 kbps high bit-rate mode.
+    Some(Submode {
+        lbr_pitch: -1,
+        forced_pitch_gain: false,
+        have_subframe_gain: 3,
+        double_codebook: true,
+        lsp: LspQuant::Nb,
+        pitch: LTP_NB,
+        innovation: SPLIT_CB_NB,
+        comb_gain: 0.05,
+    }),
+    // 3.95 kbps very low bit-rate mode.
+    Some(Submode {
+        lbr_pitch: 0,
+        forced_pitch_gain: true,
+        have_subframe_gain: 0,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        pitch: PitchQuant::Forced,
+        innovation: Innovation::SplitCodebook {
+            subvect_size: 20,
+            nb_subvect: 2,
+            shape_cb: &EXC_20_32,
+            shape_bits: 5,
+            have_sign: false,
+        },
+        comb_gain: 0.5,
+    }),
+];
+
+/// The high band submodes, indexed by their ID. Submode 0 transmits nothing.
+const SB_SUBMODES: [Option<Submode>; 5] = [
+    None,
+    // 2 kbps: the high band is folded over from the low band.
+    Some(Submode {
+        lbr_pitch: 0,
+        forced_pitch_gain: false,
+        have_subframe_gain: 1,
+        double_codebook: false,
+        lsp: LspQuant::High,
+        pitch: PitchQuant::Forced,
+        innovation: Innovation::None,
+        comb_gain: -1.0,
+    }),
+    // 5.6 kbps.
+    Some(Submode {
+        lbr_pitch: 0,
+        forced_pitch_gain: false,
+        have_subframe_gain: 1,
+        double_codebook: false,
+        lsp: LspQuant::High,
+        pitch: PitchQuant::Forced,
+        innovation: Innovation::SplitCodebook {
+            subvect_size: 10,
+            nb_subvect: 4,
+            shape_cb: &HEXC_10_32,
+            shape_bits: 5,
+            have_sign: false,
+        },
+        comb_gain: -1.0,
+    }),
+    // 9.6 kbps.
+    Some(Submode {
+        lbr_pitch: 0,
+        forced_pitch_gain: false,
+        have_subframe_gain: 1,
+        double_codebook: false,
+        lsp: LspQuant::High,
+        pitch: PitchQuant::Forced,
+        innovation: SPLIT_CB_HIGH,
+        comb_gain: -1.0,
+    }),
+    // 17.6 kbps.
+    Some(Submode {
+        lbr_pitch: 0,
+        forced_pitch_gain: false,
+        have_subframe_gain: 1,
+        double_codebook: true,
+        lsp: LspQuant::High,
+        pitch: PitchQuant::Forced,
+        innovation: SPLIT_CB_HIGH,
+        comb_gain: -1.0,
+    }),
+];
+
+/// Decodes the narrowband layer of a wideband frame.
+struct NarrowbandDecoder {
+    exc_buf: [f32; NB_EXC_BUF_LEN],
+    old_qlsp: [f32; NB_ORDER],
+    interp_qlpc: [f32; NB_ORDER],
+    mem_sp: [f32; NB_ORDER],
+    mem_hp: [f32; 2],
+    first: bool,
+    seed: u32,
+    dtx_enabled: bool,
+    voc_m1: f32,
+    voc_m2: f32,
+    voc_mean: f32,
+    voc_offset: i32,
+
+    /// The gain of the synthesis filter at the Nyquist frequency, for each
+    /// subframe of the last frame.
+    pi_gain: [f32; NB_SUBFRAMES],
+
+    /// The innovation of the last frame, which the high band folds over.
+    innov_save: [f32; NB_FRAME_SIZE],
+}
+
+impl NarrowbandDecoder {
+    fn new() -> Self {
+        Self {
+            exc_buf: [0.0; NB_EXC_BUF_LEN],
+            old_qlsp: [0.0; NB_ORDER],
+            interp_qlpc: [0.0; NB_ORDER],
+            mem_sp: [0.0; NB_ORDER],
+            mem_hp: [0.0; 2],
+            first: true,
+            seed: 1000,
+            dtx_enabled: false,
+            voc_m1: 0.0,
+            voc_m2: 0.0,
+            voc_mean: 0.0,
+            voc_offset: 0,
+            pi_gain: [0.0; NB_SUBFRAMES],
+            innov_save: [0.0; NB_FRAME_SIZE],
+        }
+    }
+
+    /// The RMS of the excitation of each subframe of the last frame.
+    fn exc_rms(&self) -> [f32; NB_SUBFRAMES] {
+        let mut rms = [0.0; NB_SUBFRAMES];
+        for (sub, rms) in rms.iter_mut().enumerate() {
+            let start = NB_EXC_OFFSET + sub * NB_SUBFRAME_SIZE;
+            *rms = compute_rms(&self.exc_buf[start..start + NB_SUBFRAME_SIZE]);
+        }
+        rms
+    }
+
+    /// Read the submode of the next narrowband frame, skipping any wideband
+    /// layers and in-band requests.
+    fn read_submode(bits: &mut BitReader) -> Result<usize, FrameError> {
+        loop {
+            if bits.remaining() < 5 {
+                return Err(FrameError::EndOfPacket);
+            }
+            // Skip up to two wideband layers.
+            for _ in 0..2 {
+                if bits.read(1) == 0 {
+                    break;
+                }
+                let submode = bits.read(SB_SUBMODE_BITS as usize) as usize;
+                bits.skip((WB_SKIP_TABLE[submode] - (SB_SUBMODE_BITS + 1)) as usize);
+                if bits.remaining() < 5 {
+                    return Err(FrameError::EndOfPacket);
+                }
+            }
+
+            if bits.remaining() < 4 {
+                return Err(FrameError::EndOfPacket);
+            }
+            match bits.read(4) {
+                15 => return Err(FrameError::EndOfPacket),
+                14 => {
+                    // Speex in-band request, which is ignored.
+                    let skip = match bits.read(4) {
+                        0..=1 => 1,
+                        2..=7 => 4,
+                        8..=9 => 8,
+                        10..=11 => 16,
+                        12..=13 => 32,
+                        _ => 64,
+                    };
+                    bits.skip(skip);
+                }
+                13 => {
+                    // User in-band request, which is ignored.
+                    let len = bits.read(4) as usize;
+                    bits.skip(5 + 8 * len);
+                }
+                m if m > 8 => return Err(FrameError::Corrupt),
+                m => return Ok(m as usize),
+            }
+        }
+    }
+
+    fn decode(
+        &mut self,
+        bits: &mut BitReader,
+        out: &mut [f32; NB_FRAME_SIZE],
+    ) -> Result<(), FrameError> {
+        let submode_id = Self::read_submode(bits)?;
+
+        // Shift all buffers by one frame.
+        self.exc_buf.copy_within(NB_FRAME_SIZE.., 0);
+        let exc = NB_EXC_OFFSET;
+
+        let submode = match &NB_SUBMODES[submode_id] {
+            Some(submode) => submode,
+            None => {
+                // Nothing was transmitted, so fill in with noise.
+                let mut lpc = [0.0; NB_ORDER];
+                bw_lpc(0.93, &self.interp_qlpc, &mut lpc);
+                let innov_gain = compute_rms(&self.exc_buf[exc..exc + NB_FRAME_SIZE]);
+                for i in 0..NB_FRAME_SIZE {
+                    self.exc_buf[exc + i] = speex_rand(innov_gain, &mut self.seed);
+                }
+                self.first = true;
+                self.innov_save = [0.0; NB_FRAME_SIZE];
+                iir_mem(
+                    &self.exc_buf[exc..exc + NB_FRAME_SIZE],
+                    &lpc,
+                    out,
+                    &mut self.mem_sp,
+                );
+                return Ok(());
+            }
+        };
+
+        let mut qlsp = [0.0; NB_ORDER];
+        lsp_unquant(submode.lsp, &mut qlsp, bits);
+        if self.first {
+            self.old_qlsp = qlsp;
+        }
+
+        // Open loop pitch for the low bit-rate modes.
+        let mut ol_pitch = 0;
+        if submode.lbr_pitch != -1 {
+            ol_pitch = NB_PITCH_START + bits.read(7) as i32;
+        }
+
+        let mut ol_pitch_coef = 0.0;
+        if submode.forced_pitch_gain {
+            let quant = bits.read(4);
+            ol_pitch_coef = (0.066667 * f64::from(quant)) as f32;
+        }
+
+        // Global excitation gain.
+        let qe = bits.read(5);
+        let ol_gain = (f64::from(qe) / 3.5).exp() as f32;
+
+        if submode_id == 1 {
+            self.dtx_enabled = bits.read(4) == 15;
+        } else {
+            self.dtx_enabled = false;
+        }
+
+        let mut best_pitch = 40;
+        let mut best_pitch_gain = 0.0;
+        for sub in 0..NB_SUBFRAMES {
+            let offset = NB_SUBFRAME_SIZE * sub;
+            let sub_exc = exc + offset;
+            for e in &mut self.exc_buf[sub_exc..sub_exc + NB_SUBFRAME_SIZE] {
+                *e = 0.0;
+            }
+
+            // Adaptive codebook contribution.
+            let pit_min = match submode.lbr_pitch {
+                -1 => NB_PITCH_START,
+                0 => ol_pitch,
+                margin => (ol_pitch - margin + 1).max(NB_PITCH_START),
+            };
+            let mut exc32 = [0.0; NB_SUBFRAME_SIZE];
+            let (pitch, pitch_gain) = pitch_unquant(
+                submode.pitch,
+                &mut self.exc_buf,
+                sub_exc,
+                &mut exc32,
+                pit_min,
+                ol_pitch_coef,
+                bits,
+            );
+
+            // Guard against crafted packets making the output blow up.
+            for e in &mut exc32 {
+                if !(*e >= -32000.0 && *e <= 32000.0) {
+                    *e = if *e < -32000.0 {
+                        -32000.0
+                    } else if *e > 32000.0 {
+                        32000.0
+                    } else {
+                        0.0
+                    };
+                }
+            }
+
+            let tmp = gain_3tap_to_1tap(&pitch_gain);
+            let best = f64::from(best_pitch_gain);
+            let near_multiple = |a: i32, b: i32| {
+                (2 * a - b).abs() < 3 || (3 * a - b).abs() < 4 || (4 * a - b).abs() < 5
+            };
+            if (tmp > best && !near_multiple(best_pitch, pitch))
+                || (tmp > 0.6 * best && near_multiple(pitch, best_pitch))
+                || (0.67 * tmp > best && near_multiple(best_pitch, pitch))
+            {
+                best_pitch = pitch;
+                if tmp > best {
+                    best_pitch_gain = tmp as f32;
+                }
+            }
+
+            // Sub-frame gain correction.
+            let ener = match submode.have_subframe_gain {
+                3 => EXC_GAIN_QUANT_SCAL3[bits.read(3) as usize] * ol_gain,
+                1 => EXC_GAIN_QUANT_SCAL1[bits.read(1) as usize] * ol_gain,
+                _ => ol_gain,
+            };
+
+            // Fixed codebook contribution.
+            let mut innov = [0.0; NB_SUBFRAME_SIZE];
+            innovation_unquant(submode.innovation, &mut innov, bits, &mut self.seed);
+            for v in &mut innov {
+                *v *= ener;
+            }
+            if submode.double_codebook {
+                let mut innov2 = [0.0; NB_SUBFRAME_SIZE];
+                innovation_unquant(submode.innovation, &mut innov2, bits, &mut self.seed);
+                let scale = 0.454545 * ener;
+                for (v, v2) in innov.iter_mut().zip(innov2.iter()) {
+                    *v += v2 * scale;
+                }
+            }
+            for i in 0..NB_SUBFRAME_SIZE {
+                self.exc_buf[sub_exc + i] = exc32[i] + innov[i];
+            }
+            self.innov_save[offset..offset + NB_SUBFRAME_SIZE].copy_from_slice(&innov);
+
+            if submode_id == 1 {
+                // Vocoder mode.
+                let g = (1.5 * (ol_pitch_coef - 0.2)).max(0.0).min(1.0);
+                for e in &mut self.exc_buf[sub_exc..sub_exc + NB_SUBFRAME_SIZE] {
+                    *e = 0.0;
+                }
+                while self.voc_offset < NB_SUBFRAME_SIZE as i32 {
+                    if self.voc_offset >= 0 {
+                        self.exc_buf[sub_exc + self.voc_offset as usize] =
+                            (f64::from(2 * ol_pitch).sqrt() * f64::from(g * ol_gain)) as f32;
+                    }
+                    self.voc_offset += ol_pitch;
+                }
+                self.voc_offset -= NB_SUBFRAME_SIZE as i32;
+
+                for (i, &innov) in innov.iter().enumerate() {
+                    let e = &mut self.exc_buf[sub_exc + i];
+                    let exci = *e;
+                    *e = (0.7 * exci + 0.3 * self.voc_m1)
+                        + ((1.0 - 0.85 * g) * innov - (0.15 * g) * self.voc_m2);
+                    self.voc_m1 = exci;
+                    self.voc_m2 = innov;
+                    self.voc_mean = 0.8 * self.voc_mean + 0.2 * *e;
+                    *e -= self.voc_mean;
+                }
+            }
+        }
+
+        // Enhance the pitch of the excitation, which is delayed by a subframe.
+        if submode.comb_gain > 0.0 {
+            let half = NB_FRAME_SIZE / 2;
+            let (first_half, second_half) = out.split_at_mut(half);
+            multicomb(
+                &self.exc_buf,
+                exc - NB_SUBFRAME_SIZE,
+                first_half,
+                best_pitch,
+                submode.comb_gain,
+            );
+            multicomb(
+                &self.exc_buf,
+                exc + NB_SUBFRAME_SIZE,
+                second_half,
+                best_pitch,
+                submode.comb_gain,
+            );
+        } else {
+            out.copy_from_slice(
+                &self.exc_buf[exc - NB_SUBFRAME_SIZE..exc + NB_FRAME_SIZE - NB_SUBFRAME_SIZE],
+            );
+        }
+
+        for sub in 0..NB_SUBFRAMES {
+            let offset = NB_SUBFRAME_SIZE * sub;
+
+            let mut interp_qlsp = [0.0; NB_ORDER];
+            lsp_interpolate(
+                &self.old_qlsp,
+                &qlsp,
+                &mut interp_qlsp,
+                sub,
+                NB_SUBFRAMES,
+                NB_LSP_MARGIN,
+            );
+            let mut ak = [0.0; NB_ORDER];
+            lsp_to_lpc(&interp_qlsp, &mut ak);
+
+            let mut pi_g = 1.0;
+            for i in (0..NB_ORDER).step_by(2) {
+                pi_g += ak[i + 1] - ak[i];
+            }
+            self.pi_gain[sub] = pi_g;
+
+            let mut sp = [0.0; NB_SUBFRAME_SIZE];
+            iir_mem(
+                &out[offset..offset + NB_SUBFRAME_SIZE],
+                &self.interp_qlpc,
+                &mut sp,
+                &mut self.mem_sp,
+            );
+            out[offset..offset + NB_SUBFRAME_SIZE].copy_from_slice(&sp);
+            self.interp_qlpc = ak;
+        }
+
+        highpass(out, &mut self.mem_hp);
+
+        self.old_qlsp = qlsp;
+        self.first = false;
+        Ok(())
+    }
+}
+
+/// Decodes wideband frames, made of a narrowband frame followed by a high band
+/// layer.
+struct WidebandDecoder {
+    low: NarrowbandDecoder,
+    old_qlsp: [f32; SB_ORDER],
+    interp_qlpc: [f32; SB_ORDER],
+    mem_sp: [f32; SB_ORDER],
+    exc_buf: [f32; NB_SUBFRAME_SIZE],
+    g0_mem: [f32; QMF_ORDER],
+    g1_mem: [f32; QMF_ORDER],
+    last_ener: f32,
+    first: bool,
+    seed: u32,
+}
+
+impl WidebandDecoder {
+    fn new() -> Self {
+        Self {
+            low: NarrowbandDecoder::new(),
+            old_qlsp: [0.0; SB_ORDER],
+            interp_qlpc: [0.0; SB_ORDER],
+            mem_sp: [0.0; SB_ORDER],
+            exc_buf: [0.0; NB_SUBFRAME_SIZE],
+            g0_mem: [0.0; QMF_ORDER],
+            g1_mem: [0.0; QMF_ORDER],
+            last_ener: 0.0,
+            first: true,
+            seed: 1000,
+        }
+    }
+
+    fn decode(
+        &mut self,
+        bits: &mut BitReader,
+        out: &mut [f32; FRAME_SIZE],
+    ) -> Result<(), FrameError> {
+        let mut low = [0.0; NB_FRAME_SIZE];
+        self.low.decode(bits, &mut low)?;
+
+        let wideband = bits.remaining() > 0 && bits.peek() == 1;
+        let submode_id = if wideband {
+            bits.read(1);
+            bits.read(SB_SUBMODE_BITS as usize) as usize
+        } else {
+            0
+        };
+
+        let mut high = [0.0; NB_FRAME_SIZE];
+        let submode = match SB_SUBMODES.get(submode_id) {
+            Some(Some(submode)) => submode,
+            Some(None) => {
+                if self.low.dtx_enabled {
+                    // Comfort noise.
+                    for h in high.iter_mut() {
+                        *h = speex_rand(self.last_ener, &mut self.seed);
+                    }
+                } else {
+                    high = [VERY_SMALL; NB_FRAME_SIZE];
+                }
+                self.first = true;
+                let input = high;
+                iir_mem(&input, &self.interp_qlpc, &mut high, &mut self.mem_sp);
+                self.qmf_synth(&low, &high, out);
+                return Ok(());
+            }
+            None => return Err(FrameError::Corrupt),
+        };
+
+        let low_pi_gain = self.low.pi_gain;
+        let low_exc_rms = self.low.exc_rms();
+
+        let mut qlsp = [0.0; SB_ORDER];
+        lsp_unquant(submode.lsp, &mut qlsp, bits);
+        if self.first {
+            self.old_qlsp = qlsp;
+        }
+
+        let mut exc_ener_sum = 0.0;
+        for sub in 0..NB_SUBFRAMES {
+            let offset = NB_SUBFRAME_SIZE * sub;
+
+            let mut interp_qlsp = [0.0; SB_ORDER];
+            lsp_interpolate(
+                &self.old_qlsp,
+                &qlsp,
+                &mut interp_qlsp,
+                sub,
+                NB_SUBFRAMES,
+                SB_LSP_MARGIN,
+            );
+            let mut ak = [0.0; SB_ORDER];
+            lsp_to_lpc(&interp_qlsp, &mut ak);
+
+            // Ratio between the responses of the low and high band filters at
+            // 4kHz.
+            let mut rh = 1.0;
+            for i in (0..SB_ORDER).step_by(2) {
+                rh += ak[i + 1] - ak[i];
+            }
+            let rl = low_pi_gain[sub];
+            let filter_ratio = ((f64::from(rl) + 0.01) / (f64::from(rh) + 0.01)) as f32;
+
+            let mut exc = [0.0; NB_SUBFRAME_SIZE];
+            if let Innovation::None = submode.innovation {
+                let quant = bits.read(5) as i32;
+                let g = f64::from(0.125 * (quant - 10) as f32).exp() as f32 / filter_ratio;
+                let low_innov = &self.low.innov_save[offset..offset + NB_SUBFRAME_SIZE];
+                for i in (0..NB_SUBFRAME_SIZE).step_by(2) {
+                    exc[i] = SB_FOLDING_GAIN * low_innov[i] * g;
+                    exc[i + 1] = -(SB_FOLDING_GAIN * low_innov[i + 1] * g);
+                }
+            } else {
+                let qgc = bits.read(4) as usize;
+                let el = low_exc_rms[sub];
+                let gc = (0.87360 * f64::from(GC_QUANT_BOUND[qgc])) as f32;
+                let scale = gc * el / filter_ratio;
+                innovation_unquant(submode.innovation, &mut exc, bits, &mut self.seed);
+                for e in &mut exc {
+                    *e *= scale;
+                }
+                if submode.double_codebook {
+                    let mut innov2 = [0.0; NB_SUBFRAME_SIZE];
+                    innovation_unquant(submode.innovation, &mut innov2, bits, &mut self.seed);
+                    let scale = 0.4 * scale;
+                    for (e, v) in exc.iter_mut().zip(innov2.iter()) {
+                        *e += v * scale;
+                    }
+                }
+            }
+
+            // The synthesis filter lags the excitation by one subframe.
+            iir_mem(
+                &self.exc_buf,
+                &self.interp_qlpc,
+                &mut high[offset..offset + NB_SUBFRAME_SIZE],
+                &mut self.mem_sp,
+            );
+            self.exc_buf = exc;
+            self.interp_qlpc = ak;
+            let rms = compute_rms(&self.exc_buf);
+            exc_ener_sum += rms * rms / NB_SUBFRAMES as f32;
+        }
+        self.last_ener = f64::from(exc_ener_sum).sqrt() as f32;
+
+        self.qmf_synth(&low, &high, out);
+        self.old_qlsp = qlsp;
+        self.first = false;
+        Ok(())
+    }
+
+    /// Recombine the low and high bands into the full band signal.
+    fn qmf_synth(
+        &mut self,
+        x1: &[f32; NB_FRAME_SIZE],
+        x2: &[f32; NB_FRAME_SIZE],
+        y: &mut [f32; FRAME_SIZE],
+    ) {
+        const M2: usize = QMF_ORDER / 2;
+        const N2: usize = NB_FRAME_SIZE;
+        let a = &QMF_COEFS;
+
+        let mut xx1 = [0.0; M2 + N2];
+        let mut xx2 = [0.0; M2 + N2];
+        for i in 0..N2 {
+            xx1[i] = x1[N2 - 1 - i];
+            xx2[i] = x2[N2 - 1 - i];
+        }
+        for i in 0..M2 {
+            xx1[N2 + i] = self.g0_mem[2 * i + 1];
+            xx2[N2 + i] = self.g1_mem[2 * i + 1];
+        }
+
+        for i in (0..N2).step_by(2) {
+            let (mut y0, mut y1, mut y2, mut y3) = (0.0f32, 0.0f32, 0.0f32, 0.0f32);
+            let mut x10 = xx1[N2 - 2 - i];
+            let mut x20 = xx2[N2 - 2 - i];
+
+            for j in (0..M2).step_by(2) {
+                let a0 = a[2 * j];
+                let a1 = a[2 * j + 1];
+                let x11 = xx1[N2 - 1 + j - i];
+                let x21 = xx2[N2 - 1 + j - i];
+
+                y0 += a0 * (x11 - x21);
+                y1 += a1 * (x11 + x21);
+                y2 += a0 * (x10 - x20);
+                y3 += a1 * (x10 + x20);
+
+                let a0 = a[2 * j + 2];
+                let a1 = a[2 * j + 3];
+                x10 = xx1[N2 + j - i];
+                x20 = xx2[N2 + j - i];
+
+                y0 += a0 * (x10 - x20);
+                y1 += a1 * (x10 + x20);
+                y2 += a0 * (x11 - x21);
+                y3 += a1 * (x11 + x21);
+            }
+            y[2 * i] = 2.0 * y0;
+            y[2 * i + 1] = 2.0 * y1;
+            y[2 * i + 2] = 2.0 * y2;
+            y[2 * i + 3] = 2.0 * y3;
+        }
+
+        for i in 0..M2 {
+            self.g0_mem[2 * i + 1] = xx1[i];
+            self.g1_mem[2 * i + 1] = xx2[i];
+        }
+    }
+}
+
+/// Decode the quantized line spectral pairs.
+fn lsp_unquant(quant: LspQuant, lsp: &mut [f32], bits: &mut BitReader) {
+    fn add(lsp: &mut [f32], cdbk: &[i8], id: u32, scale: f64) {
+        let entry = &cdbk[id as usize * lsp.len()..];
+        for (l, &c) in lsp.iter_mut().zip(entry.iter()) {
+            *l = (f64::from(*l) + scale * f64::from(c)) as f32;
+        }
+    }
+
+    const DIV_256: f64 = 0.0039062;
+    const DIV_512: f64 = 0.0019531;
+    const DIV_1024: f64 = 0.00097656;
+
+    match quant {
+        LspQuant::Nb | LspQuant::Lbr => {
+            for (i, l) in lsp.iter_mut().enumerate() {
+                *l = (0.25 * i as f64 + 0.25) as f32;
+            }
+            add(lsp, &LSP_CDBK_NB, bits.read(6), DIV_256);
+            if let LspQuant::Nb = quant {
+                add(&mut lsp[..5], &LSP_CDBK_NB_LOW1, bits.read(6), DIV_512);
+                add(&mut lsp[..5], &LSP_CDBK_NB_LOW2, bits.read(6), DIV_1024);
+                add(&mut lsp[5..], &LSP_CDBK_NB_HIGH1, bits.read(6), DIV_512);
+                add(&mut lsp[5..], &LSP_CDBK_NB_HIGH2, bits.read(6), DIV_1024);
+            } else {
+                add(&mut lsp[..5], &LSP_CDBK_NB_LOW1, bits.read(6), DIV_512);
+                add(&mut lsp[5..], &LSP_CDBK_NB_HIGH1, bits.read(6), DIV_512);
+            }
+        }
+        LspQuant::High => {
+            for (i, l) in lsp.iter_mut().enumerate() {
+                *l = (0.3125 * i as f64 + 0.75) as f32;
+            }
+            add(lsp, &LSP_CDBK_HIGH, bits.read(6), DIV_256);
+            add(lsp, &LSP_CDBK_HIGH2, bits.read(6), DIV_512);
+        }
+    }
+}
+
+/// Interpolate the line spectral pairs of a subframe between those of the
+/// last frame and the current one, keeping them apart to ensure stability.
+fn lsp_interpolate(
+    old_lsp: &[f32],
+    new_lsp: &[f32],
+    lsp: &mut [f32],
+    subframe: usize,
+    nb_subframes: usize,
+    margin: f32,
+) {
+    let len = lsp.len();
+    let tmp = (1.0 + subframe as f32) / nb_subframes as f32;
+    for i in 0..len {
+        lsp[i] = (1.0 - tmp) * old_lsp[i] + tmp * new_lsp[i];
+    }
+    if lsp[0] < margin {
+        lsp[0] = margin;
+    }
+    let max = PI - f64::from(margin);
+    if f64::from(lsp[len - 1]) > max {
+        lsp[len - 1] = max as f32;
+    }
+    for i in 1..len - 1 {
+        if lsp[i] < lsp[i - 1] + margin {
+            lsp[i] = lsp[i - 1] + margin;
+        }
+        if lsp[i] > lsp[i + 1] - margin {
+            lsp[i] = 0.5 * (lsp[i] + lsp[i + 1] - margin);
+        }
+    }
+}
+
+/// An approximation of the cosine for angles between 0 and pi.
+#[allow(clippy::excessive_precision)]
+fn spx_cos(x: f32) -> f32 {
+    const C1: f32 = 0.9999932946;
+    const C2: f32 = -0.4999124376;
+    const C3: f32 = 0.0414877472;
+    const C4: f32 = -0.0012712095;
+
+    if f64::from(x) < std::f64::consts::FRAC_PI_2 {
+        let x = x * x;
+        C1 + x * (C2 + x * (C3 + C4 * x))
+    } else {
+        let x = (PI - f64::from(x)) as f32;
+        let x = x * x;
+        -(C1 + x * (C2 + x * (C3 + C4 * x)))
+    }
+}
+
+/// Convert line spectral pairs to the coefficients of the LPC filter.
+fn lsp_to_lpc(freq: &[f32], ak: &mut [f32]) {
+    let order = freq.len();
+    let m = order / 2;
+    let mut wp = [0.0f32; 4 * 5 + 2];
+    let x_freq: Vec<f32> = freq.iter().map(|&f| spx_cos(f)).collect();
+
+    let mut xin1 = 1.0f32;
+    let mut xin2 = 1.0f32;
+    for j in 0..=order {
+        let mut n4 = 0;
+        for i in 0..m {
+            let n1 = i * 4;
+            let (n2, n3) = (n1 + 1, n1 + 2);
+            n4 = n1 + 3;
+            let xout1 = xin1 - 2.0 * x_freq[2 * i] * wp[n1] + wp[n2];
+            let xout2 = xin2 - 2.0 * x_freq[2 * i + 1] * wp[n3] + wp[n4];
+            wp[n2] = wp[n1];
+            wp[n4] = wp[n3];
+            wp[n1] = xin1;
+            wp[n3] = xin2;
+            xin1 = xout1;
+            xin2 = xout2;
+        }
+        let xout1 = xin1 + wp[n4 + 1];
+        let xout2 = xin2 - wp[n4 + 2];
+        if j > 0 {
+            ak[j - 1] = (xout1 + xout2) * 0.5;
+        }
+        wp[n4 + 1] = xin1;
+        wp[n4 + 2] = xin2;
+        xin1 = 0.0;
+        xin2 = 0.0;
+    }
+}
+
+/// Decode the pitch contribution of a subframe into `exc_out`, from the past
+/// excitation before `exc` in `exc_buf`.
+///
+/// Returns the pitch period and the gains of the 3-tap predictor.
+fn pitch_unquant(
+    quant: PitchQuant,
+    exc_buf: &mut [f32],
+    exc: usize,
+    exc_out: &mut [f32; NB_SUBFRAME_SIZE],
+    start: i32,
+    pitch_coef: f32,
+    bits: &mut BitReader,
+) -> (i32, [f32; 3]) {
+    match quant {
+        PitchQuant::Forced => {
+            let pitch_coef = if f64::from(pitch_coef) > 0.99 {
+                0.99
+            } else {
+                pitch_coef
+            };
+            for (i, e) in exc_out.iter_mut().enumerate() {
+                *e = exc_buf[exc + i - start as usize] * pitch_coef;
+                exc_buf[exc + i] = *e;
+            }
+            (start, [0.0, pitch_coef, 0.0])
+        }
+        PitchQuant::ThreeTap {
+            gain_cdbk,
+            gain_bits,
+            pitch_bits,
+        } => {
+            let pitch = bits.read(pitch_bits) as i32 + start;
+            let gain_index = bits.read(gain_bits) as usize;
+            let mut gain = [0.0f32; 3];
+            for (i, g) in gain.iter_mut().enumerate() {
+                *g = (0.015625 * f64::from(gain_cdbk[gain_index * 4 + i]) + 0.5) as f32;
+            }
+
+            *exc_out = [0.0; NB_SUBFRAME_SIZE];
+            for i in 0..3 {
+                let pp = (pitch + 1 - i as i32) as usize;
+                let tmp1 = NB_SUBFRAME_SIZE.min(pp);
+                for j in 0..tmp1 {
+                    exc_out[j] += gain[2 - i] * exc_buf[exc + j - pp];
+                }
+                let tmp3 = NB_SUBFRAME_SIZE.min(pp + pitch as usize);
+                for j in tmp1..tmp3 {
+                    exc_out[j] += gain[2 - i] * exc_buf[exc + j - pp - pitch as usize];
+                }
+            }
+            (pitch, gain)
+        }
+    }
+}
+
+/// Combine the gains of a 3-tap pitch predictor.
+fn gain_3tap_to_1tap(g: &[f32; 3]) -> f64 {
+    let side = |g: f32| {
+        if g > 0.0 {
+            f64::from(g)
+        } else {
+            -0.5 * f64::from(g)
+        }
+    };
+    f64::from(g[1].abs()) + side(g[0]) + side(g[2])
+}
+
+/// Decode the innovation of a subframe into `exc`.
+fn innovation_unquant(
+    innovation: Innovation,
+    exc: &mut [f32; NB_SUBFRAME_SIZE],
+    bits: &mut BitReader,
+    seed: &mut u32,
+) {
+    match innovation {
+        Innovation::None => (),
+        Innovation::Noise => {
+            for e in exc.iter_mut() {
+                *e = speex_rand(1.0, seed);
+            }
+        }
+        Innovation::SplitCodebook {
+            subvect_size,
+            nb_subvect,
+            shape_cb,
+            shape_bits,
+            have_sign,
+        } => {
+            let mut ind = [0usize; 8];
+            let mut signs = [false; 8];
+            for i in 0..nb_subvect {
+                signs[i] = have_sign && bits.read(1) == 1;
+                ind[i] = bits.read(shape_bits) as usize;
+            }
+            for i in 0..nb_subvect {
+                let s = if signs[i] { -1.0 } else { 1.0 };
+                for j in 0..subvect_size {
+                    let cb = f64::from(shape_cb[ind[i] * subvect_size + j]);
+                    let e = &mut exc[subvect_size * i + j];
+                    *e = (f64::from(*e) + s * 0.03125 * cb) as f32;
+                }
+            }
+        }
+    }
+}
+
+/// Enhance the periodicity of the excitation starting at `exc` in `exc_buf`
+/// with a comb filter, writing two subframes of enhanced excitation to
+/// `new_exc`.
+fn multicomb(exc_buf: &[f32], exc: usize, new_exc: &mut [f32], pitch: i32, comb_gain: f32) {
+    const MAX_PITCH: i32 = 40;
+    let nsf = new_exc.len();
+    let exc_slice = &exc_buf[exc..exc + nsf];
+
+    let mut iexc = [0.0; 2 * NB_SUBFRAME_SIZE * 2];
+    interp_pitch(exc_buf, exc, &mut iexc[..nsf], pitch);
+    if pitch > MAX_PITCH {
+        interp_pitch(exc_buf, exc, &mut iexc[nsf..2 * nsf], 2 * pitch);
+    } else {
+        interp_pitch(exc_buf, exc, &mut iexc[nsf..2 * nsf], -pitch);
+    }
+    let (iexc0, iexc1) = iexc[..2 * nsf].split_at(nsf);
+
+    let iexc0_mag = (1000.0 + f64::from(inner_prod(iexc0, iexc0))).sqrt() as f32;
+    let iexc1_mag = (1000.0 + f64::from(inner_prod(iexc1, iexc1))).sqrt() as f32;
+    let exc_mag = (1.0 + f64::from(inner_prod(exc_slice, exc_slice))).sqrt() as f32;
+    let corr0 = inner_prod(iexc0, exc_slice).max(0.0);
+    let corr1 = inner_prod(iexc1, exc_slice).max(0.0);
+
+    let pgain1 = if corr0 > iexc0_mag * exc_mag {
+        1.0
+    } else {
+        corr0 / exc_mag / iexc0_mag
+    };
+    let pgain2 = if corr1 > iexc1_mag * exc_mag {
+        1.0
+    } else {
+        corr1 / exc_mag / iexc1_mag
+    };
+    let gg1 = exc_mag / iexc0_mag;
+    let gg2 = exc_mag / iexc1_mag;
+
+    let (c1, c2) = if comb_gain > 0.0 {
+        let c1 = (0.4 * f64::from(comb_gain) + 0.07) as f32;
+        let c2 = (0.5 + 1.72 * (f64::from(c1) - 0.07)) as f32;
+        (c1, c2)
+    } else {
+        (0.0, 0.0)
+    };
+    let g1 = c1 / (1.0 - c2 * pgain1 * pgain1).max(c1);
+    let g2 = c1 / (1.0 - c2 * pgain2 * pgain2).max(c1);
+
+    let (gain0, gain1) = if pitch > MAX_PITCH {
+        (
+            (0.7 * f64::from(g1 * gg1)) as f32,
+            (0.3 * f64::from(g2 * gg2)) as f32,
+        )
+    } else {
+        (
+            (0.6 * f64::from(g1 * gg1)) as f32,
+            (0.6 * f64::from(g2 * gg2)) as f32,
+        )
+    };
+    for i in 0..nsf {
+        new_exc[i] = exc_slice[i] + (gain0 * iexc0[i] + gain1 * iexc1[i]);
+    }
+
+    let new_ener = compute_rms(new_exc).max(1.0);
+    let old_ener = compute_rms(exc_slice).max(1.0).min(new_ener);
+    let ngain = old_ener / new_ener;
+    for e in new_exc.iter_mut() {
+        *e *= ngain;
+    }
+}
+
+/// Interpolate the excitation one pitch period before `exc`, at the
+/// fractional offset that best matches it.
+fn interp_pitch(exc_buf: &[f32], exc: usize, interp: &mut [f32], pitch: i32) {
+    let len = interp.len();
+    let at = |offset: i32| exc_buf[(exc as i32 + offset) as usize];
+    let exc_slice = &exc_buf[exc..exc + len];
+
+    let mut corr = [[0.0f32; 7]; 4];
+    for i in 0..7 {
+        let start = (exc as i32 - pitch - 3 + i as i32) as usize;
+        corr[0][i] = inner_prod(exc_slice, &exc_buf[start..start + len]);
+    }
+    for i in 0..3 {
+        for j in 0..7 {
+            let i1 = 3usize.saturating_sub(j);
+            let i2 = (10 - j).min(7);
+            let mut tmp = 0.0;
+            for k in i1..i2 {
+                tmp += SHIFT_FILT[i][k] * corr[0][j + k - 3];
+            }
+            corr[i + 1][j] = tmp;
+        }
+    }
+
+    let (mut maxi, mut maxj) = (0, 0);
+    let mut maxcorr = corr[0][0];
+    for (i, row) in corr.iter().enumerate() {
+        for (j, &c) in row.iter().enumerate() {
+            if c > maxcorr {
+                maxcorr = c;
+                maxi = i;
+                maxj = j;
+            }
+        }
+    }
+
+    let lag = pitch - maxj as i32 + 3;
+    for (i, v) in interp.iter_mut().enumerate() {
+        let i = i as i32;
+        *v = if maxi > 0 {
+            let mut tmp = 0.0;
+            for (k, &f) in SHIFT_FILT[maxi - 1].iter().enumerate() {
+                tmp += at(i - lag + k as i32 - 3) * f;
+            }
+            tmp
+        } else {
+            at(i - lag)
+        };
+    }
+}
+
+fn inner_prod(x: &[f32], y: &[f32]) -> f32 {
+    let mut sum = 0.0;
+    for (x, y) in x.chunks_exact(4).zip(y.chunks_exact(4)) {
+        let mut part = 0.0;
+        part += x[0] * y[0];
+        part += x[1] * y[1];
+        part += x[2] * y[2];
+        part += x[3] * y[3];
+        sum += part;
+    }
+    sum
+}
+
+/// Filter `x` through the all-pole filter with coefficients `den`.
+fn iir_mem(x: &[f32], den: &[f32], y: &mut [f32], mem: &mut [f32]) {
+    let order = den.len();
+    for (&xi, yi) in x.iter().zip(y.iter_mut()) {
+        let out = xi + mem[0];
+        let nyi = -out;
+        for j in 0..order - 1 {
+            mem[j] = mem[j + 1] + den[j] * nyi;
+        }
+        mem[order - 1] = den[order - 1] * nyi;
+        *yi = out;
+    }
+}
+
+/// Remove DC and very low frequencies from the narrowband output.
+#[allow(clippy::excessive_precision)]
+fn highpass(x: &mut [f32], mem: &mut [f32; 2]) {
+    const DEN: [f32; 3] = [1.00000, -1.97226, 0.97332];
+    const NUM: [f32; 3] = [0.98645, -1.97277, 0.98645];
+    for xi in x.iter_mut() {
+        let vout = NUM[0] * *xi + mem[0];
+        mem[0] = (mem[1] + NUM[1] * *xi) + (-DEN[1] * vout);
+        mem[1] = NUM[2] * *xi + (-DEN[2] * vout);
+        *xi = vout;
+    }
+}
+
+/// Scale the coefficients of an LPC filter to widen its bandwidth.
+fn bw_lpc(gamma: f32, lpc_in: &[f32], lpc_out: &mut [f32]) {
+    let mut tmp = gamma;
+    for (o, &i) in lpc_out.iter_mut().zip(lpc_in.iter()) {
+        *o = tmp * i;
+        tmp *= gamma;
+    }
+}
+
+fn compute_rms(x: &[f32]) -> f32 {
+    let sum: f32 = x.iter().fold(0.0, |sum, x| sum + x * x);
+    (0.1 + f64::from(sum / x.len() as f32)).sqrt() as f32
+}
+
+/// Generate uniform noise with a standard deviation of `std`.
+fn speex_rand(std: f32, seed: &mut u32) -> f32 {
+    *seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+    let ran = f32::from_bits(0x3f80_0000 | (*seed & 0x007f_ffff)) - 1.5;
+    (3.4642 * f64::from(std) * f64::from(ran)) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_samples(path: &str) -> Vec<i16> {
+        std::fs::read(path)
+            .unwrap()
+            .chunks_exact(2)
+            .map(|s| i16::from_le_bytes([s[0], s[1]]))
+            .collect()
+    }
+
+    #[test]
+    fn decode_matches_libspeex() {
+        // A signal encoded with every quality setting in turn, which covers
+        // all of the narrowband and high band submodes.
+        let data = std::fs::read("tests/audio/speex.spx").unwrap();
+        let expected = read_samples("tests/audio/speex.pcm");
+        let decoder = SpeexDecoder::new(Cursor::new(data));
+        let samples: Vec<i16> = decoder.map(|s| s[0]).collect();
+        assert_eq!(samples.len(), expected.len());
+        for (i, (&a, &b)) in samples.iter().zip(expected.iter()).enumerate() {
+            assert!((i32::from(a) - i32::from(b)).abs() <= 1, "sample {}", i);
+        }
+    }
+
+    #[test]
+    fn decode_packets() {
+        // The same signal with one frame per packet, each padded to a byte
+        // with a terminator and prefixed with its length.
+        let data = std::fs::read("tests/audio/speex_packets.spx").unwrap();
+        let expected = read_samples("tests/audio/speex.pcm");
+        let mut packets = Vec::new();
+        let mut pos = 0;
+        while pos < data.len() {
+            let len = usize::from(u16::from_le_bytes([data[pos], data[pos + 1]]));
+            packets.push(&data[pos + 2..pos + 2 + len]);
+            pos += 2 + len;
+        }
+
+        let mut decoder = SpeexDecoder::new(packets[0]);
+        let mut samples: Vec<i16> = decoder.by_ref().map(|s| s[0]).collect();
+        for packet in &packets[1..] {
+            decoder.next_packet(packet);
+            samples.extend(decoder.by_ref().map(|s| s[0]));
+        }
+        assert_eq!(samples.len(), expected.len());
+        for (i, (&a, &b)) in samples.iter().zip(expected.iter()).enumerate() {
+            assert!((i32::from(a) - i32::from(b)).abs() <= 1, "sample {}", i);
+        }
+    }
+
+    #[test]
+    fn reset() {
+        let data = std::fs::read("tests/audio/speex.spx").unwrap();
+        let mut decoder = SpeexDecoder::new(Cursor::new(data));
+        let first: Vec<_> = decoder.by_ref().take(1000).collect();
+        decoder.reset();
+        let again: Vec<_> = decoder.by_ref().take(1000).collect();
+        assert_eq!(first, again);
+    }
+}
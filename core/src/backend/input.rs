@@ -6,6 +6,12 @@ pub trait InputBackend: Downcast {
 
     fn get_last_key_code(&self) -> KeyCode;
 
+    /// The character produced by the last key pressed or released, if any.
+    fn get_last_key_char(&self) -> Option<char>;
+
+    /// Whether a lock key (Caps Lock, Num Lock or Scroll Lock) is switched on.
+    fn is_key_toggled(&self, key: KeyCode) -> bool;
+
     fn mouse_visible(&self) -> bool;
 
     fn hide_mouse(&mut self);
@@ -17,6 +23,9 @@ pub trait InputBackend: Downcast {
 
     /// Set the clipboard to the given content
     fn set_clipboard_content(&mut self, content: String);
+
+    /// Get the current text content of the clipboard
+    fn clipboard_content(&mut self) -> String;
 }
 impl_downcast!(InputBackend);
 
@@ -38,6 +47,14 @@ impl InputBackend for NullInputBackend {
         KeyCode::Unknown
     }
 
+    fn get_last_key_char(&self) -> Option<char> {
+        None
+    }
+
+    fn is_key_toggled(&self, _key: KeyCode) -> bool {
+        false
+    }
+
     fn mouse_visible(&self) -> bool {
         true
     }
@@ -49,6 +66,10 @@ impl InputBackend for NullInputBackend {
     fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
 
     fn set_clipboard_content(&mut self, _content: String) {}
+
+    fn clipboard_content(&mut self) -> String {
+        String::new()
+    }
 }
 
 impl Default for NullInputBackend {
@@ -0,0 +1,285 @@
+//! Transport for `LocalConnection` messages, within and between players.
+
+use crate::external::Value as ExternalValue;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// A message sent with `LocalConnection.send`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalConnectionMessage {
+    /// The fully qualified name of the connection that should receive this message.
+    pub connection_name: String,
+
+    /// The name of the method to call on the receiving `LocalConnection`.
+    pub method_name: String,
+
+    /// The arguments to call the method with.
+    pub args: Vec<ExternalValue>,
+
+    /// The domain of the movie that sent this message.
+    pub sender_domain: String,
+}
+
+/// Something that happened to this player's connections since the last poll.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LocalConnectionEvent {
+    /// A message arrived for a connection that this player listens on.
+    ///
+    /// The receiver must answer it with `LocalConnectionBackend::reply`.
+    Message(u64, LocalConnectionMessage),
+
+    /// A message that this player sent was either handled or rejected.
+    Status(u64, bool),
+}
+
+pub trait LocalConnectionBackend {
+    /// Starts listening on the given connection name.
+    ///
+    /// Returns `false` if the name is already in use, by this player or any
+    /// other player sharing the transport.
+    fn connect(&mut self, name: &str) -> bool;
+
+    /// Stops listening on the given connection name.
+    fn close(&mut self, name: &str);
+
+    /// Sends a message to whoever listens on its connection name.
+    ///
+    /// Returns the ID of the message, which is used by the `Status` event
+    /// that reports whether it was handled.
+    fn send(&mut self, message: LocalConnectionMessage) -> u64;
+
+    /// Reports whether a received message was handled.
+    fn reply(&mut self, id: u64, success: bool);
+
+    /// Returns all events that have happened since the last poll.
+    fn poll_events(&mut self) -> Vec<LocalConnectionEvent>;
+}
+
+/// Routes `LocalConnection` messages between any number of endpoints.
+///
+/// Every player gets its own broker by default, so that movies in the same
+/// player can talk to each other. Players that share a broker, each with its
+/// own endpoint, can talk to each other as well.
+#[derive(Clone, Default)]
+pub struct LocalConnectionBroker(Arc<Mutex<BrokerState>>);
+
+impl LocalConnectionBroker {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new endpoint that a player can use as its `LocalConnectionBackend`.
+    pub fn endpoint(&self) -> LocalConnectionEndpoint {
+        let mut state = self.0.lock().unwrap();
+        let id = state.next_endpoint;
+        state.next_endpoint += 1;
+        state.queues.insert(id, VecDeque::new());
+
+        LocalConnectionEndpoint {
+            broker: self.clone(),
+            id,
+        }
+    }
+}
+
+#[derive(Default)]
+struct BrokerState {
+    next_endpoint: usize,
+    next_message: u64,
+
+    /// The endpoint listening on each connection name.
+    listeners: HashMap<String, usize>,
+
+    /// The endpoint that sent each message that hasn't been replied to yet.
+    senders: HashMap<u64, usize>,
+
+    /// The events waiting to be polled by each endpoint.
+    queues: HashMap<usize, VecDeque<LocalConnectionEvent>>,
+}
+
+impl BrokerState {
+    fn push_event(&mut self, endpoint: usize, event: LocalConnectionEvent) {
+        if let Some(queue) = self.queues.get_mut(&endpoint) {
+            queue.push_back(event);
+        }
+    }
+
+    fn reply(&mut self, id: u64, success: bool) {
+        if let Some(sender) = self.senders.remove(&id) {
+            self.push_event(sender, LocalConnectionEvent::Status(id, success));
+        }
+    }
+}
+
+/// One player's connection to a `LocalConnectionBroker`.
+pub struct LocalConnectionEndpoint {
+    broker: LocalConnectionBroker,
+    id: usize,
+}
+
+impl LocalConnectionBackend for LocalConnectionEndpoint {
+    fn connect(&mut self, name: &str) -> bool {
+        let mut state = self.broker.0.lock().unwrap();
+        if state.listeners.contains_key(name) {
+            return false;
+        }
+
+        state.listeners.insert(name.to_string(), self.id);
+        true
+    }
+
+    fn close(&mut self, name: &str) {
+        let mut state = self.broker.0.lock().unwrap();
+        if state.listeners.get(name) == Some(&self.id) {
+            state.listeners.remove(name);
+        }
+    }
+
+    fn send(&mut self, message: LocalConnectionMessage) -> u64 {
+        let mut state = self.broker.0.lock().unwrap();
+        let id = state.next_message;
+        state.next_message += 1;
+
+        match state.listeners.get(&message.connection_name).copied() {
+            Some(receiver) => {
+                state.senders.insert(id, self.id);
+                state.push_event(receiver, LocalConnectionEvent::Message(id, message));
+            }
+            None => state.push_event(self.id, LocalConnectionEvent::Status(id, false)),
+        }
+
+        id
+    }
+
+    fn reply(&mut self, id: u64, success: bool) {
+        self.broker.0.lock().unwrap().reply(id, success);
+    }
+
+    fn poll_events(&mut self) -> Vec<LocalConnectionEvent> {
+        let mut state = self.broker.0.lock().unwrap();
+        state
+            .queues
+            .get_mut(&self.id)
+            .map(|queue| queue.drain(..).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for LocalConnectionEndpoint {
+    fn drop(&mut self) {
+        let mut state = match self.broker.0.lock() {
+            Ok(state) => state,
+            Err(_) => return,
+        };
+
+        let id = self.id;
+        state.listeners.retain(|_, listener| *listener != id);
+
+        // Messages that will never be handled are rejected.
+        if let Some(queue) = state.queues.remove(&id) {
+            for event in queue {
+                if let LocalConnectionEvent::Message(message, _) = event {
+                    state.reply(message, false);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(connection_name: &str) -> LocalConnectionMessage {
+        LocalConnectionMessage {
+            connection_name: connection_name.to_string(),
+            method_name: "hello".to_string(),
+            args: vec!["world".into()],
+            sender_domain: "localhost".to_string(),
+        }
+    }
+
+    #[test]
+    fn connection_names_are_exclusive() {
+        let broker = LocalConnectionBroker::new();
+        let mut first = broker.endpoint();
+        let mut second = broker.endpoint();
+
+        assert!(first.connect("_channel"));
+        assert!(!first.connect("_channel"));
+        assert!(!second.connect("_channel"));
+
+        second.close("_channel");
+        assert!(!second.connect("_channel"));
+
+        first.close("_channel");
+        assert!(second.connect("_channel"));
+    }
+
+    #[test]
+    fn messages_between_endpoints() {
+        let broker = LocalConnectionBroker::new();
+        let mut sender = broker.endpoint();
+        let mut receiver = broker.endpoint();
+        assert!(receiver.connect("_channel"));
+
+        let id = sender.send(message("_channel"));
+        assert!(sender.poll_events().is_empty());
+        assert_eq!(
+            receiver.poll_events(),
+            vec![LocalConnectionEvent::Message(id, message("_channel"))]
+        );
+
+        receiver.reply(id, true);
+        assert_eq!(
+            sender.poll_events(),
+            vec![LocalConnectionEvent::Status(id, true)]
+        );
+        assert!(receiver.poll_events().is_empty());
+    }
+
+    #[test]
+    fn messages_within_endpoint() {
+        let mut endpoint = LocalConnectionBroker::new().endpoint();
+        assert!(endpoint.connect("localhost:channel"));
+
+        let id = endpoint.send(message("localhost:channel"));
+        assert_eq!(
+            endpoint.poll_events(),
+            vec![LocalConnectionEvent::Message(
+                id,
+                message("localhost:channel")
+            )]
+        );
+
+        endpoint.reply(id, false);
+        assert_eq!(
+            endpoint.poll_events(),
+            vec![LocalConnectionEvent::Status(id, false)]
+        );
+    }
+
+    #[test]
+    fn undeliverable_messages() {
+        let broker = LocalConnectionBroker::new();
+        let mut sender = broker.endpoint();
+
+        let id = sender.send(message("_nobody"));
+        assert_eq!(
+            sender.poll_events(),
+            vec![LocalConnectionEvent::Status(id, false)]
+        );
+
+        let mut receiver = broker.endpoint();
+        assert!(receiver.connect("_channel"));
+        let id = sender.send(message("_channel"));
+        drop(receiver);
+        assert_eq!(
+            sender.poll_events(),
+            vec![LocalConnectionEvent::Status(id, false)]
+        );
+
+        let mut receiver = broker.endpoint();
+        assert!(receiver.connect("_channel"));
+    }
+}
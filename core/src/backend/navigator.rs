@@ -1,6 +1,7 @@
 //! Browser-related platform functions
 
 use crate::loader::Error;
+use crate::security::Sandbox;
 use indexmap::IndexMap;
 use std::borrow::Cow;
 use std::collections::VecDeque;
@@ -107,6 +108,9 @@ pub struct RequestOptions {
     ///
     /// The body consists of data and a mime type.
     body: Option<(Vec<u8>, String)>,
+
+    /// Additional HTTP headers to send with the request, in order.
+    headers: Vec<(String, String)>,
 }
 
 impl RequestOptions {
@@ -115,6 +119,7 @@ impl RequestOptions {
         Self {
             method: NavigationMethod::GET,
             body: None,
+            headers: Vec::new(),
         }
     }
 
@@ -123,6 +128,7 @@ impl RequestOptions {
         Self {
             method: NavigationMethod::POST,
             body,
+            headers: Vec::new(),
         }
     }
 
@@ -135,12 +141,162 @@ impl RequestOptions {
     pub fn body(&self) -> &Option<(Vec<u8>, String)> {
         &self.body
     }
+
+    /// Replace the mime type of the body of this request.
+    ///
+    /// This does nothing if the request has no body.
+    pub fn set_content_type(&mut self, content_type: String) {
+        if let Some((_, mime)) = &mut self.body {
+            *mime = content_type;
+        }
+    }
+
+    /// Retrieve the additional headers of this request.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Set the additional headers to send with this request.
+    pub fn set_headers(&mut self, headers: Vec<(String, String)>) {
+        self.headers = headers;
+    }
 }
 
 /// Type alias for pinned, boxed, and owned futures that output a falliable
 /// result of type `Result<T, E>`.
 pub type OwnedFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + 'static>>;
 
+/// The size of the chunks that a `BufferedBody` hands out.
+const BUFFERED_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A source of response body data that arrives over time.
+///
+/// This is a minimal stream interface: each call to `poll_chunk` yields the
+/// next piece of the body, or `None` once the body has been fully read.
+pub trait ResponseBody {
+    /// Attempt to pull the next chunk of the body.
+    fn poll_chunk(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Vec<u8>, Error>>>;
+}
+
+/// A response body that is already fully present in memory.
+///
+/// The data is still handed out in chunks, so that loaders treat local and
+/// in-memory loads the same way as network loads.
+pub struct BufferedBody {
+    data: Vec<u8>,
+    position: usize,
+}
+
+impl BufferedBody {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data, position: 0 }
+    }
+}
+
+impl ResponseBody for BufferedBody {
+    fn poll_chunk(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Vec<u8>, Error>>> {
+        let this = self.get_mut();
+        if this.position >= this.data.len() {
+            return Poll::Ready(None);
+        }
+
+        let end = (this.position + BUFFERED_CHUNK_SIZE).min(this.data.len());
+        let chunk = this.data[this.position..end].to_vec();
+        this.position = end;
+
+        Poll::Ready(Some(Ok(chunk)))
+    }
+}
+
+/// The response to a fetch.
+pub struct Response {
+    /// The final URL of the response, after any redirects were followed.
+    pub url: String,
+
+    /// The HTTP status code of the response.
+    ///
+    /// This is `None` for responses that did not come from an HTTP server,
+    /// such as local file loads.
+    pub status: Option<u16>,
+
+    /// The response headers, in the order they were received.
+    pub headers: IndexMap<String, String>,
+
+    /// The total length of the body, if it is known ahead of time.
+    pub content_length: Option<u64>,
+
+    /// The body of the response.
+    body: Pin<Box<dyn ResponseBody>>,
+}
+
+impl Response {
+    /// Construct a response with the given body.
+    pub fn new(url: String, body: Pin<Box<dyn ResponseBody>>) -> Self {
+        Self {
+            url,
+            status: None,
+            headers: IndexMap::new(),
+            content_length: None,
+            body,
+        }
+    }
+
+    /// Construct a non-HTTP response whose body is already in memory.
+    pub fn from_data(url: String, data: Vec<u8>) -> Self {
+        let content_length = Some(data.len() as u64);
+
+        Self {
+            content_length,
+            ..Self::new(url, Box::pin(BufferedBody::new(data)))
+        }
+    }
+
+    /// Whether or not this response indicates a successful load.
+    ///
+    /// Non-HTTP responses are always successful.
+    pub fn is_success(&self) -> bool {
+        self.status.map(|s| (200..300).contains(&s)).unwrap_or(true)
+    }
+
+    /// Wait for the next chunk of the body.
+    ///
+    /// Yields `None` once the body has been fully read.
+    pub fn next_chunk(&mut self) -> NextChunk<'_> {
+        NextChunk {
+            body: self.body.as_mut(),
+        }
+    }
+
+    /// Read the rest of the body into memory.
+    pub async fn into_bytes(mut self) -> Result<Vec<u8>, Error> {
+        let mut data = Vec::with_capacity(self.content_length.unwrap_or(0) as usize);
+        while let Some(chunk) = self.next_chunk().await {
+            data.extend_from_slice(&chunk?);
+        }
+
+        Ok(data)
+    }
+}
+
+/// Future returned by `Response::next_chunk`.
+pub struct NextChunk<'a> {
+    body: Pin<&'a mut dyn ResponseBody>,
+}
+
+impl<'a> Future for NextChunk<'a> {
+    type Output = Option<Result<Vec<u8>, Error>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.body.as_mut().poll_chunk(cx)
+    }
+}
+
 /// A backend interacting with a browser environment.
 pub trait NavigatorBackend {
     /// Cause a browser navigation to a given URL.
@@ -172,8 +328,15 @@ pub trait NavigatorBackend {
         vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
     );
 
-    /// Fetch data at a given URL and return it some time in the future.
-    fn fetch(&self, url: &str, request_options: RequestOptions) -> OwnedFuture<Vec<u8>, Error>;
+    /// Fetch data at a given URL.
+    ///
+    /// The returned future resolves as soon as the response metadata is
+    /// available; the body is then read from the `Response` in chunks.
+    ///
+    /// Responses with an unsuccessful HTTP status should still resolve
+    /// successfully, so that the status can be reported to the movie. Only
+    /// failures to get any response at all should yield an error.
+    fn fetch(&self, url: &str, request_options: RequestOptions) -> OwnedFuture<Response, Error>;
 
     /// Get the amount of time since the SWF was launched.
     /// Used by the `getTimer` ActionScript call.
@@ -198,6 +361,17 @@ pub trait NavigatorBackend {
     /// current document's base URL, while the most obvious base for a desktop
     /// client would be the file-URL form of the current path.
     fn resolve_relative_url<'a>(&mut self, url: &'a str) -> Cow<'a, str>;
+
+    /// Restrict all further fetches to the given security sandbox.
+    ///
+    /// This is called once the root movie is known. Until then, fetches are
+    /// unrestricted. Backends that run inside a web browser may leave this
+    /// to the browser's own restrictions.
+    fn set_sandbox(&mut self, _sandbox: Sandbox) {}
+
+    /// Register an additional cross-domain policy file to consult, as with
+    /// `System.security.loadPolicyFile`.
+    fn load_policy_file(&mut self, _url: &str) {}
 }
 
 /// A null implementation of an event loop that only supports blocking.
@@ -309,7 +483,13 @@ pub struct NullNavigatorBackend {
     channel: Option<Sender<OwnedFuture<(), Error>>>,
 
     /// The base path for all relative fetches.
+    ///
+    /// Network URLs are served from here as well, from a subdirectory named
+    /// after their host.
     relative_base_path: PathBuf,
+
+    /// The security sandbox that fetches are subject to.
+    sandbox: Sandbox,
 }
 
 impl NullNavigatorBackend {
@@ -319,6 +499,7 @@ impl NullNavigatorBackend {
         NullNavigatorBackend {
             channel: None,
             relative_base_path: PathBuf::new(),
+            sandbox: Sandbox::default(),
         }
     }
 
@@ -334,6 +515,7 @@ impl NullNavigatorBackend {
         NullNavigatorBackend {
             channel: Some(channel),
             relative_base_path,
+            sandbox: Sandbox::default(),
         }
     }
 }
@@ -353,11 +535,24 @@ impl NavigatorBackend for NullNavigatorBackend {
     ) {
     }
 
-    fn fetch(&self, url: &str, _opts: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
-        let mut path = self.relative_base_path.clone();
-        path.push(url);
+    fn fetch(&self, url: &str, options: RequestOptions) -> OwnedFuture<Response, Error> {
+        let base_path = self.relative_base_path.clone();
+        let absolute_base_path = std::env::current_dir()
+            .map(|cwd| cwd.join(&base_path))
+            .unwrap_or_else(|_| base_path.clone());
+
+        let full_url = match url_from_relative_path(&absolute_base_path, url) {
+            Ok(full_url) => full_url,
+            Err(_) => {
+                let url = url.to_string();
+                return Box::pin(async move { Err(Error::FetchError(url)) });
+            }
+        };
 
-        Box::pin(async move { fs::read(path).map_err(Error::NetworkError) })
+        self.sandbox.fetch(full_url, options, move |url, _options| {
+            let base_path = base_path.clone();
+            Box::pin(async move { fetch_from_base_path(&base_path, &url) })
+        })
     }
 
     fn time_since_launch(&mut self) -> Duration {
@@ -378,4 +573,118 @@ impl NavigatorBackend for NullNavigatorBackend {
             url.into()
         }
     }
+
+    fn set_sandbox(&mut self, sandbox: Sandbox) {
+        self.sandbox = sandbox;
+    }
+
+    fn load_policy_file(&mut self, url: &str) {
+        if let Ok(url) = Url::parse(url) {
+            self.sandbox.add_policy_file(url);
+        }
+    }
+}
+
+/// Serve a URL from the files under `base_path`.
+///
+/// Local files are read directly. Network URLs are looked up in a
+/// subdirectory named after their host, and answered with an HTTP status so
+/// that movies can observe failed requests.
+fn fetch_from_base_path(base_path: &Path, url: &Url) -> Result<Response, Error> {
+    if url.scheme() == "file" {
+        let path = url_to_file_path(url).ok_or_else(|| Error::FetchError(url.to_string()))?;
+        let data = fs::read(path).map_err(Error::NetworkError)?;
+
+        return Ok(Response::from_data(url.to_string(), data));
+    }
+
+    let mut path = base_path.join(url.host_str().unwrap_or_default());
+    for segment in url.path_segments().into_iter().flatten() {
+        path.push(segment);
+    }
+
+    let (status, data) = match fs::read(path) {
+        Ok(data) => (200, data),
+        Err(_) => (404, Vec::new()),
+    };
+    let mut response = Response::from_data(url.to_string(), data);
+    response.status = Some(status);
+
+    Ok(response)
+}
+
+/// Convert a `file:///` URL into a filesystem path.
+#[cfg(any(unix, windows, target_os = "redox"))]
+fn url_to_file_path(url: &Url) -> Option<PathBuf> {
+    url.to_file_path().ok()
+}
+
+/// Convert a `file:///` URL into a filesystem path.
+///
+/// This is the web version of this function, which always fails.
+#[cfg(not(any(unix, windows, target_os = "redox")))]
+fn url_to_file_path(_url: &Url) -> Option<PathBuf> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Poll a future that never waits on anything until it completes.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let waker = unsafe { Waker::from_raw(NullExecutor::raw_waker()) };
+        let mut context = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(result) = future.as_mut().poll(&mut context) {
+                return result;
+            }
+        }
+    }
+
+    fn fetch(url: &str) -> Result<Response, Error> {
+        let (_executor, channel) = NullExecutor::new();
+        let navigator = NullNavigatorBackend::with_base_path("tests/swfs/avm1/http_load", channel);
+        block_on(navigator.fetch(url, RequestOptions::get()))
+    }
+
+    #[test]
+    fn buffered_body_chunks() {
+        let data: Vec<u8> = (0..BUFFERED_CHUNK_SIZE * 2 + 10).map(|i| i as u8).collect();
+        let mut response = Response::from_data("file:///data".to_string(), data.clone());
+        assert_eq!(response.content_length, Some(data.len() as u64));
+
+        let mut chunks = Vec::new();
+        while let Some(chunk) = block_on(response.next_chunk()) {
+            chunks.push(chunk.unwrap());
+        }
+
+        let lengths: Vec<usize> = chunks.iter().map(|chunk| chunk.len()).collect();
+        assert_eq!(lengths, vec![BUFFERED_CHUNK_SIZE, BUFFERED_CHUNK_SIZE, 10]);
+        assert_eq!(chunks.concat(), data);
+    }
+
+    #[test]
+    fn host_directories() {
+        let response = fetch("http://example.com/vars.txt").unwrap();
+        assert_eq!(response.status, Some(200));
+        assert!(response.is_success());
+        assert_eq!(response.url, "http://example.com/vars.txt");
+        let data = block_on(response.into_bytes()).unwrap();
+        assert_eq!(data, b"greeting=hello&other=1");
+
+        let response = fetch("http://example.com/missing.txt").unwrap();
+        assert_eq!(response.status, Some(404));
+        assert!(!response.is_success());
+    }
+
+    #[test]
+    fn local_files() {
+        let response = fetch("test.as").unwrap();
+        assert_eq!(response.status, None);
+        assert!(response.is_success());
+
+        assert!(fetch("missing.as").is_err());
+    }
 }
@@ -5,6 +5,8 @@ use std::io::Read;
 pub use swf;
 use swf::Matrix;
 
+pub mod filters;
+
 pub trait RenderBackend: Downcast {
     fn set_viewport_dimensions(&mut self, width: u32, height: u32);
     fn register_shape(&mut self, shape: DistilledShape) -> ShapeHandle;
@@ -32,6 +34,25 @@ pub trait RenderBackend: Downcast {
         swf_tag: &swf::DefineBitsLossless,
     ) -> Result<BitmapInfo, Error>;
 
+    /// Registers a bitmap from raw RGBA pixel data that is not tied to a
+    /// character, such as a decoded video frame.
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error>;
+
+    /// Replaces the pixel data of a bitmap previously registered with
+    /// `register_bitmap_raw`. The dimensions may differ from the original.
+    fn update_texture(
+        &mut self,
+        bitmap: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error>;
+
     fn begin_frame(&mut self, clear: Color);
     fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform);
     fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform);
@@ -42,6 +63,21 @@ pub trait RenderBackend: Downcast {
     fn activate_mask(&mut self);
     fn deactivate_mask(&mut self);
     fn pop_mask(&mut self);
+
+    /// Begins rendering to an offscreen buffer, so that `filters` can be applied
+    /// to everything drawn until the matching `pop_filters` call.
+    fn push_filters(&mut self, filters: &[filters::Filter]);
+
+    /// Applies the filters from the matching `push_filters` call to the offscreen
+    /// buffer, and draws the result onto the previous target.
+    fn pop_filters(&mut self);
+
+    /// Sets the blend mode used for everything drawn until the matching
+    /// `pop_blend_mode` call.
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode);
+
+    /// Restores the blend mode that was active before the matching `push_blend_mode` call.
+    fn pop_blend_mode(&mut self);
 }
 impl_downcast!(RenderBackend);
 
@@ -136,6 +172,23 @@ impl RenderBackend for NullRenderer {
             height: 0,
         })
     }
+    fn register_bitmap_raw(
+        &mut self,
+        _width: u32,
+        _height: u32,
+        _rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(BitmapHandle(0))
+    }
+    fn update_texture(
+        &mut self,
+        bitmap: BitmapHandle,
+        _width: u32,
+        _height: u32,
+        _rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(bitmap)
+    }
     fn begin_frame(&mut self, _clear: Color) {}
     fn end_frame(&mut self) {}
     fn render_bitmap(&mut self, _bitmap: BitmapHandle, _transform: &Transform) {}
@@ -146,6 +199,10 @@ impl RenderBackend for NullRenderer {
     fn activate_mask(&mut self) {}
     fn deactivate_mask(&mut self) {}
     fn pop_mask(&mut self) {}
+    fn push_filters(&mut self, _filters: &[filters::Filter]) {}
+    fn pop_filters(&mut self) {}
+    fn push_blend_mode(&mut self, _blend_mode: swf::BlendMode) {}
+    fn pop_blend_mode(&mut self) {}
 }
 
 /// The format of image data in a DefineBitsJpeg2/3 tag.
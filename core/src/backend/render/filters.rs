@@ -0,0 +1,826 @@
+//! Software implementation of the bitmap filters that can be applied to display objects.
+//!
+//! Render backends without a native implementation of a filter can render the filtered
+//! object to an offscreen buffer, and then use these to process the pixels.
+//! All filters operate on premultiplied RGBA data.
+
+use swf::{Color, GradientRecord};
+
+/// A bitmap filter applied to a display object.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    /// A filter that can also be placed on a display object by the SWF.
+    Swf(swf::Filter),
+
+    /// A `DisplacementMapFilter`, which can only be created by ActionScript.
+    DisplacementMap(Box<DisplacementMapFilter>),
+}
+
+impl From<swf::Filter> for Filter {
+    fn from(filter: swf::Filter) -> Self {
+        Filter::Swf(filter)
+    }
+}
+
+/// A filter that moves each pixel by an amount read from the color channels of a map bitmap.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisplacementMapFilter {
+    /// The width of the map bitmap, in pixels.
+    pub map_width: u32,
+
+    /// The height of the map bitmap, in pixels.
+    pub map_height: u32,
+
+    /// The pixels of the map bitmap, stored row-major as unmultiplied 32-bit ARGB values.
+    pub map_pixels: Vec<u32>,
+
+    /// The position of the top-left corner of the map bitmap in the filtered image.
+    pub map_point: (i32, i32),
+
+    /// The channel of the map used for horizontal displacement, as a `BitmapDataChannel` value.
+    pub component_x: u32,
+
+    /// The channel of the map used for vertical displacement, as a `BitmapDataChannel` value.
+    pub component_y: u32,
+
+    /// The horizontal displacement for a channel value of 255.
+    pub scale_x: f64,
+
+    /// The vertical displacement for a channel value of 255.
+    pub scale_y: f64,
+
+    /// What to draw for pixels displaced from outside of the image.
+    pub mode: DisplacementMapMode,
+
+    /// The color drawn for pixels displaced from outside of the image in `Color` mode.
+    pub color: Color,
+}
+
+/// What a `DisplacementMapFilter` draws for pixels displaced from outside of the image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisplacementMapMode {
+    /// Wrap around to the opposite side of the image.
+    Wrap,
+
+    /// Use the nearest pixel on the edge of the image.
+    Clamp,
+
+    /// Don't displace the pixel.
+    Ignore,
+
+    /// Use the color of the filter.
+    Color,
+}
+
+/// Applies each of the filters in order to premultiplied RGBA pixel data.
+pub fn apply_filters(width: u32, height: u32, rgba: &mut [u8], filters: &[Filter]) {
+    let mut image = Image::from_rgba(width as usize, height as usize, rgba);
+    for filter in filters {
+        image = apply_filter(&image, filter);
+    }
+    image.write_rgba(rgba);
+}
+
+fn apply_filter(image: &Image, filter: &Filter) -> Image {
+    match filter {
+        Filter::Swf(filter) => apply_swf_filter(image, filter),
+        Filter::DisplacementMap(filter) => displacement_map(image, filter),
+    }
+}
+
+fn apply_swf_filter(image: &Image, filter: &swf::Filter) -> Image {
+    match filter {
+        swf::Filter::BlurFilter(filter) => {
+            let mut image = image.clone();
+            box_blur(
+                &mut image.pixels,
+                image.width,
+                image.height,
+                filter.blur_x,
+                filter.blur_y,
+                filter.num_passes,
+            );
+            image
+        }
+        swf::Filter::DropShadowFilter(filter) => {
+            let color = color_to_premultiplied(&filter.color);
+            shadow(
+                image,
+                &ShadowParams {
+                    blur_x: filter.blur_x,
+                    blur_y: filter.blur_y,
+                    angle: filter.angle,
+                    distance: filter.distance,
+                    strength: filter.strength,
+                    is_inner: filter.is_inner,
+                    is_knockout: filter.is_knockout,
+                    num_passes: filter.num_passes,
+                },
+                |amount| scale(color, amount),
+            )
+        }
+        swf::Filter::GlowFilter(filter) => {
+            let color = color_to_premultiplied(&filter.color);
+            shadow(
+                image,
+                &ShadowParams {
+                    blur_x: filter.blur_x,
+                    blur_y: filter.blur_y,
+                    angle: 0.0,
+                    distance: 0.0,
+                    strength: filter.strength,
+                    is_inner: filter.is_inner,
+                    is_knockout: filter.is_knockout,
+                    num_passes: filter.num_passes,
+                },
+                |amount| scale(color, amount),
+            )
+        }
+        swf::Filter::GradientGlowFilter(filter) => shadow(
+            image,
+            &ShadowParams {
+                blur_x: filter.blur_x,
+                blur_y: filter.blur_y,
+                angle: filter.angle,
+                distance: filter.distance,
+                strength: filter.strength,
+                is_inner: filter.is_inner,
+                is_knockout: filter.is_knockout,
+                num_passes: filter.num_passes,
+            },
+            |amount| sample_gradient(&filter.colors, amount),
+        ),
+        swf::Filter::BevelFilter(filter) => {
+            let highlight = color_to_premultiplied(&filter.highlight_color);
+            let shadow = color_to_premultiplied(&filter.shadow_color);
+            bevel(
+                image,
+                &BevelParams {
+                    blur_x: filter.blur_x,
+                    blur_y: filter.blur_y,
+                    angle: filter.angle,
+                    distance: filter.distance,
+                    strength: filter.strength,
+                    is_inner: filter.is_inner,
+                    is_knockout: filter.is_knockout,
+                    is_on_top: filter.is_on_top,
+                    num_passes: filter.num_passes,
+                },
+                |amount| {
+                    if amount >= 0.0 {
+                        scale(highlight, amount)
+                    } else {
+                        scale(shadow, -amount)
+                    }
+                },
+            )
+        }
+        swf::Filter::GradientBevelFilter(filter) => bevel(
+            image,
+            &BevelParams {
+                blur_x: filter.blur_x,
+                blur_y: filter.blur_y,
+                angle: filter.angle,
+                distance: filter.distance,
+                strength: filter.strength,
+                is_inner: filter.is_inner,
+                is_knockout: filter.is_knockout,
+                is_on_top: filter.is_on_top,
+                num_passes: filter.num_passes,
+            },
+            // The shadow side of the bevel is at the start of the gradient,
+            // and the highlight side at the end.
+            |amount| sample_gradient(&filter.colors, (amount + 1.0) / 2.0),
+        ),
+        swf::Filter::ColorMatrixFilter(filter) => color_matrix(image, &filter.matrix),
+        swf::Filter::ConvolutionFilter(filter) => convolution(image, filter),
+    }
+}
+
+/// A premultiplied RGBA image with floating-point components between 0.0 and 1.0.
+#[derive(Clone, Debug)]
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Image {
+    fn from_rgba(width: usize, height: usize, rgba: &[u8]) -> Self {
+        let pixels = rgba
+            .chunks_exact(4)
+            .map(|p| {
+                [
+                    f32::from(p[0]) / 255.0,
+                    f32::from(p[1]) / 255.0,
+                    f32::from(p[2]) / 255.0,
+                    f32::from(p[3]) / 255.0,
+                ]
+            })
+            .collect();
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn write_rgba(&self, rgba: &mut [u8]) {
+        for (out, pixel) in rgba.chunks_exact_mut(4).zip(&self.pixels) {
+            let a = clamp_unit(pixel[3]);
+            // Color components can't exceed the alpha of a premultiplied color.
+            out[0] = (pixel[0].max(0.0).min(a) * 255.0).round() as u8;
+            out[1] = (pixel[1].max(0.0).min(a) * 255.0).round() as u8;
+            out[2] = (pixel[2].max(0.0).min(a) * 255.0).round() as u8;
+            out[3] = (a * 255.0).round() as u8;
+        }
+    }
+
+    /// The alpha channel of the image, offset by the given number of pixels.
+    /// Areas outside of the image are transparent.
+    fn offset_alpha(&self, dx: isize, dy: isize) -> Vec<f32> {
+        let mut alpha = vec![0.0; self.pixels.len()];
+        for y in 0..self.height {
+            let src_y = y as isize - dy;
+            if src_y < 0 || src_y >= self.height as isize {
+                continue;
+            }
+            for x in 0..self.width {
+                let src_x = x as isize - dx;
+                if src_x >= 0 && src_x < self.width as isize {
+                    alpha[y * self.width + x] =
+                        self.pixels[src_y as usize * self.width + src_x as usize][3];
+                }
+            }
+        }
+        alpha
+    }
+}
+
+/// A value that can be blurred.
+trait Blur: Copy + Default {
+    fn add(self, other: Self) -> Self;
+    fn sub(self, other: Self) -> Self;
+    fn scale(self, amount: f32) -> Self;
+}
+
+impl Blur for f32 {
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+
+    fn sub(self, other: Self) -> Self {
+        self - other
+    }
+
+    fn scale(self, amount: f32) -> Self {
+        self * amount
+    }
+}
+
+impl Blur for [f32; 4] {
+    fn add(self, other: Self) -> Self {
+        [
+            self[0] + other[0],
+            self[1] + other[1],
+            self[2] + other[2],
+            self[3] + other[3],
+        ]
+    }
+
+    fn sub(self, other: Self) -> Self {
+        [
+            self[0] - other[0],
+            self[1] - other[1],
+            self[2] - other[2],
+            self[3] - other[3],
+        ]
+    }
+
+    fn scale(self, amount: f32) -> Self {
+        scale(self, amount)
+    }
+}
+
+/// Blurs the image by repeatedly applying a box blur of the given size.
+/// Each pass makes the blur closer to a gaussian blur.
+fn box_blur<T: Blur>(
+    pixels: &mut [T],
+    width: usize,
+    height: usize,
+    blur_x: f64,
+    blur_y: f64,
+    num_passes: u8,
+) {
+    let radius_x = blur_radius(blur_x);
+    let radius_y = blur_radius(blur_y);
+    let mut line = Vec::with_capacity(std::cmp::max(width, height));
+    for _ in 0..num_passes {
+        if radius_x > 0 {
+            for y in 0..height {
+                line.clear();
+                line.extend_from_slice(&pixels[y * width..(y + 1) * width]);
+                box_blur_line(&line, radius_x, |x, value| pixels[y * width + x] = value);
+            }
+        }
+        if radius_y > 0 {
+            for x in 0..width {
+                line.clear();
+                line.extend((0..height).map(|y| pixels[y * width + x]));
+                box_blur_line(&line, radius_y, |y, value| pixels[y * width + x] = value);
+            }
+        }
+    }
+}
+
+/// The number of pixels on each side of the center of a box blur of the given width.
+pub fn blur_radius(blur: f64) -> usize {
+    if blur > 1.0 {
+        ((blur - 1.0) / 2.0).round() as usize
+    } else {
+        0
+    }
+}
+
+fn box_blur_line<T: Blur>(line: &[T], radius: usize, mut output: impl FnMut(usize, T)) {
+    let len = line.len();
+    let scale = 1.0 / (2 * radius + 1) as f32;
+    let mut sum = line
+        .iter()
+        .take(radius)
+        .fold(T::default(), |sum, &value| sum.add(value));
+    for i in 0..len {
+        if i + radius < len {
+            sum = sum.add(line[i + radius]);
+        }
+        output(i, sum.scale(scale));
+        if i >= radius {
+            sum = sum.sub(line[i - radius]);
+        }
+    }
+}
+
+struct ShadowParams {
+    blur_x: f64,
+    blur_y: f64,
+    angle: f64,
+    distance: f64,
+    strength: f32,
+    is_inner: bool,
+    is_knockout: bool,
+    num_passes: u8,
+}
+
+/// Applies a drop shadow or glow. `color` maps the strength of the shadow at a pixel
+/// to its premultiplied color.
+fn shadow(image: &Image, params: &ShadowParams, color: impl Fn(f32) -> [f32; 4]) -> Image {
+    let (dx, dy) = offset(params.angle, params.distance);
+    let mut amounts = image.offset_alpha(dx, dy);
+    if params.is_inner {
+        // An inner shadow is cast by the area outside of the object.
+        for amount in &mut amounts {
+            *amount = 1.0 - *amount;
+        }
+    }
+    box_blur(
+        &mut amounts,
+        image.width,
+        image.height,
+        params.blur_x,
+        params.blur_y,
+        params.num_passes,
+    );
+
+    let pixels = image
+        .pixels
+        .iter()
+        .zip(amounts)
+        .map(|(&src, amount)| {
+            let shadow = color(clamp_unit(amount * params.strength));
+            if params.is_inner {
+                let shadow = scale(shadow, src[3]);
+                if params.is_knockout {
+                    shadow
+                } else {
+                    over(shadow, src)
+                }
+            } else if params.is_knockout {
+                scale(shadow, 1.0 - src[3])
+            } else {
+                over(src, shadow)
+            }
+        })
+        .collect();
+
+    Image { pixels, ..*image }
+}
+
+struct BevelParams {
+    blur_x: f64,
+    blur_y: f64,
+    angle: f64,
+    distance: f64,
+    strength: f32,
+    is_inner: bool,
+    is_knockout: bool,
+    is_on_top: bool,
+    num_passes: u8,
+}
+
+/// Applies a bevel. `color` maps the strength of the bevel at a pixel to its
+/// premultiplied color, where positive values are highlights and negative values
+/// are shadows.
+fn bevel(image: &Image, params: &BevelParams, color: impl Fn(f32) -> [f32; 4]) -> Image {
+    let (dx, dy) = offset(params.angle, params.distance);
+    let mut alpha = image.offset_alpha(0, 0);
+    box_blur(
+        &mut alpha,
+        image.width,
+        image.height,
+        params.blur_x,
+        params.blur_y,
+        params.num_passes,
+    );
+    let blurred = Image {
+        pixels: alpha.iter().map(|&a| [0.0, 0.0, 0.0, a]).collect(),
+        ..*image
+    };
+    // Edges facing the light have more of the object further along the light's
+    // direction than behind it.
+    let lit = blurred.offset_alpha(-dx, -dy);
+    let unlit = blurred.offset_alpha(dx, dy);
+
+    let pixels = image
+        .pixels
+        .iter()
+        .zip(lit.into_iter().zip(unlit))
+        .map(|(&src, (lit, unlit))| {
+            let amount = ((lit - unlit) * params.strength).max(-1.0).min(1.0);
+            let bevel = color(amount);
+            let bevel = if params.is_on_top {
+                bevel
+            } else if params.is_inner {
+                scale(bevel, src[3])
+            } else {
+                scale(bevel, 1.0 - src[3])
+            };
+            if params.is_knockout {
+                bevel
+            } else {
+                over(bevel, src)
+            }
+        })
+        .collect();
+
+    Image { pixels, ..*image }
+}
+
+/// Applies a 4x5 color matrix to unmultiplied colors.
+/// The last column is an offset in the range of 0 to 255.
+fn color_matrix(image: &Image, matrix: &[f64; 20]) -> Image {
+    let pixels = image
+        .pixels
+        .iter()
+        .map(|&pixel| {
+            let src = unmultiply(pixel);
+            let mut out = [0.0; 4];
+            for (i, out) in out.iter_mut().enumerate() {
+                let row = &matrix[i * 5..i * 5 + 5];
+                let value = row[0] * f64::from(src[0])
+                    + row[1] * f64::from(src[1])
+                    + row[2] * f64::from(src[2])
+                    + row[3] * f64::from(src[3])
+                    + row[4] / 255.0;
+                *out = clamp_unit(value as f32);
+            }
+            premultiply(out)
+        })
+        .collect();
+
+    Image { pixels, ..*image }
+}
+
+fn convolution(image: &Image, filter: &swf::ConvolutionFilter) -> Image {
+    let cols = isize::from(filter.num_matrix_cols);
+    let rows = isize::from(filter.num_matrix_rows);
+    let divisor = if filter.divisor != 0.0 {
+        filter.divisor
+    } else {
+        1.0
+    };
+    let bias = filter.bias / 255.0;
+    let default_color = unmultiply(color_to_premultiplied(&filter.default_color));
+    let (width, height) = (image.width as isize, image.height as isize);
+
+    let mut pixels = Vec::with_capacity(image.pixels.len());
+    for y in 0..height {
+        for x in 0..width {
+            let mut sum = [0.0f64; 4];
+            for row in 0..rows {
+                for col in 0..cols {
+                    let weight = filter.matrix[(row * cols + col) as usize];
+                    let mut src_x = x + col - cols / 2;
+                    let mut src_y = y + row - rows / 2;
+                    let in_bounds = src_x >= 0 && src_x < width && src_y >= 0 && src_y < height;
+                    let src = if in_bounds || filter.is_clamped {
+                        src_x = src_x.max(0).min(width - 1);
+                        src_y = src_y.max(0).min(height - 1);
+                        unmultiply(image.pixels[(src_y * width + src_x) as usize])
+                    } else {
+                        default_color
+                    };
+                    for (sum, &src) in sum.iter_mut().zip(&src) {
+                        *sum += weight * f64::from(src);
+                    }
+                }
+            }
+
+            let mut out = [0.0; 4];
+            for (out, sum) in out.iter_mut().zip(&sum) {
+                *out = clamp_unit((sum / divisor + bias) as f32);
+            }
+            if filter.is_preserve_alpha {
+                out[3] = image.pixels[(y * width + x) as usize][3];
+            }
+            pixels.push(premultiply(out));
+        }
+    }
+
+    Image { pixels, ..*image }
+}
+
+fn displacement_map(image: &Image, filter: &DisplacementMapFilter) -> Image {
+    let (width, height) = (image.width as isize, image.height as isize);
+    let (map_width, map_height) = (filter.map_width as isize, filter.map_height as isize);
+    let color = color_to_premultiplied(&filter.color);
+
+    // A channel value of 128 leaves the pixel in place.
+    let channel = |argb: u32, component: u32| match component {
+        1 => (argb >> 16) & 0xFF,
+        2 => (argb >> 8) & 0xFF,
+        4 => argb & 0xFF,
+        8 => argb >> 24,
+        _ => 128,
+    };
+    let displacement =
+        |value: u32, scale: f64| ((f64::from(value) - 128.0) * scale / 256.0).round() as isize;
+
+    let mut pixels = Vec::with_capacity(image.pixels.len());
+    for y in 0..height {
+        for x in 0..width {
+            // Pixels outside of the map aren't displaced.
+            let map_x = x - filter.map_point.0 as isize;
+            let map_y = y - filter.map_point.1 as isize;
+            let (dx, dy) = if map_x >= 0 && map_x < map_width && map_y >= 0 && map_y < map_height {
+                let argb = filter
+                    .map_pixels
+                    .get((map_y * map_width + map_x) as usize)
+                    .copied()
+                    .unwrap_or(0x8080_8080);
+                (
+                    displacement(channel(argb, filter.component_x), filter.scale_x),
+                    displacement(channel(argb, filter.component_y), filter.scale_y),
+                )
+            } else {
+                (0, 0)
+            };
+
+            let src_x = x + dx;
+            let src_y = y + dy;
+            let pixel = if src_x >= 0 && src_x < width && src_y >= 0 && src_y < height {
+                image.pixels[(src_y * width + src_x) as usize]
+            } else {
+                match filter.mode {
+                    DisplacementMapMode::Wrap => {
+                        let src_x = src_x.rem_euclid(width);
+                        let src_y = src_y.rem_euclid(height);
+                        image.pixels[(src_y * width + src_x) as usize]
+                    }
+                    DisplacementMapMode::Clamp => {
+                        let src_x = src_x.max(0).min(width - 1);
+                        let src_y = src_y.max(0).min(height - 1);
+                        image.pixels[(src_y * width + src_x) as usize]
+                    }
+                    DisplacementMapMode::Ignore => image.pixels[(y * width + x) as usize],
+                    DisplacementMapMode::Color => color,
+                }
+            };
+            pixels.push(pixel);
+        }
+    }
+
+    Image { pixels, ..*image }
+}
+
+/// The pixel offset of a shadow cast at the given angle in radians.
+pub fn offset(angle: f64, distance: f64) -> (isize, isize) {
+    (
+        (distance * angle.cos()).round() as isize,
+        (distance * angle.sin()).round() as isize,
+    )
+}
+
+/// Returns the premultiplied color at `position` (between 0.0 and 1.0) in a gradient.
+fn sample_gradient(records: &[GradientRecord], position: f32) -> [f32; 4] {
+    let ratio = clamp_unit(position) * 255.0;
+    let (first, last) = match (records.first(), records.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return [0.0; 4],
+    };
+    if ratio <= f32::from(first.ratio) {
+        return color_to_premultiplied(&first.color);
+    }
+    for pair in records.windows(2) {
+        let (start, end) = (&pair[0], &pair[1]);
+        if ratio <= f32::from(end.ratio) {
+            let range = f32::from(end.ratio) - f32::from(start.ratio);
+            let t = if range > 0.0 {
+                (ratio - f32::from(start.ratio)) / range
+            } else {
+                1.0
+            };
+            let start = color_to_premultiplied(&start.color);
+            let end = color_to_premultiplied(&end.color);
+            return scale(start, 1.0 - t).add(scale(end, t));
+        }
+    }
+    color_to_premultiplied(&last.color)
+}
+
+/// Converts a color to premultiplied RGBA components between 0.0 and 1.0.
+pub fn color_to_premultiplied(color: &Color) -> [f32; 4] {
+    premultiply([
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+        f32::from(color.a) / 255.0,
+    ])
+}
+
+fn premultiply(color: [f32; 4]) -> [f32; 4] {
+    [
+        color[0] * color[3],
+        color[1] * color[3],
+        color[2] * color[3],
+        color[3],
+    ]
+}
+
+fn unmultiply(color: [f32; 4]) -> [f32; 4] {
+    if color[3] > 0.0 {
+        [
+            (color[0] / color[3]).min(1.0),
+            (color[1] / color[3]).min(1.0),
+            (color[2] / color[3]).min(1.0),
+            color[3],
+        ]
+    } else {
+        [0.0; 4]
+    }
+}
+
+fn scale(color: [f32; 4], amount: f32) -> [f32; 4] {
+    [
+        color[0] * amount,
+        color[1] * amount,
+        color[2] * amount,
+        color[3] * amount,
+    ]
+}
+
+/// Composites the premultiplied color `top` over `bottom`.
+fn over(top: [f32; 4], bottom: [f32; 4]) -> [f32; 4] {
+    top.add(scale(bottom, 1.0 - top[3]))
+}
+
+fn clamp_unit(value: f32) -> f32 {
+    value.max(0.0).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{BlurFilter, ColorMatrixFilter, GlowFilter};
+
+    /// A 5x5 transparent image with an opaque red pixel in the center.
+    fn dot() -> Vec<u8> {
+        let mut rgba = vec![0; 5 * 5 * 4];
+        rgba[12 * 4..12 * 4 + 4].copy_from_slice(&[255, 0, 0, 255]);
+        rgba
+    }
+
+    #[test]
+    fn color_matrix_filter() {
+        // Swaps the red and blue channels, and halves the alpha.
+        #[rustfmt::skip]
+        let matrix = [
+            0.0, 0.0, 1.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0, 0.0,
+            1.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.5, 0.0,
+        ];
+        let mut rgba = dot();
+        let filter = swf::Filter::ColorMatrixFilter(Box::new(ColorMatrixFilter { matrix }));
+        apply_filters(5, 5, &mut rgba, &[filter.into()]);
+        assert_eq!(&rgba[12 * 4..12 * 4 + 4], &[0, 0, 128, 128]);
+        assert!(rgba[..12 * 4].iter().all(|&n| n == 0));
+    }
+
+    #[test]
+    fn blur_filter() {
+        let mut rgba = dot();
+        let filter = swf::Filter::BlurFilter(Box::new(BlurFilter {
+            blur_x: 3.0,
+            blur_y: 3.0,
+            num_passes: 1,
+        }));
+        apply_filters(5, 5, &mut rgba, &[filter.into()]);
+        // The dot is spread evenly over the surrounding 3x3 area.
+        for y in 0..5 {
+            for x in 0..5 {
+                let i = (y * 5 + x) * 4;
+                let expected = if (1..4).contains(&x) && (1..4).contains(&y) {
+                    28
+                } else {
+                    0
+                };
+                assert_eq!(rgba[i + 3], expected, "alpha at ({}, {})", x, y);
+                assert_eq!(rgba[i], expected, "red at ({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn glow_filter_knockout() {
+        let mut rgba = dot();
+        let filter = swf::Filter::GlowFilter(Box::new(GlowFilter {
+            color: Color {
+                r: 0,
+                g: 255,
+                b: 0,
+                a: 255,
+            },
+            blur_x: 3.0,
+            blur_y: 3.0,
+            strength: 9.0,
+            is_inner: false,
+            is_knockout: true,
+            num_passes: 1,
+        }));
+        apply_filters(5, 5, &mut rgba, &[filter.into()]);
+        // The object itself is knocked out, leaving only the glow around it.
+        assert_eq!(&rgba[12 * 4..12 * 4 + 4], &[0, 0, 0, 0]);
+        assert_eq!(&rgba[11 * 4..11 * 4 + 4], &[0, 255, 0, 255]);
+        assert_eq!(&rgba[0..4], &[0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn displacement_map_filter() {
+        // Moves each pixel of the top-left 3x3 area of the map one pixel to the left,
+        // so that the dot is drawn one pixel to its right.
+        let mut rgba = dot();
+        let filter = DisplacementMapFilter {
+            map_width: 3,
+            map_height: 3,
+            map_pixels: vec![0xFF00_0000; 9],
+            map_point: (1, 1),
+            component_x: 1,
+            component_y: 2,
+            scale_x: 2.0,
+            scale_y: 0.0,
+            mode: DisplacementMapMode::Color,
+            color: Color {
+                r: 0,
+                g: 0,
+                b: 255,
+                a: 255,
+            },
+        };
+        apply_filters(
+            5,
+            5,
+            &mut rgba,
+            &[Filter::DisplacementMap(Box::new(filter.clone()))],
+        );
+        assert_eq!(&rgba[12 * 4..12 * 4 + 4], &[0, 0, 0, 0]);
+        assert_eq!(&rgba[13 * 4..13 * 4 + 4], &[255, 0, 0, 255]);
+
+        // Pixels outside of the map aren't displaced, and pixels displaced from
+        // outside of the image are filled with the color of the filter.
+        let mut rgba = dot();
+        let filter = DisplacementMapFilter {
+            map_point: (-2, 0),
+            ..filter
+        };
+        apply_filters(
+            5,
+            5,
+            &mut rgba,
+            &[Filter::DisplacementMap(Box::new(filter))],
+        );
+        assert_eq!(&rgba[12 * 4..12 * 4 + 4], &[255, 0, 0, 255]);
+        assert_eq!(&rgba[0..4], &[0, 0, 255, 255]);
+        assert_eq!(&rgba[10 * 4..10 * 4 + 4], &[0, 0, 255, 255]);
+        assert_eq!(&rgba[15 * 4..15 * 4 + 4], &[0, 0, 0, 0]);
+    }
+}
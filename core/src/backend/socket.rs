@@ -0,0 +1,85 @@
+//! Raw TCP socket backend, used by `XMLSocket`.
+
+use gc_arena::Collect;
+use std::collections::VecDeque;
+
+/// An opaque handle to a connection opened by a `SocketBackend`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Collect)]
+#[collect(require_static)]
+pub struct SocketHandle(pub u64);
+
+/// Something that happened on a socket since the last time the backend was polled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SocketEvent {
+    /// A connection attempt finished, either successfully or not.
+    Connect(SocketHandle, bool),
+
+    /// Data was received from the remote end.
+    Data(SocketHandle, Vec<u8>),
+
+    /// The remote end closed the connection, or it was lost.
+    Close(SocketHandle),
+}
+
+pub trait SocketBackend {
+    /// Starts connecting to the given host and port.
+    ///
+    /// This must not block; the outcome is reported later by a
+    /// `SocketEvent::Connect` for the returned handle.
+    fn connect(&mut self, host: &str, port: u16) -> SocketHandle;
+
+    /// Queues data to be sent over the given connection.
+    fn send(&mut self, handle: SocketHandle, data: Vec<u8>);
+
+    /// Closes the given connection.
+    ///
+    /// No further events will be reported for this handle.
+    fn close(&mut self, handle: SocketHandle);
+
+    /// Returns all events that have happened since the last poll.
+    fn poll_events(&mut self) -> Vec<SocketEvent>;
+}
+
+/// Socket backend that can't connect to anything.
+pub struct NullSocketBackend {
+    next_handle: u64,
+    events: VecDeque<SocketEvent>,
+}
+
+impl NullSocketBackend {
+    pub fn new() -> Self {
+        Self {
+            next_handle: 0,
+            events: VecDeque::new(),
+        }
+    }
+}
+
+impl SocketBackend for NullSocketBackend {
+    fn connect(&mut self, _host: &str, _port: u16) -> SocketHandle {
+        let handle = SocketHandle(self.next_handle);
+        self.next_handle += 1;
+        self.events.push_back(SocketEvent::Connect(handle, false));
+        handle
+    }
+
+    fn send(&mut self, _handle: SocketHandle, _data: Vec<u8>) {}
+
+    fn close(&mut self, handle: SocketHandle) {
+        self.events.retain(|event| match event {
+            SocketEvent::Connect(h, _) | SocketEvent::Data(h, _) | SocketEvent::Close(h) => {
+                *h != handle
+            }
+        });
+    }
+
+    fn poll_events(&mut self) -> Vec<SocketEvent> {
+        self.events.drain(..).collect()
+    }
+}
+
+impl Default for NullSocketBackend {
+    fn default() -> Self {
+        NullSocketBackend::new()
+    }
+}
@@ -0,0 +1,147 @@
+//! Video decoding backend
+
+use crate::backend::render::{BitmapInfo, RenderBackend};
+use generational_arena::{Arena, Index};
+use swf::{VideoCodec, VideoDeblocking};
+
+pub mod decoders;
+mod software;
+
+pub use software::SoftwareVideoBackend;
+
+pub type VideoStreamHandle = Index;
+
+type Error = Box<dyn std::error::Error>;
+
+/// A single frame of encoded video data, as found in a `VideoFrame` tag.
+#[derive(Copy, Clone, Debug)]
+pub struct EncodedFrame<'a> {
+    /// The codec used to encode the frame.
+    pub codec: VideoCodec,
+
+    /// The raw bitstream data of the frame.
+    pub data: &'a [u8],
+
+    /// The frame number of this frame within its stream.
+    pub frame_id: u32,
+}
+
+impl<'a> EncodedFrame<'a> {
+    /// Borrow this frame's data.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+/// A fully decoded video frame with straight (not pre-multiplied) RGBA pixels.
+#[derive(Clone, Debug)]
+pub struct DecodedFrame {
+    pub width: u16,
+    pub height: u16,
+    pub rgba: Vec<u8>,
+}
+
+/// Which previously decoded frames a given video frame needs in order to be
+/// decoded itself.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameDependency {
+    /// This frame is a keyframe and can be decoded on its own.
+    None,
+
+    /// This frame is an inter frame and requires the frames before it.
+    Past,
+}
+
+impl FrameDependency {
+    /// Whether or not this frame can be decoded without any prior frames.
+    pub fn is_keyframe(self) -> bool {
+        matches!(self, FrameDependency::None)
+    }
+}
+
+/// A backend that decodes video streams into bitmaps that can be drawn by a
+/// `RenderBackend`.
+pub trait VideoBackend {
+    /// Register a new video stream.
+    ///
+    /// The returned handle is used to refer to this stream in all other
+    /// calls. Each `Video` display object registers its own stream, as the
+    /// decoder keeps track of the previously decoded frame.
+    fn register_video_stream(
+        &mut self,
+        num_frames: u32,
+        size: (u16, u16),
+        codec: VideoCodec,
+        filter: VideoDeblocking,
+    ) -> Result<VideoStreamHandle, Error>;
+
+    /// Inspect a frame of video without decoding it.
+    ///
+    /// This is used to determine which frames are keyframes, so that seeking
+    /// only has to decode the frames after the nearest keyframe.
+    fn preload_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        encoded_frame: EncodedFrame<'_>,
+    ) -> Result<FrameDependency, Error>;
+
+    /// Decode a frame of video and upload it to the renderer.
+    ///
+    /// Frames must be decoded in order, starting from a keyframe. The
+    /// returned bitmap is owned by the stream and will be overwritten by the
+    /// next decoded frame.
+    fn decode_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        encoded_frame: EncodedFrame<'_>,
+        renderer: &mut dyn RenderBackend,
+    ) -> Result<BitmapInfo, Error>;
+}
+
+/// A video backend that refuses to decode any video.
+pub struct NullVideoBackend {
+    streams: Arena<()>,
+}
+
+impl NullVideoBackend {
+    pub fn new() -> Self {
+        Self {
+            streams: Arena::new(),
+        }
+    }
+}
+
+impl Default for NullVideoBackend {
+    fn default() -> Self {
+        NullVideoBackend::new()
+    }
+}
+
+impl VideoBackend for NullVideoBackend {
+    fn register_video_stream(
+        &mut self,
+        _num_frames: u32,
+        _size: (u16, u16),
+        _codec: VideoCodec,
+        _filter: VideoDeblocking,
+    ) -> Result<VideoStreamHandle, Error> {
+        Ok(self.streams.insert(()))
+    }
+
+    fn preload_video_stream_frame(
+        &mut self,
+        _stream: VideoStreamHandle,
+        _encoded_frame: EncodedFrame<'_>,
+    ) -> Result<FrameDependency, Error> {
+        Err("Video decoding is not supported by this backend".into())
+    }
+
+    fn decode_video_stream_frame(
+        &mut self,
+        _stream: VideoStreamHandle,
+        _encoded_frame: EncodedFrame<'_>,
+        _renderer: &mut dyn RenderBackend,
+    ) -> Result<BitmapInfo, Error> {
+        Err("Video decoding is not supported by this backend".into())
+    }
+}
@@ -0,0 +1,50 @@
+//! Video decoders.
+
+mod h263;
+mod screen;
+
+pub use h263::H263Decoder;
+pub use screen::ScreenVideoDecoder;
+
+use crate::backend::video::{DecodedFrame, EncodedFrame, Error, FrameDependency};
+use swf::VideoCodec;
+
+/// A decoder for a single video stream.
+///
+/// Decoders keep the previously decoded frame around, so frames must be fed
+/// to `decode_frame` in order, starting from a keyframe.
+pub trait VideoDecoder {
+    /// Determine what dependencies a frame has without decoding it.
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error>;
+
+    /// Decode a frame into RGBA pixels.
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error>;
+}
+
+/// Instantiate a decoder for the given codec.
+pub fn make_decoder(codec: VideoCodec, size: (u16, u16)) -> Result<Box<dyn VideoDecoder>, Error> {
+    let decoder: Box<dyn VideoDecoder> = match codec {
+        VideoCodec::H263 => Box::new(H263Decoder::new()),
+        VideoCodec::ScreenVideo => Box::new(ScreenVideoDecoder::new(size)),
+        _ => {
+            let msg = format!("make_decoder: Unsupported video codec {:?}", codec);
+            log::error!("{}", msg);
+            return Err(msg.into());
+        }
+    };
+    Ok(decoder)
+}
+
+/// Converts a BT.601 YCbCr sample to RGB.
+fn yuv_to_rgb(y: u8, cb: u8, cr: u8) -> [u8; 3] {
+    let y = (i32::from(y) - 16) * 298;
+    let cb = i32::from(cb) - 128;
+    let cr = i32::from(cr) - 128;
+
+    let clamp = |n: i32| ((n + 128) >> 8).max(0).min(255) as u8;
+    [
+        clamp(y + 409 * cr),
+        clamp(y - 100 * cb - 208 * cr),
+        clamp(y + 516 * cb),
+    ]
+}
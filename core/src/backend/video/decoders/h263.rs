@@ -0,0 +1,1077 @@
+//! Sorenson H.263 (codec 2) decoder.
+//!
+//! Sorenson Spark is a subset of baseline H.263 with a different picture
+//! header, no GOB headers, a "disposable" inter frame type, and (in version 1
+//! bitstreams) a more compact escape code for transform coefficients.
+//! Advanced prediction modes are not used by Flash encoders and are not
+//! supported here.
+
+use crate::backend::video::decoders::{yuv_to_rgb, VideoDecoder};
+use crate::backend::video::{DecodedFrame, EncodedFrame, Error, FrameDependency};
+
+pub struct H263Decoder {
+    /// The last decoded non-disposable picture.
+    reference: Option<Picture>,
+
+    /// Lookup tables for the variable-length codes.
+    tables: VlcTables,
+
+    /// Precomputed cosine basis for the inverse DCT.
+    idct: [[f32; 8]; 8],
+}
+
+impl H263Decoder {
+    pub fn new() -> Self {
+        let mut idct = [[0.0; 8]; 8];
+        for (u, row) in idct.iter_mut().enumerate() {
+            let scale = if u == 0 {
+                std::f32::consts::FRAC_1_SQRT_2 / 2.0
+            } else {
+                0.5
+            };
+            for (x, value) in row.iter_mut().enumerate() {
+                *value =
+                    scale * ((2 * x + 1) as f32 * u as f32 * std::f32::consts::PI / 16.0).cos();
+            }
+        }
+
+        Self {
+            reference: None,
+            tables: VlcTables::new(),
+            idct,
+        }
+    }
+
+    fn decode_picture(
+        &self,
+        reader: &mut BitReader<'_>,
+    ) -> Result<(PictureHeader, Picture), Error> {
+        let header = PictureHeader::read(reader)?;
+        if header.width == 0 || header.height == 0 {
+            return Err("H.263 picture has no size".into());
+        }
+
+        let reference = if header.picture_type == PictureType::Intra {
+            None
+        } else {
+            match &self.reference {
+                Some(reference)
+                    if reference.width == header.width && reference.height == header.height =>
+                {
+                    Some(reference)
+                }
+                _ => return Err("H.263 inter frame has no reference picture".into()),
+            }
+        };
+
+        let mut picture = Picture::new(header.width, header.height);
+        let mut motion_vectors = vec![(0, 0); picture.mb_width * picture.mb_height];
+        let mut quantizer = i32::from(header.quantizer);
+        for mb_y in 0..picture.mb_height {
+            for mb_x in 0..picture.mb_width {
+                self.decode_macroblock(
+                    reader,
+                    &header,
+                    reference,
+                    &mut picture,
+                    &mut motion_vectors,
+                    &mut quantizer,
+                    (mb_x, mb_y),
+                )?;
+            }
+        }
+
+        Ok((header, picture))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn decode_macroblock(
+        &self,
+        reader: &mut BitReader<'_>,
+        header: &PictureHeader,
+        reference: Option<&Picture>,
+        picture: &mut Picture,
+        motion_vectors: &mut [(i32, i32)],
+        quantizer: &mut i32,
+        (mb_x, mb_y): (usize, usize),
+    ) -> Result<(), Error> {
+        let mb_index = mb_y * picture.mb_width + mb_x;
+
+        let (is_intra, has_dquant, cbpc) = if let Some(reference) = reference {
+            if reader.read_bit()? {
+                // Not coded; copy the macroblock from the reference picture.
+                motion_vectors[mb_index] = (0, 0);
+                picture.predict_macroblock(reference, mb_x, mb_y, (0, 0));
+                return Ok(());
+            }
+
+            let mcbpc = loop {
+                match self.tables.mcbpc_inter.decode(reader)? {
+                    MCBPC_INTER_STUFFING => continue,
+                    mcbpc => break mcbpc,
+                }
+            };
+            if mcbpc & 16 != 0 {
+                return Err("H.263 advanced prediction mode is not supported".into());
+            }
+            (mcbpc & 4 != 0, mcbpc & 8 != 0, mcbpc & 3)
+        } else {
+            let mcbpc = loop {
+                match self.tables.mcbpc_intra.decode(reader)? {
+                    MCBPC_INTRA_STUFFING => continue,
+                    mcbpc => break mcbpc,
+                }
+            };
+            (true, mcbpc & 4 != 0, mcbpc & 3)
+        };
+
+        let mut cbpy = self.tables.cbpy.decode(reader)?;
+        if !is_intra {
+            cbpy ^= 0xf;
+        }
+        if has_dquant {
+            let dquant = [-1, -2, 1, 2][reader.read(2)? as usize];
+            *quantizer = (*quantizer + dquant).max(1).min(31);
+        }
+        let cbp = (cbpy << 2) | cbpc;
+
+        let motion_vector = if is_intra {
+            (0, 0)
+        } else {
+            let (pred_x, pred_y) =
+                predict_motion_vector(motion_vectors, picture.mb_width, mb_x, mb_y);
+            (
+                self.decode_motion(reader, pred_x)?,
+                self.decode_motion(reader, pred_y)?,
+            )
+        };
+        motion_vectors[mb_index] = motion_vector;
+
+        if let (false, Some(reference)) = (is_intra, reference) {
+            picture.predict_macroblock(reference, mb_x, mb_y, motion_vector);
+        }
+
+        for block_index in 0..6 {
+            let is_coded = cbp & (32 >> block_index) != 0;
+            if !is_intra && !is_coded {
+                continue;
+            }
+
+            let coefficients =
+                self.decode_block(reader, header.version, is_intra, is_coded, *quantizer)?;
+            let residual = self.inverse_dct(&coefficients);
+            picture.write_block(mb_x, mb_y, block_index, &residual, !is_intra);
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the quantized coefficients of a block and dequantizes them.
+    /// The returned coefficients are in raster order.
+    fn decode_block(
+        &self,
+        reader: &mut BitReader<'_>,
+        version: u8,
+        is_intra: bool,
+        is_coded: bool,
+        quantizer: i32,
+    ) -> Result<[i32; 64], Error> {
+        let mut block = [0; 64];
+        let mut i = 0;
+
+        if is_intra {
+            let dc = match reader.read(8)? {
+                0 | 128 => return Err("Invalid H.263 intra DC coefficient".into()),
+                255 => 128,
+                dc => dc as i32,
+            };
+            block[0] = dc * 8;
+            i = 1;
+        }
+
+        if !is_coded {
+            return Ok(block);
+        }
+
+        loop {
+            let symbol = self.tables.tcoef.decode(reader)?;
+            let (is_last, run, level) = if symbol == TCOEF_ESCAPE {
+                if version >= 1 {
+                    let is_11_bit = reader.read_bit()?;
+                    let is_last = reader.read_bit()?;
+                    let run = reader.read(6)? as usize;
+                    let level = reader.read_signed(if is_11_bit { 11 } else { 7 })?;
+                    (is_last, run, level)
+                } else {
+                    let is_last = reader.read_bit()?;
+                    let run = reader.read(6)? as usize;
+                    let level = reader.read_signed(8)?;
+                    if level == 0 || level == -128 {
+                        return Err("Invalid H.263 escaped coefficient".into());
+                    }
+                    (is_last, run, level)
+                }
+            } else {
+                let (_, _, is_last, run, level) = TCOEF[symbol];
+                let level = i32::from(level);
+                let level = if reader.read_bit()? { -level } else { level };
+                (is_last, usize::from(run), level)
+            };
+
+            i += run;
+            if i >= 64 {
+                return Err("H.263 coefficient run is out of range".into());
+            }
+            block[ZIGZAG[i]] = dequantize(level, quantizer);
+            i += 1;
+
+            if is_last {
+                break;
+            }
+        }
+
+        Ok(block)
+    }
+
+    /// Decodes a motion vector component in half-pixel units.
+    fn decode_motion(&self, reader: &mut BitReader<'_>, prediction: i32) -> Result<i32, Error> {
+        let magnitude = self.tables.mvd.decode(reader)? as i32;
+        if magnitude == 0 {
+            return Ok(prediction);
+        }
+        let difference = if reader.read_bit()? {
+            -magnitude
+        } else {
+            magnitude
+        };
+
+        // Vectors wrap around into the range -16..15.5 pixels.
+        Ok(((prediction + difference + 32) & 63) - 32)
+    }
+
+    fn inverse_dct(&self, coefficients: &[i32; 64]) -> [i32; 64] {
+        let mut rows = [0.0f32; 64];
+        for v in 0..8 {
+            for x in 0..8 {
+                rows[v * 8 + x] = (0..8)
+                    .map(|u| self.idct[u][x] * coefficients[v * 8 + u] as f32)
+                    .sum();
+            }
+        }
+
+        let mut out = [0; 64];
+        for y in 0..8 {
+            for x in 0..8 {
+                let value: f32 = (0..8).map(|v| self.idct[v][y] * rows[v * 8 + x]).sum();
+                out[y * 8 + x] = value.round() as i32;
+            }
+        }
+        out
+    }
+}
+
+impl Default for H263Decoder {
+    fn default() -> Self {
+        H263Decoder::new()
+    }
+}
+
+impl VideoDecoder for H263Decoder {
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
+        let mut reader = BitReader::new(encoded_frame.data());
+        let header = PictureHeader::read(&mut reader)?;
+        Ok(match header.picture_type {
+            PictureType::Intra => FrameDependency::None,
+            PictureType::Inter | PictureType::DisposableInter => FrameDependency::Past,
+        })
+    }
+
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+        let mut reader = BitReader::new(encoded_frame.data());
+        let (header, picture) = self.decode_picture(&mut reader)?;
+        let frame = picture.to_rgba();
+
+        // Disposable frames are never used as a reference.
+        if header.picture_type != PictureType::DisposableInter {
+            self.reference = Some(picture);
+        }
+
+        Ok(frame)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum PictureType {
+    Intra,
+    Inter,
+    DisposableInter,
+}
+
+struct PictureHeader {
+    version: u8,
+    width: usize,
+    height: usize,
+    picture_type: PictureType,
+    quantizer: u8,
+}
+
+impl PictureHeader {
+    fn read(reader: &mut BitReader<'_>) -> Result<Self, Error> {
+        if reader.read(17)? != 1 {
+            return Err("Invalid H.263 picture start code".into());
+        }
+
+        let version = reader.read(5)? as u8;
+        if version > 1 {
+            return Err(format!("Unsupported Sorenson H.263 version {}", version).into());
+        }
+
+        let _temporal_reference = reader.read(8)?;
+        let (width, height) = match reader.read(3)? {
+            0 => (reader.read(8)?, reader.read(8)?),
+            1 => (reader.read(16)?, reader.read(16)?),
+            2 => (352, 288),
+            3 => (176, 144),
+            4 => (128, 96),
+            5 => (320, 240),
+            6 => (160, 120),
+            _ => return Err("Invalid H.263 picture size".into()),
+        };
+
+        let picture_type = match reader.read(2)? {
+            0 => PictureType::Intra,
+            1 => PictureType::Inter,
+            2 => PictureType::DisposableInter,
+            _ => return Err("Invalid H.263 picture type".into()),
+        };
+
+        // The deblocking flag is only a hint to the post-processor.
+        let _deblocking = reader.read_bit()?;
+        let quantizer = reader.read(5)? as u8;
+        if quantizer == 0 {
+            return Err("Invalid H.263 quantizer".into());
+        }
+
+        // Skip any extra information.
+        while reader.read_bit()? {
+            reader.read(8)?;
+        }
+
+        Ok(Self {
+            version,
+            width: width as usize,
+            height: height as usize,
+            picture_type,
+            quantizer,
+        })
+    }
+}
+
+/// A decoded YCbCr 4:2:0 picture, padded to a whole number of macroblocks.
+struct Picture {
+    width: usize,
+    height: usize,
+    mb_width: usize,
+    mb_height: usize,
+    luma: Vec<u8>,
+    chroma_b: Vec<u8>,
+    chroma_r: Vec<u8>,
+}
+
+impl Picture {
+    fn new(width: usize, height: usize) -> Self {
+        let mb_width = (width + 15) / 16;
+        let mb_height = (height + 15) / 16;
+        let luma_size = mb_width * mb_height * 256;
+        Self {
+            width,
+            height,
+            mb_width,
+            mb_height,
+            luma: vec![0; luma_size],
+            chroma_b: vec![128; luma_size / 4],
+            chroma_r: vec![128; luma_size / 4],
+        }
+    }
+
+    /// Returns the plane for block `index` of a macroblock, its stride and
+    /// the position of the block within the plane.
+    fn block_plane(
+        &mut self,
+        mb_x: usize,
+        mb_y: usize,
+        index: usize,
+    ) -> (&mut [u8], usize, usize, usize) {
+        let luma_stride = self.mb_width * 16;
+        let chroma_stride = self.mb_width * 8;
+        match index {
+            0..=3 => (
+                &mut self.luma,
+                luma_stride,
+                mb_x * 16 + (index & 1) * 8,
+                mb_y * 16 + (index >> 1) * 8,
+            ),
+            4 => (&mut self.chroma_b, chroma_stride, mb_x * 8, mb_y * 8),
+            _ => (&mut self.chroma_r, chroma_stride, mb_x * 8, mb_y * 8),
+        }
+    }
+
+    /// Writes a block of pixels, or adds a residual to the predicted pixels.
+    fn write_block(
+        &mut self,
+        mb_x: usize,
+        mb_y: usize,
+        index: usize,
+        block: &[i32; 64],
+        is_residual: bool,
+    ) {
+        let (plane, stride, x, y) = self.block_plane(mb_x, mb_y, index);
+        for (row, values) in block.chunks_exact(8).enumerate() {
+            let start = (y + row) * stride + x;
+            for (pixel, value) in plane[start..start + 8].iter_mut().zip(values) {
+                let base = if is_residual { i32::from(*pixel) } else { 0 };
+                *pixel = (base + value).max(0).min(255) as u8;
+            }
+        }
+    }
+
+    /// Fills a macroblock with the motion-compensated prediction from the
+    /// reference picture. Motion vectors are in half-pixel units.
+    fn predict_macroblock(
+        &mut self,
+        reference: &Picture,
+        mb_x: usize,
+        mb_y: usize,
+        (mv_x, mv_y): (i32, i32),
+    ) {
+        let luma_stride = self.mb_width * 16;
+        let luma_height = self.mb_height * 16;
+        predict_block(
+            &reference.luma,
+            &mut self.luma,
+            (luma_stride, luma_height),
+            (mb_x * 16, mb_y * 16),
+            16,
+            (mv_x, mv_y),
+        );
+
+        // Chroma vectors are halved, rounding quarter-pixel positions to half-pixels.
+        let chroma_mv = ((mv_x >> 1) | (mv_x & 1), (mv_y >> 1) | (mv_y & 1));
+        let chroma_size = (self.mb_width * 8, self.mb_height * 8);
+        let chroma_position = (mb_x * 8, mb_y * 8);
+        predict_block(
+            &reference.chroma_b,
+            &mut self.chroma_b,
+            chroma_size,
+            chroma_position,
+            8,
+            chroma_mv,
+        );
+        predict_block(
+            &reference.chroma_r,
+            &mut self.chroma_r,
+            chroma_size,
+            chroma_position,
+            8,
+            chroma_mv,
+        );
+    }
+
+    fn to_rgba(&self) -> DecodedFrame {
+        let luma_stride = self.mb_width * 16;
+        let chroma_stride = self.mb_width * 8;
+        let mut rgba = Vec::with_capacity(self.width * self.height * 4);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let luma = self.luma[y * luma_stride + x];
+                let chroma_index = (y / 2) * chroma_stride + x / 2;
+                let [r, g, b] = yuv_to_rgb(
+                    luma,
+                    self.chroma_b[chroma_index],
+                    self.chroma_r[chroma_index],
+                );
+                rgba.extend_from_slice(&[r, g, b, 255]);
+            }
+        }
+
+        DecodedFrame {
+            width: self.width as u16,
+            height: self.height as u16,
+            rgba,
+        }
+    }
+}
+
+/// Copies a square block from `reference` into `out` with half-pixel
+/// interpolation. Pixels outside of the reference picture are clamped to its
+/// edges, as H.263 allows motion vectors to point outside of the picture.
+fn predict_block(
+    reference: &[u8],
+    out: &mut [u8],
+    (stride, height): (usize, usize),
+    (x, y): (usize, usize),
+    size: usize,
+    (mv_x, mv_y): (i32, i32),
+) {
+    let sample = |px: i32, py: i32| -> u32 {
+        let px = px.max(0).min(stride as i32 - 1) as usize;
+        let py = py.max(0).min(height as i32 - 1) as usize;
+        u32::from(reference[py * stride + px])
+    };
+
+    for row in 0..size {
+        for col in 0..size {
+            let half_x = (x + col) as i32 * 2 + mv_x;
+            let half_y = (y + row) as i32 * 2 + mv_y;
+            let (px, py) = (half_x >> 1, half_y >> 1);
+            let value = match (half_x & 1, half_y & 1) {
+                (0, 0) => sample(px, py),
+                (1, 0) => (sample(px, py) + sample(px + 1, py) + 1) >> 1,
+                (0, _) => (sample(px, py) + sample(px, py + 1) + 1) >> 1,
+                _ => {
+                    (sample(px, py)
+                        + sample(px + 1, py)
+                        + sample(px, py + 1)
+                        + sample(px + 1, py + 1)
+                        + 2)
+                        >> 2
+                }
+            };
+            out[(y + row) * stride + x + col] = value as u8;
+        }
+    }
+}
+
+/// Predicts a macroblock's motion vector from the median of its neighbors to
+/// the left, above, and above-right.
+fn predict_motion_vector(
+    motion_vectors: &[(i32, i32)],
+    mb_width: usize,
+    mb_x: usize,
+    mb_y: usize,
+) -> (i32, i32) {
+    let index = mb_y * mb_width + mb_x;
+    let left = if mb_x > 0 {
+        motion_vectors[index - 1]
+    } else {
+        (0, 0)
+    };
+    if mb_y == 0 {
+        return left;
+    }
+
+    let above = motion_vectors[index - mb_width];
+    let above_right = if mb_x + 1 < mb_width {
+        motion_vectors[index - mb_width + 1]
+    } else {
+        (0, 0)
+    };
+
+    let median = |a: i32, b: i32, c: i32| a.max(b).min(a.min(b).max(c));
+    (
+        median(left.0, above.0, above_right.0),
+        median(left.1, above.1, above_right.1),
+    )
+}
+
+/// Reconstructs a coefficient from its quantized level.
+fn dequantize(level: i32, quantizer: i32) -> i32 {
+    if level == 0 {
+        return 0;
+    }
+    let magnitude = quantizer * (2 * level.abs() + 1) - (1 - quantizer % 2);
+    (magnitude * level.signum()).max(-2048).min(2047)
+}
+
+/// A big-endian bit reader over a byte slice.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// Returns the next `num_bits` bits without consuming them, padding with
+    /// zeroes past the end of the data.
+    fn peek(&self, num_bits: u32) -> u32 {
+        let mut value = 0;
+        for bit in self.position..self.position + num_bits as usize {
+            let byte = self.data.get(bit / 8).copied().unwrap_or(0);
+            value = (value << 1) | u32::from((byte >> (7 - bit % 8)) & 1);
+        }
+        value
+    }
+
+    fn skip(&mut self, num_bits: u32) -> Result<(), Error> {
+        self.position += num_bits as usize;
+        if self.position > self.data.len() * 8 {
+            return Err("Unexpected end of H.263 bitstream".into());
+        }
+        Ok(())
+    }
+
+    fn read(&mut self, num_bits: u32) -> Result<u32, Error> {
+        let value = self.peek(num_bits);
+        self.skip(num_bits)?;
+        Ok(value)
+    }
+
+    fn read_bit(&mut self) -> Result<bool, Error> {
+        Ok(self.read(1)? != 0)
+    }
+
+    fn read_signed(&mut self, num_bits: u32) -> Result<i32, Error> {
+        let shift = 32 - num_bits;
+        Ok(((self.read(num_bits)? << shift) as i32) >> shift)
+    }
+}
+
+/// A lookup table for decoding a set of variable-length codes.
+struct Vlc {
+    max_len: u32,
+
+    /// Maps every `max_len`-bit sequence to the length and symbol of the code
+    /// it begins with. A length of zero marks an invalid code.
+    table: Vec<(u8, u8)>,
+}
+
+impl Vlc {
+    /// Builds a table from `(code, length)` pairs. The index of each pair is
+    /// the symbol it decodes to.
+    fn new(codes: &[(u16, u8)]) -> Self {
+        let max_len = codes
+            .iter()
+            .map(|&(_, len)| u32::from(len))
+            .max()
+            .unwrap_or(0);
+        let mut table = vec![(0, 0); 1 << max_len];
+        for (symbol, &(code, len)) in codes.iter().enumerate() {
+            let shift = max_len - u32::from(len);
+            let start = usize::from(code) << shift;
+            for entry in &mut table[start..start + (1 << shift)] {
+                *entry = (len, symbol as u8);
+            }
+        }
+        Self { max_len, table }
+    }
+
+    fn decode(&self, reader: &mut BitReader<'_>) -> Result<usize, Error> {
+        let (len, symbol) = self.table[reader.peek(self.max_len) as usize];
+        if len == 0 {
+            return Err("Invalid H.263 variable-length code".into());
+        }
+        reader.skip(len.into())?;
+        Ok(symbol.into())
+    }
+}
+
+struct VlcTables {
+    mcbpc_intra: Vlc,
+    mcbpc_inter: Vlc,
+    cbpy: Vlc,
+    mvd: Vlc,
+    tcoef: Vlc,
+}
+
+impl VlcTables {
+    fn new() -> Self {
+        let tcoef: Vec<_> = TCOEF
+            .iter()
+            .map(|&(code, len, _, _, _)| (code, len))
+            .chain(std::iter::once(TCOEF_ESCAPE_CODE))
+            .collect();
+        Self {
+            mcbpc_intra: Vlc::new(&MCBPC_INTRA),
+            mcbpc_inter: Vlc::new(&MCBPC_INTER),
+            cbpy: Vlc::new(&CBPY),
+            mvd: Vlc::new(&MVD),
+            tcoef: Vlc::new(&tcoef),
+        }
+    }
+}
+
+/// MCBPC codes for intra pictures, indexed by `dquant << 2 | cbpc`.
+const MCBPC_INTRA: [(u16, u8); 9] = [
+    (0b1, 1),
+    (0b001, 3),
+    (0b010, 3),
+    (0b011, 3),
+    (0b0001, 4),
+    (0b000001, 6),
+    (0b000010, 6),
+    (0b000011, 6),
+    (0b000000001, 9),
+];
+const MCBPC_INTRA_STUFFING: usize = 8;
+
+/// MCBPC codes for inter pictures, indexed by
+/// `inter4v << 4 | dquant << 3 | intra << 2 | cbpc`.
+const MCBPC_INTER: [(u16, u8); 21] = [
+    (0b1, 1),
+    (0b0011, 4),
+    (0b0010, 4),
+    (0b000101, 6),
+    (0b00011, 5),
+    (0b00000100, 8),
+    (0b00000011, 8),
+    (0b0000011, 7),
+    (0b011, 3),
+    (0b0000111, 7),
+    (0b0000110, 7),
+    (0b000000101, 9),
+    (0b000100, 6),
+    (0b000000100, 9),
+    (0b000000011, 9),
+    (0b000000010, 9),
+    (0b010, 3),
+    (0b0000101, 7),
+    (0b0000100, 7),
+    (0b00000101, 8),
+    (0b000000001, 9),
+];
+const MCBPC_INTER_STUFFING: usize = 20;
+
+/// CBPY codes, indexed by the coded block pattern of an intra macroblock.
+const CBPY: [(u16, u8); 16] = [
+    (0b0011, 4),
+    (0b00101, 5),
+    (0b00100, 5),
+    (0b1001, 4),
+    (0b00011, 5),
+    (0b0111, 4),
+    (0b000010, 6),
+    (0b1011, 4),
+    (0b00010, 5),
+    (0b000011, 6),
+    (0b0101, 4),
+    (0b1010, 4),
+    (0b0100, 4),
+    (0b1000, 4),
+    (0b0110, 4),
+    (0b11, 2),
+];
+
+/// Motion vector difference codes, indexed by magnitude in half-pixels.
+/// Every non-zero magnitude is followed by a sign bit.
+const MVD: [(u16, u8); 33] = [
+    (0b1, 1),
+    (0b01, 2),
+    (0b001, 3),
+    (0b0001, 4),
+    (0b000011, 6),
+    (0b0000101, 7),
+    (0b0000100, 7),
+    (0b0000011, 7),
+    (0b000001011, 9),
+    (0b000001010, 9),
+    (0b000001001, 9),
+    (0b0000010001, 10),
+    (0b0000010000, 10),
+    (0b0000001111, 10),
+    (0b0000001110, 10),
+    (0b0000001101, 10),
+    (0b0000001100, 10),
+    (0b0000001011, 10),
+    (0b0000001010, 10),
+    (0b0000001001, 10),
+    (0b0000001000, 10),
+    (0b0000000111, 10),
+    (0b0000000110, 10),
+    (0b0000000101, 10),
+    (0b0000000100, 10),
+    (0b00000000111, 11),
+    (0b00000000110, 11),
+    (0b00000000101, 11),
+    (0b00000000100, 11),
+    (0b00000000011, 11),
+    (0b00000000010, 11),
+    (0b000000000011, 12),
+    (0b000000000010, 12),
+];
+
+/// Transform coefficient codes as `(code, length, last, run, level)`.
+/// Every code is followed by a sign bit.
+const TCOEF: [(u16, u8, bool, u8, u8); 102] = [
+    (0x2, 2, false, 0, 1),
+    (0xf, 4, false, 0, 2),
+    (0x15, 6, false, 0, 3),
+    (0x17, 7, false, 0, 4),
+    (0x1f, 8, false, 0, 5),
+    (0x25, 9, false, 0, 6),
+    (0x24, 9, false, 0, 7),
+    (0x21, 10, false, 0, 8),
+    (0x20, 10, false, 0, 9),
+    (0x7, 11, false, 0, 10),
+    (0x6, 11, false, 0, 11),
+    (0x20, 11, false, 0, 12),
+    (0x6, 3, false, 1, 1),
+    (0x14, 6, false, 1, 2),
+    (0x1e, 8, false, 1, 3),
+    (0xf, 10, false, 1, 4),
+    (0x21, 11, false, 1, 5),
+    (0x50, 12, false, 1, 6),
+    (0xe, 4, false, 2, 1),
+    (0x1d, 8, false, 2, 2),
+    (0xe, 10, false, 2, 3),
+    (0x51, 12, false, 2, 4),
+    (0xd, 5, false, 3, 1),
+    (0x23, 9, false, 3, 2),
+    (0xd, 10, false, 3, 3),
+    (0xc, 5, false, 4, 1),
+    (0x22, 9, false, 4, 2),
+    (0x52, 12, false, 4, 3),
+    (0xb, 5, false, 5, 1),
+    (0xc, 10, false, 5, 2),
+    (0x53, 12, false, 5, 3),
+    (0x13, 6, false, 6, 1),
+    (0xb, 10, false, 6, 2),
+    (0x54, 12, false, 6, 3),
+    (0x12, 6, false, 7, 1),
+    (0xa, 10, false, 7, 2),
+    (0x11, 6, false, 8, 1),
+    (0x9, 10, false, 8, 2),
+    (0x10, 6, false, 9, 1),
+    (0x8, 10, false, 9, 2),
+    (0x16, 7, false, 10, 1),
+    (0x55, 12, false, 10, 2),
+    (0x15, 7, false, 11, 1),
+    (0x14, 7, false, 12, 1),
+    (0x1c, 8, false, 13, 1),
+    (0x1b, 8, false, 14, 1),
+    (0x21, 9, false, 15, 1),
+    (0x20, 9, false, 16, 1),
+    (0x1f, 9, false, 17, 1),
+    (0x1e, 9, false, 18, 1),
+    (0x1d, 9, false, 19, 1),
+    (0x1c, 9, false, 20, 1),
+    (0x1b, 9, false, 21, 1),
+    (0x1a, 9, false, 22, 1),
+    (0x22, 11, false, 23, 1),
+    (0x23, 11, false, 24, 1),
+    (0x56, 12, false, 25, 1),
+    (0x57, 12, false, 26, 1),
+    (0x7, 4, true, 0, 1),
+    (0x19, 9, true, 0, 2),
+    (0x5, 11, true, 0, 3),
+    (0xf, 6, true, 1, 1),
+    (0x4, 11, true, 1, 2),
+    (0xe, 6, true, 2, 1),
+    (0xd, 6, true, 3, 1),
+    (0xc, 6, true, 4, 1),
+    (0x13, 7, true, 5, 1),
+    (0x12, 7, true, 6, 1),
+    (0x11, 7, true, 7, 1),
+    (0x10, 7, true, 8, 1),
+    (0x1a, 8, true, 9, 1),
+    (0x19, 8, true, 10, 1),
+    (0x18, 8, true, 11, 1),
+    (0x17, 8, true, 12, 1),
+    (0x16, 8, true, 13, 1),
+    (0x15, 8, true, 14, 1),
+    (0x14, 8, true, 15, 1),
+    (0x13, 8, true, 16, 1),
+    (0x18, 9, true, 17, 1),
+    (0x17, 9, true, 18, 1),
+    (0x16, 9, true, 19, 1),
+    (0x15, 9, true, 20, 1),
+    (0x14, 9, true, 21, 1),
+    (0x13, 9, true, 22, 1),
+    (0x12, 9, true, 23, 1),
+    (0x11, 9, true, 24, 1),
+    (0x7, 10, true, 25, 1),
+    (0x6, 10, true, 26, 1),
+    (0x5, 10, true, 27, 1),
+    (0x4, 10, true, 28, 1),
+    (0x24, 11, true, 29, 1),
+    (0x25, 11, true, 30, 1),
+    (0x26, 11, true, 31, 1),
+    (0x27, 11, true, 32, 1),
+    (0x58, 12, true, 33, 1),
+    (0x59, 12, true, 34, 1),
+    (0x5a, 12, true, 35, 1),
+    (0x5b, 12, true, 36, 1),
+    (0x5c, 12, true, 37, 1),
+    (0x5d, 12, true, 38, 1),
+    (0x5e, 12, true, 39, 1),
+    (0x5f, 12, true, 40, 1),
+];
+const TCOEF_ESCAPE: usize = 102;
+const TCOEF_ESCAPE_CODE: (u16, u8) = (0b0000011, 7);
+
+/// Maps zigzag scan order to raster order.
+const ZIGZAG: [usize; 64] = [
+    0, 1, 8, 16, 9, 2, 3, 10, 17, 24, 32, 25, 18, 11, 4, 5, 12, 19, 26, 33, 40, 48, 41, 34, 27, 20,
+    13, 6, 7, 14, 21, 28, 35, 42, 49, 56, 57, 50, 43, 36, 29, 22, 15, 23, 30, 37, 44, 51, 58, 59,
+    52, 45, 38, 31, 39, 46, 53, 60, 61, 54, 47, 55, 62, 63,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::VideoCodec;
+
+    #[derive(Default)]
+    struct BitWriter {
+        data: Vec<u8>,
+        num_bits: usize,
+    }
+
+    impl BitWriter {
+        fn write(&mut self, value: u32, num_bits: u32) {
+            for i in (0..num_bits).rev() {
+                if self.num_bits % 8 == 0 {
+                    self.data.push(0);
+                }
+                let bit = ((value >> i) & 1) as u8;
+                *self.data.last_mut().unwrap() |= bit << (7 - self.num_bits % 8);
+                self.num_bits += 1;
+            }
+        }
+
+        fn write_code(&mut self, (code, len): (u16, u8)) {
+            self.write(code.into(), len.into());
+        }
+
+        fn write_header(&mut self, picture_type: u32, width: u32, height: u32, quantizer: u32) {
+            self.write(1, 17);
+            self.write(0, 5);
+            self.write(0, 8);
+            self.write(0, 3);
+            self.write(width, 8);
+            self.write(height, 8);
+            self.write(picture_type, 2);
+            self.write(0, 1);
+            self.write(quantizer, 5);
+            self.write(0, 1);
+        }
+    }
+
+    fn frame(data: &[u8], frame_id: u32) -> EncodedFrame<'_> {
+        EncodedFrame {
+            codec: VideoCodec::H263,
+            data,
+            frame_id,
+        }
+    }
+
+    fn pixel(frame: &DecodedFrame, x: usize, y: usize) -> [u8; 3] {
+        let i = (y * usize::from(frame.width) + x) * 4;
+        [frame.rgba[i], frame.rgba[i + 1], frame.rgba[i + 2]]
+    }
+
+    /// A 16x16 keyframe with a single flat macroblock of the given luma.
+    fn flat_keyframe(luma: u32) -> Vec<u8> {
+        let mut writer = BitWriter::default();
+        writer.write_header(0, 16, 16, 2);
+        writer.write_code(MCBPC_INTRA[0]);
+        writer.write_code(CBPY[0]);
+        for _ in 0..4 {
+            writer.write(luma, 8);
+        }
+        writer.write(255, 8);
+        writer.write(255, 8);
+        writer.data
+    }
+
+    #[test]
+    fn vlc_tables_decode_every_code() {
+        let tables = VlcTables::new();
+        let cases: [(&Vlc, &[(u16, u8)]); 4] = [
+            (&tables.mcbpc_intra, &MCBPC_INTRA),
+            (&tables.mcbpc_inter, &MCBPC_INTER),
+            (&tables.cbpy, &CBPY),
+            (&tables.mvd, &MVD),
+        ];
+        for (vlc, codes) in cases.iter() {
+            for (symbol, &code) in codes.iter().enumerate() {
+                let mut writer = BitWriter::default();
+                writer.write_code(code);
+                let mut reader = BitReader::new(&writer.data);
+                assert_eq!(vlc.decode(&mut reader).unwrap(), symbol);
+            }
+        }
+        for (symbol, &(code, len, _, _, _)) in TCOEF.iter().enumerate() {
+            let mut writer = BitWriter::default();
+            writer.write_code((code, len));
+            let mut reader = BitReader::new(&writer.data);
+            assert_eq!(tables.tcoef.decode(&mut reader).unwrap(), symbol);
+        }
+    }
+
+    #[test]
+    fn decode_intra_and_skipped_frames() {
+        let mut decoder = H263Decoder::new();
+        let keyframe = flat_keyframe(100);
+        assert_eq!(
+            decoder.preload_frame(frame(&keyframe, 0)).unwrap(),
+            FrameDependency::None
+        );
+        let decoded = decoder.decode_frame(frame(&keyframe, 0)).unwrap();
+        let gray = yuv_to_rgb(100, 128, 128);
+        assert_eq!((decoded.width, decoded.height), (16, 16));
+        assert!(decoded
+            .rgba
+            .chunks_exact(4)
+            .all(|p| p == [gray[0], gray[1], gray[2], 255]));
+
+        // An inter frame where the only macroblock is not coded.
+        let mut writer = BitWriter::default();
+        writer.write_header(1, 16, 16, 2);
+        writer.write(1, 1);
+        assert_eq!(
+            decoder.preload_frame(frame(&writer.data, 1)).unwrap(),
+            FrameDependency::Past
+        );
+        let decoded = decoder.decode_frame(frame(&writer.data, 1)).unwrap();
+        assert_eq!(pixel(&decoded, 15, 15), gray);
+    }
+
+    #[test]
+    fn decode_inter_residual() {
+        let mut decoder = H263Decoder::new();
+        decoder.decode_frame(frame(&flat_keyframe(100), 0)).unwrap();
+
+        // Add a flat residual to the top-left luma block only.
+        let mut writer = BitWriter::default();
+        writer.write_header(1, 16, 16, 2);
+        writer.write(0, 1);
+        writer.write_code(MCBPC_INTER[0]);
+        writer.write_code(CBPY[0b1000 ^ 0xf]);
+        writer.write_code(MVD[0]);
+        writer.write_code(MVD[0]);
+        writer.write_code(TCOEF_ESCAPE_CODE);
+        writer.write(1, 1);
+        writer.write(0, 6);
+        writer.write(40, 8);
+
+        let decoded = decoder.decode_frame(frame(&writer.data, 1)).unwrap();
+        // 40 dequantizes to 161, which is a flat offset of 20 after the IDCT.
+        assert_eq!(pixel(&decoded, 0, 0), yuv_to_rgb(120, 128, 128));
+        assert_eq!(pixel(&decoded, 7, 7), yuv_to_rgb(120, 128, 128));
+        assert_eq!(pixel(&decoded, 8, 0), yuv_to_rgb(100, 128, 128));
+    }
+
+    #[test]
+    fn inter_frame_without_keyframe_is_an_error() {
+        let mut writer = BitWriter::default();
+        writer.write_header(1, 16, 16, 2);
+        writer.write(1, 1);
+        let mut decoder = H263Decoder::new();
+        assert!(decoder.decode_frame(frame(&writer.data, 0)).is_err());
+    }
+
+    #[test]
+    fn motion_vector_prediction() {
+        let vectors = [(2, 0), (4, 0), (-6, 2), (0, 0), (8, 8), (0, 0)];
+        // First row only predicts from the left.
+        assert_eq!(predict_motion_vector(&vectors, 3, 0, 0), (0, 0));
+        assert_eq!(predict_motion_vector(&vectors, 3, 2, 0), (4, 0));
+        // Median of left (0,0), above (2,0) and above-right (4,0).
+        assert_eq!(predict_motion_vector(&vectors, 3, 0, 1), (2, 0));
+        // The above-right neighbor is outside the picture on the last column.
+        assert_eq!(predict_motion_vector(&vectors, 3, 2, 1), (0, 2));
+    }
+}
@@ -0,0 +1,224 @@
+//! Screen Video (codec 3) decoder.
+//!
+//! Screen Video splits the image into a grid of blocks, each of which is
+//! either a zlib-compressed chunk of BGR pixels, or empty when the block is
+//! unchanged from the previous frame. Both the block grid and the rows inside
+//! each block are stored bottom-up.
+
+use crate::backend::video::decoders::VideoDecoder;
+use crate::backend::video::{DecodedFrame, EncodedFrame, Error, FrameDependency};
+use std::io::Read;
+
+pub struct ScreenVideoDecoder {
+    /// The most recently decoded frame, used as the base for inter frames.
+    last_frame: Option<DecodedFrame>,
+}
+
+/// The header at the start of each Screen Video frame.
+struct ScreenVideoHeader {
+    block_width: usize,
+    block_height: usize,
+    image_width: usize,
+    image_height: usize,
+}
+
+impl ScreenVideoHeader {
+    fn read(data: &[u8]) -> Result<Self, Error> {
+        if data.len() < 4 {
+            return Err("Screen Video frame is missing its header".into());
+        }
+
+        let block_width = (usize::from(data[0] >> 4) + 1) * 16;
+        let image_width = usize::from(u16::from_be_bytes([data[0], data[1]]) & 0xfff);
+        let block_height = (usize::from(data[2] >> 4) + 1) * 16;
+        let image_height = usize::from(u16::from_be_bytes([data[2], data[3]]) & 0xfff);
+
+        Ok(Self {
+            block_width,
+            block_height,
+            image_width,
+            image_height,
+        })
+    }
+
+    fn num_blocks(&self) -> usize {
+        let cols = (self.image_width + self.block_width - 1) / self.block_width;
+        let rows = (self.image_height + self.block_height - 1) / self.block_height;
+        cols * rows
+    }
+}
+
+/// Iterates over the compressed block data of a frame, in bitstream order.
+/// Unchanged blocks yield an empty slice.
+fn blocks<'a>(
+    header: &ScreenVideoHeader,
+    mut data: &'a [u8],
+) -> impl Iterator<Item = Result<&'a [u8], Error>> {
+    (0..header.num_blocks()).map(move |_| {
+        if data.len() < 2 {
+            return Err("Screen Video frame is truncated".into());
+        }
+        let size = usize::from(u16::from_be_bytes([data[0], data[1]]));
+        if data.len() < 2 + size {
+            return Err("Screen Video block is truncated".into());
+        }
+        let block = &data[2..2 + size];
+        data = &data[2 + size..];
+        Ok(block)
+    })
+}
+
+impl ScreenVideoDecoder {
+    pub fn new(_size: (u16, u16)) -> Self {
+        Self { last_frame: None }
+    }
+}
+
+impl VideoDecoder for ScreenVideoDecoder {
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
+        let data = encoded_frame.data();
+        let header = ScreenVideoHeader::read(data)?;
+        for block in blocks(&header, &data[4..]) {
+            if block?.is_empty() {
+                return Ok(FrameDependency::Past);
+            }
+        }
+
+        Ok(FrameDependency::None)
+    }
+
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+        let data = encoded_frame.data();
+        let header = ScreenVideoHeader::read(data)?;
+        let (width, height) = (header.image_width, header.image_height);
+
+        let mut frame = match self.last_frame.take() {
+            Some(frame)
+                if usize::from(frame.width) == width && usize::from(frame.height) == height =>
+            {
+                frame
+            }
+            _ => DecodedFrame {
+                width: width as u16,
+                height: height as u16,
+                rgba: vec![0; width * height * 4],
+            },
+        };
+
+        let cols = (width + header.block_width - 1) / header.block_width;
+        let mut pixels = Vec::with_capacity(header.block_width * header.block_height * 3);
+        for (i, block) in blocks(&header, &data[4..]).enumerate() {
+            let block = block?;
+            if block.is_empty() {
+                // Unchanged from the previous frame.
+                continue;
+            }
+
+            // Blocks are ordered left-to-right, bottom-to-top.
+            let x_min = (i % cols) * header.block_width;
+            let y_min = (i / cols) * header.block_height;
+            let block_width = header.block_width.min(width - x_min);
+            let block_height = header.block_height.min(height - y_min);
+
+            pixels.clear();
+            flate2::bufread::ZlibDecoder::new(block).read_to_end(&mut pixels)?;
+            if pixels.len() < block_width * block_height * 3 {
+                return Err("Screen Video block has too little pixel data".into());
+            }
+
+            for (row, bgr_row) in pixels
+                .chunks_exact(block_width * 3)
+                .take(block_height)
+                .enumerate()
+            {
+                let y = height - 1 - (y_min + row);
+                let start = (y * width + x_min) * 4;
+                let out_row = &mut frame.rgba[start..start + block_width * 4];
+                for (out, bgr) in out_row.chunks_exact_mut(4).zip(bgr_row.chunks_exact(3)) {
+                    out.copy_from_slice(&[bgr[2], bgr[1], bgr[0], 255]);
+                }
+            }
+        }
+
+        self.last_frame = Some(frame.clone());
+        Ok(frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+    use swf::VideoCodec;
+
+    /// Encodes a frame with 16x16 blocks. `blocks` holds the BGR data of each
+    /// block in bitstream order, or `None` for an unchanged block.
+    fn encode_frame(width: u16, height: u16, blocks: &[Option<Vec<u8>>]) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        for block in blocks {
+            if let Some(block) = block {
+                let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+                encoder.write_all(block).unwrap();
+                let compressed = encoder.finish().unwrap();
+                data.extend_from_slice(&(compressed.len() as u16).to_be_bytes());
+                data.extend_from_slice(&compressed);
+            } else {
+                data.extend_from_slice(&[0, 0]);
+            }
+        }
+        data
+    }
+
+    fn frame(data: &[u8], frame_id: u32) -> EncodedFrame<'_> {
+        EncodedFrame {
+            codec: VideoCodec::ScreenVideo,
+            data,
+            frame_id,
+        }
+    }
+
+    #[test]
+    fn decode_keyframe_and_inter_frame() {
+        // A 20x16 image is two blocks wide; the second block is only 4 pixels wide.
+        let blue = [255, 0, 0].repeat(16 * 16);
+        let red = [0, 0, 255].repeat(4 * 16);
+        let keyframe = encode_frame(20, 16, &[Some(blue), Some(red)]);
+
+        let mut decoder = ScreenVideoDecoder::new((20, 16));
+        assert_eq!(
+            decoder.preload_frame(frame(&keyframe, 0)).unwrap(),
+            FrameDependency::None
+        );
+        let decoded = decoder.decode_frame(frame(&keyframe, 0)).unwrap();
+        assert_eq!((decoded.width, decoded.height), (20, 16));
+        assert_eq!(&decoded.rgba[0..4], &[0, 0, 255, 255]);
+        assert_eq!(&decoded.rgba[16 * 4..17 * 4], &[255, 0, 0, 255]);
+
+        // Only the second block changes; its bottom row becomes green.
+        let mut green_bottom = [0, 0, 255].repeat(4 * 16);
+        green_bottom[..12].copy_from_slice(&[0, 255, 0].repeat(4));
+        let inter_frame = encode_frame(20, 16, &[None, Some(green_bottom)]);
+        assert_eq!(
+            decoder.preload_frame(frame(&inter_frame, 1)).unwrap(),
+            FrameDependency::Past
+        );
+        let decoded = decoder.decode_frame(frame(&inter_frame, 1)).unwrap();
+        assert_eq!(&decoded.rgba[0..4], &[0, 0, 255, 255]);
+        assert_eq!(&decoded.rgba[16 * 4..17 * 4], &[255, 0, 0, 255]);
+        let bottom_row = 15 * 20 * 4;
+        assert_eq!(
+            &decoded.rgba[bottom_row + 16 * 4..bottom_row + 17 * 4],
+            &[0, 255, 0, 255]
+        );
+    }
+
+    #[test]
+    fn truncated_frame_is_an_error() {
+        let mut decoder = ScreenVideoDecoder::new((16, 16));
+        assert!(decoder.decode_frame(frame(&[0, 16, 0, 16, 1], 0)).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+//! Pure software video decoding backend.
+
+use crate::backend::render::{BitmapHandle, BitmapInfo, RenderBackend};
+use crate::backend::video::decoders::{make_decoder, VideoDecoder};
+use crate::backend::video::{
+    EncodedFrame, Error, FrameDependency, VideoBackend, VideoStreamHandle,
+};
+use generational_arena::Arena;
+use swf::{VideoCodec, VideoDeblocking};
+
+/// A video backend that decodes frames on the CPU using Ruffle's own
+/// decoders and uploads the result to the renderer as a bitmap.
+pub struct SoftwareVideoBackend {
+    streams: Arena<VideoStream>,
+}
+
+impl SoftwareVideoBackend {
+    pub fn new() -> Self {
+        Self {
+            streams: Arena::new(),
+        }
+    }
+}
+
+impl Default for SoftwareVideoBackend {
+    fn default() -> Self {
+        SoftwareVideoBackend::new()
+    }
+}
+
+impl VideoBackend for SoftwareVideoBackend {
+    fn register_video_stream(
+        &mut self,
+        _num_frames: u32,
+        size: (u16, u16),
+        codec: VideoCodec,
+        _filter: VideoDeblocking,
+    ) -> Result<VideoStreamHandle, Error> {
+        let decoder = make_decoder(codec, size)?;
+        let stream = VideoStream::new(decoder);
+        let stream_handle = self.streams.insert(stream);
+        Ok(stream_handle)
+    }
+
+    fn preload_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        encoded_frame: EncodedFrame<'_>,
+    ) -> Result<FrameDependency, Error> {
+        let stream = self
+            .streams
+            .get_mut(stream)
+            .ok_or("Unregistered video stream")?;
+
+        stream.decoder.preload_frame(encoded_frame)
+    }
+
+    fn decode_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        encoded_frame: EncodedFrame<'_>,
+        renderer: &mut dyn RenderBackend,
+    ) -> Result<BitmapInfo, Error> {
+        let stream = self
+            .streams
+            .get_mut(stream)
+            .ok_or("Unregistered video stream")?;
+
+        let frame = stream.decoder.decode_frame(encoded_frame)?;
+        let (width, height) = (frame.width, frame.height);
+        let handle = if let Some(bitmap) = stream.bitmap {
+            renderer.update_texture(bitmap, width.into(), height.into(), frame.rgba)?
+        } else {
+            renderer.register_bitmap_raw(width.into(), height.into(), frame.rgba)?
+        };
+        stream.bitmap = Some(handle);
+
+        Ok(BitmapInfo {
+            handle,
+            width,
+            height,
+        })
+    }
+}
+
+/// A single preloaded video stream.
+struct VideoStream {
+    /// The bitmap the most recently decoded frame was uploaded to.
+    bitmap: Option<BitmapHandle>,
+
+    /// The decoder for this stream's codec.
+    decoder: Box<dyn VideoDecoder>,
+}
+
+impl VideoStream {
+    fn new(decoder: Box<dyn VideoDecoder>) -> Self {
+        Self {
+            bitmap: None,
+            decoder,
+        }
+    }
+}
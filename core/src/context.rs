@@ -2,13 +2,23 @@
 use crate::avm1;
 
 use crate::avm1::globals::system::SystemProperties;
-use crate::avm1::{Avm1, Object as Avm1Object, Timers, Value as Avm1Value};
+use crate::avm1::{
+    Avm1, LocalConnections, Object as Avm1Object, Timers, Value as Avm1Value, XmlSockets,
+};
 use crate::avm2::{Avm2, Object as Avm2Object, Value as Avm2Value};
 use crate::backend::input::InputBackend;
+use crate::backend::local_connection::LocalConnectionBackend;
 use crate::backend::locale::LocaleBackend;
 use crate::backend::log::LogBackend;
+use crate::backend::socket::SocketBackend;
 use crate::backend::storage::StorageBackend;
-use crate::backend::{audio::AudioBackend, navigator::NavigatorBackend, render::RenderBackend};
+use crate::backend::video::VideoBackend;
+use crate::backend::{
+    audio::{AudioBackend, AudioManager},
+    navigator::NavigatorBackend,
+    render::RenderBackend,
+};
+use crate::context_menu::ContextMenuState;
 use crate::display_object::EditText;
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
@@ -16,6 +26,7 @@ use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::player::Player;
 use crate::prelude::*;
+use crate::security::ScriptPermissions;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::transform::TransformStack;
 use core::fmt;
@@ -61,6 +72,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The audio backend, used by display objects and AVM to play audio.
     pub audio: &'a mut (dyn AudioBackend + 'a),
 
+    /// The audio manager, tracking all playing sounds and their owners.
+    pub audio_manager: &'a mut AudioManager<'gc>,
+
     /// The navigator backend, used by the AVM to make HTTP requests and visit webpages.
     pub navigator: &'a mut (dyn NavigatorBackend + 'a),
 
@@ -79,6 +93,15 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The logging backend, used for trace output capturing
     pub log: &'a mut dyn LogBackend,
 
+    /// The video backend, used for video decoding
+    pub video: &'a mut dyn VideoBackend,
+
+    /// The socket backend, used for `XMLSocket` connections
+    pub socket: &'a mut dyn SocketBackend,
+
+    /// The transport used by `LocalConnection` to talk to this and other players
+    pub local_connection: &'a mut dyn LocalConnectionBackend,
+
     /// The RNG, used by the AVM `RandomNumber` opcode,  `Math.random(),` and `random()`.
     pub rng: &'a mut SmallRng,
 
@@ -116,6 +139,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The system properties
     pub system: &'a mut SystemProperties,
 
+    /// The domains that each movie has allowed to script it.
+    pub script_permissions: &'a mut ScriptPermissions,
+
     /// The current instance ID. Used to generate default `instanceN` names.
     pub instance_counter: &'a mut i32,
 
@@ -128,6 +154,12 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// Timed callbacks created with `setInterval`/`setTimeout`.
     pub timers: &'a mut Timers<'gc>,
 
+    /// Open connections created with `XMLSocket`.
+    pub xml_sockets: &'a mut XmlSockets<'gc>,
+
+    /// `LocalConnection` objects that are listening or waiting for a message status.
+    pub local_connections: &'a mut LocalConnections<'gc>,
+
     /// The AVM1 global state.
     pub avm1: &'a mut Avm1<'gc>,
 
@@ -146,6 +178,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
 
     /// A tracker for the current keyboard focused element
     pub focus_tracker: FocusTracker<'gc>,
+
+    /// The context menu most recently shown by the host, if any.
+    pub current_context_menu: &'a mut Option<ContextMenuState<'gc>>,
 }
 
 unsafe impl<'a, 'gc, 'gc_context> Collect for UpdateContext<'a, 'gc, 'gc_context> {
@@ -157,6 +192,7 @@ unsafe impl<'a, 'gc, 'gc_context> Collect for UpdateContext<'a, 'gc, 'gc_context
         self.needs_render.trace(cc);
         self.swf.trace(cc);
         self.audio.trace(cc);
+        self.audio_manager.trace(cc);
         self.navigator.trace(cc);
         self.renderer.trace(cc);
         self.input.trace(cc);
@@ -173,9 +209,12 @@ unsafe impl<'a, 'gc, 'gc_context> Collect for UpdateContext<'a, 'gc, 'gc_context
         self.shared_objects.trace(cc);
         self.unbound_text_fields.trace(cc);
         self.timers.trace(cc);
+        self.xml_sockets.trace(cc);
+        self.local_connections.trace(cc);
         self.avm1.trace(cc);
         self.avm2.trace(cc);
         self.focus_tracker.trace(cc);
+        self.current_context_menu.trace(cc);
     }
 }
 
@@ -200,10 +239,14 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             needs_render: self.needs_render,
             swf: self.swf,
             audio: self.audio,
+            audio_manager: self.audio_manager,
             navigator: self.navigator,
             renderer: self.renderer,
             locale: self.locale,
             log: self.log,
+            video: self.video,
+            socket: self.socket,
+            local_connection: self.local_connection,
             input: self.input,
             storage: self.storage,
             rng: self.rng,
@@ -216,16 +259,20 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             player: self.player.clone(),
             load_manager: self.load_manager,
             system: self.system,
+            script_permissions: self.script_permissions,
             instance_counter: self.instance_counter,
             shared_objects: self.shared_objects,
             unbound_text_fields: self.unbound_text_fields,
             timers: self.timers,
+            xml_sockets: self.xml_sockets,
+            local_connections: self.local_connections,
             avm1: self.avm1,
             avm2: self.avm2,
             external_interface: self.external_interface,
             update_start: self.update_start,
             max_execution_duration: self.max_execution_duration,
             focus_tracker: self.focus_tracker,
+            current_context_menu: self.current_context_menu,
         }
     }
 }
@@ -0,0 +1,273 @@
+//! Context menu shown by the host when the stage is right-clicked.
+//!
+//! The player builds the menu for a point on the stage, the host shows it
+//! however it likes, and then tells the player which item was chosen.
+
+use crate::avm1::Object as Avm1Object;
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
+use crate::prelude::*;
+use gc_arena::Collect;
+
+/// The menu most recently built for the host, along with what each of its
+/// items does.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct ContextMenuState<'gc> {
+    info: Vec<ContextMenuItem>,
+    callbacks: Vec<ContextMenuCallback<'gc>>,
+}
+
+impl<'gc> ContextMenuState<'gc> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add an item to the end of the menu.
+    pub fn push(&mut self, item: ContextMenuItem, callback: ContextMenuCallback<'gc>) {
+        self.info.push(item);
+        self.callbacks.push(callback);
+    }
+
+    /// The items of the menu, in the order they should be shown.
+    pub fn info(&self) -> &[ContextMenuItem] {
+        &self.info
+    }
+
+    /// The action of the item at the given index.
+    pub fn callback(&self, index: usize) -> Option<ContextMenuCallback<'gc>> {
+        self.callbacks.get(index).copied()
+    }
+
+    /// Add a group of built-in items, separated from any items before it.
+    fn push_group(&mut self, items: Vec<(&str, bool, bool, ContextMenuCallback<'gc>)>) {
+        let mut separator_before = !self.info.is_empty();
+        for (caption, checked, enabled, callback) in items {
+            self.push(
+                ContextMenuItem {
+                    enabled,
+                    separator_before,
+                    checked,
+                    caption: caption.to_string(),
+                },
+                callback,
+            );
+            separator_before = false;
+        }
+    }
+
+    /// Add the built-in items that the movie allows, controlling the zoom of
+    /// the player and the root movie's playback.
+    ///
+    /// Playback items are only shown for movies with more than one frame. Each
+    /// group of built-in items is separated from the items before it.
+    pub fn build_builtin_items(
+        &mut self,
+        builtin_items: BuiltInItemFlags,
+        root: Option<MovieClip<'gc>>,
+        is_zoomed: bool,
+    ) {
+        if builtin_items.zoom {
+            self.push_group(vec![
+                ("Zoom In", false, true, ContextMenuCallback::Zoom(Zoom::In)),
+                (
+                    "Zoom Out",
+                    false,
+                    is_zoomed,
+                    ContextMenuCallback::Zoom(Zoom::Out),
+                ),
+                (
+                    "Show All",
+                    false,
+                    is_zoomed,
+                    ContextMenuCallback::Zoom(Zoom::ShowAll),
+                ),
+            ]);
+        }
+
+        let root = match root {
+            Some(root) if root.total_frames() > 1 => root,
+            _ => return,
+        };
+
+        let mut playback = Vec::new();
+        if builtin_items.play {
+            playback.push(("Play", root.playing(), true, ContextMenuCallback::Play));
+        }
+        if builtin_items.loop_ {
+            playback.push(("Loop", root.looping(), true, ContextMenuCallback::Loop));
+        }
+        self.push_group(playback);
+
+        let mut navigation = Vec::new();
+        if builtin_items.rewind {
+            navigation.push((
+                "Rewind",
+                false,
+                root.current_frame() > 1,
+                ContextMenuCallback::Rewind,
+            ));
+        }
+        if builtin_items.forward_and_back {
+            navigation.push((
+                "Forward",
+                false,
+                root.current_frame() < root.total_frames(),
+                ContextMenuCallback::Forward,
+            ));
+            navigation.push((
+                "Back",
+                false,
+                root.current_frame() > 1,
+                ContextMenuCallback::Back,
+            ));
+        }
+        self.push_group(navigation);
+    }
+}
+
+/// Which of the built-in items a movie allows in its context menu, as set by
+/// `ContextMenu.builtInItems`.
+#[derive(Clone, Copy, Debug)]
+pub struct BuiltInItemFlags {
+    pub zoom: bool,
+    pub play: bool,
+    pub loop_: bool,
+    pub rewind: bool,
+    pub forward_and_back: bool,
+}
+
+impl Default for BuiltInItemFlags {
+    fn default() -> Self {
+        Self {
+            zoom: true,
+            play: true,
+            loop_: true,
+            rewind: true,
+            forward_and_back: true,
+        }
+    }
+}
+
+/// An item of the context menu, as shown by the host.
+#[derive(Clone, Debug, PartialEq, Collect)]
+#[collect(require_static)]
+pub struct ContextMenuItem {
+    /// Whether the item can be chosen. Disabled items should be greyed out.
+    pub enabled: bool,
+
+    /// Whether a separator should be shown above the item.
+    pub separator_before: bool,
+
+    /// Whether the item should be shown with a check mark.
+    pub checked: bool,
+
+    /// The text of the item.
+    pub caption: String,
+}
+
+/// What happens when an item of the context menu is chosen.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub enum ContextMenuCallback<'gc> {
+    /// Toggle playback of the root movie.
+    Play,
+
+    /// Go to the first frame of the root movie and stop.
+    Rewind,
+
+    /// Go to the next frame of the root movie.
+    Forward,
+
+    /// Go to the previous frame of the root movie.
+    Back,
+
+    /// Toggle whether the root movie goes back to its first frame after its last.
+    Loop,
+
+    /// Change the zoom of the player. This is handled by the player itself.
+    Zoom(Zoom),
+
+    /// Call the `onSelect` handler of an AVM1 `ContextMenuItem`, passing it
+    /// the object that was right-clicked.
+    Avm1 {
+        target: DisplayObject<'gc>,
+        item: Avm1Object<'gc>,
+    },
+}
+
+/// Find the topmost visible display object under a point of the stage.
+///
+/// Unlike `mouse_pick`, this finds any object whose shape is under the point,
+/// whether or not it responds to the mouse.
+pub fn hit_test<'gc>(
+    object: DisplayObject<'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    point: (Twips, Twips),
+) -> Option<DisplayObject<'gc>> {
+    if !object.visible() {
+        return None;
+    }
+
+    // Masks are never hit, and children are tested from the top down.
+    let mut children: Vec<_> = object
+        .children()
+        .filter(|child| child.clip_depth() == 0)
+        .collect();
+    children.sort_by_key(|child| std::cmp::Reverse(child.depth()));
+    for child in children {
+        if let Some(hit) = hit_test(child, context, point) {
+            return Some(hit);
+        }
+    }
+
+    if object.hit_test_shape(context, point) {
+        Some(object)
+    } else {
+        None
+    }
+}
+
+/// A change to the zoom of the player, chosen from the context menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub enum Zoom {
+    /// Double the zoom, centered on the point that was right-clicked.
+    In,
+
+    /// Halve the zoom, centered on the point that was right-clicked.
+    Out,
+
+    /// Show the whole stage again.
+    ShowAll,
+}
+
+/// Run the action of a built-in item on the root movie.
+///
+/// Returns `false` for items that don't act on the root movie.
+pub fn run_builtin_callback<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    callback: ContextMenuCallback<'gc>,
+) -> bool {
+    let root = match context.levels.get(&0).and_then(|root| root.as_movie_clip()) {
+        Some(root) => root,
+        None => return false,
+    };
+
+    match callback {
+        ContextMenuCallback::Play => {
+            if root.playing() {
+                root.stop(context);
+            } else {
+                root.play(context);
+            }
+        }
+        ContextMenuCallback::Rewind => root.goto_frame(context, 1, true),
+        ContextMenuCallback::Forward => root.next_frame(context),
+        ContextMenuCallback::Back => root.prev_frame(context),
+        ContextMenuCallback::Loop => root.set_looping(context.gc_context, !root.looping()),
+        ContextMenuCallback::Zoom(_) | ContextMenuCallback::Avm1 { .. } => return false,
+    }
+
+    true
+}
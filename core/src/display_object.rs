@@ -1,6 +1,12 @@
 use crate::avm1::{Object as Avm1Object, TObject, Value as Avm1Value};
-use crate::avm2::Value as Avm2Value;
+use crate::avm2::{
+    root_error_handler as avm2_root_error_handler, Activation as Avm2Activation, Avm2,
+    Error as Avm2Error, Namespace as Avm2Namespace, Object as Avm2Object, QName as Avm2QName,
+    StageObject as Avm2StageObject, TObject as Avm2TObject, Value as Avm2Value,
+};
+use crate::backend::render::filters::Filter;
 use crate::context::{RenderContext, UpdateContext};
+use crate::drawing::Drawing;
 use crate::player::NEWEST_PLAYER_VERSION;
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
@@ -14,6 +20,7 @@ use std::cell::{Ref, RefMut};
 use std::cmp::min;
 use std::fmt::Debug;
 use std::sync::Arc;
+use swf::BlendMode;
 
 mod bitmap;
 mod button;
@@ -22,6 +29,7 @@ mod graphic;
 mod morph_shape;
 mod movie_clip;
 mod text;
+mod video;
 
 use crate::avm1::activation::Activation;
 use crate::backend::input::MouseCursor;
@@ -33,6 +41,7 @@ pub use graphic::Graphic;
 pub use morph_shape::{MorphShape, MorphShapeStatic};
 pub use movie_clip::{MovieClip, Scene};
 pub use text::Text;
+pub use video::Video;
 
 #[derive(Clone, Debug)]
 pub struct DisplayObjectBase<'gc> {
@@ -43,6 +52,24 @@ pub struct DisplayObjectBase<'gc> {
     name: String,
     clip_depth: Depth,
 
+    /// The bitmap filters applied to this display object when it is rendered.
+    filters: Vec<Filter>,
+
+    /// How this display object is blended with the content behind it.
+    blend_mode: BlendMode,
+
+    /// The position of this object in the tab order, set by `tabIndex`.
+    tab_index: Option<i32>,
+
+    /// Whether this object takes part in tab ordering, set by `tabEnabled`.
+    /// When unset, the object's type decides.
+    tab_enabled: Option<bool>,
+
+    /// Whether a yellow rectangle is drawn around this object when it has
+    /// keyboard focus, set by `_focusrect`.
+    /// When unset, the setting of the root movie is used.
+    focus_rect: Option<bool>,
+
     // Cached transform properties `_xscale`, `_yscale`, `_rotation`.
     // These are expensive to calculate, so they will be calculated and cached
     // when AS requests one of these properties.
@@ -78,6 +105,11 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             transform: Default::default(),
             name: Default::default(),
             clip_depth: Default::default(),
+            filters: Default::default(),
+            blend_mode: BlendMode::Normal,
+            tab_index: None,
+            tab_enabled: None,
+            focus_rect: None,
             rotation: Degrees::from_radians(0.0),
             scale_x: Percent::from_unit(1.0),
             scale_y: Percent::from_unit(1.0),
@@ -85,7 +117,11 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             first_child: None,
             prev_sibling: None,
             next_sibling: None,
-            flags: DisplayObjectFlags::Visible.into(),
+            flags: DisplayObjectFlags::Visible
+                | DisplayObjectFlags::TabChildren
+                | DisplayObjectFlags::MouseEnabled
+                | DisplayObjectFlags::MouseChildren
+                | DisplayObjectFlags::UseHandCursor,
         }
     }
 }
@@ -105,7 +141,10 @@ impl<'gc> DisplayObjectBase<'gc> {
     /// Reset all properties that would be adjusted by a movie load.
     fn reset_for_movie_load(&mut self) {
         self.first_child = None;
-        self.flags = DisplayObjectFlags::Visible.into();
+        self.flags = DisplayObjectFlags::Visible
+            | DisplayObjectFlags::MouseEnabled
+            | DisplayObjectFlags::MouseChildren
+            | DisplayObjectFlags::UseHandCursor;
     }
 
     fn id(&self) -> CharacterId {
@@ -283,6 +322,86 @@ impl<'gc> DisplayObjectBase<'gc> {
     fn set_clip_depth(&mut self, _context: MutationContext<'gc, '_>, depth: Depth) {
         self.clip_depth = depth;
     }
+    fn filters(&self) -> &[Filter] {
+        &self.filters
+    }
+    fn set_filters(&mut self, _context: MutationContext<'gc, '_>, filters: Vec<Filter>) {
+        self.filters = filters;
+    }
+    fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+    fn set_blend_mode(&mut self, _context: MutationContext<'gc, '_>, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+    fn tab_index(&self) -> Option<i32> {
+        self.tab_index
+    }
+    fn set_tab_index(&mut self, tab_index: Option<i32>) {
+        self.tab_index = tab_index;
+    }
+    fn tab_enabled(&self) -> Option<bool> {
+        self.tab_enabled
+    }
+    fn set_tab_enabled(&mut self, tab_enabled: Option<bool>) {
+        self.tab_enabled = tab_enabled;
+    }
+    fn tab_children(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::TabChildren)
+    }
+    fn set_tab_children(&mut self, value: bool) {
+        if value {
+            self.flags.insert(DisplayObjectFlags::TabChildren);
+        } else {
+            self.flags.remove(DisplayObjectFlags::TabChildren);
+        }
+    }
+    fn mouse_enabled(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::MouseEnabled)
+    }
+    fn set_mouse_enabled(&mut self, value: bool) {
+        if value {
+            self.flags.insert(DisplayObjectFlags::MouseEnabled);
+        } else {
+            self.flags.remove(DisplayObjectFlags::MouseEnabled);
+        }
+    }
+    fn mouse_children(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::MouseChildren)
+    }
+    fn set_mouse_children(&mut self, value: bool) {
+        if value {
+            self.flags.insert(DisplayObjectFlags::MouseChildren);
+        } else {
+            self.flags.remove(DisplayObjectFlags::MouseChildren);
+        }
+    }
+    fn button_mode(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::ButtonMode)
+    }
+    fn set_button_mode(&mut self, value: bool) {
+        if value {
+            self.flags.insert(DisplayObjectFlags::ButtonMode);
+        } else {
+            self.flags.remove(DisplayObjectFlags::ButtonMode);
+        }
+    }
+    fn use_hand_cursor(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::UseHandCursor)
+    }
+    fn set_use_hand_cursor(&mut self, value: bool) {
+        if value {
+            self.flags.insert(DisplayObjectFlags::UseHandCursor);
+        } else {
+            self.flags.remove(DisplayObjectFlags::UseHandCursor);
+        }
+    }
+    fn focus_rect(&self) -> Option<bool> {
+        self.focus_rect
+    }
+    fn set_focus_rect(&mut self, focus_rect: Option<bool>) {
+        self.focus_rect = focus_rect;
+    }
     fn parent(&self) -> Option<DisplayObject<'gc>> {
         self.parent
     }
@@ -380,6 +499,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         MorphShape(MorphShape<'gc>),
         MovieClip(MovieClip<'gc>),
         Text(Text<'gc>),
+        Video(Video<'gc>),
     }
 )]
 pub trait TDisplayObject<'gc>:
@@ -652,6 +772,105 @@ pub trait TDisplayObject<'gc>:
 
     fn clip_depth(&self) -> Depth;
     fn set_clip_depth(&self, context: MutationContext<'gc, '_>, depth: Depth);
+
+    /// The bitmap filters applied to this display object when it is rendered.
+    /// Returned by the `filters` ActionScript property.
+    fn filters(&self) -> Vec<Filter>;
+
+    /// Sets the bitmap filters applied to this display object.
+    /// Set by the `filters` ActionScript property.
+    fn set_filters(&self, context: MutationContext<'gc, '_>, filters: Vec<Filter>);
+
+    /// The blend mode used to composite this display object.
+    /// Returned by the `blendMode` ActionScript property.
+    fn blend_mode(&self) -> BlendMode;
+
+    /// Sets the blend mode used to composite this display object.
+    /// Set by the `blendMode` ActionScript property.
+    fn set_blend_mode(&self, context: MutationContext<'gc, '_>, blend_mode: BlendMode);
+
+    /// The position of this display object in the tab order, if it has one.
+    /// Returned by the `tabIndex` ActionScript property.
+    fn tab_index(&self) -> Option<i32>;
+
+    /// Sets the position of this display object in the tab order.
+    /// Set by the `tabIndex` ActionScript property.
+    fn set_tab_index(&self, context: MutationContext<'gc, '_>, tab_index: Option<i32>);
+
+    /// Whether this display object was explicitly added to or removed from the
+    /// tab order.
+    /// Returned by the `tabEnabled` ActionScript property.
+    fn tab_enabled(&self) -> Option<bool>;
+
+    /// Sets whether this display object is part of the tab order.
+    /// Set by the `tabEnabled` ActionScript property.
+    fn set_tab_enabled(&self, context: MutationContext<'gc, '_>, tab_enabled: Option<bool>);
+
+    /// Whether the children of this display object are part of the tab order.
+    /// Returned by the `tabChildren` ActionScript property.
+    fn tab_children(&self) -> bool;
+
+    /// Sets whether the children of this display object are part of the tab
+    /// order.
+    /// Set by the `tabChildren` ActionScript property.
+    fn set_tab_children(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// Whether this display object can be the target of mouse events.
+    /// Returned by the `mouseEnabled` ActionScript property.
+    fn mouse_enabled(&self) -> bool;
+
+    /// Sets whether this display object can be the target of mouse events.
+    /// Set by the `mouseEnabled` ActionScript property.
+    fn set_mouse_enabled(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// Whether the children of this display object can be the target of mouse
+    /// events. If not, this object receives them in their place.
+    /// Returned by the `mouseChildren` ActionScript property.
+    fn mouse_children(&self) -> bool;
+
+    /// Sets whether the children of this display object can be the target of
+    /// mouse events.
+    /// Set by the `mouseChildren` ActionScript property.
+    fn set_mouse_children(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// Whether this display object behaves like a button.
+    /// Returned by the `buttonMode` ActionScript property.
+    fn button_mode(&self) -> bool;
+
+    /// Sets whether this display object behaves like a button.
+    /// Set by the `buttonMode` ActionScript property.
+    fn set_button_mode(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// Whether a hand cursor is shown over this display object when it is in
+    /// button mode.
+    /// Returned by the `useHandCursor` ActionScript property.
+    fn use_hand_cursor(&self) -> bool;
+
+    /// Sets whether a hand cursor is shown over this display object when it
+    /// is in button mode.
+    /// Set by the `useHandCursor` ActionScript property.
+    fn set_use_hand_cursor(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// Whether a focus rectangle is drawn around this display object, if it
+    /// was set on this object.
+    /// Returned by the `_focusrect` ActionScript property.
+    fn focus_rect(&self) -> Option<bool>;
+
+    /// Sets whether a focus rectangle is drawn around this display object.
+    /// Set by the `_focusrect` ActionScript property.
+    fn set_focus_rect(&self, context: MutationContext<'gc, '_>, focus_rect: Option<bool>);
+
+    /// Whether this display object is part of the tab order when `tabEnabled`
+    /// has not been set.
+    fn is_tab_enabled_by_default(&self, _context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        false
+    }
+
+    /// Whether this display object is part of the tab order.
+    fn is_tab_enabled(&self, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        self.tab_enabled()
+            .unwrap_or_else(|| self.is_tab_enabled_by_default(context))
+    }
     fn parent(&self) -> Option<DisplayObject<'gc>>;
     fn set_parent(&self, context: MutationContext<'gc, '_>, parent: Option<DisplayObject<'gc>>);
     fn first_child(&self) -> Option<DisplayObject<'gc>>;
@@ -773,11 +992,25 @@ pub trait TDisplayObject<'gc>:
     fn as_morph_shape(&self) -> Option<MorphShape<'gc>> {
         None
     }
+    fn as_video(&self) -> Option<Video<'gc>> {
+        None
+    }
+    fn as_bitmap(&self) -> Option<Bitmap<'gc>> {
+        None
+    }
+
+    /// Obtain the drawing that script-driven vector graphics are drawn into,
+    /// if this display object has one.
+    fn as_drawing(&self, _gc_context: MutationContext<'gc, '_>) -> Option<RefMut<'_, Drawing>> {
+        None
+    }
     fn apply_place_object(
         &self,
-        gc_context: MutationContext<'gc, '_>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
         place_object: &swf::PlaceObject,
     ) {
+        let gc_context = context.gc_context;
+
         // PlaceObject tags only apply if this onject has not been dynamically moved by AS code.
         if !self.transformed_by_script() {
             if let Some(matrix) = &place_object.matrix {
@@ -792,9 +1025,18 @@ pub trait TDisplayObject<'gc>:
             if let Some(clip_depth) = place_object.clip_depth {
                 self.set_clip_depth(gc_context, clip_depth.into());
             }
+            if let Some(filters) = &place_object.filters {
+                let filters = filters.iter().cloned().map(Filter::from).collect();
+                self.set_filters(gc_context, filters);
+            }
+            if let Some(blend_mode) = place_object.blend_mode {
+                self.set_blend_mode(gc_context, blend_mode);
+            }
             if let Some(ratio) = place_object.ratio {
                 if let Some(mut morph_shape) = self.as_morph_shape() {
                     morph_shape.set_ratio(gc_context, ratio);
+                } else if let Some(video) = self.as_video() {
+                    video.seek(context, ratio.into());
                 }
             }
             // Clip events only apply to movie clips.
@@ -825,6 +1067,8 @@ pub trait TDisplayObject<'gc>:
         self.set_matrix(gc_context, &*other.matrix());
         self.set_color_transform(gc_context, &*other.color_transform());
         self.set_clip_depth(gc_context, other.clip_depth());
+        self.set_filters(gc_context, other.filters());
+        self.set_blend_mode(gc_context, other.blend_mode());
         self.set_name(gc_context, &*other.name());
         if let (Some(mut me), Some(other)) = (self.as_morph_shape(), other.as_morph_shape()) {
             me.set_ratio(gc_context, other.ratio());
@@ -844,6 +1088,12 @@ pub trait TDisplayObject<'gc>:
         Avm2Value::Undefined // todo: see above
     }
 
+    /// Associate an AVM2 object with this display object.
+    ///
+    /// This is used when a display object is created by script, rather than
+    /// being placed on the timeline.
+    fn set_object2(&self, _mc: MutationContext<'gc, '_>, _to: Avm2Object<'gc>) {}
+
     /// Tests if a given stage position point intersects with the world bounds of this object.
     fn hit_test_bounds(&self, pos: (Twips, Twips)) -> bool {
         self.world_bounds().contains(pos)
@@ -904,7 +1154,7 @@ pub trait TDisplayObject<'gc>:
     }
 
     /// The cursor to use when this object is the hovered element under a mouse
-    fn mouse_cursor(&self) -> MouseCursor {
+    fn mouse_cursor(&self, _context: &mut UpdateContext<'_, 'gc, '_>) -> MouseCursor {
         MouseCursor::Hand
     }
 
@@ -1053,6 +1303,89 @@ macro_rules! impl_display_object_sansbounds {
         ) {
             self.0.write(context).$field.set_clip_depth(context, depth)
         }
+        fn filters(&self) -> Vec<crate::backend::render::filters::Filter> {
+            self.0.read().$field.filters().to_vec()
+        }
+        fn set_filters(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            filters: Vec<crate::backend::render::filters::Filter>,
+        ) {
+            self.0.write(context).$field.set_filters(context, filters)
+        }
+        fn blend_mode(&self) -> swf::BlendMode {
+            self.0.read().$field.blend_mode()
+        }
+        fn set_blend_mode(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            blend_mode: swf::BlendMode,
+        ) {
+            self.0
+                .write(context)
+                .$field
+                .set_blend_mode(context, blend_mode)
+        }
+        fn tab_index(&self) -> Option<i32> {
+            self.0.read().$field.tab_index()
+        }
+        fn set_tab_index(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            tab_index: Option<i32>,
+        ) {
+            self.0.write(context).$field.set_tab_index(tab_index)
+        }
+        fn tab_enabled(&self) -> Option<bool> {
+            self.0.read().$field.tab_enabled()
+        }
+        fn set_tab_enabled(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            tab_enabled: Option<bool>,
+        ) {
+            self.0.write(context).$field.set_tab_enabled(tab_enabled)
+        }
+        fn tab_children(&self) -> bool {
+            self.0.read().$field.tab_children()
+        }
+        fn set_tab_children(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_tab_children(value)
+        }
+        fn mouse_enabled(&self) -> bool {
+            self.0.read().$field.mouse_enabled()
+        }
+        fn set_mouse_enabled(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_mouse_enabled(value)
+        }
+        fn mouse_children(&self) -> bool {
+            self.0.read().$field.mouse_children()
+        }
+        fn set_mouse_children(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_mouse_children(value)
+        }
+        fn button_mode(&self) -> bool {
+            self.0.read().$field.button_mode()
+        }
+        fn set_button_mode(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_button_mode(value)
+        }
+        fn use_hand_cursor(&self) -> bool {
+            self.0.read().$field.use_hand_cursor()
+        }
+        fn set_use_hand_cursor(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_use_hand_cursor(value)
+        }
+        fn focus_rect(&self) -> Option<bool> {
+            self.0.read().$field.focus_rect()
+        }
+        fn set_focus_rect(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            focus_rect: Option<bool>,
+        ) {
+            self.0.write(context).$field.set_focus_rect(focus_rect)
+        }
         fn parent(&self) -> Option<crate::display_object::DisplayObject<'gc>> {
             self.0.read().$field.parent()
         }
@@ -1171,7 +1504,9 @@ pub fn render_children<'gc>(
         // Check if we need to pop off a mask.
         // This must be a while loop because multiple masks can be popped
         // at the same dpeth.
-        while clip_depth > 0 && depth >= clip_depth {
+        // The clip depth is the top-most depth that is masked, so the mask
+        // also applies to the object at that depth (SWF19 p.36).
+        while clip_depth > 0 && depth > clip_depth {
             // Clear the mask stencil and pop the mask.
             let (prev_clip_depth, clip_child) = clip_depth_stack.pop().unwrap();
             clip_depth = prev_clip_depth;
@@ -1192,7 +1527,7 @@ pub fn render_children<'gc>(
             context.renderer.activate_mask();
         } else if child.visible() {
             // Normal child.
-            child.render(context);
+            render_with_effects(context, child);
         }
     }
 
@@ -1206,6 +1541,220 @@ pub fn render_children<'gc>(
     }
 }
 
+/// Renders a display object, applying its blend mode and bitmap filters.
+fn render_with_effects<'gc>(context: &mut RenderContext<'_, 'gc>, child: DisplayObject<'gc>) {
+    let blend_mode = match child.blend_mode() {
+        // Alpha and erase only apply to the content of a parent with the layer blend mode,
+        // and are otherwise drawn normally.
+        BlendMode::Alpha | BlendMode::Erase
+            if child.parent().map(|parent| parent.blend_mode()) != Some(BlendMode::Layer) =>
+        {
+            BlendMode::Normal
+        }
+        blend_mode => blend_mode,
+    };
+    let filters = child.filters();
+
+    if blend_mode != BlendMode::Normal {
+        context.renderer.push_blend_mode(blend_mode);
+    }
+    if filters.is_empty() {
+        child.render(context);
+    } else {
+        context.renderer.push_filters(&filters);
+        child.render(context);
+        context.renderer.pop_filters();
+    }
+    if blend_mode != BlendMode::Normal {
+        context.renderer.pop_blend_mode();
+    }
+}
+
+/// Returns the ActionScript name of a blend mode, as used by the `blendMode` property.
+pub fn blend_mode_name(blend_mode: BlendMode) -> &'static str {
+    match blend_mode {
+        BlendMode::Normal => "normal",
+        BlendMode::Layer => "layer",
+        BlendMode::Multiply => "multiply",
+        BlendMode::Screen => "screen",
+        BlendMode::Lighten => "lighten",
+        BlendMode::Darken => "darken",
+        BlendMode::Difference => "difference",
+        BlendMode::Add => "add",
+        BlendMode::Subtract => "subtract",
+        BlendMode::Invert => "invert",
+        BlendMode::Alpha => "alpha",
+        BlendMode::Erase => "erase",
+        BlendMode::Overlay => "overlay",
+        BlendMode::HardLight => "hardlight",
+    }
+}
+
+/// Parses the ActionScript name of a blend mode.
+pub fn blend_mode_from_name(name: &str) -> Option<BlendMode> {
+    Some(match name {
+        "normal" => BlendMode::Normal,
+        "layer" => BlendMode::Layer,
+        "multiply" => BlendMode::Multiply,
+        "screen" => BlendMode::Screen,
+        "lighten" => BlendMode::Lighten,
+        "darken" => BlendMode::Darken,
+        "difference" => BlendMode::Difference,
+        "add" => BlendMode::Add,
+        "subtract" => BlendMode::Subtract,
+        "invert" => BlendMode::Invert,
+        "alpha" => BlendMode::Alpha,
+        "erase" => BlendMode::Erase,
+        "overlay" => BlendMode::Overlay,
+        "hardlight" => BlendMode::HardLight,
+        _ => return None,
+    })
+}
+
+/// Construct the AVM2 object for a display object placed by the timeline or
+/// by a loaded movie.
+///
+/// `class` is the symbol class assigned to the display object's character, if
+/// any. Otherwise, the builtin class that owns `builtin_proto` is used. The
+/// new object is associated with the display object before its constructor
+/// runs, so that the constructor may manipulate it.
+pub fn construct_avm2_object<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    display_object: DisplayObject<'gc>,
+    class: Option<Avm2Object<'gc>>,
+    mut builtin_proto: Avm2Object<'gc>,
+) -> Result<Avm2Object<'gc>, Avm2Error<'gc>> {
+    let mut activation = Avm2Activation::from_nothing(context.reborrow());
+    let (constr, proto) = match class {
+        Some(mut class) => {
+            let proto = class
+                .get_property(
+                    class,
+                    &Avm2QName::new(Avm2Namespace::public_namespace(), "prototype"),
+                    &mut activation,
+                )?
+                .coerce_to_object(&mut activation)?;
+
+            (class, proto)
+        }
+        None => {
+            let constr = builtin_proto
+                .get_property(
+                    builtin_proto,
+                    &Avm2QName::new(Avm2Namespace::public_namespace(), "constructor"),
+                    &mut activation,
+                )?
+                .coerce_to_object(&mut activation)?;
+
+            (constr, builtin_proto)
+        }
+    };
+
+    let object: Avm2Object<'gc> =
+        Avm2StageObject::for_display_object(activation.context.gc_context, display_object, proto)
+            .into();
+
+    display_object.set_object2(activation.context.gc_context, object);
+    constr.call(Some(object), &[], &mut activation, Some(proto))?;
+
+    Ok(object)
+}
+
+/// Dispatch the `added` and `addedToStage` events for a newly-added child.
+///
+/// `added` is dispatched on the child and bubbles up to its ancestors. If the
+/// child is now attached to a level, `addedToStage` is then dispatched on it
+/// and each of its descendants, without bubbling.
+pub fn dispatch_added_event<'gc>(
+    child: DisplayObject<'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) {
+    dispatch_avm2_event(context, child, "added", true);
+
+    let mut root = child;
+    while let Some(parent) = root.parent() {
+        root = parent;
+    }
+
+    if context
+        .levels
+        .values()
+        .any(|level| DisplayObject::ptr_eq(*level, root))
+    {
+        dispatch_added_to_stage_event(child, context);
+    }
+}
+
+/// Dispatch `addedToStage` on a display object and all of its descendants.
+fn dispatch_added_to_stage_event<'gc>(
+    child: DisplayObject<'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) {
+    dispatch_avm2_event(context, child, "addedToStage", false);
+
+    let grandchildren: Vec<DisplayObject<'gc>> = child.children().collect();
+    for grandchild in grandchildren {
+        dispatch_added_to_stage_event(grandchild, context);
+    }
+}
+
+/// Dispatch the `removed` and `removedFromStage` events for a child that is
+/// about to be removed from its parent.
+///
+/// This must be called while the child is still attached, so that `removed`
+/// bubbles up through its current ancestors.
+pub fn dispatch_removed_event<'gc>(
+    child: DisplayObject<'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) {
+    dispatch_avm2_event(context, child, "removed", true);
+
+    let mut root = child;
+    while let Some(parent) = root.parent() {
+        root = parent;
+    }
+
+    if context
+        .levels
+        .values()
+        .any(|level| DisplayObject::ptr_eq(*level, root))
+    {
+        dispatch_removed_from_stage_event(child, context);
+    }
+}
+
+/// Dispatch `removedFromStage` on a display object and all of its
+/// descendants.
+fn dispatch_removed_from_stage_event<'gc>(
+    child: DisplayObject<'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) {
+    dispatch_avm2_event(context, child, "removedFromStage", false);
+
+    let grandchildren: Vec<DisplayObject<'gc>> = child.children().collect();
+    for grandchild in grandchildren {
+        dispatch_removed_from_stage_event(grandchild, context);
+    }
+}
+
+/// Dispatch a plain AVM2 `Event` on a display object, if it has an AVM2
+/// object.
+fn dispatch_avm2_event<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    target: DisplayObject<'gc>,
+    event_type: &'static str,
+    bubbles: bool,
+) {
+    if let Avm2Value::Object(object) = target.object2() {
+        let event_proto = context.avm2.prototypes().event;
+        let args = [event_type.into(), bubbles.into()];
+
+        if let Err(e) = Avm2::dispatch_event(context, event_proto, &args, object) {
+            avm2_root_error_handler(context, e);
+        }
+    }
+}
+
 pub fn get_child_by_name<'gc>(
     children: &std::collections::BTreeMap<Depth, DisplayObject<'gc>>,
     name: &str,
@@ -1250,6 +1799,25 @@ enum DisplayObjectFlags {
     /// Whether this object has been transformed by ActionScript.
     /// When this flag is set, changes from SWF `PlaceObject` tags are ignored.
     TransformedByScript,
+
+    /// Whether the children of this object are part of the tab order
+    /// (`tabChildren` property).
+    TabChildren,
+
+    /// Whether this object can be the target of mouse events (`mouseEnabled`
+    /// property).
+    MouseEnabled,
+
+    /// Whether the children of this object can be the target of mouse events
+    /// (`mouseChildren` property).
+    MouseChildren,
+
+    /// Whether this object behaves like a button (`buttonMode` property).
+    ButtonMode,
+
+    /// Whether a hand cursor is shown over this object in button mode
+    /// (`useHandCursor` property).
+    UseHandCursor,
 }
 
 pub struct ChildIter<'gc> {
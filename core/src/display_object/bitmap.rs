@@ -1,11 +1,14 @@
 //! Bitmap display object
 
+use crate::avm1::Object as Avm1Object;
+use crate::avm2::{Object as Avm2Object, TObject as Avm2TObject, Value as Avm2Value};
 use crate::backend::render::BitmapHandle;
 use crate::context::{RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::display_object::{construct_avm2_object, DisplayObjectBase, TDisplayObject};
 use crate::prelude::*;
 use crate::types::{Degrees, Percent};
-use gc_arena::{Collect, Gc, GcCell};
+use crate::vminterface::{AvmType, Instantiator};
+use gc_arena::{Collect, Gc, GcCell, MutationContext};
 
 /// A Bitmap display object is a raw bitamp on the stage.
 /// This can only be instanitated on the display list in SWFv9 AVM2 files.
@@ -21,7 +24,17 @@ pub struct Bitmap<'gc>(GcCell<'gc, BitmapData<'gc>>);
 #[derive(Clone, Debug)]
 pub struct BitmapData<'gc> {
     base: DisplayObjectBase<'gc>,
-    static_data: Gc<'gc, BitmapStatic>,
+
+    /// The bitmap character this was instantiated from, if any.
+    static_data: Option<Gc<'gc, BitmapStatic>>,
+
+    /// The AVM2 `BitmapData` object whose pixels are displayed, if any.
+    ///
+    /// This takes precedence over the bitmap character.
+    bitmap_data: Option<Avm2Object<'gc>>,
+
+    /// The AVM2 `Bitmap` object for this display object, if any.
+    avm2_object: Option<Avm2Object<'gc>>,
 }
 
 impl<'gc> Bitmap<'gc> {
@@ -36,7 +49,7 @@ impl<'gc> Bitmap<'gc> {
             context.gc_context,
             BitmapData {
                 base: Default::default(),
-                static_data: Gc::allocate(
+                static_data: Some(Gc::allocate(
                     context.gc_context,
                     BitmapStatic {
                         id,
@@ -44,22 +57,74 @@ impl<'gc> Bitmap<'gc> {
                         width,
                         height,
                     },
-                ),
+                )),
+                bitmap_data: None,
+                avm2_object: None,
+            },
+        ))
+    }
+
+    /// Construct a bitmap for an AVM2 `Bitmap` created by script, displaying
+    /// the pixels of an AVM2 `BitmapData` object.
+    pub fn new_with_avm2(
+        gc_context: MutationContext<'gc, '_>,
+        avm2_object: Avm2Object<'gc>,
+        bitmap_data: Option<Avm2Object<'gc>>,
+    ) -> Self {
+        Bitmap(GcCell::allocate(
+            gc_context,
+            BitmapData {
+                base: Default::default(),
+                static_data: None,
+                bitmap_data,
+                avm2_object: Some(avm2_object),
             },
         ))
     }
 
     #[allow(dead_code)]
-    pub fn bitmap_handle(self) -> BitmapHandle {
-        self.0.read().static_data.bitmap_handle
+    pub fn bitmap_handle(self) -> Option<BitmapHandle> {
+        self.0.read().static_data.map(|s| s.bitmap_handle)
     }
 
     pub fn width(self) -> u16 {
-        self.0.read().static_data.width
+        let read = self.0.read();
+
+        if let Some(bitmap_data) = read.bitmap_data {
+            return bitmap_data
+                .as_bitmap_data()
+                .map(|storage| storage.width() as u16)
+                .unwrap_or(0);
+        }
+
+        read.static_data.map(|s| s.width).unwrap_or(0)
     }
 
     pub fn height(self) -> u16 {
-        self.0.read().static_data.height
+        let read = self.0.read();
+
+        if let Some(bitmap_data) = read.bitmap_data {
+            return bitmap_data
+                .as_bitmap_data()
+                .map(|storage| storage.height() as u16)
+                .unwrap_or(0);
+        }
+
+        read.static_data.map(|s| s.height).unwrap_or(0)
+    }
+
+    /// The AVM2 `BitmapData` object displayed by this bitmap, if any.
+    pub fn bitmap_data(self) -> Option<Avm2Object<'gc>> {
+        self.0.read().bitmap_data
+    }
+
+    /// Change the AVM2 `BitmapData` object displayed by this bitmap.
+    pub fn set_bitmap_data(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        bitmap_data: Option<Avm2Object<'gc>>,
+    ) {
+        self.0.write(gc_context).bitmap_data = bitmap_data;
     }
 }
 
@@ -67,15 +132,15 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
     impl_display_object!(base);
 
     fn id(&self) -> CharacterId {
-        self.0.read().static_data.id
+        self.0.read().static_data.map(|s| s.id).unwrap_or(0)
     }
 
     fn self_bounds(&self) -> BoundingBox {
         BoundingBox {
             x_min: Twips::new(0),
             y_min: Twips::new(0),
-            x_max: Twips::new(Bitmap::width(*self)),
-            y_max: Twips::new(Bitmap::height(*self)),
+            x_max: Twips::from_pixels(Bitmap::width(*self).into()),
+            y_max: Twips::from_pixels(Bitmap::height(*self).into()),
             valid: true,
         }
     }
@@ -90,14 +155,63 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
             return;
         }
 
-        context.transform_stack.push(&*self.transform());
+        let read = self.0.read();
+        let bitmap_handle = if let Some(bitmap_data) = read.bitmap_data {
+            let storage = bitmap_data.as_bitmap_data();
+
+            storage.and_then(|storage| storage.bitmap_handle(context.renderer))
+        } else {
+            read.static_data.map(|s| s.bitmap_handle)
+        };
+
+        if let Some(bitmap_handle) = bitmap_handle {
+            context.transform_stack.push(&*self.transform());
+            context
+                .renderer
+                .render_bitmap(bitmap_handle, context.transform_stack.transform());
+            context.transform_stack.pop();
+        }
+    }
+
+    fn post_instantiation(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        display_object: DisplayObject<'gc>,
+        _init_object: Option<Avm1Object<'gc>>,
+        _instantiated_by: Instantiator,
+        run_frame: bool,
+    ) {
+        let vm_type = self
+            .movie()
+            .map(|movie| context.library.library_for_movie_mut(movie).avm_type());
+
+        if vm_type == Some(AvmType::Avm2) && self.0.read().avm2_object.is_none() {
+            let bitmap_proto = context.avm2.prototypes().bitmap;
+
+            if let Err(e) = construct_avm2_object(context, display_object, None, bitmap_proto) {
+                crate::avm2::root_error_handler(context, e);
+            }
+        }
+
+        if run_frame {
+            self.run_frame(context);
+        }
+    }
+
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .avm2_object
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
 
-        context.renderer.render_bitmap(
-            self.0.read().static_data.bitmap_handle,
-            context.transform_stack.transform(),
-        );
+    fn set_object2(&self, mc: MutationContext<'gc, '_>, to: Avm2Object<'gc>) {
+        self.0.write(mc).avm2_object = Some(to);
+    }
 
-        context.transform_stack.pop();
+    fn as_bitmap(&self) -> Option<Bitmap<'gc>> {
+        Some(*self)
     }
 }
 
@@ -105,6 +219,8 @@ unsafe impl<'gc> gc_arena::Collect for BitmapData<'gc> {
     fn trace(&self, cc: gc_arena::CollectionContext) {
         self.base.trace(cc);
         self.static_data.trace(cc);
+        self.bitmap_data.trace(cc);
+        self.avm2_object.trace(cc);
     }
 }
 
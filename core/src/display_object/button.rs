@@ -1,11 +1,13 @@
 use crate::avm1::{Object, StageObject, Value};
+use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
+use crate::backend::render::filters::Filter;
 use crate::context::{ActionType, RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::display_object::{construct_avm2_object, DisplayObjectBase, TDisplayObject};
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult};
 use crate::prelude::*;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::types::{Degrees, Percent};
-use crate::vminterface::Instantiator;
+use crate::vminterface::{AvmObject, AvmType, Instantiator};
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
@@ -23,7 +25,8 @@ pub struct ButtonData<'gc> {
     hit_area: BTreeMap<Depth, DisplayObject<'gc>>,
     children: BTreeMap<Depth, DisplayObject<'gc>>,
     tracking: ButtonTracking,
-    object: Option<Object<'gc>>,
+    object: Option<AvmObject<'gc>>,
+    avm2_constructor: Option<Avm2Object<'gc>>,
     initialized: bool,
     has_focus: bool,
 }
@@ -72,6 +75,7 @@ impl<'gc> Button<'gc> {
                 state: self::ButtonState::Up,
                 initialized: false,
                 object: None,
+                avm2_constructor: None,
                 tracking: if button.is_track_as_menu {
                     ButtonTracking::Menu
                 } else {
@@ -82,6 +86,16 @@ impl<'gc> Button<'gc> {
         ))
     }
 
+    /// Set the AVM2 class that instances of this button are constructed
+    /// with, as assigned by a `SymbolClass` tag.
+    pub fn set_avm2_constructor(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        constr: Option<Avm2Object<'gc>>,
+    ) {
+        self.0.write(gc_context).avm2_constructor = constr;
+    }
+
     pub fn set_sounds(self, gc_context: MutationContext<'gc, '_>, sounds: swf::ButtonSounds) {
         let button = self.0.write(gc_context);
         let mut static_data = button.static_data.write(gc_context);
@@ -150,6 +164,9 @@ impl<'gc> Button<'gc> {
                         context.gc_context,
                         &record.color_transform.clone().into(),
                     );
+                    let filters = record.filters.iter().cloned().map(Filter::from).collect();
+                    child.set_filters(context.gc_context, filters);
+                    child.set_blend_mode(context.gc_context, record.blend_mode);
                     child.set_depth(context.gc_context, record.depth.into());
 
                     new_children.push((child, record.depth.into()));
@@ -201,19 +218,41 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
     ) {
         self.set_default_instance_name(context);
 
+        let vm_type = context
+            .library
+            .library_for_movie_mut(self.0.read().static_data.read().swf.clone())
+            .avm_type();
+
         let mut mc = self.0.write(context.gc_context);
         if mc.object.is_none() {
-            let object = StageObject::for_display_object(
-                context.gc_context,
-                display_object,
-                Some(context.system_prototypes.button),
-            );
-            mc.object = Some(object.into());
+            if vm_type == AvmType::Avm2 {
+                let class = mc.avm2_constructor;
+                drop(mc);
 
-            drop(mc);
+                if run_frame {
+                    self.run_frame(context);
+                }
 
-            if run_frame {
-                self.run_frame(context);
+                let simplebutton_proto = context.avm2.prototypes().simplebutton;
+                if let Err(e) =
+                    construct_avm2_object(context, display_object, class, simplebutton_proto)
+                {
+                    crate::avm2::root_error_handler(context, e);
+                }
+            } else {
+                let object: Object<'gc> = StageObject::for_display_object(
+                    context.gc_context,
+                    display_object,
+                    Some(context.system_prototypes.button),
+                )
+                .into();
+                mc.object = Some(object.into());
+
+                drop(mc);
+
+                if run_frame {
+                    self.run_frame(context);
+                }
             }
         }
     }
@@ -320,10 +359,24 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         self.0
             .read()
             .object
+            .and_then(|o| o.as_avm1_object().ok())
             .map(Value::from)
             .unwrap_or(Value::Undefined)
     }
 
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .object
+            .and_then(|o| o.as_avm2_object().ok())
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
+
+    fn set_object2(&self, mc: MutationContext<'gc, '_>, to: Avm2Object<'gc>) {
+        self.0.write(mc).object = Some(to.into());
+    }
+
     fn as_button(&self) -> Option<Self> {
         Some(*self)
     }
@@ -399,11 +452,14 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         // Queue ActionScript-defined event handlers after the SWF defined ones.
         // (e.g., clip.onRelease = foo).
         if context.swf.version() >= 6 {
-            if let Some(name) = event.method_name() {
+            if let (Some(name), Some(object)) = (
+                event.method_name(),
+                write.object.and_then(|o| o.as_avm1_object().ok()),
+            ) {
                 context.action_queue.queue_actions(
                     self_display_object,
                     ActionType::Method {
-                        object: write.object.unwrap(),
+                        object,
                         name,
                         args: vec![],
                     },
@@ -428,6 +484,10 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         true
     }
 
+    fn is_tab_enabled_by_default(&self, _context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        true
+    }
+
     fn on_focus_changed(&self, context: MutationContext<'gc, '_>, focused: bool) {
         self.0.write(context).has_focus = focused;
     }
@@ -453,7 +513,14 @@ impl<'gc> ButtonData<'gc> {
                 .library_for_movie_mut(self.movie())
                 .get_sound(*id)
             {
-                let _ = context.audio.start_sound(sound_handle, sound_info);
+                // The button itself is borrowed, so the sound is owned by its parent instead.
+                // Buttons have no sound transform of their own, so this plays identically.
+                let _ = context.audio_manager.start_sound(
+                    context.audio,
+                    sound_handle,
+                    sound_info,
+                    self.base.parent(),
+                );
             }
         }
     }
@@ -502,6 +569,7 @@ unsafe impl<'gc> gc_arena::Collect for ButtonData<'gc> {
         self.base.trace(cc);
         self.static_data.trace(cc);
         self.object.trace(cc);
+        self.avm2_constructor.trace(cc);
     }
 }
 
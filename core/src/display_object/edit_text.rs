@@ -1,24 +1,28 @@
 //! `EditText` display object and support code.
 use crate::avm1::activation::{Activation, ActivationIdentifier};
+use crate::avm1::globals::as_broadcaster;
 use crate::avm1::globals::text_field::attach_virtual_properties;
+use crate::avm1::object::style_sheet_object::StyleSheetObject;
 use crate::avm1::{Avm1, AvmString, Object, StageObject, TObject, Value};
+use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
 use crate::backend::input::MouseCursor;
+use crate::backend::navigator::RequestOptions;
 use crate::context::{RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::display_object::{construct_avm2_object, DisplayObjectBase, MovieClip, TDisplayObject};
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode};
 use crate::font::{round_down_to_pixel, Glyph};
 use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutContent, TextFormat};
 use crate::prelude::*;
 use crate::shape_utils::DrawCommand;
-use crate::tag_utils::SwfMovie;
+use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::transform::Transform;
 use crate::types::{Degrees, Percent};
-use crate::vminterface::Instantiator;
+use crate::vminterface::{AvmObject, AvmType, Instantiator};
 use crate::xml::XMLDocument;
 use chrono::Utc;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
-use std::{cell::Ref, sync::Arc};
+use std::{borrow::Cow, cell::Ref, sync::Arc};
 use swf::Twips;
 
 /// Boxed error type.
@@ -95,6 +99,45 @@ pub struct EditTextData<'gc> {
     /// If the text field renders as HTML.
     is_html: bool,
 
+    /// The stylesheet used to style the HTML document, if any.
+    style_sheet: Option<StyleSheetObject<'gc>>,
+
+    /// The text ranges covered by each hyperlink in the HTML document.
+    ///
+    /// This is only tracked when a stylesheet is present, so that `a:hover`
+    /// styles can be applied.
+    links: Vec<(usize, usize)>,
+
+    /// The index of the hyperlink that the mouse is over, if any.
+    hovered_link: Option<usize>,
+
+    /// The display objects of the images embedded in the HTML document.
+    ///
+    /// These are in the same order as the images of the text spans.
+    images: Vec<DisplayObject<'gc>>,
+
+    /// If the text field hides its contents, as in a password entry field.
+    is_password: bool,
+
+    /// The characters that the user may enter into this text field, or `None`
+    /// if any character may be entered.
+    ///
+    /// This uses the syntax of the `restrict` ActionScript property.
+    restrict: Option<String>,
+
+    /// The maximum number of characters the user may enter, or 0 if there is
+    /// no limit.
+    max_chars: i32,
+
+    /// The topmost visible line of text, starting at 1.
+    scroll: usize,
+
+    /// Whether the mouse wheel scrolls the text when it is over the field.
+    is_mouse_wheel_enabled: bool,
+
+    /// The horizontal scroll position of the text, in pixels.
+    hscroll: f64,
+
     /// The current border drawing.
     drawing: Drawing,
 
@@ -112,8 +155,8 @@ pub struct EditTextData<'gc> {
     /// The current intrinsic bounds of the text field.
     bounds: BoundingBox,
 
-    /// The AVM1 object handle
-    object: Option<Object<'gc>>,
+    /// The AVM1 or AVM2 object handle
+    object: Option<AvmObject<'gc>>,
 
     /// The variable path that this text field is bound to (AVM1 only).
     variable: Option<String>,
@@ -129,6 +172,9 @@ pub struct EditTextData<'gc> {
 
     /// Whether or not this EditText has the current keyboard focus
     has_focus: bool,
+
+    /// Whether the user is currently dragging out a selection with the mouse.
+    is_selecting: bool,
 }
 
 impl<'gc> EditText<'gc> {
@@ -143,6 +189,8 @@ impl<'gc> EditText<'gc> {
         let is_selectable = swf_tag.is_selectable;
         let is_editable = !swf_tag.is_read_only;
         let is_html = swf_tag.is_html;
+        let is_password = swf_tag.is_password;
+        let max_chars = swf_tag.max_length.map(i32::from).unwrap_or(0);
         let document = XMLDocument::new(context.gc_context);
         let text = swf_tag.initial_text.clone().unwrap_or_default();
         let default_format = TextFormat::from_swf_tag(swf_tag.clone(), swf_movie.clone(), context);
@@ -174,6 +222,7 @@ impl<'gc> EditText<'gc> {
             bounds.width() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0),
             swf_tag.is_word_wrap,
             swf_tag.is_device_font,
+            &[],
         );
 
         let has_border = swf_tag.has_border;
@@ -210,6 +259,16 @@ impl<'gc> EditText<'gc> {
                 has_border,
                 is_device_font,
                 is_html,
+                style_sheet: None,
+                links: vec![],
+                hovered_link: None,
+                images: vec![],
+                is_password,
+                restrict: None,
+                max_chars,
+                scroll: 1,
+                is_mouse_wheel_enabled: true,
+                hscroll: 0.0,
                 drawing: Drawing::new(),
                 object: None,
                 layout,
@@ -221,6 +280,7 @@ impl<'gc> EditText<'gc> {
                 firing_variable_binding: false,
                 selection: None,
                 has_focus: false,
+                is_selecting: false,
             },
         ));
 
@@ -255,7 +315,7 @@ impl<'gc> EditText<'gc> {
                 b: 0,
                 a: 0xFF,
             }),
-            max_length: Some(width as u16),
+            max_length: None,
             layout: Some(swf::TextLayout {
                 align: swf::TextAlign::Left,
                 left_margin: Twips::from_pixels(0.0),
@@ -302,9 +362,14 @@ impl<'gc> EditText<'gc> {
         let tf = edit_text.text_spans.default_format().clone();
 
         edit_text.text_spans.replace_text(0, len, &text, Some(&tf));
+        edit_text.text_spans.clear_images();
+        let had_images = !edit_text.images.is_empty();
 
         drop(edit_text);
 
+        if had_images {
+            self.instantiate_images(context);
+        }
         self.relayout(context);
 
         Ok(())
@@ -336,6 +401,7 @@ impl<'gc> EditText<'gc> {
     ) -> Result<(), Error> {
         if self.is_html() {
             let html_string = text.replace("<sbr>", "\n").replace("<br>", "\n");
+            let html_string = close_img_tags(&html_string);
             let document = XMLDocument::new(context.gc_context);
 
             if let Err(err) =
@@ -353,8 +419,17 @@ impl<'gc> EditText<'gc> {
         Ok(())
     }
 
+    /// Get the HTML tree for the given display object.
+    ///
+    /// In stylesheet mode, this is the document that was originally set.
+    /// Otherwise, it is regenerated from the current text spans.
     pub fn html_tree(self, context: &mut UpdateContext<'_, 'gc, '_>) -> XMLDocument<'gc> {
-        self.0.read().text_spans.raise_to_html(context.gc_context)
+        let edit_text = self.0.read();
+        if edit_text.style_sheet.is_some() {
+            edit_text.document
+        } else {
+            edit_text.text_spans.raise_to_html(context.gc_context)
+        }
     }
 
     /// Set the HTML tree for the given display object.
@@ -371,10 +446,12 @@ impl<'gc> EditText<'gc> {
         let mut write = self.0.write(context.gc_context);
 
         write.document = doc;
-        write.text_spans.lower_from_html(doc);
+        write.hovered_link = None;
+        write.lower_document();
 
         drop(write);
 
+        self.instantiate_images(context);
         self.relayout(context);
     }
 
@@ -476,6 +553,144 @@ impl<'gc> EditText<'gc> {
         self.0.write(context.gc_context).is_html = is_html;
     }
 
+    pub fn style_sheet(self) -> Option<StyleSheetObject<'gc>> {
+        self.0.read().style_sheet
+    }
+
+    /// Set the stylesheet used to style this text field's HTML.
+    ///
+    /// The current HTML document is restyled immediately. Later changes to the
+    /// stylesheet only take effect once the HTML is set again.
+    pub fn set_style_sheet(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        style_sheet: Option<StyleSheetObject<'gc>>,
+    ) {
+        let mut edit_text = self.0.write(context.gc_context);
+        edit_text.style_sheet = style_sheet;
+        edit_text.hovered_link = None;
+        let is_html = edit_text.is_html;
+        if is_html {
+            edit_text.lower_document();
+        }
+        drop(edit_text);
+
+        if is_html {
+            self.instantiate_images(context);
+        }
+        self.relayout(context);
+    }
+
+    pub fn is_editable(self) -> bool {
+        self.0.read().is_editable
+    }
+
+    pub fn set_editable(self, is_editable: bool, context: MutationContext<'gc, '_>) {
+        self.0.write(context).is_editable = is_editable;
+    }
+
+    pub fn is_password(self) -> bool {
+        self.0.read().is_password
+    }
+
+    pub fn set_password(self, is_password: bool, context: MutationContext<'gc, '_>) {
+        self.0.write(context).is_password = is_password;
+    }
+
+    pub fn restrict(&self) -> Option<Ref<str>> {
+        let text = self.0.read();
+        if text.restrict.is_some() {
+            Some(Ref::map(text, |text| text.restrict.as_deref().unwrap()))
+        } else {
+            None
+        }
+    }
+
+    pub fn set_restrict(self, restrict: Option<String>, context: MutationContext<'gc, '_>) {
+        self.0.write(context).restrict = restrict;
+    }
+
+    pub fn max_chars(self) -> i32 {
+        self.0.read().max_chars
+    }
+
+    pub fn set_max_chars(self, max_chars: i32, context: MutationContext<'gc, '_>) {
+        self.0.write(context).max_chars = max_chars.max(0);
+    }
+
+    /// The topmost visible line of text, starting at 1.
+    pub fn scroll(self) -> usize {
+        self.0.read().scroll
+    }
+
+    pub fn set_scroll(self, scroll: f64, context: MutationContext<'gc, '_>) {
+        let mut edit_text = self.0.write(context);
+        let max_scroll = edit_text.max_scroll();
+        edit_text.scroll = if scroll.is_nan() || scroll < 1.0 {
+            1
+        } else {
+            (scroll as usize).min(max_scroll)
+        };
+        edit_text.position_images(context);
+    }
+
+    /// The largest value that `scroll` can take while still filling the text
+    /// field with text.
+    pub fn max_scroll(self) -> usize {
+        self.0.read().max_scroll()
+    }
+
+    pub fn is_mouse_wheel_enabled(self) -> bool {
+        self.0.read().is_mouse_wheel_enabled
+    }
+
+    pub fn set_mouse_wheel_enabled(self, is_enabled: bool, context: MutationContext<'gc, '_>) {
+        self.0.write(context).is_mouse_wheel_enabled = is_enabled;
+    }
+
+    /// Scroll the text by a number of lines in response to the mouse wheel,
+    /// where positive values scroll towards the top of the text.
+    ///
+    /// Returns `true` if the text was scrolled, in which case `onScroller` is
+    /// broadcast.
+    pub fn mouse_wheel_scroll(self, lines: f64, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        if !self.is_mouse_wheel_enabled() {
+            return false;
+        }
+
+        let scroll_state = self.scroll_state();
+        let scroll = self.scroll();
+        self.set_scroll(scroll as f64 - lines.round(), context.gc_context);
+        self.notify_user_change(false, scroll_state, context);
+        self.scroll() != scroll
+    }
+
+    /// The bottommost visible line of text, starting at 1.
+    pub fn bottom_scroll(self) -> usize {
+        self.0.read().bottom_scroll()
+    }
+
+    /// The horizontal scroll position of the text, in pixels.
+    pub fn hscroll(self) -> f64 {
+        self.0.read().hscroll
+    }
+
+    pub fn set_hscroll(self, hscroll: f64, context: MutationContext<'gc, '_>) {
+        let mut edit_text = self.0.write(context);
+        let max_hscroll = edit_text.max_hscroll();
+        edit_text.hscroll = if hscroll.is_nan() {
+            0.0
+        } else {
+            hscroll.max(0.0).min(max_hscroll.to_pixels())
+        };
+        edit_text.position_images(context);
+    }
+
+    /// The largest value that `hscroll` can take, in pixels.
+    pub fn max_hscroll(self) -> f64 {
+        self.0.read().max_hscroll().to_pixels()
+    }
+
     pub fn replace_text(
         self,
         from: usize,
@@ -633,6 +848,11 @@ impl<'gc> EditText<'gc> {
         let is_word_wrap = edit_text.is_word_wrap;
         let movie = edit_text.static_data.swf.clone();
         let width = edit_text.bounds.width() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0);
+        let image_bounds: Vec<BoundingBox> = edit_text
+            .images
+            .iter()
+            .map(|image| image.bounds())
+            .collect();
 
         let (new_layout, intrinsic_bounds) = LayoutBox::lower_from_text_spans(
             &edit_text.text_spans,
@@ -641,6 +861,7 @@ impl<'gc> EditText<'gc> {
             width,
             is_word_wrap,
             edit_text.is_device_font,
+            &image_bounds,
         );
 
         edit_text.layout = new_layout;
@@ -684,6 +905,87 @@ impl<'gc> EditText<'gc> {
                 edit_text.base.set_transformed_by_script(true);
             }
         }
+
+        // The text may have shrunk, so don't leave it scrolled past its end.
+        edit_text.scroll = edit_text.scroll.min(edit_text.max_scroll());
+        edit_text.hscroll = edit_text.hscroll.min(edit_text.max_hscroll().to_pixels());
+        edit_text.position_images(context.gc_context);
+    }
+
+    /// Create the display objects for every image embedded in the text,
+    /// replacing any that already exist.
+    ///
+    /// Each image's `src` is first looked up as the linkage name of a symbol
+    /// in this text field's library; otherwise, it is loaded as an external
+    /// movie.
+    fn instantiate_images(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let old_images = std::mem::take(&mut self.0.write(context.gc_context).images);
+        for image in old_images {
+            image.unload(context);
+        }
+
+        let movie = self.0.read().static_data.swf.clone();
+        let text_images = self.0.read().text_spans.images().to_vec();
+        let mut images = Vec::with_capacity(text_images.len());
+
+        for text_image in text_images {
+            let library_image = context
+                .library
+                .library_for_movie(movie.clone())
+                .filter(|library| {
+                    library
+                        .get_character_by_export_name(&text_image.src)
+                        .is_some()
+                })
+                .and_then(|library| {
+                    library
+                        .instantiate_by_export_name(&text_image.src, context.gc_context)
+                        .ok()
+                });
+            let is_library_image = library_image.is_some();
+            let image = library_image.unwrap_or_else(|| {
+                MovieClip::new(SwfSlice::empty(movie.clone()), context.gc_context).into()
+            });
+
+            image.set_parent(context.gc_context, Some(self.into()));
+            if let Some(id) = &text_image.id {
+                image.set_name(context.gc_context, id);
+            }
+            image.post_instantiation(context, image, None, Instantiator::Avm1, false);
+
+            if !is_library_image && !text_image.src.is_empty() {
+                if let Some(player) = context.player.clone() {
+                    let fetch = context
+                        .navigator
+                        .fetch(&text_image.src, RequestOptions::get());
+                    let process = context.load_manager.load_movie_into_clip(
+                        player,
+                        image,
+                        fetch,
+                        text_image.src.clone(),
+                        None,
+                        None,
+                    );
+                    context.navigator.spawn_future(process);
+                }
+            }
+
+            images.push(image);
+        }
+
+        self.0.write(context.gc_context).images = images;
+    }
+
+    /// Find the display object of the embedded image with the given `id`.
+    pub fn image_reference(self, id: &str) -> Option<DisplayObject<'gc>> {
+        let edit_text = self.0.read();
+        edit_text
+            .text_spans
+            .images()
+            .iter()
+            .zip(edit_text.images.iter())
+            .find(|(text_image, _)| text_image.id.as_deref() == Some(id))
+            .map(|(_, image)| *image)
     }
 
     /// Measure the width and height of the `EditText`'s current text load.
@@ -706,6 +1008,12 @@ impl<'gc> EditText<'gc> {
         let edit_text = self.0.read();
         let selection = edit_text.selection;
 
+        let box_start = if let LayoutContent::Text { start, .. } = lbox.content() {
+            Some(*start)
+        } else {
+            None
+        };
+
         let caret = if let LayoutContent::Text { start, end, .. } = &lbox.content() {
             if let Some(selection) = selection {
                 if selection.is_caret()
@@ -714,7 +1022,7 @@ impl<'gc> EditText<'gc> {
                     && selection.end() <= *end
                     && Utc::now().timestamp_subsec_millis() / 500 == 0
                 {
-                    Some((selection.start() - start, end - start))
+                    Some(selection.start() - start)
                 } else {
                     None
                 }
@@ -732,36 +1040,50 @@ impl<'gc> EditText<'gc> {
         if let Some((text, _tf, font, params, color)) =
             lbox.as_renderable_text(edit_text.text_spans.text())
         {
+            // Glyph positions are in characters, while the selection is in bytes.
+            let offsets: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+            let caret = caret.map(|caret_pos| {
+                (
+                    text.get(..caret_pos).unwrap_or(text).chars().count(),
+                    offsets.len(),
+                )
+            });
+            let text = edit_text.display_text(text);
             let baseline_adjustmnet =
                 font.get_baseline_for_height(params.height()) - params.height();
             font.evaluate(
-                text,
+                &text,
                 self.text_transform(color, baseline_adjustmnet),
                 params,
                 |pos, transform, glyph: &Glyph, advance, x| {
+                    let is_selected = match (selection, box_start) {
+                        (Some(selection), Some(box_start)) => offsets
+                            .get(pos)
+                            .map(|offset| selection.contains(box_start + offset))
+                            .unwrap_or(false),
+                        _ => false,
+                    };
+
                     // If it's highlighted, override the color.
                     // TODO: We should draw a black background and change the color to white,
                     //  but for now let's just change it to be slightly different colour.
-                    match selection {
-                        Some(selection) if selection.contains(pos) => {
-                            context.transform_stack.push(&Transform {
-                                matrix: transform.matrix,
-                                color_transform: transform.color_transform
-                                    * ColorTransform {
-                                        r_mult: 0.75,
-                                        g_mult: 0.75,
-                                        b_mult: 0.75,
-                                        a_mult: 1.0,
-                                        r_add: 0.0,
-                                        g_add: 0.0,
-                                        b_add: 1.0,
-                                        a_add: 0.0,
-                                    },
-                            });
-                        }
-                        _ => {
-                            context.transform_stack.push(&transform);
-                        }
+                    if is_selected {
+                        context.transform_stack.push(&Transform {
+                            matrix: transform.matrix,
+                            color_transform: transform.color_transform
+                                * ColorTransform {
+                                    r_mult: 0.75,
+                                    g_mult: 0.75,
+                                    b_mult: 0.75,
+                                    a_mult: 1.0,
+                                    r_add: 0.0,
+                                    g_add: 0.0,
+                                    b_add: 1.0,
+                                    a_add: 0.0,
+                                },
+                        });
+                    } else {
+                        context.transform_stack.push(&transform);
                     }
 
                     // Render glyph.
@@ -956,51 +1278,197 @@ impl<'gc> EditText<'gc> {
         }
     }
 
-    pub fn screen_position_to_index(self, position: (Twips, Twips)) -> Option<usize> {
+    /// Converts a position in screen coordinates into one relative to the
+    /// top-left of the (scrolled) laid-out text.
+    fn screen_position_to_text_position(self, position: (Twips, Twips)) -> (Twips, Twips) {
         let text = self.0.read();
         let position = self.global_to_local(position);
-        let position = (
-            position.0 + Twips::from_pixels(Self::INTERNAL_PADDING),
-            position.1 + Twips::from_pixels(Self::INTERNAL_PADDING),
+        let padding = Twips::from_pixels(Self::INTERNAL_PADDING);
+        (
+            position.0 - text.bounds.x_min - padding + Twips::from_pixels(text.hscroll),
+            position.1 - text.bounds.y_min - padding + text.scroll_offset(),
+        )
+    }
+
+    pub fn screen_position_to_index(self, position: (Twips, Twips)) -> Option<usize> {
+        let position = self.screen_position_to_text_position(position);
+        let text = self.0.read();
+        let lines = text.line_extents();
+        let line = lines
+            .iter()
+            .position(|&(_, bottom)| position.1 < bottom)
+            .unwrap_or_else(|| lines.len().saturating_sub(1));
+        text.index_at(line, position.0)
+    }
+
+    /// Finds the text position of the character under the given screen
+    /// position, if there is one.
+    fn screen_position_to_character(self, position: (Twips, Twips)) -> Option<usize> {
+        if !self.hit_test_bounds(position) {
+            return None;
+        }
+
+        let position = self.screen_position_to_text_position(position);
+        self.0.read().character_at(position)
+    }
+
+    /// Finds the URL and target of the hyperlink under the given screen
+    /// position, if there is one.
+    fn link_at_position(self, position: (Twips, Twips)) -> Option<(String, String)> {
+        let index = self.screen_position_to_character(position)?;
+        let edit_text = self.0.read();
+        let (_start, _end, _text, span) = edit_text
+            .text_spans
+            .iter_spans()
+            .find(|&(start, end, _, _)| index >= start && index < end)?;
+
+        if span.url.is_empty() {
+            None
+        } else {
+            Some((span.url.clone(), span.target.clone()))
+        }
+    }
+
+    /// Opens a hyperlink that the user clicked on.
+    ///
+    /// Links using the `asfunction:` protocol call an ActionScript function on
+    /// the text field's timeline instead of navigating; for example,
+    /// `asfunction:showDetails,42` calls `showDetails("42")`.
+    fn open_link(self, url: &str, target: &str, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let (function_name, arg) = match parse_as_function(url) {
+            Some(as_function) => as_function,
+            None => {
+                let window = if target.is_empty() {
+                    None
+                } else {
+                    Some(target.to_string())
+                };
+                context
+                    .navigator
+                    .navigate_to_url(url.to_string(), window, None);
+                return;
+            }
+        };
+
+        let args: Vec<Value<'gc>> = arg
+            .map(|arg| AvmString::new(context.gc_context, arg.to_string()).into())
+            .into_iter()
+            .collect();
+
+        let parent = self.parent().unwrap_or_else(|| self.into());
+        let this = match parent.object() {
+            Value::Object(this) => this,
+            _ => return,
+        };
+
+        let globals = context.avm1.global_object_cell();
+        let swf_version = context.swf.header().version;
+        let mut activation = Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[Text Field Link]"),
+            swf_version,
+            globals,
+            parent,
+        );
+        let result = activation.run_with_child_frame_for_display_object(
+            "[asfunction]",
+            parent,
+            swf_version,
+            |activation| {
+                activation
+                    .get_variable(function_name)?
+                    .call_with_default_this(this, function_name, activation, None, &args)
+            },
         );
 
-        for layout_box in text.layout.iter() {
-            let transform: Transform = layout_box.bounds().origin().into();
-            let mut matrix = transform.matrix;
-            matrix.invert();
-            let local_position = matrix * position;
+        if let Err(e) = result {
+            log::warn!("Error calling asfunction {}: {}", function_name, e);
+        }
+    }
 
-            if let Some((text, _tf, font, params, color)) =
-                layout_box.as_renderable_text(text.text_spans.text())
-            {
-                let mut result = None;
-                let baseline_adjustment =
-                    font.get_baseline_for_height(params.height()) - params.height();
-                font.evaluate(
-                    text,
-                    self.text_transform(color, baseline_adjustment),
-                    params,
-                    |pos, _transform, _glyph: &Glyph, advance, x| {
-                        if local_position.0 >= x
-                            && local_position.0 <= x + advance
-                            && local_position.1 >= Twips::zero()
-                            && local_position.1 <= params.height()
-                        {
-                            if local_position.0 >= x + (advance / 2) {
-                                result = Some(pos + 1);
-                            } else {
-                                result = Some(pos);
-                            }
-                        }
+    /// Track which hyperlink the mouse is over, restyling the text if it
+    /// changed so that `a:hover` styles apply.
+    fn update_hovered_link(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if self.0.read().links.is_empty() {
+            return;
+        }
+
+        let hovered_link = self
+            .screen_position_to_character(*context.mouse_position)
+            .and_then(|index| {
+                self.0
+                    .read()
+                    .links
+                    .iter()
+                    .rposition(|&(start, end)| index >= start && index < end)
+            });
+
+        if self.0.read().hovered_link != hovered_link {
+            let mut edit_text = self.0.write(context.gc_context);
+            edit_text.hovered_link = hovered_link;
+            edit_text.lower_document();
+            drop(edit_text);
+
+            self.relayout(context);
+        }
+    }
+
+    /// Replaces the selected text with text typed or pasted by the user.
+    ///
+    /// The text is filtered through `restrict` and `maxChars`, so nothing may
+    /// end up being inserted. Returns whether the text changed.
+    fn replace_selection_with_input(
+        self,
+        text: &str,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> bool {
+        let selection = match self.selection() {
+            Some(selection) => selection,
+            None => return false,
+        };
+
+        let edit_text = self.0.read();
+        let mut input = String::with_capacity(text.len());
+        for character in text.chars() {
+            let character = match character {
+                '\n' | '\r' if edit_text.is_multiline => '\n',
+                _ if character.is_control() => continue,
+                _ => match &edit_text.restrict {
+                    Some(restrict) => match restrict_character(restrict, character) {
+                        Some(character) => character,
+                        None => continue,
                     },
-                );
-                if result.is_some() {
-                    return result;
-                }
+                    None => character,
+                },
+            };
+            input.push(character);
+        }
+
+        if edit_text.max_chars > 0 {
+            let current_text = edit_text.text_spans.text();
+            let remaining_text_length = current_text.chars().count()
+                - current_text
+                    .get(selection.start()..selection.end())
+                    .map(|s| s.chars().count())
+                    .unwrap_or(0);
+            let allowed_length =
+                (edit_text.max_chars as usize).saturating_sub(remaining_text_length);
+            if let Some((end, _)) = input.char_indices().nth(allowed_length) {
+                input.truncate(end);
             }
         }
+        drop(edit_text);
 
-        None
+        if input.is_empty() && selection.is_caret() {
+            return false;
+        }
+
+        self.replace_text(selection.start(), selection.end(), &input, context);
+        self.set_selection(
+            Some(TextSelection::for_position(selection.start() + input.len())),
+            context.gc_context,
+        );
+        true
     }
 
     pub fn text_input(self, character: char, context: &mut UpdateContext<'_, 'gc, '_>) {
@@ -1009,8 +1477,9 @@ impl<'gc> EditText<'gc> {
         }
 
         if let Some(selection) = self.selection() {
+            let scroll_state = self.scroll_state();
             let mut changed = false;
-            match character as u8 {
+            match character as u32 {
                 8 | 127 if !selection.is_caret() => {
                     // Backspace or delete with multiple characters selected
                     self.replace_text(selection.start(), selection.end(), "", context);
@@ -1024,9 +1493,10 @@ impl<'gc> EditText<'gc> {
                     // Backspace with caret
                     if selection.start() > 0 {
                         // Delete previous character
-                        self.replace_text(selection.start() - 1, selection.start(), "", context);
+                        let previous = self.0.read().previous_char_boundary(selection.start());
+                        self.replace_text(previous, selection.start(), "", context);
                         self.set_selection(
-                            Some(TextSelection::for_position(selection.start() - 1)),
+                            Some(TextSelection::for_position(previous)),
                             context.gc_context,
                         );
                         changed = true;
@@ -1036,43 +1506,662 @@ impl<'gc> EditText<'gc> {
                     // Delete with caret
                     if selection.end() < self.text_length() {
                         // Delete next character
-                        self.replace_text(selection.start(), selection.start() + 1, "", context);
+                        let next = self.0.read().next_char_boundary(selection.start());
+                        self.replace_text(selection.start(), next, "", context);
                         // No need to change selection
                         changed = true;
                     }
                 }
-                32..=126 => {
-                    // ASCII
-                    // TODO: Make this actually good and not basic ASCII :)
-                    self.replace_text(
-                        selection.start(),
-                        selection.end(),
-                        &character.to_string(),
-                        context,
-                    );
-                    self.set_selection(
-                        Some(TextSelection::for_position(selection.start() + 1)),
-                        context.gc_context,
-                    );
-                    changed = true;
+                _ => {
+                    changed = self.replace_selection_with_input(&character.to_string(), context);
                 }
-                _ => {}
             }
 
-            if changed {
-                let globals = context.avm1.global_object_cell();
-                let swf_version = context.swf.header().version;
-                let mut activation = Activation::from_nothing(
-                    context.reborrow(),
-                    ActivationIdentifier::root("[Propagate Text Binding]"),
-                    swf_version,
-                    globals,
-                    self.into(),
+            self.0.write(context.gc_context).scroll_to_caret();
+            self.notify_user_change(changed, scroll_state, context);
+        }
+    }
+
+    /// Handles a keyboard shortcut (a key pressed while Control is held down)
+    /// while this text field has focus.
+    pub fn text_control_input(self, key_code: KeyCode, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let edit_text = self.0.read();
+        if !edit_text.is_selectable && !edit_text.is_editable {
+            return;
+        }
+        let selection = match edit_text.selection {
+            Some(selection) => selection,
+            None => return,
+        };
+        let selected_text = edit_text
+            .text_spans
+            .text()
+            .get(selection.start()..selection.end())
+            .unwrap_or_default()
+            .to_string();
+        let can_copy = !edit_text.is_password && !selection.is_caret();
+        let is_editable = edit_text.is_editable;
+        drop(edit_text);
+
+        let scroll_state = self.scroll_state();
+        let mut changed = false;
+        match key_code {
+            KeyCode::A => {
+                self.set_selection(
+                    Some(TextSelection::for_range(0, self.text_length())),
+                    context.gc_context,
                 );
-                self.propagate_text_binding(&mut activation);
             }
+            KeyCode::C if can_copy => {
+                context.input.set_clipboard_content(selected_text);
+            }
+            KeyCode::X if can_copy && is_editable => {
+                context.input.set_clipboard_content(selected_text);
+                changed = self.replace_selection_with_input("", context);
+            }
+            KeyCode::V if is_editable => {
+                let content = context.input.clipboard_content();
+                changed = self.replace_selection_with_input(&content, context);
+            }
+            _ => return,
         }
+
+        self.0.write(context.gc_context).scroll_to_caret();
+        self.notify_user_change(changed, scroll_state, context);
     }
+
+    /// Moves the caret in response to a navigation key.
+    ///
+    /// Returns `false` if the key does not navigate within the text.
+    fn navigate(self, key_code: ButtonKeyCode, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        let is_extending = context.input.is_key_down(KeyCode::Shift);
+        let scroll_state = self.scroll_state();
+        let mut edit_text = self.0.write(context.gc_context);
+        let mut selection = match edit_text.selection {
+            Some(selection) => selection,
+            None => return false,
+        };
+        let length = edit_text.text_spans.text().len();
+        let caret_line = edit_text.caret_position(selection.to);
+        selection.to = match key_code {
+            ButtonKeyCode::Left if selection.to > 0 => {
+                edit_text.previous_char_boundary(selection.to)
+            }
+            ButtonKeyCode::Right if selection.to < length => {
+                edit_text.next_char_boundary(selection.to)
+            }
+            ButtonKeyCode::Home => caret_line
+                .and_then(|(line, _)| edit_text.index_at(line, Twips::new(std::i32::MIN)))
+                .unwrap_or(0),
+            ButtonKeyCode::End => caret_line
+                .and_then(|(line, _)| edit_text.index_at(line, Twips::new(std::i32::MAX)))
+                .unwrap_or(length),
+            ButtonKeyCode::Up => match caret_line {
+                Some((line, x)) if line > 0 => {
+                    edit_text.index_at(line - 1, x).unwrap_or(selection.to)
+                }
+                _ => 0,
+            },
+            ButtonKeyCode::Down => match caret_line {
+                Some((line, x)) if line + 1 < edit_text.line_extents().len() => {
+                    edit_text.index_at(line + 1, x).unwrap_or(selection.to)
+                }
+                _ => length,
+            },
+            ButtonKeyCode::Left | ButtonKeyCode::Right => selection.to,
+            _ => return false,
+        };
+        if !is_extending {
+            selection.from = selection.to;
+        }
+        selection.clamp(length);
+        edit_text.selection = Some(selection);
+        edit_text.scroll_to_caret();
+        drop(edit_text);
+
+        self.notify_user_change(false, scroll_state, context);
+        true
+    }
+
+    /// The current scroll position and limits, used to tell when the text
+    /// field has scrolled.
+    fn scroll_state(self) -> (usize, usize, Twips, Twips) {
+        let edit_text = self.0.read();
+        (
+            edit_text.scroll,
+            edit_text.max_scroll(),
+            Twips::from_pixels(edit_text.hscroll),
+            edit_text.max_hscroll(),
+        )
+    }
+
+    /// Notifies scripts of changes made by the user to this text field.
+    ///
+    /// `onChanged` is broadcast if `text_changed` is set, and `onScroller` is
+    /// broadcast if the scroll state differs from `old_scroll_state`.
+    fn notify_user_change(
+        self,
+        text_changed: bool,
+        old_scroll_state: (usize, usize, Twips, Twips),
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) {
+        let scrolled = self.scroll_state() != old_scroll_state;
+        if !text_changed && !scrolled {
+            return;
+        }
+
+        let globals = context.avm1.global_object_cell();
+        let swf_version = context.swf.header().version;
+        let mut activation = Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[Text Field Event]"),
+            swf_version,
+            globals,
+            self.into(),
+        );
+        if text_changed {
+            self.propagate_text_binding(&mut activation);
+            self.broadcast_event(&mut activation, "onChanged");
+        }
+        if scrolled {
+            self.broadcast_event(&mut activation, "onScroller");
+        }
+    }
+
+    /// Broadcasts an event to the listeners of this text field, which include
+    /// the text field itself.
+    pub fn broadcast_event(self, activation: &mut Activation<'_, 'gc, '_>, method_name: &str) {
+        if let Value::Object(object) = self.object() {
+            let _ = as_broadcaster::broadcast_internal(
+                activation,
+                object,
+                &[object.into()],
+                method_name,
+            );
+        }
+    }
+}
+
+impl<'gc> EditTextData<'gc> {
+    /// Lower the HTML document into text spans, applying the stylesheet if
+    /// there is one.
+    fn lower_document(&mut self) {
+        if let Some(style_sheet) = self.style_sheet {
+            self.links = self.text_spans.lower_from_css(
+                self.document,
+                &style_sheet.style_sheet(),
+                self.hovered_link,
+            );
+        } else {
+            self.text_spans.lower_from_html(self.document);
+            self.links.clear();
+        }
+    }
+
+    /// Move and scale each embedded image to fit its layout box.
+    ///
+    /// Images are positioned relative to the text field itself, so this must
+    /// be done again whenever the text is scrolled.
+    fn position_images(&self, gc_context: MutationContext<'gc, '_>) {
+        let padding = Twips::from_pixels(EditText::INTERNAL_PADDING);
+        let text_x = self.bounds.x_min + padding - Twips::from_pixels(self.hscroll);
+        let text_y = self.bounds.y_min + padding - self.scroll_offset();
+
+        for layout_box in self.layout.iter() {
+            let image = match layout_box.content() {
+                LayoutContent::Image { index } => match self.images.get(*index) {
+                    Some(image) => *image,
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            let bounds = layout_box.bounds();
+            let natural_bounds = image.bounds();
+            let scale = |size: Twips, natural_size: Twips| {
+                if natural_size > Twips::zero() {
+                    size.get() as f32 / natural_size.get() as f32
+                } else {
+                    1.0
+                }
+            };
+
+            let matrix = Matrix::translate(text_x + bounds.offset_x(), text_y + bounds.offset_y())
+                * Matrix::scale(
+                    scale(bounds.width(), natural_bounds.width()),
+                    scale(bounds.height(), natural_bounds.height()),
+                )
+                * Matrix::translate(
+                    Twips::zero() - natural_bounds.x_min,
+                    Twips::zero() - natural_bounds.y_min,
+                );
+            image.set_matrix(gc_context, &matrix);
+        }
+    }
+
+    /// Whether any image that was laid out without content has since gained
+    /// some.
+    ///
+    /// This happens when an external image without an explicit size finishes
+    /// loading, and it needs to take up space in the text.
+    fn images_need_relayout(&self) -> bool {
+        self.layout.iter().any(|layout_box| {
+            let index = match layout_box.content() {
+                LayoutContent::Image { index } => *index,
+                _ => return false,
+            };
+
+            match (self.text_spans.images().get(index), self.images.get(index)) {
+                (Some(text_image), Some(image)) if text_image.needs_natural_size() => {
+                    let bounds = layout_box.bounds();
+                    let size = text_image.size(&image.bounds());
+                    (bounds.width() == Twips::zero() || bounds.height() == Twips::zero())
+                        && (bounds.width() != size.width() || bounds.height() != size.height())
+                }
+                _ => false,
+            }
+        })
+    }
+
+    /// The text as it should be displayed to the user.
+    fn display_text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.is_password {
+            Cow::Owned("*".repeat(text.chars().count()))
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    fn previous_char_boundary(&self, index: usize) -> usize {
+        let text = self.text_spans.text();
+        text.get(..index)
+            .and_then(|text| text.char_indices().next_back())
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    }
+
+    fn next_char_boundary(&self, index: usize) -> usize {
+        let text = self.text_spans.text();
+        text.get(index..)
+            .and_then(|text| text.chars().next())
+            .map(|c| index + c.len_utf8())
+            .unwrap_or_else(|| text.len())
+    }
+
+    /// The vertical extents of each line of laid-out text, from top to bottom.
+    fn line_extents(&self) -> Vec<(Twips, Twips)> {
+        let mut lines: Vec<(Twips, Twips)> = Vec::new();
+        for layout_box in self.layout.iter().filter(|b| b.is_text_box()) {
+            let bounds = layout_box.bounds();
+            let (top, bottom) = (bounds.offset_y(), bounds.extent_y());
+            // Boxes on the same line may be offset from each other if they
+            // have different font sizes, so group them by overlap.
+            if let Some(line) = lines
+                .iter_mut()
+                .find(|(line_top, line_bottom)| top < *line_bottom && bottom > *line_top)
+            {
+                line.0 = line.0.min(top);
+                line.1 = line.1.max(bottom);
+            } else {
+                lines.push((top, bottom));
+            }
+        }
+        lines.sort();
+        lines
+    }
+
+    /// The size of the area that text is displayed in.
+    fn visible_size(&self) -> (Twips, Twips) {
+        let padding = Twips::from_pixels(EditText::INTERNAL_PADDING * 2.0);
+        (
+            self.bounds.width() - padding,
+            self.bounds.height() - padding,
+        )
+    }
+
+    fn max_scroll(&self) -> usize {
+        let lines = self.line_extents();
+        let (_, height) = self.visible_size();
+        if let Some(&(_, last_bottom)) = lines.last() {
+            lines
+                .iter()
+                .position(|&(top, _)| last_bottom - top <= height)
+                .unwrap_or(lines.len() - 1)
+                + 1
+        } else {
+            1
+        }
+    }
+
+    fn bottom_scroll(&self) -> usize {
+        let lines = self.line_extents();
+        let (_, height) = self.visible_size();
+        let top = lines
+            .get(self.scroll - 1)
+            .map(|&(top, _)| top)
+            .unwrap_or_default();
+        let visible_lines = lines
+            .iter()
+            .skip(self.scroll - 1)
+            .take_while(|&&(_, bottom)| bottom - top <= height)
+            .count();
+        self.scroll + visible_lines.max(1) - 1
+    }
+
+    fn max_hscroll(&self) -> Twips {
+        let (width, _) = self.visible_size();
+        (self.intrinsic_bounds.width() - width).max(Twips::zero())
+    }
+
+    /// The vertical distance the text is scrolled by.
+    fn scroll_offset(&self) -> Twips {
+        let lines = self.line_extents();
+        match (lines.first(), lines.get(self.scroll - 1)) {
+            (Some(&(first_top, _)), Some(&(top, _))) => top - first_top,
+            _ => Twips::zero(),
+        }
+    }
+
+    /// Finds the text position closest to the given horizontal position on a
+    /// line of text.
+    fn index_at(&self, line: usize, x: Twips) -> Option<usize> {
+        let &(line_top, line_bottom) = self.line_extents().get(line)?;
+        let mut result = None;
+        for layout_box in self.layout.iter() {
+            let bounds = layout_box.bounds();
+            let (start, end) = match layout_box.content() {
+                LayoutContent::Text { start, end, .. }
+                    if bounds.offset_y() < line_bottom && bounds.extent_y() > line_top =>
+                {
+                    (*start, *end)
+                }
+                _ => continue,
+            };
+
+            if x < bounds.offset_x() {
+                return result.or(Some(start));
+            } else if x >= bounds.extent_x() {
+                result = Some(end);
+                continue;
+            }
+
+            let (text, _tf, font, params, _color) =
+                layout_box.as_renderable_text(self.text_spans.text())?;
+            let local_x = x - bounds.offset_x();
+            let mut char_index = None;
+            font.evaluate(
+                &self.display_text(text),
+                Default::default(),
+                params,
+                |pos, _transform, _glyph: &Glyph, advance, glyph_x| {
+                    if char_index.is_none() && local_x < glyph_x + advance {
+                        if local_x >= glyph_x + (advance / 2) {
+                            char_index = Some(pos + 1);
+                        } else {
+                            char_index = Some(pos);
+                        }
+                    }
+                },
+            );
+            return Some(match char_index {
+                Some(char_index) => {
+                    start
+                        + text
+                            .char_indices()
+                            .nth(char_index)
+                            .map(|(i, _)| i)
+                            .unwrap_or_else(|| text.len())
+                }
+                None => end,
+            });
+        }
+        result
+    }
+
+    /// Finds the text position of the character under the given point, if
+    /// there is one.
+    ///
+    /// The point is relative to the top-left of the (scrolled) text.
+    fn character_at(&self, position: (Twips, Twips)) -> Option<usize> {
+        for layout_box in self.layout.iter() {
+            let bounds = layout_box.bounds();
+            let start = match layout_box.content() {
+                LayoutContent::Text { start, .. }
+                    if position.0 >= bounds.offset_x()
+                        && position.0 < bounds.extent_x()
+                        && position.1 >= bounds.offset_y()
+                        && position.1 < bounds.extent_y() =>
+                {
+                    *start
+                }
+                _ => continue,
+            };
+
+            let (text, _tf, font, params, _color) =
+                layout_box.as_renderable_text(self.text_spans.text())?;
+            let local_x = position.0 - bounds.offset_x();
+            let mut char_index = None;
+            font.evaluate(
+                &self.display_text(text),
+                Default::default(),
+                params,
+                |pos, _transform, _glyph: &Glyph, advance, glyph_x| {
+                    if char_index.is_none() && local_x >= glyph_x && local_x < glyph_x + advance {
+                        char_index = Some(pos);
+                    }
+                },
+            );
+            return char_index
+                .and_then(|char_index| text.char_indices().nth(char_index))
+                .map(|(i, _)| start + i);
+        }
+        None
+    }
+
+    /// Finds the line and horizontal position of the caret placed at the
+    /// given text position.
+    fn caret_position(&self, index: usize) -> Option<(usize, Twips)> {
+        let lines = self.line_extents();
+        for layout_box in self.layout.iter() {
+            let bounds = layout_box.bounds();
+            let start = match layout_box.content() {
+                LayoutContent::Text { start, end, .. } if index >= *start && index <= *end => {
+                    *start
+                }
+                _ => continue,
+            };
+            let line = lines
+                .iter()
+                .position(|&(top, bottom)| bounds.offset_y() < bottom && bounds.extent_y() > top)?;
+
+            let (text, _tf, font, params, _color) =
+                layout_box.as_renderable_text(self.text_spans.text())?;
+            let char_index = text.get(..index - start).unwrap_or(text).chars().count();
+            let mut x = bounds.width();
+            font.evaluate(
+                &self.display_text(text),
+                Default::default(),
+                params,
+                |pos, _transform, _glyph: &Glyph, _advance, glyph_x| {
+                    if pos == char_index {
+                        x = glyph_x;
+                    }
+                },
+            );
+            return Some((line, bounds.offset_x() + x));
+        }
+        None
+    }
+
+    /// Scrolls the text so that the caret is visible.
+    fn scroll_to_caret(&mut self) {
+        let caret = match self.selection {
+            Some(selection) => selection.to(),
+            None => return,
+        };
+
+        if let Some((line, x)) = self.caret_position(caret) {
+            let line = line + 1;
+            if line < self.scroll {
+                self.scroll = line;
+            } else {
+                let max_scroll = self.max_scroll();
+                while line > self.bottom_scroll() && self.scroll < max_scroll {
+                    self.scroll += 1;
+                }
+            }
+
+            let (width, _) = self.visible_size();
+            let mut hscroll = Twips::from_pixels(self.hscroll);
+            if x < hscroll {
+                hscroll = x;
+            } else if x > hscroll + width {
+                hscroll = x - width;
+            }
+            self.hscroll = hscroll
+                .min(self.max_hscroll())
+                .max(Twips::zero())
+                .to_pixels();
+        }
+    }
+}
+
+/// Self-closes every `<img>` tag in a string of HTML text.
+///
+/// Flash doesn't require `<img>` tags to be closed, but our XML parser does.
+/// Tags that are already self-closed, or are immediately followed by a closing
+/// tag, are left alone.
+fn close_img_tags(html: &str) -> Cow<str> {
+    let lowercase = html.to_ascii_lowercase();
+    let mut result = String::new();
+    let mut last_end = 0;
+    let mut search_from = 0;
+
+    while let Some(found) = lowercase[search_from..].find("<img") {
+        let name_end = search_from + found + 4;
+        search_from = name_end;
+
+        match lowercase[name_end..].chars().next() {
+            Some(c) if c.is_whitespace() || c == '>' || c == '/' => {}
+            _ => continue,
+        }
+
+        // Find the end of the tag, skipping over quoted attribute values.
+        let mut quote = None;
+        let mut tag_end = None;
+        for (i, c) in lowercase[name_end..].char_indices() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '"' || c == '\'' => quote = Some(c),
+                None if c == '>' => {
+                    tag_end = Some(name_end + i);
+                    break;
+                }
+                None => {}
+            }
+        }
+
+        let tag_end = match tag_end {
+            Some(tag_end) => tag_end,
+            None => break,
+        };
+        search_from = tag_end + 1;
+
+        if lowercase[..tag_end].ends_with('/')
+            || lowercase[tag_end + 1..].trim_start().starts_with("</img")
+        {
+            continue;
+        }
+
+        result.push_str(&html[last_end..tag_end]);
+        result.push('/');
+        last_end = tag_end;
+    }
+
+    if last_end == 0 {
+        Cow::Borrowed(html)
+    } else {
+        result.push_str(&html[last_end..]);
+        Cow::Owned(result)
+    }
+}
+
+/// Splits an `asfunction:` URL into the function name and its argument.
+///
+/// Returns `None` if the URL doesn't use the `asfunction:` protocol.
+fn parse_as_function(url: &str) -> Option<(&str, Option<&str>)> {
+    const AS_FUNCTION: &str = "asfunction:";
+    let protocol = url.get(..AS_FUNCTION.len())?;
+    if !protocol.eq_ignore_ascii_case(AS_FUNCTION) {
+        return None;
+    }
+
+    let mut parts = url[AS_FUNCTION.len()..].splitn(2, ',');
+    let function_name = parts.next().unwrap_or_default().trim();
+    Some((function_name, parts.next()))
+}
+
+/// Returns the character that should be entered when the user types
+/// `character` into a text field restricted to the characters in `restrict`,
+/// or `None` if the character can't be entered.
+///
+/// `restrict` lists the characters that are allowed, with `-` denoting a
+/// range of characters. A `^` toggles between allowing and disallowing the
+/// characters that follow it, and `\` escapes the next character. If only the
+/// other case of a letter is allowed, the letter is converted to that case.
+fn restrict_character(restrict: &str, character: char) -> Option<char> {
+    if is_character_allowed(restrict, character) {
+        return Some(character);
+    }
+
+    let other_case = if character.is_lowercase() {
+        character.to_uppercase().next()
+    } else {
+        character.to_lowercase().next()
+    };
+    other_case.filter(|&c| c != character && is_character_allowed(restrict, c))
+}
+
+fn is_character_allowed(restrict: &str, character: char) -> bool {
+    // A leading `^` means that every character starts out allowed.
+    let mut is_allowed = restrict.starts_with('^');
+    let mut is_including = true;
+    let mut last_character = None;
+    let mut chars = restrict.chars().peekable();
+    while let Some(c) = chars.next() {
+        let literal = match c {
+            '^' => {
+                is_including = !is_including;
+                last_character = None;
+                continue;
+            }
+            '-' if last_character.is_some() && chars.peek().is_some() => {
+                let from = last_character.take().unwrap();
+                let to = match chars.next() {
+                    Some('\\') => chars.next(),
+                    to => to,
+                };
+                if let Some(to) = to {
+                    if from <= character && character <= to {
+                        is_allowed = is_including;
+                    }
+                }
+                continue;
+            }
+            '\\' => match chars.next() {
+                Some(c) => c,
+                None => break,
+            },
+            c => c,
+        };
+
+        if literal == character {
+            is_allowed = is_including;
+        }
+        last_character = Some(literal);
+    }
+    is_allowed
 }
 
 impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
@@ -1086,8 +2175,19 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         Some(self.0.read().static_data.swf.clone())
     }
 
-    fn run_frame(&self, _context: &mut UpdateContext) {
-        // Noop
+    fn run_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let images = self.0.read().images.clone();
+        for image in images {
+            image.run_frame(context);
+        }
+
+        if self.0.read().images_need_relayout() {
+            self.relayout(context);
+        } else {
+            // The text may have been scrolled since the last frame.
+            let edit_text = self.0.read();
+            edit_text.position_images(context.gc_context);
+        }
     }
 
     fn as_edit_text(&self) -> Option<EditText<'gc>> {
@@ -1104,9 +2204,14 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
     ) {
         self.set_default_instance_name(context);
 
+        let vm_type = self
+            .movie()
+            .map(|movie| context.library.library_for_movie_mut(movie).avm_type())
+            .unwrap_or(AvmType::Avm1);
+
         let mut text = self.0.write(context.gc_context);
-        if text.object.is_none() {
-            let object = StageObject::for_display_object(
+        if vm_type == AvmType::Avm1 && text.object.is_none() {
+            let object: Object<'gc> = StageObject::for_display_object(
                 context.gc_context,
                 display_object,
                 Some(context.system_prototypes.text_field),
@@ -1117,9 +2222,10 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
                 context.gc_context,
                 object,
                 context.system_prototypes.function,
+                context.system_prototypes.array,
             );
 
-            text.object = Some(object);
+            text.object = Some(object.into());
         }
 
         text.document = text
@@ -1132,21 +2238,36 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         for layout_box in text.layout.iter() {
             new_layout.push(layout_box.duplicate(context.gc_context));
         }
+        let has_images = !text.text_spans.images().is_empty();
+        let needs_avm2_object = vm_type == AvmType::Avm2 && text.object.is_none();
         drop(text);
 
-        // If this text field has a variable set, initialize text field binding.
-        Avm1::run_with_stack_frame_for_display_object(
-            (*self).into(),
-            context.swf.version(),
-            context,
-            |activation| {
-                if !self.try_bind_text_field_variable(activation, true) {
-                    activation.context.unbound_text_fields.push(*self);
-                }
-                // People can bind to properties of TextFields the same as other display objects.
-                self.bind_text_field_variables(activation);
-            },
-        );
+        if has_images {
+            self.instantiate_images(context);
+            self.relayout(context);
+        }
+
+        if needs_avm2_object {
+            let textfield_proto = context.avm2.prototypes().textfield;
+
+            if let Err(e) = construct_avm2_object(context, display_object, None, textfield_proto) {
+                crate::avm2::root_error_handler(context, e);
+            }
+        } else {
+            // If this text field has a variable set, initialize text field binding.
+            Avm1::run_with_stack_frame_for_display_object(
+                (*self).into(),
+                context.swf.version(),
+                context,
+                |activation| {
+                    if !self.try_bind_text_field_variable(activation, true) {
+                        activation.context.unbound_text_fields.push(*self);
+                    }
+                    // People can bind to properties of TextFields the same as other display objects.
+                    self.bind_text_field_variables(activation);
+                },
+            );
+        }
 
         if run_frame {
             self.run_frame(context);
@@ -1157,10 +2278,24 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         self.0
             .read()
             .object
+            .and_then(|o| o.as_avm1_object().ok())
             .map(Value::from)
             .unwrap_or(Value::Undefined)
     }
 
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .object
+            .and_then(|o| o.as_avm2_object().ok())
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
+
+    fn set_object2(&self, mc: MutationContext<'gc, '_>, to: Avm2Object<'gc>) {
+        self.0.write(mc).object = Some(to.into());
+    }
+
     fn self_bounds(&self) -> BoundingBox {
         self.0.read().bounds.clone()
     }
@@ -1264,12 +2399,29 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         );
         context.renderer.activate_mask();
 
+        // Images are positioned relative to the text field, rather than its
+        // bounds.
+        if !edit_text.images.is_empty() {
+            context.transform_stack.push(&Transform {
+                matrix: Matrix {
+                    tx: Twips::zero() - edit_text.bounds.x_min,
+                    ty: Twips::zero() - edit_text.bounds.y_min,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+            for image in edit_text.images.iter() {
+                image.render(context);
+            }
+            context.transform_stack.pop();
+        }
+
         // TODO: Where does this come from? How is this different than INTERNAL_PADDING? Does this apply to y as well?
         // If this is actually right, offset the border in `redraw_border` instead of doing an extra push.
         context.transform_stack.push(&Transform {
             matrix: Matrix {
-                tx: Twips::from_pixels(Self::INTERNAL_PADDING),
-                ty: Twips::from_pixels(Self::INTERNAL_PADDING),
+                tx: Twips::from_pixels(Self::INTERNAL_PADDING - edit_text.hscroll),
+                ty: Twips::from_pixels(Self::INTERNAL_PADDING) - edit_text.scroll_offset(),
                 ..Default::default()
             },
             ..Default::default()
@@ -1345,6 +2497,11 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
                 .retain(|&text_field| !DisplayObject::ptr_eq(text_field.into(), (*self).into()));
         }
 
+        let images = self.0.read().images.clone();
+        for image in images {
+            image.unload(context);
+        }
+
         self.set_removed(context.gc_context, true);
     }
 
@@ -1354,16 +2511,25 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         self_node: DisplayObject<'gc>,
         point: (Twips, Twips),
     ) -> Option<DisplayObject<'gc>> {
-        // The button is hovered if the mouse is over any child nodes.
-        if self.visible() && self.is_selectable() && self.hit_test_shape(context, point) {
+        // Hyperlinks can be clicked even if the text isn't selectable.
+        if self.visible()
+            && (self.is_selectable() || self.link_at_position(point).is_some())
+            && self.hit_test_shape(context, point)
+        {
             Some(self_node)
         } else {
             None
         }
     }
 
-    fn mouse_cursor(&self) -> MouseCursor {
-        MouseCursor::IBeam
+    fn mouse_cursor(&self, context: &mut UpdateContext<'_, 'gc, '_>) -> MouseCursor {
+        if self.link_at_position(*context.mouse_position).is_some() {
+            MouseCursor::Hand
+        } else if self.is_selectable() {
+            MouseCursor::IBeam
+        } else {
+            MouseCursor::Arrow
+        }
     }
 
     fn on_focus_changed(&self, context: MutationContext<'gc, '_>, focused: bool) {
@@ -1371,6 +2537,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         text.has_focus = focused;
         if !focused {
             text.selection = None;
+            text.is_selecting = false;
         }
     }
 
@@ -1379,6 +2546,10 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         true
     }
 
+    fn is_tab_enabled_by_default(&self, _context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        self.is_editable()
+    }
+
     fn handle_clip_event(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -1386,45 +2557,49 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
     ) -> ClipEventResult {
         match event {
             ClipEvent::Press => {
+                if let Some((url, target)) = self.link_at_position(*context.mouse_position) {
+                    self.open_link(&url, &target, context);
+                    return ClipEventResult::Handled;
+                }
+
                 let tracker = context.focus_tracker;
                 tracker.set(Some((*self).into()), context);
-                if let Some(position) = self
+                let position = self
                     .screen_position_to_index(*context.mouse_position)
-                    .map(TextSelection::for_position)
-                {
-                    self.0.write(context.gc_context).selection = Some(position);
-                } else {
-                    self.0.write(context.gc_context).selection =
-                        Some(TextSelection::for_position(self.text_length()));
-                }
+                    .unwrap_or_else(|| self.text_length());
+                let is_extending = context.input.is_key_down(KeyCode::Shift);
+                let mut text = self.0.write(context.gc_context);
+                text.selection = match text.selection {
+                    Some(selection) if is_extending => {
+                        Some(TextSelection::for_range(selection.from(), position))
+                    }
+                    _ => Some(TextSelection::for_position(position)),
+                };
+                text.is_selecting = true;
                 ClipEventResult::Handled
             }
-            ClipEvent::KeyPress { key_code } => {
-                let mut text = self.0.write(context.gc_context);
-                let selection = text.selection;
-                if let Some(mut selection) = selection {
-                    let length = text.text_spans.text().len();
-                    match key_code {
-                        ButtonKeyCode::Left if selection.to > 0 => {
-                            if context.input.is_key_down(KeyCode::Shift) {
-                                selection.to -= 1;
-                            } else {
-                                selection.to -= 1;
-                                selection.from = selection.to;
-                            }
+            ClipEvent::MouseMove => {
+                if self.0.read().is_selecting {
+                    let scroll_state = self.scroll_state();
+                    if let Some(position) = self.screen_position_to_index(*context.mouse_position) {
+                        let mut text = self.0.write(context.gc_context);
+                        if let Some(selection) = text.selection {
+                            text.selection =
+                                Some(TextSelection::for_range(selection.from(), position));
+                            text.scroll_to_caret();
                         }
-                        ButtonKeyCode::Right if selection.to < length => {
-                            if context.input.is_key_down(KeyCode::Shift) {
-                                selection.to += 1;
-                            } else {
-                                selection.to += 1;
-                                selection.from = selection.to;
-                            }
-                        }
-                        _ => {}
                     }
-                    selection.clamp(length);
-                    text.selection = Some(selection);
+                    self.notify_user_change(false, scroll_state, context);
+                }
+                self.update_hovered_link(context);
+                ClipEventResult::NotHandled
+            }
+            ClipEvent::MouseUp => {
+                self.0.write(context.gc_context).is_selecting = false;
+                ClipEventResult::NotHandled
+            }
+            ClipEvent::KeyPress { key_code } => {
+                if self.navigate(key_code, context) {
                     ClipEventResult::Handled
                 } else {
                     ClipEventResult::NotHandled
@@ -1525,3 +2700,66 @@ impl TextSelection {
         self.to == self.from
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{close_img_tags, parse_as_function, restrict_character};
+
+    #[test]
+    fn restrict_ranges() {
+        assert_eq!(restrict_character("A-Z 0-9", 'Q'), Some('Q'));
+        assert_eq!(restrict_character("A-Z 0-9", '5'), Some('5'));
+        assert_eq!(restrict_character("A-Z 0-9", ' '), Some(' '));
+        assert_eq!(restrict_character("A-Z 0-9", '!'), None);
+        assert_eq!(restrict_character("", 'a'), None);
+    }
+
+    #[test]
+    fn restrict_exclusions() {
+        assert_eq!(restrict_character("^0-9", 'a'), Some('a'));
+        assert_eq!(restrict_character("^0-9", '3'), None);
+        assert_eq!(restrict_character("a-z^aeiou", 'b'), Some('b'));
+        assert_eq!(restrict_character("a-z^aeiou", 'e'), None);
+    }
+
+    #[test]
+    fn restrict_escapes() {
+        assert_eq!(restrict_character("0-9\\-", '-'), Some('-'));
+        assert_eq!(restrict_character("\\^", '^'), Some('^'));
+        assert_eq!(restrict_character("\\\\", '\\'), Some('\\'));
+        assert_eq!(restrict_character("a\\-c", 'b'), None);
+    }
+
+    #[test]
+    fn restrict_converts_case() {
+        assert_eq!(restrict_character("A-Z", 'q'), Some('Q'));
+        assert_eq!(restrict_character("a-z", 'Q'), Some('q'));
+        assert_eq!(restrict_character("a-zA-Z", 'Q'), Some('Q'));
+    }
+
+    #[test]
+    fn as_function_urls() {
+        assert_eq!(
+            parse_as_function("asfunction:showDetails,42"),
+            Some(("showDetails", Some("42")))
+        );
+        assert_eq!(
+            parse_as_function("ASFunction:_root.log,a,b"),
+            Some(("_root.log", Some("a,b")))
+        );
+        assert_eq!(parse_as_function("asfunction:reset"), Some(("reset", None)));
+        assert_eq!(parse_as_function("http://example.com/"), None);
+        assert_eq!(parse_as_function("asfunc"), None);
+    }
+
+    #[test]
+    fn img_tags_are_closed() {
+        assert_eq!(
+            close_img_tags("<p><img src='a.jpg'>Text<IMG SRC=\"b\" ALT=\"a>b\"></p>"),
+            "<p><img src='a.jpg'/>Text<IMG SRC=\"b\" ALT=\"a>b\"/></p>"
+        );
+        assert_eq!(close_img_tags("<img src='a'/>"), "<img src='a'/>");
+        assert_eq!(close_img_tags("<img src='a'></img>"), "<img src='a'></img>");
+        assert_eq!(close_img_tags("<imgs><image>"), "<imgs><image>");
+    }
+}
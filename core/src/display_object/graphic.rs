@@ -1,9 +1,14 @@
+use crate::avm1::Object as Avm1Object;
+use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
 use crate::backend::render::ShapeHandle;
 use crate::context::{RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::display_object::{construct_avm2_object, DisplayObjectBase, TDisplayObject};
+use crate::drawing::Drawing;
 use crate::prelude::*;
 use crate::types::{Degrees, Percent};
-use gc_arena::{Collect, GcCell};
+use crate::vminterface::{AvmType, Instantiator};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::RefMut;
 
 #[derive(Clone, Debug, Collect, Copy)]
 #[collect(no_drop)]
@@ -13,6 +18,12 @@ pub struct Graphic<'gc>(GcCell<'gc, GraphicData<'gc>>);
 pub struct GraphicData<'gc> {
     base: DisplayObjectBase<'gc>,
     static_data: gc_arena::Gc<'gc, GraphicStatic>,
+
+    /// Vector graphics drawn by script through the AVM2 `Graphics` API.
+    drawing: Option<Drawing>,
+
+    /// The AVM2 `Shape` object for this graphic, if any.
+    avm2_object: Option<Avm2Object<'gc>>,
 }
 
 impl<'gc> Graphic<'gc> {
@@ -28,9 +39,40 @@ impl<'gc> Graphic<'gc> {
             GraphicData {
                 base: Default::default(),
                 static_data: gc_arena::Gc::allocate(context.gc_context, static_data),
+                drawing: None,
+                avm2_object: None,
             },
         ))
     }
+
+    /// Construct an empty graphic for a `Shape` created by AVM2 script.
+    pub fn new_with_avm2(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        avm2_object: Avm2Object<'gc>,
+    ) -> Self {
+        let swf_shape = swf::Shape {
+            version: 1,
+            id: 0,
+            shape_bounds: Default::default(),
+            edge_bounds: Default::default(),
+            has_fill_winding_rule: false,
+            has_non_scaling_strokes: false,
+            has_scaling_strokes: false,
+            styles: swf::ShapeStyles {
+                fill_styles: Vec::new(),
+                line_styles: Vec::new(),
+            },
+            shape: Vec::new(),
+        };
+        let graphic = Self::from_swf_tag(context, swf_shape);
+        let mut write = graphic.0.write(context.gc_context);
+
+        write.drawing = Some(Drawing::new());
+        write.avm2_object = Some(avm2_object);
+        drop(write);
+
+        graphic
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
@@ -41,7 +83,14 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
     }
 
     fn self_bounds(&self) -> BoundingBox {
-        self.0.read().static_data.bounds.clone()
+        let read = self.0.read();
+        let mut bounds = read.static_data.bounds.clone();
+
+        if let Some(drawing) = &read.drawing {
+            bounds.union(&drawing.self_bounds());
+        }
+
+        bounds
     }
 
     fn world_bounds(&self) -> BoundingBox {
@@ -72,6 +121,10 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
             context.transform_stack.transform(),
         );
 
+        if let Some(drawing) = &self.0.read().drawing {
+            drawing.render(context);
+        }
+
         context.transform_stack.pop();
     }
 
@@ -84,18 +137,70 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
         if self.world_bounds().contains(point) {
             let local_matrix = self.global_to_local_matrix();
             let point = local_matrix * point;
-            let shape = &self.0.read().static_data.shape;
-            crate::shape_utils::shape_hit_test(shape, point, &local_matrix)
+            let read = self.0.read();
+
+            if let Some(drawing) = &read.drawing {
+                if drawing.hit_test(point, &local_matrix) {
+                    return true;
+                }
+            }
+
+            crate::shape_utils::shape_hit_test(&read.static_data.shape, point, &local_matrix)
         } else {
             false
         }
     }
+
+    fn post_instantiation(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        display_object: DisplayObject<'gc>,
+        _init_object: Option<Avm1Object<'gc>>,
+        _instantiated_by: Instantiator,
+        run_frame: bool,
+    ) {
+        let vm_type = self
+            .movie()
+            .map(|movie| context.library.library_for_movie_mut(movie).avm_type());
+
+        if vm_type == Some(AvmType::Avm2) && self.0.read().avm2_object.is_none() {
+            let shape_proto = context.avm2.prototypes().shape;
+
+            if let Err(e) = construct_avm2_object(context, display_object, None, shape_proto) {
+                crate::avm2::root_error_handler(context, e);
+            }
+        }
+
+        if run_frame {
+            self.run_frame(context);
+        }
+    }
+
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .avm2_object
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
+
+    fn set_object2(&self, mc: MutationContext<'gc, '_>, to: Avm2Object<'gc>) {
+        self.0.write(mc).avm2_object = Some(to);
+    }
+
+    fn as_drawing(&self, gc_context: MutationContext<'gc, '_>) -> Option<RefMut<'_, Drawing>> {
+        Some(RefMut::map(self.0.write(gc_context), |graphic| {
+            graphic.drawing.get_or_insert_with(Drawing::new)
+        }))
+    }
 }
 
 unsafe impl<'gc> gc_arena::Collect for GraphicData<'gc> {
     fn trace(&self, cc: gc_arena::CollectionContext) {
         self.base.trace(cc);
         self.static_data.trace(cc);
+        self.drawing.trace(cc);
+        self.avm2_object.trace(cc);
     }
 }
 
@@ -4,16 +4,18 @@ use crate::avm1::{
 };
 use crate::avm2::Activation as Avm2Activation;
 use crate::avm2::{
-    Avm2, Error as Avm2Error, Namespace as Avm2Namespace, Object as Avm2Object, QName as Avm2QName,
-    StageObject as Avm2StageObject, TObject as Avm2TObject, Value as Avm2Value,
+    root_error_handler as avm2_root_error_handler, Avm2, Object as Avm2Object, QName as Avm2QName,
+    Value as Avm2Value,
 };
-use crate::backend::audio::AudioStreamHandle;
+use crate::backend::audio::{AudioStreamHandle, SoundTransform};
+use crate::backend::input::MouseCursor;
 
 use crate::avm1::activation::{Activation as Avm1Activation, ActivationIdentifier};
 use crate::character::Character;
 use crate::context::{ActionType, RenderContext, UpdateContext};
 use crate::display_object::{
-    Bitmap, Button, DisplayObjectBase, EditText, Graphic, MorphShapeStatic, TDisplayObject, Text,
+    construct_avm2_object, dispatch_added_event, Bitmap, Button, DisplayObjectBase, EditText,
+    Graphic, MorphShapeStatic, TDisplayObject, Text, Video,
 };
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult};
@@ -26,7 +28,7 @@ use crate::vminterface::{AvmObject, AvmType, Instantiator};
 use enumset::{EnumSet, EnumSetType};
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use smallvec::SmallVec;
-use std::cell::Ref;
+use std::cell::{Ref, RefMut};
 use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
 use std::sync::Arc;
@@ -61,6 +63,9 @@ pub struct MovieClipData<'gc> {
     drawing: Drawing,
     is_focusable: bool,
     has_focus: bool,
+
+    /// The sound transform applied to all sounds played by this clip.
+    sound_transform: SoundTransform,
 }
 
 unsafe impl<'gc> Collect for MovieClipData<'gc> {
@@ -98,6 +103,7 @@ impl<'gc> MovieClip<'gc> {
                 drawing: Drawing::new(),
                 is_focusable: false,
                 has_focus: false,
+                sound_transform: Default::default(),
             },
         ))
     }
@@ -136,6 +142,7 @@ impl<'gc> MovieClip<'gc> {
                 drawing: Drawing::new(),
                 is_focusable: false,
                 has_focus: false,
+                sound_transform: Default::default(),
             },
         ))
     }
@@ -258,7 +265,10 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_font_3(context, reader),
-                TagCode::DefineFont4 => unimplemented!(),
+                TagCode::DefineFont4 => self
+                    .0
+                    .write(context.gc_context)
+                    .define_font_4(context, reader),
                 TagCode::DefineMorphShape => self.0.write(context.gc_context).define_morph_shape(
                     context,
                     reader,
@@ -305,6 +315,10 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_text(context, reader, 2),
+                TagCode::DefineVideoStream => self
+                    .0
+                    .write(context.gc_context)
+                    .define_video_stream(context, reader),
                 TagCode::DoInitAction => self.do_init_action(context, reader, tag_len),
                 TagCode::DoAbc => self.do_abc(context, reader, tag_len),
                 TagCode::SymbolClass => self.symbol_class(context, reader),
@@ -392,6 +406,10 @@ impl<'gc> MovieClip<'gc> {
                         tag_len,
                     )
                 }
+                TagCode::VideoFrame => self
+                    .0
+                    .write(context.gc_context)
+                    .preload_video_frame(context, reader, tag_len),
                 _ => Ok(()),
             }
         };
@@ -475,12 +493,13 @@ impl<'gc> MovieClip<'gc> {
         let domain = library.avm2_domain();
 
         // The rest of the tag is an ABC file so we can take our SwfSlice now.
+        let abc_len = tag_len - reader.get_ref().position() as usize;
         let slice = self
             .0
             .read()
             .static_data
             .swf
-            .resize_to_reader(reader, tag_len)
+            .resize_to_reader(reader, abc_len)
             .ok_or_else(|| {
                 std::io::Error::new(
                     std::io::ErrorKind::Other,
@@ -489,7 +508,7 @@ impl<'gc> MovieClip<'gc> {
             })?;
 
         if let Err(e) = Avm2::load_abc(slice, &name, is_lazy_initialize, context, domain) {
-            log::warn!("Error loading ABC file: {}", e);
+            avm2_root_error_handler(context, e);
         }
 
         Ok(())
@@ -535,16 +554,22 @@ impl<'gc> MovieClip<'gc> {
                             //TODO: This assumes only the root movie has `SymbolClass` tags.
                             self.set_avm2_constructor(activation.context.gc_context, Some(proto));
                             self.construct_as_avm2_object(&mut activation.context, self.into());
-                        } else if let Some(Character::MovieClip(mc)) =
-                            library.get_character_by_id(id)
-                        {
-                            mc.set_avm2_constructor(activation.context.gc_context, Some(proto))
                         } else {
-                            log::warn!(
-                                "Symbol class {} cannot be assigned to invalid character id {}",
-                                class_name,
-                                id
-                            );
+                            match library.get_character_by_id(id) {
+                                Some(Character::MovieClip(mc)) => mc.set_avm2_constructor(
+                                    activation.context.gc_context,
+                                    Some(proto),
+                                ),
+                                Some(Character::Button(button)) => button.set_avm2_constructor(
+                                    activation.context.gc_context,
+                                    Some(proto),
+                                ),
+                                _ => log::warn!(
+                                    "Symbol class {} cannot be assigned to invalid character id {}",
+                                    class_name,
+                                    id
+                                ),
+                            }
                         }
                     }
                     Err(e) => log::warn!(
@@ -595,7 +620,6 @@ impl<'gc> MovieClip<'gc> {
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub fn playing(self) -> bool {
         self.0.read().playing()
     }
@@ -604,6 +628,20 @@ impl<'gc> MovieClip<'gc> {
         self.0.read().programmatically_played()
     }
 
+    /// Whether this clip goes back to its first frame after playing its last.
+    pub fn looping(self) -> bool {
+        !self.0.read().flags.contains(MovieClipFlags::NoLoop)
+    }
+
+    pub fn set_looping(self, mc: MutationContext<'gc, '_>, value: bool) {
+        let mut write = self.0.write(mc);
+        if value {
+            write.flags.remove(MovieClipFlags::NoLoop);
+        } else {
+            write.flags.insert(MovieClipFlags::NoLoop);
+        }
+    }
+
     pub fn set_programmatically_played(self, mc: MutationContext<'gc, '_>) {
         self.0.write(mc).set_programmatically_played()
     }
@@ -998,6 +1036,93 @@ impl<'gc> MovieClip<'gc> {
         }
     }
 
+    /// Returns the number of children on this clip's display list.
+    pub fn num_children(self) -> usize {
+        self.0.read().children.len()
+    }
+
+    /// Returns the child at a given index of this clip's display list, as
+    /// AVM2's `getChildAt` counts them.
+    pub fn child_by_index(self, index: usize) -> Option<DisplayObject<'gc>> {
+        self.0.read().children.values().nth(index).copied()
+    }
+
+    /// Returns the display list index of a child of this clip.
+    pub fn child_index(self, child: DisplayObject<'gc>) -> Option<usize> {
+        self.0
+            .read()
+            .children
+            .values()
+            .position(|c| DisplayObject::ptr_eq(*c, child))
+    }
+
+    /// Inserts a child at a given index of this clip's display list, as with
+    /// AVM2's `addChildAt`.
+    ///
+    /// Children are ordered by depth, so any children at or above the index
+    /// are moved up a depth to make room. The child must not currently have a
+    /// parent.
+    pub fn insert_child_at_index(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        child: DisplayObject<'gc>,
+        index: usize,
+    ) {
+        let depth = if let Some(existing) = self.child_by_index(index) {
+            let depth = existing.depth();
+            let mut write = self.0.write(context.gc_context);
+            let above: Vec<(Depth, DisplayObject<'gc>)> = write
+                .children
+                .range(depth..)
+                .map(|(depth, child)| (*depth, *child))
+                .collect();
+
+            for (depth, _) in above.iter() {
+                write.children.remove(depth);
+            }
+
+            for (depth, above_child) in above {
+                above_child.set_depth(context.gc_context, depth + 1);
+                write.children.insert(depth + 1, above_child);
+            }
+
+            depth
+        } else {
+            self.highest_depth().map(|depth| depth + 1).unwrap_or(0)
+        };
+
+        self.add_child_from_avm(context, child, depth);
+        child.set_removed(context.gc_context, false);
+    }
+
+    /// Removes a child from this clip's display list without unloading it,
+    /// as with AVM2's `removeChild`.
+    ///
+    /// Unlike AVM1, removed children remain usable and may be added to the
+    /// display list again.
+    pub fn remove_child_from_avm2(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        child: DisplayObject<'gc>,
+    ) {
+        let mut parent = self.0.write(context.gc_context);
+        let is_child = parent
+            .children
+            .get(&child.depth())
+            .map(|c| DisplayObject::ptr_eq(*c, child))
+            .unwrap_or(false);
+
+        if is_child {
+            parent.children.remove(&child.depth());
+            parent.unlink_child_from_exec_list(context.gc_context, child);
+            drop(parent);
+
+            child.set_parent(context.gc_context, None);
+            child.set_prev_sibling(context.gc_context, None);
+            child.set_next_sibling(context.gc_context, None);
+        }
+    }
+
     /// Returns an iterator of AVM1 `DoAction` blocks on the given frame number.
     /// Used by the AVM `Call` action.
     pub fn actions_on_frame(
@@ -1087,14 +1212,14 @@ impl<'gc> MovieClip<'gc> {
         // Advance frame number.
         if self.current_frame() < self.total_frames() {
             self.0.write(context.gc_context).current_frame += 1;
-        } else if self.total_frames() > 1 {
+        } else if self.total_frames() > 1 && self.looping() {
             // Looping acts exactly like a gotoAndPlay(1).
             // Specifically, object that existed on frame 1 should not be destroyed
             // and recreated.
             self.run_goto(self_display_object, context, 1);
             return;
         } else {
-            // Single frame clips do not play.
+            // Single frame clips, and clips that don't loop, stop on their last frame.
             self.stop(context);
         }
 
@@ -1199,8 +1324,9 @@ impl<'gc> MovieClip<'gc> {
                     }
                 }
                 // Run first frame.
-                child.apply_place_object(context.gc_context, place_object);
+                child.apply_place_object(context, place_object);
                 child.post_instantiation(context, child, None, Instantiator::Movie, false);
+                dispatch_added_event(child, context);
                 child.run_frame(context);
             }
             Some(child)
@@ -1368,7 +1494,7 @@ impl<'gc> MovieClip<'gc> {
                 // If it's a rewind, we removed any dead children above, so we always
                 // modify the previous child.
                 Some(prev_child) if params.id() == 0 || is_rewind => {
-                    prev_child.apply_place_object(context.gc_context, &params.place_object);
+                    prev_child.apply_place_object(context, &params.place_object);
                 }
                 _ => {
                     if let Some(child) = clip.instantiate_child(
@@ -1547,41 +1673,18 @@ impl<'gc> MovieClip<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         display_object: DisplayObject<'gc>,
     ) {
-        let constructor = self
-            .0
-            .read()
-            .avm_constructor
-            .unwrap_or_else(|| context.avm2.prototypes().movieclip.into());
-        if let AvmObject::Avm2(mut constr) = constructor {
-            let mut constr_thing = || {
-                let mut activation = Avm2Activation::from_nothing(context.reborrow());
-                let proto = constr
-                    .get_property(
-                        constr,
-                        &Avm2QName::new(Avm2Namespace::public_namespace(), "prototype"),
-                        &mut activation,
-                    )?
-                    .coerce_to_object(&mut activation)?;
-                let object = Avm2StageObject::for_display_object(
-                    activation.context.gc_context,
-                    display_object,
-                    proto,
-                )
-                .into();
-
-                constr.call(Some(object), &[], &mut activation, Some(proto))?;
-
-                Ok(object)
-            };
-            let result: Result<Avm2Object<'gc>, Avm2Error> = constr_thing();
-
-            if let Ok(object) = result {
-                self.0.write(context.gc_context).object = Some(object.into());
-            } else if let Err(e) = result {
-                log::error!("Got {} when constructing AVM2 side of display object", e);
+        let class = match self.0.read().avm_constructor {
+            Some(AvmObject::Avm2(class)) => Some(class),
+            Some(AvmObject::Avm1(_)) => {
+                log::error!("Attempted to construct AVM2 movieclip with AVM1 constructor!");
+                return;
             }
-        } else {
-            log::error!("Attempted to construct AVM2 movieclip with AVM1 constructor!");
+            None => None,
+        };
+        let builtin_proto = context.avm2.prototypes().movieclip;
+
+        if let Err(e) = construct_avm2_object(context, display_object, class, builtin_proto) {
+            avm2_root_error_handler(context, e);
         }
     }
 
@@ -1627,9 +1730,40 @@ impl<'gc> MovieClip<'gc> {
         }
     }
 
+    /// Whether this clip operates in "button mode", which it does if it has a
+    /// mouse handler, either via on(..) or via property mc.onRelease, etc.
+    pub fn is_button_mode(self, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        if self.0.read().has_button_clip_event {
+            return true;
+        }
+
+        let mut activation = Avm1Activation::from_stub(
+            context.reborrow(),
+            ActivationIdentifier::root("[Button Mode]"),
+        );
+        let object = self.object().coerce_to_object(&mut activation);
+
+        ClipEvent::BUTTON_EVENT_METHODS
+            .iter()
+            .any(|handler| object.has_property(&mut activation, handler))
+    }
+
     pub fn set_focusable(self, focusable: bool, context: &mut UpdateContext<'_, 'gc, '_>) {
         self.0.write(context.gc_context).is_focusable = focusable;
     }
+
+    pub fn sound_transform(self) -> SoundTransform {
+        self.0.read().sound_transform
+    }
+
+    pub fn set_sound_transform(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        sound_transform: SoundTransform,
+    ) {
+        self.0.write(context.gc_context).sound_transform = sound_transform;
+        context.audio_manager.set_sound_transforms_dirty();
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
@@ -1722,28 +1856,33 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         point: (Twips, Twips),
     ) -> Option<DisplayObject<'gc>> {
         if self.visible() {
-            if self.world_bounds().contains(point) {
-                // This movieclip operates in "button mode" if it has a mouse handler,
-                // either via on(..) or via property mc.onRelease, etc.
-                let is_button_mode = {
-                    if self.0.read().has_button_clip_event {
-                        true
-                    } else {
-                        let mut activation = Avm1Activation::from_stub(
-                            context.reborrow(),
-                            ActivationIdentifier::root("[Mouse Pick]"),
-                        );
-                        let object = self.object().coerce_to_object(&mut activation);
-
-                        ClipEvent::BUTTON_EVENT_METHODS
-                            .iter()
-                            .any(|handler| object.has_property(&mut activation, handler))
+            if let Avm2Value::Object(_) = self.object2() {
+                // AVM2 clips are always interactive, so the topmost child
+                // under the mouse wins, and then the clip's own shape. With
+                // `mouseChildren` unset, the clip takes the place of its
+                // children, and with `mouseEnabled` unset, its parent takes
+                // the place of the clip.
+                if self.mouse_children() {
+                    for child in self.0.read().children.values().rev() {
+                        let result = child.mouse_pick(context, *child, point);
+                        if result.is_some() {
+                            return result;
+                        }
                     }
-                };
+                }
 
-                if is_button_mode && self.hit_test_shape(context, point) {
+                if self.mouse_enabled() && self.hit_test_shape(context, point) {
                     return Some(self_node);
                 }
+
+                return None;
+            }
+
+            if self.world_bounds().contains(point)
+                && self.is_button_mode(context)
+                && self.hit_test_shape(context, point)
+            {
+                return Some(self_node);
             }
 
             // Maybe we could skip recursing down at all if !world_bounds.contains(point),
@@ -1759,6 +1898,29 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         None
     }
 
+    fn mouse_cursor(&self, _context: &mut UpdateContext<'_, 'gc, '_>) -> MouseCursor {
+        if let Avm2Value::Object(_) = self.object2() {
+            // AVM2 clips show a hand cursor if they, or the nearest ancestor
+            // in `buttonMode`, have `useHandCursor` set.
+            let mut node = Some(DisplayObject::from(*self));
+            while let Some(dobj) = node {
+                if dobj.button_mode() {
+                    if dobj.use_hand_cursor() {
+                        return MouseCursor::Hand;
+                    }
+
+                    break;
+                }
+
+                node = dobj.parent();
+            }
+
+            MouseCursor::Arrow
+        } else {
+            MouseCursor::Hand
+        }
+    }
+
     fn handle_clip_event(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -1828,6 +1990,14 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
             .unwrap_or(Avm2Value::Undefined)
     }
 
+    fn set_object2(&self, mc: MutationContext<'gc, '_>, to: Avm2Object<'gc>) {
+        self.0.write(mc).object = Some(to.into());
+    }
+
+    fn as_drawing(&self, gc_context: MutationContext<'gc, '_>) -> Option<RefMut<'_, Drawing>> {
+        Some(RefMut::map(self.0.write(gc_context), |mc| &mut mc.drawing))
+    }
+
     fn unload(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
         for child in self.children() {
             child.unload(context);
@@ -1866,6 +2036,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         self.0.read().is_focusable
     }
 
+    fn is_tab_enabled_by_default(&self, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        self.is_button_mode(context)
+    }
+
     fn on_focus_changed(&self, context: MutationContext<'gc, '_>, focused: bool) {
         self.0.write(context).has_focus = focused;
     }
@@ -1985,23 +2159,34 @@ impl<'gc> MovieClipData<'gc> {
         &mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
         child: DisplayObject<'gc>,
+    ) {
+        self.unlink_child_from_exec_list(context.gc_context, child);
+
+        // Flag child as removed.
+        child.unload(context);
+    }
+
+    /// Removes a child from the execution list without unloading it.
+    /// This does not affect the render list.
+    fn unlink_child_from_exec_list(
+        &mut self,
+        gc_context: MutationContext<'gc, '_>,
+        child: DisplayObject<'gc>,
     ) {
         // Remove from children linked list.
         let prev = child.prev_sibling();
         let next = child.next_sibling();
         if let Some(prev) = prev {
-            prev.set_next_sibling(context.gc_context, next);
+            prev.set_next_sibling(gc_context, next);
         }
         if let Some(next) = next {
-            next.set_prev_sibling(context.gc_context, prev);
+            next.set_prev_sibling(gc_context, prev);
         }
         if let Some(head) = self.first_child() {
             if DisplayObject::ptr_eq(head, child) {
-                self.set_first_child(context.gc_context, next);
+                self.set_first_child(gc_context, next);
             }
         }
-        // Flag child as removed.
-        child.unload(context);
     }
 
     /// Handles a PlaceObject tag when running a goto action.
@@ -2307,6 +2492,48 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn preload_video_frame(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+        tag_len: usize,
+    ) -> DecodeResult {
+        let stream_id = reader.read_character_id()?;
+        let frame_num = reader.read_u16()?;
+        let frame_data = self
+            .static_data
+            .swf
+            .resize_to_reader(reader, tag_len - 4)
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "Cannot preload video frame with invalid offset and length!",
+                )
+            })?;
+
+        match context
+            .library
+            .library_for_movie_mut(self.movie())
+            .get_character_by_id(stream_id)
+        {
+            Some(Character::Video(video)) => {
+                video.preload_swf_frame(
+                    frame_num.into(),
+                    (frame_data.start, frame_data.end),
+                    context.gc_context,
+                );
+            }
+            _ => log::warn!(
+                "Attempted to preload video frame {} for non-video character {}",
+                frame_num,
+                stream_id
+            ),
+        }
+
+        Ok(())
+    }
+
     #[inline]
     fn preload_sound_stream_head(
         &mut self,
@@ -2647,6 +2874,24 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_font_4(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+    ) -> DecodeResult {
+        let font = reader.read_define_font_4()?;
+        match Font::from_font4_tag(context.gc_context, context.renderer, &font) {
+            Ok(font_object) => context
+                .library
+                .library_for_movie_mut(self.movie())
+                .register_character(font.id, Character::Font(font_object)),
+            Err(e) => log::error!("MovieClip::define_font_4: Unable to register font: {}", e),
+        }
+
+        Ok(())
+    }
+
     #[inline]
     fn define_sound(
         &mut self,
@@ -2668,6 +2913,22 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_video_stream(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+    ) -> DecodeResult {
+        let streamdef = reader.read_define_video_stream()?;
+        let id = streamdef.id;
+        let video = Video::from_swf_tag(self.movie(), streamdef, context.gc_context);
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .register_character(id, Character::Video(video));
+        Ok(())
+    }
+
     fn define_sprite(
         &mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -2893,7 +3154,7 @@ impl<'gc, 'a> MovieClip<'gc> {
                     .get(&place_object.depth.into())
                     .copied()
                 {
-                    child.apply_place_object(context.gc_context, &place_object);
+                    child.apply_place_object(context, &place_object);
                     child
                 } else {
                     return Ok(());
@@ -2940,23 +3201,36 @@ impl<'gc, 'a> MovieClip<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         _reader: &mut SwfStream<&'a [u8]>,
     ) -> DecodeResult {
-        let mut mc = self.0.write(context.gc_context);
-        if let (Some(stream_info), None) = (&mc.static_data.audio_stream_info, mc.audio_stream) {
-            let slice = mc
-                .static_data
-                .swf
-                .to_start_and_end(mc.tag_stream_pos as usize, mc.tag_stream_len())
-                .ok_or_else(|| {
-                    std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        "Invalid slice generated when constructing sound stream block",
-                    )
-                })?;
-            let audio_stream =
-                context
-                    .audio
-                    .start_stream(mc.id(), mc.current_frame() + 1, slice, &stream_info);
-            mc.audio_stream = audio_stream.ok();
+        let mc = self.0.read();
+        let stream = match (&mc.static_data.audio_stream_info, mc.audio_stream) {
+            (Some(stream_info), None) => {
+                let slice = mc
+                    .static_data
+                    .swf
+                    .to_start_and_end(mc.tag_stream_pos as usize, mc.tag_stream_len())
+                    .ok_or_else(|| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::Other,
+                            "Invalid slice generated when constructing sound stream block",
+                        )
+                    })?;
+                Some((slice, stream_info.clone(), mc.current_frame() + 1))
+            }
+            _ => None,
+        };
+        // The clip must not be borrowed while the stream starts, as the
+        // sound transforms of it and its ancestors are read.
+        drop(mc);
+
+        if let Some((slice, stream_info, frame)) = stream {
+            let audio_stream = context.audio_manager.start_stream(
+                context.audio,
+                frame,
+                slice,
+                &stream_info,
+                self.into(),
+            );
+            self.0.write(context.gc_context).audio_stream = audio_stream;
         }
 
         Ok(())
@@ -2979,13 +3253,23 @@ impl<'gc, 'a> MovieClip<'gc> {
             match start_sound.sound_info.event {
                 // "Event" sounds always play, independent of the timeline.
                 SoundEvent::Event => {
-                    let _ = context.audio.start_sound(handle, &start_sound.sound_info);
+                    let _ = context.audio_manager.start_sound(
+                        context.audio,
+                        handle,
+                        &start_sound.sound_info,
+                        Some(self.into()),
+                    );
                 }
 
                 // "Start" sounds only play if an instance of the same sound is not already playing.
                 SoundEvent::Start => {
                     if !context.audio.is_sound_playing_with_handle(handle) {
-                        let _ = context.audio.start_sound(handle, &start_sound.sound_info);
+                        let _ = context.audio_manager.start_sound(
+                            context.audio,
+                            handle,
+                            &start_sound.sound_info,
+                            Some(self.into()),
+                        );
                     }
                 }
 
@@ -3168,6 +3452,11 @@ enum MovieClipFlags {
     /// The AS3 `isPlaying` property is broken and yields false until you first
     /// call `play` to unbreak it. This flag tracks that bug.
     ProgrammaticallyPlayed,
+
+    /// Whether this `MovieClip` stops on its last frame instead of looping.
+    ///
+    /// This is toggled on the root movie by the "Loop" item of the context menu.
+    NoLoop,
 }
 
 /// Actions that are attached to a `MovieClip` event in
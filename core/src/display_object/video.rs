@@ -0,0 +1,325 @@
+//! Video player display object
+
+use crate::avm1::Object as Avm1Object;
+use crate::backend::render::BitmapInfo;
+use crate::backend::video::{EncodedFrame, VideoStreamHandle};
+use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::prelude::*;
+use crate::tag_utils::SwfMovie;
+use crate::types::{Degrees, Percent};
+use crate::vminterface::Instantiator;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use swf::DefineVideoStream;
+
+/// A Video display object is a high-level interface to a video player.
+///
+/// Video data may be embedded within a `DefineVideoStream` tag, in which case
+/// the `VideoFrame` tags of the timeline provide the frames, and the ratio of
+/// the placing `PlaceObject` tag selects which frame is shown.
+#[derive(Clone, Debug, Collect, Copy)]
+#[collect(no_drop)]
+pub struct Video<'gc>(GcCell<'gc, VideoData<'gc>>);
+
+#[derive(Clone, Debug)]
+pub struct VideoData<'gc> {
+    base: DisplayObjectBase<'gc>,
+
+    /// The source of the video data, shared between all instances.
+    source: GcCell<'gc, VideoSource>,
+
+    /// The decoder stream of this instance.
+    stream: VideoStream,
+
+    /// The frames of the source that can be decoded on their own.
+    keyframes: BTreeSet<u32>,
+
+    /// The most recently decoded frame and its bitmap.
+    decoded_frame: Option<(u32, BitmapInfo)>,
+}
+
+/// The state of a video's decoder stream.
+#[derive(Clone, Debug)]
+pub enum VideoStream {
+    /// The stream has not been registered yet; the frame to show once it is.
+    Uninstantiated(u32),
+
+    /// The stream is registered with the video backend.
+    Instantiated(VideoStreamHandle),
+}
+
+/// Where a video's frames come from.
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+pub enum VideoSource {
+    /// A `DefineVideoStream` with frames from `VideoFrame` tags.
+    Swf {
+        movie: Arc<SwfMovie>,
+        streamdef: DefineVideoStream,
+
+        /// The location of each frame's data within the movie.
+        frames: BTreeMap<u32, (usize, usize)>,
+    },
+}
+
+impl<'gc> Video<'gc> {
+    /// Construct a video display object from a `DefineVideoStream` tag.
+    pub fn from_swf_tag(
+        movie: Arc<SwfMovie>,
+        streamdef: DefineVideoStream,
+        gc_context: MutationContext<'gc, '_>,
+    ) -> Self {
+        let source = GcCell::allocate(
+            gc_context,
+            VideoSource::Swf {
+                movie,
+                streamdef,
+                frames: BTreeMap::new(),
+            },
+        );
+
+        Video(GcCell::allocate(
+            gc_context,
+            VideoData {
+                base: Default::default(),
+                source,
+                stream: VideoStream::Uninstantiated(0),
+                keyframes: BTreeSet::new(),
+                decoded_frame: None,
+            },
+        ))
+    }
+
+    /// Record the location of a `VideoFrame` tag's data within the movie.
+    pub fn preload_swf_frame(
+        self,
+        frame_id: u32,
+        data_range: (usize, usize),
+        gc_context: MutationContext<'gc, '_>,
+    ) {
+        let read = self.0.read();
+        match &mut *read.source.write(gc_context) {
+            VideoSource::Swf { frames, .. } => {
+                frames.insert(frame_id, data_range);
+            }
+        };
+    }
+
+    /// Show a particular frame of the video.
+    ///
+    /// Inter frames depend on the frames before them, so seeking anywhere
+    /// other than the next frame decodes everything from the closest
+    /// keyframe onwards.
+    pub fn seek(self, context: &mut UpdateContext<'_, 'gc, '_>, frame_id: u32) {
+        let read = self.0.read();
+        let stream = match read.stream {
+            VideoStream::Uninstantiated(_) => {
+                drop(read);
+                self.0.write(context.gc_context).stream = VideoStream::Uninstantiated(frame_id);
+                return;
+            }
+            VideoStream::Instantiated(stream) => stream,
+        };
+
+        let last_frame = read.decoded_frame.map(|(last_frame, _)| last_frame);
+        if last_frame == Some(frame_id) {
+            return;
+        }
+
+        let is_keyframe = read.keyframes.contains(&frame_id);
+        let first_frame = if is_keyframe || last_frame.map(|f| f + 1) == Some(frame_id) {
+            frame_id
+        } else {
+            let keyframe = read
+                .keyframes
+                .range(..frame_id)
+                .next_back()
+                .copied()
+                .unwrap_or(0);
+            match last_frame {
+                // Continue from the current frame if it's past the keyframe.
+                Some(last_frame) if last_frame >= keyframe && last_frame < frame_id => {
+                    last_frame + 1
+                }
+                _ => keyframe,
+            }
+        };
+        drop(read);
+
+        for frame in first_frame..=frame_id {
+            self.decode_frame(context, stream, frame);
+        }
+    }
+
+    fn decode_frame(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        stream: VideoStreamHandle,
+        frame_id: u32,
+    ) {
+        let source = self.0.read().source;
+        let result = match &*source.read() {
+            VideoSource::Swf {
+                movie,
+                streamdef,
+                frames,
+            } => match frames.get(&frame_id) {
+                Some(&(start, end)) => context.video.decode_video_stream_frame(
+                    stream,
+                    EncodedFrame {
+                        codec: streamdef.codec,
+                        data: &movie.data()[start..end],
+                        frame_id,
+                    },
+                    context.renderer,
+                ),
+                // Not every frame of the timeline has a `VideoFrame` tag.
+                None => return,
+            },
+        };
+
+        match result {
+            Ok(bitmap) => {
+                self.0.write(context.gc_context).decoded_frame = Some((frame_id, bitmap));
+            }
+            Err(e) => log::error!("Unable to decode video frame {}: {}", frame_id, e),
+        }
+    }
+
+    fn size(self) -> (u16, u16) {
+        match &*self.0.read().source.read() {
+            VideoSource::Swf { streamdef, .. } => (streamdef.width, streamdef.height),
+        }
+    }
+}
+
+impl<'gc> TDisplayObject<'gc> for Video<'gc> {
+    impl_display_object!(base);
+
+    fn id(&self) -> CharacterId {
+        match &*self.0.read().source.read() {
+            VideoSource::Swf { streamdef, .. } => streamdef.id,
+        }
+    }
+
+    fn as_video(&self) -> Option<Video<'gc>> {
+        Some(*self)
+    }
+
+    fn post_instantiation(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        _display_object: DisplayObject<'gc>,
+        _init_object: Option<Avm1Object<'gc>>,
+        _instantiated_by: Instantiator,
+        _run_frame: bool,
+    ) {
+        let source = self.0.read().source;
+        let (stream, keyframes) = match &*source.read() {
+            VideoSource::Swf {
+                movie,
+                streamdef,
+                frames,
+            } => {
+                let stream = match context.video.register_video_stream(
+                    streamdef.num_frames.into(),
+                    (streamdef.width, streamdef.height),
+                    streamdef.codec,
+                    streamdef.deblocking,
+                ) {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        log::error!("Unable to register video stream: {}", e);
+                        return;
+                    }
+                };
+
+                let mut keyframes = BTreeSet::new();
+                for (&frame_id, &(start, end)) in frames.iter() {
+                    let dependency = context.video.preload_video_stream_frame(
+                        stream,
+                        EncodedFrame {
+                            codec: streamdef.codec,
+                            data: &movie.data()[start..end],
+                            frame_id,
+                        },
+                    );
+                    match dependency {
+                        Ok(dependency) if dependency.is_keyframe() => {
+                            keyframes.insert(frame_id);
+                        }
+                        Ok(_) => (),
+                        Err(e) => {
+                            log::warn!("Unable to preload video frame {}: {}", frame_id, e);
+                            break;
+                        }
+                    }
+                }
+
+                (stream, keyframes)
+            }
+        };
+
+        let mut write = self.0.write(context.gc_context);
+        let starting_frame = match write.stream {
+            VideoStream::Uninstantiated(frame_id) => frame_id,
+            VideoStream::Instantiated(_) => 0,
+        };
+        write.stream = VideoStream::Instantiated(stream);
+        write.keyframes = keyframes;
+        drop(write);
+
+        self.seek(context, starting_frame);
+    }
+
+    fn self_bounds(&self) -> BoundingBox {
+        let (width, height) = self.size();
+        BoundingBox {
+            x_min: Twips::new(0),
+            y_min: Twips::new(0),
+            x_max: Twips::from_pixels(width.into()),
+            y_max: Twips::from_pixels(height.into()),
+            valid: true,
+        }
+    }
+
+    fn run_frame(&self, _context: &mut UpdateContext) {
+        // Noop
+    }
+
+    fn render(&self, context: &mut RenderContext) {
+        if !self.world_bounds().intersects(&context.view_bounds) {
+            // Off-screen; culled
+            return;
+        }
+
+        let bitmap = if let Some((_, bitmap)) = self.0.read().decoded_frame {
+            bitmap
+        } else {
+            return;
+        };
+
+        context.transform_stack.push(&*self.transform());
+
+        // Stretch the decoded frame to the size of the video.
+        let (width, height) = self.size();
+        let mut transform = context.transform_stack.transform().clone();
+        transform.matrix *= Matrix::scale(
+            f32::from(width) / f32::from(bitmap.width),
+            f32::from(height) / f32::from(bitmap.height),
+        );
+        context.renderer.render_bitmap(bitmap.handle, &transform);
+
+        context.transform_stack.pop();
+    }
+}
+
+unsafe impl<'gc> gc_arena::Collect for VideoData<'gc> {
+    #[inline]
+    fn trace(&self, cc: gc_arena::CollectionContext) {
+        self.base.trace(cc);
+        self.source.trace(cc);
+    }
+}
@@ -227,6 +227,12 @@ impl Drawing {
     }
 }
 
+impl Default for Drawing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 fn stretch_bounding_box(
     bounding_box: &mut BoundingBox,
     command: &DrawCommand,
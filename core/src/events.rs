@@ -3,14 +3,35 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 #[allow(clippy::enum_variant_names)]
 #[derive(Debug)]
 pub enum PlayerEvent {
-    KeyDown { key_code: KeyCode },
-    KeyUp { key_code: KeyCode },
-    MouseMove { x: f64, y: f64 },
-    MouseUp { x: f64, y: f64 },
-    MouseDown { x: f64, y: f64 },
+    KeyDown {
+        key_code: KeyCode,
+        key_char: Option<char>,
+        key_location: KeyLocation,
+    },
+    KeyUp {
+        key_code: KeyCode,
+        key_char: Option<char>,
+        key_location: KeyLocation,
+    },
+    MouseMove {
+        x: f64,
+        y: f64,
+    },
+    MouseUp {
+        x: f64,
+        y: f64,
+    },
+    MouseDown {
+        x: f64,
+        y: f64,
+    },
     MouseLeft,
-    MouseWheel { delta: MouseWheelDelta },
-    TextInput { codepoint: char },
+    MouseWheel {
+        delta: MouseWheelDelta,
+    },
+    TextInput {
+        codepoint: char,
+    },
 }
 
 /// The distance scrolled by the mouse wheel.
@@ -82,13 +103,28 @@ impl ClipEvent {
     pub fn propagates(self) -> bool {
         matches!(
             self,
-            Self::MouseUp | Self::MouseDown | Self::MouseMove | Self::KeyPress { .. } | Self::KeyDown | Self::KeyUp
+            Self::MouseUp
+                | Self::MouseDown
+                | Self::MouseMove
+                | Self::KeyPress { .. }
+                | Self::KeyDown
+                | Self::KeyUp
         )
     }
 
     /// Indicates whether this is an event type used by Buttons (i.e., on that can be used in an `on` handler in Flash).
     pub fn is_button_event(self) -> bool {
-        matches!(self, Self::DragOut | Self::DragOver | Self::KeyPress { .. } | Self::Press | Self::RollOut | Self::RollOver | Self::Release | Self::ReleaseOutside)
+        matches!(
+            self,
+            Self::DragOut
+                | Self::DragOver
+                | Self::KeyPress { .. }
+                | Self::Press
+                | Self::RollOut
+                | Self::RollOver
+                | Self::Release
+                | Self::ReleaseOutside
+        )
     }
 
     /// Returns the method name of the event handler for this event.
@@ -123,6 +159,7 @@ impl ClipEvent {
 pub enum KeyCode {
     Unknown = 0,
     Backspace = 8,
+    Tab = 9,
     Return = 13,
     Shift = 16,
     Control = 17,
@@ -203,6 +240,7 @@ pub enum KeyCode {
     Insert = 45,
     Delete = 46,
     Pause = 19,
+    NumLock = 144,
     ScrollLock = 145,
     F1 = 112,
     F2 = 113,
@@ -216,6 +254,22 @@ pub enum KeyCode {
     F10 = 121,
     F11 = 122,
     F12 = 123,
+    F13 = 124,
+    F14 = 125,
+    F15 = 126,
+}
+
+/// Which of several keys with the same key code was pressed, such as the left
+/// or right Shift key.
+///
+/// Equivalent to AS3 `KeyLocation`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u8)]
+pub enum KeyLocation {
+    Standard = 0,
+    Left = 1,
+    Right = 2,
+    NumPad = 3,
 }
 
 /// Key codes for SWF4 keyPress button handlers. These are annoyingly different than
@@ -352,6 +406,7 @@ pub fn key_code_to_button_key_code(key_code: KeyCode) -> Option<ButtonKeyCode> {
         KeyCode::Down => ButtonKeyCode::Down,
         KeyCode::PgUp => ButtonKeyCode::PgUp,
         KeyCode::PgDown => ButtonKeyCode::PgDown,
+        KeyCode::Tab => ButtonKeyCode::Tab,
         KeyCode::Escape => ButtonKeyCode::Escape,
         _ => return None,
     };
@@ -1,19 +1,64 @@
 use crate::avm1::{Avm1, Value};
-use crate::context::UpdateContext;
+use crate::context::{RenderContext, UpdateContext};
 pub use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::events::ClipEvent;
+use crate::prelude::*;
 use gc_arena::{Collect, GcCell, MutationContext};
 
+/// The width of the focus rectangle, in pixels.
+const FOCUS_RECT_THICKNESS: f32 = 2.0;
+
 #[collect(no_drop)]
 #[derive(Clone, Copy, Collect, Debug)]
-pub struct FocusTracker<'gc>(GcCell<'gc, Option<DisplayObject<'gc>>>);
+pub struct FocusTracker<'gc>(GcCell<'gc, FocusTrackerData<'gc>>);
+
+#[collect(no_drop)]
+#[derive(Clone, Collect, Debug)]
+struct FocusTrackerData<'gc> {
+    /// The object that currently has keyboard focus.
+    focus: Option<DisplayObject<'gc>>,
+
+    /// Whether focus was last moved with the keyboard, in which case the
+    /// focused object is highlighted with a focus rectangle and can be
+    /// activated with Enter or Space.
+    highlight: bool,
+
+    /// The object that had focus when the last key was pressed, or the root
+    /// movie if nothing did. Used by `Key.isAccessible`.
+    key_target: Option<DisplayObject<'gc>>,
+}
 
 impl<'gc> FocusTracker<'gc> {
     pub fn new(gc_context: MutationContext<'gc, '_>) -> Self {
-        Self(GcCell::allocate(gc_context, None))
+        Self(GcCell::allocate(
+            gc_context,
+            FocusTrackerData {
+                focus: None,
+                highlight: false,
+                key_target: None,
+            },
+        ))
     }
 
     pub fn get(&self) -> Option<DisplayObject<'gc>> {
-        *self.0.read()
+        self.0.read().focus
+    }
+
+    /// Whether the focused object was focused with the keyboard.
+    pub fn is_highlighted(&self) -> bool {
+        let read = self.0.read();
+        read.highlight && read.focus.is_some()
+    }
+
+    /// The object that received the last key press.
+    pub fn key_target(&self) -> Option<DisplayObject<'gc>> {
+        self.0.read().key_target
+    }
+
+    /// Remember which object is receiving a key press.
+    pub fn set_key_target(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let key_target = self.get().or_else(|| context.levels.get(&0).copied());
+        self.0.write(context.gc_context).key_target = key_target;
     }
 
     pub fn set(
@@ -21,7 +66,11 @@ impl<'gc> FocusTracker<'gc> {
         focused_element: Option<DisplayObject<'gc>>,
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) {
-        let old = std::mem::replace(&mut *self.0.write(context.gc_context), focused_element);
+        let old = {
+            let mut write = self.0.write(context.gc_context);
+            write.highlight = false;
+            std::mem::replace(&mut write.focus, focused_element)
+        };
 
         if old.is_none() && focused_element.is_none() {
             // We didn't have anything, we still don't, no change.
@@ -56,4 +105,174 @@ impl<'gc> FocusTracker<'gc> {
             ],
         );
     }
+
+    /// Stop highlighting the focused object, such as when the mouse is used.
+    ///
+    /// The focused object keeps focus, but leaves its rolled over state.
+    pub fn clear_highlight(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if !self.is_highlighted() {
+            return;
+        }
+
+        self.0.write(context.gc_context).highlight = false;
+
+        if let Some(focus) = self.get() {
+            let is_hovered = context
+                .mouse_hovered_object
+                .map(|hovered| DisplayObject::ptr_eq(hovered, focus))
+                .unwrap_or(false);
+
+            if focus.as_edit_text().is_none() && !is_hovered {
+                focus.handle_clip_event(context, ClipEvent::RollOut);
+            }
+        }
+    }
+
+    /// Move focus to the next object in the tab order, or the previous one if
+    /// `reverse` is set, as happens when Tab or Shift+Tab is pressed.
+    ///
+    /// Buttons and clips focused this way are rolled over, as if the mouse
+    /// had moved over them.
+    pub fn cycle(&self, context: &mut UpdateContext<'_, 'gc, '_>, reverse: bool) {
+        let tab_order = Self::tab_order(context);
+        if tab_order.is_empty() {
+            return;
+        }
+
+        let old = self.get();
+        let old_index = old.and_then(|old| {
+            tab_order
+                .iter()
+                .position(|object| DisplayObject::ptr_eq(*object, old))
+        });
+        let next_index = match (old_index, reverse) {
+            (Some(index), false) => (index + 1) % tab_order.len(),
+            (Some(index), true) => (index + tab_order.len() - 1) % tab_order.len(),
+            (None, false) => 0,
+            (None, true) => tab_order.len() - 1,
+        };
+        let next = tab_order[next_index];
+
+        if let Some(old) = old {
+            if self.is_highlighted() && old.as_edit_text().is_none() {
+                old.handle_clip_event(context, ClipEvent::RollOut);
+            }
+        }
+
+        self.set(Some(next), context);
+        self.0.write(context.gc_context).highlight = true;
+
+        if next.as_edit_text().is_none() {
+            next.handle_clip_event(context, ClipEvent::RollOver);
+        }
+    }
+
+    /// Build the list of objects that can be focused with Tab, in order.
+    ///
+    /// If any object has a `tabIndex`, only objects with a `tabIndex` take part,
+    /// ordered by it. Otherwise objects are ordered by their position on the
+    /// stage, from top to bottom and left to right.
+    fn tab_order(context: &mut UpdateContext<'_, 'gc, '_>) -> Vec<DisplayObject<'gc>> {
+        let mut tab_order = Vec::new();
+        let levels: Vec<DisplayObject<'gc>> = context.levels.values().copied().collect();
+        for level in levels {
+            Self::collect_tabbable(context, level, &mut tab_order);
+        }
+
+        if tab_order.iter().any(|object| object.tab_index().is_some()) {
+            tab_order.retain(|object| object.tab_index().is_some());
+            tab_order.sort_by_key(|object| object.tab_index());
+        } else {
+            tab_order.sort_by_key(|object| {
+                let bounds = object.world_bounds();
+                (bounds.y_min, bounds.x_min)
+            });
+        }
+
+        tab_order
+    }
+
+    fn collect_tabbable(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: DisplayObject<'gc>,
+        tab_order: &mut Vec<DisplayObject<'gc>>,
+    ) {
+        if !object.visible() || object.removed() {
+            return;
+        }
+
+        if object.is_tab_enabled(context) {
+            tab_order.push(object);
+        }
+
+        if object.tab_children() {
+            for child in object.children() {
+                Self::collect_tabbable(context, child, tab_order);
+            }
+        }
+    }
+
+    /// Draw the yellow focus rectangle around the focused object, if it was
+    /// focused with the keyboard.
+    ///
+    /// Text fields are never highlighted. `_focusrect` on the focused object
+    /// takes precedence over `_focusrect` on the root movie.
+    pub fn render_highlight(
+        &self,
+        context: &mut RenderContext<'_, 'gc>,
+        root: Option<DisplayObject<'gc>>,
+    ) {
+        let focus = match self.get() {
+            Some(focus) if self.is_highlighted() && focus.as_edit_text().is_none() => focus,
+            _ => return,
+        };
+
+        let show_focus_rect = focus
+            .focus_rect()
+            .or_else(|| root.and_then(|root| root.focus_rect()))
+            .unwrap_or(true);
+        if !show_focus_rect {
+            return;
+        }
+
+        let bounds = focus.world_bounds();
+        if !bounds.valid {
+            return;
+        }
+
+        let width = bounds.width().to_pixels() as f32;
+        let height = bounds.height().to_pixels() as f32;
+        let thickness = Twips::from_pixels(FOCUS_RECT_THICKNESS.into());
+        let edges = [
+            Matrix::create_box(width, FOCUS_RECT_THICKNESS, 0.0, bounds.x_min, bounds.y_min),
+            Matrix::create_box(
+                width,
+                FOCUS_RECT_THICKNESS,
+                0.0,
+                bounds.x_min,
+                bounds.y_max - thickness,
+            ),
+            Matrix::create_box(
+                FOCUS_RECT_THICKNESS,
+                height,
+                0.0,
+                bounds.x_min,
+                bounds.y_min,
+            ),
+            Matrix::create_box(
+                FOCUS_RECT_THICKNESS,
+                height,
+                0.0,
+                bounds.x_max - thickness,
+                bounds.y_min,
+            ),
+        ];
+
+        let view_matrix = context.transform_stack.transform().matrix;
+        for edge in edges.iter() {
+            context
+                .renderer
+                .draw_rect(Color::from_rgb(0xFFFF00, 0xFF), &(view_matrix * *edge));
+        }
+    }
 }
@@ -149,6 +149,45 @@ impl<'gc> Font<'gc> {
         )))
     }
 
+    /// Construct a font from a `DefineFont4` tag.
+    ///
+    /// DefineFont4 embeds an OpenType font with CFF outlines, intended for use
+    /// by the Flash Text Engine. Its outlines are converted into ordinary SWF
+    /// glyph shapes. If the font data is missing or cannot be decoded, the
+    /// font is created without any glyphs, and will be rendered as a device
+    /// font instead.
+    pub fn from_font4_tag(
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn RenderBackend,
+        tag: &swf::Font4,
+    ) -> Result<Font<'gc>, Error> {
+        let decoded = tag.data.as_deref().and_then(opentype_to_swf_glyphs);
+        if decoded.is_none() {
+            log::warn!(
+                "Unable to decode glyphs for DefineFont4 font {} ({}); using a device font",
+                tag.id,
+                tag.name
+            );
+        }
+        let (glyphs, layout) = decoded.unwrap_or_default();
+
+        let font = swf::Font {
+            version: 4,
+            id: tag.id,
+            name: tag.name.clone(),
+            language: swf::Language::Unknown,
+            layout,
+            glyphs,
+            is_small_text: false,
+            is_shift_jis: false,
+            is_ansi: false,
+            is_bold: tag.is_bold,
+            is_italic: tag.is_italic,
+        };
+
+        Self::from_swf_tag(gc_context, renderer, &font)
+    }
+
     /// Returns whether this font contains glyph shapes.
     /// If not, this font should be rendered as a device font.
     pub fn has_glyphs(self) -> bool {
@@ -361,6 +400,266 @@ impl<'gc> Font<'gc> {
     }
 }
 
+/// The size of the EM square used by DefineFont3 and DefineFont4 glyphs.
+const FONT4_EM_SQUARE: f32 = 20480.0;
+
+/// Convert the contents of an OpenType font into SWF glyphs and layout.
+///
+/// Glyphs are produced for every Unicode code point in the Basic
+/// Multilingual Plane that the font maps, scaled to the DefineFont3 EM
+/// square. This returns `None` if the font data could not be parsed.
+fn opentype_to_swf_glyphs(data: &[u8]) -> Option<(Vec<swf::Glyph>, Option<swf::FontLayout>)> {
+    let font = ttf_parser::Font::from_data(data, 0)?;
+    let units_per_em = font.units_per_em().filter(|upem| *upem > 0)?;
+    let scale = FONT4_EM_SQUARE / f32::from(units_per_em);
+
+    let mut glyphs = vec![];
+    for c in opentype_code_points(data)
+        .into_iter()
+        .filter_map(|code| std::char::from_u32(code.into()))
+    {
+        let glyph_id = match font.glyph_index(c) {
+            Some(glyph_id) if glyph_id.0 != 0 => glyph_id,
+            _ => continue,
+        };
+
+        // Glyphs without outlines, such as spaces, are left empty.
+        let mut builder = GlyphShapeBuilder::new(scale);
+        if font.outline_glyph(glyph_id, &mut builder).is_none() {
+            builder.records.clear();
+        }
+
+        let advance = f32::from(font.glyph_hor_advance(glyph_id).unwrap_or(0)) * scale;
+        glyphs.push(swf::Glyph {
+            shape_records: builder.records,
+            code: c as u16,
+            advance: Some(advance.round().min(f32::from(i16::MAX)) as i16),
+            bounds: None,
+        });
+    }
+
+    let layout = swf::FontLayout {
+        ascent: (f32::from(font.ascender()) * scale).max(0.0) as u16,
+        descent: (-f32::from(font.descender()) * scale).max(0.0) as u16,
+        leading: (f32::from(font.line_gap()) * scale) as i16,
+        kerning: vec![],
+    };
+
+    Some((glyphs, Some(layout)))
+}
+
+/// List the code points in the Basic Multilingual Plane that may be mapped to
+/// a glyph by the Unicode subtables of an OpenType font's `cmap` table.
+///
+/// `ttf_parser` can only look up one code point at a time, so this reads the
+/// ranges covered by each subtable, allowing glyphs to be built without
+/// probing every possible code point. Some code points in these ranges may
+/// still map to the missing glyph.
+fn opentype_code_points(data: &[u8]) -> std::collections::BTreeSet<u16> {
+    let u16_at = |offset: usize| {
+        data.get(offset..offset + 2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+    };
+    let u32_at = |offset: usize| {
+        data.get(offset..offset + 4)
+            .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    };
+
+    let mut code_points = std::collections::BTreeSet::new();
+    let mut add_range = |start: u32, end: u32| {
+        if start <= end && start <= 0xFFFF {
+            code_points.extend(start as u16..=end.min(0xFFFF) as u16);
+        }
+    };
+
+    // Find the `cmap` table in the table directory.
+    let num_tables = u16_at(4).unwrap_or(0);
+    let cmap = (0..usize::from(num_tables))
+        .map(|i| 12 + i * 16)
+        .find(|record| data.get(*record..*record + 4) == Some(b"cmap"))
+        .and_then(|record| u32_at(record + 8));
+    let cmap = match cmap {
+        Some(cmap) => cmap as usize,
+        None => return code_points,
+    };
+
+    let num_subtables = u16_at(cmap + 2).unwrap_or(0);
+    for i in 0..usize::from(num_subtables) {
+        let record = cmap + 4 + i * 8;
+        let (platform_id, encoding_id) = match (u16_at(record), u16_at(record + 2)) {
+            (Some(platform_id), Some(encoding_id)) => (platform_id, encoding_id),
+            _ => break,
+        };
+        let is_unicode = platform_id == 0 || (platform_id == 3 && [1, 10].contains(&encoding_id));
+        let subtable = match u32_at(record + 4) {
+            Some(offset) if is_unicode => cmap + offset as usize,
+            _ => continue,
+        };
+
+        match u16_at(subtable) {
+            // Byte encoding table.
+            Some(0) => add_range(0, 0xFF),
+            // Segment mapping to delta values.
+            Some(4) => {
+                let seg_count_x2 = usize::from(u16_at(subtable + 6).unwrap_or(0));
+                let end_codes = subtable + 14;
+                let start_codes = end_codes + seg_count_x2 + 2;
+                for segment in (0..seg_count_x2).step_by(2) {
+                    if let (Some(start), Some(end)) =
+                        (u16_at(start_codes + segment), u16_at(end_codes + segment))
+                    {
+                        add_range(start.into(), end.into());
+                    }
+                }
+            }
+            // Trimmed table mapping.
+            Some(6) => {
+                if let (Some(first), Some(count)) = (u16_at(subtable + 6), u16_at(subtable + 8)) {
+                    if count > 0 {
+                        add_range(first.into(), u32::from(first) + u32::from(count) - 1);
+                    }
+                }
+            }
+            // Segmented coverage.
+            Some(12) => {
+                let num_groups = u32_at(subtable + 12).unwrap_or(0) as usize;
+                for group in 0..num_groups {
+                    let group = subtable + 16 + group * 12;
+                    match (u32_at(group), u32_at(group + 4)) {
+                        (Some(start), Some(end)) => add_range(start, end),
+                        _ => break,
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    code_points
+}
+
+/// Builds SWF shape records from the outline of an OpenType glyph.
+struct GlyphShapeBuilder {
+    /// The scale from font units to twips.
+    scale: f32,
+
+    /// The shape records produced so far.
+    records: Vec<swf::ShapeRecord>,
+
+    /// The current drawing position, in twips.
+    position: (Twips, Twips),
+
+    /// The start of the current contour, in twips.
+    contour_start: (Twips, Twips),
+
+    /// The current drawing position, in font units.
+    last_point: (f32, f32),
+}
+
+impl GlyphShapeBuilder {
+    fn new(scale: f32) -> Self {
+        Self {
+            scale,
+            records: vec![],
+            position: (Twips::zero(), Twips::zero()),
+            contour_start: (Twips::zero(), Twips::zero()),
+            last_point: (0.0, 0.0),
+        }
+    }
+
+    /// Convert a point in font units to twips.
+    ///
+    /// OpenType's Y axis points up, while SWF's points down.
+    fn to_twips(&self, x: f32, y: f32) -> (Twips, Twips) {
+        (
+            Twips::new((x * self.scale).round() as i32),
+            Twips::new((-y * self.scale).round() as i32),
+        )
+    }
+
+    fn straight_edge_to(&mut self, point: (Twips, Twips)) {
+        self.records.push(swf::ShapeRecord::StraightEdge {
+            delta_x: point.0 - self.position.0,
+            delta_y: point.1 - self.position.1,
+        });
+        self.position = point;
+    }
+}
+
+impl ttf_parser::OutlineBuilder for GlyphShapeBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let point = self.to_twips(x, y);
+        let fill_style_0 = if self.records.is_empty() {
+            Some(1)
+        } else {
+            None
+        };
+
+        self.records
+            .push(swf::ShapeRecord::StyleChange(swf::StyleChangeData {
+                move_to: Some(point),
+                fill_style_0,
+                fill_style_1: None,
+                line_style: None,
+                new_styles: None,
+            }));
+        self.position = point;
+        self.contour_start = point;
+        self.last_point = (x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let point = self.to_twips(x, y);
+        self.straight_edge_to(point);
+        self.last_point = (x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let control = self.to_twips(x1, y1);
+        let anchor = self.to_twips(x, y);
+        self.records.push(swf::ShapeRecord::CurvedEdge {
+            control_delta_x: control.0 - self.position.0,
+            control_delta_y: control.1 - self.position.1,
+            anchor_delta_x: anchor.0 - control.0,
+            anchor_delta_y: anchor.1 - control.1,
+        });
+        self.position = anchor;
+        self.last_point = (x, y);
+    }
+
+    /// SWF shapes only support quadratic curves, so cubic curves are split in
+    /// half and each half is approximated by a single quadratic curve.
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let mid = |a: (f32, f32), b: (f32, f32)| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+        let quad_control = |p0: (f32, f32), c1: (f32, f32), c2: (f32, f32), p3: (f32, f32)| {
+            (
+                (3.0 * (c1.0 + c2.0) - p0.0 - p3.0) / 4.0,
+                (3.0 * (c1.1 + c2.1) - p0.1 - p3.1) / 4.0,
+            )
+        };
+
+        let p0 = self.last_point;
+        let (c1, c2, p3) = ((x1, y1), (x2, y2), (x, y));
+        let m01 = mid(p0, c1);
+        let m12 = mid(c1, c2);
+        let m23 = mid(c2, p3);
+        let m012 = mid(m01, m12);
+        let m123 = mid(m12, m23);
+        let split = mid(m012, m123);
+
+        let first = quad_control(p0, m01, m012, split);
+        self.quad_to(first.0, first.1, split.0, split.1);
+        let second = quad_control(split, m123, m23, p3);
+        self.quad_to(second.0, second.1, p3.0, p3.1);
+    }
+
+    fn close(&mut self) {
+        if self.position != self.contour_start {
+            self.straight_edge_to(self.contour_start);
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Glyph {
     pub shape_handle: ShapeHandle,
@@ -427,7 +726,7 @@ impl FontDescriptor {
 #[cfg(test)]
 mod tests {
     use crate::backend::render::{NullRenderer, RenderBackend};
-    use crate::font::{EvalParameters, Font};
+    use crate::font::{opentype_code_points, EvalParameters, Font, GlyphShapeBuilder};
     use crate::player::{Player, DEVICE_FONT_TAG};
     use gc_arena::{rootless_arena, MutationContext};
     use std::ops::DerefMut;
@@ -603,4 +902,84 @@ mod tests {
             assert_eq!(None, breakpoint5);
         });
     }
+
+    #[test]
+    fn font4_without_data_uses_device_font() {
+        rootless_arena(|mc| {
+            let mut renderer: Box<dyn RenderBackend> = Box::new(NullRenderer::new());
+            for data in &[None, Some(b"not an opentype font".to_vec())] {
+                let tag = swf::Font4 {
+                    id: 1,
+                    is_italic: false,
+                    is_bold: false,
+                    name: "Test Font".to_string(),
+                    data: data.clone(),
+                };
+                let font = Font::from_font4_tag(mc, renderer.deref_mut(), &tag).unwrap();
+
+                assert!(!font.has_glyphs());
+                assert_eq!("Test Font", font.descriptor().class());
+            }
+        })
+    }
+
+    #[test]
+    fn opentype_code_points_reads_cmap_ranges() {
+        // A table directory holding only a `cmap` table, with a format 4
+        // subtable mapping 'A'-'C' and the required final 0xFFFF segment, and
+        // a format 12 subtable mapping 'x'-'z'.
+        let mut data = vec![0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+        data.extend(b"cmap");
+        data.extend(&[0, 0, 0, 0, 0, 0, 0, 28, 0, 0, 0, 0]);
+        data.extend(&[0, 0, 0, 2]);
+        data.extend(&[0, 3, 0, 1, 0, 0, 0, 20]);
+        data.extend(&[0, 3, 0, 10, 0, 0, 0, 52]);
+        data.extend(&[0, 4, 0, 32, 0, 0, 0, 4, 0, 4, 0, 1, 0, 0]);
+        data.extend(&[0, 67, 255, 255, 0, 0, 0, 65, 255, 255]);
+        data.extend(&[0, 0, 0, 1, 0, 0, 0, 0]);
+        data.extend(&[0, 12, 0, 0, 0, 0, 0, 28, 0, 0, 0, 0, 0, 0, 0, 1]);
+        data.extend(&[0, 0, 0, 120, 0, 0, 0, 122, 0, 0, 0, 4]);
+
+        let code_points: Vec<u16> = opentype_code_points(&data).into_iter().collect();
+
+        assert_eq!(code_points, vec![65, 66, 67, 120, 121, 122, 0xFFFF]);
+    }
+
+    #[test]
+    fn glyph_shape_builder_closes_contours() {
+        use ttf_parser::OutlineBuilder;
+
+        let mut builder = GlyphShapeBuilder::new(2.0);
+        builder.move_to(0.0, 0.0);
+        builder.line_to(10.0, 0.0);
+        builder.quad_to(10.0, 10.0, 0.0, 10.0);
+        builder.close();
+
+        assert_eq!(
+            builder.records,
+            vec![
+                swf::ShapeRecord::StyleChange(swf::StyleChangeData {
+                    move_to: Some((Twips::new(0), Twips::new(0))),
+                    fill_style_0: Some(1),
+                    fill_style_1: None,
+                    line_style: None,
+                    new_styles: None,
+                }),
+                swf::ShapeRecord::StraightEdge {
+                    delta_x: Twips::new(20),
+                    delta_y: Twips::new(0),
+                },
+                swf::ShapeRecord::CurvedEdge {
+                    control_delta_x: Twips::new(0),
+                    control_delta_y: Twips::new(-20),
+                    anchor_delta_x: Twips::new(-20),
+                    anchor_delta_y: Twips::new(0),
+                },
+                swf::ShapeRecord::StraightEdge {
+                    delta_x: Twips::new(0),
+                    delta_y: Twips::new(20),
+                },
+            ]
+        );
+    }
 }
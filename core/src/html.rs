@@ -1,14 +1,18 @@
 //! HTML related utilities
 
 mod dimensions;
+mod image;
 mod iterators;
 mod layout;
+mod stylesheet;
 mod text_format;
 
 pub use dimensions::BoxBounds;
 pub use dimensions::Position;
 pub use dimensions::Size;
+pub use image::TextImage;
 pub use layout::{LayoutBox, LayoutContent};
+pub use stylesheet::{CssDeclarations, StyleSheet};
 pub use text_format::{FormatSpans, TextFormat, TextSpan};
 
 #[cfg(test)]
@@ -0,0 +1,128 @@
+//! Images embedded in HTML text
+
+use crate::html::dimensions::Size;
+use crate::prelude::*;
+use crate::xml::{XMLDocument, XMLName, XMLNode};
+use gc_arena::MutationContext;
+
+/// The side of the text field an image is placed against.
+///
+/// Text flows around the opposite side of the image.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImageAlign {
+    Left,
+    Right,
+}
+
+/// An image embedded in HTML text with an `<img>` tag.
+///
+/// The image itself is a display object owned by the text field; this only
+/// describes where it goes and how large it should be.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextImage {
+    /// The text position the image was placed at.
+    pub position: usize,
+
+    /// The linkage name of a library symbol, or the URL of an external image
+    /// or movie.
+    pub src: String,
+
+    /// The instance name of the image, as used by `getImageReference`.
+    pub id: Option<String>,
+
+    /// The width to display the image at, in pixels.
+    ///
+    /// If unset, the natural width of the image is used.
+    pub width: Option<f64>,
+
+    /// The height to display the image at, in pixels.
+    ///
+    /// If unset, the natural height of the image is used.
+    pub height: Option<f64>,
+
+    /// The side of the text field the image is placed against.
+    pub align: ImageAlign,
+
+    /// The space left between the image and text to its side, in pixels.
+    pub hspace: f64,
+
+    /// The space left between the image and text below it, in pixels.
+    pub vspace: f64,
+}
+
+impl TextImage {
+    /// Read an image out of an `<img>` element.
+    pub fn from_html_element(node: XMLNode<'_>, position: usize) -> Self {
+        let attribute =
+            |name: &str| node.attribute_value_ignore_ascii_case(&XMLName::from_str(name));
+        let number = |name: &str| attribute(name).and_then(|v| v.trim().parse::<f64>().ok());
+
+        Self {
+            position,
+            src: attribute("src").unwrap_or_default(),
+            id: attribute("id").filter(|id| !id.is_empty()),
+            width: number("width"),
+            height: number("height"),
+            align: match attribute("align") {
+                Some(align) if align.trim().eq_ignore_ascii_case("right") => ImageAlign::Right,
+                _ => ImageAlign::Left,
+            },
+            hspace: number("hspace").unwrap_or(8.0),
+            vspace: number("vspace").unwrap_or(8.0),
+        }
+    }
+
+    /// Produce an `<IMG>` element describing this image.
+    pub fn raise_to_html<'gc>(
+        &self,
+        mc: MutationContext<'gc, '_>,
+        document: XMLDocument<'gc>,
+    ) -> XMLNode<'gc> {
+        let img = XMLNode::new_element(mc, "IMG", document);
+
+        img.set_attribute_value(mc, &XMLName::from_str("SRC"), &self.src);
+
+        if let Some(width) = self.width {
+            img.set_attribute_value(mc, &XMLName::from_str("WIDTH"), &width.to_string());
+        }
+
+        if let Some(height) = self.height {
+            img.set_attribute_value(mc, &XMLName::from_str("HEIGHT"), &height.to_string());
+        }
+
+        let align = match self.align {
+            ImageAlign::Left => "left",
+            ImageAlign::Right => "right",
+        };
+        img.set_attribute_value(mc, &XMLName::from_str("ALIGN"), align);
+
+        if let Some(id) = &self.id {
+            img.set_attribute_value(mc, &XMLName::from_str("ID"), id);
+        }
+
+        img.set_attribute_value(mc, &XMLName::from_str("HSPACE"), &self.hspace.to_string());
+        img.set_attribute_value(mc, &XMLName::from_str("VSPACE"), &self.vspace.to_string());
+
+        img
+    }
+
+    /// Whether this image needs to know its natural size to be laid out.
+    pub fn needs_natural_size(&self) -> bool {
+        self.width.is_none() || self.height.is_none()
+    }
+
+    /// Calculate the size to display the image at, given the bounds of the
+    /// image's content.
+    pub fn size(&self, natural_bounds: &BoundingBox) -> Size<Twips> {
+        let width = self
+            .width
+            .map(Twips::from_pixels)
+            .unwrap_or_else(|| natural_bounds.width());
+        let height = self
+            .height
+            .map(Twips::from_pixels)
+            .unwrap_or_else(|| natural_bounds.height());
+
+        Size::from((width.max(Twips::zero()), height.max(Twips::zero())))
+    }
+}
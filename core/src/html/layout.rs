@@ -5,13 +5,14 @@ use crate::context::UpdateContext;
 use crate::drawing::Drawing;
 use crate::font::{EvalParameters, Font};
 use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::image::{ImageAlign, TextImage};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::prelude::*;
 use crate::shape_utils::DrawCommand;
 use crate::tag_utils::SwfMovie;
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::cmp::{max, min};
 use std::sync::Arc;
-use swf::Twips;
 
 /// Draw an underline on a particular drawing.
 ///
@@ -33,6 +34,28 @@ fn draw_underline(drawing: &mut Drawing, starting_pos: Position<Twips>, width: T
     });
 }
 
+/// Split a piece of text at the position of every image placed within it.
+///
+/// Each segment of text is returned along with its position. There is always
+/// at least one segment, even if the text is empty.
+fn split_at_images<'a>(text: &'a str, start: usize, images: &[TextImage]) -> Vec<(&'a str, usize)> {
+    let mut segments = vec![];
+    let mut last_split = 0;
+
+    for image in images {
+        if image.position > start + last_split && image.position < start + text.len() {
+            let split = image.position - start;
+            if text.is_char_boundary(split) {
+                segments.push((&text[last_split..split], start + last_split));
+                last_split = split;
+            }
+        }
+    }
+
+    segments.push((&text[last_split..], start + last_split));
+    segments
+}
+
 /// Contains information relating to the current layout operation.
 pub struct LayoutContext<'a, 'gc> {
     /// The movie this layout context is pulling fonts from.
@@ -87,10 +110,39 @@ pub struct LayoutContext<'a, 'gc> {
 
     /// The total width of the text field being laid out.
     max_bounds: Twips,
+
+    /// The images embedded in the text being laid out.
+    images: &'a [TextImage],
+
+    /// The bounds of the content of each embedded image.
+    ///
+    /// Images without an entry here are treated as having no content.
+    image_bounds: &'a [BoundingBox],
+
+    /// The index of the next image to be placed.
+    next_image: usize,
+
+    /// The layout boxes of all images placed so far.
+    ///
+    /// Images are not part of any line, so they are kept apart from the other
+    /// boxes until layout has finished.
+    image_boxes: Vec<LayoutBox<'gc>>,
+
+    /// The images that text is currently flowing around.
+    ///
+    /// Each image is stored as the side it is placed against, the bottom of
+    /// the area it takes up, and the width of that area.
+    floats: Vec<(ImageAlign, Twips, Twips)>,
 }
 
 impl<'a, 'gc> LayoutContext<'a, 'gc> {
-    fn new(movie: Arc<SwfMovie>, max_bounds: Twips, text: &'a str) -> Self {
+    fn new(
+        movie: Arc<SwfMovie>,
+        max_bounds: Twips,
+        text: &'a str,
+        images: &'a [TextImage],
+        image_bounds: &'a [BoundingBox],
+    ) -> Self {
         Self {
             movie,
             cursor: Default::default(),
@@ -104,6 +156,11 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             current_line: 0,
             current_line_span: Default::default(),
             max_bounds,
+            images,
+            image_bounds,
+            next_image: 0,
+            image_boxes: Vec::new(),
+            floats: Vec::new(),
         }
     }
 
@@ -252,9 +309,11 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         let mut line_bounds = line_bounds.unwrap_or_else(Default::default);
 
+        let (left_float, right_float) = self.float_widths();
         let left_adjustment =
-            Self::left_alignment_offset(&self.current_line_span, self.is_first_line);
-        let right_adjustment = Twips::from_pixels(self.current_line_span.right_margin);
+            Self::left_alignment_offset(&self.current_line_span, self.is_first_line) + left_float;
+        let right_adjustment =
+            Twips::from_pixels(self.current_line_span.right_margin) + right_float;
 
         let misalignment =
             self.max_bounds - left_adjustment - right_adjustment - line_bounds.width();
@@ -515,23 +574,86 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ///
     /// Offsets returned by this function should not be considered final;
     fn wrap_dimensions(&self, current_span: &TextSpan) -> (Twips, Twips) {
-        let width = self.max_bounds - Twips::from_pixels(self.current_line_span.right_margin);
-        let offset = Self::left_alignment_offset(current_span, self.is_first_line);
+        let (left_float, right_float) = self.float_widths();
+        let width =
+            self.max_bounds - Twips::from_pixels(self.current_line_span.right_margin) - right_float;
+        let offset = Self::left_alignment_offset(current_span, self.is_first_line) + left_float;
 
         (width, offset + self.cursor.x())
     }
 
+    /// Calculate how much of the current line is taken up by images on its
+    /// left and right sides.
+    fn float_widths(&self) -> (Twips, Twips) {
+        let mut left = Twips::zero();
+        let mut right = Twips::zero();
+
+        for (align, bottom, width) in self.floats.iter() {
+            if *bottom > self.cursor.y() {
+                match align {
+                    ImageAlign::Left => left += *width,
+                    ImageAlign::Right => right += *width,
+                }
+            }
+        }
+
+        (left, right)
+    }
+
+    /// Place all images that occur at or before a given text position.
+    ///
+    /// Images are placed at the top of the current line, against the side of
+    /// the text field they are aligned to. Text on this and later lines flows
+    /// around them until their bottom edge has been passed.
+    fn place_images(&mut self, position: usize) {
+        while let Some(image) = self.images.get(self.next_image) {
+            if image.position > position {
+                break;
+            }
+
+            let size = image.size(
+                self.image_bounds
+                    .get(self.next_image)
+                    .unwrap_or(&Default::default()),
+            );
+            let hspace = Twips::from_pixels(image.hspace);
+            let vspace = Twips::from_pixels(image.vspace);
+            let (left_float, right_float) = self.float_widths();
+            let x = match image.align {
+                ImageAlign::Left => left_float,
+                ImageAlign::Right => self.max_bounds - right_float - size.width(),
+            };
+
+            let mut new_image = LayoutBox::from_image(self.next_image);
+            new_image.bounds =
+                BoxBounds::from_position_and_size(Position::from((x, self.cursor.y())), size);
+
+            self.floats.push((
+                image.align,
+                self.cursor.y() + size.height() + vspace,
+                size.width() + hspace,
+            ));
+            self.image_boxes.push(new_image);
+            self.next_image += 1;
+        }
+    }
+
     /// Destroy the layout context, returning the newly constructed layout list.
     fn end_layout(
         mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
+        self.place_images(std::usize::MAX);
         self.fixup_line(context, !self.has_line_break, true);
 
-        (
-            self.boxes,
-            self.exterior_bounds.unwrap_or_else(Default::default),
-        )
+        let mut exterior_bounds = self.exterior_bounds.unwrap_or_else(Default::default);
+        for image in self.image_boxes.iter() {
+            exterior_bounds += image.bounds;
+        }
+
+        self.boxes.append(&mut self.image_boxes);
+
+        (self.boxes, exterior_bounds)
     }
 
     fn is_start_of_line(&self) -> bool {
@@ -555,7 +677,8 @@ pub struct LayoutBox<'gc> {
 
 /// Represents different content modes of a given `LayoutBox`.
 ///
-/// Currently, a `LayoutBox` can contain `Text`, `Bullet`s, or a `Drawing`.
+/// Currently, a `LayoutBox` can contain `Text`, `Bullet`s, a `Drawing`, or an
+/// `Image`.
 #[derive(Clone, Debug, Collect)]
 #[collect(no_drop)]
 pub enum LayoutContent<'gc> {
@@ -609,6 +732,16 @@ pub enum LayoutContent<'gc> {
     /// layout box's bounds. The size of those bounds do not affect the
     /// rendering of the drawing.
     Drawing(Drawing),
+
+    /// A layout box containing an embedded image.
+    ///
+    /// The image is displayed by the text field at the position and size of
+    /// the layout box's bounds.
+    Image {
+        /// The index of the image within the `FormatSpans` that generated this
+        /// layout box.
+        index: usize,
+    },
 }
 
 impl<'gc> LayoutBox<'gc> {
@@ -652,10 +785,22 @@ impl<'gc> LayoutBox<'gc> {
         }
     }
 
+    /// Construct an image.
+    pub fn from_image(index: usize) -> Self {
+        Self {
+            bounds: Default::default(),
+            content: LayoutContent::Image { index },
+        }
+    }
+
     /// Construct a new layout hierarchy from text spans.
     ///
     /// The returned bounds will include both the text bounds itself, as well
     /// as left and right margins on any of the lines.
+    ///
+    /// `image_bounds` gives the bounds of the content of each image embedded
+    /// in the text, which is used to size images that don't specify their own
+    /// width or height.
     pub fn lower_from_text_spans(
         fs: &FormatSpans,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -663,8 +808,10 @@ impl<'gc> LayoutBox<'gc> {
         bounds: Twips,
         is_word_wrap: bool,
         is_device_font: bool,
+        image_bounds: &[BoundingBox],
     ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
-        let mut layout_context = LayoutContext::new(movie, bounds, fs.text());
+        let mut layout_context =
+            LayoutContext::new(movie, bounds, fs.text(), fs.images(), image_bounds);
 
         for (span_start, _end, span_text, span) in fs.iter_spans() {
             if let Some(font) = layout_context.resolve_font(context, &span, is_device_font) {
@@ -688,69 +835,76 @@ impl<'gc> LayoutBox<'gc> {
                         _ => {}
                     }
 
-                    let start = span_start + slice_start;
+                    let piece_start = span_start + slice_start;
 
-                    let mut last_breakpoint = 0;
+                    for (text, start) in split_at_images(text, piece_start, fs.images()) {
+                        layout_context.place_images(start);
 
-                    if is_word_wrap {
-                        let (mut width, mut offset) = layout_context.wrap_dimensions(&span);
+                        let mut last_breakpoint = 0;
 
-                        while let Some(breakpoint) = font.wrap_line(
-                            &text[last_breakpoint..],
-                            params,
-                            width,
-                            offset,
-                            layout_context.is_start_of_line(),
-                        ) {
-                            if breakpoint == 0 {
-                                layout_context.newline(context);
+                        if is_word_wrap {
+                            let (mut width, mut offset) = layout_context.wrap_dimensions(&span);
 
-                                let next_dim = layout_context.wrap_dimensions(&span);
+                            while let Some(breakpoint) = font.wrap_line(
+                                &text[last_breakpoint..],
+                                params,
+                                width,
+                                offset,
+                                layout_context.is_start_of_line(),
+                            ) {
+                                if breakpoint == 0 {
+                                    layout_context.newline(context);
 
-                                width = next_dim.0;
-                                offset = next_dim.1;
+                                    let next_dim = layout_context.wrap_dimensions(&span);
+
+                                    width = next_dim.0;
+                                    offset = next_dim.1;
+
+                                    if last_breakpoint >= text.len() {
+                                        break;
+                                    } else {
+                                        continue;
+                                    }
+                                }
 
+                                // This ensures that the space causing the line break
+                                // is included in the line it broke.
+                                let next_breakpoint =
+                                    min(last_breakpoint + breakpoint + 1, text.len());
+
+                                layout_context.append_text(
+                                    &text[last_breakpoint..next_breakpoint],
+                                    start + last_breakpoint,
+                                    start + next_breakpoint,
+                                    span,
+                                );
+
+                                last_breakpoint = next_breakpoint;
                                 if last_breakpoint >= text.len() {
                                     break;
-                                } else {
-                                    continue;
                                 }
+
+                                layout_context.newline(context);
+                                let next_dim = layout_context.wrap_dimensions(&span);
+
+                                width = next_dim.0;
+                                offset = next_dim.1;
                             }
+                        }
 
-                            // This ensures that the space causing the line break
-                            // is included in the line it broke.
-                            let next_breakpoint = min(last_breakpoint + breakpoint + 1, text.len());
+                        let span_end = text.len();
 
+                        if last_breakpoint < span_end {
                             layout_context.append_text(
-                                &text[last_breakpoint..next_breakpoint],
+                                &text[last_breakpoint..span_end],
                                 start + last_breakpoint,
-                                start + next_breakpoint,
+                                start + span_end,
                                 span,
                             );
-
-                            last_breakpoint = next_breakpoint;
-                            if last_breakpoint >= text.len() {
-                                break;
-                            }
-
-                            layout_context.newline(context);
-                            let next_dim = layout_context.wrap_dimensions(&span);
-
-                            width = next_dim.0;
-                            offset = next_dim.1;
                         }
                     }
 
-                    let span_end = text.len();
-
-                    if last_breakpoint < span_end {
-                        layout_context.append_text(
-                            &text[last_breakpoint..span_end],
-                            start + last_breakpoint,
-                            start + span_end,
-                            span,
-                        );
-                    }
+                    layout_context.place_images(piece_start + text.len());
                 }
             }
         }
@@ -794,6 +948,7 @@ impl<'gc> LayoutBox<'gc> {
                 color,
             } => Some(("\u{2022}", &text_format, *font, *params, color.0.clone())),
             LayoutContent::Drawing(..) => None,
+            LayoutContent::Image { .. } => None,
         }
     }
 
@@ -803,6 +958,7 @@ impl<'gc> LayoutBox<'gc> {
             LayoutContent::Text { .. } => None,
             LayoutContent::Bullet { .. } => None,
             LayoutContent::Drawing(drawing) => Some(drawing),
+            LayoutContent::Image { .. } => None,
         }
     }
 
@@ -811,6 +967,7 @@ impl<'gc> LayoutBox<'gc> {
             LayoutContent::Text { .. } => true,
             LayoutContent::Bullet { .. } => false,
             LayoutContent::Drawing(..) => false,
+            LayoutContent::Image { .. } => false,
         }
     }
 
@@ -819,6 +976,7 @@ impl<'gc> LayoutBox<'gc> {
             LayoutContent::Text { .. } => false,
             LayoutContent::Bullet { .. } => true,
             LayoutContent::Drawing(..) => false,
+            LayoutContent::Image { .. } => false,
         }
     }
 
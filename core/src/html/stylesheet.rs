@@ -0,0 +1,245 @@
+//! CSS stylesheets for HTML text
+
+use crate::xml::{XMLName, XMLNode};
+use gc_arena::Collect;
+use std::collections::BTreeMap;
+
+/// A list of CSS properties and their values.
+///
+/// Property names are stored in the camel-cased form that ActionScript uses
+/// (e.g. `fontFamily` instead of `font-family`). Values are stored exactly as
+/// written and are only interpreted when a style is applied to text.
+pub type CssDeclarations = Vec<(String, String)>;
+
+/// How an element participates in text layout, as set by the CSS `display`
+/// property.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Display {
+    /// The element's content continues on the current line.
+    Inline,
+
+    /// The element's content is followed by a line break.
+    Block,
+
+    /// The element and all of its content are hidden.
+    None,
+}
+
+impl Display {
+    /// Parse the value of a `display` property.
+    pub fn from_css(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "inline" => Some(Display::Inline),
+            "block" => Some(Display::Block),
+            "none" => Some(Display::None),
+            _ => None,
+        }
+    }
+
+    /// The display mode an element gets when no style says otherwise.
+    pub fn default_for_element(node: XMLNode<'_>) -> Self {
+        match node.tag_name() {
+            Some(name)
+                if name.eq_ignore_ascii_case(&XMLName::from_str("p"))
+                    || name.eq_ignore_ascii_case(&XMLName::from_str("li")) =>
+            {
+                Display::Block
+            }
+            _ => Display::Inline,
+        }
+    }
+}
+
+/// A set of named CSS styles, as used by `TextField.StyleSheet`.
+///
+/// Styles are keyed by their selector, which is either an element name
+/// (`p`), a class name (`.heading`), or a link pseudo-class (`a:hover`).
+/// Selectors are case-insensitive and stored in lowercase.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(require_static)]
+pub struct StyleSheet {
+    styles: BTreeMap<String, CssDeclarations>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Remove all styles from the stylesheet.
+    pub fn clear(&mut self) {
+        self.styles.clear();
+    }
+
+    /// Retrieve the style for a given selector.
+    pub fn style(&self, selector: &str) -> Option<&CssDeclarations> {
+        self.styles.get(&selector.to_ascii_lowercase())
+    }
+
+    /// Replace the style for a given selector, or remove it if `None` is
+    /// given.
+    pub fn set_style(&mut self, selector: &str, style: Option<CssDeclarations>) {
+        let selector = selector.to_ascii_lowercase();
+        match style {
+            Some(style) => self.styles.insert(selector, style),
+            None => self.styles.remove(&selector),
+        };
+    }
+
+    /// List the selectors of every style in the stylesheet.
+    pub fn style_names(&self) -> impl Iterator<Item = &str> {
+        self.styles.keys().map(|name| name.as_str())
+    }
+
+    /// Parse a CSS document and add all of its rules to the stylesheet.
+    ///
+    /// Rules replace any existing style with the same selector. If the
+    /// document cannot be parsed, the stylesheet is left unchanged and `false`
+    /// is returned.
+    pub fn parse_css(&mut self, css: &str) -> bool {
+        match parse_css(css) {
+            Some(rules) => {
+                for (selector, declarations) in rules {
+                    self.set_style(&selector, Some(declarations));
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Collect all declarations that apply to an HTML element, in cascade
+    /// order.
+    ///
+    /// Element styles come first, followed by class styles, and then (for
+    /// links) the `a:link` and `a:hover` pseudo-classes. Later declarations
+    /// take precedence over earlier ones.
+    pub fn declarations_for_element(
+        &self,
+        node: XMLNode<'_>,
+        is_hovered: bool,
+    ) -> Vec<&(String, String)> {
+        let mut declarations = vec![];
+        let tag_name = match node.tag_name() {
+            Some(name) => name.node_name().to_ascii_lowercase(),
+            None => return declarations,
+        };
+
+        if let Some(style) = self.styles.get(&tag_name) {
+            declarations.extend(style.iter());
+        }
+
+        if let Some(classes) = node.attribute_value_ignore_ascii_case(&XMLName::from_str("class")) {
+            for class in classes.split_whitespace() {
+                if let Some(style) = self.style(&format!(".{}", class)) {
+                    declarations.extend(style.iter());
+                }
+            }
+        }
+
+        if tag_name == "a" {
+            if let Some(style) = self.styles.get("a:link") {
+                declarations.extend(style.iter());
+            }
+
+            if is_hovered {
+                if let Some(style) = self.styles.get("a:hover") {
+                    declarations.extend(style.iter());
+                }
+            }
+        }
+
+        declarations
+    }
+}
+
+/// Convert a CSS property name into the form used by ActionScript.
+///
+/// Property names are case-insensitive. Hyphenated names are camel-cased, so
+/// `font-family` becomes `fontFamily`.
+fn property_name(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut uppercase_next = false;
+    for c in name.chars() {
+        if c == '-' {
+            uppercase_next = true;
+        } else if uppercase_next {
+            result.push(c.to_ascii_uppercase());
+            uppercase_next = false;
+        } else {
+            result.push(c.to_ascii_lowercase());
+        }
+    }
+    result
+}
+
+/// Remove all `/* ... */` comments from a CSS document.
+fn strip_comments(css: &str) -> Option<String> {
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        result.push_str(&rest[..start]);
+        let end = rest[start + 2..].find("*/")?;
+        rest = &rest[start + 2 + end + 2..];
+    }
+    result.push_str(rest);
+    Some(result)
+}
+
+/// Parse a CSS document into a list of selectors and their declarations.
+///
+/// Only the subset of CSS understood by Flash Player is supported: plain
+/// selector lists followed by a block of `name: value` declarations. Rules
+/// with several selectors are duplicated for each selector. Returns `None` if
+/// the document is malformed.
+pub fn parse_css(css: &str) -> Option<Vec<(String, CssDeclarations)>> {
+    let css = strip_comments(css)?;
+    let mut rules = vec![];
+    let mut rest = css.as_str();
+
+    while let Some(open) = rest.find('{') {
+        let close = open + rest[open..].find('}')?;
+        let selectors = &rest[..open];
+        let body = &rest[open + 1..close];
+        if body.contains('{') {
+            return None;
+        }
+
+        let mut declarations = CssDeclarations::new();
+        for declaration in body.split(';') {
+            if declaration.trim().is_empty() {
+                continue;
+            }
+
+            let colon = declaration.find(':')?;
+            let name = declaration[..colon].trim();
+            if name.is_empty() {
+                return None;
+            }
+
+            let name = property_name(name);
+            let value = declaration[colon + 1..].trim().to_string();
+            match declarations.iter_mut().find(|(n, _)| *n == name) {
+                Some(existing) => existing.1 = value,
+                None => declarations.push((name, value)),
+            }
+        }
+
+        for selector in selectors.split(',') {
+            let selector = selector.trim();
+            if selector.is_empty() {
+                return None;
+            }
+
+            rules.push((selector.to_string(), declarations.clone()));
+        }
+
+        rest = &rest[close + 1..];
+    }
+
+    if rest.trim().is_empty() {
+        Some(rules)
+    } else {
+        None
+    }
+}
@@ -1,7 +1,11 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::image::ImageAlign;
+use crate::html::stylesheet::{parse_css, StyleSheet};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::xml::XMLDocument;
+use gc_arena::rootless_arena;
 use swf::{Rectangle, Twips};
 
 #[test]
@@ -863,3 +867,221 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn css_parse_rules() {
+    let rules = parse_css(
+        "/* Headings */ .heading, h1 { font-size: 24px; FONT-weight: bold }\n\
+         p{color:#FF0000;}",
+    )
+    .expect("Valid CSS");
+
+    assert_eq!(
+        rules,
+        vec![
+            (
+                ".heading".to_string(),
+                vec![
+                    ("fontSize".to_string(), "24px".to_string()),
+                    ("fontWeight".to_string(), "bold".to_string())
+                ]
+            ),
+            (
+                "h1".to_string(),
+                vec![
+                    ("fontSize".to_string(), "24px".to_string()),
+                    ("fontWeight".to_string(), "bold".to_string())
+                ]
+            ),
+            (
+                "p".to_string(),
+                vec![("color".to_string(), "#FF0000".to_string())]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn css_parse_invalid() {
+    assert_eq!(parse_css("p { color: red"), None);
+    assert_eq!(parse_css("p { color }"), None);
+    assert_eq!(parse_css("p { color: red } span"), None);
+    assert_eq!(parse_css(", p { color: red }"), None);
+    assert_eq!(parse_css("p { color: red } /* unterminated"), None);
+}
+
+#[test]
+fn stylesheet_parse_css_is_atomic() {
+    let mut ss = StyleSheet::new();
+
+    assert!(ss.parse_css("P { color: #00FF00 }"));
+    assert!(!ss.parse_css("p { color: #0000FF } span {"));
+
+    assert_eq!(
+        ss.style("p"),
+        Some(&vec![("color".to_string(), "#00FF00".to_string())])
+    );
+    assert_eq!(ss.style_names().collect::<Vec<_>>(), vec!["p"]);
+}
+
+#[test]
+fn textformat_from_css() {
+    let declarations = vec![
+        ("color".to_string(), "#0F8".to_string()),
+        ("fontFamily".to_string(), "'Arial', sans-serif".to_string()),
+        ("fontSize".to_string(), "16.5px".to_string()),
+        ("fontWeight".to_string(), "bold".to_string()),
+        ("textAlign".to_string(), "center".to_string()),
+        ("textDecoration".to_string(), "underline".to_string()),
+        ("marginLeft".to_string(), "4".to_string()),
+        ("display".to_string(), "block".to_string()),
+    ];
+    let tf = TextFormat::from_css_declarations(
+        &declarations,
+        TextFormat {
+            italic: Some(true),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(tf.color, Some(swf::Color::from_rgb(0x00FF88, 0xFF)));
+    assert_eq!(tf.font, Some("Arial".to_string()));
+    assert_eq!(tf.size, Some(16.0));
+    assert_eq!(tf.bold, Some(true));
+    assert_eq!(tf.italic, Some(true));
+    assert_eq!(tf.align, Some(swf::TextAlign::Center));
+    assert_eq!(tf.underline, Some(true));
+    assert_eq!(tf.left_margin, Some(4.0));
+}
+
+#[test]
+fn formatspans_lower_from_css() {
+    rootless_arena(|mc| {
+        let mut ss = StyleSheet::new();
+        assert!(ss.parse_css(
+            ".title { font-weight: bold; display: block } \
+             .hidden { display: none } \
+             a:hover { text-decoration: underline }"
+        ));
+
+        let document = XMLDocument::new(mc);
+        document
+            .as_node()
+            .replace_with_str(
+                mc,
+                "<span class=\"title\">News</span><span class=\"hidden\">Secret</span>\
+                 <p>Read <a href=\"one\">one</a> or <a href=\"two\">two</a></p>",
+                false,
+            )
+            .expect("Parsed document");
+
+        let mut fs = FormatSpans::new();
+        let links = fs.lower_from_css(document, &ss, Some(1));
+
+        assert_eq!("News\nRead one or two\n", fs.text());
+        assert_eq!(vec![(10, 13), (17, 20)], links);
+
+        let title = fs.get_text_format(0, 4);
+        assert_eq!(title.bold, Some(true));
+
+        let link = fs.get_text_format(10, 13);
+        assert_eq!(link.url, Some("one".to_string()));
+        assert_eq!(link.underline, Some(false));
+
+        let hovered_link = fs.get_text_format(17, 20);
+        assert_eq!(hovered_link.url, Some("two".to_string()));
+        assert_eq!(hovered_link.underline, Some(true));
+    })
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn formatspans_lower_images() {
+    rootless_arena(|mc| {
+        let document = XMLDocument::new(mc);
+        document
+            .as_node()
+            .replace_with_str(
+                mc,
+                "<p>Intro <img src=\"logo\" id=\"pic\" width=\"20\" align=\"right\" \
+                 hspace=\"2\"/>text</p><p><img src=\"photo.swf\"/></p>",
+                false,
+            )
+            .expect("Parsed document");
+
+        let mut fs = FormatSpans::new();
+        fs.lower_from_html(document);
+
+        assert_eq!("Intro text\n\n", fs.text());
+
+        let images = fs.images();
+        assert_eq!(2, images.len());
+
+        assert_eq!(6, images[0].position);
+        assert_eq!("logo", images[0].src);
+        assert_eq!(Some("pic".to_string()), images[0].id);
+        assert_eq!(Some(20.0), images[0].width);
+        assert_eq!(None, images[0].height);
+        assert_eq!(ImageAlign::Right, images[0].align);
+        assert_eq!(2.0, images[0].hspace);
+        assert_eq!(8.0, images[0].vspace);
+
+        assert_eq!(11, images[1].position);
+        assert_eq!("photo.swf", images[1].src);
+        assert_eq!(None, images[1].id);
+        assert_eq!(ImageAlign::Left, images[1].align);
+    })
+}
+
+#[test]
+fn formatspans_replace_text_moves_images() {
+    rootless_arena(|mc| {
+        let document = XMLDocument::new(mc);
+        document
+            .as_node()
+            .replace_with_str(mc, "ab<img src=\"one\"/>cd<img src=\"two\"/>ef", false)
+            .expect("Parsed document");
+
+        let mut fs = FormatSpans::new();
+        fs.lower_from_html(document);
+
+        let positions = |fs: &FormatSpans| -> Vec<usize> {
+            fs.images().iter().map(|image| image.position).collect()
+        };
+        assert_eq!(vec![2, 4], positions(&fs));
+
+        // Inserting text at an image places it after the image.
+        fs.replace_text(2, 2, "xyz", None);
+        assert_eq!("abxyzcdef", fs.text());
+        assert_eq!(vec![2, 7], positions(&fs));
+
+        // Images within replaced text end up after the replacement.
+        fs.replace_text(1, 8, "-", None);
+        assert_eq!("a-f", fs.text());
+        assert_eq!(vec![2, 2], positions(&fs));
+    })
+}
+
+#[test]
+fn formatspans_raise_images() {
+    rootless_arena(|mc| {
+        let document = XMLDocument::new(mc);
+        document
+            .as_node()
+            .replace_with_str(
+                mc,
+                "ab<img src=\"one\" width=\"10\" height=\"5\"/>cd<img src=\"two\"/>",
+                false,
+            )
+            .expect("Parsed document");
+
+        let mut fs = FormatSpans::new();
+        fs.lower_from_html(document);
+
+        let mut raised = FormatSpans::new();
+        raised.lower_from_html(fs.raise_to_html(mc));
+
+        assert_eq!("abcd\n", raised.text());
+        assert_eq!(fs.images(), raised.images());
+    })
+}
@@ -3,7 +3,9 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
 use crate::context::UpdateContext;
+use crate::html::image::TextImage;
 use crate::html::iterators::TextSpanIter;
+use crate::html::stylesheet::{Display, StyleSheet};
 use crate::tag_utils::SwfMovie;
 use crate::xml::{Step, XMLDocument, XMLName, XMLNode};
 use gc_arena::{Collect, MutationContext};
@@ -83,6 +85,56 @@ fn process_html_entity(src: &str) -> Cow<str> {
     }
 }
 
+/// Parse the leading number of a CSS value, ignoring any trailing unit.
+///
+/// For example, both `12` and `12px` parse as 12.
+fn parse_css_number(value: &str) -> Option<f64> {
+    let end = value
+        .char_indices()
+        .find(|&(i, c)| !(c.is_ascii_digit() || c == '.' || (i == 0 && (c == '-' || c == '+'))))
+        .map(|(i, _)| i)
+        .unwrap_or_else(|| value.len());
+
+    value[..end].parse().ok()
+}
+
+/// Parse a CSS color in `#RRGGBB` or `#RGB` notation.
+fn parse_css_color(value: &str) -> Option<swf::Color> {
+    if !value.starts_with('#') {
+        return None;
+    }
+
+    let hex = &value[1..];
+    let rgb = match hex.len() {
+        6 => u32::from_str_radix(hex, 16).ok()?,
+        3 => {
+            let short = u32::from_str_radix(hex, 16).ok()?;
+            let (r, g, b) = ((short >> 8) & 0xF, (short >> 4) & 0xF, short & 0xF);
+            ((r * 0x11) << 16) | ((g * 0x11) << 8) | (b * 0x11)
+        }
+        _ => return None,
+    };
+
+    Some(swf::Color::from_rgb(rgb, 0xFF))
+}
+
+/// Parse a CSS `font-family` list.
+///
+/// Only the first font in the list is used. The generic family names are
+/// mapped onto Flash's device font names.
+fn parse_css_font_family(value: &str) -> Option<String> {
+    let font = value.split(',').next()?.trim();
+    let font = font.trim_matches(|c| c == '"' || c == '\'').trim();
+
+    match font.to_ascii_lowercase().as_str() {
+        "" => None,
+        "sans-serif" => Some("_sans".to_string()),
+        "serif" => Some("_serif".to_string()),
+        "mono" | "monospace" => Some("_typewriter".to_string()),
+        _ => Some(font.to_string()),
+    }
+}
+
 /// A set of text formatting options to be applied to some part, or the whole
 /// of, a given text field.
 ///
@@ -261,9 +313,9 @@ impl TextFormat {
 
     /// Extract text format parameters from presentational markup.
     ///
-    /// This assumes the "legacy" HTML path that only supports a handful of
-    /// elements. The "stylesheet" HTML path will also require CSS style
-    /// calculation for each node, followed by style conversion.
+    /// This only supports the handful of elements understood by the "legacy"
+    /// HTML path. The "stylesheet" HTML path applies CSS styles on top of
+    /// this; see `from_css_declarations`.
     ///
     /// This function accepts a `TextFormat`, which should be a text format
     /// loaded with all of the *currently existing* styles at this point in the
@@ -399,6 +451,97 @@ impl TextFormat {
         tf
     }
 
+    /// Extract text format parameters from a list of CSS declarations.
+    ///
+    /// Only the properties supported by `TextField.StyleSheet` are
+    /// recognized; anything else (including `display`) is ignored. Later
+    /// declarations override earlier ones. As with presentational markup, any
+    /// property not set by a declaration is retained from the given format.
+    pub fn from_css_declarations<'a>(
+        declarations: impl IntoIterator<Item = &'a (String, String)>,
+        mut tf: TextFormat,
+    ) -> Self {
+        for (name, value) in declarations {
+            let value = value.trim();
+            match name.as_str() {
+                "color" => {
+                    if let Some(color) = parse_css_color(value) {
+                        tf.color = Some(color);
+                    }
+                }
+                "fontFamily" => {
+                    if let Some(font) = parse_css_font_family(value) {
+                        tf.font = Some(font);
+                    }
+                }
+                "fontSize" => {
+                    if let Some(size) = parse_css_number(value).filter(|size| *size > 0.0) {
+                        tf.size = Some(size.trunc());
+                    }
+                }
+                "fontStyle" => match value.to_ascii_lowercase().as_str() {
+                    "normal" => tf.italic = Some(false),
+                    "italic" => tf.italic = Some(true),
+                    _ => {}
+                },
+                "fontWeight" => match value.to_ascii_lowercase().as_str() {
+                    "normal" => tf.bold = Some(false),
+                    "bold" => tf.bold = Some(true),
+                    _ => {}
+                },
+                "kerning" => match value.to_ascii_lowercase().as_str() {
+                    "true" => tf.kerning = Some(true),
+                    "false" => tf.kerning = Some(false),
+                    _ => {
+                        if let Some(kerning) = parse_css_number(value) {
+                            tf.kerning = Some(kerning != 0.0);
+                        }
+                    }
+                },
+                "leading" => {
+                    if let Some(leading) = parse_css_number(value) {
+                        tf.leading = Some(leading.trunc());
+                    }
+                }
+                "letterSpacing" => {
+                    if let Some(letter_spacing) = parse_css_number(value) {
+                        tf.letter_spacing = Some(letter_spacing);
+                    }
+                }
+                "marginLeft" => {
+                    if let Some(margin) = parse_css_number(value) {
+                        tf.left_margin = Some(margin.trunc());
+                    }
+                }
+                "marginRight" => {
+                    if let Some(margin) = parse_css_number(value) {
+                        tf.right_margin = Some(margin.trunc());
+                    }
+                }
+                "textAlign" => match value.to_ascii_lowercase().as_str() {
+                    "left" => tf.align = Some(swf::TextAlign::Left),
+                    "center" => tf.align = Some(swf::TextAlign::Center),
+                    "right" => tf.align = Some(swf::TextAlign::Right),
+                    "justify" => tf.align = Some(swf::TextAlign::Justify),
+                    _ => {}
+                },
+                "textDecoration" => match value.to_ascii_lowercase().as_str() {
+                    "none" => tf.underline = Some(false),
+                    "underline" => tf.underline = Some(true),
+                    _ => {}
+                },
+                "textIndent" => {
+                    if let Some(indent) = parse_css_number(value) {
+                        tf.indent = Some(indent.trunc());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        tf
+    }
+
     /// Construct a `TextFormat` AVM1 object from this text format object.
     pub fn as_avm1_object<'gc>(
         &self,
@@ -885,6 +1028,9 @@ pub struct FormatSpans {
     text: String,
     spans: Vec<TextSpan>,
     default_format: TextFormat,
+
+    /// Images embedded in the text, in order of their position.
+    images: Vec<TextImage>,
 }
 
 impl Default for FormatSpans {
@@ -899,6 +1045,7 @@ impl FormatSpans {
             text: "".to_string(),
             spans: vec![TextSpan::default()],
             default_format: TextFormat::default(),
+            images: vec![],
         }
     }
 
@@ -909,6 +1056,7 @@ impl FormatSpans {
             text: text.to_string(),
             spans: spans.to_vec(),
             default_format: Default::default(),
+            images: vec![],
         }
     }
 
@@ -925,6 +1073,16 @@ impl FormatSpans {
         &self.text
     }
 
+    /// Retrieve the images embedded in the text, in order of their position.
+    pub fn images(&self) -> &[TextImage] {
+        &self.images
+    }
+
+    /// Remove all embedded images.
+    pub fn clear_images(&mut self) {
+        self.images.clear();
+    }
+
     /// Retrieve the text span at a particular index.
     ///
     /// Text span indices are ephemeral and can change arbitrarily any time the
@@ -1209,6 +1367,14 @@ impl FormatSpans {
 
         self.text = new_string;
 
+        // Images stay attached to the text that follows them. Any image within
+        // the replaced text ends up after the replacement.
+        for image in self.images.iter_mut() {
+            if image.position > from {
+                image.position = image.position.max(to) - to + from + with.len();
+            }
+        }
+
         self.normalize();
     }
 
@@ -1238,6 +1404,7 @@ impl FormatSpans {
 
         self.text = "".to_string();
         self.spans = vec![];
+        self.images = vec![];
 
         for step in tree.as_node().walk().unwrap() {
             match step {
@@ -1266,6 +1433,22 @@ impl FormatSpans {
                             .unwrap()
                             .node_name()
                             .eq_ignore_ascii_case("br") => {}
+                Step::In(node)
+                    if node
+                        .tag_name()
+                        .unwrap()
+                        .node_name()
+                        .eq_ignore_ascii_case("img") =>
+                {
+                    self.images
+                        .push(TextImage::from_html_element(node, self.text.len()));
+                    format_stack.push(
+                        format_stack
+                            .last()
+                            .cloned()
+                            .unwrap_or_else(Default::default),
+                    );
+                }
                 Step::In(node) => format_stack.push(TextFormat::from_presentational_markup(
                     node,
                     format_stack
@@ -1310,6 +1493,127 @@ impl FormatSpans {
         }
     }
 
+    /// Lower an HTML tree into text-span representation, styling it with a
+    /// CSS stylesheet.
+    ///
+    /// Presentational markup is respected just as in `lower_from_html`, with
+    /// styles from the stylesheet taking precedence over it. The CSS
+    /// `display` property controls whether elements are followed by a line
+    /// break or hidden entirely.
+    ///
+    /// Links (`a` elements) are numbered in document order; the link numbered
+    /// `hovered_link` is additionally styled with `a:hover`. The text range
+    /// covered by each link is returned in the same order.
+    pub fn lower_from_css(
+        &mut self,
+        tree: XMLDocument<'_>,
+        style_sheet: &StyleSheet,
+        hovered_link: Option<usize>,
+    ) -> Vec<(usize, usize)> {
+        let mut format_stack = vec![(self.default_format.clone(), Display::Inline, None)];
+        let mut last_successful_format = None;
+        let mut hidden_depth = 0;
+        let mut links = vec![];
+
+        self.text = "".to_string();
+        self.spans = vec![];
+        self.images = vec![];
+
+        for step in tree.as_node().walk().unwrap() {
+            match step {
+                Step::In(node) | Step::Out(node)
+                    if node.tag_name().map_or(false, |name| {
+                        name.eq_ignore_ascii_case(&XMLName::from_str("br"))
+                            || name.eq_ignore_ascii_case(&XMLName::from_str("sbr"))
+                    }) =>
+                {
+                    if step.stepped_in() && hidden_depth == 0 {
+                        let format = format_stack.last().map(|(tf, _, _)| tf);
+                        self.replace_text(self.text.len(), self.text.len(), "\n", format);
+                    }
+                }
+                Step::In(node) => {
+                    let is_link = node.tag_name().map_or(false, |name| {
+                        name.eq_ignore_ascii_case(&XMLName::from_str("a"))
+                    });
+                    let link_index = if is_link {
+                        links.push((self.text.len(), self.text.len()));
+                        Some(links.len() - 1)
+                    } else {
+                        None
+                    };
+
+                    let parent_format = format_stack
+                        .last()
+                        .map(|(tf, _, _)| tf.clone())
+                        .unwrap_or_else(Default::default);
+                    let declarations = style_sheet.declarations_for_element(
+                        node,
+                        link_index.is_some() && link_index == hovered_link,
+                    );
+                    let tf = TextFormat::from_css_declarations(
+                        declarations.iter().cloned(),
+                        TextFormat::from_presentational_markup(node, parent_format),
+                    );
+                    let display = declarations
+                        .iter()
+                        .rev()
+                        .filter(|(name, _)| name == "display")
+                        .find_map(|(_, value)| Display::from_css(value))
+                        .unwrap_or_else(|| Display::default_for_element(node));
+
+                    let is_image = node.tag_name().map_or(false, |name| {
+                        name.eq_ignore_ascii_case(&XMLName::from_str("img"))
+                    });
+                    if hidden_depth > 0 || display == Display::None {
+                        hidden_depth += 1;
+                    } else if is_image {
+                        self.images
+                            .push(TextImage::from_html_element(node, self.text.len()));
+                    }
+
+                    format_stack.push((tf, display, link_index));
+                }
+                Step::Around(node) if node.is_text() => {
+                    if hidden_depth == 0 {
+                        let format = format_stack.last().map(|(tf, _, _)| tf);
+                        self.replace_text(
+                            self.text.len(),
+                            self.text.len(),
+                            &process_html_entity(&node.node_value().unwrap()),
+                            format,
+                        );
+                        last_successful_format = format.cloned();
+                    }
+                }
+                Step::Out(_) => {
+                    let (_tf, display, link_index) = match format_stack.pop() {
+                        Some(entry) => entry,
+                        None => continue,
+                    };
+
+                    if hidden_depth > 0 {
+                        hidden_depth -= 1;
+                    } else if display == Display::Block {
+                        self.replace_text(
+                            self.text.len(),
+                            self.text.len(),
+                            "\n",
+                            last_successful_format.as_ref(),
+                        );
+                    }
+
+                    if let Some(link_index) = link_index {
+                        links[link_index].1 = self.text.len();
+                    }
+                }
+                _ => {}
+            };
+        }
+
+        links
+    }
+
     #[allow(clippy::float_cmp)]
     pub fn raise_to_html<'gc>(&self, mc: MutationContext<'gc, '_>) -> XMLDocument<'gc> {
         let document = XMLDocument::new(mc);
@@ -1328,6 +1632,8 @@ impl FormatSpans {
         let mut last_b = None;
         let mut last_i = None;
         let mut last_u = None;
+        let mut next_image = 0;
+        let mut last_text_parent = root;
 
         for (start, _end, text, span) in self.iter_spans() {
             let ls = &last_span.unwrap();
@@ -1606,19 +1912,19 @@ impl FormatSpans {
                 }
 
                 let span_text = if last_bullet.is_some() {
-                    XMLNode::new_text(mc, line, document)
+                    line
                 } else {
                     let line_start = line.as_ptr() as usize - text.as_ptr() as usize;
-                    let line_with_newline = if line_start > 0 {
+                    if line_start > 0 {
                         text.get(line_start - 1..line.len() + 1).unwrap_or(line)
                     } else {
                         line
-                    };
-
-                    XMLNode::new_text(mc, line_with_newline, document)
+                    }
                 };
+                let span_text_start =
+                    start + (span_text.as_ptr() as usize - text.as_ptr() as usize);
 
-                last_u
+                last_text_parent = last_u
                     .or(last_i)
                     .or(last_b)
                     .or(last_a)
@@ -1626,15 +1932,68 @@ impl FormatSpans {
                     .or(last_paragraph)
                     .or(last_bullet)
                     .or(last_text_format_element)
-                    .unwrap_or(root)
-                    .append_child(mc, span_text)
-                    .unwrap();
+                    .unwrap_or(root);
+                self.raise_text_with_images(
+                    mc,
+                    document,
+                    last_text_parent,
+                    span_text,
+                    span_text_start,
+                    &mut next_image,
+                );
 
                 last_span = Some(span);
                 can_span_create_bullets = true;
             }
         }
 
+        // Images after the end of the text still need to be kept.
+        for image in self.images.get(next_image..).unwrap_or_default() {
+            last_text_parent
+                .append_child(mc, image.raise_to_html(mc, document))
+                .unwrap();
+        }
+
         document
     }
+
+    /// Append a run of text to an HTML node, interrupting it with `IMG`
+    /// elements for any images placed within it.
+    ///
+    /// `next_image` is the index of the first image that has yet to be
+    /// raised, and will be advanced past any images raised by this call.
+    fn raise_text_with_images<'gc>(
+        &self,
+        mc: MutationContext<'gc, '_>,
+        document: XMLDocument<'gc>,
+        mut parent: XMLNode<'gc>,
+        text: &str,
+        text_start: usize,
+        next_image: &mut usize,
+    ) {
+        let mut last_split = 0;
+
+        while let Some(image) = self.images.get(*next_image) {
+            let split = image.position.saturating_sub(text_start);
+            if split >= text.len() || !text.is_char_boundary(split) {
+                break;
+            }
+
+            if split > last_split {
+                let text_node = XMLNode::new_text(mc, &text[last_split..split], document);
+                parent.append_child(mc, text_node).unwrap();
+                last_split = split;
+            }
+
+            parent
+                .append_child(mc, image.raise_to_html(mc, document))
+                .unwrap();
+            *next_image += 1;
+        }
+
+        if last_split == 0 || last_split < text.len() {
+            let text_node = XMLNode::new_text(mc, &text[last_split..], document);
+            parent.append_child(mc, text_node).unwrap();
+        }
+    }
 }
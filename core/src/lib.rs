@@ -22,6 +22,7 @@ mod character;
 mod collect;
 pub mod color_transform;
 pub mod context;
+pub mod context_menu;
 mod drawing;
 mod ecma_conversions;
 pub mod events;
@@ -33,6 +34,7 @@ pub mod loader;
 mod player;
 mod prelude;
 pub mod property_map;
+pub mod security;
 pub mod shape_utils;
 pub mod string_utils;
 pub mod tag_utils;
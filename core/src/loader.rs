@@ -2,9 +2,11 @@
 
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::{Avm1, AvmString, Object, TObject, Value};
-use crate::avm2::Domain as Avm2Domain;
-use crate::backend::navigator::OwnedFuture;
-use crate::context::{ActionQueue, ActionType};
+use crate::avm2::{
+    root_error_handler as avm2_root_error_handler, Avm2, Domain as Avm2Domain, Object as Avm2Object,
+};
+use crate::backend::navigator::{OwnedFuture, Response};
+use crate::context::{ActionQueue, ActionType, UpdateContext};
 use crate::display_object::{DisplayObject, MorphShape, TDisplayObject};
 use crate::player::{Player, NEWEST_PLAYER_VERSION};
 use crate::property_map::PropertyMap;
@@ -55,6 +57,9 @@ pub enum Error {
     #[error("Network unavailable.")]
     NetworkUnavailable,
 
+    #[error("Access to {0} was denied by the security sandbox")]
+    SecurityViolation(String),
+
     // TODO: We can't support lifetimes on this error object yet (or we'll need some backends inside
     // the GC arena). We're losing info here. How do we fix that?
     #[error("Error running avm1 script: {0}")]
@@ -124,7 +129,7 @@ impl<'gc> LoadManager<'gc> {
     pub fn load_root_movie(
         &mut self,
         player: Weak<Mutex<Player>>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Response, Error>,
         url: String,
         parameters: PropertyMap<String>,
     ) -> OwnedFuture<(), Error> {
@@ -144,14 +149,16 @@ impl<'gc> LoadManager<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         target_clip: DisplayObject<'gc>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Response, Error>,
         url: String,
         target_broadcaster: Option<Object<'gc>>,
+        target_loader_info: Option<Avm2Object<'gc>>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::Movie {
             self_handle: None,
             target_clip,
             target_broadcaster,
+            target_loader_info,
             load_complete: false,
         };
         let handle = self.add_loader(loader);
@@ -192,7 +199,7 @@ impl<'gc> LoadManager<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         target_object: Object<'gc>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Response, Error>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::Form {
             self_handle: None,
@@ -213,7 +220,7 @@ impl<'gc> LoadManager<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         target_object: Object<'gc>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Response, Error>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::LoadVars {
             self_handle: None,
@@ -235,7 +242,7 @@ impl<'gc> LoadManager<'gc> {
         player: Weak<Mutex<Player>>,
         target_node: XMLNode<'gc>,
         active_clip: DisplayObject<'gc>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Response, Error>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::XML {
             self_handle: None,
@@ -277,6 +284,10 @@ pub enum Loader<'gc> {
         /// into.
         target_broadcaster: Option<Object<'gc>>,
 
+        /// AVM2 `LoaderInfo` to dispatch load events on, if this load was
+        /// started by an AVM2 `Loader`.
+        target_loader_info: Option<Avm2Object<'gc>>,
+
         /// Indicates that the load has completed.
         ///
         /// This flag exists to prevent a situation in which loading a movie
@@ -331,10 +342,12 @@ unsafe impl<'gc> Collect for Loader<'gc> {
             Loader::Movie {
                 target_clip,
                 target_broadcaster,
+                target_loader_info,
                 ..
             } => {
                 target_clip.trace(cc);
                 target_broadcaster.trace(cc);
+                target_loader_info.trace(cc);
             }
             Loader::Form { target_object, .. } => target_object.trace(cc),
             Loader::LoadVars { target_object, .. } => target_object.trace(cc),
@@ -362,7 +375,7 @@ impl<'gc> Loader<'gc> {
     pub fn root_movie_loader(
         &mut self,
         player: Weak<Mutex<Player>>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Response, Error>,
         mut url: String,
         parameters: PropertyMap<String>,
     ) -> OwnedFuture<(), Error> {
@@ -387,10 +400,13 @@ impl<'gc> Loader<'gc> {
                     Ok(())
                 })?;
 
-            let data = (fetch.await)
-                .and_then(|data| Ok((data.len(), SwfMovie::from_data(&data, Some(url.clone()))?)));
+            let data = match fetch.await {
+                Ok(response) => response.into_bytes().await,
+                Err(e) => Err(e),
+            };
+            let data = data.and_then(|data| Ok(SwfMovie::from_data(&data, Some(url.clone()))?));
 
-            if let Ok((_length, mut movie)) = data {
+            if let Ok(mut movie) = data {
                 for (key, value) in parameters.iter() {
                     movie.parameters_mut().insert(key, value.to_owned(), false);
                 }
@@ -413,7 +429,7 @@ impl<'gc> Loader<'gc> {
     pub fn movie_loader(
         &mut self,
         player: Weak<Mutex<Player>>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Response, Error>,
         mut url: String,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
@@ -432,12 +448,14 @@ impl<'gc> Loader<'gc> {
                 .update(|uc| -> Result<(), Error> {
                     url = uc.navigator.resolve_relative_url(&url).into_owned();
 
-                    let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
+                    let (clip, broadcaster, loader_info) = match uc.load_manager.get_loader(handle)
+                    {
                         Some(Loader::Movie {
                             target_clip,
                             target_broadcaster,
+                            target_loader_info,
                             ..
-                        }) => (*target_clip, *target_broadcaster),
+                        }) => (*target_clip, *target_broadcaster, *target_loader_info),
                         None => return Err(Error::Cancelled),
                         _ => unreachable!(),
                     };
@@ -459,12 +477,33 @@ impl<'gc> Loader<'gc> {
                         );
                     }
 
+                    dispatch_loader_info_event(uc, loader_info, "open");
+
                     Ok(())
                 })?;
 
-            let data = (fetch.await)
-                .and_then(|data| Ok((data.len(), SwfMovie::from_data(&data, Some(url.clone()))?)));
-            if let Ok((length, movie)) = data {
+            let mut status = None;
+            let data = async {
+                let response = fetch.await?;
+                status = response.status;
+                if !response.is_success() {
+                    return Err(Error::FetchError(response.url));
+                }
+
+                let data = read_response_body(response, |loaded, total| {
+                    player
+                        .lock()
+                        .expect("Could not lock player!!")
+                        .update(|uc| movie_loader_progress(uc, handle, loaded, total))
+                })
+                .await?;
+
+                Ok(SwfMovie::from_data(&data, Some(url.clone()))?)
+            }
+            .await;
+            let status = status.unwrap_or(0);
+
+            if let Ok(movie) = data {
                 let movie = Arc::new(movie);
 
                 player
@@ -477,31 +516,17 @@ impl<'gc> Loader<'gc> {
                             .library_for_movie_mut(movie.clone())
                             .set_avm2_domain(domain);
 
-                        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
-                            Some(Loader::Movie {
-                                target_clip,
-                                target_broadcaster,
-                                ..
-                            }) => (*target_clip, *target_broadcaster),
-                            None => return Err(Error::Cancelled),
-                            _ => unreachable!(),
-                        };
-
-                        if let Some(broadcaster) = broadcaster {
-                            Avm1::run_stack_frame_for_method(
-                                clip,
-                                broadcaster,
-                                NEWEST_PLAYER_VERSION,
-                                uc,
-                                "broadcastMessage",
-                                &[
-                                    "onLoadProgress".into(),
-                                    Value::Object(broadcaster),
-                                    length.into(),
-                                    length.into(),
-                                ],
-                            );
-                        }
+                        let (clip, broadcaster, loader_info) =
+                            match uc.load_manager.get_loader(handle) {
+                                Some(Loader::Movie {
+                                    target_clip,
+                                    target_broadcaster,
+                                    target_loader_info,
+                                    ..
+                                }) => (*target_clip, *target_broadcaster, *target_loader_info),
+                                None => return Err(Error::Cancelled),
+                                _ => unreachable!(),
+                            };
 
                         let mut mc = clip
                             .as_movie_clip()
@@ -531,10 +556,17 @@ impl<'gc> Loader<'gc> {
                                 NEWEST_PLAYER_VERSION,
                                 uc,
                                 "broadcastMessage",
-                                &["onLoadComplete".into(), Value::Object(broadcaster)],
+                                &[
+                                    "onLoadComplete".into(),
+                                    Value::Object(broadcaster),
+                                    status.into(),
+                                ],
                             );
                         }
 
+                        dispatch_loader_info_event(uc, loader_info, "init");
+                        dispatch_loader_info_event(uc, loader_info, "complete");
+
                         if let Some(Loader::Movie { load_complete, .. }) =
                             uc.load_manager.get_loader_mut(handle)
                         {
@@ -553,15 +585,17 @@ impl<'gc> Loader<'gc> {
                     .lock()
                     .expect("Could not lock player!!")
                     .update(|uc| -> Result<(), Error> {
-                        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
-                            Some(Loader::Movie {
-                                target_clip,
-                                target_broadcaster,
-                                ..
-                            }) => (*target_clip, *target_broadcaster),
-                            None => return Err(Error::Cancelled),
-                            _ => unreachable!(),
-                        };
+                        let (clip, broadcaster, loader_info) =
+                            match uc.load_manager.get_loader(handle) {
+                                Some(Loader::Movie {
+                                    target_clip,
+                                    target_broadcaster,
+                                    target_loader_info,
+                                    ..
+                                }) => (*target_clip, *target_broadcaster, *target_loader_info),
+                                None => return Err(Error::Cancelled),
+                                _ => unreachable!(),
+                            };
 
                         if let Some(broadcaster) = broadcaster {
                             Avm1::run_stack_frame_for_method(
@@ -574,10 +608,13 @@ impl<'gc> Loader<'gc> {
                                     "onLoadError".into(),
                                     Value::Object(broadcaster),
                                     "LoadNeverCompleted".into(),
+                                    status.into(),
                                 ],
                             );
                         }
 
+                        dispatch_loader_info_event(uc, loader_info, "ioError");
+
                         if let Some(Loader::Movie { load_complete, .. }) =
                             uc.load_manager.get_loader_mut(handle)
                         {
@@ -593,7 +630,7 @@ impl<'gc> Loader<'gc> {
     pub fn form_loader(
         &mut self,
         player: Weak<Mutex<Player>>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Response, Error>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::Form { self_handle, .. } => self_handle.expect("Loader not self-introduced"),
@@ -605,7 +642,11 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let data = fetch.await?;
+            let response = fetch.await?;
+            if !response.is_success() {
+                return Err(Error::FetchError(response.url));
+            }
+            let data = response.into_bytes().await?;
 
             // Fire the load handler.
             player.lock().unwrap().update(|uc| {
@@ -638,7 +679,7 @@ impl<'gc> Loader<'gc> {
     pub fn load_vars_loader(
         &mut self,
         player: Weak<Mutex<Player>>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Response, Error>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::LoadVars { self_handle, .. } => {
@@ -652,7 +693,28 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let data = fetch.await;
+            let mut status = None;
+            let data = async {
+                let response = fetch.await?;
+                status = response.status;
+                if !response.is_success() {
+                    return Err(Error::FetchError(response.url));
+                }
+
+                read_response_body(response, |loaded, total| {
+                    player.lock().unwrap().update(|uc| {
+                        let that = match uc.load_manager.get_loader(handle) {
+                            Some(&Loader::LoadVars { target_object, .. }) => target_object,
+                            None => return Err(Error::Cancelled),
+                            _ => return Err(Error::NotLoadVarsLoader),
+                        };
+
+                        set_avm1_bytes_progress(uc, that, loaded, total)
+                    })
+                })
+                .await
+            }
+            .await;
 
             // Fire the load handler.
             player.lock().unwrap().update(|uc| {
@@ -670,6 +732,11 @@ impl<'gc> Loader<'gc> {
 
                 match data {
                     Ok(data) => {
+                        if let Some(status) = status {
+                            let _ =
+                                that.call_method("onHTTPStatus", &[status.into()], &mut activation);
+                        }
+
                         // Fire the onData method with the loaded string.
                         let string_data = AvmString::new(
                             activation.context.gc_context,
@@ -679,9 +746,11 @@ impl<'gc> Loader<'gc> {
                     }
                     Err(_) => {
                         // TODO: Log "Error opening URL" trace similar to the Flash Player?
-                        // Simulate 404 HTTP status. This should probably be fired elsewhere
-                        // because a failed local load doesn't fire a 404.
-                        let _ = that.call_method("onHTTPStatus", &[404.into()], &mut activation);
+                        // Loads that never got an HTTP response simulate a 404 status. This
+                        // should probably be fired elsewhere because a failed local load
+                        // doesn't fire a 404.
+                        let status = status.unwrap_or(404);
+                        let _ = that.call_method("onHTTPStatus", &[status.into()], &mut activation);
 
                         // Fire the onData method with no data to indicate an unsuccessful load.
                         let _ = that.call_method("onData", &[Value::Undefined], &mut activation);
@@ -740,7 +809,7 @@ impl<'gc> Loader<'gc> {
     pub fn xml_loader(
         &mut self,
         player: Weak<Mutex<Player>>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Response, Error>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::XML { self_handle, .. } => self_handle.expect("Loader not self-introduced"),
@@ -752,7 +821,31 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let data = fetch.await;
+            let mut status = None;
+            let data = async {
+                let response = fetch.await?;
+                status = response.status;
+                if !response.is_success() {
+                    return Err(Error::FetchError(response.url));
+                }
+
+                read_response_body(response, |loaded, total| {
+                    player.lock().unwrap().update(|uc| {
+                        let mut node = match uc.load_manager.get_loader(handle) {
+                            Some(Loader::XML { target_node, .. }) => *target_node,
+                            None => return Err(Error::Cancelled),
+                            _ => unreachable!(),
+                        };
+
+                        let object =
+                            node.script_object(uc.gc_context, Some(uc.avm1.prototypes().xml_node));
+                        set_avm1_bytes_progress(uc, object, loaded, total)
+                    })
+                })
+                .await
+            }
+            .await;
+
             if let Ok(data) = data {
                 let xmlstring = String::from_utf8(data)?;
 
@@ -776,7 +869,7 @@ impl<'gc> Loader<'gc> {
                             NEWEST_PLAYER_VERSION,
                             uc,
                             "onHTTPStatus",
-                            &[200.into()],
+                            &[status.unwrap_or(200).into()],
                         );
 
                         Avm1::run_stack_frame_for_method(
@@ -813,7 +906,7 @@ impl<'gc> Loader<'gc> {
                             NEWEST_PLAYER_VERSION,
                             uc,
                             "onHTTPStatus",
-                            &[404.into()],
+                            &[status.unwrap_or(404).into()],
                         );
 
                         Avm1::run_stack_frame_for_method(
@@ -834,3 +927,98 @@ impl<'gc> Loader<'gc> {
         })
     }
 }
+
+/// The most memory reserved up front for a response body.
+///
+/// `Content-Length` comes from the server, so it can't be trusted to size the
+/// buffer; bodies larger than this grow as their chunks arrive.
+const MAX_PREALLOCATED_BODY: usize = 16 * 1024 * 1024;
+
+/// Read the whole body of a response, calling `on_progress` with the number of
+/// bytes loaded so far and the expected total after each chunk arrives.
+///
+/// If the response did not tell us how long it is, the total is reported as
+/// the number of bytes loaded so far.
+async fn read_response_body(
+    mut response: Response,
+    mut on_progress: impl FnMut(usize, usize) -> Result<(), Error>,
+) -> Result<Vec<u8>, Error> {
+    let total = response.content_length.map(|len| len as usize);
+    let mut data = Vec::with_capacity(total.unwrap_or(0).min(MAX_PREALLOCATED_BODY));
+
+    while let Some(chunk) = response.next_chunk().await {
+        data.extend_from_slice(&chunk?);
+        on_progress(data.len(), total.unwrap_or(data.len()))?;
+    }
+
+    Ok(data)
+}
+
+/// Report download progress to the listeners of a movie loader.
+fn movie_loader_progress<'gc>(
+    uc: &mut UpdateContext<'_, 'gc, '_>,
+    handle: Handle,
+    loaded: usize,
+    total: usize,
+) -> Result<(), Error> {
+    let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
+        Some(Loader::Movie {
+            target_clip,
+            target_broadcaster,
+            ..
+        }) => (*target_clip, *target_broadcaster),
+        None => return Err(Error::Cancelled),
+        _ => unreachable!(),
+    };
+
+    if let Some(broadcaster) = broadcaster {
+        Avm1::run_stack_frame_for_method(
+            clip,
+            broadcaster,
+            NEWEST_PLAYER_VERSION,
+            uc,
+            "broadcastMessage",
+            &[
+                "onLoadProgress".into(),
+                Value::Object(broadcaster),
+                loaded.into(),
+                total.into(),
+            ],
+        );
+    }
+
+    Ok(())
+}
+
+/// Update the hidden properties backing `getBytesLoaded` and `getBytesTotal`
+/// on an AVM1 `LoadVars` or `XML` object.
+fn set_avm1_bytes_progress<'gc>(
+    uc: &mut UpdateContext<'_, 'gc, '_>,
+    object: Object<'gc>,
+    loaded: usize,
+    total: usize,
+) -> Result<(), Error> {
+    let mut activation =
+        Activation::from_stub(uc.reborrow(), ActivationIdentifier::root("[Form Loader]"));
+
+    object.set("_bytesLoaded", loaded.into(), &mut activation)?;
+    object.set("_bytesTotal", total.into(), &mut activation)?;
+
+    Ok(())
+}
+
+/// Dispatch a load event on an AVM2 `LoaderInfo`, if there is one.
+fn dispatch_loader_info_event<'gc>(
+    uc: &mut UpdateContext<'_, 'gc, '_>,
+    loader_info: Option<Avm2Object<'gc>>,
+    event_type: &'static str,
+) {
+    if let Some(loader_info) = loader_info {
+        let event_proto = uc.avm2.prototypes().event;
+        let args = [event_type.into(), false.into()];
+
+        if let Err(e) = Avm2::dispatch_event(uc, event_proto, &args, loader_info) {
+            avm2_root_error_handler(uc, e);
+        }
+    }
+}
@@ -2,18 +2,30 @@ use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::debug::VariableDumper;
 use crate::avm1::globals::system::SystemProperties;
 use crate::avm1::object::Object;
-use crate::avm1::{Avm1, AvmString, ScriptObject, TObject, Timers, Value};
-use crate::avm2::{Avm2, Domain as Avm2Domain};
+use crate::avm1::{
+    Avm1, AvmString, LocalConnections, ScriptObject, TObject, Timers, Value, XmlSockets,
+};
+use crate::avm2::{
+    root_error_handler as avm2_root_error_handler, Avm2, Domain as Avm2Domain, Event as Avm2Event,
+    Value as Avm2Value,
+};
 use crate::backend::input::{InputBackend, MouseCursor};
+use crate::backend::local_connection::{LocalConnectionBackend, LocalConnectionBroker};
 use crate::backend::locale::LocaleBackend;
 use crate::backend::navigator::{NavigatorBackend, RequestOptions};
+use crate::backend::socket::SocketBackend;
 use crate::backend::storage::StorageBackend;
+use crate::backend::video::VideoBackend;
 use crate::backend::{
-    audio::AudioBackend, log::LogBackend, render::Letterbox, render::RenderBackend,
+    audio::{AudioBackend, AudioManager},
+    log::LogBackend,
+    render::Letterbox,
+    render::RenderBackend,
 };
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
+use crate::context_menu::{self, ContextMenuCallback, ContextMenuItem, ContextMenuState, Zoom};
 use crate::display_object::{EditText, MorphShape, MovieClip};
-use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, PlayerEvent};
+use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, KeyLocation, PlayerEvent};
 use crate::external::Value as ExternalValue;
 use crate::external::{ExternalInterface, ExternalInterfaceProvider};
 use crate::focus_tracker::FocusTracker;
@@ -21,6 +33,7 @@ use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::prelude::*;
 use crate::property_map::PropertyMap;
+use crate::security::{Sandbox, ScriptPermissions};
 use crate::tag_utils::SwfMovie;
 use crate::transform::TransformStack;
 use crate::vminterface::Instantiator;
@@ -34,6 +47,7 @@ use std::convert::TryFrom;
 use std::ops::DerefMut;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
+use url::Url;
 
 pub static DEVICE_FONT_TAG: &[u8] = include_bytes!("../assets/noto-sans-definefont3.bin");
 
@@ -82,11 +96,23 @@ struct GcRootData<'gc> {
     /// Timed callbacks created with `setInterval`/`setTimeout`.
     timers: Timers<'gc>,
 
+    /// Open connections created with `XMLSocket`.
+    xml_sockets: XmlSockets<'gc>,
+
+    /// `LocalConnection` objects that are listening or waiting for a message status.
+    local_connections: LocalConnections<'gc>,
+
     /// External interface for (for example) JavaScript <-> ActionScript interaction
     external_interface: ExternalInterface<'gc>,
 
     /// A tracker for the current keyboard focused element
     focus_tracker: FocusTracker<'gc>,
+
+    /// Manager of active sound instances.
+    audio_manager: AudioManager<'gc>,
+
+    /// The context menu most recently built for the host, if any.
+    current_context_menu: Option<ContextMenuState<'gc>>,
 }
 
 impl<'gc> GcRootData<'gc> {
@@ -106,7 +132,11 @@ impl<'gc> GcRootData<'gc> {
         &mut HashMap<String, Object<'gc>>,
         &mut Vec<EditText<'gc>>,
         &mut Timers<'gc>,
+        &mut XmlSockets<'gc>,
+        &mut LocalConnections<'gc>,
         &mut ExternalInterface<'gc>,
+        &mut AudioManager<'gc>,
+        &mut Option<ContextMenuState<'gc>>,
     ) {
         (
             &mut self.levels,
@@ -119,7 +149,11 @@ impl<'gc> GcRootData<'gc> {
             &mut self.shared_objects,
             &mut self.unbound_text_fields,
             &mut self.timers,
+            &mut self.xml_sockets,
+            &mut self.local_connections,
             &mut self.external_interface,
+            &mut self.audio_manager,
+            &mut self.current_context_menu,
         )
     }
 }
@@ -134,6 +168,9 @@ type Input = Box<dyn InputBackend>;
 type Storage = Box<dyn StorageBackend>;
 type Locale = Box<dyn LocaleBackend>;
 type Log = Box<dyn LogBackend>;
+type Video = Box<dyn VideoBackend>;
+type Socket = Box<dyn SocketBackend>;
+type LocalConnection = Box<dyn LocalConnectionBackend>;
 
 pub struct Player {
     /// The version of the player we're emulating.
@@ -159,10 +196,20 @@ pub struct Player {
     input: Input,
     locale: Locale,
     log: Log,
+    video: Video,
+    socket: Socket,
+    local_connection: LocalConnection,
     transform_stack: TransformStack,
     view_matrix: Matrix,
     inverse_view_matrix: Matrix,
 
+    /// The zoom chosen from the context menu, applied to the stage before it
+    /// is scaled into the viewport.
+    zoom_matrix: Matrix,
+
+    /// The point of the stage that was right-clicked to open the context menu.
+    context_menu_point: (Twips, Twips),
+
     storage: Storage,
 
     rng: SmallRng,
@@ -187,6 +234,9 @@ pub struct Player {
 
     system: SystemProperties,
 
+    /// The domains that each movie has allowed to script it.
+    script_permissions: ScriptPermissions,
+
     /// The current instance ID. Used to generate default `instanceN` names.
     instance_counter: i32,
 
@@ -206,6 +256,7 @@ pub struct Player {
 }
 
 impl Player {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         renderer: Renderer,
         audio: Audio,
@@ -213,7 +264,9 @@ impl Player {
         input: Input,
         storage: Storage,
         locale: Locale,
+        video: Video,
         log: Log,
+        socket: Socket,
     ) -> Result<Arc<Mutex<Self>>, Error> {
         let fake_movie = Arc::new(SwfMovie::empty(NEWEST_PLAYER_VERSION));
         let movie_width = 550;
@@ -237,6 +290,8 @@ impl Player {
             transform_stack: TransformStack::new(),
             view_matrix: Default::default(),
             inverse_view_matrix: Default::default(),
+            zoom_matrix: Default::default(),
+            context_menu_point: Default::default(),
 
             rng: SmallRng::from_seed([0u8; 16]), // TODO(Herschel): Get a proper seed on all platforms.
 
@@ -255,8 +310,12 @@ impl Player {
                         shared_objects: HashMap::new(),
                         unbound_text_fields: Vec::new(),
                         timers: Timers::new(),
+                        xml_sockets: XmlSockets::new(),
+                        local_connections: LocalConnections::new(),
                         external_interface: ExternalInterface::new(),
                         focus_tracker: FocusTracker::new(gc_context),
+                        audio_manager: AudioManager::new(),
+                        current_context_menu: None,
                     },
                 ))
             }),
@@ -280,8 +339,12 @@ impl Player {
             input,
             locale,
             log,
+            video,
+            socket,
+            local_connection: Box::new(LocalConnectionBroker::new().endpoint()),
             self_reference: None,
             system: SystemProperties::default(),
+            script_permissions: ScriptPermissions::new(),
             instance_counter: 0,
             time_til_next_timer: None,
             storage,
@@ -300,7 +363,7 @@ impl Player {
             );
             context.levels.insert(0u32, fake_root.into());
 
-            Avm2::load_player_globals(context)
+            Avm2::load_player_globals(context).map_err(|e| e.to_string())
         })?;
 
         player.build_matrices();
@@ -348,6 +411,14 @@ impl Player {
         self.movie_width = movie.width();
         self.movie_height = movie.height();
         self.frame_rate = movie.header().frame_rate.into();
+
+        let sandbox_type = self.system.sandbox_type_for_movie(&movie);
+        info!("Playing in the {} sandbox", sandbox_type);
+        self.system.sandbox_type = sandbox_type;
+        let origin = movie.url().and_then(|url| Url::parse(url).ok());
+        self.navigator
+            .set_sandbox(Sandbox::new(sandbox_type, origin));
+
         self.swf = movie;
         self.instance_counter = 0;
 
@@ -445,6 +516,8 @@ impl Player {
             }
 
             self.update_timers(dt);
+            self.update_sockets();
+            self.update_local_connections();
             self.audio.tick();
         }
     }
@@ -512,6 +585,7 @@ impl Player {
         if cfg!(feature = "avm_debug") {
             if let PlayerEvent::KeyDown {
                 key_code: KeyCode::V,
+                ..
             } = event
             {
                 if self.input.is_key_down(KeyCode::Control) && self.input.is_key_down(KeyCode::Alt)
@@ -548,6 +622,7 @@ impl Player {
 
             if let PlayerEvent::KeyDown {
                 key_code: KeyCode::D,
+                ..
             } = event
             {
                 if self.input.is_key_down(KeyCode::Control) && self.input.is_key_down(KeyCode::Alt)
@@ -595,7 +670,7 @@ impl Player {
             }
 
             // Special keys have custom values for keyPress.
-            PlayerEvent::KeyDown { key_code } => {
+            PlayerEvent::KeyDown { key_code, .. } => {
                 if let Some(key_code) = crate::events::key_code_to_button_key_code(key_code) {
                     Some(ClipEvent::KeyPress { key_code })
                 } else {
@@ -627,6 +702,82 @@ impl Player {
             });
         }
 
+        // Keyboard shortcuts such as copy and paste are handled by the focused text field.
+        if let PlayerEvent::KeyDown { key_code, .. } = event {
+            if self.input.is_key_down(KeyCode::Control) {
+                self.mutate_with_update_context(|context| {
+                    if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
+                        text.text_control_input(key_code, context);
+                    }
+                });
+            }
+        }
+
+        // Tab moves keyboard focus, and Enter or Space activate a button focused with the keyboard.
+        match event {
+            PlayerEvent::KeyDown {
+                key_code: KeyCode::Tab,
+                ..
+            } => {
+                let reverse = self.input.is_key_down(KeyCode::Shift);
+                self.mutate_with_update_context(|context| {
+                    let tracker = context.focus_tracker;
+                    tracker.cycle(context, reverse);
+                });
+                needs_render = true;
+            }
+            PlayerEvent::KeyDown {
+                key_code: KeyCode::Return,
+                ..
+            }
+            | PlayerEvent::KeyDown {
+                key_code: KeyCode::Space,
+                ..
+            } => {
+                self.mutate_with_update_context(|context| {
+                    let tracker = context.focus_tracker;
+                    if let Some(focus) = tracker.get() {
+                        if tracker.is_highlighted() && focus.as_edit_text().is_none() {
+                            focus.handle_clip_event(context, ClipEvent::Press);
+                        }
+                    }
+                });
+                needs_render = true;
+            }
+            PlayerEvent::KeyUp {
+                key_code: KeyCode::Return,
+                ..
+            }
+            | PlayerEvent::KeyUp {
+                key_code: KeyCode::Space,
+                ..
+            } => {
+                self.mutate_with_update_context(|context| {
+                    let tracker = context.focus_tracker;
+                    if let Some(focus) = tracker.get() {
+                        if tracker.is_highlighted() && focus.as_edit_text().is_none() {
+                            focus.handle_clip_event(context, ClipEvent::Release);
+                        }
+                    }
+                });
+                needs_render = true;
+            }
+            PlayerEvent::MouseDown { .. } => {
+                self.mutate_with_update_context(|context| {
+                    let tracker = context.focus_tracker;
+                    tracker.clear_highlight(context);
+                });
+            }
+            _ => (),
+        }
+
+        if let PlayerEvent::KeyDown { .. } = event {
+            self.mutate_with_update_context(|context| {
+                let tracker = context.focus_tracker;
+                tracker.set_key_target(context);
+            });
+        }
+
         // Propagte clip events.
         self.mutate_with_update_context(|context| {
             let (clip_event, listener) = match event {
@@ -650,7 +801,11 @@ impl Player {
                 ),
                 PlayerEvent::MouseWheel { delta } => {
                     let delta = Value::from(delta.lines());
-                    (None, Some(("Mouse", "onMouseWheel", vec![delta])))
+                    let target = context
+                        .mouse_hovered_object
+                        .map(|node| node.object())
+                        .unwrap_or(Value::Undefined);
+                    (None, Some(("Mouse", "onMouseWheel", vec![delta, target])))
                 }
                 _ => (None, None),
             };
@@ -685,6 +840,72 @@ impl Player {
                 }
             }
 
+            // Fire AVM2 input events on the hovered or focused object.
+            match event {
+                PlayerEvent::MouseMove { .. } => {
+                    let target = Self::avm2_event_target(context, context.mouse_hovered_object);
+                    Self::dispatch_avm2_mouse_event(
+                        context,
+                        "mouseMove",
+                        true,
+                        target,
+                        is_mouse_down,
+                        0,
+                    );
+                }
+                PlayerEvent::MouseDown { .. } => {
+                    let target = Self::avm2_event_target(context, context.mouse_hovered_object);
+                    Self::dispatch_avm2_mouse_event(context, "mouseDown", true, target, true, 0);
+                }
+                PlayerEvent::MouseUp { .. } => {
+                    let target = Self::avm2_event_target(context, context.mouse_hovered_object);
+                    Self::dispatch_avm2_mouse_event(context, "mouseUp", true, target, false, 0);
+                    Self::dispatch_avm2_mouse_event(context, "click", true, target, false, 0);
+                }
+                PlayerEvent::MouseWheel { delta } => {
+                    let target = Self::avm2_event_target(context, context.mouse_hovered_object);
+                    Self::dispatch_avm2_mouse_event(
+                        context,
+                        "mouseWheel",
+                        true,
+                        target,
+                        is_mouse_down,
+                        delta.lines() as i32,
+                    );
+                }
+                PlayerEvent::KeyDown {
+                    key_code,
+                    key_char,
+                    key_location,
+                } => {
+                    let target = Self::avm2_event_target(context, context.focus_tracker.get());
+                    Self::dispatch_avm2_keyboard_event(
+                        context,
+                        "keyDown",
+                        target,
+                        key_code,
+                        key_char,
+                        key_location,
+                    );
+                }
+                PlayerEvent::KeyUp {
+                    key_code,
+                    key_char,
+                    key_location,
+                } => {
+                    let target = Self::avm2_event_target(context, context.focus_tracker.get());
+                    Self::dispatch_avm2_keyboard_event(
+                        context,
+                        "keyUp",
+                        target,
+                        key_code,
+                        key_char,
+                        key_location,
+                    );
+                }
+                _ => (),
+            }
+
             match event {
                 PlayerEvent::MouseDown { .. } => {
                     is_mouse_down = true;
@@ -702,6 +923,17 @@ impl Player {
                     }
                 }
 
+                PlayerEvent::MouseWheel { delta } => {
+                    if let Some(text) = context
+                        .mouse_hovered_object
+                        .and_then(|node| node.as_edit_text())
+                    {
+                        if text.mouse_wheel_scroll(delta.lines(), context) {
+                            needs_render = true;
+                        }
+                    }
+                }
+
                 _ => (),
             }
 
@@ -741,6 +973,155 @@ impl Player {
         });
     }
 
+    /// Build the context menu for a right-click at the given point of the
+    /// viewport, in pixels.
+    ///
+    /// The menu comes from the `menu` property of the clicked object or its
+    /// nearest ancestor that has one, and its `onSelect` handler is called
+    /// before the items are read. The returned items should be shown by the
+    /// host, which then calls `run_context_menu_callback` with the index of
+    /// the chosen item.
+    pub fn prepare_context_menu(&mut self, x: f64, y: f64) -> Vec<ContextMenuItem> {
+        let point = self.inverse_view_matrix * (Twips::from_pixels(x), Twips::from_pixels(y));
+        self.context_menu_point = point;
+        let is_zoomed = self.zoom_matrix != Matrix::identity();
+
+        self.update(|context| {
+            let root = match context.levels.get(&0) {
+                Some(root) => *root,
+                None => return Vec::new(),
+            };
+
+            // Any object under the mouse counts, even if it doesn't handle mouse events.
+            let mut hit = None;
+            for (_depth, level) in context.levels.clone().iter().rev() {
+                hit = context_menu::hit_test(*level, context, point);
+                if hit.is_some() {
+                    break;
+                }
+            }
+
+            // The target is the nearest clip, button or text field that was clicked.
+            let target = std::iter::successors(hit, |dobj| dobj.parent())
+                .find(|dobj| matches!(dobj.object(), Value::Object(_)))
+                .unwrap_or(root);
+
+            let version = context.swf.version();
+            let globals = context.avm1.global_object_cell();
+            let mut activation = Activation::from_nothing(
+                context.reborrow(),
+                ActivationIdentifier::root("[Context Menu]"),
+                version,
+                globals,
+                target,
+            );
+
+            let mut menu = None;
+            let mut node = Some(target);
+            while let Some(dobj) = node {
+                if let Value::Object(object) = dobj.object() {
+                    if let Ok(Value::Object(object_menu)) = object.get("menu", &mut activation) {
+                        menu = Some(object_menu);
+                        break;
+                    }
+                }
+                node = dobj.parent();
+            }
+
+            if let Some(menu) = menu {
+                Avm1::run_stack_frame_for_method(
+                    target,
+                    menu,
+                    version,
+                    &mut activation.context,
+                    "onSelect",
+                    &[target.object(), menu.into()],
+                );
+            }
+
+            let state = crate::avm1::globals::context_menu::make_context_menu_state(
+                menu,
+                target,
+                is_zoomed,
+                &mut activation,
+            )
+            .unwrap_or_else(|_| {
+                let mut state = ContextMenuState::new();
+                state.build_builtin_items(Default::default(), root.as_movie_clip(), is_zoomed);
+                state
+            });
+
+            let items = state.info().to_vec();
+            *activation.context.current_context_menu = Some(state);
+            items
+        })
+    }
+
+    /// Run the action of the item at `index` of the menu most recently
+    /// returned by `prepare_context_menu`.
+    ///
+    /// Disabled items and out-of-range indices are ignored.
+    pub fn run_context_menu_callback(&mut self, index: usize) {
+        let zoom = self.update(|context| {
+            let menu = match context.current_context_menu.take() {
+                Some(menu) => menu,
+                None => return None,
+            };
+
+            let enabled = menu.info().get(index).map(|item| item.enabled);
+            let callback = match (enabled, menu.callback(index)) {
+                (Some(true), Some(callback)) => callback,
+                _ => return None,
+            };
+
+            match callback {
+                ContextMenuCallback::Avm1 { target, item } => {
+                    Avm1::run_stack_frame_for_method(
+                        target,
+                        item,
+                        context.swf.version(),
+                        context,
+                        "onSelect",
+                        &[target.object(), item.into()],
+                    );
+                }
+                ContextMenuCallback::Zoom(zoom) => return Some(zoom),
+                _ => {
+                    context_menu::run_builtin_callback(context, callback);
+                }
+            }
+            None
+        });
+
+        if let Some(zoom) = zoom {
+            self.zoom(zoom);
+        }
+    }
+
+    /// Zooms the stage in or out around the point that was right-clicked to
+    /// open the context menu.
+    fn zoom(&mut self, zoom: Zoom) {
+        let (x, y) = self.context_menu_point;
+        let scale = match zoom {
+            Zoom::In => 2.0,
+            Zoom::Out => 0.5,
+            Zoom::ShowAll => 1.0,
+        };
+        let zoom_matrix = self.zoom_matrix
+            * Matrix::translate(x, y)
+            * Matrix::scale(scale, scale)
+            * Matrix::translate(Twips::new(-x.get()), Twips::new(-y.get()));
+
+        // The stage can't be zoomed out further than showing all of it.
+        self.zoom_matrix = if zoom != Zoom::ShowAll && zoom_matrix.a > 1.0 {
+            zoom_matrix
+        } else {
+            Matrix::identity()
+        };
+        self.build_matrices();
+        self.needs_render = true;
+    }
+
     /// Checks to see if a recent update has caused the current mouse hover
     /// node to change.
     fn update_roll_over(&mut self) -> bool {
@@ -764,19 +1145,29 @@ impl Player {
 
             let cur_hovered = context.mouse_hovered_object;
 
-            if cur_hovered.map(|d| d.as_ptr()) != new_hovered.map(|d| d.as_ptr()) {
+            let hover_changed = if cur_hovered.map(|d| d.as_ptr())
+                != new_hovered.map(|d| d.as_ptr())
+            {
                 // RollOut of previous node.
                 if let Some(node) = cur_hovered {
                     if !node.removed() {
                         node.handle_clip_event(context, ClipEvent::RollOut);
+
+                        let target = Self::avm2_event_target(context, Some(node));
+                        Self::dispatch_avm2_mouse_event(
+                            context, "mouseOut", true, target, false, 0,
+                        );
+                        Self::dispatch_avm2_roll_event(context, "rollOut", Some(node), new_hovered);
                     }
                 }
 
                 // RollOver on new node.I still
-                new_cursor = MouseCursor::Arrow;
                 if let Some(node) = new_hovered {
-                    new_cursor = node.mouse_cursor();
                     node.handle_clip_event(context, ClipEvent::RollOver);
+
+                    let target = Self::avm2_event_target(context, Some(node));
+                    Self::dispatch_avm2_mouse_event(context, "mouseOver", true, target, false, 0);
+                    Self::dispatch_avm2_roll_event(context, "rollOver", Some(node), cur_hovered);
                 }
 
                 context.mouse_hovered_object = new_hovered;
@@ -785,7 +1176,16 @@ impl Player {
                 true
             } else {
                 false
-            }
+            };
+
+            // The cursor can change while moving within an object, e.g. over a
+            // hyperlink in a text field.
+            new_cursor = context
+                .mouse_hovered_object
+                .map(|node| node.mouse_cursor(context))
+                .unwrap_or(MouseCursor::Arrow);
+
+            hover_changed
         });
 
         // Update mouse cursor if it has changed.
@@ -797,6 +1197,151 @@ impl Player {
         hover_changed
     }
 
+    /// Find the display object that should receive an AVM2 input event.
+    ///
+    /// This is the nearest ancestor of `node` (or `node` itself) that has an
+    /// AVM2 object, falling back to the root level if there is none.
+    fn avm2_event_target<'gc>(
+        context: &UpdateContext<'_, 'gc, '_>,
+        node: Option<DisplayObject<'gc>>,
+    ) -> Option<DisplayObject<'gc>> {
+        let mut node = node;
+        while let Some(dobj) = node {
+            if let Avm2Value::Object(_) = dobj.object2() {
+                return Some(dobj);
+            }
+
+            node = dobj.parent();
+        }
+
+        context
+            .levels
+            .get(&0)
+            .copied()
+            .filter(|root| matches!(root.object2(), Avm2Value::Object(_)))
+    }
+
+    /// Dispatch an AVM2 `rollOut` or `rollOver` event as the mouse moves from
+    /// one hovered object to another.
+    ///
+    /// Unlike `mouseOut` and `mouseOver`, these events do not bubble. Instead,
+    /// they are dispatched to `node` and each of its ancestors, other than the
+    /// root, that does not also contain `related_node`. This means that an
+    /// object does not roll out when the mouse moves onto one of its children.
+    /// `rollOut` is dispatched from the innermost object outwards, and
+    /// `rollOver` from the outermost object inwards.
+    fn dispatch_avm2_roll_event<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        node: Option<DisplayObject<'gc>>,
+        related_node: Option<DisplayObject<'gc>>,
+    ) {
+        let ancestors = |mut node: Option<DisplayObject<'gc>>| {
+            let mut ancestors = Vec::new();
+            while let Some(dobj) = node {
+                node = dobj.parent();
+                if node.is_some() {
+                    if let Avm2Value::Object(_) = dobj.object2() {
+                        ancestors.push(dobj);
+                    }
+                }
+            }
+
+            ancestors
+        };
+        let related_ancestors = ancestors(related_node);
+        let mut targets: Vec<_> = ancestors(node)
+            .into_iter()
+            .filter(|dobj| {
+                !dobj.removed()
+                    && !related_ancestors
+                        .iter()
+                        .any(|related| DisplayObject::ptr_eq(*related, *dobj))
+            })
+            .collect();
+
+        if event_type == "rollOver" {
+            targets.reverse();
+        }
+
+        for target in targets {
+            Self::dispatch_avm2_mouse_event(context, event_type, false, Some(target), false, 0);
+        }
+    }
+
+    /// Dispatch an AVM2 `MouseEvent` at the current mouse position.
+    fn dispatch_avm2_mouse_event<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        bubbles: bool,
+        target: Option<DisplayObject<'gc>>,
+        button_down: bool,
+        delta: i32,
+    ) {
+        let target = match target {
+            Some(target) => target,
+            None => return,
+        };
+
+        if let Avm2Value::Object(target_object) = target.object2() {
+            let (local_x, local_y) = target.global_to_local(*context.mouse_position);
+            let args = [
+                event_type.into(),
+                bubbles.into(),
+                false.into(),
+                local_x.to_pixels().into(),
+                local_y.to_pixels().into(),
+                Avm2Value::Null,
+                context.input.is_key_down(KeyCode::Control).into(),
+                context.input.is_key_down(KeyCode::Alt).into(),
+                context.input.is_key_down(KeyCode::Shift).into(),
+                button_down.into(),
+                delta.into(),
+            ];
+            let mouse_event_proto = context.avm2.prototypes().mouse_event;
+
+            if let Err(e) = Avm2::dispatch_event(context, mouse_event_proto, &args, target_object) {
+                avm2_root_error_handler(context, e);
+            }
+        }
+    }
+
+    /// Dispatch an AVM2 `KeyboardEvent` for a given key.
+    fn dispatch_avm2_keyboard_event<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        target: Option<DisplayObject<'gc>>,
+        key_code: KeyCode,
+        key_char: Option<char>,
+        key_location: KeyLocation,
+    ) {
+        let target = match target {
+            Some(target) => target,
+            None => return,
+        };
+
+        if let Avm2Value::Object(target_object) = target.object2() {
+            let args = [
+                event_type.into(),
+                true.into(),
+                false.into(),
+                key_char.map_or(0, |c| c as u32).into(),
+                (key_code as u8).into(),
+                (key_location as u8).into(),
+                context.input.is_key_down(KeyCode::Control).into(),
+                context.input.is_key_down(KeyCode::Alt).into(),
+                context.input.is_key_down(KeyCode::Shift).into(),
+            ];
+            let keyboard_event_proto = context.avm2.prototypes().keyboard_event;
+
+            if let Err(e) =
+                Avm2::dispatch_event(context, keyboard_event_proto, &args, target_object)
+            {
+                avm2_root_error_handler(context, e);
+            }
+        }
+    }
+
     /// Preload the first movie in the player.
     ///
     /// This should only be called once. Further movie loads should preload the
@@ -827,10 +1372,32 @@ impl Player {
             // because level updates can create more levels, which we don't
             // want to run frames on
             let levels: Vec<_> = update_context.levels.values().copied().collect();
+            let display_object_proto = update_context.avm2.prototypes().display_object;
+
+            Avm2::broadcast_event(
+                update_context,
+                Avm2Event::new("enterFrame"),
+                display_object_proto,
+            );
 
             for level in levels {
                 level.run_frame(update_context);
             }
+
+            Avm2::broadcast_event(
+                update_context,
+                Avm2Event::new("frameConstructed"),
+                display_object_proto,
+            );
+
+            // Frame scripts run before `exitFrame` is broadcast.
+            Self::run_actions(update_context);
+
+            Avm2::broadcast_event(
+                update_context,
+                Avm2Event::new("exitFrame"),
+                display_object_proto,
+            );
         });
         self.needs_render = true;
     }
@@ -866,6 +1433,10 @@ impl Player {
             for (_depth, level) in root_data.levels.iter() {
                 level.render(&mut render_context);
             }
+
+            root_data
+                .focus_tracker
+                .render_highlight(&mut render_context, root_data.levels.get(&0).copied());
         });
         transform_stack.pop();
 
@@ -882,6 +1453,14 @@ impl Player {
         &mut self.audio
     }
 
+    pub fn navigator(&self) -> &Navigator {
+        &self.navigator
+    }
+
+    pub fn navigator_mut(&mut self) -> &mut Navigator {
+        &mut self.navigator
+    }
+
     // The frame rate of the current movie in FPS.
     pub fn frame_rate(&self) -> f64 {
         self.frame_rate
@@ -1017,7 +1596,7 @@ impl Player {
                     if let Err(e) =
                         Avm2::run_stack_frame_for_callable(callable, reciever, &args[..], context)
                     {
-                        log::error!("Unhandled AVM2 exception in event handler: {}", e);
+                        avm2_root_error_handler(context, e);
                     }
                 }
             }
@@ -1045,7 +1624,7 @@ impl Player {
             d: scale,
             tx: Twips::from_pixels(margin_width.into()),
             ty: Twips::from_pixels(margin_height.into()),
-        };
+        } * self.zoom_matrix;
         self.inverse_view_matrix = self.view_matrix;
         self.inverse_view_matrix.invert();
 
@@ -1083,10 +1662,14 @@ impl Player {
             stage_height,
             player,
             system_properties,
+            script_permissions,
             instance_counter,
             storage,
             locale,
             logging,
+            video,
+            socket,
+            local_connection,
             needs_render,
             max_execution_duration,
         ) = (
@@ -1103,10 +1686,14 @@ impl Player {
             Twips::from_pixels(self.movie_height.into()),
             self.self_reference.clone(),
             &mut self.system,
+            &mut self.script_permissions,
             &mut self.instance_counter,
             self.storage.deref_mut(),
             self.locale.deref_mut(),
             self.log.deref_mut(),
+            self.video.deref_mut(),
+            self.socket.deref_mut(),
+            self.local_connection.deref_mut(),
             &mut self.needs_render,
             self.max_execution_duration,
         );
@@ -1126,7 +1713,11 @@ impl Player {
                 shared_objects,
                 unbound_text_fields,
                 timers,
+                xml_sockets,
+                local_connections,
                 external_interface,
+                audio_manager,
+                current_context_menu,
             ) = root_data.update_context_params();
 
             let mut update_context = UpdateContext {
@@ -1150,13 +1741,19 @@ impl Player {
                 player,
                 load_manager,
                 system: system_properties,
+                script_permissions,
                 instance_counter,
                 storage,
                 locale,
                 log: logging,
+                video,
+                socket,
+                local_connection,
                 shared_objects,
                 unbound_text_fields,
                 timers,
+                xml_sockets,
+                local_connections,
                 needs_render,
                 avm1,
                 avm2,
@@ -1164,6 +1761,8 @@ impl Player {
                 update_start: Instant::now(),
                 max_execution_duration,
                 focus_tracker,
+                audio_manager,
+                current_context_menu,
             };
 
             let ret = f(&mut update_context);
@@ -1205,6 +1804,8 @@ impl Player {
 
             Self::run_actions(context);
 
+            context.audio_manager.update_sounds(context.audio);
+
             rval
         });
 
@@ -1236,10 +1837,40 @@ impl Player {
             self.mutate_with_update_context(|context| Timers::update_timers(context, dt));
     }
 
+    /// Run the callbacks of any `XMLSocket` connections that received events.
+    pub fn update_sockets(&mut self) {
+        self.update(|context| {
+            XmlSockets::update_sockets(context);
+        });
+    }
+
+    /// Deliver `LocalConnection` messages and report the status of sent ones.
+    pub fn update_local_connections(&mut self) {
+        self.update(|context| {
+            LocalConnections::update_local_connections(context);
+        });
+    }
+
+    /// Replaces the transport used by `LocalConnection`.
+    ///
+    /// Each player talks only to itself by default; players given endpoints
+    /// of the same `LocalConnectionBroker` can talk to each other.
+    pub fn set_local_connection_backend(&mut self, backend: LocalConnection) {
+        self.local_connection = backend;
+    }
+
     /// Returns whether this player consumes mouse wheel events.
     /// Used by web to prevent scrolling.
     pub fn should_prevent_scrolling(&mut self) -> bool {
-        self.mutate_with_update_context(|context| context.avm1.has_mouse_listener())
+        self.mutate_with_update_context(|context| {
+            let over_scrollable_text = context
+                .mouse_hovered_object
+                .and_then(|node| node.as_edit_text())
+                .map_or(false, |text| {
+                    text.is_mouse_wheel_enabled() && text.max_scroll() > 1
+                });
+            context.avm1.has_mouse_listener() || over_scrollable_text
+        })
     }
 
     pub fn add_external_interface(&mut self, provider: Box<dyn ExternalInterfaceProvider>) {
@@ -1273,6 +1904,19 @@ impl Player {
     pub fn set_max_execution_duration(&mut self, max_execution_duration: Duration) {
         self.max_execution_duration = max_execution_duration
     }
+
+    pub fn trust_local_movies(&self) -> bool {
+        self.system.trust_local_movies
+    }
+
+    /// Set whether local movies should be trusted.
+    ///
+    /// Untrusted local movies are restricted to either local files or the
+    /// network, as chosen by the movie itself. This must be set before the
+    /// root movie is loaded to have any effect.
+    pub fn set_trust_local_movies(&mut self, trust_local_movies: bool) {
+        self.system.trust_local_movies = trust_local_movies
+    }
 }
 
 pub struct DragObject<'gc> {
@@ -0,0 +1,832 @@
+//! Flash Player security sandboxes and cross-domain policies
+
+use crate::backend::navigator::{OwnedFuture, RequestOptions, Response};
+use crate::loader::Error;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use url::Url;
+
+/// The content type that policy files other than the master policy file must
+/// be served with under the `by-content-type` meta-policy.
+const POLICY_CONTENT_TYPE: &str = "text/x-cross-domain-policy";
+
+/// The HTTP header that may override the meta-policy of a server.
+const META_POLICY_HEADER: &str = "X-Permitted-Cross-Domain-Policies";
+
+/// Available type of sandbox for a given SWF
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SandboxType {
+    Remote,
+    LocalWithFile,
+    LocalWithNetwork,
+    LocalTrusted,
+}
+
+impl SandboxType {
+    /// Determine the sandbox that a movie loaded from `url` plays in.
+    ///
+    /// Movies loaded over the network are always remote. Local movies are
+    /// sandboxed according to the network flag in their `FileAttributes`,
+    /// unless the player has been told to trust them.
+    pub fn for_movie(url: Option<&str>, use_network_sandbox: bool, trusted: bool) -> Self {
+        let is_local = url
+            .map(|url| {
+                Url::parse(url)
+                    .map(|url| url.scheme() == "file")
+                    .unwrap_or(true)
+            })
+            .unwrap_or(true);
+
+        if !is_local {
+            SandboxType::Remote
+        } else if trusted {
+            SandboxType::LocalTrusted
+        } else if use_network_sandbox {
+            SandboxType::LocalWithNetwork
+        } else {
+            SandboxType::LocalWithFile
+        }
+    }
+
+    /// Check whether a movie loaded from `url` may open a socket to `host`.
+    ///
+    /// Socket policy files aren't supported, so remote movies may only
+    /// connect back to the host that they were loaded from. Local movies
+    /// need network access.
+    pub fn can_connect_socket(self, url: Option<&str>, host: &str) -> bool {
+        match self {
+            SandboxType::LocalTrusted | SandboxType::LocalWithNetwork => true,
+            SandboxType::LocalWithFile => false,
+            SandboxType::Remote => url
+                .and_then(|url| Url::parse(url).ok())
+                .and_then(|url| {
+                    url.host_str()
+                        .map(|origin| origin.eq_ignore_ascii_case(host))
+                })
+                .unwrap_or(false),
+        }
+    }
+}
+
+impl fmt::Display for SandboxType {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(match self {
+            SandboxType::Remote => "remote",
+            SandboxType::LocalWithFile => "localWithFile",
+            SandboxType::LocalWithNetwork => "localWithNetwork",
+            SandboxType::LocalTrusted => "localTrusted",
+        })
+    }
+}
+
+/// Check if a domain pattern from a policy file or `allowDomain` call
+/// matches a host.
+///
+/// Patterns may be `*`, an exact host, or a `*.` wildcard that matches the
+/// domain itself and any of its subdomains.
+pub fn domain_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.trim().to_ascii_lowercase();
+    let host = host.to_ascii_lowercase();
+
+    if pattern == "*" {
+        true
+    } else if let Some(domain) = pattern.strip_prefix("*.") {
+        host == domain || host.ends_with(&format!(".{}", domain))
+    } else {
+        pattern == host
+    }
+}
+
+/// The meta-policy of a server, which decides which policy files besides the
+/// master policy file are honoured.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MetaPolicy {
+    /// No policy files are honoured, not even the master policy file.
+    None,
+
+    /// Only the master policy file is honoured.
+    MasterOnly,
+
+    /// Only policy files served as `text/x-cross-domain-policy` are honoured.
+    ByContentType,
+
+    /// Only policy files named `crossdomain.xml` are honoured.
+    ByFtpFilename,
+
+    /// All policy files are honoured.
+    All,
+}
+
+impl MetaPolicy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "none" | "none-this-response" => Some(Self::None),
+            "master-only" => Some(Self::MasterOnly),
+            "by-content-type" => Some(Self::ByContentType),
+            "by-ftp-filename" => Some(Self::ByFtpFilename),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
+
+/// A single `allow-access-from` rule of a policy file.
+#[derive(Clone, Debug)]
+struct AllowAccessFrom {
+    domain: String,
+    secure: bool,
+}
+
+/// A parsed `crossdomain.xml` policy file.
+#[derive(Clone, Debug, Default)]
+pub struct CrossDomainPolicy {
+    /// The meta-policy declared with `site-control`, if any.
+    site_control: Option<MetaPolicy>,
+
+    /// The domains that are granted access.
+    allow_access_from: Vec<AllowAccessFrom>,
+}
+
+impl CrossDomainPolicy {
+    /// Parse a policy file.
+    ///
+    /// Returns `None` if the data is not a policy file.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader::from_reader(data);
+        let mut buf = Vec::new();
+        let mut policy = Self::default();
+        let mut found_root = false;
+
+        loop {
+            match reader.read_event(&mut buf).ok()? {
+                Event::Start(bs) | Event::Empty(bs) => {
+                    let attribute = |name: &[u8]| {
+                        bs.attributes()
+                            .filter_map(|a| a.ok())
+                            .find(|a| a.key == name)
+                            .and_then(|a| {
+                                String::from_utf8(a.unescaped_value().ok()?.to_vec()).ok()
+                            })
+                    };
+
+                    match bs.name() {
+                        b"cross-domain-policy" => found_root = true,
+                        b"site-control" if found_root => {
+                            policy.site_control = attribute(b"permitted-cross-domain-policies")
+                                .and_then(|value| MetaPolicy::parse(&value));
+                        }
+                        b"allow-access-from" if found_root => {
+                            if let Some(domain) = attribute(b"domain") {
+                                let secure = attribute(b"secure")
+                                    .map(|secure| secure.trim() != "false")
+                                    .unwrap_or(true);
+                                policy
+                                    .allow_access_from
+                                    .push(AllowAccessFrom { domain, secure });
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+
+            buf.clear();
+        }
+
+        if found_root {
+            Some(policy)
+        } else {
+            None
+        }
+    }
+
+    /// The meta-policy declared in this policy file.
+    ///
+    /// Policy files that don't declare one default to `master-only`.
+    pub fn site_control(&self) -> MetaPolicy {
+        self.site_control.unwrap_or(MetaPolicy::MasterOnly)
+    }
+
+    /// Check if this policy grants access to movies from `origin`.
+    ///
+    /// `is_secure_policy` indicates that the policy was served over HTTPS, in
+    /// which case `secure` rules only admit HTTPS origins.
+    ///
+    /// Local movies are only admitted by a `*` rule.
+    pub fn permits(&self, origin: Option<&Url>, is_secure_policy: bool) -> bool {
+        self.allow_access_from.iter().any(|rule| {
+            if rule.secure && is_secure_policy && origin.map(|o| o.scheme()) != Some("https") {
+                return false;
+            }
+
+            match origin.and_then(|origin| origin.host_str()) {
+                Some(host) => domain_matches(&rule.domain, host),
+                None => rule.domain.trim() == "*",
+            }
+        })
+    }
+}
+
+/// The outcome of checking a URL against a sandbox.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// The URL may be loaded.
+    Allowed,
+
+    /// The URL may never be loaded from this sandbox.
+    Denied,
+
+    /// The URL may only be loaded if a cross-domain policy permits it.
+    RequiresPolicy,
+}
+
+/// A policy file that has been fetched, along with how it was served.
+struct LoadedPolicy {
+    policy: CrossDomainPolicy,
+
+    /// The meta-policy set with the `X-Permitted-Cross-Domain-Policies`
+    /// header, which overrides the policy's own `site-control`.
+    header_meta_policy: Option<MetaPolicy>,
+
+    /// Whether the policy was served with the policy content type.
+    has_policy_content_type: bool,
+
+    /// Whether the policy was served over HTTPS.
+    is_secure: bool,
+}
+
+impl LoadedPolicy {
+    fn meta_policy(&self) -> MetaPolicy {
+        self.header_meta_policy
+            .unwrap_or_else(|| self.policy.site_control())
+    }
+}
+
+/// State shared between a sandbox and the fetches it has started.
+#[derive(Default)]
+struct SandboxState {
+    /// Policy files fetched so far, keyed by URL.
+    ///
+    /// `None` records a policy file that could not be loaded.
+    policies: HashMap<String, Option<Rc<LoadedPolicy>>>,
+
+    /// Additional policy files registered with `loadPolicyFile`.
+    policy_files: Vec<Url>,
+}
+
+/// The security sandbox that a navigator enforces on fetches.
+///
+/// Clones of a sandbox share their policy cache.
+#[derive(Clone)]
+pub struct Sandbox {
+    sandbox_type: SandboxType,
+
+    /// The URL of the movie that requests are made on behalf of.
+    origin: Option<Url>,
+
+    state: Rc<RefCell<SandboxState>>,
+}
+
+impl Default for Sandbox {
+    fn default() -> Self {
+        Self::new(SandboxType::LocalTrusted, None)
+    }
+}
+
+impl Sandbox {
+    pub fn new(sandbox_type: SandboxType, origin: Option<Url>) -> Self {
+        Self {
+            sandbox_type,
+            origin,
+            state: Default::default(),
+        }
+    }
+
+    pub fn sandbox_type(&self) -> SandboxType {
+        self.sandbox_type
+    }
+
+    /// Register an additional policy file to consult for its host, as with
+    /// `System.security.loadPolicyFile`.
+    pub fn add_policy_file(&self, url: Url) {
+        let mut state = self.state.borrow_mut();
+        if !state.policy_files.contains(&url) {
+            state.policy_files.push(url);
+        }
+    }
+
+    /// Check whether this sandbox may load the given URL.
+    pub fn check(&self, url: &Url) -> Access {
+        let is_local = url.scheme() == "file";
+
+        match self.sandbox_type {
+            SandboxType::LocalTrusted => Access::Allowed,
+            SandboxType::LocalWithFile if is_local => Access::Allowed,
+            SandboxType::LocalWithFile => Access::Denied,
+            SandboxType::LocalWithNetwork if is_local => Access::Denied,
+            SandboxType::LocalWithNetwork => Access::RequiresPolicy,
+            SandboxType::Remote if is_local => Access::Denied,
+            SandboxType::Remote => match &self.origin {
+                Some(origin) if origin.origin() == url.origin() => Access::Allowed,
+                _ => Access::RequiresPolicy,
+            },
+        }
+    }
+
+    /// Fetch a URL on behalf of the sandboxed movie.
+    ///
+    /// `fetch` performs the actual, unrestricted fetch. It is used both for
+    /// the requested URL and for any policy files that need to be consulted.
+    ///
+    /// If the request is redirected, the URL that it ends up at must be
+    /// permitted as well.
+    pub fn fetch<F>(
+        &self,
+        url: Url,
+        options: RequestOptions,
+        fetch: F,
+    ) -> OwnedFuture<Response, Error>
+    where
+        F: Fn(Url, RequestOptions) -> OwnedFuture<Response, Error> + 'static,
+    {
+        let sandbox = self.clone();
+        Box::pin(async move {
+            if !sandbox.is_permitted(&url, &fetch).await {
+                return Err(Error::SecurityViolation(url.into_string()));
+            }
+
+            let response = fetch(url.clone(), options).await?;
+            let final_url = Url::parse(&response.url)
+                .map_err(|_| Error::SecurityViolation(response.url.clone()))?;
+            if final_url != url && !sandbox.is_permitted(&final_url, &fetch).await {
+                return Err(Error::SecurityViolation(final_url.into_string()));
+            }
+
+            Ok(response)
+        })
+    }
+
+    /// Check whether this sandbox may load the given URL, consulting policy
+    /// files if needed.
+    async fn is_permitted<F>(&self, url: &Url, fetch: &F) -> bool
+    where
+        F: Fn(Url, RequestOptions) -> OwnedFuture<Response, Error>,
+    {
+        match self.check(url) {
+            Access::Allowed => true,
+            Access::Denied => false,
+            Access::RequiresPolicy => self.is_permitted_by_policy(url, fetch).await,
+        }
+    }
+
+    /// Consult the policy files of the host of `url` to see if our origin
+    /// may load it.
+    async fn is_permitted_by_policy<F>(&self, url: &Url, fetch: &F) -> bool
+    where
+        F: Fn(Url, RequestOptions) -> OwnedFuture<Response, Error>,
+    {
+        let master_url = match url.join("/crossdomain.xml") {
+            Ok(master_url) => master_url,
+            Err(_) => return false,
+        };
+
+        let master = match self.load_policy(master_url.clone(), fetch).await {
+            Some(master) => master,
+            None => {
+                log::warn!("No cross-domain policy permits loading {}", url);
+                return false;
+            }
+        };
+
+        let meta_policy = master.meta_policy();
+        if meta_policy == MetaPolicy::None {
+            return false;
+        }
+
+        if master
+            .policy
+            .permits(self.origin.as_ref(), master.is_secure)
+        {
+            return true;
+        }
+
+        let policy_files: Vec<Url> = self
+            .state
+            .borrow()
+            .policy_files
+            .iter()
+            .filter(|policy_url| {
+                policy_url.origin() == url.origin()
+                    && **policy_url != master_url
+                    && url.path().starts_with(policy_directory(policy_url))
+            })
+            .cloned()
+            .collect();
+
+        for policy_url in policy_files {
+            let honoured = match meta_policy {
+                MetaPolicy::All | MetaPolicy::ByContentType => true,
+                MetaPolicy::ByFtpFilename => policy_url.path().ends_with("/crossdomain.xml"),
+                MetaPolicy::None | MetaPolicy::MasterOnly => false,
+            };
+            if !honoured {
+                continue;
+            }
+
+            if let Some(loaded) = self.load_policy(policy_url, fetch).await {
+                if meta_policy == MetaPolicy::ByContentType && !loaded.has_policy_content_type {
+                    continue;
+                }
+
+                if loaded
+                    .policy
+                    .permits(self.origin.as_ref(), loaded.is_secure)
+                {
+                    return true;
+                }
+            }
+        }
+
+        log::warn!("No cross-domain policy permits loading {}", url);
+        false
+    }
+
+    /// Fetch and parse a policy file, or retrieve it from the cache.
+    async fn load_policy<F>(&self, policy_url: Url, fetch: &F) -> Option<Rc<LoadedPolicy>>
+    where
+        F: Fn(Url, RequestOptions) -> OwnedFuture<Response, Error>,
+    {
+        if let Some(policy) = self.state.borrow().policies.get(policy_url.as_str()) {
+            return policy.clone();
+        }
+
+        let key = policy_url.to_string();
+        let is_secure = policy_url.scheme() == "https";
+        let loaded = async {
+            let response = fetch(policy_url, RequestOptions::get()).await.ok()?;
+            if !response.is_success() {
+                return None;
+            }
+
+            let header = |name: &str| {
+                response
+                    .headers
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                    .map(|(_, value)| value.clone())
+            };
+            let header_meta_policy =
+                header(META_POLICY_HEADER).and_then(|value| MetaPolicy::parse(&value));
+            let has_policy_content_type = header("Content-Type")
+                .map(|value| value.trim().starts_with(POLICY_CONTENT_TYPE))
+                .unwrap_or(false);
+
+            let data = response.into_bytes().await.ok()?;
+            let policy = CrossDomainPolicy::parse(&data)?;
+
+            Some(Rc::new(LoadedPolicy {
+                policy,
+                header_meta_policy,
+                has_policy_content_type,
+                is_secure,
+            }))
+        }
+        .await;
+
+        self.state.borrow_mut().policies.insert(key, loaded.clone());
+
+        loaded
+    }
+}
+
+/// The directory that a non-master policy file governs.
+fn policy_directory(url: &Url) -> &str {
+    let path = url.path();
+    &path[..path.rfind('/').map(|i| i + 1).unwrap_or(0)]
+}
+
+/// Tracks which domains each movie has allowed to script it with
+/// `System.security.allowDomain`.
+#[derive(Default)]
+pub struct ScriptPermissions {
+    /// Domain patterns allowed by each movie, keyed by movie URL.
+    allowed_domains: HashMap<String, Vec<String>>,
+}
+
+impl ScriptPermissions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow movies from `domain` to script the movie at `movie_url`.
+    ///
+    /// The domain may also be given as a full URL.
+    pub fn allow_domain(&mut self, movie_url: Option<&str>, domain: &str) {
+        let domain = Url::parse(domain)
+            .ok()
+            .and_then(|url| url.host_str().map(|host| host.to_string()))
+            .unwrap_or_else(|| domain.to_string());
+
+        self.allowed_domains
+            .entry(movie_url.unwrap_or_default().to_string())
+            .or_default()
+            .push(domain);
+    }
+
+    /// Check if the movie at `caller_url` may script the movie at
+    /// `target_url`.
+    ///
+    /// Movies from the same domain may always script each other. Local
+    /// movies count as one domain, and may script anything if they are
+    /// trusted.
+    pub fn can_script(
+        &self,
+        caller_url: Option<&str>,
+        target_url: Option<&str>,
+        local_sandbox: SandboxType,
+    ) -> bool {
+        if caller_url == target_url {
+            return true;
+        }
+
+        let caller_domain = movie_domain(caller_url);
+        if caller_domain.is_none() && local_sandbox == SandboxType::LocalTrusted {
+            return true;
+        }
+
+        if caller_domain == movie_domain(target_url) {
+            return true;
+        }
+
+        let allowed = match self.allowed_domains.get(target_url.unwrap_or_default()) {
+            Some(allowed) => allowed,
+            None => return false,
+        };
+
+        allowed.iter().any(|pattern| match &caller_domain {
+            Some(domain) => domain_matches(pattern, domain),
+            None => pattern.trim() == "*",
+        })
+    }
+}
+
+/// The domain of a movie, or `None` for local movies.
+fn movie_domain(url: Option<&str>) -> Option<String> {
+    let url = Url::parse(url?).ok()?;
+    if url.scheme() == "file" {
+        return None;
+    }
+
+    url.host_str().map(|host| host.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::navigator::NullExecutor;
+    use std::cell::Cell;
+
+    const POLICY: &str = r#"<?xml version="1.0"?>
+<cross-domain-policy>
+    <site-control permitted-cross-domain-policies="all"/>
+    <allow-access-from domain="*.example.com"/>
+</cross-domain-policy>"#;
+
+    /// A fetch that serves `files` by URL, and counts how often it was
+    /// called.
+    fn fake_fetch(
+        files: Vec<(&'static str, &'static str)>,
+        count: Rc<Cell<usize>>,
+    ) -> impl Fn(Url, RequestOptions) -> OwnedFuture<Response, Error> {
+        move |url, _options| {
+            count.set(count.get() + 1);
+            let file = files.iter().find(|(file_url, _)| *file_url == url.as_str());
+            let mut response = Response::from_data(
+                url.to_string(),
+                file.map(|(_, data)| data.as_bytes().to_vec())
+                    .unwrap_or_default(),
+            );
+            response.status = Some(if file.is_some() { 200 } else { 404 });
+            Box::pin(async move { Ok(response) })
+        }
+    }
+
+    /// Run a sandboxed fetch to completion and report whether it succeeded.
+    fn run_fetch<F>(sandbox: &Sandbox, url: &str, fetch: F) -> bool
+    where
+        F: Fn(Url, RequestOptions) -> OwnedFuture<Response, Error> + 'static,
+    {
+        let (mut executor, channel) = NullExecutor::new();
+        let result = Rc::new(Cell::new(false));
+        let result_inner = result.clone();
+        let future = sandbox.fetch(Url::parse(url).unwrap(), RequestOptions::get(), fetch);
+        channel
+            .send(Box::pin(async move {
+                result_inner.set(future.await.is_ok());
+                Ok(())
+            }))
+            .unwrap();
+        executor.block_all().unwrap();
+
+        result.get()
+    }
+
+    #[test]
+    fn domain_patterns() {
+        assert!(domain_matches("*", "example.com"));
+        assert!(domain_matches("example.com", "EXAMPLE.com"));
+        assert!(domain_matches("*.example.com", "www.example.com"));
+        assert!(domain_matches("*.example.com", "example.com"));
+        assert!(!domain_matches("*.example.com", "badexample.com"));
+        assert!(!domain_matches("example.com", "www.example.com"));
+    }
+
+    #[test]
+    fn parse_policy() {
+        let policy = CrossDomainPolicy::parse(POLICY.as_bytes()).unwrap();
+        let origin = Url::parse("http://www.example.com/movie.swf").unwrap();
+        let other = Url::parse("http://www.other.com/movie.swf").unwrap();
+
+        assert_eq!(policy.site_control(), MetaPolicy::All);
+        assert!(policy.permits(Some(&origin), false));
+        assert!(!policy.permits(Some(&other), false));
+        assert!(!policy.permits(None, false));
+        assert!(CrossDomainPolicy::parse(b"<html></html>").is_none());
+    }
+
+    #[test]
+    fn local_sandboxes() {
+        let file = Url::parse("file:///movies/data.txt").unwrap();
+        let remote = Url::parse("http://example.com/data.txt").unwrap();
+
+        let sandbox = Sandbox::new(SandboxType::LocalWithFile, None);
+        assert_eq!(sandbox.check(&file), Access::Allowed);
+        assert_eq!(sandbox.check(&remote), Access::Denied);
+
+        let sandbox = Sandbox::new(SandboxType::LocalWithNetwork, None);
+        assert_eq!(sandbox.check(&file), Access::Denied);
+        assert_eq!(sandbox.check(&remote), Access::RequiresPolicy);
+
+        let sandbox = Sandbox::new(SandboxType::LocalTrusted, None);
+        assert_eq!(sandbox.check(&file), Access::Allowed);
+        assert_eq!(sandbox.check(&remote), Access::Allowed);
+    }
+
+    #[test]
+    fn socket_hosts() {
+        let movie = Some("http://www.example.com/movie.swf");
+
+        assert!(SandboxType::Remote.can_connect_socket(movie, "www.example.com"));
+        assert!(SandboxType::Remote.can_connect_socket(movie, "WWW.example.com"));
+        assert!(!SandboxType::Remote.can_connect_socket(movie, "example.com"));
+        assert!(!SandboxType::Remote.can_connect_socket(movie, "chat.other.com"));
+        assert!(!SandboxType::LocalWithFile.can_connect_socket(None, "localhost"));
+        assert!(SandboxType::LocalWithNetwork.can_connect_socket(None, "chat.other.com"));
+        assert!(SandboxType::LocalTrusted.can_connect_socket(None, "chat.other.com"));
+    }
+
+    #[test]
+    fn remote_fetch_requires_policy() {
+        let origin = Url::parse("http://www.example.com/movie.swf").unwrap();
+        let sandbox = Sandbox::new(SandboxType::Remote, Some(origin));
+        let count = Rc::new(Cell::new(0));
+
+        let files = vec![
+            ("http://www.example.com/data.txt", "same origin"),
+            ("http://api.other.com/crossdomain.xml", POLICY),
+            ("http://api.other.com/data.txt", "allowed by policy"),
+            ("http://cdn.other.net/data.txt", "no policy"),
+        ];
+
+        assert!(run_fetch(
+            &sandbox,
+            "http://www.example.com/data.txt",
+            fake_fetch(files.clone(), count.clone())
+        ));
+        assert!(run_fetch(
+            &sandbox,
+            "http://api.other.com/data.txt",
+            fake_fetch(files.clone(), count.clone())
+        ));
+        assert!(!run_fetch(
+            &sandbox,
+            "http://cdn.other.net/data.txt",
+            fake_fetch(files.clone(), count.clone())
+        ));
+        assert!(!run_fetch(
+            &sandbox,
+            "file:///etc/passwd",
+            fake_fetch(files, count.clone())
+        ));
+
+        // One fetch for each allowed URL, plus one for each policy file.
+        assert_eq!(count.get(), 4);
+    }
+
+    #[test]
+    fn redirects_must_be_permitted() {
+        let origin = Url::parse("http://www.example.com/movie.swf").unwrap();
+        let sandbox = Sandbox::new(SandboxType::Remote, Some(origin));
+
+        // Serves the policy for api.other.com, and redirects `/redirect?to=URL` to `URL`.
+        let fetch = |url: Url, _options| -> OwnedFuture<Response, Error> {
+            let (final_url, data) = match url.query_pairs().find(|(key, _)| key == "to") {
+                Some((_, to)) => (to.into_owned(), "redirected"),
+                None if url.as_str() == "http://api.other.com/crossdomain.xml" => {
+                    (url.to_string(), POLICY)
+                }
+                None => (url.to_string(), ""),
+            };
+            let mut response = Response::from_data(final_url, data.as_bytes().to_vec());
+            response.status = Some(if data.is_empty() { 404 } else { 200 });
+            Box::pin(async move { Ok(response) })
+        };
+
+        assert!(run_fetch(
+            &sandbox,
+            "http://www.example.com/redirect?to=http://api.other.com/data.txt",
+            fetch
+        ));
+        assert!(!run_fetch(
+            &sandbox,
+            "http://www.example.com/redirect?to=http://cdn.other.net/data.txt",
+            fetch
+        ));
+        assert!(!run_fetch(
+            &sandbox,
+            "http://www.example.com/redirect?to=file:///etc/passwd",
+            fetch
+        ));
+    }
+
+    #[test]
+    fn master_only_ignores_other_policies() {
+        let origin = Url::parse("http://www.example.com/movie.swf").unwrap();
+        let sandbox = Sandbox::new(SandboxType::Remote, Some(origin));
+        sandbox.add_policy_file(Url::parse("http://api.other.com/data/policy.xml").unwrap());
+
+        let master = r#"<cross-domain-policy><site-control permitted-cross-domain-policies="master-only"/></cross-domain-policy>"#;
+        let files = vec![
+            ("http://api.other.com/crossdomain.xml", master),
+            ("http://api.other.com/data/policy.xml", POLICY),
+            ("http://api.other.com/data/file.txt", "data"),
+        ];
+        let count = Rc::new(Cell::new(0));
+        assert!(!run_fetch(
+            &sandbox,
+            "http://api.other.com/data/file.txt",
+            fake_fetch(files.clone(), count.clone())
+        ));
+
+        let master = r#"<cross-domain-policy><site-control permitted-cross-domain-policies="all"/></cross-domain-policy>"#;
+        let files = vec![
+            ("http://api.other.com/crossdomain.xml", master),
+            ("http://api.other.com/data/policy.xml", POLICY),
+            ("http://api.other.com/data/file.txt", "data"),
+            ("http://api.other.com/file.txt", "data"),
+        ];
+        let sandbox = Sandbox::new(
+            SandboxType::Remote,
+            Some(Url::parse("http://www.example.com/movie.swf").unwrap()),
+        );
+        sandbox.add_policy_file(Url::parse("http://api.other.com/data/policy.xml").unwrap());
+        assert!(run_fetch(
+            &sandbox,
+            "http://api.other.com/data/file.txt",
+            fake_fetch(files.clone(), count.clone())
+        ));
+
+        // Policy files only govern their own directory.
+        assert!(!run_fetch(
+            &sandbox,
+            "http://api.other.com/file.txt",
+            fake_fetch(files, count)
+        ));
+    }
+
+    #[test]
+    fn allow_domain() {
+        let local = Some("file:///movies/main.swf");
+        let remote = Some("http://www.example.com/child.swf");
+        let mut permissions = ScriptPermissions::new();
+
+        assert!(permissions.can_script(local, local, SandboxType::LocalWithFile));
+        assert!(!permissions.can_script(local, remote, SandboxType::LocalWithFile));
+        assert!(permissions.can_script(local, remote, SandboxType::LocalTrusted));
+        assert!(!permissions.can_script(remote, local, SandboxType::LocalWithFile));
+
+        permissions.allow_domain(remote, "*");
+        assert!(permissions.can_script(local, remote, SandboxType::LocalWithFile));
+
+        let other = Some("http://other.com/main.swf");
+        permissions.allow_domain(local, "http://other.com/main.swf");
+        assert!(permissions.can_script(other, local, SandboxType::LocalWithFile));
+        assert!(!permissions.can_script(remote, local, SandboxType::LocalWithFile));
+    }
+}
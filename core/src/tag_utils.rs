@@ -130,6 +130,25 @@ impl SwfMovie {
         self.url.as_deref()
     }
 
+    /// Whether this SWF asked to be given network access rather than local
+    /// file access when played locally.
+    ///
+    /// This is read from the `FileAttributes` tag, which must be the first
+    /// tag of the SWF if it is present.
+    pub fn use_network_sandbox(&self) -> bool {
+        let mut reader = swf::read::Reader::new(&self.data[..], self.version());
+        match reader.read_tag_code_and_length() {
+            Ok((tag_code, tag_len)) if tag_code == TagCode::FileAttributes as u16 => {
+                let tag_data = reader.get_ref().get(..tag_len).unwrap_or_default();
+                swf::read::Reader::new(tag_data, self.version())
+                    .read_file_attributes()
+                    .map(|attributes| attributes.use_network_sandbox)
+                    .unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+
     pub fn parameters(&self) -> &PropertyMap<String> {
         &self.parameters
     }
@@ -793,7 +793,10 @@ impl<'gc> XMLNode<'gc> {
     /// Document roots and elements can yield children, while all other
     /// elements are structurally prohibited from adopting child `XMLNode`s.
     pub fn has_children(self) -> bool {
-        matches!(*self.0.read(), XMLNodeData::Element { .. } | XMLNodeData::DocumentRoot { .. })
+        matches!(
+            *self.0.read(),
+            XMLNodeData::Element { .. } | XMLNodeData::DocumentRoot { .. }
+        )
     }
 
     /// Returns an iterator that yields child nodes.
@@ -932,6 +935,11 @@ impl<'gc> XMLNode<'gc> {
         }
     }
 
+    /// Check if two nodes are the same node.
+    pub fn ptr_eq(self, other: XMLNode<'gc>) -> bool {
+        GcCell::ptr_eq(self.0, other.0)
+    }
+
     /// Check if this XML node constitutes the root of a whole document.
     pub fn is_document_root(self) -> bool {
         matches!(*self.0.read(), XMLNodeData::DocumentRoot { .. })
@@ -947,8 +955,7 @@ impl<'gc> XMLNode<'gc> {
         matches!(*self.0.read(), XMLNodeData::Text { .. })
     }
 
-    /// Check if this XML node constitutes text.
-    #[allow(dead_code)]
+    /// Check if this XML node constitutes a comment.
     pub fn is_comment(self) -> bool {
         matches!(*self.0.read(), XMLNodeData::Comment { .. })
     }
@@ -3,18 +3,31 @@
 //! Trace output can be compared with correct output from the official Flash Payer.
 
 use approx::assert_relative_eq;
+use indexmap::IndexMap;
 use ruffle_core::backend::locale::NullLocaleBackend;
 use ruffle_core::backend::log::LogBackend;
-use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
+use ruffle_core::backend::navigator::{
+    NavigationMethod, NavigatorBackend, NullExecutor, NullNavigatorBackend, OwnedFuture,
+    RequestOptions, Response,
+};
+use ruffle_core::backend::socket::NullSocketBackend;
 use ruffle_core::backend::storage::MemoryStorageBackend;
+use ruffle_core::backend::video::NullVideoBackend;
 use ruffle_core::backend::{
-    audio::NullAudioBackend, input::NullInputBackend, render::NullRenderer,
+    audio::NullAudioBackend,
+    input::{InputBackend, MouseCursor, NullInputBackend},
+    render::{NullRenderer, RenderBackend},
 };
 use ruffle_core::context::UpdateContext;
+use ruffle_core::events::{KeyCode, KeyLocation, MouseWheelDelta};
 use ruffle_core::external::Value as ExternalValue;
 use ruffle_core::external::{ExternalInterfaceMethod, ExternalInterfaceProvider};
+use ruffle_core::loader::Error as LoaderError;
+use ruffle_core::security::Sandbox;
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::Player;
+use ruffle_core::{Player, PlayerEvent};
+use ruffle_render_software::SoftwareRenderBackend;
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -64,6 +77,23 @@ macro_rules! swf_tests_approx {
     };
 }
 
+// This macro generates test cases that compare the rendered output of an SWF with an image.
+macro_rules! swf_image_tests {
+    ($($(#[$attr:meta])* ($name:ident, $path:expr, $num_frames:literal),)*) => {
+        $(
+        #[test]
+        $(#[$attr])*
+        fn $name() -> Result<(), Error> {
+            test_swf_image(
+                concat!("tests/swfs/", $path, "/test.swf"),
+                $num_frames,
+                concat!("tests/swfs/", $path, "/expected.png"),
+            )
+        }
+        )*
+    };
+}
+
 // List of SWFs to test.
 // Format: (test_name, test_folder, number_of_frames_to_run)
 // The test folder is a relative to core/tests/swfs
@@ -211,6 +241,12 @@ swf_tests! {
     (mcl_unloadclip, "avm1/mcl_unloadclip", 11),
     (mcl_getprogress, "avm1/mcl_getprogress", 6),
     (load_vars, "avm1/load_vars", 2),
+    (http_load, "avm1/http_load", 3),
+    (sandbox_local_with_file, "avm1/sandbox_local_with_file", 3),
+    (sandbox_local_with_network, "avm1/sandbox_local_with_network", 3),
+    (script_access, "avm1/script_access", 8),
+    (xml_socket_sandbox, "avm1/xml_socket_sandbox", 3),
+    (local_connection, "avm1/local_connection", 5),
     (loadvariables, "avm1/loadvariables", 3),
     (loadvariablesnum, "avm1/loadvariablesnum", 3),
     (loadvariables_method, "avm1/loadvariables_method", 3),
@@ -262,6 +298,7 @@ swf_tests! {
     (object_function, "avm1/object_function", 1),
     (parse_int, "avm1/parse_int", 1),
     (bitmap_filter, "avm1/bitmap_filter", 1),
+    (displacement_map_filter, "avm1/displacement_map_filter", 1),
     (blur_filter, "avm1/blur_filter", 1),
     (date_constructor, "avm1/date/constructor", 1),
     (removed_clip_halts_script, "avm1/removed_clip_halts_script", 13),
@@ -409,6 +446,27 @@ swf_tests! {
     (as3_movieclip_constr, "avm2/movieclip_constr", 1),
     (as3_lazyinit, "avm2/lazyinit", 1),
     (as3_trace, "avm2/trace", 1),
+    (as3_try_catch, "avm2/try_catch", 1),
+    (as3_runtime_errors, "avm2/runtime_errors", 1),
+    (as3_lookupswitch, "avm2/lookupswitch", 1),
+    (as3_swap, "avm2/swap", 1),
+    (as3_checkfilter, "avm2/checkfilter", 1),
+    (as3_dxns, "avm2/dxns", 1),
+    (as3_getdescendants, "avm2/getdescendants", 1),
+    (as3_blend_mode, "avm2/blend_mode", 1),
+    (as3_typeof, "avm2/typeof", 1),
+    (as3_astype, "avm2/astype", 1),
+    (as3_coerce, "avm2/coerce", 1),
+    (as3_event_dispatch, "avm2/event_dispatch", 1),
+    (as3_vector_basic, "avm2/vector_basic", 1),
+    (as3_dictionary, "avm2/dictionary", 1),
+    (as3_bytearray, "avm2/bytearray", 1),
+    (as3_regexp, "avm2/regexp", 1),
+    (as3_date, "avm2/date", 1),
+    (as3_json, "avm2/json", 1),
+    (as3_xml, "avm2/xml", 1),
+    (as3_display_list, "avm2/display_list", 1),
+    (as3_tab_order, "avm2/tab_order", 1),
 }
 
 // TODO: These tests have some inaccuracies currently, so we use approx_eq to test that numeric values are close enough.
@@ -430,6 +488,15 @@ swf_tests_approx! {
     (as3_math, "avm2/math", 1, max_relative = 30.0 * std::f64::EPSILON),
 }
 
+// List of SWFs to render with the software renderer.
+// Format: (test_name, test_folder, number_of_frames_to_run)
+// The last frame is compared with `expected.png` in the test folder.
+swf_image_tests! {
+    (render_shapes, "render/shapes", 1),
+    (render_blend_modes, "render/blend_modes", 1),
+    (render_clip_depth, "render/clip_depth", 1),
+}
+
 #[test]
 fn external_interface_avm1() -> Result<(), Error> {
     test_swf(
@@ -500,6 +567,366 @@ fn timeout_avm1() -> Result<(), Error> {
     )
 }
 
+#[test]
+fn as3_mouse_events() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/mouse_events/test.swf",
+        1,
+        "tests/swfs/avm2/mouse_events/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player_locked = player.lock().unwrap();
+            let moves = [
+                ("// move over outer", 25.0, 150.0),
+                ("// move onto inner", 75.0, 150.0),
+                ("// move back to outer", 25.0, 150.0),
+                ("// move out", 300.0, 300.0),
+                ("// move over blocked", 250.0, 150.0),
+                ("// move over leaf", 450.0, 150.0),
+            ];
+            for (comment, x, y) in moves.iter().copied() {
+                player_locked.log_backend().avm_trace(comment);
+                player_locked.handle_event(PlayerEvent::MouseMove { x, y });
+            }
+
+            player_locked.log_backend().avm_trace("// click leaf");
+            player_locked.handle_event(PlayerEvent::MouseDown { x: 450.0, y: 150.0 });
+            player_locked.handle_event(PlayerEvent::MouseUp { x: 450.0, y: 150.0 });
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn context_menu_host() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/context_menu_host/test.swf",
+        1,
+        "tests/swfs/avm1/context_menu_host/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player_locked = player.lock().unwrap();
+            let steps = [
+                ("// open over stage", 50.0, 50.0, None),
+                ("// choose Custom", 50.0, 50.0, Some("Custom")),
+                ("// choose Disabled", 50.0, 50.0, Some("Disabled")),
+                ("// open over box", 150.0, 150.0, None),
+                ("// choose Box item", 150.0, 150.0, Some("Box item")),
+                ("// choose Loop", 50.0, 50.0, Some("Loop")),
+                ("// choose Zoom In", 50.0, 50.0, Some("Zoom In")),
+                ("// open over stage", 50.0, 50.0, None),
+                ("// choose Show All", 50.0, 50.0, Some("Show All")),
+                ("// choose Play", 50.0, 50.0, Some("Play")),
+            ];
+            for (comment, x, y, choice) in steps.iter().copied() {
+                player_locked.log_backend().avm_trace(comment);
+                let items = player_locked.prepare_context_menu(x, y);
+                match choice {
+                    Some(caption) => {
+                        let index = items.iter().position(|item| item.caption == caption);
+                        player_locked.run_context_menu_callback(index.unwrap());
+                    }
+                    None => {
+                        for item in items {
+                            let line = format!(
+                                "{}{}{}{}",
+                                if item.separator_before { "---- " } else { "" },
+                                item.caption,
+                                if item.enabled { "" } else { " (disabled)" },
+                                if item.checked { " (checked)" } else { "" },
+                            );
+                            player_locked.log_backend().avm_trace(&line);
+                        }
+                    }
+                }
+            }
+            player_locked.run_frame();
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn request_headers() -> Result<(), Error> {
+    let requests = Rc::new(RefCell::new(Vec::new()));
+    test_swf(
+        "tests/swfs/avm1/request_headers/test.swf",
+        1,
+        "tests/swfs/avm1/request_headers/output.txt",
+        |player| {
+            let mut player = player.lock().unwrap();
+            let inner = std::mem::replace(
+                player.navigator_mut(),
+                Box::new(NullNavigatorBackend::new()),
+            );
+            *player.navigator_mut() = Box::new(RecordingNavigatorBackend {
+                inner,
+                requests: requests.clone(),
+            });
+            Ok(())
+        },
+        |player| {
+            let player = player.lock().unwrap();
+            for request in requests.borrow().iter() {
+                player.log_backend().avm_trace(request);
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn key_input_avm1() -> Result<(), Error> {
+    test_swf_with_input(
+        "tests/swfs/avm1/key_input/test.swf",
+        1,
+        "tests/swfs/avm1/key_input/output.txt",
+        Box::new(TestInputBackend::default()),
+        |_| Ok(()),
+        |player| {
+            let mut player_locked = player.lock().unwrap();
+            let keys = [
+                ("// type a", KeyCode::A, Some('a'), KeyLocation::Standard),
+                ("// press Shift", KeyCode::Shift, None, KeyLocation::Left),
+                ("// type A", KeyCode::A, Some('A'), KeyLocation::Standard),
+                ("// release Shift", KeyCode::Shift, None, KeyLocation::Left),
+                (
+                    "// press Caps Lock",
+                    KeyCode::CapsLock,
+                    None,
+                    KeyLocation::Standard,
+                ),
+                ("// type 1", KeyCode::Key1, Some('1'), KeyLocation::Standard),
+                (
+                    "// press Num Lock",
+                    KeyCode::NumLock,
+                    None,
+                    KeyLocation::NumPad,
+                ),
+                (
+                    "// type 5 on the numpad",
+                    KeyCode::Numpad5,
+                    Some('5'),
+                    KeyLocation::NumPad,
+                ),
+                ("// type F1", KeyCode::F1, None, KeyLocation::Standard),
+            ];
+            for (comment, key_code, key_char, key_location) in keys.iter().copied() {
+                player_locked.log_backend().avm_trace(comment);
+                // Shift is held while A is typed.
+                let presses: &[bool] = match key_code {
+                    KeyCode::Shift if comment.starts_with("// press") => &[true],
+                    KeyCode::Shift => &[false],
+                    _ => &[true, false],
+                };
+                for pressed in presses.iter().copied() {
+                    let event = player_locked
+                        .input_mut()
+                        .downcast_mut::<TestInputBackend>()
+                        .unwrap()
+                        .key_event(key_code, key_char, key_location, pressed);
+                    player_locked.handle_event(event);
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn edittext_input() -> Result<(), Error> {
+    test_swf_with_input(
+        "tests/swfs/avm1/edittext_input/test.swf",
+        1,
+        "tests/swfs/avm1/edittext_input/output.txt",
+        Box::new(TestInputBackend::default()),
+        |_| Ok(()),
+        |player| {
+            let mut player_locked = player.lock().unwrap();
+            // Each step is typed text, keys held down while others are pressed, and the keys pressed.
+            let steps: &[(&str, &str, &[KeyCode], &[KeyCode])] = &[
+                ("// type hello", "hello", &[], &[]),
+                (
+                    "// move left twice",
+                    "",
+                    &[],
+                    &[KeyCode::Left, KeyCode::Left],
+                ),
+                ("// type X", "X", &[], &[]),
+                (
+                    "// select two characters to the left",
+                    "",
+                    &[KeyCode::Shift],
+                    &[KeyCode::Left, KeyCode::Left],
+                ),
+                ("// type Y over the selection", "Y", &[], &[]),
+                ("// backspace", "\u{8}", &[], &[]),
+                (
+                    "// select all and copy",
+                    "",
+                    &[KeyCode::Control],
+                    &[KeyCode::A, KeyCode::C],
+                ),
+                ("// move to the end", "", &[], &[KeyCode::End]),
+                (
+                    "// paste past maxChars",
+                    "",
+                    &[KeyCode::Control],
+                    &[KeyCode::V],
+                ),
+                ("// type past maxChars", "Z", &[], &[]),
+                (
+                    "// select all and cut",
+                    "",
+                    &[KeyCode::Control],
+                    &[KeyCode::A, KeyCode::X],
+                ),
+                (
+                    "// paste into the empty field",
+                    "",
+                    &[KeyCode::Control],
+                    &[KeyCode::V],
+                ),
+            ];
+            let key_events = |player: &mut Player, key_code: KeyCode, pressed: bool| {
+                let event = player
+                    .input_mut()
+                    .downcast_mut::<TestInputBackend>()
+                    .unwrap()
+                    .key_event(key_code, None, KeyLocation::Standard, pressed);
+                player.handle_event(event);
+            };
+            let run_steps =
+                |player: &mut Player, steps: &[(&str, &str, &[KeyCode], &[KeyCode])]| {
+                    for (comment, text, held, pressed) in steps.iter().copied() {
+                        player.log_backend().avm_trace(comment);
+                        for codepoint in text.chars() {
+                            player.handle_event(PlayerEvent::TextInput { codepoint });
+                        }
+                        for key_code in held.iter().copied() {
+                            key_events(player, key_code, true);
+                        }
+                        for key_code in pressed.iter().copied() {
+                            key_events(player, key_code, true);
+                            key_events(player, key_code, false);
+                        }
+                        for key_code in held.iter().copied() {
+                            key_events(player, key_code, false);
+                        }
+                        player.run_frame();
+                    }
+                };
+
+            player_locked.log_backend().avm_trace("// click plain");
+            player_locked.handle_event(PlayerEvent::MouseDown { x: 100.0, y: 10.0 });
+            player_locked.handle_event(PlayerEvent::MouseUp { x: 100.0, y: 10.0 });
+            player_locked.run_frame();
+            run_steps(&mut player_locked, steps);
+
+            player_locked.log_backend().avm_trace("// click secret");
+            player_locked.handle_event(PlayerEvent::MouseDown { x: 100.0, y: 60.0 });
+            player_locked.handle_event(PlayerEvent::MouseUp { x: 100.0, y: 60.0 });
+            player_locked.run_frame();
+            run_steps(
+                &mut player_locked,
+                &[
+                    (
+                        "// select all and copy the password",
+                        "",
+                        &[KeyCode::Control],
+                        &[KeyCode::A, KeyCode::C],
+                    ),
+                    (
+                        "// paste over the password",
+                        "",
+                        &[KeyCode::Control],
+                        &[KeyCode::V],
+                    ),
+                    ("// type into the password", "!", &[], &[]),
+                ],
+            );
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn edittext_link_click() -> Result<(), Error> {
+    let requests = Rc::new(RefCell::new(Vec::new()));
+    test_swf_with_input(
+        "tests/swfs/avm1/edittext_link_click/test.swf",
+        1,
+        "tests/swfs/avm1/edittext_link_click/output.txt",
+        Box::new(TestInputBackend::default()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            let inner = std::mem::replace(
+                player.navigator_mut(),
+                Box::new(NullNavigatorBackend::new()),
+            );
+            *player.navigator_mut() = Box::new(RecordingNavigatorBackend {
+                inner,
+                requests: requests.clone(),
+            });
+            Ok(())
+        },
+        |player| {
+            let mut player_locked = player.lock().unwrap();
+            let clicks = [
+                ("// click the page link", 20.0, 8.0),
+                ("// click the asfunction link", 20.0, 24.0),
+                ("// click the plain text", 20.0, 40.0),
+            ];
+            for (comment, x, y) in clicks.iter().copied() {
+                player_locked.log_backend().avm_trace(comment);
+                player_locked.handle_event(PlayerEvent::MouseMove { x, y });
+                let cursor = player_locked
+                    .input_mut()
+                    .downcast_mut::<TestInputBackend>()
+                    .unwrap()
+                    .cursor;
+                player_locked
+                    .log_backend()
+                    .avm_trace(&format!("// cursor {:?}", cursor));
+                player_locked.handle_event(PlayerEvent::MouseDown { x, y });
+                player_locked.handle_event(PlayerEvent::MouseUp { x, y });
+                for request in requests.borrow_mut().drain(..) {
+                    player_locked.log_backend().avm_trace(&request);
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn edittext_mouse_wheel() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/edittext_mouse_wheel/test.swf",
+        1,
+        "tests/swfs/avm1/edittext_mouse_wheel/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player_locked = player.lock().unwrap();
+            let wheels = [
+                ("// scroll down over scrolling", 50.0, 30.0, -3.0),
+                ("// scroll past the end of scrolling", 50.0, 30.0, -100.0),
+                ("// scroll up over scrolling", 50.0, 30.0, 2.0),
+                ("// scroll down over fixed", 250.0, 30.0, -3.0),
+                ("// scroll down over the stage", 150.0, 200.0, -3.0),
+            ];
+            for (comment, x, y, lines) in wheels.iter().copied() {
+                player_locked.log_backend().avm_trace(comment);
+                player_locked.handle_event(PlayerEvent::MouseMove { x, y });
+                player_locked.handle_event(PlayerEvent::MouseWheel {
+                    delta: MouseWheelDelta::Lines(lines),
+                });
+            }
+            Ok(())
+        },
+    )
+}
+
 /// Wrapper around string slice that makes debug output `{:?}` to print string same way as `{}`.
 /// Used in different `assert*!` macros in combination with `pretty_assertions` crate to make
 /// test failures to show nice diffs.
@@ -536,6 +963,25 @@ fn test_swf(
     expected_output_path: &str,
     before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
     before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+) -> Result<(), Error> {
+    test_swf_with_input(
+        swf_path,
+        num_frames,
+        expected_output_path,
+        Box::new(NullInputBackend::new()),
+        before_start,
+        before_end,
+    )
+}
+
+/// Like `test_swf`, but with the given input backend.
+fn test_swf_with_input(
+    swf_path: &str,
+    num_frames: u32,
+    expected_output_path: &str,
+    input: Box<dyn InputBackend>,
+    before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+    before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
 ) -> Result<(), Error> {
     let mut expected_output = std::fs::read_to_string(expected_output_path)?.replace("\r\n", "\n");
 
@@ -544,7 +990,14 @@ fn test_swf(
         expected_output = expected_output[0..expected_output.len() - "\n".len()].to_string();
     }
 
-    let trace_log = run_swf(swf_path, num_frames, before_start, before_end)?;
+    let trace_log = run_swf(
+        swf_path,
+        num_frames,
+        Box::new(NullRenderer),
+        input,
+        before_start,
+        before_end,
+    )?;
     assert_eq!(
         trace_log, expected_output,
         "ruffle output != flash player output"
@@ -564,7 +1017,14 @@ fn test_swf_approx(
     before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
     before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
 ) -> Result<(), Error> {
-    let trace_log = run_swf(swf_path, num_frames, before_start, before_end)?;
+    let trace_log = run_swf(
+        swf_path,
+        num_frames,
+        Box::new(NullRenderer),
+        Box::new(NullInputBackend::new()),
+        before_start,
+        before_end,
+    )?;
     let mut expected_data = std::fs::read_to_string(expected_output_path)?;
 
     // Strip a trailing newline if it has one.
@@ -605,11 +1065,83 @@ fn test_swf_approx(
     Ok(())
 }
 
+/// Loads an SWF and renders it with the software renderer after a number of frames.
+/// Tests that the rendered image matches the given expected image.
+fn test_swf_image(swf_path: &str, num_frames: u32, expected_image_path: &str) -> Result<(), Error> {
+    // Allow for small rounding differences between the rasterizer and the expected image.
+    const TOLERANCE: u8 = 2;
+
+    let movie = SwfMovie::from_path(swf_path)?;
+    let (width, height) = (movie.width(), movie.height());
+    let image = Rc::new(RefCell::new(Vec::new()));
+    let renderer = Box::new(SoftwareRenderBackend::new(width, height));
+    run_swf(
+        swf_path,
+        num_frames,
+        renderer,
+        Box::new(NullInputBackend::new()),
+        |player| {
+            player
+                .lock()
+                .unwrap()
+                .set_viewport_dimensions(width, height);
+            Ok(())
+        },
+        |player| {
+            let mut player = player.lock().unwrap();
+            player.render();
+            if let Some(renderer) = player.renderer().downcast_ref::<SoftwareRenderBackend>() {
+                *image.borrow_mut() = renderer.capture_frame();
+            }
+            Ok(())
+        },
+    )?;
+    let actual = image.borrow();
+
+    let decoder = png::Decoder::new(std::fs::File::open(expected_image_path)?);
+    let (info, mut reader) = decoder.read_info()?;
+    std::assert_eq!(
+        (info.color_type, info.bit_depth),
+        (png::ColorType::RGBA, png::BitDepth::Eight),
+        "expected image must be 8-bit RGBA"
+    );
+    std::assert_eq!(
+        (info.width, info.height),
+        (width, height),
+        "expected image size doesn't match the stage size"
+    );
+    let mut expected = vec![0; info.buffer_size()];
+    reader.next_frame(&mut expected)?;
+
+    let matches = actual.len() == expected.len()
+        && actual
+            .iter()
+            .zip(&expected)
+            .all(|(&a, &e)| (i16::from(a) - i16::from(e)).abs() <= i16::from(TOLERANCE));
+    if !matches {
+        // Write out the rendered image to make the failure easier to inspect.
+        let actual_image_path = Path::new(expected_image_path).with_file_name("actual.png");
+        let file = std::fs::File::create(&actual_image_path)?;
+        let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+        encoder.set_color(png::ColorType::RGBA);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&actual)?;
+        panic!(
+            "rendered image doesn't match {}, see {}",
+            expected_image_path,
+            actual_image_path.display()
+        );
+    }
+    Ok(())
+}
+
 /// Loads an SWF and runs it through the Ruffle core for a number of frames.
 /// Tests that the trace output matches the given expected output.
 fn run_swf(
     swf_path: &str,
     num_frames: u32,
+    renderer: Box<dyn RenderBackend>,
+    input: Box<dyn InputBackend>,
     before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
     before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
 ) -> Result<String, Error> {
@@ -620,13 +1152,15 @@ fn run_swf(
     let trace_output = Rc::new(RefCell::new(Vec::new()));
 
     let player = Player::new(
-        Box::new(NullRenderer),
+        renderer,
         Box::new(NullAudioBackend::new()),
         Box::new(NullNavigatorBackend::with_base_path(base_path, channel)),
-        Box::new(NullInputBackend::new()),
+        input,
         Box::new(MemoryStorageBackend::default()),
         Box::new(NullLocaleBackend::new()),
+        Box::new(NullVideoBackend::new()),
         Box::new(TestLogBackend::new(trace_output.clone())),
+        Box::new(NullSocketBackend::new()),
     )?;
     player.lock().unwrap().set_root_movie(Arc::new(movie));
     player
@@ -639,6 +1173,8 @@ fn run_swf(
     for _ in 0..num_frames {
         player.lock().unwrap().run_frame();
         player.lock().unwrap().update_timers(frame_time);
+        player.lock().unwrap().update_sockets();
+        player.lock().unwrap().update_local_connections();
         executor.poll_all().unwrap();
     }
 
@@ -666,6 +1202,157 @@ impl LogBackend for TestLogBackend {
     }
 }
 
+/// Input backend that remembers the keys pressed by a test, the way a real
+/// frontend would.
+#[derive(Default)]
+struct TestInputBackend {
+    keys_down: Vec<KeyCode>,
+    toggled: Vec<KeyCode>,
+    last_key: Option<KeyCode>,
+    last_char: Option<char>,
+    clipboard: String,
+    cursor: Option<MouseCursor>,
+}
+
+impl TestInputBackend {
+    /// Press or release a key, returning the event to send to the player.
+    fn key_event(
+        &mut self,
+        key_code: KeyCode,
+        key_char: Option<char>,
+        key_location: KeyLocation,
+        pressed: bool,
+    ) -> PlayerEvent {
+        self.last_key = Some(key_code);
+        self.last_char = key_char;
+        if pressed {
+            if matches!(key_code, KeyCode::CapsLock | KeyCode::NumLock) {
+                if let Some(i) = self.toggled.iter().position(|key| *key == key_code) {
+                    self.toggled.remove(i);
+                } else {
+                    self.toggled.push(key_code);
+                }
+            }
+            self.keys_down.push(key_code);
+            PlayerEvent::KeyDown {
+                key_code,
+                key_char,
+                key_location,
+            }
+        } else {
+            self.keys_down.retain(|key| *key != key_code);
+            PlayerEvent::KeyUp {
+                key_code,
+                key_char,
+                key_location,
+            }
+        }
+    }
+}
+
+impl InputBackend for TestInputBackend {
+    fn is_key_down(&self, key: KeyCode) -> bool {
+        self.keys_down.contains(&key)
+    }
+
+    fn get_last_key_code(&self) -> KeyCode {
+        self.last_key.unwrap_or(KeyCode::Unknown)
+    }
+
+    fn get_last_key_char(&self) -> Option<char> {
+        self.last_char
+    }
+
+    fn is_key_toggled(&self, key: KeyCode) -> bool {
+        self.toggled.contains(&key)
+    }
+
+    fn mouse_visible(&self) -> bool {
+        true
+    }
+
+    fn hide_mouse(&mut self) {}
+
+    fn show_mouse(&mut self) {}
+
+    fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
+        self.cursor = Some(cursor);
+    }
+
+    fn set_clipboard_content(&mut self, content: String) {
+        self.clipboard = content;
+    }
+
+    fn clipboard_content(&mut self) -> String {
+        self.clipboard.clone()
+    }
+}
+
+/// Navigator backend that writes down every request the player makes, then
+/// passes it on to another backend.
+struct RecordingNavigatorBackend {
+    inner: Box<dyn NavigatorBackend>,
+    requests: Rc<RefCell<Vec<String>>>,
+}
+
+impl NavigatorBackend for RecordingNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        url: String,
+        window: Option<String>,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.requests
+            .borrow_mut()
+            .push(format!("// navigate {}", url));
+        self.inner.navigate_to_url(url, window, vars_method)
+    }
+
+    fn fetch(
+        &self,
+        url: &str,
+        request_options: RequestOptions,
+    ) -> OwnedFuture<Response, LoaderError> {
+        let method = match request_options.method() {
+            NavigationMethod::GET => "GET",
+            NavigationMethod::POST => "POST",
+        };
+        let mut request = format!("// fetch {} {}", method, url);
+        if let Some((body, mime)) = request_options.body() {
+            request.push_str(&format!(
+                "\n//   Content-Type: {}\n//   body: {}",
+                mime,
+                String::from_utf8_lossy(body)
+            ));
+        }
+        for (name, value) in request_options.headers() {
+            request.push_str(&format!("\n//   {}: {}", name, value));
+        }
+        self.requests.borrow_mut().push(request);
+        self.inner.fetch(url, request_options)
+    }
+
+    fn time_since_launch(&mut self) -> Duration {
+        self.inner.time_since_launch()
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), LoaderError>) {
+        self.inner.spawn_future(future)
+    }
+
+    fn resolve_relative_url<'a>(&mut self, url: &'a str) -> Cow<'a, str> {
+        self.inner.resolve_relative_url(url)
+    }
+
+    fn set_sandbox(&mut self, sandbox: Sandbox) {
+        self.inner.set_sandbox(sandbox)
+    }
+
+    fn load_policy_file(&mut self, url: &str) {
+        self.inner.load_policy_file(url)
+    }
+}
+
 #[derive(Default)]
 pub struct ExternalInterfaceTestProvider {}
 
@@ -0,0 +1,43 @@
+// The host builds context menus from this movie and picks items from them.
+var rootMenu = new ContextMenu(function(obj, menu) {
+    trace("// rootMenu.onSelect " + (obj == _root) + " " + (menu == rootMenu));
+});
+rootMenu.customItems.push(new ContextMenuItem("Custom", function(obj, item) {
+    trace("// Custom.onSelect " + (obj == _root) + " " + item.caption);
+}));
+var disabled = new ContextMenuItem("Disabled", function(obj, item) {
+    trace("// Disabled.onSelect");
+}, true, false);
+rootMenu.customItems.push(disabled);
+var hidden = new ContextMenuItem("Hidden", function(obj, item) {
+    trace("// Hidden.onSelect");
+});
+hidden.visible = false;
+rootMenu.customItems.push(hidden);
+_root.menu = rootMenu;
+
+var box = _root.createEmptyMovieClip("box", 1);
+box.beginFill(0xFF0000);
+box.moveTo(100, 100);
+box.lineTo(200, 100);
+box.lineTo(200, 200);
+box.lineTo(100, 200);
+box.lineTo(100, 100);
+box.endFill();
+var boxMenu = new ContextMenu(function(obj, menu) {
+    trace("// boxMenu.onSelect " + (obj == box));
+});
+boxMenu.hideBuiltInItems();
+boxMenu.customItems.push(new ContextMenuItem("Box item", function(obj, item) {
+    trace("// Box item.onSelect " + (obj == box));
+}));
+box.menu = boxMenu;
+
+// The box doesn't respond to the mouse, but still gets its own menu.
+box.enabled = false;
+
+trace("_currentframe: " + _currentframe);
+stop();
+// frame
+trace("_currentframe: " + _currentframe);
+stop();
@@ -0,0 +1,42 @@
+// Tests the properties of DisplacementMapFilter, and applying it to a clip.
+var map = {};
+var point = new flash.geom.Point(4, 5);
+var filter = new flash.filters.DisplacementMapFilter(map, point, 1, 2, 10, 20, "clamp", 0xFF0000, 0.5);
+trace("// new DisplacementMapFilter(...)");
+trace(filter.mapBitmap == map);
+trace(filter.mapPoint);
+trace(filter.componentX + " " + filter.componentY);
+trace(filter.scaleX + " " + filter.scaleY);
+trace(filter.mode);
+trace(filter.color + " " + filter.alpha);
+trace(filter instanceof flash.filters.BitmapFilter);
+
+trace("// mapPoint is copied");
+point.x = 100;
+trace(filter.mapPoint);
+
+trace("// mode");
+filter.mode = "color";
+trace(filter.mode);
+filter.mode = "ignore";
+trace(filter.mode);
+filter.mode = "invalid";
+trace(filter.mode);
+
+trace("// clone()");
+var cloned = filter.clone();
+trace(cloned == filter);
+trace(cloned.mapBitmap == map);
+trace(cloned.mapPoint);
+trace(cloned.componentX + " " + cloned.componentY + " " + cloned.scaleX + " " + cloned.scaleY);
+trace(cloned.mode + " " + cloned.color + " " + cloned.alpha);
+
+trace("// _root.filters");
+filter.mode = "color";
+_root.filters = [filter];
+var applied = _root.filters[0];
+trace(applied instanceof flash.filters.DisplacementMapFilter);
+trace(applied == filter);
+trace(applied.mapPoint);
+trace(applied.componentX + " " + applied.componentY + " " + applied.scaleX + " " + applied.scaleY);
+trace(applied.mode + " " + applied.color + " " + applied.alpha);
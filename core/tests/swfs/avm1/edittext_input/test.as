@@ -0,0 +1,28 @@
+// The host types into an input field and a password field, moving the caret,
+// selecting text and using the clipboard between frames.
+function makeField(name, depth, y) {
+    var field = _root.createTextField(name, depth, 0, y, 200, 20);
+    field.type = "input";
+    field.border = true;
+    return field;
+}
+var plain = makeField("plain", 1, 0);
+plain.maxChars = 8;
+var secret = makeField("secret", 2, 50);
+secret.password = true;
+secret.text = "hunter2";
+
+trace("plain.maxChars: " + plain.maxChars);
+trace("secret.password: " + secret.password);
+
+plain.onChanged = function(field) {
+    trace("onChanged: " + field);
+};
+secret.onChanged = plain.onChanged;
+
+_root.onEnterFrame = function() {
+    var focus = Selection.getFocus();
+    trace("focus " + focus + ", plain.text \"" + plain.text + "\", secret.text \"" + secret.text
+        + "\", selection " + Selection.getBeginIndex() + "-" + Selection.getEndIndex()
+        + ", caret " + Selection.getCaretIndex());
+};
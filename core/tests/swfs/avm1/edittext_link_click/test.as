@@ -0,0 +1,12 @@
+// The host moves the mouse over and clicks the links in an HTML text field.
+var field = _root.createTextField("field", 1, 0, 0, 300, 100);
+field.html = true;
+field.multiline = true;
+field.selectable = false;
+field.htmlText = "<p><a href=\"http://example.com/page\" target=\"_blank\">Visit the page</a></p>"
+    + "<p><a href=\"asfunction:showDetails,42\">Show details</a></p>"
+    + "<p>Plain text</p>";
+
+function showDetails(arg) {
+    trace("showDetails: " + arg + " (" + typeof arg + "), this " + this);
+}
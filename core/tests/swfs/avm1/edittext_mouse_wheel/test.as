@@ -0,0 +1,32 @@
+// The host scrolls the mouse wheel over two text fields, one of which ignores the wheel.
+function makeField(name, depth, x) {
+    var field = _root.createTextField(name, depth, x, 0, 100, 60);
+    field.multiline = true;
+    field.wordWrap = false;
+    field.border = true;
+    var text = "";
+    for (var i = 1; i <= 20; i++) {
+        text += "Line " + i + "\n";
+    }
+    field.text = text;
+    return field;
+}
+var scrolling = makeField("scrolling", 1, 0);
+var fixed = makeField("fixed", 2, 200);
+fixed.mouseWheelEnabled = false;
+
+trace("scrolling.mouseWheelEnabled: " + scrolling.mouseWheelEnabled);
+trace("fixed.mouseWheelEnabled: " + fixed.mouseWheelEnabled);
+trace("scrolling.maxscroll > 1: " + (scrolling.maxscroll > 1));
+
+var listener = new Object();
+listener.onMouseWheel = function(delta, target) {
+    trace("onMouseWheel: " + delta + " " + target + ", scrolling.scroll " + scrolling.scroll
+        + ", fixed.scroll " + fixed.scroll);
+};
+Mouse.addListener(listener);
+
+scrolling.onScroller = function(field) {
+    trace("onScroller: " + field + ", scroll " + field.scroll);
+};
+fixed.onScroller = scrolling.onScroller;
@@ -0,0 +1,50 @@
+// Loads from http://example.com/, which the test navigator serves from the example.com directory.
+// big.swf is padded so that it arrives in more than one chunk.
+var listener = new Object();
+listener.onLoadStart = function(target) {
+    trace("mcl.onLoadStart");
+};
+listener.onLoadProgress = function(target, loaded, total) {
+    trace("mcl.onLoadProgress: " + loaded + " / " + total);
+};
+listener.onLoadComplete = function(target, status) {
+    trace("mcl.onLoadComplete: " + status);
+};
+listener.onLoadError = function(target, error, status) {
+    trace("mcl.onLoadError: " + error + ", " + status);
+};
+var mcl = new MovieClipLoader();
+mcl.addListener(listener);
+_root.createEmptyMovieClip("big", 1);
+mcl.loadClip("http://example.com/big.swf", _root.big);
+_root.createEmptyMovieClip("missing", 2);
+mcl.loadClip("http://example.com/missing.swf", _root.missing);
+
+function makeVars(name) {
+    var vars = new LoadVars();
+    vars.onHTTPStatus = function(status) {
+        trace(name + ".onHTTPStatus: " + status);
+    };
+    vars.onLoad = function(success) {
+        trace(name + ".onLoad: " + success + ", greeting " + this.greeting + ", "
+            + this.getBytesLoaded() + " / " + this.getBytesTotal());
+    };
+    return vars;
+}
+makeVars("vars").load("http://example.com/vars.txt");
+makeVars("missingVars").load("http://example.com/missing.txt");
+
+function makeXml(name) {
+    var xml = new XML();
+    xml.ignoreWhite = true;
+    xml.onHTTPStatus = function(status) {
+        trace(name + ".onHTTPStatus: " + status);
+    };
+    xml.onLoad = function(success) {
+        trace(name + ".onLoad: " + success + ", " + this.firstChild.nodeName + ", "
+            + this.getBytesLoaded() + " / " + this.getBytesTotal());
+    };
+    return xml;
+}
+makeXml("xml").load("http://example.com/data.xml");
+makeXml("missingXml").load("http://example.com/missing.xml");
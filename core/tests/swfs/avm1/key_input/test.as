@@ -0,0 +1,20 @@
+// The host presses keys, and the listener reports what Key says about them.
+function report(event) {
+    trace(event + ": getCode " + Key.getCode() + ", getAscii " + Key.getAscii()
+        + ", isDown " + Key.isDown(Key.getCode()) + ", SHIFT " + Key.isDown(Key.SHIFT)
+        + ", CAPSLOCK " + Key.isToggled(Key.CAPSLOCK) + ", NUMLOCK " + Key.isToggled(144)
+        + ", isAccessible " + Key.isAccessible());
+}
+var listener = new Object();
+listener.onKeyDown = function() {
+    report("onKeyDown");
+};
+listener.onKeyUp = function() {
+    report("onKeyUp");
+};
+Key.addListener(listener);
+
+trace("isToggled(CAPSLOCK): " + Key.isToggled(Key.CAPSLOCK));
+trace("isToggled(65): " + Key.isToggled(65));
+trace("isToggled(): " + Key.isToggled());
+trace("isAccessible: " + Key.isAccessible());
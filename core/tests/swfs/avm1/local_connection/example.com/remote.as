@@ -0,0 +1,9 @@
+// Loaded from http://example.com/ by test.swf.
+var lc = new LocalConnection();
+lc.onStatus = function(info) {
+    trace("remote.onStatus: " + info.level);
+};
+trace("remote.domain(): " + lc.domain());
+lc.send("localhost:lobby", "hello", "from", "remote");
+lc.send("lobby", "hello", "unqualified");
+lc.send("_strict", "hello", "denied");
@@ -0,0 +1,47 @@
+// LocalConnection between this movie and remote.swf, which is loaded from http://example.com/.
+// The test navigator serves http://example.com/ from the example.com directory.
+var receiver = new LocalConnection();
+receiver.hello = function(a, b) {
+    trace("receiver.hello: " + a + ", " + b);
+};
+receiver.allowDomain = function(domain) {
+    trace("receiver.allowDomain: " + domain);
+    return domain == "example.com";
+};
+trace("receiver.domain(): " + receiver.domain());
+trace("receiver.connect(\"lobby\"): " + receiver.connect("lobby"));
+trace("receiver.connect(\"again\"): " + receiver.connect("again"));
+
+var other = new LocalConnection();
+trace("other.connect(\"Lobby\"): " + other.connect("Lobby"));
+trace("other.connect(\"localhost:lobby\"): " + other.connect("localhost:lobby"));
+
+var strict = new LocalConnection();
+strict.hello = function(a) {
+    trace("strict.hello: " + a);
+};
+strict.allowDomain = function(domain) {
+    trace("strict.allowDomain: " + domain);
+    return false;
+};
+trace("strict.connect(\"_strict\"): " + strict.connect("_strict"));
+
+var sender = new LocalConnection();
+sender.onStatus = function(info) {
+    trace("sender.onStatus: " + info.level);
+};
+trace("sender.send(\"lobby\"): " + sender.send("lobby", "hello", "world", 5));
+trace("sender.send(\"nobody\"): " + sender.send("nobody", "hello"));
+trace("sender.send(\"lobby\", \"connect\"): " + sender.send("lobby", "connect"));
+
+_root.createEmptyMovieClip("remote", 1);
+_root.remote.loadMovie("http://example.com/remote.swf");
+// frame
+trace("// frame 2");
+// frame
+trace("// frame 3");
+// frame
+trace("// frame 4");
+// frame
+strict.close();
+sender.send("_strict", "hello", "closed");
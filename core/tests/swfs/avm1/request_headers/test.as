@@ -0,0 +1,21 @@
+// Sends requests with custom headers to http://example.com/.
+// The test harness traces each request the player makes after the last frame.
+var vars = new LoadVars();
+vars.greeting = "hello";
+vars.addRequestHeader("X-Custom", "1");
+vars.addRequestHeader(["X-A", "a", "X-B", "b"]);
+vars.addRequestHeader("X-Number", 5);
+vars.addRequestHeader("Referer", "http://example.org/");
+vars.addRequestHeader("user-agent", "test");
+trace("vars._customHeaders: " + vars._customHeaders);
+vars.sendAndLoad("http://example.com/vars.txt", new LoadVars(), "POST");
+
+var typed = new LoadVars();
+typed.greeting = "hello";
+typed.contentType = "text/plain";
+typed.sendAndLoad("http://example.com/typed.txt", new LoadVars(), "POST");
+
+var xml = new XML("<a>b</a>");
+xml.contentType = "text/xml";
+xml.addRequestHeader("X-Xml", "yes");
+xml.sendAndLoad("http://example.com/data.xml", new XML());
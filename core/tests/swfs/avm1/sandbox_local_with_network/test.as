@@ -0,0 +1,13 @@
+// Untrusted local movies play in the sandbox chosen by the UseNetwork flag in their FileAttributes.
+// http://example.com/ is served from the example.com directory by the test navigator.
+trace("System.security.sandboxType: " + System.security.sandboxType);
+
+function load(name, url) {
+    var vars = new LoadVars();
+    vars.onLoad = function(success) {
+        trace(name + ".onLoad: " + success + ", greeting " + this.greeting);
+    };
+    vars.load(url);
+}
+load("local", "vars.txt");
+load("remote", "http://example.com/vars.txt");
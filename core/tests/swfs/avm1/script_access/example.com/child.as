@@ -0,0 +1,10 @@
+// A remote movie that only lets other domains script it once the root movie asks.
+var secret = "hidden";
+var doomed = "doomed";
+this.onEnterFrame = function() {
+    if (_global.unlock && !this.unlocked) {
+        this.unlocked = true;
+        System.security.allowDomain("*");
+        trace("child: allowed all domains");
+    }
+};
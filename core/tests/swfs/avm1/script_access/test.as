@@ -0,0 +1,43 @@
+// A local movie loads a remote movie, which it may only script once the remote
+// movie calls System.security.allowDomain.
+// http://example.com/ is served from the example.com directory by the test navigator.
+var holder = _root.createEmptyMovieClip("holder", 1);
+holder.loadMovie("http://example.com/child.swf");
+
+function probe(label) {
+    trace("// " + label);
+    trace("holder.secret: " + holder.secret);
+    trace("holder.doomed: " + holder.doomed);
+    trace("holder.extra: " + holder.extra);
+    trace("getProperty _x: " + getProperty(holder, 0));
+    var found = false;
+    for (var key in holder) {
+        if (key == "secret") {
+            found = true;
+        }
+    }
+    trace("for..in finds secret: " + found);
+    setTarget("holder");
+    trace("tellTarget by path: " + getProperty("", 13));
+    setTarget("");
+    setTarget(holder);
+    trace("tellTarget by reference: " + getProperty("", 13));
+    setTarget("");
+
+    holder.extra = "set by root";
+    setProperty(holder, 0, 42);
+    trace("delete holder.doomed: " + (delete holder.doomed));
+}
+
+var step = 0;
+_root.onEnterFrame = function() {
+    step++;
+    if (step == 3) {
+        probe("before allowDomain");
+        _global.unlock = true;
+    } else if (step == 5) {
+        probe("after allowDomain");
+    } else if (step == 6) {
+        probe("after writing");
+    }
+};
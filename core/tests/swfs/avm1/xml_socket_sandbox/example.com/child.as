@@ -0,0 +1,8 @@
+// A remote movie may only open sockets back to the host that it came from.
+function connect(name, host) {
+    var socket = new XMLSocket();
+    trace("child: connect to " + name + ": " + socket.connect(host, 2000));
+}
+connect("its own host", null);
+connect("example.com", "example.com");
+connect("other.com", "other.com");
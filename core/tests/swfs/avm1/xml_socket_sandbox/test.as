@@ -0,0 +1,10 @@
+// Sockets are checked against the sandbox of the movie that opens them, not the root movie's.
+// http://example.com/ is served from the example.com directory by the test navigator.
+function connect(name, host) {
+    var socket = new XMLSocket();
+    trace("root: connect to " + name + ": " + socket.connect(host, 2000));
+}
+connect("other.com", "other.com");
+
+var holder = _root.createEmptyMovieClip("holder", 1);
+holder.loadMovie("http://example.com/child.swf");
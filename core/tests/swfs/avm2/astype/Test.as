@@ -0,0 +1,30 @@
+﻿package {
+	public class Test {}
+}
+
+trace("// \"hello\" as String");
+trace("hello" as String);
+trace("// \"hello\" as Number");
+trace("hello" as Number);
+trace("// 5.5 as Number");
+trace(5.5 as Number);
+trace("// [1, 2] as Array");
+trace([1, 2] as Array);
+trace("// [1, 2] as String");
+trace([1, 2] as String);
+trace("// null as Object");
+trace(null as Object);
+trace("// undefined as Object");
+trace(undefined as Object);
+trace("// (new Test() as Test) === null");
+trace((new Test() as Test) === null);
+trace("// ({} as Test) === null");
+trace(({} as Test) === null);
+
+var cls:Class = String;
+trace("// \"late\" as cls (cls = String)");
+trace("late" as cls);
+
+cls = Array;
+trace("// \"late\" as cls (cls = Array)");
+trace("late" as cls);
@@ -0,0 +1,27 @@
+package {
+	public class Test {}
+}
+
+import flash.display.Sprite;
+
+var s:Sprite = new Sprite();
+trace("// default");
+trace(s.blendMode);
+
+trace("// valid names");
+var names:Array = ["normal", "layer", "multiply", "screen", "lighten", "darken", "difference", "add", "subtract", "invert", "alpha", "erase", "overlay", "hardlight"];
+for (var i:int = 0; i < names.length; i++) {
+	s.blendMode = names[i];
+	trace(s.blendMode);
+}
+
+trace("// invalid name");
+s.blendMode = "multiply";
+try {
+	s.blendMode = "shader";
+	trace("unreachable");
+} catch (e:ArgumentError) {
+	trace(e.errorID);
+	trace(e is ArgumentError);
+}
+trace(s.blendMode);
@@ -0,0 +1,59 @@
+package {
+	public class Test {}
+}
+
+import flash.utils.ByteArray;
+import flash.utils.Endian;
+
+var ba:ByteArray = new ByteArray();
+ba.writeInt(0x01020304);
+ba.writeShort(-2);
+ba.writeUTF("hi");
+ba.writeByte(200);
+
+trace("// ba.length");
+trace(ba.length);
+trace("// ba.position");
+trace(ba.position);
+trace("// ba[0]");
+trace(ba[0]);
+trace("// ba[3]");
+trace(ba[3]);
+
+ba.position = 0;
+trace("// ba.readInt()");
+trace(ba.readInt());
+trace("// ba.readShort()");
+trace(ba.readShort());
+trace("// ba.readUTF()");
+trace(ba.readUTF());
+trace("// ba.readByte()");
+trace(ba.readByte());
+trace("// ba.bytesAvailable");
+trace(ba.bytesAvailable);
+
+ba[15] = 7;
+trace("// ba.length after ba[15] = 7");
+trace(ba.length);
+
+var le:ByteArray = new ByteArray();
+le.endian = Endian.LITTLE_ENDIAN;
+le.writeInt(1);
+trace("// le.endian");
+trace(le.endian);
+trace("// le[0]");
+trace(le[0]);
+trace("// le[3]");
+trace(le[3]);
+
+var text:ByteArray = new ByteArray();
+text.writeUTFBytes("hello world");
+trace("// text.toString()");
+trace(text.toString());
+
+text.compress();
+text.uncompress();
+trace("// text.toString() after compress and uncompress");
+trace(text.toString());
+trace("// text.length");
+trace(text.length);
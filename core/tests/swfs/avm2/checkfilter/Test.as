@@ -0,0 +1,30 @@
+﻿package {
+	public class Test {}
+}
+
+var x:XML = new XML("<list><item kind=\"a\">one</item><item kind=\"b\">two</item><item kind=\"a\">three</item></list>");
+
+trace("// x.item.(@kind == \"a\").length()");
+trace(x.item.(@kind == "a").length());
+trace("// x.item.(@kind == \"a\")[1]");
+trace(x.item.(@kind == "a")[1]);
+trace("// x.item.(@kind == \"c\").length()");
+trace(x.item.(@kind == "c").length());
+
+trace("// filtering an Object");
+var o:Object = {kind: "a"};
+try {
+	o.(kind == "a");
+	trace("unreachable");
+} catch (e:TypeError) {
+	trace(e.errorID);
+}
+
+trace("// filtering a Number");
+var n = 5;
+try {
+	n.(kind == "a");
+	trace("unreachable");
+} catch (e:TypeError) {
+	trace(e.errorID);
+}
@@ -0,0 +1,39 @@
+﻿package {
+	public class Test {}
+}
+
+trace("// var a:Array = [1, 2, 3]");
+var a:Array = [1, 2, 3];
+trace(a);
+trace("// var t:Test = null");
+var t:Test = null;
+trace(t);
+trace("// var t:Test = undefined");
+t = undefined;
+trace(t);
+trace("// var o:Object = \"string\"");
+var o:Object = "string";
+trace(o);
+trace("// var s:String = 5");
+var s:String = 5;
+trace(s);
+trace("// var s:String = null");
+s = null;
+trace(s);
+trace("// var n:Number = \"3.5\"");
+var n:Number = "3.5";
+trace(n);
+trace("// var i:int = 7.9");
+var i:int = 7.9;
+trace(i);
+trace("// var u:uint = -1");
+var u:uint = -1;
+trace(u);
+trace("// var b:Boolean = \"x\"");
+var b:Boolean = "x";
+trace(b);
+trace("// var b:Boolean = 0");
+b = 0;
+trace(b);
+trace("// (t = new Test()) === null");
+trace((t = new Test()) === null);
@@ -0,0 +1,52 @@
+package {
+	public class Test {}
+}
+
+var d:Date = new Date(2001, 1, 3, 4, 5, 6);
+trace("// d");
+trace(d);
+trace("// d.getTime()");
+trace(d.getTime());
+trace("// d.fullYear");
+trace(d.fullYear);
+trace("// d.month");
+trace(d.month);
+trace("// d.date");
+trace(d.date);
+trace("// d.day");
+trace(d.day);
+trace("// d.hours");
+trace(d.hours);
+trace("// d.getUTCHours()");
+trace(d.getUTCHours());
+trace("// d.getUTCDate()");
+trace(d.getUTCDate());
+trace("// d.timezoneOffset");
+trace(d.timezoneOffset);
+trace("// d.toUTCString()");
+trace(d.toUTCString());
+trace("// d.toDateString()");
+trace(d.toDateString());
+trace("// d.toTimeString()");
+trace(d.toTimeString());
+trace("// d.toLocaleString()");
+trace(d.toLocaleString());
+
+d.setMonth(11);
+trace("// d after d.setMonth(11)");
+trace(d);
+
+d.date = 32;
+trace("// d after d.date = 32");
+trace(d);
+
+trace("// Date.UTC(2000, 0, 1)");
+trace(Date.UTC(2000, 0, 1));
+trace("// Date.parse(\"Sat Feb 3 04:05:06 GMT+0545 2001\")");
+trace(Date.parse("Sat Feb 3 04:05:06 GMT+0545 2001"));
+trace("// new Date()");
+trace(new Date());
+trace("// new Date(0).toUTCString()");
+trace(new Date(0).toUTCString());
+trace("// new Date(NaN)");
+trace(new Date(NaN));
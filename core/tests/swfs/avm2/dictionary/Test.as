@@ -0,0 +1,41 @@
+package {
+	public class Test {}
+}
+
+import flash.utils.Dictionary;
+
+var d:Dictionary = new Dictionary();
+var a:Object = {};
+var b:Object = {};
+
+d[a] = "value a";
+d[b] = "value b";
+d["name"] = "string key";
+
+trace("// d[a]");
+trace(d[a]);
+trace("// d[b]");
+trace(d[b]);
+trace("// d[\"name\"]");
+trace(d["name"]);
+trace("// a in d");
+trace(a in d);
+
+delete d[a];
+
+trace("// d[a] after delete d[a]");
+trace(d[a]);
+trace("// a in d");
+trace(a in d);
+trace("// b in d");
+trace(b in d);
+
+trace("// for (var k in d)");
+var count:int = 0;
+for (var k in d) {
+	count++;
+	if (k === b) {
+		trace("found b");
+	}
+}
+trace(count);
@@ -0,0 +1,56 @@
+package {
+	public class Test {}
+}
+
+import flash.display.BitmapData;
+import flash.display.Shape;
+import flash.display.Sprite;
+
+var s:Sprite = new Sprite();
+var a:Shape = new Shape();
+var b:Sprite = new Sprite();
+trace("// s.numChildren");
+trace(s.numChildren);
+s.addChild(a);
+s.addChild(b);
+trace("// s.numChildren after adding a and b");
+trace(s.numChildren);
+trace("// s.getChildAt(0) === a");
+trace(s.getChildAt(0) === a);
+trace("// s.getChildIndex(b)");
+trace(s.getChildIndex(b));
+trace("// a.parent === s");
+trace(a.parent === s);
+s.setChildIndex(b, 0);
+trace("// s.getChildAt(0) === b after setChildIndex(b, 0)");
+trace(s.getChildAt(0) === b);
+b.name = "child";
+trace("// s.getChildByName(\"child\") === b");
+trace(s.getChildByName("child") === b);
+s.removeChild(a);
+trace("// s.numChildren after removeChild(a)");
+trace(s.numChildren);
+trace("// a.parent");
+trace(a.parent);
+trace("// s.contains(a)");
+trace(s.contains(a));
+trace("// s.contains(b)");
+trace(s.contains(b));
+var g = a.graphics;
+g.beginFill(0xFF0000);
+g.drawRect(0, 0, 10, 20);
+g.endFill();
+trace("// a.width");
+trace(a.width);
+trace("// a.height");
+trace(a.height);
+var bmd:BitmapData = new BitmapData(4, 3, true, 0xFF00FF00);
+trace("// bmd.width");
+trace(bmd.width);
+trace("// bmd.height");
+trace(bmd.height);
+trace("// bmd.getPixel(0, 0)");
+trace(bmd.getPixel(0, 0));
+bmd.setPixel32(1, 1, 0xFF0000FF);
+trace("// bmd.getPixel32(1, 1)");
+trace(bmd.getPixel32(1, 1));
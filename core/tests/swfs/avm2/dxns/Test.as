@@ -0,0 +1,21 @@
+﻿package {
+	public class Test {}
+}
+
+function staticNamespace() {
+	default xml namespace = "http://example.com/static";
+	var x:XML = new XML("<a><b>static</b></a>");
+	trace(x.b);
+}
+
+function dynamicNamespace(uri) {
+	default xml namespace = uri;
+	var x:XML = new XML("<a><b>dynamic</b></a>");
+	trace(x.b);
+}
+
+trace("// default xml namespace = \"http://example.com/static\"");
+staticNamespace();
+trace("// default xml namespace = uri");
+dynamicNamespace("http://example.com/dynamic");
+trace("// done");
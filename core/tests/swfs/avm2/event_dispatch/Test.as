@@ -0,0 +1,65 @@
+﻿package {
+	public class Test {}
+}
+
+import flash.events.Event;
+import flash.events.EventDispatcher;
+import flash.events.MouseEvent;
+
+var d = new EventDispatcher();
+
+function report(label, e) {
+	trace(label, e.type, e.eventPhase, e.target === d, e.currentTarget === d);
+}
+
+var listener = function(e) { report("listener:", e); };
+
+trace("// addEventListener and dispatchEvent");
+d.addEventListener("test", listener);
+trace(d.hasEventListener("test"));
+trace(d.hasEventListener("other"));
+trace(d.willTrigger("test"));
+trace(d.dispatchEvent(new Event("test", false, false)));
+
+trace("// adding the same listener twice");
+d.addEventListener("test", listener);
+trace(d.dispatchEvent(new Event("test", false, false)));
+
+trace("// priority ordering");
+d.addEventListener("prio", function(e) { report("low:", e); }, false, 0);
+d.addEventListener("prio", function(e) { report("high:", e); }, false, 10);
+d.addEventListener("prio", function(e) { report("middle:", e); }, false, 5);
+trace(d.dispatchEvent(new Event("prio", false, false)));
+
+trace("// stopImmediatePropagation");
+d.addEventListener("stop", function(e) { report("stopper:", e); e.stopImmediatePropagation(); }, false, 5);
+d.addEventListener("stop", function(e) { report("never:", e); }, false, 0);
+trace(d.dispatchEvent(new Event("stop", false, false)));
+
+trace("// preventDefault");
+d.addEventListener("cancel", function(e) { report("canceller:", e); e.preventDefault(); });
+trace(d.dispatchEvent(new Event("cancel", false, true)));
+trace(d.dispatchEvent(new Event("cancel", false, false)));
+
+trace("// removeEventListener");
+d.removeEventListener("test", function(e) { report("other:", e); });
+trace(d.hasEventListener("test"));
+d.removeEventListener("test", listener);
+trace(d.hasEventListener("test"));
+trace(d.dispatchEvent(new Event("test", false, false)));
+
+trace("// Event properties");
+var e = new Event("custom", true, false);
+trace(e);
+trace(e.target);
+trace(e.clone());
+trace(e.clone() === e);
+trace(Event.ENTER_FRAME);
+
+trace("// MouseEvent");
+var m = new MouseEvent("click");
+trace(m.bubbles);
+trace(m.localX);
+trace(m.ctrlKey);
+trace(m.relatedObject);
+trace(MouseEvent.MOUSE_DOWN);
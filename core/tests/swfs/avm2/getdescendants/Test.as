@@ -0,0 +1,27 @@
+﻿package {
+	public class Test {}
+}
+
+var x:XML = new XML("<a id=\"1\"><b id=\"2\"><c id=\"3\">deep</c></b><c id=\"4\">shallow</c></a>");
+
+trace("// x..c.length()");
+trace(x..c.length());
+trace("// x..c[0]");
+trace(x..c[0]);
+trace("// x..c[1]");
+trace(x..c[1]);
+trace("// x..b..c");
+trace(x..b..c);
+trace("// x..missing.length()");
+trace(x..missing.length());
+trace("// x.b..c.length()");
+trace(x.b..c.length());
+
+trace("// descendants of an Object");
+var o:Object = {c: 1};
+try {
+	o..c;
+	trace("unreachable");
+} catch (e:TypeError) {
+	trace(e.errorID);
+}
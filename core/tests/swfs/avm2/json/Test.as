@@ -0,0 +1,28 @@
+package {
+	public class Test {}
+}
+
+var o:Object = JSON.parse('{"name": "ruffle", "list": [1, 2.5, "three", true, null], "nested": {"a": {"b": 7}}}');
+trace("// o.name");
+trace(o.name);
+trace("// o.list");
+trace(o.list);
+trace("// o.list.length");
+trace(o.list.length);
+trace("// o.nested.a.b");
+trace(o.nested.a.b);
+
+trace("// JSON.stringify([1, \"two\", true, null])");
+trace(JSON.stringify([1, "two", true, null]));
+trace("// JSON.stringify({greeting: \"hi \\\"there\\\"\\n\"})");
+trace(JSON.stringify({greeting: "hi \"there\"\n"}));
+trace("// JSON.stringify(\"text\")");
+trace(JSON.stringify("text"));
+trace("// JSON.stringify(undefined)");
+trace(JSON.stringify(undefined));
+trace("// JSON.stringify([[1, 2], {x: 1}], null, 2)");
+trace(JSON.stringify([[1, 2], {x: 1}], null, 2));
+trace("// JSON.stringify([1, 2, 3], replacer)");
+trace(JSON.stringify([1, 2, 3], function(k, v) {
+	return typeof v == "number" ? v * 10 : v;
+}));
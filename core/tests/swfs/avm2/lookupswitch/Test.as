@@ -0,0 +1,34 @@
+﻿package {
+	public class Test {}
+}
+
+function describe(i) {
+	switch (i) {
+		case 0:
+			trace("zero");
+			break;
+		case 1:
+			trace("one");
+			break;
+		case 2:
+			trace("two");
+		case 3:
+			trace("three");
+			break;
+		default:
+			trace("default");
+	}
+}
+
+trace("// describe(-1)");
+describe(-1);
+trace("// describe(0)");
+describe(0);
+trace("// describe(1)");
+describe(1);
+trace("// describe(2)");
+describe(2);
+trace("// describe(3)");
+describe(3);
+trace("// describe(4)");
+describe(4);
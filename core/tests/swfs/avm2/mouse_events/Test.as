@@ -0,0 +1,73 @@
+﻿package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			setup(this);
+		}
+	}
+}
+
+import flash.display.Sprite;
+
+function report(e) {
+	trace(e.currentTarget.name + ": " + e.type + " (target " + e.target.name + ")");
+}
+
+function listen(s) {
+	s.addEventListener("mouseOver", report);
+	s.addEventListener("mouseOut", report);
+	s.addEventListener("rollOver", report);
+	s.addEventListener("rollOut", report);
+	s.addEventListener("click", report);
+}
+
+function box(name, x, y) {
+	var s = new Sprite();
+	s.name = name;
+	s.graphics.beginFill(0xFF0000);
+	s.graphics.drawRect(x, y, 100, 100);
+	s.graphics.endFill();
+	return s;
+}
+
+function setup(root) {
+	var outer = box("outer", 0, 100);
+	var inner = box("inner", 50, 100);
+	outer.addEventListener("added", report);
+	root.addChild(outer);
+	outer.addChild(inner);
+	listen(outer);
+	listen(inner);
+
+	var holder = new Sprite();
+	holder.name = "holder";
+	var blocked = box("blocked", 200, 100);
+	blocked.mouseEnabled = false;
+	holder.addChild(blocked);
+	root.addChild(holder);
+	listen(holder);
+	listen(blocked);
+
+	var container = new Sprite();
+	container.name = "container";
+	var leaf = box("leaf", 400, 100);
+	container.addChild(leaf);
+	container.mouseChildren = false;
+	root.addChild(container);
+	listen(container);
+	listen(leaf);
+
+	trace("// defaults");
+	trace(inner.mouseEnabled);
+	trace(inner.mouseChildren);
+	trace(inner.buttonMode);
+	trace(inner.useHandCursor);
+	trace("// after setting");
+	trace(blocked.mouseEnabled);
+	trace(container.mouseChildren);
+	container.buttonMode = true;
+	trace(container.buttonMode);
+	container.useHandCursor = false;
+	trace(container.useHandCursor);
+}
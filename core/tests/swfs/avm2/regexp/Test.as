@@ -0,0 +1,54 @@
+package {
+	public class Test {}
+}
+
+var re:RegExp = /(\d+)-(\d+)/;
+trace("// re.source");
+trace(re.source);
+trace("// re");
+trace(re);
+
+var m:Object = re.exec("call 555-1234 now");
+trace("// re.exec(\"call 555-1234 now\")");
+trace(m);
+trace("// m.index");
+trace(m.index);
+trace("// m[2]");
+trace(m[2]);
+trace("// re.exec(\"no digits\")");
+trace(re.exec("no digits"));
+trace("// re.test(\"1-2\")");
+trace(re.test("1-2"));
+
+var g:RegExp = /a./gi;
+trace("// g.global");
+trace(g.global);
+trace("// g.ignoreCase");
+trace(g.ignoreCase);
+trace("// g.exec(\"xAbxacxad\")");
+trace(g.exec("xAbxacxad"));
+trace("// g.lastIndex");
+trace(g.lastIndex);
+trace("// g.exec(\"xAbxacxad\")");
+trace(g.exec("xAbxacxad"));
+trace("// g.lastIndex");
+trace(g.lastIndex);
+
+trace("// /^b/m.test(\"a\\nb\")");
+trace(/^b/m.test("a\nb"));
+trace("// /^b/.test(\"a\\nb\")");
+trace(/^b/.test("a\nb"));
+trace("// /a.c/s.test(\"a\\nc\")");
+trace(/a.c/s.test("a\nc"));
+trace("// /a b # comment/x.test(\"ab\")");
+trace(/a b # comment/x.test("ab"));
+trace("// /^hello$/i.test(\"HELLO\")");
+trace(/^hello$/i.test("HELLO"));
+trace("// /(cat|dog)s?/.exec(\"hotdogs\")");
+trace(/(cat|dog)s?/.exec("hotdogs"));
+trace("// /(\\w)\\1/.exec(\"abccd\")");
+trace(/(\w)\1/.exec("abccd"));
+trace("// /[^a-c]+/.exec(\"abcdefa\")");
+trace(/[^a-c]+/.exec("abcdefa"));
+trace("// new RegExp(\"x+\", \"g\")");
+trace(new RegExp("x+", "g"));
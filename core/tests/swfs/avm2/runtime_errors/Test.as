@@ -0,0 +1,84 @@
+﻿package {
+	public class Test {}
+}
+
+function readNull() {
+	var n:Object = null;
+	return n.x;
+}
+
+trace("// property access on null");
+var o:Object = null;
+try {
+	o.foo;
+	trace("unreachable");
+} catch (e:TypeError) {
+	trace(e);
+	trace(e.errorID);
+	trace(e is Error);
+}
+
+trace("// calling a missing method");
+var p:Object = {};
+try {
+	p.missing();
+} catch (e:TypeError) {
+	trace(e.errorID);
+}
+
+trace("// undefined variable");
+try {
+	undefinedVariable;
+} catch (e:ReferenceError) {
+	trace(e.name);
+	trace(e.errorID);
+}
+
+trace("// error raised in a called function");
+try {
+	readNull();
+} catch (e:Error) {
+	trace(e.name);
+}
+
+trace("// handlers for other types are skipped");
+try {
+	o.foo;
+} catch (e:RangeError) {
+	trace("caught as RangeError");
+} catch (e:TypeError) {
+	trace("caught as TypeError");
+}
+
+trace("// constructing errors");
+try {
+	throw new ArgumentError("bad argument", 2004);
+} catch (e:ArgumentError) {
+	trace(e.message);
+	trace(e.errorID);
+	trace(e.toString());
+}
+var err:Error = new Error();
+trace(err);
+trace(err.message == "");
+trace(err.getStackTrace());
+try {
+	throw new RangeError("out of range");
+} catch (e:TypeError) {
+	trace("caught as TypeError");
+} catch (e:Error) {
+	trace(e);
+}
+
+trace("// errors from the flash.errors package");
+import flash.utils.ByteArray;
+import flash.errors.EOFError;
+import flash.errors.IOError;
+var bytes:ByteArray = new ByteArray();
+try {
+	bytes.readByte();
+} catch (e:EOFError) {
+	trace(e);
+	trace(e.errorID);
+	trace(e is IOError);
+}
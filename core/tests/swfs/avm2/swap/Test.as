@@ -0,0 +1,22 @@
+﻿package {
+	public class Test {}
+}
+
+var g;
+var o:Object = {};
+
+trace("// trace(g = \"global\")");
+trace(g = "global");
+trace("// g");
+trace(g);
+
+trace("// trace(o.x = 5)");
+trace(o.x = 5);
+trace("// o.x");
+trace(o.x);
+
+trace("// var chained = o.y = g = \"chained\"");
+var chained = o.y = g = "chained";
+trace(chained);
+trace(o.y);
+trace(g);
@@ -0,0 +1,27 @@
+package {
+	public class Test {}
+}
+
+import flash.display.Sprite;
+
+var s:Sprite = new Sprite();
+trace("// s.tabEnabled");
+trace(s.tabEnabled);
+trace("// s.tabIndex");
+trace(s.tabIndex);
+trace("// s.tabChildren");
+trace(s.tabChildren);
+trace("// s.focusRect");
+trace(s.focusRect);
+s.tabEnabled = true;
+s.tabIndex = 3;
+s.tabChildren = false;
+s.focusRect = false;
+trace("// s.tabEnabled after setting it to true");
+trace(s.tabEnabled);
+trace("// s.tabIndex after setting it to 3");
+trace(s.tabIndex);
+trace("// s.tabChildren after setting it to false");
+trace(s.tabChildren);
+trace("// s.focusRect after setting it to false");
+trace(s.focusRect);
@@ -0,0 +1,52 @@
+﻿package {
+	public class Test {}
+}
+
+function thrower() {
+	throw "from function";
+}
+
+trace("// throw and catch a string");
+try {
+	throw "thrown string";
+	trace("unreachable");
+} catch (e) {
+	trace(e);
+}
+
+trace("// finally runs after catch");
+try {
+	throw 5;
+} catch (e) {
+	trace(e);
+} finally {
+	trace("finally");
+}
+
+trace("// typed catch");
+try {
+	throw "string error";
+} catch (e:Number) {
+	trace("caught as Number");
+} catch (e:String) {
+	trace("caught as String");
+}
+
+trace("// exception thrown from a called function");
+try {
+	thrower();
+} catch (e) {
+	trace(e);
+}
+
+trace("// throw from a catch block");
+try {
+	try {
+		throw "inner";
+	} catch (e) {
+		trace("inner catch");
+		throw "outer";
+	}
+} catch (e) {
+	trace(e);
+}
@@ -0,0 +1,26 @@
+﻿package {
+	public class Test {}
+}
+
+trace("// typeof undefined");
+trace(typeof undefined);
+trace("// typeof null");
+trace(typeof null);
+trace("// typeof true");
+trace(typeof true);
+trace("// typeof 5");
+trace(typeof 5);
+trace("// typeof 5.5");
+trace(typeof 5.5);
+trace("// typeof \"string\"");
+trace(typeof "string");
+trace("// typeof {}");
+trace(typeof {});
+trace("// typeof []");
+trace(typeof []);
+trace("// typeof function() {}");
+trace(typeof function() {});
+trace("// typeof Test");
+trace(typeof Test);
+trace("// typeof new Test()");
+trace(typeof new Test());
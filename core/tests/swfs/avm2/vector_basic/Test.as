@@ -0,0 +1,64 @@
+package {
+	public class Test {}
+}
+
+var v:Vector.<int> = new Vector.<int>();
+v.push(1);
+v.push(2);
+v.push(3);
+
+trace("// v.length");
+trace(v.length);
+trace("// v.toString()");
+trace(v.toString());
+trace("// v[1]");
+trace(v[1]);
+
+v[3] = 4;
+trace("// v after v[3] = 4");
+trace(v);
+
+v.push("5");
+trace("// v[4] + 1 after v.push(\"5\")");
+trace(v[4] + 1);
+
+trace("// v.join(\"-\")");
+trace(v.join("-"));
+trace("// v.indexOf(3)");
+trace(v.indexOf(3));
+trace("// v.indexOf(9)");
+trace(v.indexOf(9));
+trace("// v.pop()");
+trace(v.pop());
+trace("// v.shift()");
+trace(v.shift());
+trace("// v.slice(1, 3)");
+trace(v.slice(1, 3));
+trace("// v.reverse()");
+trace(v.reverse());
+
+trace("// for each (var x in v)");
+for each (var x in v) {
+	trace(x);
+}
+
+trace("// for (var k in v)");
+for (var k in v) {
+	trace(k);
+}
+
+var s:Vector.<String> = new Vector.<String>(2);
+trace("// s.length");
+trace(s.length);
+trace("// s[0]");
+trace(s[0]);
+
+var n:Vector.<Number> = new Vector.<Number>(2);
+trace("// n[1]");
+trace(n[1]);
+
+var f:Vector.<int> = new Vector.<int>(3, true);
+trace("// f.fixed");
+trace(f.fixed);
+trace("// f");
+trace(f);
@@ -0,0 +1,38 @@
+package {
+	public class Test {}
+}
+
+var x:XML = <library name="city"><book id="1"><title>Persuasion</title><year>1817</year></book><book id="2"><title>Emma</title><year>1815</year></book></library>;
+trace("// x.@name");
+trace(x.@name);
+trace("// x.book.length()");
+trace(x.book.length());
+trace("// x.book[1].title");
+trace(x.book[1].title);
+trace("// x.book.(@id == \"2\").year");
+trace(x.book.(@id == "2").year);
+trace("// x..title.length()");
+trace(x..title.length());
+trace("// x..title");
+trace(x..title);
+trace("// typeof x");
+trace(typeof x);
+trace("// x.child(\"book\")[0].@id");
+trace(x.child("book")[0].@id);
+
+trace("// x.book[0].year = \"1818\"");
+x.book[0].year = "1818";
+trace(x.book[0].year);
+
+trace("// x.@name = \"town\"");
+x.@name = "town";
+trace(x.@name);
+
+trace("// delete x.book[1].year");
+delete x.book[1].year;
+trace(x.book[1].toXMLString());
+
+trace("// new XML(\"<a><b>hi &amp; bye</b></a>\").b");
+trace(new XML("<a><b>hi &amp; bye</b></a>").b);
+trace("// x.toXMLString()");
+trace(x.toXMLString());
@@ -0,0 +1,51 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			setup(this);
+		}
+	}
+}
+
+import flash.display.Sprite;
+
+// Each blend mode is drawn in a column, over the stage in the first row,
+// and inside a parent with the layer blend mode in the second row.
+var modes:Array = ["normal", "layer", "multiply", "screen", "lighten", "darken", "difference", "add", "subtract", "invert", "alpha", "erase", "overlay", "hardlight"];
+
+function rect(s, color, alpha, x, y, width, height) {
+	s.graphics.beginFill(color, alpha);
+	s.graphics.drawRect(x, y, width, height);
+	s.graphics.endFill();
+}
+
+function backdrop(s, x, y) {
+	rect(s, 0x3366CC, 1, x, y, 20, 10);
+	rect(s, 0xCC9933, 0.5, x, y + 10, 20, 10);
+}
+
+function tile(root, mode, x) {
+	var background:Sprite = new Sprite();
+	backdrop(background, x, 0);
+	root.addChild(background);
+	var source:Sprite = new Sprite();
+	rect(source, 0xE08040, 0.75, x + 5, 5, 10, 10);
+	source.blendMode = mode;
+	root.addChild(source);
+
+	var layer:Sprite = new Sprite();
+	layer.blendMode = "layer";
+	backdrop(layer, x, 20);
+	var child:Sprite = new Sprite();
+	rect(child, 0xE08040, 0.75, x, 20, 20, 20);
+	child.blendMode = mode;
+	layer.addChild(child);
+	root.addChild(layer);
+}
+
+function setup(root) {
+	for (var i:int = 0; i < modes.length; i++) {
+		tile(root, modes[i], i * 20);
+	}
+}
@@ -1,10 +1,10 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use generational_arena::Arena;
 use ruffle_core::backend::audio::decoders::{
-    self, AdpcmDecoder, Mp3Decoder, PcmDecoder, SeekableDecoder,
+    self, AdpcmDecoder, Mp3Decoder, NellymoserDecoder, PcmDecoder, SeekableDecoder, SpeexDecoder,
 };
 use ruffle_core::backend::audio::{
-    swf, AudioBackend, AudioStreamHandle, SoundHandle, SoundInstanceHandle,
+    swf, AudioBackend, AudioStreamHandle, SoundHandle, SoundInstanceHandle, SoundTransform,
 };
 use ruffle_core::tag_utils::SwfSlice;
 use std::io::Cursor;
@@ -66,6 +66,9 @@ struct SoundInstance {
     /// If this flag is false, the sound will be cleaned up during the
     /// next loop of the sound thread.
     active: bool,
+
+    /// The mixing matrix applied to this sound, from `SoundTransform::matrix`.
+    transform: [f32; 4],
 }
 
 impl CpalAudioBackend {
@@ -166,6 +169,13 @@ impl CpalAudioBackend {
                 format.sample_rate.into(),
                 data,
             )),
+            AudioCompression::Nellymoser
+            | AudioCompression::Nellymoser8Khz
+            | AudioCompression::Nellymoser16Khz => Box::new(NellymoserDecoder::new(
+                data,
+                decoders::effective_sample_rate(format),
+            )),
+            AudioCompression::Speex => Box::new(SpeexDecoder::new(data)),
             _ => {
                 let msg = format!(
                     "start_stream: Unhandled audio compression {:?}",
@@ -182,7 +192,7 @@ impl CpalAudioBackend {
     /// TODO: Allow interpolator to be user-configurable?
     fn make_resampler<S: Send + dasp::signal::Signal<Frame = [i16; 2]>>(
         &self,
-        format: &swf::SoundFormat,
+        sample_rate: u16,
         mut signal: S,
     ) -> dasp::signal::interpolate::Converter<
         S,
@@ -194,7 +204,7 @@ impl CpalAudioBackend {
         dasp::signal::interpolate::Converter::from_hz_to_hz(
             signal,
             interpolator,
-            format.sample_rate.into(),
+            sample_rate.into(),
             self.output_config.sample_rate.0.into(),
         )
     }
@@ -209,6 +219,7 @@ impl CpalAudioBackend {
     ) -> Result<Box<dyn Send + dasp::signal::Signal<Frame = [i16; 2]>>, Error> {
         // Instantiate a decoder for the compression that the sound data uses.
         let decoder = Self::make_seekable_decoder(&sound.format, data)?;
+        let sample_rate = decoder.sample_rate();
 
         // Wrap the decoder in the event sound signal (controls looping/envelope)
         let signal = EventSoundSignal::new_with_settings(
@@ -219,7 +230,7 @@ impl CpalAudioBackend {
         );
         // Convert the `Decoder` to a `Signal`, and resample it the the output
         // sample rate.
-        let signal = self.make_resampler(&sound.format, signal);
+        let signal = self.make_resampler(sample_rate, signal);
         Ok(Box::new(signal))
     }
 
@@ -231,11 +242,12 @@ impl CpalAudioBackend {
     ) -> Result<Box<dyn 'a + Send + dasp::signal::Signal<Frame = [i16; 2]>>, Error> {
         // Instantiate a decoder for the compression that the sound data uses.
         let clip_stream_decoder = decoders::make_stream_decoder(format, data_stream)?;
+        let sample_rate = clip_stream_decoder.sample_rate();
 
         // Convert the `Decoder` to a `Signal`, and resample it the the output
         // sample rate.
         let signal = dasp::signal::from_iter(clip_stream_decoder);
-        let signal = Box::new(self.make_resampler(format, signal));
+        let signal = Box::new(self.make_resampler(sample_rate, signal));
         Ok(signal)
     }
 
@@ -248,11 +260,12 @@ impl CpalAudioBackend {
     ) -> Result<Box<dyn 'a + Send + dasp::signal::Signal<Frame = [i16; 2]>>, Error> {
         // Instantiate a decoder for the compression that the sound data uses.
         let decoder = decoders::make_decoder(format, data_stream)?;
+        let sample_rate = decoder.sample_rate();
 
         // Convert the `Decoder` to a `Signal`, and resample it the the output
         // sample rate.
         let signal = dasp::signal::from_iter(decoder);
-        let signal = self.make_resampler(format, signal);
+        let signal = self.make_resampler(sample_rate, signal);
         Ok(Box::new(signal))
     }
 
@@ -282,6 +295,13 @@ impl CpalAudioBackend {
             for (_, sound) in sound_instances.iter_mut() {
                 if sound.active && !sound.signal.is_exhausted() {
                     let sound_frame = sound.signal.next();
+                    let [left_to_left, left_to_right, right_to_left, right_to_right] =
+                        sound.transform;
+                    let (left, right) = (f32::from(sound_frame[0]), f32::from(sound_frame[1]));
+                    let sound_frame = [
+                        (left * left_to_left + right * right_to_left) as i16,
+                        (left * left_to_right + right * right_to_right) as i16,
+                    ];
                     let sound_frame: Stereo<T::Signed> = Frame::map(sound_frame, Sample::to_sample);
                     output_frame = output_frame.add_amp(sound_frame);
                 } else {
@@ -347,6 +367,7 @@ impl AudioBackend for CpalAudioBackend {
             clip_id: Some(clip_id),
             signal,
             active: true,
+            transform: SoundTransform::default().matrix(),
         });
         Ok(handle)
     }
@@ -384,6 +405,7 @@ impl AudioBackend for CpalAudioBackend {
             clip_id: None,
             signal,
             active: true,
+            transform: SoundTransform::default().matrix(),
         });
         Ok(handle)
     }
@@ -411,6 +433,14 @@ impl AudioBackend for CpalAudioBackend {
         sound_instances.retain(|_, instance| instance.handle != handle);
     }
 
+    fn is_sound_playing(&mut self, instance: SoundInstanceHandle) -> bool {
+        let sound_instances = self.sound_instances.lock().unwrap();
+        sound_instances
+            .get(instance)
+            .map(|instance| instance.active)
+            .unwrap_or(false)
+    }
+
     fn get_sound_duration(&self, sound: SoundHandle) -> Option<u32> {
         if let Some(sound) = self.sounds.get(sound) {
             // AS duration does not subtract skip_sample_frames.
@@ -430,6 +460,13 @@ impl AudioBackend for CpalAudioBackend {
             .any(|(_, instance)| instance.handle == handle && instance.active)
     }
 
+    fn set_sound_transform(&mut self, instance: SoundInstanceHandle, transform: SoundTransform) {
+        let mut sound_instances = self.sound_instances.lock().unwrap();
+        if let Some(instance) = sound_instances.get_mut(instance) {
+            instance.transform = transform.matrix();
+        }
+    }
+
     fn tick(&mut self) {}
 }
 
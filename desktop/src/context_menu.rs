@@ -0,0 +1,258 @@
+//! Native popup menu for the player's right-click context menu.
+
+use ruffle_core::context_menu::ContextMenuItem;
+use winit::window::Window;
+
+/// Show `items` as a popup menu at the mouse cursor, blocking until the user
+/// chooses an item or dismisses the menu.
+///
+/// Returns the index of the chosen item, if any.
+#[cfg(windows)]
+pub fn show_context_menu(window: &Window, items: &[ContextMenuItem]) -> Option<usize> {
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use winapi::shared::windef::{HWND, POINT};
+    use winapi::um::winuser::*;
+    use winit::platform::windows::WindowExtWindows;
+
+    if items.is_empty() {
+        return None;
+    }
+
+    unsafe {
+        let menu = CreatePopupMenu();
+        if menu.is_null() {
+            return None;
+        }
+
+        for (i, item) in items.iter().enumerate() {
+            if item.separator_before && i > 0 {
+                AppendMenuW(menu, MF_SEPARATOR, 0, std::ptr::null());
+            }
+
+            let mut flags = MF_STRING;
+            if !item.enabled {
+                flags |= MF_GRAYED;
+            }
+            if item.checked {
+                flags |= MF_CHECKED;
+            }
+
+            // A single `&` marks a keyboard shortcut in Win32 menus.
+            let caption: Vec<u16> = OsStr::new(&item.caption.replace('&', "&&"))
+                .encode_wide()
+                .chain(Some(0))
+                .collect();
+
+            // Command IDs start at 1, because `TrackPopupMenu` returns 0 when
+            // the menu is dismissed.
+            AppendMenuW(menu, flags, i + 1, caption.as_ptr());
+        }
+
+        let mut point = POINT { x: 0, y: 0 };
+        GetCursorPos(&mut point);
+        let chosen = TrackPopupMenu(
+            menu,
+            TPM_RETURNCMD | TPM_RIGHTBUTTON,
+            point.x,
+            point.y,
+            0,
+            window.hwnd() as HWND,
+            std::ptr::null(),
+        );
+        DestroyMenu(menu);
+
+        if chosen > 0 {
+            Some(chosen as usize - 1)
+        } else {
+            None
+        }
+    }
+}
+
+/// Show `items` as a popup menu at the mouse cursor, blocking until the user
+/// chooses an item or dismisses the menu.
+///
+/// Returns the index of the chosen item, if any.
+#[cfg(target_os = "macos")]
+pub fn show_context_menu(window: &Window, items: &[ContextMenuItem]) -> Option<usize> {
+    use cocoa::base::{id, nil, BOOL, NO, YES};
+    use cocoa::foundation::{NSAutoreleasePool, NSPoint, NSString};
+    use objc::declare::ClassDecl;
+    use objc::runtime::{Class, Object, Sel};
+    use objc::{class, msg_send, sel, sel_impl};
+    use winit::platform::macos::WindowExtMacOS;
+
+    /// The action of every item, which records the tag of the chosen item.
+    extern "C" fn choose_item(this: &mut Object, _: Sel, sender: id) {
+        unsafe {
+            let tag: isize = msg_send![sender, tag];
+            this.set_ivar("chosen", tag);
+        }
+    }
+
+    /// The class of the object that receives the menu's actions, registered
+    /// with the Objective-C runtime the first time a menu is shown.
+    fn target_class() -> &'static Class {
+        Class::get("RuffleContextMenuTarget").unwrap_or_else(|| {
+            let mut decl = ClassDecl::new("RuffleContextMenuTarget", class!(NSObject)).unwrap();
+            decl.add_ivar::<isize>("chosen");
+            unsafe {
+                decl.add_method(
+                    sel!(chooseItem:),
+                    choose_item as extern "C" fn(&mut Object, Sel, id),
+                );
+            }
+            decl.register()
+        })
+    }
+
+    if items.is_empty() {
+        return None;
+    }
+
+    unsafe {
+        let pool = NSAutoreleasePool::new(nil);
+
+        let target: id = msg_send![target_class(), new];
+        (*target).set_ivar::<isize>("chosen", 0);
+
+        let menu: id = msg_send![class!(NSMenu), new];
+        let menu = menu.autorelease();
+        // Otherwise AppKit decides for itself which items are enabled.
+        let _: () = msg_send![menu, setAutoenablesItems: NO];
+
+        let key_equivalent = NSString::alloc(nil).init_str("").autorelease();
+        for (i, item) in items.iter().enumerate() {
+            if item.separator_before && i > 0 {
+                let separator: id = msg_send![class!(NSMenuItem), separatorItem];
+                let _: () = msg_send![menu, addItem: separator];
+            }
+
+            let title = NSString::alloc(nil).init_str(&item.caption).autorelease();
+            let menu_item: id = msg_send![class!(NSMenuItem), alloc];
+            let menu_item: id = msg_send![menu_item, initWithTitle: title action: sel!(chooseItem:) keyEquivalent: key_equivalent];
+            let menu_item = menu_item.autorelease();
+            let _: () = msg_send![menu_item, setTarget: target];
+            // Tags start at 1, so that 0 means the menu was dismissed.
+            let _: () = msg_send![menu_item, setTag: i as isize + 1];
+            let _: () = msg_send![menu_item, setEnabled: if item.enabled { YES } else { NO }];
+            // `NSControlStateValueOn` and `NSControlStateValueOff`.
+            let _: () = msg_send![menu_item, setState: if item.checked { 1isize } else { 0isize }];
+            let _: () = msg_send![menu, addItem: menu_item];
+        }
+
+        let view = window.ns_view() as id;
+        let ns_window: id = msg_send![view, window];
+        let location: NSPoint = msg_send![ns_window, mouseLocationOutsideOfEventStream];
+        let location: NSPoint = msg_send![view, convertPoint: location fromView: nil];
+        let _: BOOL =
+            msg_send![menu, popUpMenuPositioningItem: nil atLocation: location inView: view];
+
+        let chosen = *(*target).get_ivar::<isize>("chosen");
+        let _: () = msg_send![target, release];
+        pool.drain();
+
+        if chosen > 0 {
+            Some(chosen as usize - 1)
+        } else {
+            None
+        }
+    }
+}
+
+/// Show `items` as a popup menu at the mouse cursor, blocking until the user
+/// chooses an item or dismisses the menu.
+///
+/// Returns the index of the chosen item, if any.
+#[cfg(not(any(windows, target_os = "macos")))]
+pub fn show_context_menu(_window: &Window, items: &[ContextMenuItem]) -> Option<usize> {
+    use gtk::prelude::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    if items.is_empty() {
+        return None;
+    }
+
+    if let Err(e) = gtk::init() {
+        log::warn!("Couldn't show context menu: {}", e);
+        return None;
+    }
+
+    // GTK can't place the menu or grab the pointer without a window of its
+    // own to attach it to, so a tiny popup window is put under the cursor.
+    let anchor = gtk::Window::new(gtk::WindowType::Popup);
+    anchor.set_type_hint(gdk::WindowTypeHint::PopupMenu);
+    anchor.set_default_size(1, 1);
+    if let Some(pointer) = anchor
+        .get_display()
+        .get_default_seat()
+        .and_then(|seat| seat.get_pointer())
+    {
+        let (_screen, x, y) = pointer.get_position();
+        anchor.move_(x, y);
+    }
+    anchor.show();
+
+    let chosen = Rc::new(Cell::new(None));
+    let menu = gtk::MenuBuilder::new().attach_widget(&anchor).build();
+    for (i, item) in items.iter().enumerate() {
+        if item.separator_before && i > 0 {
+            menu.append(&gtk::SeparatorMenuItem::new());
+        }
+
+        let menu_item: gtk::MenuItem = if item.checked {
+            // Checking the item emits `activate`, so this is done before the
+            // handler is connected.
+            let check_item = gtk::CheckMenuItem::with_label(&item.caption);
+            check_item.set_active(true);
+            check_item.upcast()
+        } else {
+            gtk::MenuItem::with_label(&item.caption)
+        };
+        menu_item.set_sensitive(item.enabled);
+
+        let chosen = chosen.clone();
+        menu_item.connect_activate(move |_| chosen.set(Some(i)));
+        menu.append(&menu_item);
+    }
+
+    // The menu is deactivated before the chosen item is activated, but in the
+    // same iteration of the main loop, so `gtk::main` only returns after both.
+    menu.connect_deactivate(|_| gtk::main_quit());
+    menu.show_all();
+    menu.popup_at_widget(
+        &anchor,
+        gdk::Gravity::NorthWest,
+        gdk::Gravity::NorthWest,
+        None,
+    );
+
+    // If the menu couldn't be shown, or went away without being deactivated,
+    // stop waiting for it rather than blocking the player forever.
+    let finished = Rc::new(Cell::new(false));
+    {
+        let menu = menu.clone();
+        let finished = finished.clone();
+        glib::timeout_add_local(100, move || {
+            if finished.get() {
+                return glib::Continue(false);
+            }
+            let is_shown = menu
+                .get_toplevel()
+                .map_or(false, |window| window.get_mapped());
+            if !is_shown {
+                log::warn!("Context menu was closed without being deactivated");
+                gtk::main_quit();
+                return glib::Continue(false);
+            }
+            glib::Continue(true)
+        });
+    }
+    gtk::main();
+    finished.set(true);
+    anchor.close();
+
+    chosen.get()
+}
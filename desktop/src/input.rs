@@ -1,7 +1,7 @@
 use clipboard::{ClipboardContext, ClipboardProvider};
 use ruffle_core::backend::input::{InputBackend, MouseCursor};
-use ruffle_core::events::{KeyCode, PlayerEvent};
-use std::collections::HashSet;
+use ruffle_core::events::{KeyCode, KeyLocation, PlayerEvent};
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use winit::event::{ElementState, VirtualKeyCode, WindowEvent};
 use winit::window::Window;
@@ -11,6 +11,16 @@ pub struct WinitInputBackend {
     window: Rc<Window>,
     cursor_visible: bool,
     last_key: KeyCode,
+    last_char: Option<char>,
+    /// The characters typed by the keys that are down, reported again when they are released.
+    key_chars: HashMap<VirtualKeyCode, char>,
+    /// A key press waiting for the character it types, which winit sends as a
+    /// separate `ReceivedCharacter` event straight after the `KeyboardInput`.
+    pending_key_down: Option<(VirtualKeyCode, KeyCode)>,
+    // The lock keys are tracked from key presses on platforms where the OS can't be asked.
+    caps_lock: bool,
+    num_lock: bool,
+    scroll_lock: bool,
     clipboard: ClipboardContext,
 }
 
@@ -20,44 +30,103 @@ impl WinitInputBackend {
             keys_down: HashSet::new(),
             cursor_visible: true,
             last_key: KeyCode::Unknown,
+            last_char: None,
+            key_chars: HashMap::new(),
+            pending_key_down: None,
+            caps_lock: false,
+            num_lock: false,
+            scroll_lock: false,
             window,
             clipboard: ClipboardProvider::new().unwrap(),
         }
     }
 
-    /// Process an input event, and returns an event that should be forward to the player, if any.
-    pub fn handle_event(&mut self, event: WindowEvent) -> Option<PlayerEvent> {
+    /// Process an input event, and returns the events that should be forwarded to the player.
+    pub fn handle_event(&mut self, event: WindowEvent) -> Vec<PlayerEvent> {
+        let mut events = Vec::new();
         match event {
-            WindowEvent::KeyboardInput { input, .. } => match input.state {
-                ElementState::Pressed => {
-                    if let Some(key) = input.virtual_keycode {
-                        self.keys_down.insert(key);
+            WindowEvent::KeyboardInput { input, .. } => {
+                events.extend(self.flush_key_down());
+                let key = match input.virtual_keycode {
+                    Some(key) => key,
+                    None => return events,
+                };
+                match input.state {
+                    ElementState::Pressed => {
+                        // Key repeats don't toggle the lock keys again.
+                        if self.keys_down.insert(key) {
+                            match key {
+                                VirtualKeyCode::Capital => self.caps_lock = !self.caps_lock,
+                                VirtualKeyCode::Numlock => self.num_lock = !self.num_lock,
+                                VirtualKeyCode::Scroll => self.scroll_lock = !self.scroll_lock,
+                                _ => (),
+                            }
+                        }
+                        self.key_chars.remove(&key);
                         if let Some(key_code) = winit_to_ruffle_key_code(key) {
-                            self.last_key = key_code;
-                            return Some(PlayerEvent::KeyDown { key_code });
+                            self.pending_key_down = Some((key, key_code));
                         } else {
                             self.last_key = KeyCode::Unknown;
+                            self.last_char = None;
                         }
                     }
-                }
-                ElementState::Released => {
-                    if let Some(key) = input.virtual_keycode {
+                    ElementState::Released => {
                         self.keys_down.remove(&key);
+                        self.last_char = self.key_chars.remove(&key);
                         if let Some(key_code) = winit_to_ruffle_key_code(key) {
                             self.last_key = key_code;
-                            return Some(PlayerEvent::KeyUp { key_code });
+                            events.push(PlayerEvent::KeyUp {
+                                key_code,
+                                key_char: self.last_char,
+                                key_location: winit_key_location(key),
+                            });
                         } else {
                             self.last_key = KeyCode::Unknown;
                         }
                     }
                 }
-            },
+            }
             WindowEvent::ReceivedCharacter(codepoint) => {
-                return Some(PlayerEvent::TextInput { codepoint });
+                if let Some((key, key_code)) = self.pending_key_down.take() {
+                    self.key_chars.insert(key, codepoint);
+                    events.push(self.key_down(key, key_code, Some(codepoint)));
+                }
+
+                // Keys pressed with Command on macOS are shortcuts, not text.
+                let is_shortcut = cfg!(target_os = "macos")
+                    && (self.keys_down.contains(&VirtualKeyCode::LWin)
+                        || self.keys_down.contains(&VirtualKeyCode::RWin));
+                if !is_shortcut {
+                    events.push(PlayerEvent::TextInput { codepoint });
+                }
             }
-            _ => (),
+            _ => events.extend(self.flush_key_down()),
+        }
+        events
+    }
+
+    /// Send a key press that didn't type a character.
+    ///
+    /// This should be called once all window events have been handled, so that
+    /// the press reaches the player before the next frame.
+    pub fn flush_key_down(&mut self) -> Option<PlayerEvent> {
+        let (key, key_code) = self.pending_key_down.take()?;
+        Some(self.key_down(key, key_code, None))
+    }
+
+    fn key_down(
+        &mut self,
+        key: VirtualKeyCode,
+        key_code: KeyCode,
+        key_char: Option<char>,
+    ) -> PlayerEvent {
+        self.last_key = key_code;
+        self.last_char = key_char;
+        PlayerEvent::KeyDown {
+            key_code,
+            key_char,
+            key_location: winit_key_location(key),
         }
-        None
     }
 }
 
@@ -66,14 +135,23 @@ impl InputBackend for WinitInputBackend {
         match key {
             KeyCode::Unknown => false,
             KeyCode::Backspace => self.keys_down.contains(&VirtualKeyCode::Back),
-            KeyCode::Return => self.keys_down.contains(&VirtualKeyCode::Return),
+            KeyCode::Tab => self.keys_down.contains(&VirtualKeyCode::Tab),
+            KeyCode::Return => {
+                self.keys_down.contains(&VirtualKeyCode::Return)
+                    || self.keys_down.contains(&VirtualKeyCode::NumpadEnter)
+            }
             KeyCode::Shift => {
                 self.keys_down.contains(&VirtualKeyCode::LShift)
                     || self.keys_down.contains(&VirtualKeyCode::RShift)
             }
+            // Flash Player treats Command as Control on macOS, so shortcuts such as
+            // copy and paste work with either key.
             KeyCode::Control => {
                 self.keys_down.contains(&VirtualKeyCode::LControl)
                     || self.keys_down.contains(&VirtualKeyCode::RControl)
+                    || (cfg!(target_os = "macos")
+                        && (self.keys_down.contains(&VirtualKeyCode::LWin)
+                            || self.keys_down.contains(&VirtualKeyCode::RWin)))
             }
             KeyCode::Alt => {
                 self.keys_down.contains(&VirtualKeyCode::LAlt)
@@ -155,6 +233,7 @@ impl InputBackend for WinitInputBackend {
             KeyCode::Insert => self.keys_down.contains(&VirtualKeyCode::Insert),
             KeyCode::Delete => self.keys_down.contains(&VirtualKeyCode::Delete),
             KeyCode::Pause => self.keys_down.contains(&VirtualKeyCode::Pause),
+            KeyCode::NumLock => self.keys_down.contains(&VirtualKeyCode::Numlock),
             KeyCode::ScrollLock => self.keys_down.contains(&VirtualKeyCode::Scroll),
             KeyCode::F1 => self.keys_down.contains(&VirtualKeyCode::F1),
             KeyCode::F2 => self.keys_down.contains(&VirtualKeyCode::F2),
@@ -168,6 +247,9 @@ impl InputBackend for WinitInputBackend {
             KeyCode::F10 => self.keys_down.contains(&VirtualKeyCode::F10),
             KeyCode::F11 => self.keys_down.contains(&VirtualKeyCode::F11),
             KeyCode::F12 => self.keys_down.contains(&VirtualKeyCode::F12),
+            KeyCode::F13 => self.keys_down.contains(&VirtualKeyCode::F13),
+            KeyCode::F14 => self.keys_down.contains(&VirtualKeyCode::F14),
+            KeyCode::F15 => self.keys_down.contains(&VirtualKeyCode::F15),
         }
     }
 
@@ -175,6 +257,19 @@ impl InputBackend for WinitInputBackend {
         self.last_key
     }
 
+    fn get_last_key_char(&self) -> Option<char> {
+        self.last_char
+    }
+
+    fn is_key_toggled(&self, key: KeyCode) -> bool {
+        os_lock_key_state(&self.window, key).unwrap_or_else(|| match key {
+            KeyCode::CapsLock => self.caps_lock,
+            KeyCode::NumLock => self.num_lock,
+            KeyCode::ScrollLock => self.scroll_lock,
+            _ => false,
+        })
+    }
+
     fn mouse_visible(&self) -> bool {
         self.cursor_visible
     }
@@ -203,6 +298,10 @@ impl InputBackend for WinitInputBackend {
     fn set_clipboard_content(&mut self, content: String) {
         self.clipboard.set_contents(content).unwrap();
     }
+
+    fn clipboard_content(&mut self) -> String {
+        self.clipboard.get_contents().unwrap_or_default()
+    }
 }
 
 /// Converts a winit `VirtualKeyCode` into a Ruffle `KeyCode`.
@@ -210,7 +309,8 @@ impl InputBackend for WinitInputBackend {
 fn winit_to_ruffle_key_code(key_code: VirtualKeyCode) -> Option<KeyCode> {
     let out = match key_code {
         VirtualKeyCode::Back => KeyCode::Backspace,
-        VirtualKeyCode::Return => KeyCode::Return,
+        VirtualKeyCode::Tab => KeyCode::Tab,
+        VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => KeyCode::Return,
         VirtualKeyCode::LShift | VirtualKeyCode::RShift => KeyCode::Shift,
         VirtualKeyCode::LControl | VirtualKeyCode::RControl => KeyCode::Control,
         VirtualKeyCode::LAlt | VirtualKeyCode::RAlt => KeyCode::Alt,
@@ -290,6 +390,7 @@ fn winit_to_ruffle_key_code(key_code: VirtualKeyCode) -> Option<KeyCode> {
         VirtualKeyCode::Insert => KeyCode::Insert,
         VirtualKeyCode::Delete => KeyCode::Delete,
         VirtualKeyCode::Pause => KeyCode::Pause,
+        VirtualKeyCode::Numlock => KeyCode::NumLock,
         VirtualKeyCode::Scroll => KeyCode::ScrollLock,
         VirtualKeyCode::F1 => KeyCode::F1,
         VirtualKeyCode::F2 => KeyCode::F2,
@@ -303,7 +404,124 @@ fn winit_to_ruffle_key_code(key_code: VirtualKeyCode) -> Option<KeyCode> {
         VirtualKeyCode::F10 => KeyCode::F10,
         VirtualKeyCode::F11 => KeyCode::F11,
         VirtualKeyCode::F12 => KeyCode::F12,
+        VirtualKeyCode::F13 => KeyCode::F13,
+        VirtualKeyCode::F14 => KeyCode::F14,
+        VirtualKeyCode::F15 => KeyCode::F15,
         _ => return None,
     };
     Some(out)
 }
+
+/// Returns where on the keyboard a winit `VirtualKeyCode` is located.
+fn winit_key_location(key_code: VirtualKeyCode) -> KeyLocation {
+    match key_code {
+        VirtualKeyCode::LShift | VirtualKeyCode::LControl | VirtualKeyCode::LAlt => {
+            KeyLocation::Left
+        }
+        VirtualKeyCode::RShift | VirtualKeyCode::RControl | VirtualKeyCode::RAlt => {
+            KeyLocation::Right
+        }
+        VirtualKeyCode::Numpad0
+        | VirtualKeyCode::Numpad1
+        | VirtualKeyCode::Numpad2
+        | VirtualKeyCode::Numpad3
+        | VirtualKeyCode::Numpad4
+        | VirtualKeyCode::Numpad5
+        | VirtualKeyCode::Numpad6
+        | VirtualKeyCode::Numpad7
+        | VirtualKeyCode::Numpad8
+        | VirtualKeyCode::Numpad9
+        | VirtualKeyCode::NumpadMultiply
+        | VirtualKeyCode::NumpadAdd
+        | VirtualKeyCode::NumpadSubtract
+        | VirtualKeyCode::NumpadDecimal
+        | VirtualKeyCode::NumpadDivide
+        | VirtualKeyCode::NumpadEnter
+        | VirtualKeyCode::Numlock => KeyLocation::NumPad,
+        _ => KeyLocation::Standard,
+    }
+}
+
+/// Asks the OS whether a lock key is switched on.
+#[cfg(windows)]
+fn os_lock_key_state(_window: &Window, key: KeyCode) -> Option<bool> {
+    use winapi::um::winuser::{GetKeyState, VK_CAPITAL, VK_NUMLOCK, VK_SCROLL};
+
+    let virtual_key = match key {
+        KeyCode::CapsLock => VK_CAPITAL,
+        KeyCode::NumLock => VK_NUMLOCK,
+        KeyCode::ScrollLock => VK_SCROLL,
+        _ => return None,
+    };
+    // The low bit is set while the key is toggled on.
+    Some(unsafe { GetKeyState(virtual_key) } & 1 != 0)
+}
+
+/// Asks the OS whether a lock key is switched on.
+#[cfg(target_os = "macos")]
+fn os_lock_key_state(_window: &Window, key: KeyCode) -> Option<bool> {
+    use cocoa::foundation::NSUInteger;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    /// `NSEventModifierFlagCapsLock`.
+    const CAPS_LOCK_FLAG: NSUInteger = 1 << 16;
+
+    // Macs have no Num Lock or Scroll Lock.
+    match key {
+        KeyCode::CapsLock => {
+            let flags: NSUInteger = unsafe { msg_send![class!(NSEvent), modifierFlags] };
+            Some(flags & CAPS_LOCK_FLAG != 0)
+        }
+        _ => None,
+    }
+}
+
+/// Asks the OS whether a lock key is switched on.
+///
+/// Only X11 can be asked. Under Wayland, this returns `None`.
+#[cfg(not(any(windows, target_os = "macos")))]
+fn os_lock_key_state(window: &Window, key: KeyCode) -> Option<bool> {
+    use std::ffi::CStr;
+    use std::os::raw::c_int;
+    use winit::platform::unix::WindowExtUnix;
+    use x11_dl::xlib::Xlib;
+
+    thread_local! {
+        /// Xlib is loaded the first time a lock key is checked, rather than on every key press.
+        static XLIB: Option<Xlib> = Xlib::open().ok();
+    }
+
+    // Indicators are looked up by name, because X servers may number them differently.
+    let name: &CStr = match key {
+        KeyCode::CapsLock => CStr::from_bytes_with_nul(b"Caps Lock\0").unwrap(),
+        KeyCode::NumLock => CStr::from_bytes_with_nul(b"Num Lock\0").unwrap(),
+        KeyCode::ScrollLock => CStr::from_bytes_with_nul(b"Scroll Lock\0").unwrap(),
+        _ => return None,
+    };
+
+    let display = window.xlib_display()?;
+    XLIB.with(|xlib| {
+        let xlib = xlib.as_ref()?;
+        unsafe {
+            // `False` is 0; the atom is only looked up, never created.
+            let atom = (xlib.XInternAtom)(display as _, name.as_ptr(), 0);
+            if atom == 0 {
+                return None;
+            }
+            let mut state: c_int = 0;
+            let found = (xlib.XkbGetNamedIndicator)(
+                display as _,
+                atom,
+                std::ptr::null_mut(),
+                &mut state,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            );
+            if found != 0 {
+                Some(state != 0)
+            } else {
+                None
+            }
+        }
+    })
+}
@@ -0,0 +1,1087 @@
+//! `LocalConnection` transport shared by every desktop player on this machine.
+//!
+//! The first player to start hosts a `LocalConnectionBroker` on a loopback
+//! port, and every later player talks to that broker over TCP. Each request
+//! from a player is answered with exactly one response, so players simply
+//! poll the broker for events.
+//!
+//! The hosting player publishes a random key in the user's data directory,
+//! and only players that present it may use the broker. Each player states
+//! the domain of its movie when it joins, and the broker stamps every message
+//! that the player sends with that domain.
+//!
+//! Talking to the broker happens on a thread of its own, so that a slow or
+//! vanished broker never holds up the player. When the broker goes away, the
+//! remaining players elect a new one between themselves and claim their
+//! connection names again.
+
+use ruffle_core::backend::local_connection::{
+    LocalConnectionBackend, LocalConnectionBroker, LocalConnectionEndpoint, LocalConnectionEvent,
+    LocalConnectionMessage,
+};
+use ruffle_core::external::Value as ExternalValue;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::convert::TryInto;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The loopback port that the hosting player listens on.
+pub const BROKER_PORT: u16 = 19351;
+
+/// How long to wait for the broker to answer before giving up on it.
+const BROKER_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long the broker waits for a request from a player before giving up on it.
+///
+/// Players poll far more often than this, so a player that stays quiet for
+/// this long is gone.
+const PLAYER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often players poll the broker for events.
+const POLL_INTERVAL: Duration = Duration::from_millis(15);
+
+/// How often a player tries to host or join a broker before using one of its own.
+const JOIN_ATTEMPTS: u32 = 10;
+
+/// How long to wait between attempts to host or join a broker.
+const JOIN_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// The largest frame that is accepted from the other side.
+const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+/// How deeply the values in a message may be nested.
+const MAX_VALUE_DEPTH: usize = 64;
+
+/// The longest domain that a player may state when it joins.
+const MAX_DOMAIN_LEN: usize = 255;
+
+/// The number of random bytes in the key of a hosted broker.
+const KEY_SIZE: usize = 32;
+
+/// Returns a `LocalConnection` transport shared with the other players of
+/// this user, for a player whose movie is from the given domain.
+///
+/// If no broker can be shared, the player gets a broker of its own, so that
+/// movies inside it can still talk to each other.
+pub fn connect_to_broker(domain: &str) -> Box<dyn LocalConnectionBackend> {
+    match dirs::data_local_dir() {
+        Some(dir) => Box::new(BrokerClient::start(
+            Rendezvous {
+                port: BROKER_PORT,
+                key_path: dir.join("ruffle").join("local_connection.key"),
+            },
+            domain,
+        )),
+        None => {
+            log::warn!("Unable to share LocalConnection without a data directory");
+            Box::new(LocalConnectionBroker::new().endpoint())
+        }
+    }
+}
+
+/// Where the players of a user find their broker.
+#[derive(Clone)]
+struct Rendezvous {
+    /// The loopback port that the hosting player listens on.
+    port: u16,
+
+    /// The file that the hosting player publishes its key in.
+    key_path: PathBuf,
+}
+
+/// A player's connection to the shared broker.
+///
+/// All I/O happens on a worker thread, which this hands requests to and
+/// collects events from.
+pub struct BrokerClient {
+    commands: Sender<Command>,
+    events: Receiver<LocalConnectionEvent>,
+    next_message: u64,
+}
+
+impl BrokerClient {
+    fn start(rendezvous: Rendezvous, domain: &str) -> Self {
+        let (commands, command_receiver) = channel();
+        let (event_sender, events) = channel();
+        let domain = domain.to_string();
+        thread::spawn(move || {
+            let link = join(&rendezvous, &domain);
+            let worker = Worker {
+                rendezvous,
+                domain,
+                link,
+                names: Vec::new(),
+                sent: HashMap::new(),
+                received: HashMap::new(),
+                next_received: 0,
+                events: event_sender,
+            };
+            worker.run(command_receiver);
+        });
+
+        Self {
+            commands,
+            events,
+            next_message: 0,
+        }
+    }
+}
+
+impl LocalConnectionBackend for BrokerClient {
+    fn connect(&mut self, name: &str) -> bool {
+        let (answer, result) = channel();
+        if self
+            .commands
+            .send(Command::Connect(name.to_string(), answer))
+            .is_err()
+        {
+            return false;
+        }
+
+        // Electing a new broker may take a while, so wait for that as well.
+        let timeout = BROKER_TIMEOUT + JOIN_RETRY_DELAY * JOIN_ATTEMPTS;
+        result.recv_timeout(timeout).unwrap_or(false)
+    }
+
+    fn close(&mut self, name: &str) {
+        let _ = self.commands.send(Command::Close(name.to_string()));
+    }
+
+    fn send(&mut self, message: LocalConnectionMessage) -> u64 {
+        let id = self.next_message;
+        self.next_message += 1;
+        let _ = self.commands.send(Command::Send(id, message));
+        id
+    }
+
+    fn reply(&mut self, id: u64, success: bool) {
+        let _ = self.commands.send(Command::Reply(id, success));
+    }
+
+    fn poll_events(&mut self) -> Vec<LocalConnectionEvent> {
+        self.events.try_iter().collect()
+    }
+}
+
+/// A request from the player to its worker thread.
+enum Command {
+    Connect(String, Sender<bool>),
+    Close(String),
+    Send(u64, LocalConnectionMessage),
+    Reply(u64, bool),
+}
+
+/// Talks to the broker on behalf of a player.
+///
+/// The broker has its own message IDs, which change whenever a new broker
+/// is elected, so the worker gives the player IDs of its own.
+struct Worker {
+    rendezvous: Rendezvous,
+    domain: String,
+    link: Link,
+
+    /// The connection names that the player listens on.
+    names: Vec<String>,
+
+    /// The player's ID of each sent message, by the broker's ID.
+    sent: HashMap<u64, u64>,
+
+    /// The broker's ID of each received message, by the player's ID.
+    received: HashMap<u64, u64>,
+
+    next_received: u64,
+    events: Sender<LocalConnectionEvent>,
+}
+
+impl Worker {
+    /// Handles the player's requests and polls the broker until the player goes away.
+    fn run(mut self, commands: Receiver<Command>) {
+        loop {
+            let command = match commands.recv_timeout(POLL_INTERVAL) {
+                Ok(command) => Some(command),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => return,
+            };
+
+            let result = command
+                .into_iter()
+                .chain(commands.try_iter())
+                .try_for_each(|command| self.handle(command))
+                .and_then(|_| self.poll());
+            if let Err(e) = result {
+                log::warn!("Lost the LocalConnection broker: {}", e);
+                self.rejoin();
+            }
+        }
+    }
+
+    fn handle(&mut self, command: Command) -> io::Result<()> {
+        match command {
+            Command::Connect(name, answer) => {
+                let success = self.link.connect(&name)?;
+                if success {
+                    self.names.push(name);
+                }
+                let _ = answer.send(success);
+            }
+            Command::Close(name) => {
+                self.names.retain(|other| *other != name);
+                self.link.close(&name)?;
+            }
+            Command::Send(id, message) => match self.link.send(message) {
+                Ok(Some(broker_id)) => {
+                    self.sent.insert(broker_id, id);
+                }
+                Ok(None) => self.report(LocalConnectionEvent::Status(id, false)),
+                Err(e) => {
+                    self.report(LocalConnectionEvent::Status(id, false));
+                    return Err(e);
+                }
+            },
+            Command::Reply(id, success) => {
+                // Messages received from a previous broker can't be answered anymore.
+                if let Some(broker_id) = self.received.remove(&id) {
+                    self.link.reply(broker_id, success)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self) -> io::Result<()> {
+        for event in self.link.poll_events()? {
+            let event = match event {
+                LocalConnectionEvent::Message(broker_id, message) => {
+                    let id = self.next_received;
+                    self.next_received += 1;
+                    self.received.insert(id, broker_id);
+                    LocalConnectionEvent::Message(id, message)
+                }
+                LocalConnectionEvent::Status(broker_id, success) => {
+                    match self.sent.remove(&broker_id) {
+                        Some(id) => LocalConnectionEvent::Status(id, success),
+                        None => continue,
+                    }
+                }
+            };
+            self.report(event);
+        }
+        Ok(())
+    }
+
+    fn report(&self, event: LocalConnectionEvent) {
+        let _ = self.events.send(event);
+    }
+
+    /// Joins a new broker after losing the current one, and claims the
+    /// player's connection names there.
+    fn rejoin(&mut self) {
+        // Messages that were in flight will never be answered.
+        for (_, id) in std::mem::take(&mut self.sent) {
+            self.report(LocalConnectionEvent::Status(id, false));
+        }
+        self.received.clear();
+
+        self.link = join(&self.rendezvous, &self.domain);
+
+        let link = &mut self.link;
+        self.names.retain(|name| match link.connect(name) {
+            Ok(true) => true,
+            Ok(false) => {
+                log::warn!("LocalConnection {} was taken by another player", name);
+                false
+            }
+            // The next attempt to join will claim it.
+            Err(_) => true,
+        });
+    }
+}
+
+/// Hosts the broker if no other player does yet, or joins the one that does.
+///
+/// If neither works, returns a broker of the player's own.
+fn join(rendezvous: &Rendezvous, domain: &str) -> Link {
+    for attempt in 0..JOIN_ATTEMPTS {
+        if attempt > 0 {
+            thread::sleep(JOIN_RETRY_DELAY);
+        }
+
+        match TcpListener::bind((Ipv4Addr::LOCALHOST, rendezvous.port)) {
+            Ok(listener) => match HostedBroker::start(listener, &rendezvous.key_path) {
+                Ok((hosted, endpoint)) => return Link::Host(endpoint, hosted),
+                Err(e) => {
+                    log::warn!("Unable to host the LocalConnection broker: {}", e);
+                    break;
+                }
+            },
+            Err(e) => log::debug!("Not hosting the LocalConnection broker: {}", e),
+        }
+
+        match connect_remote(rendezvous, domain) {
+            Ok(stream) => return Link::Remote(stream),
+            Err(e) => log::debug!("Unable to join the LocalConnection broker: {}", e),
+        }
+    }
+
+    log::warn!("Unable to share LocalConnection with other players");
+    Link::Private(LocalConnectionBroker::new().endpoint())
+}
+
+/// Connects to the broker of another player, presenting its key.
+fn connect_remote(rendezvous: &Rendezvous, domain: &str) -> io::Result<TcpStream> {
+    let key = fs::read_to_string(&rendezvous.key_path)?;
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, rendezvous.port));
+    let mut stream = TcpStream::connect_timeout(&address, BROKER_TIMEOUT)?;
+    stream.set_read_timeout(Some(BROKER_TIMEOUT))?;
+    stream.set_nodelay(true)?;
+
+    let mut hello = Vec::new();
+    write_string(&mut hello, key.trim());
+    write_string(&mut hello, domain);
+    if !Decoder::new(&request(&mut stream, &hello)?).bool()? {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "the broker rejected our key",
+        ));
+    }
+    Ok(stream)
+}
+
+/// The broker that a worker talks to.
+enum Link {
+    /// This player hosts the broker.
+    Host(LocalConnectionEndpoint, HostedBroker),
+
+    /// Another player hosts the broker.
+    Remote(TcpStream),
+
+    /// No broker could be shared, so this player has one of its own.
+    Private(LocalConnectionEndpoint),
+}
+
+impl Link {
+    fn connect(&mut self, name: &str) -> io::Result<bool> {
+        match self {
+            Link::Host(endpoint, _) | Link::Private(endpoint) => Ok(endpoint.connect(name)),
+            Link::Remote(stream) => {
+                let mut data = vec![REQUEST_CONNECT];
+                write_string(&mut data, name);
+                Decoder::new(&request(stream, &data)?).bool()
+            }
+        }
+    }
+
+    fn close(&mut self, name: &str) -> io::Result<()> {
+        match self {
+            Link::Host(endpoint, _) | Link::Private(endpoint) => endpoint.close(name),
+            Link::Remote(stream) => {
+                let mut data = vec![REQUEST_CLOSE];
+                write_string(&mut data, name);
+                request(stream, &data)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends a message, returning the broker's ID of it.
+    ///
+    /// Returns `None` if the message is too large to be sent.
+    fn send(&mut self, message: LocalConnectionMessage) -> io::Result<Option<u64>> {
+        match self {
+            Link::Host(endpoint, _) | Link::Private(endpoint) => Ok(Some(endpoint.send(message))),
+            Link::Remote(stream) => {
+                let mut data = vec![REQUEST_SEND];
+                write_message(&mut data, &message);
+                if data.len() > MAX_FRAME_SIZE {
+                    return Ok(None);
+                }
+                Decoder::new(&request(stream, &data)?).u64().map(Some)
+            }
+        }
+    }
+
+    fn reply(&mut self, id: u64, success: bool) -> io::Result<()> {
+        match self {
+            Link::Host(endpoint, _) | Link::Private(endpoint) => endpoint.reply(id, success),
+            Link::Remote(stream) => {
+                let mut data = vec![REQUEST_REPLY];
+                write_u64(&mut data, id);
+                write_bool(&mut data, success);
+                request(stream, &data)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn poll_events(&mut self) -> io::Result<Vec<LocalConnectionEvent>> {
+        match self {
+            Link::Host(endpoint, _) | Link::Private(endpoint) => Ok(endpoint.poll_events()),
+            Link::Remote(stream) => {
+                let response = request(stream, &[REQUEST_POLL])?;
+                let mut response = Decoder::new(&response);
+                let len = response.u32()?;
+                (0..len).map(|_| response.event()).collect()
+            }
+        }
+    }
+}
+
+/// A broker hosted by this player, which stops when dropped.
+struct HostedBroker {
+    port: u16,
+    players: Arc<Mutex<Players>>,
+}
+
+/// The players connected to a hosted broker.
+#[derive(Default)]
+struct Players {
+    stopped: bool,
+    next_player: usize,
+    streams: HashMap<usize, TcpStream>,
+}
+
+impl HostedBroker {
+    /// Starts hosting a broker on the listener, publishing a new key at the given path.
+    ///
+    /// Returns the endpoint of the hosting player as well.
+    fn start(
+        listener: TcpListener,
+        key_path: &Path,
+    ) -> io::Result<(Self, LocalConnectionEndpoint)> {
+        let key = generate_key()?;
+        publish_key(key_path, &key)?;
+
+        let port = listener.local_addr()?.port();
+        let broker = LocalConnectionBroker::new();
+        let endpoint = broker.endpoint();
+        let players = Arc::new(Mutex::new(Players::default()));
+        let hosted = Self {
+            port,
+            players: players.clone(),
+        };
+        thread::spawn(move || run_broker(listener, broker, key, players));
+        Ok((hosted, endpoint))
+    }
+}
+
+impl Drop for HostedBroker {
+    fn drop(&mut self) {
+        if let Ok(mut players) = self.players.lock() {
+            players.stopped = true;
+            for (_, stream) in players.streams.drain() {
+                let _ = stream.shutdown(Shutdown::Both);
+            }
+        }
+
+        // Wake up the thread accepting players, so that it notices.
+        let _ = TcpStream::connect((Ipv4Addr::LOCALHOST, self.port));
+    }
+}
+
+/// Generates a random, hex encoded key.
+fn generate_key() -> io::Result<String> {
+    let mut key = [0; KEY_SIZE];
+    getrandom::getrandom(&mut key)?;
+    Ok(key.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Writes the key to the given path, readable only by the current user.
+///
+/// On Windows, files in the user's local data directory are private already.
+fn publish_key(path: &Path, key: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    // Write to a new file first, so that other players never read half a key.
+    let temp_path = path.with_extension("tmp");
+    let _ = fs::remove_file(&temp_path);
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(&temp_path)?.write_all(key.as_bytes())?;
+    fs::rename(temp_path, path)
+}
+
+/// Accepts players, giving each one its own endpoint of the broker.
+fn run_broker(
+    listener: TcpListener,
+    broker: LocalConnectionBroker,
+    key: String,
+    players: Arc<Mutex<Players>>,
+) {
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::warn!("Unable to accept a LocalConnection player: {}", e);
+                continue;
+            }
+        };
+
+        let player = {
+            let mut players = players.lock().unwrap();
+            if players.stopped {
+                return;
+            }
+            let clone = match stream.try_clone() {
+                Ok(clone) => clone,
+                Err(e) => {
+                    log::warn!("Unable to accept a LocalConnection player: {}", e);
+                    continue;
+                }
+            };
+            let player = players.next_player;
+            players.next_player += 1;
+            players.streams.insert(player, clone);
+            player
+        };
+
+        let endpoint = broker.endpoint();
+        let key = key.clone();
+        let players = players.clone();
+        thread::spawn(move || {
+            if let Err(e) = serve_player(stream, endpoint, &key) {
+                log::debug!("LocalConnection player disconnected: {}", e);
+            }
+            players.lock().unwrap().streams.remove(&player);
+        });
+    }
+}
+
+/// Answers requests from a player until it disconnects.
+///
+/// Dropping the endpoint afterwards releases the player's connection names.
+fn serve_player(
+    mut stream: TcpStream,
+    mut endpoint: LocalConnectionEndpoint,
+    key: &str,
+) -> io::Result<()> {
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(BROKER_TIMEOUT))?;
+    let domain = authenticate(&mut stream, key)?;
+    stream.set_read_timeout(Some(PLAYER_TIMEOUT))?;
+
+    // Events that didn't fit in the response to the last poll.
+    let mut backlog = VecDeque::new();
+    loop {
+        let request = read_frame(&mut stream)?;
+        let mut request = Decoder::new(&request);
+        let mut response = Vec::new();
+        match request.u8()? {
+            REQUEST_CONNECT => {
+                let success = endpoint.connect(&request.string()?);
+                write_bool(&mut response, success);
+            }
+            REQUEST_CLOSE => endpoint.close(&request.string()?),
+            REQUEST_SEND => {
+                let id = endpoint.send(request.message(&domain)?);
+                write_u64(&mut response, id);
+            }
+            REQUEST_REPLY => {
+                let id = request.u64()?;
+                endpoint.reply(id, request.bool()?);
+            }
+            REQUEST_POLL => {
+                backlog.extend(endpoint.poll_events());
+                write_events(&mut response, &mut backlog, &mut endpoint);
+            }
+            _ => return Err(invalid_data()),
+        }
+        write_frame(&mut stream, &response)?;
+    }
+}
+
+/// Checks that a player presents the broker's key, and returns the domain
+/// that the player states.
+fn authenticate(stream: &mut TcpStream, key: &str) -> io::Result<String> {
+    let hello = read_frame(stream)?;
+    let mut hello = Decoder::new(&hello);
+    let presented = hello.string()?;
+    let domain = hello.string()?.to_ascii_lowercase();
+
+    let accepted = keys_match(presented.as_bytes(), key.as_bytes());
+    let mut response = Vec::new();
+    write_bool(&mut response, accepted);
+    write_frame(stream, &response)?;
+
+    if !accepted {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "a player presented the wrong key",
+        ));
+    }
+    if domain.len() > MAX_DOMAIN_LEN {
+        return Err(invalid_data());
+    }
+    Ok(domain)
+}
+
+/// Compares two keys in constant time.
+fn keys_match(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Writes as many events from the backlog as fit in a frame.
+///
+/// Messages that wouldn't fit in a frame even on their own are rejected.
+fn write_events(
+    data: &mut Vec<u8>,
+    backlog: &mut VecDeque<LocalConnectionEvent>,
+    endpoint: &mut LocalConnectionEndpoint,
+) {
+    let mut events = Vec::new();
+    let mut len = 0;
+    while let Some(event) = backlog.pop_front() {
+        let mut encoded = Vec::new();
+        write_event(&mut encoded, &event);
+        if encoded.len() + 4 > MAX_FRAME_SIZE {
+            if let LocalConnectionEvent::Message(id, _) = event {
+                endpoint.reply(id, false);
+            }
+            continue;
+        }
+        if events.len() + encoded.len() + 4 > MAX_FRAME_SIZE {
+            backlog.push_front(event);
+            break;
+        }
+        events.extend_from_slice(&encoded);
+        len += 1;
+    }
+    write_u32(data, len);
+    data.extend_from_slice(&events);
+}
+
+const REQUEST_CONNECT: u8 = 0;
+const REQUEST_CLOSE: u8 = 1;
+const REQUEST_SEND: u8 = 2;
+const REQUEST_REPLY: u8 = 3;
+const REQUEST_POLL: u8 = 4;
+
+const EVENT_MESSAGE: u8 = 0;
+const EVENT_STATUS: u8 = 1;
+
+const VALUE_NULL: u8 = 0;
+const VALUE_BOOL: u8 = 1;
+const VALUE_NUMBER: u8 = 2;
+const VALUE_STRING: u8 = 3;
+const VALUE_OBJECT: u8 = 4;
+const VALUE_LIST: u8 = 5;
+
+fn invalid_data() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "malformed LocalConnection data")
+}
+
+/// Sends a request to the broker and waits for the response.
+fn request(stream: &mut TcpStream, request: &[u8]) -> io::Result<Vec<u8>> {
+    write_frame(stream, request)?;
+    read_frame(stream)
+}
+
+/// Reads a length-prefixed frame.
+fn read_frame(stream: &mut impl Read) -> io::Result<Vec<u8>> {
+    let mut len = [0; 4];
+    stream.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_FRAME_SIZE {
+        return Err(invalid_data());
+    }
+    let mut frame = vec![0; len];
+    stream.read_exact(&mut frame)?;
+    Ok(frame)
+}
+
+/// Writes a length-prefixed frame.
+fn write_frame(stream: &mut impl Write, frame: &[u8]) -> io::Result<()> {
+    let mut data = Vec::with_capacity(frame.len() + 4);
+    write_u32(&mut data, frame.len() as u32);
+    data.extend_from_slice(frame);
+    stream.write_all(&data)
+}
+
+fn write_bool(data: &mut Vec<u8>, value: bool) {
+    data.push(value as u8);
+}
+
+fn write_u32(data: &mut Vec<u8>, value: u32) {
+    data.extend_from_slice(&value.to_be_bytes());
+}
+
+fn write_u64(data: &mut Vec<u8>, value: u64) {
+    data.extend_from_slice(&value.to_be_bytes());
+}
+
+fn write_string(data: &mut Vec<u8>, value: &str) {
+    write_u32(data, value.len() as u32);
+    data.extend_from_slice(value.as_bytes());
+}
+
+fn write_value(data: &mut Vec<u8>, value: &ExternalValue) {
+    match value {
+        ExternalValue::Null => data.push(VALUE_NULL),
+        ExternalValue::Bool(value) => {
+            data.push(VALUE_BOOL);
+            write_bool(data, *value);
+        }
+        ExternalValue::Number(value) => {
+            data.push(VALUE_NUMBER);
+            write_u64(data, value.to_bits());
+        }
+        ExternalValue::String(value) => {
+            data.push(VALUE_STRING);
+            write_string(data, value);
+        }
+        ExternalValue::Object(values) => {
+            data.push(VALUE_OBJECT);
+            write_u32(data, values.len() as u32);
+            for (key, value) in values {
+                write_string(data, key);
+                write_value(data, value);
+            }
+        }
+        ExternalValue::List(values) => {
+            data.push(VALUE_LIST);
+            write_u32(data, values.len() as u32);
+            for value in values {
+                write_value(data, value);
+            }
+        }
+    }
+}
+
+/// Writes a message without its sender domain, which the broker fills in.
+fn write_message(data: &mut Vec<u8>, message: &LocalConnectionMessage) {
+    write_string(data, &message.connection_name);
+    write_string(data, &message.method_name);
+    write_u32(data, message.args.len() as u32);
+    for arg in &message.args {
+        write_value(data, arg);
+    }
+}
+
+fn write_event(data: &mut Vec<u8>, event: &LocalConnectionEvent) {
+    match event {
+        LocalConnectionEvent::Message(id, message) => {
+            data.push(EVENT_MESSAGE);
+            write_u64(data, *id);
+            write_string(data, &message.sender_domain);
+            write_message(data, message);
+        }
+        LocalConnectionEvent::Status(id, success) => {
+            data.push(EVENT_STATUS);
+            write_u64(data, *id);
+            write_bool(data, *success);
+        }
+    }
+}
+
+/// Reads the values written by the `write_*` functions.
+struct Decoder<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn bytes(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(invalid_data());
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn bool(&mut self) -> io::Result<bool> {
+        Ok(self.u8()? != 0)
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        let bytes = self.bytes(4)?.try_into().map_err(|_| invalid_data())?;
+        Ok(u32::from_be_bytes(bytes))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        let bytes = self.bytes(8)?.try_into().map_err(|_| invalid_data())?;
+        Ok(u64::from_be_bytes(bytes))
+    }
+
+    fn string(&mut self) -> io::Result<String> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.bytes(len)?.to_vec()).map_err(|_| invalid_data())
+    }
+
+    /// Reads a value that is nested inside `depth` other values.
+    fn value(&mut self, depth: usize) -> io::Result<ExternalValue> {
+        if depth > MAX_VALUE_DEPTH {
+            return Err(invalid_data());
+        }
+
+        Ok(match self.u8()? {
+            VALUE_NULL => ExternalValue::Null,
+            VALUE_BOOL => ExternalValue::Bool(self.bool()?),
+            VALUE_NUMBER => ExternalValue::Number(f64::from_bits(self.u64()?)),
+            VALUE_STRING => ExternalValue::String(self.string()?),
+            VALUE_OBJECT => {
+                let mut values = BTreeMap::new();
+                for _ in 0..self.u32()? {
+                    let key = self.string()?;
+                    values.insert(key, self.value(depth + 1)?);
+                }
+                ExternalValue::Object(values)
+            }
+            VALUE_LIST => {
+                let len = self.u32()?;
+                let values = (0..len).map(|_| self.value(depth + 1));
+                ExternalValue::List(values.collect::<Result<_, _>>()?)
+            }
+            _ => return Err(invalid_data()),
+        })
+    }
+
+    /// Reads a message, which was sent from the given domain.
+    fn message(&mut self, sender_domain: &str) -> io::Result<LocalConnectionMessage> {
+        let connection_name = self.string()?;
+        let method_name = self.string()?;
+        let len = self.u32()?;
+        let args = (0..len).map(|_| self.value(0)).collect::<Result<_, _>>()?;
+        Ok(LocalConnectionMessage {
+            connection_name,
+            method_name,
+            args,
+            sender_domain: sender_domain.to_string(),
+        })
+    }
+
+    fn event(&mut self) -> io::Result<LocalConnectionEvent> {
+        Ok(match self.u8()? {
+            EVENT_MESSAGE => {
+                let id = self.u64()?;
+                let sender_domain = self.string()?;
+                LocalConnectionEvent::Message(id, self.message(&sender_domain)?)
+            }
+            EVENT_STATUS => {
+                let id = self.u64()?;
+                LocalConnectionEvent::Status(id, self.bool()?)
+            }
+            _ => return Err(invalid_data()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a rendezvous on a loopback port that nothing listens on.
+    fn rendezvous() -> Rendezvous {
+        let port = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let key_path = std::env::temp_dir()
+            .join(format!("ruffle-{}", std::process::id()))
+            .join(format!("local_connection-{}.key", port));
+        Rendezvous { port, key_path }
+    }
+
+    fn message(connection_name: &str, sender_domain: &str) -> LocalConnectionMessage {
+        let mut object = BTreeMap::new();
+        object.insert("count".to_string(), ExternalValue::Number(2.5));
+        object.insert("on".to_string(), ExternalValue::Bool(true));
+        LocalConnectionMessage {
+            connection_name: connection_name.to_string(),
+            method_name: "hello".to_string(),
+            args: vec![
+                "world".into(),
+                ExternalValue::Null,
+                ExternalValue::Object(object),
+                ExternalValue::List(vec![ExternalValue::Number(-1.0)]),
+            ],
+            sender_domain: sender_domain.to_string(),
+        }
+    }
+
+    /// Polls until at least one event arrives, or gives up after a while.
+    fn wait_for_events(backend: &mut dyn LocalConnectionBackend) -> Vec<LocalConnectionEvent> {
+        for _ in 0..300 {
+            let events = backend.poll_events();
+            if !events.is_empty() {
+                return events;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        Vec::new()
+    }
+
+    /// Sends a message until it arrives at the receiver, and returns the
+    /// receiver's ID of it.
+    fn deliver(
+        sender: &mut dyn LocalConnectionBackend,
+        receiver: &mut dyn LocalConnectionBackend,
+        message: LocalConnectionMessage,
+        expected: LocalConnectionMessage,
+    ) -> u64 {
+        for _ in 0..50 {
+            sender.send(message.clone());
+            if let [LocalConnectionEvent::Message(id, received)] = &wait_for_events(receiver)[..] {
+                assert_eq!(*received, expected);
+                return *id;
+            }
+            // The receiver hasn't claimed its name yet, so the message bounced.
+            wait_for_events(sender);
+        }
+        panic!("the message never arrived");
+    }
+
+    #[test]
+    fn encoding_round_trip() {
+        let events = vec![
+            LocalConnectionEvent::Message(7, message("localhost:lobby", "example.com")),
+            LocalConnectionEvent::Status(8, false),
+        ];
+        for event in events {
+            let mut data = Vec::new();
+            write_event(&mut data, &event);
+            assert_eq!(Decoder::new(&data).event().unwrap(), event);
+        }
+        assert!(Decoder::new(&[EVENT_STATUS, 0]).event().is_err());
+
+        // Players can't pick the domain that their messages are sent from.
+        let mut data = Vec::new();
+        write_message(&mut data, &message("localhost:lobby", "spoofed.com"));
+        assert_eq!(
+            Decoder::new(&data).message("example.com").unwrap(),
+            message("localhost:lobby", "example.com")
+        );
+    }
+
+    #[test]
+    fn frames_are_limited() {
+        let mut data = Vec::new();
+        write_u32(&mut data, MAX_FRAME_SIZE as u32 + 1);
+        let error = read_frame(&mut &data[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let mut value = ExternalValue::Null;
+        for _ in 0..MAX_VALUE_DEPTH {
+            value = ExternalValue::List(vec![value]);
+        }
+        let mut data = Vec::new();
+        write_value(&mut data, &value);
+        assert_eq!(Decoder::new(&data).value(0).unwrap(), value);
+
+        let value = ExternalValue::List(vec![value]);
+        let mut data = Vec::new();
+        write_value(&mut data, &value);
+        assert!(Decoder::new(&data).value(0).is_err());
+    }
+
+    #[test]
+    fn players_share_the_hosted_broker() {
+        let rendezvous = rendezvous();
+        let mut host = BrokerClient::start(rendezvous.clone(), "host.com");
+        let mut client = BrokerClient::start(rendezvous.clone(), "client.com");
+
+        assert!(client.connect("localhost:lobby"));
+        assert!(!host.connect("localhost:lobby"));
+
+        let sent = host.send(message("localhost:lobby", "host.com"));
+        let received = match &wait_for_events(&mut client)[..] {
+            [LocalConnectionEvent::Message(id, received)] => {
+                assert_eq!(*received, message("localhost:lobby", "host.com"));
+                *id
+            }
+            events => panic!("unexpected events {:?}", events),
+        };
+        client.reply(received, true);
+        assert_eq!(
+            wait_for_events(&mut host),
+            vec![LocalConnectionEvent::Status(sent, true)]
+        );
+
+        // The broker stamps messages with the domain that the player joined with.
+        assert!(host.connect("localhost:hall"));
+        client.send(message("localhost:hall", "spoofed.com"));
+        match &wait_for_events(&mut host)[..] {
+            [LocalConnectionEvent::Message(_, received)] => {
+                assert_eq!(*received, message("localhost:hall", "client.com"))
+            }
+            events => panic!("unexpected events {:?}", events),
+        }
+
+        let sent = client.send(message("localhost:nobody", "client.com"));
+        assert_eq!(
+            wait_for_events(&mut client),
+            vec![LocalConnectionEvent::Status(sent, false)]
+        );
+
+        // Closing the client's connection frees the name for the host, once
+        // the broker gets to it.
+        client.close("localhost:lobby");
+        let claimed = (0..100).any(|_| {
+            thread::sleep(Duration::from_millis(10));
+            host.connect("localhost:lobby")
+        });
+        assert!(claimed);
+        let _ = fs::remove_file(rendezvous.key_path);
+    }
+
+    #[test]
+    fn players_must_present_the_key() {
+        let rendezvous = rendezvous();
+        let mut host = BrokerClient::start(rendezvous.clone(), "host.com");
+        assert!(host.connect("localhost:lobby"));
+
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, rendezvous.port)).unwrap();
+        let mut hello = Vec::new();
+        write_string(&mut hello, "guessed");
+        write_string(&mut hello, "evil.com");
+        let response = request(&mut stream, &hello).unwrap();
+        assert!(!Decoder::new(&response).bool().unwrap());
+
+        // The broker hangs up instead of answering anything else.
+        let mut data = vec![REQUEST_CONNECT];
+        write_string(&mut data, "localhost:evil");
+        assert!(request(&mut stream, &data).is_err());
+        let _ = fs::remove_file(rendezvous.key_path);
+    }
+
+    #[test]
+    fn players_elect_a_new_broker_when_the_host_exits() {
+        let rendezvous = rendezvous();
+        let host = BrokerClient::start(rendezvous.clone(), "host.com");
+        let mut client = BrokerClient::start(rendezvous.clone(), "client.com");
+        assert!(client.connect("localhost:lobby"));
+
+        drop(host);
+
+        // Whoever hosts the new broker, the client claims its name there again.
+        let mut late = BrokerClient::start(rendezvous.clone(), "late.com");
+        let received = deliver(
+            &mut late,
+            &mut client,
+            message("localhost:lobby", "late.com"),
+            message("localhost:lobby", "late.com"),
+        );
+        client.reply(received, true);
+        let _ = fs::remove_file(rendezvous.key_path);
+    }
+}
@@ -1,16 +1,20 @@
 #![allow(clippy::unneeded_field_pattern)]
 
 mod audio;
+mod context_menu;
 mod custom_event;
 mod executor;
 mod input;
+mod local_connection;
 mod locale;
 mod navigator;
+mod socket;
 mod storage;
 mod task;
 
 use crate::custom_event::RuffleEvent;
 use crate::executor::GlutinAsyncExecutor;
+use crate::socket::DesktopSocketBackend;
 use clap::Clap;
 use isahc::config::RedirectPolicy;
 use isahc::prelude::*;
@@ -26,6 +30,7 @@ use url::Url;
 
 use crate::storage::DiskStorageBackend;
 use ruffle_core::backend::log::NullLogBackend;
+use ruffle_core::backend::video::SoftwareVideoBackend;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use std::io::Read;
@@ -75,6 +80,11 @@ struct Opt {
     /// (Optional) Proxy to use when loading movies via URL
     #[clap(long, case_insensitive = true)]
     proxy: Option<Url>,
+
+    /// Trust local movies, letting them access both local files and the network. By default,
+    /// they are restricted to one or the other, depending on what the movie asks for.
+    #[clap(long)]
+    trusted: bool,
 }
 
 #[cfg(feature = "render_trace")]
@@ -181,6 +191,10 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
         trace_path(&opt),
     )?);
     let (executor, chan) = GlutinAsyncExecutor::new(event_loop.create_proxy());
+    let domain = movie_url
+        .host_str()
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_else(|| "localhost".to_string());
     let navigator = Box::new(navigator::ExternalNavigatorBackend::new(
         movie_url,
         chan,
@@ -199,8 +213,15 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
         input,
         storage,
         locale,
+        Box::new(SoftwareVideoBackend::new()),
         Box::new(NullLogBackend::new()),
+        Box::new(DesktopSocketBackend::new()),
     )?;
+    player.lock().unwrap().set_trust_local_movies(opt.trusted);
+    player
+        .lock()
+        .unwrap()
+        .set_local_connection_backend(local_connection::connect_to_broker(&domain));
     player.lock().unwrap().set_root_movie(Arc::new(movie));
     player.lock().unwrap().set_is_playing(true); // Desktop player will auto-play.
 
@@ -223,6 +244,19 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
 
                 // Core loop
                 winit::event::Event::MainEventsCleared => {
+                    // A key press that typed no character is still waiting to be sent.
+                    {
+                        let mut player_lock = player.lock().unwrap();
+                        if let Some(event) = player_lock
+                            .input_mut()
+                            .downcast_mut::<input::WinitInputBackend>()
+                            .unwrap()
+                            .flush_key_down()
+                        {
+                            player_lock.handle_event(event);
+                        }
+                    }
+
                     let new_time = Instant::now();
                     let dt = new_time.duration_since(time).as_micros();
                     if dt > 0 {
@@ -282,6 +316,24 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
                             window.request_redraw();
                         }
                     }
+                    WindowEvent::MouseInput {
+                        button: MouseButton::Right,
+                        state: ElementState::Pressed,
+                        ..
+                    } => {
+                        // Don't hold the player lock while the menu is open.
+                        let items = player
+                            .lock()
+                            .unwrap()
+                            .prepare_context_menu(mouse_pos.x, mouse_pos.y);
+                        if let Some(index) = context_menu::show_context_menu(&window, &items) {
+                            let mut player_lock = player.lock().unwrap();
+                            player_lock.run_context_menu_callback(index);
+                            if player_lock.needs_render() {
+                                window.request_redraw();
+                            }
+                        }
+                    }
                     WindowEvent::MouseWheel { delta, .. } => {
                         use ruffle_core::events::MouseWheelDelta;
                         let mut player_lock = player.lock().unwrap();
@@ -305,16 +357,16 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
                     WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
                     WindowEvent::KeyboardInput { .. } | WindowEvent::ReceivedCharacter(_) => {
                         let mut player_lock = player.lock().unwrap();
-                        if let Some(event) = player_lock
+                        let events = player_lock
                             .input_mut()
                             .downcast_mut::<input::WinitInputBackend>()
                             .unwrap()
-                            .handle_event(event)
-                        {
+                            .handle_event(event);
+                        for event in events {
                             player_lock.handle_event(event);
-                            if player_lock.needs_render() {
-                                window.request_redraw();
-                            }
+                        }
+                        if player_lock.needs_render() {
+                            window.request_redraw();
                         }
                     }
                     _ => (),
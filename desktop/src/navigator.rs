@@ -1,18 +1,22 @@
 //! Navigator backend for web
 
 use crate::custom_event::RuffleEvent;
+use futures_lite::AsyncRead;
 use isahc::config::RedirectPolicy;
 use isahc::prelude::*;
 use ruffle_core::backend::navigator::{
-    NavigationMethod, NavigatorBackend, OwnedFuture, RequestOptions,
+    NavigationMethod, NavigatorBackend, OwnedFuture, RequestOptions, Response as FetchResponse,
+    ResponseBody,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
+use ruffle_core::security::Sandbox;
 use std::borrow::Cow;
 use std::fs;
-use std::io::Read;
+use std::pin::Pin;
 use std::rc::Rc;
 use std::sync::mpsc::Sender;
+use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 use url::Url;
 use winit::event_loop::EventLoopProxy;
@@ -34,6 +38,9 @@ pub struct ExternalNavigatorBackend {
 
     // Client to use for network requests
     client: Option<Rc<HttpClient>>,
+
+    /// The security sandbox that fetches are subject to.
+    sandbox: Sandbox,
 }
 
 impl ExternalNavigatorBackend {
@@ -58,6 +65,7 @@ impl ExternalNavigatorBackend {
             client,
             movie_url,
             start_time: Instant::now(),
+            sandbox: Sandbox::default(),
         }
     }
 }
@@ -111,36 +119,20 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         Instant::now().duration_since(self.start_time)
     }
 
-    fn fetch(&self, url: &str, options: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
-        // TODO: honor sandbox type (local-with-filesystem, local-with-network, remote, ...)
-        let full_url = self.movie_url.clone().join(url).unwrap();
+    fn fetch(&self, url: &str, options: RequestOptions) -> OwnedFuture<FetchResponse, Error> {
+        let full_url = match self.movie_url.join(url) {
+            Ok(full_url) => full_url,
+            Err(_) => {
+                let url = url.to_string();
+                return Box::pin(async move { Err(Error::FetchError(url)) });
+            }
+        };
 
         let client = self.client.clone();
-        match full_url.scheme() {
-            "file" => Box::pin(async move {
-                fs::read(full_url.to_file_path().unwrap()).map_err(Error::NetworkError)
-            }),
-            _ => Box::pin(async move {
-                let client = client.ok_or(Error::NetworkUnavailable)?;
-
-                let request = match options.method() {
-                    NavigationMethod::GET => Request::get(full_url.to_string()),
-                    NavigationMethod::POST => Request::post(full_url.to_string()),
-                };
-
-                let (body_data, _) = options.body().clone().unwrap_or_default();
-                let body = request
-                    .body(body_data)
-                    .map_err(|e| Error::FetchError(e.to_string()))?;
-
-                let response = client
-                    .send_async(body)
-                    .await
-                    .map_err(|e| Error::FetchError(e.to_string()))?;
-
-                response_to_bytes(response).map_err(|e| Error::FetchError(e.to_string()))
-            }),
-        }
+        self.sandbox
+            .fetch(full_url, options, move |full_url, options| {
+                fetch_unrestricted(client.clone(), full_url, options)
+            })
     }
 
     fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
@@ -161,10 +153,122 @@ impl NavigatorBackend for ExternalNavigatorBackend {
             url.into()
         }
     }
+
+    fn set_sandbox(&mut self, sandbox: Sandbox) {
+        self.sandbox = sandbox;
+    }
+
+    fn load_policy_file(&mut self, url: &str) {
+        if let Ok(url) = self.movie_url.join(url) {
+            self.sandbox.add_policy_file(url);
+        }
+    }
 }
 
-fn response_to_bytes(res: Response<Body>) -> Result<Vec<u8>, std::io::Error> {
-    let mut buffer: Vec<u8> = Vec::new();
-    res.into_body().read_to_end(&mut buffer)?;
-    Ok(buffer)
+/// Fetch a URL without any sandbox restrictions.
+fn fetch_unrestricted(
+    client: Option<Rc<HttpClient>>,
+    full_url: Url,
+    options: RequestOptions,
+) -> OwnedFuture<FetchResponse, Error> {
+    match full_url.scheme() {
+        "file" => Box::pin(async move {
+            let data = fs::read(full_url.to_file_path().unwrap()).map_err(Error::NetworkError)?;
+
+            Ok(FetchResponse::from_data(full_url.into_string(), data))
+        }),
+        _ => Box::pin(async move {
+            let client = client.ok_or(Error::NetworkUnavailable)?;
+
+            let mut request = match options.method() {
+                NavigationMethod::GET => Request::get(full_url.to_string()),
+                NavigationMethod::POST => Request::post(full_url.to_string()),
+            };
+
+            let (body_data, mime) = options.body().clone().unwrap_or_default();
+
+            // A custom `Content-Type` header takes precedence over the body's mime type.
+            let has_content_type = options
+                .headers()
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("Content-Type"));
+            if !mime.is_empty() && !has_content_type {
+                request = request.header("Content-Type", mime);
+            }
+
+            for (name, value) in options.headers() {
+                request = request.header(name.as_str(), value.as_str());
+            }
+
+            let body = request
+                .body(body_data)
+                .map_err(|e| Error::FetchError(e.to_string()))?;
+
+            let response = client
+                .send_async(body)
+                .await
+                .map_err(|e| Error::FetchError(e.to_string()))?;
+
+            Ok(convert_response(full_url, response))
+        }),
+    }
+}
+
+/// The size of the buffer used to read HTTP response bodies.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// A response body that reads from an isahc body as data arrives.
+struct HttpBody {
+    body: Pin<Box<Body>>,
+
+    /// The buffer that each read goes into, kept between reads.
+    buffer: Vec<u8>,
+}
+
+impl ResponseBody for HttpBody {
+    fn poll_chunk(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Vec<u8>, Error>>> {
+        let this = self.get_mut();
+        match this.body.as_mut().poll_read(cx, &mut this.buffer) {
+            Poll::Ready(Ok(0)) => Poll::Ready(None),
+            Poll::Ready(Ok(len)) => Poll::Ready(Some(Ok(this.buffer[..len].to_vec()))),
+            Poll::Ready(Err(e)) => Poll::Ready(Some(Err(Error::NetworkError(e)))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Convert an isahc response into a fetch response.
+fn convert_response(request_url: Url, response: Response<Body>) -> FetchResponse {
+    let url = response
+        .effective_uri()
+        .map(|uri| uri.to_string())
+        .unwrap_or_else(|| request_url.into_string());
+    let status = response.status().as_u16();
+    let headers = response
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            )
+        })
+        .collect();
+    let content_length = response.body().len();
+
+    let mut fetch_response = FetchResponse::new(
+        url,
+        Box::pin(HttpBody {
+            body: Box::pin(response.into_body()),
+            buffer: vec![0; CHUNK_SIZE],
+        }),
+    );
+    fetch_response.status = Some(status);
+    fetch_response.headers = headers;
+    fetch_response.content_length = content_length;
+
+    fetch_response
 }
@@ -0,0 +1,235 @@
+//! Socket backend over std TCP streams.
+
+use ruffle_core::backend::socket::{SocketBackend, SocketEvent, SocketHandle};
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// How long a connection attempt may take before it's considered failed.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// The size of the buffer used to read from sockets.
+const READ_BUFFER_SIZE: usize = 4096;
+
+/// Socket backend that runs each connection on its own threads.
+///
+/// A connection thread connects and then writes everything that is sent,
+/// while a reader thread reports incoming data. Events are collected over a
+/// channel until the player polls for them.
+pub struct DesktopSocketBackend {
+    next_handle: u64,
+    connections: HashMap<SocketHandle, Sender<Vec<u8>>>,
+    event_sender: Sender<SocketEvent>,
+    event_receiver: Receiver<SocketEvent>,
+}
+
+impl DesktopSocketBackend {
+    pub fn new() -> Self {
+        let (event_sender, event_receiver) = channel();
+        Self {
+            next_handle: 0,
+            connections: HashMap::new(),
+            event_sender,
+            event_receiver,
+        }
+    }
+}
+
+impl SocketBackend for DesktopSocketBackend {
+    fn connect(&mut self, host: &str, port: u16) -> SocketHandle {
+        let handle = SocketHandle(self.next_handle);
+        self.next_handle += 1;
+
+        let (data_sender, data_receiver) = channel();
+        self.connections.insert(handle, data_sender);
+
+        let host = host.to_string();
+        let events = self.event_sender.clone();
+        thread::spawn(move || run_connection(handle, &host, port, data_receiver, events));
+
+        handle
+    }
+
+    fn send(&mut self, handle: SocketHandle, data: Vec<u8>) {
+        if let Some(connection) = self.connections.get(&handle) {
+            let _ = connection.send(data);
+        }
+    }
+
+    fn close(&mut self, handle: SocketHandle) {
+        // Dropping the sender makes the connection thread shut the stream down.
+        self.connections.remove(&handle);
+    }
+
+    fn poll_events(&mut self) -> Vec<SocketEvent> {
+        let connections = &mut self.connections;
+        self.event_receiver
+            .try_iter()
+            .filter(|event| {
+                let handle = match event {
+                    SocketEvent::Connect(handle, _)
+                    | SocketEvent::Data(handle, _)
+                    | SocketEvent::Close(handle) => handle,
+                };
+                match event {
+                    SocketEvent::Connect(_, false) | SocketEvent::Close(_) => {
+                        connections.remove(handle).is_some()
+                    }
+                    _ => connections.contains_key(handle),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for DesktopSocketBackend {
+    fn default() -> Self {
+        DesktopSocketBackend::new()
+    }
+}
+
+/// Connects to the given host, then writes any queued data until the
+/// connection is closed by either side.
+fn run_connection(
+    handle: SocketHandle,
+    host: &str,
+    port: u16,
+    data: Receiver<Vec<u8>>,
+    events: Sender<SocketEvent>,
+) {
+    let stream = match connect(host, port) {
+        Ok(stream) => stream,
+        Err(e) => {
+            log::warn!("Unable to connect to {}:{}: {}", host, port, e);
+            let _ = events.send(SocketEvent::Connect(handle, false));
+            return;
+        }
+    };
+    let _ = events.send(SocketEvent::Connect(handle, true));
+
+    if let Ok(reader) = stream.try_clone() {
+        let events = events.clone();
+        thread::spawn(move || run_reader(handle, reader, events));
+    }
+
+    let mut writer = stream;
+    for message in data {
+        if writer.write_all(&message).is_err() {
+            break;
+        }
+    }
+
+    let _ = writer.shutdown(Shutdown::Both);
+}
+
+/// Reports everything read from the stream until it's closed.
+fn run_reader(handle: SocketHandle, mut stream: TcpStream, events: Sender<SocketEvent>) {
+    let mut buffer = [0; READ_BUFFER_SIZE];
+    loop {
+        match stream.read(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(len) => {
+                if events
+                    .send(SocketEvent::Data(handle, buffer[..len].to_vec()))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        }
+    }
+
+    let _ = events.send(SocketEvent::Close(handle));
+}
+
+/// Connects to the first address of the given host that accepts the connection.
+fn connect(host: &str, port: u16) -> io::Result<TcpStream> {
+    let mut last_error = None;
+    for address in (host, port).to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, CONNECT_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    Err(last_error
+        .unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, "host has no addresses")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::time::Instant;
+
+    /// Polls the backend until it reports at least one event.
+    fn wait_for_events(backend: &mut DesktopSocketBackend) -> Vec<SocketEvent> {
+        let start = Instant::now();
+        loop {
+            let events = backend.poll_events();
+            if !events.is_empty() || start.elapsed() > Duration::from_secs(5) {
+                return events;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn loopback() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut message = [0; 6];
+            stream.read_exact(&mut message).unwrap();
+            assert_eq!(&message, b"<ping>");
+            stream.write_all(b"<pong/>\0").unwrap();
+        });
+
+        let mut backend = DesktopSocketBackend::new();
+        let handle = backend.connect("127.0.0.1", port);
+        assert_eq!(
+            wait_for_events(&mut backend),
+            vec![SocketEvent::Connect(handle, true)]
+        );
+
+        backend.send(handle, b"<ping>".to_vec());
+        server.join().unwrap();
+
+        let mut received = Vec::new();
+        loop {
+            let events = wait_for_events(&mut backend);
+            assert!(!events.is_empty(), "Timed out waiting for the server");
+            for event in events {
+                match event {
+                    SocketEvent::Data(h, data) if h == handle => received.extend_from_slice(&data),
+                    SocketEvent::Close(h) if h == handle => {
+                        assert_eq!(received, b"<pong/>\0");
+                        return;
+                    }
+                    event => panic!("Unexpected event {:?}", event),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn connection_refused() {
+        // Bind and immediately drop a listener to find a port nothing listens on.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let mut backend = DesktopSocketBackend::new();
+        let handle = backend.connect("127.0.0.1", port);
+        assert_eq!(
+            wait_for_events(&mut backend),
+            vec![SocketEvent::Connect(handle, false)]
+        );
+    }
+}
@@ -7,9 +7,13 @@ use ruffle_core::backend::input::NullInputBackend;
 use ruffle_core::backend::locale::NullLocaleBackend;
 use ruffle_core::backend::log::NullLogBackend;
 use ruffle_core::backend::navigator::NullNavigatorBackend;
+use ruffle_core::backend::render::RenderBackend;
+use ruffle_core::backend::socket::NullSocketBackend;
 use ruffle_core::backend::storage::MemoryStorageBackend;
+use ruffle_core::backend::video::SoftwareVideoBackend;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::Player;
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::{wgpu, Descriptors, WgpuRenderBackend};
@@ -80,20 +84,27 @@ struct Opt {
     #[clap(long, short, case_insensitive = true, default_value = "high", arg_enum)]
     power: PowerPreference,
 
+    /// Render on the CPU instead of using a graphics device.
+    /// This is slower, but works on machines without hardware acceleration.
+    #[clap(long)]
+    software: bool,
+
     /// Location to store a wgpu trace output
     #[clap(long, parse(from_os_str))]
     #[cfg(feature = "render_trace")]
     trace_path: Option<PathBuf>,
 }
 
+/// Captures frames of an SWF.
+/// Frames are rendered with wgpu using `descriptors`, or with the software renderer if `None`.
 fn take_screenshot(
-    descriptors: Descriptors,
+    descriptors: Option<Descriptors>,
     swf_path: &Path,
     frames: u32,
     skipframes: u32,
     progress: &Option<ProgressBar>,
     size: SizeOpt,
-) -> Result<(Descriptors, Vec<RgbaImage>), Box<dyn std::error::Error>> {
+) -> Result<(Option<Descriptors>, Vec<RgbaImage>), Box<dyn std::error::Error>> {
     let movie = SwfMovie::from_path(&swf_path)?;
 
     let width = size.width.unwrap_or_else(|| movie.width());
@@ -102,15 +113,22 @@ fn take_screenshot(
     let height = size.height.unwrap_or_else(|| movie.height());
     let height = (height as f32 * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height));
+    let renderer: Box<dyn RenderBackend> = if let Some(descriptors) = descriptors {
+        let target = TextureTarget::new(&descriptors.device, (width, height));
+        Box::new(WgpuRenderBackend::new(descriptors, target)?)
+    } else {
+        Box::new(SoftwareRenderBackend::new(width, height))
+    };
     let player = Player::new(
-        Box::new(WgpuRenderBackend::new(descriptors, target)?),
+        renderer,
         Box::new(NullAudioBackend::new()),
         Box::new(NullNavigatorBackend::new()),
         Box::new(NullInputBackend::new()),
         Box::new(MemoryStorageBackend::default()),
         Box::new(NullLocaleBackend::new()),
+        Box::new(SoftwareVideoBackend::new()),
         Box::new(NullLogBackend::new()),
+        Box::new(NullSocketBackend::new()),
     )?;
 
     player
@@ -134,12 +152,21 @@ fn take_screenshot(
         if i >= skipframes {
             player.lock().unwrap().render();
             let mut player = player.lock().unwrap();
-            let renderer = player
-                .renderer_mut()
-                .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                .unwrap();
-            let target = renderer.target();
-            if let Some(image) = target.capture(renderer.device()) {
+            let renderer = player.renderer_mut();
+            let image = if let Some(renderer) =
+                renderer.downcast_ref::<WgpuRenderBackend<TextureTarget>>()
+            {
+                renderer.target().capture(renderer.device())
+            } else if let Some(renderer) = renderer.downcast_ref::<SoftwareRenderBackend>() {
+                RgbaImage::from_raw(
+                    renderer.width(),
+                    renderer.height(),
+                    renderer.capture_frame(),
+                )
+            } else {
+                None
+            };
+            if let Some(image) = image {
                 result.push(image);
             } else {
                 return Err(format!("Unable to capture frame {} of {:?}", i, swf_path).into());
@@ -158,8 +185,7 @@ fn take_screenshot(
         .destroy()
         .downcast::<WgpuRenderBackend<TextureTarget>>()
         .ok()
-        .unwrap()
-        .descriptors();
+        .map(|renderer| renderer.descriptors());
     Ok((descriptors, result))
 }
 
@@ -193,7 +219,7 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn Error>> {
+fn capture_single_swf(descriptors: Option<Descriptors>, opt: &Opt) -> Result<(), Box<dyn Error>> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         if opt.frames == 1 {
@@ -270,7 +296,10 @@ fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn
     Ok(())
 }
 
-fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn Error>> {
+fn capture_multiple_swfs(
+    mut descriptors: Option<Descriptors>,
+    opt: &Opt,
+) -> Result<(), Box<dyn Error>> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -369,15 +398,14 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
     None
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let opt: Opt = Opt::parse();
+fn create_descriptors(opt: &Opt) -> Result<Descriptors, Box<dyn Error>> {
     let instance = wgpu::Instance::new(opt.graphics.into());
     let adapter = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
         power_preference: opt.power.into(),
         compatible_surface: None,
     }))
     .ok_or(
-        "This tool requires hardware acceleration, but no compatible graphics device was found.",
+        "This tool requires hardware acceleration, but no compatible graphics device was found. Try --software to render on the CPU.",
     )?;
 
     let (device, queue) = block_on(adapter.request_device(
@@ -388,7 +416,16 @@ fn main() -> Result<(), Box<dyn Error>> {
         },
         trace_path(&opt),
     ))?;
-    let descriptors = Descriptors::new(device, queue)?;
+    Descriptors::new(device, queue)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let opt: Opt = Opt::parse();
+    let descriptors = if opt.software {
+        None
+    } else {
+        Some(create_descriptors(&opt)?)
+    };
 
     if opt.swf.is_file() {
         capture_single_swf(descriptors, &opt)?;
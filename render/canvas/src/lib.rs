@@ -1,4 +1,5 @@
 use ruffle_core::backend::render::{
+    filters,
     swf::{self, CharacterId, GradientInterpolation, GradientSpread},
     Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, JpegTagFormat, Letterbox, RenderBackend,
     ShapeHandle, Transform,
@@ -9,10 +10,10 @@ use ruffle_core::swf::Matrix;
 use ruffle_web_common::JsResult;
 use std::collections::HashMap;
 use std::convert::TryInto;
-use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen::{Clamped, JsCast, JsValue};
 use web_sys::{
     CanvasGradient, CanvasPattern, CanvasRenderingContext2d, Element, HtmlCanvasElement,
-    HtmlImageElement, Path2d, SvgsvgElement,
+    HtmlImageElement, ImageData, Path2d, SvgsvgElement,
 };
 
 type Error = Box<dyn std::error::Error>;
@@ -32,6 +33,8 @@ pub struct WebCanvasRenderBackend {
     use_color_transform_hack: bool,
     pixelated_property_value: &'static str,
     deactivating_mask: bool,
+    filters: Vec<Vec<filters::Filter>>,
+    blend_modes: Vec<swf::BlendMode>,
 }
 
 /// Canvas-drawable shape data extracted from an SWF file.
@@ -215,6 +218,8 @@ impl WebCanvasRenderBackend {
             viewport_height: 0,
             use_color_transform_hack: is_firefox,
             deactivating_mask: false,
+            filters: Vec::new(),
+            blend_modes: Vec::new(),
 
             // For rendering non-smoothed bitmaps.
             // crisp-edges works in Firefox, pixelated works in Chrome (and others)?
@@ -255,6 +260,25 @@ impl WebCanvasRenderBackend {
         ))
     }
 
+    /// Wraps raw RGBA pixels in an image element.
+    fn rgba_to_bitmap_data(width: u32, height: u32, rgba: Vec<u8>) -> Result<BitmapData, Error> {
+        let png = Self::bitmap_to_png_data_uri(Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(rgba),
+        })?;
+
+        let image = HtmlImageElement::new().unwrap();
+        image.set_src(&png);
+
+        Ok(BitmapData {
+            image,
+            width,
+            height,
+            data: png,
+        })
+    }
+
     // Pushes a fresh canvas onto the stack to use as a render target.
     fn push_render_target(&mut self) {
         self.cur_render_target += 1;
@@ -290,6 +314,9 @@ impl WebCanvasRenderBackend {
         let height = self.canvas.height();
         self.context
             .clear_rect(0.0, 0.0, width.into(), height.into());
+        self.context
+            .set_global_composite_operation("source-over")
+            .warn_on_error();
     }
 
     fn pop_render_target(&mut self) -> (HtmlCanvasElement, CanvasRenderingContext2d) {
@@ -398,11 +425,7 @@ impl WebCanvasRenderBackend {
         })
     }
 
-    fn register_bitmap_raw(
-        &mut self,
-        id: CharacterId,
-        bitmap: Bitmap,
-    ) -> Result<BitmapInfo, Error> {
+    fn register_bitmap(&mut self, id: CharacterId, bitmap: Bitmap) -> Result<BitmapInfo, Error> {
         let (width, height) = (bitmap.width, bitmap.height);
         let png = Self::bitmap_to_png_data_uri(bitmap)?;
 
@@ -501,7 +524,7 @@ impl RenderBackend for WebCanvasRenderBackend {
             self.register_bitmap_pure_jpeg(id, data)
         } else {
             let bitmap = ruffle_core::backend::render::decode_define_bits_jpeg(data, None)?;
-            self.register_bitmap_raw(id, bitmap)
+            self.register_bitmap(id, bitmap)
         }
     }
 
@@ -513,7 +536,7 @@ impl RenderBackend for WebCanvasRenderBackend {
     ) -> Result<BitmapInfo, Error> {
         let bitmap =
             ruffle_core::backend::render::decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
-        self.register_bitmap_raw(id, bitmap)
+        self.register_bitmap(id, bitmap)
     }
 
     fn register_bitmap_png(
@@ -542,6 +565,32 @@ impl RenderBackend for WebCanvasRenderBackend {
         })
     }
 
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let handle = BitmapHandle(self.bitmaps.len());
+        self.bitmaps
+            .push(Self::rgba_to_bitmap_data(width, height, rgba)?);
+        Ok(handle)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        if handle.0 >= self.bitmaps.len() {
+            return Err("update_texture: Bitmap not registered".into());
+        }
+        self.bitmaps[handle.0] = Self::rgba_to_bitmap_data(width, height, rgba)?;
+        Ok(handle)
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         // Reset canvas transform in case it was left in a dirty state.
         self.context.reset_transform().unwrap();
@@ -555,6 +604,8 @@ impl RenderBackend for WebCanvasRenderBackend {
             .fill_rect(0.0, 0.0, width.into(), height.into());
 
         self.deactivating_mask = false;
+        self.filters.clear();
+        self.blend_modes.clear();
     }
 
     fn end_frame(&mut self) {
@@ -746,6 +797,101 @@ impl RenderBackend for WebCanvasRenderBackend {
             .draw_image_with_html_canvas_element(&maskee_canvas, 0.0, 0.0)
             .unwrap();
     }
+
+    fn push_filters(&mut self, filters: &[filters::Filter]) {
+        self.filters.push(filters.to_vec());
+        self.push_render_target();
+    }
+
+    fn pop_filters(&mut self) {
+        let filters = self.filters.pop().unwrap_or_default();
+        let (filtered_canvas, filtered_context) = self.pop_render_target();
+
+        let width = filtered_canvas.width();
+        let height = filtered_canvas.height();
+        let image_data =
+            match filtered_context.get_image_data(0.0, 0.0, width.into(), height.into()) {
+                Ok(image_data) => image_data,
+                Err(e) => {
+                    log::warn!("Unable to read filtered canvas: {:?}", e);
+                    return;
+                }
+            };
+
+        // Canvas image data has straight alpha, but filters operate on premultiplied alpha.
+        let mut rgba = image_data.data().0;
+        for pixel in rgba.chunks_exact_mut(4) {
+            let alpha = f32::from(pixel[3]) / 255.0;
+            for channel in &mut pixel[..3] {
+                *channel = (f32::from(*channel) * alpha) as u8;
+            }
+        }
+        filters::apply_filters(width, height, &mut rgba, &filters);
+        for pixel in rgba.chunks_exact_mut(4) {
+            let alpha = f32::from(pixel[3]) / 255.0;
+            if alpha > 0.0 {
+                for channel in &mut pixel[..3] {
+                    *channel = (f32::from(*channel) / alpha).min(255.0) as u8;
+                }
+            }
+        }
+
+        if let Ok(image_data) =
+            ImageData::new_with_u8_clamped_array_and_sh(Clamped(&mut rgba), width, height)
+        {
+            filtered_context
+                .put_image_data(&image_data, 0.0, 0.0)
+                .warn_on_error();
+        }
+
+        self.context.reset_transform().warn_on_error();
+        self.context
+            .draw_image_with_html_canvas_element(&filtered_canvas, 0.0, 0.0)
+            .warn_on_error();
+    }
+
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode) {
+        self.blend_modes.push(blend_mode);
+        self.push_render_target();
+    }
+
+    fn pop_blend_mode(&mut self) {
+        let blend_mode = self.blend_modes.pop().unwrap_or(swf::BlendMode::Normal);
+        let (blended_canvas, _blended_context) = self.pop_render_target();
+
+        self.context.reset_transform().warn_on_error();
+        self.context
+            .set_global_composite_operation(blend_mode_to_composite_operation(blend_mode))
+            .warn_on_error();
+        self.context
+            .draw_image_with_html_canvas_element(&blended_canvas, 0.0, 0.0)
+            .warn_on_error();
+        self.context
+            .set_global_composite_operation("source-over")
+            .warn_on_error();
+    }
+}
+
+/// Returns the canvas composite operation closest to the given blend mode.
+///
+/// Canvas has no equivalent of `Subtract` or `Invert`, so these are drawn normally.
+fn blend_mode_to_composite_operation(blend_mode: swf::BlendMode) -> &'static str {
+    use swf::BlendMode;
+    match blend_mode {
+        BlendMode::Multiply => "multiply",
+        BlendMode::Screen => "screen",
+        BlendMode::Lighten => "lighten",
+        BlendMode::Darken => "darken",
+        BlendMode::Difference => "difference",
+        BlendMode::Add => "lighter",
+        BlendMode::Alpha => "destination-in",
+        BlendMode::Erase => "destination-out",
+        BlendMode::Overlay => "overlay",
+        BlendMode::HardLight => "hard-light",
+        BlendMode::Normal | BlendMode::Layer | BlendMode::Subtract | BlendMode::Invert => {
+            "source-over"
+        }
+    }
 }
 
 #[allow(clippy::cognitive_complexity)]
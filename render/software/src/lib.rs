@@ -0,0 +1,699 @@
+//! A render backend that rasterizes on the CPU.
+//!
+//! This needs neither a GPU nor a browser, so it can be used to render frames on headless
+//! machines, such as for visual regression tests. Shapes are tessellated with the same
+//! tessellator as the GPU backends, and drawn without anti-aliasing.
+
+use ruffle_core::backend::render::{
+    decode_define_bits_jpeg, decode_define_bits_lossless, filters, glue_tables_to_jpeg,
+    srgb_to_linear, swf, unmultiply_alpha_rgba, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo,
+    Color, Letterbox, RenderBackend, ShapeHandle, Transform,
+};
+use ruffle_core::color_transform::ColorTransform;
+use ruffle_core::shape_utils::DistilledShape;
+use ruffle_core::swf::Matrix;
+use ruffle_render_common_tess::{
+    DrawType as TessDrawType, Gradient as TessGradient, GradientType, ShapeTessellator,
+};
+use std::ops::Range;
+
+mod blend;
+mod raster;
+
+use raster::{
+    premultiply, Affine, ColorAdjustments, GradientShape, GradientSpread, Paint, Rgba, StencilMode,
+    Target, Texture, GRADIENT_LUT_SIZE,
+};
+
+type Error = Box<dyn std::error::Error>;
+
+/// The vertices and indices of a unit square, used to draw bitmaps and rectangles.
+const QUAD_VERTICES: [[f32; 2]; 4] = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+const QUAD_INDICES: [u32; 6] = [0, 1, 2, 0, 2, 3];
+
+pub struct SoftwareRenderBackend {
+    shape_tessellator: ShapeTessellator,
+    meshes: Vec<Mesh>,
+    textures: Vec<(swf::CharacterId, Texture)>,
+
+    /// The target currently being drawn to.
+    target: Target,
+
+    /// The offscreen layers currently being drawn, from outermost to innermost.
+    layers: Vec<Layer>,
+
+    mask_state: MaskState,
+    num_masks: u32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum MaskState {
+    NoMask,
+    DrawMaskStencil,
+    DrawMaskedContent,
+    ClearMaskStencil,
+}
+
+/// An offscreen layer for a display object with bitmap filters or a blend mode.
+struct Layer {
+    /// The target to composite the layer onto once it is finished.
+    parent: Target,
+    filters: Vec<filters::Filter>,
+    blend_mode: swf::BlendMode,
+
+    /// The mask state to restore once the layer is finished.
+    mask_state: MaskState,
+    num_masks: u32,
+}
+
+struct Mesh {
+    draws: Vec<Draw>,
+}
+
+struct Draw {
+    vertices: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+    draw_type: DrawType,
+}
+
+enum DrawType {
+    /// Solid color fills, as runs of triangles in the index buffer sharing a color.
+    Color(Vec<(Color, Range<usize>)>),
+    Gradient(Box<Gradient>),
+    Bitmap(BitmapDraw),
+}
+
+struct Gradient {
+    matrix: Affine,
+    shape: GradientShape,
+    spread: GradientSpread,
+
+    /// The gradient colors with straight alpha, before any color transform.
+    lut: Vec<Rgba>,
+}
+
+struct BitmapDraw {
+    matrix: Affine,
+    handle: Option<BitmapHandle>,
+    is_smoothed: bool,
+    is_repeating: bool,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            shape_tessellator: ShapeTessellator::new(),
+            meshes: Vec::new(),
+            textures: Vec::new(),
+            target: Target::new(width, height),
+            layers: Vec::new(),
+            mask_state: MaskState::NoMask,
+            num_masks: 0,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.target.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.target.height
+    }
+
+    /// Returns the pixels of the last rendered frame as RGBA data with straight alpha.
+    pub fn capture_frame(&self) -> Vec<u8> {
+        let target = self
+            .layers
+            .first()
+            .map(|layer| &layer.parent)
+            .unwrap_or(&self.target);
+        let mut rgba = target.to_rgba8();
+        unmultiply_alpha_rgba(&mut rgba);
+        rgba
+    }
+
+    fn register_shape_internal(&mut self, shape: DistilledShape) -> Mesh {
+        let textures = &self.textures;
+        let lyon_mesh = self.shape_tessellator.tessellate_shape(shape, |id| {
+            textures
+                .iter()
+                .find(|(other_id, _tex)| *other_id == id)
+                .map(|tex| (tex.1.width, tex.1.height))
+        });
+
+        let mut draws = Vec::with_capacity(lyon_mesh.len());
+        for draw in lyon_mesh {
+            let vertices: Vec<[f32; 2]> = draw.vertices.iter().map(|v| v.position).collect();
+            let draw_type = match draw.draw_type {
+                TessDrawType::Color => {
+                    // Each triangle takes the color of its first vertex.
+                    let mut runs: Vec<(Color, Range<usize>)> = Vec::new();
+                    for (i, triangle) in draw.indices.chunks_exact(3).enumerate() {
+                        let color = draw
+                            .vertices
+                            .get(triangle[0] as usize)
+                            .map(|v| v.color)
+                            .unwrap_or(0);
+                        let color = Color {
+                            r: color as u8,
+                            g: (color >> 8) as u8,
+                            b: (color >> 16) as u8,
+                            a: (color >> 24) as u8,
+                        };
+                        match runs.last_mut() {
+                            Some((run_color, range)) if *run_color == color => {
+                                range.end = i * 3 + 3;
+                            }
+                            _ => runs.push((color, i * 3..i * 3 + 3)),
+                        }
+                    }
+                    DrawType::Color(runs)
+                }
+                TessDrawType::Gradient(gradient) => {
+                    DrawType::Gradient(Box::new(convert_gradient(gradient)))
+                }
+                TessDrawType::Bitmap(bitmap) => DrawType::Bitmap(BitmapDraw {
+                    matrix: Affine::from_gl_matrix(bitmap.matrix),
+                    handle: self
+                        .textures
+                        .iter()
+                        .position(|(id, _tex)| *id == bitmap.id)
+                        .map(BitmapHandle),
+                    is_smoothed: bitmap.is_smoothed,
+                    is_repeating: bitmap.is_repeating,
+                }),
+            };
+            draws.push(Draw {
+                vertices,
+                indices: draw.indices,
+                draw_type,
+            });
+        }
+
+        Mesh { draws }
+    }
+
+    fn register_bitmap(
+        &mut self,
+        id: swf::CharacterId,
+        bitmap: Bitmap,
+    ) -> Result<BitmapInfo, Error> {
+        let handle = BitmapHandle(self.textures.len());
+        let width = bitmap.width;
+        let height = bitmap.height;
+        self.textures.push((id, bitmap_to_texture(bitmap)));
+
+        Ok(BitmapInfo {
+            handle,
+            width: width as u16,
+            height: height as u16,
+        })
+    }
+
+    /// Returns how draws should use the stencil buffer in the current mask state.
+    fn stencil_mode(&self) -> StencilMode {
+        let num_masks = self.num_masks.min(255) as u8;
+        match self.mask_state {
+            MaskState::NoMask => StencilMode::None,
+            MaskState::DrawMaskStencil => StencilMode::Increment(num_masks.saturating_sub(1)),
+            MaskState::DrawMaskedContent => StencilMode::Test(num_masks),
+            MaskState::ClearMaskStencil => StencilMode::Decrement(num_masks),
+        }
+    }
+
+    /// Begins drawing to a new offscreen layer.
+    fn push_layer(&mut self, filters: Vec<filters::Filter>, blend_mode: swf::BlendMode) {
+        let target = Target::new(self.target.width, self.target.height);
+        let parent = std::mem::replace(&mut self.target, target);
+        self.layers.push(Layer {
+            parent,
+            filters,
+            blend_mode,
+            mask_state: self.mask_state,
+            num_masks: self.num_masks,
+        });
+
+        // Masks outside of the layer are applied when it is composited.
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+    }
+
+    /// Finishes drawing to the current offscreen layer, and composites it onto its parent.
+    fn pop_layer(&mut self) {
+        let layer = if let Some(layer) = self.layers.pop() {
+            layer
+        } else {
+            log::error!("Layer stack underflow");
+            return;
+        };
+
+        let mut target = std::mem::replace(&mut self.target, layer.parent);
+        self.mask_state = layer.mask_state;
+        self.num_masks = layer.num_masks;
+
+        if !layer.filters.is_empty() {
+            let mut rgba = target.to_rgba8();
+            filters::apply_filters(target.width, target.height, &mut rgba, &layer.filters);
+            target.set_rgba8(&rgba);
+        }
+
+        let stencil_mode = match self.stencil_mode() {
+            StencilMode::Test(value) => StencilMode::Test(value),
+            _ => StencilMode::None,
+        };
+        blend::composite(&mut self.target, &target, layer.blend_mode, stencil_mode);
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
+        self.target = Target::new(width, height);
+        self.layers.clear();
+    }
+
+    fn register_shape(&mut self, shape: DistilledShape) -> ShapeHandle {
+        let handle = ShapeHandle(self.meshes.len());
+        let mesh = self.register_shape_internal(shape);
+        self.meshes.push(mesh);
+        handle
+    }
+
+    fn replace_shape(&mut self, shape: DistilledShape, handle: ShapeHandle) {
+        let mesh = self.register_shape_internal(shape);
+        self.meshes[handle.0] = mesh;
+    }
+
+    fn register_glyph_shape(&mut self, glyph: &swf::Glyph) -> ShapeHandle {
+        let shape = ruffle_core::shape_utils::swf_glyph_to_shape(glyph);
+        let handle = ShapeHandle(self.meshes.len());
+        let mesh = self.register_shape_internal((&shape).into());
+        self.meshes.push(mesh);
+        handle
+    }
+
+    fn register_bitmap_jpeg(
+        &mut self,
+        id: swf::CharacterId,
+        data: &[u8],
+        jpeg_tables: Option<&[u8]>,
+    ) -> Result<BitmapInfo, Error> {
+        let data = glue_tables_to_jpeg(data, jpeg_tables);
+        self.register_bitmap_jpeg_2(id, &data[..])
+    }
+
+    fn register_bitmap_jpeg_2(
+        &mut self,
+        id: swf::CharacterId,
+        data: &[u8],
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap = decode_define_bits_jpeg(data, None)?;
+        self.register_bitmap(id, bitmap)
+    }
+
+    fn register_bitmap_jpeg_3(
+        &mut self,
+        id: swf::CharacterId,
+        jpeg_data: &[u8],
+        alpha_data: &[u8],
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap = decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
+        self.register_bitmap(id, bitmap)
+    }
+
+    fn register_bitmap_png(
+        &mut self,
+        swf_tag: &swf::DefineBitsLossless,
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap = decode_define_bits_lossless(swf_tag)?;
+        self.register_bitmap(swf_tag.id, bitmap)
+    }
+
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(self
+            .register_bitmap(
+                0,
+                Bitmap {
+                    width,
+                    height,
+                    data: BitmapFormat::Rgba(rgba),
+                },
+            )?
+            .handle)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        if let Some((_id, texture)) = self.textures.get_mut(handle.0) {
+            *texture = Texture {
+                width,
+                height,
+                rgba,
+            };
+            Ok(handle)
+        } else {
+            Err("update_texture: Bitmap not registered".into())
+        }
+    }
+
+    fn begin_frame(&mut self, clear: Color) {
+        if !self.layers.is_empty() {
+            let layer = self.layers.swap_remove(0);
+            self.target = layer.parent;
+            self.layers.clear();
+        }
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+        self.target.clear(color_to_rgba(&clear));
+    }
+
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform) {
+        let stencil_mode = self.stencil_mode();
+        if let Some((_id, texture)) = self.textures.get(bitmap.0) {
+            // Scale the quad to the bitmap's dimensions.
+            let matrix = transform.matrix
+                * Matrix {
+                    a: texture.width as f32,
+                    d: texture.height as f32,
+                    ..Default::default()
+                };
+            let paint = Paint::Bitmap {
+                screen_to_uv: match matrix_to_affine(&matrix).inverse() {
+                    Some(screen_to_uv) => screen_to_uv,
+                    None => return,
+                },
+                texture,
+                is_smoothed: true,
+                is_repeating: false,
+                color_adjustments: color_adjustments(&transform.color_transform),
+            };
+            self.target.draw_triangles(
+                &QUAD_VERTICES,
+                &QUAD_INDICES,
+                &matrix_to_affine(&matrix),
+                &paint,
+                stencil_mode,
+            );
+        }
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
+        let stencil_mode = self.stencil_mode();
+        let world = matrix_to_affine(&transform.matrix);
+        let screen_to_local = if let Some(screen_to_local) = world.inverse() {
+            screen_to_local
+        } else {
+            return;
+        };
+        let color_adjustments = color_adjustments(&transform.color_transform);
+
+        let mesh = if let Some(mesh) = self.meshes.get(shape.0) {
+            mesh
+        } else {
+            return;
+        };
+        let textures = &self.textures;
+        for draw in &mesh.draws {
+            match &draw.draw_type {
+                DrawType::Color(runs) => {
+                    for (color, range) in runs {
+                        let paint = Paint::Solid(color_adjustments.apply([
+                            f32::from(color.r) / 255.0,
+                            f32::from(color.g) / 255.0,
+                            f32::from(color.b) / 255.0,
+                            f32::from(color.a) / 255.0,
+                        ]));
+                        self.target.draw_triangles(
+                            &draw.vertices,
+                            &draw.indices[range.clone()],
+                            &world,
+                            &paint,
+                            stencil_mode,
+                        );
+                    }
+                }
+                DrawType::Gradient(gradient) => {
+                    let lut: Vec<Rgba> = gradient
+                        .lut
+                        .iter()
+                        .map(|color| color_adjustments.apply(*color))
+                        .collect();
+                    let paint = Paint::Gradient {
+                        screen_to_uv: screen_to_local.then(&gradient.matrix),
+                        shape: gradient.shape,
+                        spread: gradient.spread,
+                        lut: &lut,
+                    };
+                    self.target.draw_triangles(
+                        &draw.vertices,
+                        &draw.indices,
+                        &world,
+                        &paint,
+                        stencil_mode,
+                    );
+                }
+                DrawType::Bitmap(bitmap) => {
+                    let texture = match bitmap.handle.and_then(|h| textures.get(h.0)) {
+                        Some((_id, texture)) => texture,
+                        None => continue,
+                    };
+                    let paint = Paint::Bitmap {
+                        screen_to_uv: screen_to_local.then(&bitmap.matrix),
+                        texture,
+                        is_smoothed: bitmap.is_smoothed,
+                        is_repeating: bitmap.is_repeating,
+                        color_adjustments,
+                    };
+                    self.target.draw_triangles(
+                        &draw.vertices,
+                        &draw.indices,
+                        &world,
+                        &paint,
+                        stencil_mode,
+                    );
+                }
+            }
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: &Matrix) {
+        let stencil_mode = self.stencil_mode();
+        self.target.draw_triangles(
+            &QUAD_VERTICES,
+            &QUAD_INDICES,
+            &matrix_to_affine(matrix),
+            &Paint::Solid(color_to_rgba(&color)),
+            stencil_mode,
+        );
+    }
+
+    fn end_frame(&mut self) {}
+
+    fn draw_letterbox(&mut self, letterbox: Letterbox) {
+        let width = self.target.width;
+        let height = self.target.height;
+        match letterbox {
+            Letterbox::None => (),
+            Letterbox::Letterbox(margin_height) => {
+                let margin_height = margin_height as u32;
+                self.target.clear_rect(0, 0, width, margin_height);
+                self.target.clear_rect(
+                    0,
+                    height.saturating_sub(margin_height),
+                    width,
+                    margin_height,
+                );
+            }
+            Letterbox::Pillarbox(margin_width) => {
+                let margin_width = margin_width as u32;
+                self.target.clear_rect(0, 0, margin_width, height);
+                self.target
+                    .clear_rect(width.saturating_sub(margin_width), 0, margin_width, height);
+            }
+        }
+    }
+
+    fn push_mask(&mut self) {
+        assert!(
+            self.mask_state == MaskState::NoMask || self.mask_state == MaskState::DrawMaskedContent
+        );
+        self.num_masks += 1;
+        self.mask_state = MaskState::DrawMaskStencil;
+    }
+
+    fn activate_mask(&mut self) {
+        assert!(self.num_masks > 0 && self.mask_state == MaskState::DrawMaskStencil);
+        self.mask_state = MaskState::DrawMaskedContent;
+    }
+
+    fn deactivate_mask(&mut self) {
+        assert!(self.num_masks > 0 && self.mask_state == MaskState::DrawMaskedContent);
+        self.mask_state = MaskState::ClearMaskStencil;
+    }
+
+    fn pop_mask(&mut self) {
+        assert!(self.num_masks > 0 && self.mask_state == MaskState::ClearMaskStencil);
+        self.num_masks -= 1;
+        self.mask_state = if self.num_masks == 0 {
+            MaskState::NoMask
+        } else {
+            MaskState::DrawMaskedContent
+        };
+    }
+
+    fn push_filters(&mut self, filters: &[filters::Filter]) {
+        self.push_layer(filters.to_vec(), swf::BlendMode::Normal);
+    }
+
+    fn pop_filters(&mut self) {
+        self.pop_layer();
+    }
+
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode) {
+        self.push_layer(Vec::new(), blend_mode);
+    }
+
+    fn pop_blend_mode(&mut self) {
+        self.pop_layer();
+    }
+}
+
+fn matrix_to_affine(matrix: &Matrix) -> Affine {
+    Affine {
+        a: matrix.a,
+        b: matrix.b,
+        c: matrix.c,
+        d: matrix.d,
+        tx: matrix.tx.to_pixels() as f32,
+        ty: matrix.ty.to_pixels() as f32,
+    }
+}
+
+fn color_adjustments(color_transform: &ColorTransform) -> ColorAdjustments {
+    ColorAdjustments {
+        mult: [
+            color_transform.r_mult,
+            color_transform.g_mult,
+            color_transform.b_mult,
+            color_transform.a_mult,
+        ],
+        add: [
+            color_transform.r_add,
+            color_transform.g_add,
+            color_transform.b_add,
+            color_transform.a_add,
+        ],
+    }
+}
+
+/// Converts a color to premultiplied RGBA.
+fn color_to_rgba(color: &Color) -> Rgba {
+    premultiply([
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+        f32::from(color.a) / 255.0,
+    ])
+}
+
+fn bitmap_to_texture(bitmap: Bitmap) -> Texture {
+    let rgba = match bitmap.data {
+        BitmapFormat::Rgba(rgba) => rgba,
+        BitmapFormat::Rgb(rgb) => {
+            let mut rgba = Vec::with_capacity(rgb.len() / 3 * 4);
+            for pixel in rgb.chunks_exact(3) {
+                rgba.extend_from_slice(pixel);
+                rgba.push(255);
+            }
+            rgba
+        }
+    };
+    Texture {
+        width: bitmap.width,
+        height: bitmap.height,
+        rgba,
+    }
+}
+
+/// Builds the lookup table for a gradient, matching the GPU backends' gradient shaders.
+fn convert_gradient(gradient: TessGradient) -> Gradient {
+    let num_colors = (gradient.num_colors as usize)
+        .min(gradient.ratios.len())
+        .min(gradient.colors.len());
+    let ratios = &gradient.ratios[..num_colors];
+    let is_linear_rgb = gradient.interpolation == swf::GradientInterpolation::LinearRGB;
+    let colors: Vec<Rgba> = gradient.colors[..num_colors]
+        .iter()
+        .map(|color| {
+            if is_linear_rgb {
+                srgb_to_linear(*color)
+            } else {
+                *color
+            }
+        })
+        .collect();
+
+    let mut lut = Vec::with_capacity(GRADIENT_LUT_SIZE);
+    for i in 0..GRADIENT_LUT_SIZE {
+        let t = i as f32 / (GRADIENT_LUT_SIZE - 1) as f32;
+        let color = match ratios.iter().position(|ratio| t <= *ratio) {
+            _ if colors.is_empty() => [0.0; 4],
+            Some(0) => colors[0],
+            Some(j) => {
+                let a = (t - ratios[j - 1]) / (ratios[j] - ratios[j - 1]);
+                let mut color = [0.0; 4];
+                for (c, out) in color.iter_mut().enumerate() {
+                    *out = colors[j - 1][c] + (colors[j][c] - colors[j - 1][c]) * a;
+                }
+                color
+            }
+            None => colors[colors.len() - 1],
+        };
+        lut.push(if is_linear_rgb {
+            linear_to_srgb(color)
+        } else {
+            color
+        });
+    }
+
+    Gradient {
+        matrix: Affine::from_gl_matrix(gradient.matrix),
+        shape: match gradient.gradient_type {
+            GradientType::Linear => GradientShape::Linear,
+            GradientType::Radial => GradientShape::Radial,
+            GradientType::Focal => GradientShape::Focal(gradient.focal_point),
+        },
+        spread: match gradient.repeat_mode {
+            swf::GradientSpread::Pad => GradientSpread::Pad,
+            swf::GradientSpread::Repeat => GradientSpread::Repeat,
+            swf::GradientSpread::Reflect => GradientSpread::Reflect,
+        },
+        lut,
+    }
+}
+
+/// Converts an RGBA color from linear color space to sRGB space.
+fn linear_to_srgb(color: Rgba) -> Rgba {
+    fn to_srgb_channel(n: f32) -> f32 {
+        if n <= 0.003_130_8 {
+            n * 12.92
+        } else {
+            1.055 * n.powf(1.0 / 2.4) - 0.055
+        }
+    }
+    [
+        to_srgb_channel(color[0]),
+        to_srgb_channel(color[1]),
+        to_srgb_channel(color[2]),
+        color[3],
+    ]
+}
@@ -0,0 +1,553 @@
+//! Triangle rasterization into a premultiplied RGBA target.
+
+/// A premultiplied RGBA color, with each component in the range `0.0..=1.0`.
+pub type Rgba = [f32; 4];
+
+/// The number of entries in a gradient lookup table.
+pub const GRADIENT_LUT_SIZE: usize = 256;
+
+/// A 2D affine transform.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Affine {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub tx: f32,
+    pub ty: f32,
+}
+
+impl Affine {
+    /// Converts a column-major 3x3 matrix, as produced by the tessellator.
+    pub fn from_gl_matrix(m: [[f32; 3]; 3]) -> Self {
+        Self {
+            a: m[0][0],
+            b: m[0][1],
+            c: m[1][0],
+            d: m[1][1],
+            tx: m[2][0],
+            ty: m[2][1],
+        }
+    }
+
+    pub fn transform_point(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.a * x + self.c * y + self.tx,
+            self.b * x + self.d * y + self.ty,
+        )
+    }
+
+    /// Returns the transform that applies `self` followed by `other`.
+    pub fn then(&self, other: &Affine) -> Affine {
+        Affine {
+            a: other.a * self.a + other.c * self.b,
+            b: other.b * self.a + other.d * self.b,
+            c: other.a * self.c + other.c * self.d,
+            d: other.b * self.c + other.d * self.d,
+            tx: other.a * self.tx + other.c * self.ty + other.tx,
+            ty: other.b * self.tx + other.d * self.ty + other.ty,
+        }
+    }
+
+    /// Returns the inverse of this transform, or `None` if it is degenerate.
+    pub fn inverse(&self) -> Option<Affine> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        Some(Affine {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            tx: (self.c * self.ty - self.d * self.tx) / det,
+            ty: (self.b * self.tx - self.a * self.ty) / det,
+        })
+    }
+}
+
+/// A color transform, applied to colors with straight alpha.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ColorAdjustments {
+    pub mult: [f32; 4],
+    pub add: [f32; 4],
+}
+
+impl ColorAdjustments {
+    pub const IDENTITY: ColorAdjustments = ColorAdjustments {
+        mult: [1.0; 4],
+        add: [0.0; 4],
+    };
+
+    /// Transforms a color with straight alpha, returning the premultiplied result.
+    pub fn apply(&self, color: Rgba) -> Rgba {
+        let mut out = [0.0; 4];
+        for i in 0..4 {
+            out[i] = (color[i] * self.mult[i] + self.add[i]).max(0.0).min(1.0);
+        }
+        premultiply(out)
+    }
+}
+
+pub fn premultiply(color: Rgba) -> Rgba {
+    [
+        color[0] * color[3],
+        color[1] * color[3],
+        color[2] * color[3],
+        color[3],
+    ]
+}
+
+pub fn unmultiply(color: Rgba) -> Rgba {
+    if color[3] > 0.0 {
+        [
+            (color[0] / color[3]).min(1.0),
+            (color[1] / color[3]).min(1.0),
+            (color[2] / color[3]).min(1.0),
+            color[3],
+        ]
+    } else {
+        [0.0; 4]
+    }
+}
+
+/// Bitmap pixel data with premultiplied alpha.
+#[derive(Clone, Debug)]
+pub struct Texture {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl Texture {
+    fn texel(&self, x: i64, y: i64, is_repeating: bool) -> Rgba {
+        let width = i64::from(self.width);
+        let height = i64::from(self.height);
+        let (x, y) = if is_repeating {
+            (x.rem_euclid(width), y.rem_euclid(height))
+        } else {
+            (x.max(0).min(width - 1), y.max(0).min(height - 1))
+        };
+        let i = ((y * width + x) * 4) as usize;
+        [
+            f32::from(self.rgba[i]) / 255.0,
+            f32::from(self.rgba[i + 1]) / 255.0,
+            f32::from(self.rgba[i + 2]) / 255.0,
+            f32::from(self.rgba[i + 3]) / 255.0,
+        ]
+    }
+
+    /// Samples the texture at normalized coordinates, returning a premultiplied color.
+    fn sample(&self, u: f32, v: f32, is_smoothed: bool, is_repeating: bool) -> Rgba {
+        if self.width == 0 || self.height == 0 {
+            return [0.0; 4];
+        }
+
+        let x = u * self.width as f32;
+        let y = v * self.height as f32;
+        if !is_smoothed {
+            return self.texel(x.floor() as i64, y.floor() as i64, is_repeating);
+        }
+
+        let x = x - 0.5;
+        let y = y - 0.5;
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let fx = x - x0;
+        let fy = y - y0;
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let top_left = self.texel(x0, y0, is_repeating);
+        let top_right = self.texel(x0 + 1, y0, is_repeating);
+        let bottom_left = self.texel(x0, y0 + 1, is_repeating);
+        let bottom_right = self.texel(x0 + 1, y0 + 1, is_repeating);
+        let mut out = [0.0; 4];
+        for i in 0..4 {
+            let top = top_left[i] + (top_right[i] - top_left[i]) * fx;
+            let bottom = bottom_left[i] + (bottom_right[i] - bottom_left[i]) * fx;
+            out[i] = top + (bottom - top) * fy;
+        }
+        out
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GradientShape {
+    Linear,
+    Radial,
+    Focal(f32),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GradientSpread {
+    Pad,
+    Repeat,
+    Reflect,
+}
+
+/// How each pixel of a triangle is colored.
+pub enum Paint<'a> {
+    /// A single premultiplied color.
+    Solid(Rgba),
+
+    /// A gradient, looked up from a table of premultiplied colors.
+    Gradient {
+        screen_to_uv: Affine,
+        shape: GradientShape,
+        spread: GradientSpread,
+        lut: &'a [Rgba],
+    },
+
+    /// A bitmap fill.
+    Bitmap {
+        screen_to_uv: Affine,
+        texture: &'a Texture,
+        is_smoothed: bool,
+        is_repeating: bool,
+        color_adjustments: ColorAdjustments,
+    },
+}
+
+impl<'a> Paint<'a> {
+    /// Returns the premultiplied color of the pixel centered at the given point.
+    #[allow(clippy::many_single_char_names)]
+    fn shade(&self, x: f32, y: f32) -> Rgba {
+        match self {
+            Paint::Solid(color) => *color,
+            Paint::Gradient {
+                screen_to_uv,
+                shape,
+                spread,
+                lut,
+            } => {
+                let (u, v) = screen_to_uv.transform_point(x, y);
+                let t = match shape {
+                    GradientShape::Linear => u,
+                    GradientShape::Radial => {
+                        let (u, v) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                        (u * u + v * v).sqrt()
+                    }
+                    GradientShape::Focal(focal_point) => {
+                        let (u, v) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                        let (dx, dy) = (focal_point - u, -v);
+                        let l = (dx * dx + dy * dy).sqrt();
+                        if l > 0.0 {
+                            let (dx, dy) = (dx / l, dy / l);
+                            l / ((1.0 - focal_point * focal_point * dy * dy).sqrt()
+                                + focal_point * dx)
+                        } else {
+                            0.0
+                        }
+                    }
+                };
+                let t = match spread {
+                    GradientSpread::Pad => t.max(0.0).min(1.0),
+                    GradientSpread::Repeat => t - t.floor(),
+                    GradientSpread::Reflect => {
+                        let t = t.abs();
+                        if (t as i64) % 2 == 0 {
+                            t.fract()
+                        } else {
+                            1.0 - t.fract()
+                        }
+                    }
+                };
+                if lut.is_empty() || !t.is_finite() {
+                    return [0.0; 4];
+                }
+                let index = (t * (lut.len() - 1) as f32).round() as usize;
+                lut[index.min(lut.len() - 1)]
+            }
+            Paint::Bitmap {
+                screen_to_uv,
+                texture,
+                is_smoothed,
+                is_repeating,
+                color_adjustments,
+            } => {
+                let (u, v) = screen_to_uv.transform_point(x, y);
+                let color = texture.sample(u, v, *is_smoothed, *is_repeating);
+                if *color_adjustments == ColorAdjustments::IDENTITY {
+                    color
+                } else if color[3] > 0.0 {
+                    color_adjustments.apply(unmultiply(color))
+                } else {
+                    color
+                }
+            }
+        }
+    }
+}
+
+/// How a draw interacts with the stencil buffer used for masking.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StencilMode {
+    /// Draw everywhere.
+    None,
+
+    /// Only draw pixels where the stencil equals the given value.
+    Test(u8),
+
+    /// Draw no color, and increment the stencil where it equals the given value.
+    Increment(u8),
+
+    /// Draw no color, and decrement the stencil where it equals the given value.
+    Decrement(u8),
+}
+
+/// An RGBA framebuffer with a stencil buffer.
+pub struct Target {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<Rgba>,
+    pub stencil: Vec<u8>,
+}
+
+impl Target {
+    pub fn new(width: u32, height: u32) -> Self {
+        let len = width as usize * height as usize;
+        Self {
+            width,
+            height,
+            pixels: vec![[0.0; 4]; len],
+            stencil: vec![0; len],
+        }
+    }
+
+    pub fn clear(&mut self, color: Rgba) {
+        for pixel in &mut self.pixels {
+            *pixel = color;
+        }
+        for stencil in &mut self.stencil {
+            *stencil = 0;
+        }
+    }
+
+    /// Clears a rectangle of pixels to transparent black.
+    pub fn clear_rect(&mut self, x: u32, y: u32, width: u32, height: u32) {
+        let x_end = (x + width).min(self.width);
+        let y_end = (y + height).min(self.height);
+        for y in y..y_end {
+            for x in x..x_end {
+                self.pixels[(y * self.width + x) as usize] = [0.0; 4];
+            }
+        }
+    }
+
+    /// Draws a triangle mesh transformed by `world`, blending it with the normal blend mode.
+    pub fn draw_triangles(
+        &mut self,
+        vertices: &[[f32; 2]],
+        indices: &[u32],
+        world: &Affine,
+        paint: &Paint<'_>,
+        stencil_mode: StencilMode,
+    ) {
+        let points: Vec<(f32, f32)> = vertices
+            .iter()
+            .map(|v| world.transform_point(v[0], v[1]))
+            .collect();
+
+        for triangle in indices.chunks_exact(3) {
+            let (a, b, c) = match (
+                points.get(triangle[0] as usize),
+                points.get(triangle[1] as usize),
+                points.get(triangle[2] as usize),
+            ) {
+                (Some(a), Some(b), Some(c)) => (*a, *b, *c),
+                _ => continue,
+            };
+
+            let width = self.width;
+            let pixels = &mut self.pixels;
+            let stencil = &mut self.stencil;
+            rasterize_triangle(self.width, self.height, a, b, c, |x, y| {
+                let i = (y * width + x) as usize;
+                match stencil_mode {
+                    StencilMode::None => {}
+                    StencilMode::Test(value) => {
+                        if stencil[i] != value {
+                            return;
+                        }
+                    }
+                    StencilMode::Increment(value) => {
+                        if stencil[i] == value {
+                            stencil[i] = value.saturating_add(1);
+                        }
+                        return;
+                    }
+                    StencilMode::Decrement(value) => {
+                        if stencil[i] == value {
+                            stencil[i] = value.saturating_sub(1);
+                        }
+                        return;
+                    }
+                }
+                let src = paint.shade(x as f32 + 0.5, y as f32 + 0.5);
+                let dst = &mut pixels[i];
+                let inv_alpha = 1.0 - src[3];
+                for c in 0..4 {
+                    dst[c] = src[c] + dst[c] * inv_alpha;
+                }
+            });
+        }
+    }
+
+    /// Converts the pixels to premultiplied 8-bit RGBA.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.pixels.len() * 4);
+        for pixel in &self.pixels {
+            for c in pixel {
+                out.push((c.max(0.0).min(1.0) * 255.0).round() as u8);
+            }
+        }
+        out
+    }
+
+    /// Replaces the pixels with premultiplied 8-bit RGBA data.
+    pub fn set_rgba8(&mut self, rgba: &[u8]) {
+        for (pixel, rgba) in self.pixels.iter_mut().zip(rgba.chunks_exact(4)) {
+            for c in 0..4 {
+                pixel[c] = f32::from(rgba[c]) / 255.0;
+            }
+        }
+    }
+}
+
+/// Calls `f` for every pixel whose center lies inside the triangle `a`, `b`, `c`.
+///
+/// Uses the top-left fill rule, so pixels on edges shared by adjacent triangles are only
+/// drawn once.
+#[allow(clippy::many_single_char_names)]
+fn rasterize_triangle(
+    width: u32,
+    height: u32,
+    a: (f32, f32),
+    b: (f32, f32),
+    c: (f32, f32),
+    mut f: impl FnMut(u32, u32),
+) {
+    fn edge(from: (f32, f32), to: (f32, f32), p: (f32, f32)) -> f32 {
+        (to.0 - from.0) * (p.1 - from.1) - (to.1 - from.1) * (p.0 - from.0)
+    }
+
+    // Wind the triangle consistently so that the inside has positive edge values.
+    let area = edge(a, b, c);
+    if area == 0.0 || !area.is_finite() {
+        return;
+    }
+    let (b, c) = if area < 0.0 { (c, b) } else { (b, c) };
+
+    // In screen space (y down) with this winding, a top edge is horizontal and runs
+    // left-to-right, and a left edge runs upwards.
+    fn is_top_left(from: (f32, f32), to: (f32, f32)) -> bool {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        (dy == 0.0 && dx > 0.0) || dy < 0.0
+    }
+    let edges = [(b, c), (c, a), (a, b)];
+    let biases: Vec<f32> = edges
+        .iter()
+        .map(|&(from, to)| if is_top_left(from, to) { 0.0 } else { -1.0 })
+        .collect();
+
+    let min_x = a.0.min(b.0).min(c.0).floor().max(0.0) as u32;
+    let min_y = a.1.min(b.1).min(c.1).floor().max(0.0) as u32;
+    let max_x = (a.0.max(b.0).max(c.0).ceil().min(width as f32)) as u32;
+    let max_y = (a.1.max(b.1).max(c.1).ceil().min(height as f32)) as u32;
+
+    for y in min_y..max_y {
+        let py = y as f32 + 0.5;
+        for x in min_x..max_x {
+            let p = (x as f32 + 0.5, py);
+            let inside = edges.iter().zip(&biases).all(|(&(from, to), &bias)| {
+                let w = edge(from, to, p);
+                w > 0.0 || (w == 0.0 && bias == 0.0)
+            });
+            if inside {
+                f(x, y);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDENTITY: Affine = Affine {
+        a: 1.0,
+        b: 0.0,
+        c: 0.0,
+        d: 1.0,
+        tx: 0.0,
+        ty: 0.0,
+    };
+
+    fn coverage(vertices: &[[f32; 2]], indices: &[u32]) -> Vec<Rgba> {
+        let mut target = Target::new(4, 4);
+        target.draw_triangles(
+            vertices,
+            indices,
+            &IDENTITY,
+            &Paint::Solid([0.5, 0.0, 0.0, 0.5]),
+            StencilMode::None,
+        );
+        target.pixels
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn shared_edges_are_drawn_once() {
+        // A 2x2 square split along its diagonal, in both windings.
+        let vertices = [[1.0, 1.0], [3.0, 1.0], [3.0, 3.0], [1.0, 3.0]];
+        for indices in &[[0, 1, 2, 0, 2, 3], [0, 2, 1, 0, 3, 2]] {
+            let pixels = coverage(&vertices, indices);
+            for y in 0..4 {
+                for x in 0..4 {
+                    let expected = if (1..3).contains(&x) && (1..3).contains(&y) {
+                        [0.5, 0.0, 0.0, 0.5]
+                    } else {
+                        [0.0; 4]
+                    };
+                    assert_eq!(pixels[y * 4 + x], expected, "pixel ({}, {})", x, y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn affine_inverse() {
+        let transform = Affine {
+            a: 2.0,
+            b: 0.5,
+            c: -1.0,
+            d: 3.0,
+            tx: 10.0,
+            ty: -4.0,
+        };
+        let inverse = transform.inverse().unwrap();
+        let (x, y) = transform.transform_point(3.0, 7.0);
+        let (x, y) = inverse.transform_point(x, y);
+        assert!((x - 3.0).abs() < 1e-4 && (y - 7.0).abs() < 1e-4);
+        assert!(transform.then(&inverse).transform_point(1.0, 2.0).0 - 1.0 < 1e-4);
+    }
+
+    #[test]
+    fn masks_use_stencil() {
+        let mut target = Target::new(4, 1);
+        let mask = [[0.0, 0.0], [2.0, 0.0], [2.0, 1.0], [0.0, 1.0]];
+        let content = [[1.0, 0.0], [4.0, 0.0], [4.0, 1.0], [1.0, 1.0]];
+        let indices = [0, 1, 2, 0, 2, 3];
+        let paint = Paint::Solid([1.0, 1.0, 1.0, 1.0]);
+        target.draw_triangles(
+            &mask,
+            &indices,
+            &IDENTITY,
+            &paint,
+            StencilMode::Increment(0),
+        );
+        target.draw_triangles(&content, &indices, &IDENTITY, &paint, StencilMode::Test(1));
+        let alphas: Vec<f32> = target.pixels.iter().map(|p| p[3]).collect();
+        assert_eq!(alphas, vec![0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(target.stencil, vec![1, 1, 0, 0]);
+    }
+}
@@ -0,0 +1,34 @@
+#version 100
+precision mediump float;
+
+uniform mat4 view_matrix;
+uniform mat4 world_matrix;
+uniform vec4 mult_color;
+uniform vec4 add_color;
+uniform mat3 u_matrix;
+
+uniform sampler2D u_texture;
+// The distance between samples, in texture coordinates.
+uniform vec2 u_step;
+// The number of samples on each side of the center.
+uniform int u_radius;
+
+varying vec2 frag_uv;
+
+// The largest radius of a box blur, for a blur of 255 pixels.
+const int MAX_RADIUS = 127;
+
+// One pass of a box blur along a single axis.
+void main() {
+    vec4 sum = vec4(0.0);
+    // Loops must have a constant length in GLSL ES 1.0.
+    for (int i = -MAX_RADIUS; i <= MAX_RADIUS; i++) {
+        if (i >= -u_radius && i <= u_radius) {
+            vec2 uv = frag_uv + u_step * float(i);
+            // Areas outside of the texture are transparent.
+            vec2 inside = step(vec2(0.0), uv) * step(uv, vec2(1.0));
+            sum += texture2D(u_texture, uv) * inside.x * inside.y;
+        }
+    }
+    gl_FragColor = sum / float(2 * u_radius + 1);
+}
@@ -0,0 +1,23 @@
+#version 100
+precision mediump float;
+
+uniform mat4 view_matrix;
+uniform mat4 world_matrix;
+uniform vec4 mult_color;
+uniform vec4 add_color;
+uniform mat3 u_matrix;
+
+uniform sampler2D u_texture;
+uniform mat4 u_color_matrix;
+// The offsets added to each component, between 0.0 and 1.0.
+uniform vec4 u_offset;
+
+varying vec2 frag_uv;
+
+void main() {
+    vec4 color = texture2D(u_texture, frag_uv);
+    // The matrix applies to unmultiplied colors. Transparent pixels have a black color.
+    vec4 src = vec4(min(color.rgb / max(color.a, 0.0001), vec3(1.0)), color.a);
+    vec4 result = clamp(u_color_matrix * src + u_offset, 0.0, 1.0);
+    gl_FragColor = vec4(result.rgb * result.a, result.a);
+}
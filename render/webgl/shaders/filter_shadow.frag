@@ -0,0 +1,56 @@
+#version 100
+precision mediump float;
+
+uniform mat4 view_matrix;
+uniform mat4 world_matrix;
+uniform vec4 mult_color;
+uniform vec4 add_color;
+uniform mat3 u_matrix;
+
+uniform sampler2D u_texture;
+uniform sampler2D u_blurred;
+// The premultiplied color of a glow or drop shadow, or of the highlight of a bevel.
+uniform vec4 u_color;
+// The premultiplied color of the shadow of a bevel.
+uniform vec4 u_shadow_color;
+// The offset of the shadow, in texture coordinates.
+uniform vec2 u_shadow_offset;
+uniform float u_strength;
+// The inner, knockout, bevel and on top flags, which are either 0.0 or 1.0.
+uniform vec4 u_flags;
+
+varying vec2 frag_uv;
+
+// Applies a glow, drop shadow or bevel, given the object and a blurred copy of it.
+void main() {
+    float inner = u_flags.x;
+    float knockout = u_flags.y;
+    float bevel = u_flags.z;
+    float on_top = u_flags.w;
+
+    vec4 src = texture2D(u_texture, frag_uv);
+
+    // The blurred object behind and ahead of this pixel, in the direction of the light.
+    // Areas outside of the texture are transparent.
+    vec2 behind_uv = frag_uv - u_shadow_offset;
+    vec2 ahead_uv = frag_uv + u_shadow_offset;
+    vec2 behind_inside = step(vec2(0.0), behind_uv) * step(behind_uv, vec2(1.0));
+    vec2 ahead_inside = step(vec2(0.0), ahead_uv) * step(ahead_uv, vec2(1.0));
+    float behind = texture2D(u_blurred, behind_uv).a * behind_inside.x * behind_inside.y;
+    float ahead = texture2D(u_blurred, ahead_uv).a * ahead_inside.x * ahead_inside.y;
+
+    // Glows and drop shadows are cast by the object, or by the area outside of it if inner.
+    float shadow_amount = clamp(mix(behind, 1.0 - behind, inner) * u_strength, 0.0, 1.0);
+    // Bevels are highlighted on the edges facing the light, and shaded on the opposite edges.
+    float bevel_amount = clamp((ahead - behind) * u_strength, -1.0, 1.0);
+    vec4 bevel_color = max(bevel_amount, 0.0) * u_color + max(-bevel_amount, 0.0) * u_shadow_color;
+    vec4 effect = mix(shadow_amount * u_color, bevel_color, bevel);
+
+    // The effect only covers the inside or the outside of the object, unless it is a bevel on top.
+    float coverage = mix(1.0 - src.a, src.a, inner);
+    effect *= mix(coverage, 1.0, bevel * on_top);
+
+    // Outer glows and drop shadows are drawn behind the object, and everything else in front of it.
+    float behind_object = (1.0 - inner) * (1.0 - bevel);
+    gl_FragColor = effect + src * (1.0 - knockout) * mix(1.0 - effect.a, 1.0, behind_object);
+}
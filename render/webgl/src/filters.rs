@@ -0,0 +1,551 @@
+//! Bitmap filters applied on the GPU, as full-screen passes over offscreen textures.
+//!
+//! Filters without a GPU implementation are applied in software instead, by reading
+//! back the offscreen texture.
+
+use crate::{Error, MaskState, ShaderProgram, ShaderUniform, Texture, WebGlRenderBackend};
+use ruffle_core::backend::render::filters::{blur_radius, color_to_premultiplied, offset, Filter};
+use ruffle_core::backend::render::{Bitmap, BitmapFormat, BitmapHandle, RenderBackend, Transform};
+use ruffle_core::swf::{self, Matrix, Twips};
+use ruffle_web_common::JsResult;
+use web_sys::{WebGlFramebuffer, WebGlRenderbuffer, WebGlRenderingContext as Gl, WebGlTexture};
+
+/// The largest radius supported by the blur shader, which is that of a blur of 255 pixels.
+const MAX_BLUR_RADIUS: i32 = 127;
+
+/// Returns whether all of the given filters can be applied on the GPU.
+pub fn is_supported(filters: &[Filter]) -> bool {
+    filters.iter().all(|filter| {
+        matches!(
+            filter,
+            Filter::Swf(swf::Filter::BlurFilter(_))
+                | Filter::Swf(swf::Filter::ColorMatrixFilter(_))
+                | Filter::Swf(swf::Filter::DropShadowFilter(_))
+                | Filter::Swf(swf::Filter::GlowFilter(_))
+                | Filter::Swf(swf::Filter::BevelFilter(_))
+        )
+    })
+}
+
+/// A texture the size of the viewport, attached to a framebuffer so that it can be drawn to.
+pub struct RenderTarget {
+    framebuffer: WebGlFramebuffer,
+    texture: WebGlTexture,
+}
+
+/// Content drawn offscreen, so that its filters can be applied before it is drawn.
+pub struct FilterTarget {
+    render_target: RenderTarget,
+    stencil_renderbuffer: WebGlRenderbuffer,
+    filters: Vec<Filter>,
+
+    /// The masks outside of the filtered content, which are restored when it is drawn.
+    mask_state: MaskState,
+    num_masks: u32,
+}
+
+/// The parameters of a glow, drop shadow or bevel.
+struct ShadowParams {
+    color: [f32; 4],
+    shadow_color: [f32; 4],
+    angle: f64,
+    distance: f64,
+    strength: f32,
+    inner: bool,
+    knockout: bool,
+    bevel: bool,
+    on_top: bool,
+}
+
+impl WebGlRenderBackend {
+    /// Begins drawing content to a new offscreen target, to which the filters are
+    /// applied once it is popped.
+    pub(crate) fn push_filter_target(
+        &mut self,
+        filters: Vec<Filter>,
+    ) -> Result<FilterTarget, Error> {
+        let render_target = self.create_render_target()?;
+        let gl = &self.gl;
+
+        // Masks can be used inside of filtered content.
+        let stencil_renderbuffer = gl
+            .create_renderbuffer()
+            .ok_or("Unable to create renderbuffer")?;
+        gl.bind_renderbuffer(Gl::RENDERBUFFER, Some(&stencil_renderbuffer));
+        gl.renderbuffer_storage(
+            Gl::RENDERBUFFER,
+            Gl::STENCIL_INDEX8,
+            self.viewport_width as i32,
+            self.viewport_height as i32,
+        );
+        gl.framebuffer_renderbuffer(
+            Gl::FRAMEBUFFER,
+            Gl::STENCIL_ATTACHMENT,
+            Gl::RENDERBUFFER,
+            Some(&stencil_renderbuffer),
+        );
+        gl.bind_renderbuffer(Gl::RENDERBUFFER, None);
+
+        gl.color_mask(true, true, true, true);
+        gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        gl.stencil_mask(0xff);
+        gl.clear(Gl::COLOR_BUFFER_BIT | Gl::STENCIL_BUFFER_BIT);
+
+        // Masks outside of the filtered content are applied when the result is drawn.
+        let filter_target = FilterTarget {
+            render_target,
+            stencil_renderbuffer,
+            filters,
+            mask_state: self.mask_state,
+            num_masks: self.num_masks,
+        };
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+        self.mask_state_dirty = true;
+        Ok(filter_target)
+    }
+
+    /// Applies the filters to the content of an offscreen target, and draws the result
+    /// onto the previous target.
+    pub(crate) fn pop_filter_target(&mut self, filter_target: FilterTarget) -> Result<(), Error> {
+        let FilterTarget {
+            render_target,
+            stencil_renderbuffer,
+            filters,
+            mask_state,
+            num_masks,
+        } = filter_target;
+        self.gl.delete_renderbuffer(Some(&stencil_renderbuffer));
+        self.mask_state = mask_state;
+        self.num_masks = num_masks;
+        self.mask_state_dirty = true;
+
+        if is_supported(&filters) {
+            let filtered = self.apply_filters(render_target, &filters);
+            self.bind_current_framebuffer();
+            let RenderTarget {
+                framebuffer,
+                texture,
+            } = filtered?;
+            self.gl.delete_framebuffer(Some(&framebuffer));
+
+            // Offscreen textures are stored from the bottom up, so they are flipped vertically.
+            let transform = Transform {
+                matrix: Matrix {
+                    d: -1.0,
+                    ty: Twips::from_pixels(self.viewport_height.into()),
+                    ..Matrix::identity()
+                },
+                ..Default::default()
+            };
+            self.textures.push((
+                0,
+                Texture {
+                    width: self.viewport_width as u32,
+                    height: self.viewport_height as u32,
+                    texture,
+                },
+            ));
+            self.render_bitmap(BitmapHandle(self.textures.len() - 1), &transform);
+        } else {
+            let width = self.viewport_width as u32;
+            let height = self.viewport_height as u32;
+            let rgba = self.read_render_target(&render_target, width, height);
+            self.delete_render_target(render_target);
+            self.bind_current_framebuffer();
+
+            // Both the framebuffer and the bitmap use premultiplied alpha.
+            let mut rgba = rgba?;
+            ruffle_core::backend::render::filters::apply_filters(
+                width, height, &mut rgba, &filters,
+            );
+            let bitmap = Bitmap {
+                width,
+                height,
+                data: BitmapFormat::Rgba(rgba),
+            };
+            let handle = self.register_bitmap(0, bitmap)?.handle;
+            self.render_bitmap(handle, &Transform::default());
+        }
+
+        if let Some((_id, texture)) = self.textures.pop() {
+            self.gl.delete_texture(Some(&texture.texture));
+        }
+        Ok(())
+    }
+
+    /// Deletes an offscreen target without drawing it.
+    pub(crate) fn delete_filter_target(&self, filter_target: FilterTarget) {
+        self.gl
+            .delete_renderbuffer(Some(&filter_target.stencil_renderbuffer));
+        self.delete_render_target(filter_target.render_target);
+    }
+
+    /// Binds the framebuffer that content is currently drawn to.
+    pub(crate) fn bind_current_framebuffer(&self) {
+        let framebuffer =
+            if let Some(filter_target) = self.filter_targets.iter().rev().flatten().next() {
+                Some(&filter_target.render_target.framebuffer)
+            } else {
+                self.msaa_buffers
+                    .as_ref()
+                    .map(|msaa_buffers| &msaa_buffers.render_framebuffer)
+            };
+        self.gl.bind_framebuffer(Gl::FRAMEBUFFER, framebuffer);
+    }
+
+    /// Reads back the contents of a render target as premultiplied RGBA, from the top down.
+    fn read_render_target(
+        &self,
+        render_target: &RenderTarget,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<u8>, Error> {
+        let mut rgba = vec![0; (width * height * 4) as usize];
+        self.gl
+            .bind_framebuffer(Gl::FRAMEBUFFER, Some(&render_target.framebuffer));
+        self.gl
+            .read_pixels_with_opt_u8_array(
+                0,
+                0,
+                width as i32,
+                height as i32,
+                Gl::RGBA,
+                Gl::UNSIGNED_BYTE,
+                Some(&mut rgba),
+            )
+            .into_js_result()?;
+        Ok(rgba
+            .chunks_exact(width as usize * 4)
+            .rev()
+            .flatten()
+            .copied()
+            .collect())
+    }
+
+    /// Creates an empty texture the size of the viewport, and binds its framebuffer.
+    fn create_render_target(&self) -> Result<RenderTarget, Error> {
+        let gl = &self.gl;
+        let texture = gl.create_texture().ok_or("Unable to create texture")?;
+        gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, Gl::NEAREST as i32);
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, Gl::NEAREST as i32);
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, Gl::CLAMP_TO_EDGE as i32);
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, Gl::CLAMP_TO_EDGE as i32);
+        gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+            Gl::TEXTURE_2D,
+            0,
+            Gl::RGBA as i32,
+            self.viewport_width as i32,
+            self.viewport_height as i32,
+            0,
+            Gl::RGBA,
+            Gl::UNSIGNED_BYTE,
+            None,
+        )
+        .into_js_result()?;
+        gl.bind_texture(Gl::TEXTURE_2D, None);
+
+        let framebuffer = gl
+            .create_framebuffer()
+            .ok_or("Unable to create framebuffer")?;
+        gl.bind_framebuffer(Gl::FRAMEBUFFER, Some(&framebuffer));
+        gl.framebuffer_texture_2d(
+            Gl::FRAMEBUFFER,
+            Gl::COLOR_ATTACHMENT0,
+            Gl::TEXTURE_2D,
+            Some(&texture),
+            0,
+        );
+
+        Ok(RenderTarget {
+            framebuffer,
+            texture,
+        })
+    }
+
+    fn delete_render_target(&self, render_target: RenderTarget) {
+        self.gl.delete_framebuffer(Some(&render_target.framebuffer));
+        self.gl.delete_texture(Some(&render_target.texture));
+    }
+
+    /// Applies each of the filters in order, returning the filtered render target.
+    fn apply_filters(
+        &mut self,
+        render_target: RenderTarget,
+        filters: &[Filter],
+    ) -> Result<RenderTarget, Error> {
+        // Each pass replaces the whole of its target.
+        self.gl.disable(Gl::BLEND);
+        self.gl.disable(Gl::STENCIL_TEST);
+
+        let result = filters
+            .iter()
+            .try_fold(render_target, |render_target, filter| {
+                let filtered = self.apply_filter(&render_target.texture, filter);
+                self.delete_render_target(render_target);
+                filtered
+            });
+
+        self.gl.enable(Gl::BLEND);
+        self.active_program = std::ptr::null();
+        self.mult_color = None;
+        self.add_color = None;
+        self.mask_state_dirty = true;
+        result
+    }
+
+    fn apply_filter(&self, texture: &WebGlTexture, filter: &Filter) -> Result<RenderTarget, Error> {
+        let filter = match filter {
+            Filter::Swf(filter) => filter,
+            Filter::DisplacementMap(_) => {
+                unreachable!("Filter {:?} isn't supported on the GPU", filter)
+            }
+        };
+        match filter {
+            swf::Filter::BlurFilter(filter) => {
+                self.blur(texture, filter.blur_x, filter.blur_y, filter.num_passes)
+            }
+            swf::Filter::ColorMatrixFilter(filter) => self.color_matrix(texture, &filter.matrix),
+            swf::Filter::DropShadowFilter(filter) => {
+                let blurred =
+                    self.blur(texture, filter.blur_x, filter.blur_y, filter.num_passes)?;
+                let result = self.shadow(
+                    texture,
+                    &blurred.texture,
+                    ShadowParams {
+                        color: color_to_premultiplied(&filter.color),
+                        shadow_color: [0.0; 4],
+                        angle: filter.angle,
+                        distance: filter.distance,
+                        strength: filter.strength,
+                        inner: filter.is_inner,
+                        knockout: filter.is_knockout,
+                        bevel: false,
+                        on_top: false,
+                    },
+                );
+                self.delete_render_target(blurred);
+                result
+            }
+            swf::Filter::GlowFilter(filter) => {
+                let blurred =
+                    self.blur(texture, filter.blur_x, filter.blur_y, filter.num_passes)?;
+                let result = self.shadow(
+                    texture,
+                    &blurred.texture,
+                    ShadowParams {
+                        color: color_to_premultiplied(&filter.color),
+                        shadow_color: [0.0; 4],
+                        angle: 0.0,
+                        distance: 0.0,
+                        strength: filter.strength,
+                        inner: filter.is_inner,
+                        knockout: filter.is_knockout,
+                        bevel: false,
+                        on_top: false,
+                    },
+                );
+                self.delete_render_target(blurred);
+                result
+            }
+            swf::Filter::BevelFilter(filter) => {
+                let blurred =
+                    self.blur(texture, filter.blur_x, filter.blur_y, filter.num_passes)?;
+                let result = self.shadow(
+                    texture,
+                    &blurred.texture,
+                    ShadowParams {
+                        color: color_to_premultiplied(&filter.highlight_color),
+                        shadow_color: color_to_premultiplied(&filter.shadow_color),
+                        angle: filter.angle,
+                        distance: filter.distance,
+                        strength: filter.strength,
+                        inner: filter.is_inner,
+                        knockout: filter.is_knockout,
+                        bevel: true,
+                        on_top: filter.is_on_top,
+                    },
+                );
+                self.delete_render_target(blurred);
+                result
+            }
+            swf::Filter::GradientGlowFilter(_)
+            | swf::Filter::GradientBevelFilter(_)
+            | swf::Filter::ConvolutionFilter(_) => {
+                unreachable!("Filter {:?} isn't supported on the GPU", filter)
+            }
+        }
+    }
+
+    /// Blurs the texture by repeatedly applying a box blur of the given size,
+    /// horizontally and then vertically.
+    fn blur(
+        &self,
+        texture: &WebGlTexture,
+        blur_x: f64,
+        blur_y: f64,
+        num_passes: u8,
+    ) -> Result<RenderTarget, Error> {
+        let radius_x = (blur_radius(blur_x) as i32).min(MAX_BLUR_RADIUS);
+        let radius_y = (blur_radius(blur_y) as i32).min(MAX_BLUR_RADIUS);
+        let step_x = [1.0 / self.viewport_width, 0.0];
+        let step_y = [0.0, 1.0 / self.viewport_height];
+
+        let mut blurred: Option<RenderTarget> = None;
+        for _ in 0..num_passes {
+            for &(step, radius) in &[(step_x, radius_x), (step_y, radius_y)] {
+                if radius > 0 {
+                    let source = blurred.as_ref().map_or(texture, |blurred| &blurred.texture);
+                    let pass = self.blur_pass(source, step, radius);
+                    if let Some(previous) = blurred.take() {
+                        self.delete_render_target(previous);
+                    }
+                    blurred = Some(pass?);
+                }
+            }
+        }
+
+        // A blur with a radius of 0 copies the texture.
+        match blurred {
+            Some(blurred) => Ok(blurred),
+            None => self.blur_pass(texture, step_x, 0),
+        }
+    }
+
+    fn blur_pass(
+        &self,
+        texture: &WebGlTexture,
+        step: [f32; 2],
+        radius: i32,
+    ) -> Result<RenderTarget, Error> {
+        self.draw_pass(&self.blur_program, &[texture], |program| {
+            program.uniform2fv(&self.gl, ShaderUniform::BlurStep, &step);
+            program.uniform1i(&self.gl, ShaderUniform::BlurRadius, radius);
+        })
+    }
+
+    /// Applies a 4x5 color matrix to unmultiplied colors.
+    fn color_matrix(
+        &self,
+        texture: &WebGlTexture,
+        matrix: &[f64; 20],
+    ) -> Result<RenderTarget, Error> {
+        // The rows of the matrix become the rows of a column-major matrix,
+        // and the last column is an offset in the range of 0 to 255.
+        let mut columns = [[0.0; 4]; 4];
+        let mut offsets = [0.0; 4];
+        for (row, offset) in offsets.iter_mut().enumerate() {
+            for (column, values) in columns.iter_mut().enumerate() {
+                values[row] = matrix[row * 5 + column] as f32;
+            }
+            *offset = (matrix[row * 5 + 4] / 255.0) as f32;
+        }
+
+        self.draw_pass(&self.color_matrix_program, &[texture], |program| {
+            program.uniform_matrix4fv(&self.gl, ShaderUniform::ColorMatrix, &columns);
+            program.uniform4fv(&self.gl, ShaderUniform::ColorMatrixOffset, &offsets);
+        })
+    }
+
+    /// Applies a glow, drop shadow or bevel, given the texture and a blurred copy of it.
+    fn shadow(
+        &self,
+        texture: &WebGlTexture,
+        blurred: &WebGlTexture,
+        params: ShadowParams,
+    ) -> Result<RenderTarget, Error> {
+        // Textures are stored from the bottom up, so a shadow cast downwards has a
+        // negative vertical offset.
+        let (dx, dy) = offset(params.angle, params.distance);
+        let shadow_offset = [
+            dx as f32 / self.viewport_width,
+            -dy as f32 / self.viewport_height,
+        ];
+        let flags = [
+            flag(params.inner),
+            flag(params.knockout),
+            flag(params.bevel),
+            flag(params.on_top),
+        ];
+
+        self.draw_pass(&self.shadow_program, &[texture, blurred], |program| {
+            program.uniform4fv(&self.gl, ShaderUniform::ShadowColor, &params.color);
+            program.uniform4fv(
+                &self.gl,
+                ShaderUniform::BevelShadowColor,
+                &params.shadow_color,
+            );
+            program.uniform2fv(&self.gl, ShaderUniform::ShadowOffset, &shadow_offset);
+            program.uniform1f(&self.gl, ShaderUniform::ShadowStrength, params.strength);
+            program.uniform4fv(&self.gl, ShaderUniform::ShadowFlags, &flags);
+        })
+    }
+
+    /// Draws a full-screen pass into a new render target, sampling from the given textures.
+    fn draw_pass(
+        &self,
+        program: &ShaderProgram,
+        textures: &[&WebGlTexture],
+        set_uniforms: impl FnOnce(&ShaderProgram),
+    ) -> Result<RenderTarget, Error> {
+        let render_target = self.create_render_target()?;
+        let gl = &self.gl;
+        gl.use_program(Some(&program.program));
+
+        // Scale to fill the target.
+        program.uniform_matrix4fv(
+            gl,
+            ShaderUniform::WorldMatrix,
+            &[
+                [2.0, 0.0, 0.0, 0.0],
+                [0.0, 2.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [-1.0, -1.0, 0.0, 1.0],
+            ],
+        );
+        program.uniform_matrix4fv(
+            gl,
+            ShaderUniform::ViewMatrix,
+            &[
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        );
+        program.uniform_matrix3fv(
+            gl,
+            ShaderUniform::TextureMatrix,
+            &[[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        );
+
+        for (i, texture) in textures.iter().enumerate() {
+            gl.active_texture(Gl::TEXTURE0 + i as u32);
+            gl.bind_texture(Gl::TEXTURE_2D, Some(texture));
+        }
+        program.uniform1i(gl, ShaderUniform::BitmapTexture, 0);
+        program.uniform1i(gl, ShaderUniform::BlurredTexture, 1);
+        set_uniforms(program);
+
+        let quad = &self.meshes[self.quad_shape.0];
+        self.bind_vertex_array(Some(&quad.draws[0].vao));
+        gl.draw_elements_with_i32(
+            Gl::TRIANGLES,
+            quad.draws[0].num_indices,
+            Gl::UNSIGNED_SHORT,
+            0,
+        );
+        gl.active_texture(Gl::TEXTURE0);
+
+        Ok(render_target)
+    }
+}
+
+fn flag(value: bool) -> f32 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
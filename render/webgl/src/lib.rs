@@ -1,3 +1,4 @@
+use ruffle_core::backend::render::filters::Filter;
 use ruffle_core::backend::render::swf;
 use ruffle_core::backend::render::{
     srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, Letterbox,
@@ -14,6 +15,8 @@ use web_sys::{
     WebGlTexture, WebGlUniformLocation, WebGlVertexArrayObject, WebglDebugRendererInfo,
 };
 
+mod filters;
+
 type Error = Box<dyn std::error::Error>;
 
 const COLOR_VERTEX_GLSL: &str = include_str!("../shaders/color.vert");
@@ -21,6 +24,9 @@ const COLOR_FRAGMENT_GLSL: &str = include_str!("../shaders/color.frag");
 const TEXTURE_VERTEX_GLSL: &str = include_str!("../shaders/texture.vert");
 const GRADIENT_FRAGMENT_GLSL: &str = include_str!("../shaders/gradient.frag");
 const BITMAP_FRAGMENT_GLSL: &str = include_str!("../shaders/bitmap.frag");
+const FILTER_BLUR_FRAGMENT_GLSL: &str = include_str!("../shaders/filter_blur.frag");
+const FILTER_COLOR_MATRIX_FRAGMENT_GLSL: &str = include_str!("../shaders/filter_color_matrix.frag");
+const FILTER_SHADOW_FRAGMENT_GLSL: &str = include_str!("../shaders/filter_shadow.frag");
 const NUM_VERTEX_ATTRIBUTES: u32 = 2;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,6 +54,9 @@ pub struct WebGlRenderBackend {
     color_program: ShaderProgram,
     bitmap_program: ShaderProgram,
     gradient_program: ShaderProgram,
+    blur_program: ShaderProgram,
+    color_matrix_program: ShaderProgram,
+    shadow_program: ShaderProgram,
 
     shape_tessellator: ShapeTessellator,
 
@@ -60,8 +69,12 @@ pub struct WebGlRenderBackend {
     num_masks: u32,
     mask_state_dirty: bool,
 
+    /// The offscreen targets of filtered content, or `None` where one couldn't be created.
+    filter_targets: Vec<Option<filters::FilterTarget>>,
+
     active_program: *const ShaderProgram,
-    blend_func: (u32, u32),
+    blend_func: (u32, u32, u32),
+    blend_modes: Vec<swf::BlendMode>,
     mult_color: Option<[f32; 4]>,
     add_color: Option<[f32; 4]>,
 
@@ -164,13 +177,23 @@ impl WebGlRenderBackend {
         let bitmap_fragment = Self::compile_shader(&gl, Gl::FRAGMENT_SHADER, BITMAP_FRAGMENT_GLSL)?;
         let gradient_fragment =
             Self::compile_shader(&gl, Gl::FRAGMENT_SHADER, GRADIENT_FRAGMENT_GLSL)?;
+        let blur_fragment =
+            Self::compile_shader(&gl, Gl::FRAGMENT_SHADER, FILTER_BLUR_FRAGMENT_GLSL)?;
+        let color_matrix_fragment =
+            Self::compile_shader(&gl, Gl::FRAGMENT_SHADER, FILTER_COLOR_MATRIX_FRAGMENT_GLSL)?;
+        let shadow_fragment =
+            Self::compile_shader(&gl, Gl::FRAGMENT_SHADER, FILTER_SHADOW_FRAGMENT_GLSL)?;
 
         let color_program = ShaderProgram::new(&gl, &color_vertex, &color_fragment)?;
         let bitmap_program = ShaderProgram::new(&gl, &texture_vertex, &bitmap_fragment)?;
         let gradient_program = ShaderProgram::new(&gl, &texture_vertex, &gradient_fragment)?;
+        let blur_program = ShaderProgram::new(&gl, &texture_vertex, &blur_fragment)?;
+        let color_matrix_program =
+            ShaderProgram::new(&gl, &texture_vertex, &color_matrix_fragment)?;
+        let shadow_program = ShaderProgram::new(&gl, &texture_vertex, &shadow_fragment)?;
 
         gl.enable(Gl::BLEND);
-        gl.blend_func(Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA);
+        set_blend_func(&gl, (Gl::FUNC_ADD, Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA));
 
         // Necessary to load RGB textures (alignment defaults to 4).
         gl.pixel_storei(Gl::UNPACK_ALIGNMENT, 1);
@@ -186,6 +209,9 @@ impl WebGlRenderBackend {
             color_program,
             gradient_program,
             bitmap_program,
+            blur_program,
+            color_matrix_program,
+            shadow_program,
 
             shape_tessellator: ShapeTessellator::new(),
 
@@ -200,8 +226,11 @@ impl WebGlRenderBackend {
             num_masks: 0,
             mask_state_dirty: true,
 
+            filter_targets: Vec::new(),
+
             active_program: std::ptr::null(),
-            blend_func: (Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA),
+            blend_func: (Gl::FUNC_ADD, Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA),
+            blend_modes: Vec::new(),
             mult_color: None,
             add_color: None,
         };
@@ -297,7 +326,7 @@ impl WebGlRenderBackend {
             draws: vec![Draw {
                 draw_type: DrawType::Bitmap(BitmapDraw {
                     matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
-                    id: 0,
+                    handle: None,
 
                     is_smoothed: true,
                     is_repeating: false,
@@ -546,7 +575,11 @@ impl WebGlRenderBackend {
                     Draw {
                         draw_type: DrawType::Bitmap(BitmapDraw {
                             matrix: bitmap.matrix,
-                            id: bitmap.id,
+                            handle: self
+                                .textures
+                                .iter()
+                                .position(|(id, _tex)| *id == bitmap.id)
+                                .map(BitmapHandle),
                             is_smoothed: bitmap.is_smoothed,
                             is_repeating: bitmap.is_repeating,
                         }),
@@ -798,11 +831,69 @@ impl RenderBackend for WebGlRenderBackend {
         self.register_bitmap(swf_tag.id, bitmap)
     }
 
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(self
+            .register_bitmap(
+                0,
+                Bitmap {
+                    width,
+                    height,
+                    data: BitmapFormat::Rgba(rgba),
+                },
+            )?
+            .handle)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let texture = if let Some((_id, texture)) = self.textures.get_mut(handle.0) {
+            texture
+        } else {
+            return Err("update_texture: Bitmap not registered".into());
+        };
+
+        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&texture.texture));
+        self.gl
+            .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+                Gl::TEXTURE_2D,
+                0,
+                Gl::RGBA as i32,
+                width as i32,
+                height as i32,
+                0,
+                Gl::RGBA,
+                Gl::UNSIGNED_BYTE,
+                Some(&rgba),
+            )
+            .into_js_result()?;
+        texture.width = width;
+        texture.height = height;
+
+        Ok(handle)
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         self.active_program = std::ptr::null();
         self.mask_state = MaskState::NoMask;
         self.num_masks = 0;
         self.mask_state_dirty = true;
+        self.blend_modes.clear();
+        for filter_target in std::mem::take(&mut self.filter_targets)
+            .into_iter()
+            .flatten()
+        {
+            self.delete_filter_target(filter_target);
+        }
 
         self.mult_color = None;
         self.add_color = None;
@@ -905,14 +996,14 @@ impl RenderBackend for WebGlRenderBackend {
         // TODO: Might be better to make this separate code to render the bitmap
         // instead of going through render_shape. But render_shape already handles
         // masking etc.
-        if let Some((id, bitmap)) = self.textures.get(bitmap.0) {
+        if let Some((_id, texture)) = self.textures.get(bitmap.0) {
             // Adjust the quad draw to use the target bitmap.
             let mesh = &mut self.meshes[self.quad_shape.0];
             let draw = &mut mesh.draws[0];
-            let width = bitmap.width as f32;
-            let height = bitmap.height as f32;
-            if let DrawType::Bitmap(BitmapDraw { id: draw_id, .. }) = &mut draw.draw_type {
-                *draw_id = *id;
+            let width = texture.width as f32;
+            let height = texture.height as f32;
+            if let DrawType::Bitmap(BitmapDraw { handle, .. }) = &mut draw.draw_type {
+                *handle = Some(bitmap);
             }
 
             // Scale the quad to the bitmap's dimensions.
@@ -964,15 +1055,11 @@ impl RenderBackend for WebGlRenderBackend {
         for draw in &mesh.draws {
             self.bind_vertex_array(Some(&draw.vao));
 
-            let (program, src_blend, dst_blend) = match &draw.draw_type {
-                DrawType::Color => (&self.color_program, Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA),
-                DrawType::Gradient(_) => (
-                    &self.gradient_program,
-                    Gl::SRC_ALPHA,
-                    Gl::ONE_MINUS_SRC_ALPHA,
-                ),
+            let (program, premultiplied) = match &draw.draw_type {
+                DrawType::Color => (&self.color_program, false),
+                DrawType::Gradient(_) => (&self.gradient_program, false),
                 // Bitmaps use pre-multiplied alpha.
-                DrawType::Bitmap { .. } => (&self.bitmap_program, Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA),
+                DrawType::Bitmap { .. } => (&self.bitmap_program, true),
             };
 
             // Set common render state, while minimizing unnecessary state changes.
@@ -986,11 +1073,17 @@ impl RenderBackend for WebGlRenderBackend {
 
                 self.mult_color = None;
                 self.add_color = None;
+            }
 
-                if (src_blend, dst_blend) != self.blend_func {
-                    self.gl.blend_func(src_blend, dst_blend);
-                    self.blend_func = (src_blend, dst_blend);
-                }
+            // Set the blend function for the current blend mode.
+            let blend_func = blend_func(
+                self.blend_modes.last().copied(),
+                premultiplied,
+                self.gl2.is_some(),
+            );
+            if blend_func != self.blend_func {
+                set_blend_func(&self.gl, blend_func);
+                self.blend_func = blend_func;
             }
 
             program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
@@ -1047,10 +1140,10 @@ impl RenderBackend for WebGlRenderBackend {
                     );
                 }
                 DrawType::Bitmap(bitmap) => {
-                    let texture = if let Some(texture) =
-                        self.textures.iter().find(|(id, _tex)| *id == bitmap.id)
+                    let texture = if let Some((_id, texture)) =
+                        bitmap.handle.and_then(|handle| self.textures.get(handle.0))
                     {
-                        &texture.1
+                        texture
                     } else {
                         // Bitmap not registered
                         continue;
@@ -1121,8 +1214,6 @@ impl RenderBackend for WebGlRenderBackend {
         self.set_stencil_state();
 
         let program = &self.color_program;
-        let src_blend = Gl::SRC_ALPHA;
-        let dst_blend = Gl::ONE_MINUS_SRC_ALPHA;
 
         // Set common render state, while minimizing unnecessary state changes.
         // TODO: Using designated layout specifiers in WebGL2/OpenGL ES 3, we could guarantee that uniforms
@@ -1135,13 +1226,14 @@ impl RenderBackend for WebGlRenderBackend {
 
             self.mult_color = None;
             self.add_color = None;
-
-            if (src_blend, dst_blend) != self.blend_func {
-                self.gl.blend_func(src_blend, dst_blend);
-                self.blend_func = (src_blend, dst_blend);
-            }
         };
 
+        let blend_func = blend_func(self.blend_modes.last().copied(), false, self.gl2.is_some());
+        if blend_func != self.blend_func {
+            set_blend_func(&self.gl, blend_func);
+            self.blend_func = blend_func;
+        }
+
         self.color_program
             .uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
         if Some(mult_color) != self.mult_color {
@@ -1241,6 +1333,78 @@ impl RenderBackend for WebGlRenderBackend {
         };
         self.mask_state_dirty = true;
     }
+
+    fn push_filters(&mut self, filters: &[Filter]) {
+        let filter_target = match self.push_filter_target(filters.to_vec()) {
+            Ok(filter_target) => Some(filter_target),
+            Err(e) => {
+                // The content is drawn without its filters instead.
+                log::error!("Couldn't create offscreen target for filters: {}", e);
+                self.bind_current_framebuffer();
+                None
+            }
+        };
+        self.filter_targets.push(filter_target);
+    }
+
+    fn pop_filters(&mut self) {
+        if let Some(Some(filter_target)) = self.filter_targets.pop() {
+            if let Err(e) = self.pop_filter_target(filter_target) {
+                log::error!("Couldn't apply filters: {}", e);
+            }
+        }
+    }
+
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode) {
+        // Blend modes are applied to each shape as it is drawn, rather than to the
+        // display object as a whole.
+        self.blend_modes.push(blend_mode);
+    }
+
+    fn pop_blend_mode(&mut self) {
+        self.blend_modes.pop();
+    }
+}
+
+/// Returns the blend equation and blend function used to draw with a blend mode.
+///
+/// `Difference`, `Invert`, `Overlay` and `HardLight` can't be expressed with blend
+/// functions, and `Lighten` and `Darken` require WebGL 2, so these are drawn normally.
+fn blend_func(
+    blend_mode: Option<swf::BlendMode>,
+    premultiplied: bool,
+    is_webgl2: bool,
+) -> (u32, u32, u32) {
+    use swf::BlendMode;
+    let src_blend = if premultiplied {
+        Gl::ONE
+    } else {
+        Gl::SRC_ALPHA
+    };
+    match blend_mode.unwrap_or(BlendMode::Normal) {
+        BlendMode::Add => (Gl::FUNC_ADD, src_blend, Gl::ONE),
+        BlendMode::Subtract => (Gl::FUNC_REVERSE_SUBTRACT, src_blend, Gl::ONE),
+        BlendMode::Multiply => (Gl::FUNC_ADD, Gl::DST_COLOR, Gl::ONE_MINUS_SRC_ALPHA),
+        BlendMode::Screen => (Gl::FUNC_ADD, src_blend, Gl::ONE_MINUS_SRC_COLOR),
+        BlendMode::Lighten if is_webgl2 => (Gl2::MAX, Gl::ONE, Gl::ONE),
+        BlendMode::Darken if is_webgl2 => (Gl2::MIN, Gl::ONE, Gl::ONE),
+        BlendMode::Alpha => (Gl::FUNC_ADD, Gl::ZERO, Gl::SRC_ALPHA),
+        BlendMode::Erase => (Gl::FUNC_ADD, Gl::ZERO, Gl::ONE_MINUS_SRC_ALPHA),
+        _ => (Gl::FUNC_ADD, src_blend, Gl::ONE_MINUS_SRC_ALPHA),
+    }
+}
+
+/// Sets the blend equation and blend function.
+fn set_blend_func(gl: &Gl, (equation, src_blend, dst_blend): (u32, u32, u32)) {
+    gl.blend_equation(equation);
+    // Alpha is always blended as premultiplied, so that filtered content drawn
+    // offscreen has the correct alpha.
+    let src_alpha_blend = if src_blend == Gl::ZERO {
+        Gl::ZERO
+    } else {
+        Gl::ONE
+    };
+    gl.blend_func_separate(src_blend, dst_blend, src_alpha_blend, dst_blend);
 }
 
 struct Texture {
@@ -1264,7 +1428,7 @@ struct Gradient {
 #[derive(Clone, Debug)]
 struct BitmapDraw {
     matrix: [[f32; 3]; 3],
-    id: swf::CharacterId,
+    handle: Option<BitmapHandle>,
     is_repeating: bool,
     is_smoothed: bool,
 }
@@ -1308,7 +1472,7 @@ struct ShaderProgram {
 }
 
 // These should match the uniform names in the shaders.
-const NUM_UNIFORMS: usize = 13;
+const NUM_UNIFORMS: usize = 23;
 const UNIFORM_NAMES: [&str; NUM_UNIFORMS] = [
     "world_matrix",
     "view_matrix",
@@ -1323,6 +1487,16 @@ const UNIFORM_NAMES: [&str; NUM_UNIFORMS] = [
     "u_focal_point",
     "u_interpolation",
     "u_texture",
+    "u_blurred",
+    "u_step",
+    "u_radius",
+    "u_color_matrix",
+    "u_offset",
+    "u_color",
+    "u_shadow_color",
+    "u_shadow_offset",
+    "u_strength",
+    "u_flags",
 ];
 
 enum ShaderUniform {
@@ -1339,6 +1513,16 @@ enum ShaderUniform {
     GradientFocalPoint,
     GradientInterpolation,
     BitmapTexture,
+    BlurredTexture,
+    BlurStep,
+    BlurRadius,
+    ColorMatrix,
+    ColorMatrixOffset,
+    ShadowColor,
+    BevelShadowColor,
+    ShadowOffset,
+    ShadowStrength,
+    ShadowFlags,
 }
 
 impl ShaderProgram {
@@ -1351,6 +1535,10 @@ impl ShaderProgram {
         gl.attach_shader(&program, &vertex_shader);
         gl.attach_shader(&program, &fragment_shader);
 
+        // Programs sharing a vertex shader can then draw the same VAOs.
+        gl.bind_attrib_location(&program, 0, "position");
+        gl.bind_attrib_location(&program, 1, "color");
+
         gl.link_program(&program);
         if !gl
             .get_program_parameter(&program, Gl::LINK_STATUS)
@@ -1404,6 +1592,10 @@ impl ShaderProgram {
         gl.uniform1i(self.uniforms[uniform as usize].as_ref(), value);
     }
 
+    fn uniform2fv(&self, gl: &Gl, uniform: ShaderUniform, values: &[f32]) {
+        gl.uniform2fv_with_f32_array(self.uniforms[uniform as usize].as_ref(), values);
+    }
+
     fn uniform4fv(&self, gl: &Gl, uniform: ShaderUniform, values: &[f32]) {
         gl.uniform4fv_with_f32_array(self.uniforms[uniform as usize].as_ref(), values);
     }
@@ -4,3 +4,8 @@ glslangValidator -V ./shaders/color.vert -o ./shaders/color.vert.spv
 glslangValidator -V ./shaders/bitmap.frag -o ./shaders/bitmap.frag.spv
 glslangValidator -V ./shaders/gradient.frag -o ./shaders/gradient.frag.spv
 glslangValidator -V ./shaders/texture.vert -o ./shaders/texture.vert.spv
+glslangValidator -V ./shaders/filter.vert -o ./shaders/filter.vert.spv
+glslangValidator -V ./shaders/filter_blur.frag -o ./shaders/filter_blur.frag.spv
+glslangValidator -V ./shaders/filter_color_matrix.frag -o ./shaders/filter_color_matrix.frag.spv
+glslangValidator -V ./shaders/filter_shadow.frag -o ./shaders/filter_shadow.frag.spv
+glslangValidator -V ./shaders/filter_blend.frag -o ./shaders/filter_blend.frag.spv
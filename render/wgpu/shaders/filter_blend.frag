@@ -0,0 +1,37 @@
+#version 450
+
+// Set 0: blend
+layout(set = 0, binding = 0) uniform Blend {
+    // The difference, invert, overlay and hardlight flags, one of which is 1.0.
+    vec4 u_mode;
+};
+layout(set = 0, binding = 1) uniform texture2D t_color;
+layout(set = 0, binding = 2) uniform texture2D t_backdrop;
+
+// Set 1: sampler
+layout(set = 1, binding = 0) uniform sampler s_color;
+
+layout(location=0) in vec2 frag_uv;
+
+layout(location=0) out vec4 out_color;
+
+// Blends the object with the content behind it, for the blend modes that can't be drawn
+// with fixed-function blending. The result gives the blended color when drawn normally.
+void main() {
+    vec4 src = texture(sampler2D(t_color, s_color), frag_uv);
+    vec4 dst = texture(sampler2D(t_backdrop, s_color), frag_uv);
+    vec3 s = min(src.rgb / max(src.a, 0.0001), vec3(1.0));
+    vec3 d = min(dst.rgb / max(dst.a, 0.0001), vec3(1.0));
+
+    vec3 difference = abs(s - d);
+    vec3 invert = vec3(1.0) - d;
+    // Hard light multiplies or screens depending on the object, and overlay on the backdrop.
+    vec3 multiply = 2.0 * s * d;
+    vec3 screen = vec3(1.0) - 2.0 * (vec3(1.0) - s) * (vec3(1.0) - d);
+    vec3 overlay = mix(multiply, screen, step(0.5, d));
+    vec3 hard_light = mix(multiply, screen, step(0.5, s));
+    vec3 blended = difference * u_mode.x + invert * u_mode.y + overlay * u_mode.z + hard_light * u_mode.w;
+
+    // Where there is no backdrop, the object is drawn normally.
+    out_color = vec4(mix(s, blended, dst.a) * src.a, src.a);
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+#version 450
+
+// Set 0: filter
+layout(set = 0, binding = 0) uniform Blur {
+    // The distance between samples, in texture coordinates.
+    vec2 u_step;
+    // The number of samples on each side of the center.
+    int u_radius;
+};
+layout(set = 0, binding = 1) uniform texture2D t_color;
+
+// Set 1: sampler
+layout(set = 1, binding = 0) uniform sampler s_color;
+
+layout(location=0) in vec2 frag_uv;
+
+layout(location=0) out vec4 out_color;
+
+// One pass of a box blur along a single axis.
+void main() {
+    vec4 sum = vec4(0.0);
+    for (int i = -u_radius; i <= u_radius; i++) {
+        vec2 uv = frag_uv + u_step * float(i);
+        // Areas outside of the texture are transparent.
+        vec2 inside = step(vec2(0.0), uv) * step(uv, vec2(1.0));
+        sum += texture(sampler2D(t_color, s_color), uv) * inside.x * inside.y;
+    }
+    out_color = sum / float(2 * u_radius + 1);
+}
\ No newline at end of file
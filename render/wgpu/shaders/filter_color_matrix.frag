@@ -0,0 +1,24 @@
+#version 450
+
+// Set 0: filter
+layout(set = 0, binding = 0) uniform ColorMatrix {
+    mat4 u_matrix;
+    // The offsets added to each component, between 0.0 and 1.0.
+    vec4 u_offset;
+};
+layout(set = 0, binding = 1) uniform texture2D t_color;
+
+// Set 1: sampler
+layout(set = 1, binding = 0) uniform sampler s_color;
+
+layout(location=0) in vec2 frag_uv;
+
+layout(location=0) out vec4 out_color;
+
+void main() {
+    vec4 color = texture(sampler2D(t_color, s_color), frag_uv);
+    // The matrix applies to unmultiplied colors. Transparent pixels have a black color.
+    vec4 src = vec4(min(color.rgb / max(color.a, 0.0001), vec3(1.0)), color.a);
+    vec4 result = clamp(u_matrix * src + u_offset, 0.0, 1.0);
+    out_color = vec4(result.rgb * result.a, result.a);
+}
\ No newline at end of file
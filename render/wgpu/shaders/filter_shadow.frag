@@ -0,0 +1,55 @@
+#version 450
+
+// Set 0: filter
+layout(set = 0, binding = 0) uniform Shadow {
+    // The premultiplied color of a glow or drop shadow, or of the highlight of a bevel.
+    vec4 u_color;
+    // The premultiplied color of the shadow of a bevel.
+    vec4 u_shadow_color;
+    // The offset of the shadow, in texture coordinates.
+    vec2 u_offset;
+    float u_strength;
+    // Flags, which are either 0.0 or 1.0.
+    float u_inner;
+    float u_knockout;
+    float u_bevel;
+    float u_on_top;
+};
+layout(set = 0, binding = 1) uniform texture2D t_color;
+layout(set = 0, binding = 2) uniform texture2D t_blurred;
+
+// Set 1: sampler
+layout(set = 1, binding = 0) uniform sampler s_color;
+
+layout(location=0) in vec2 frag_uv;
+
+layout(location=0) out vec4 out_color;
+
+// Applies a glow, drop shadow or bevel, given the object and a blurred copy of it.
+void main() {
+    vec4 src = texture(sampler2D(t_color, s_color), frag_uv);
+
+    // The blurred object behind and ahead of this pixel, in the direction of the light.
+    // Areas outside of the texture are transparent.
+    vec2 behind_uv = frag_uv - u_offset;
+    vec2 ahead_uv = frag_uv + u_offset;
+    vec2 behind_inside = step(vec2(0.0), behind_uv) * step(behind_uv, vec2(1.0));
+    vec2 ahead_inside = step(vec2(0.0), ahead_uv) * step(ahead_uv, vec2(1.0));
+    float behind = texture(sampler2D(t_blurred, s_color), behind_uv).a * behind_inside.x * behind_inside.y;
+    float ahead = texture(sampler2D(t_blurred, s_color), ahead_uv).a * ahead_inside.x * ahead_inside.y;
+
+    // Glows and drop shadows are cast by the object, or by the area outside of it if inner.
+    float shadow_amount = clamp(mix(behind, 1.0 - behind, u_inner) * u_strength, 0.0, 1.0);
+    // Bevels are highlighted on the edges facing the light, and shaded on the opposite edges.
+    float bevel_amount = clamp((ahead - behind) * u_strength, -1.0, 1.0);
+    vec4 bevel_color = max(bevel_amount, 0.0) * u_color + max(-bevel_amount, 0.0) * u_shadow_color;
+    vec4 effect = mix(shadow_amount * u_color, bevel_color, u_bevel);
+
+    // The effect only covers the inside or the outside of the object, unless it is a bevel on top.
+    float coverage = mix(1.0 - src.a, src.a, u_inner);
+    effect *= mix(coverage, 1.0, u_bevel * u_on_top);
+
+    // Outer glows and drop shadows are drawn behind the object, and everything else in front of it.
+    float behind_object = (1.0 - u_inner) * (1.0 - u_bevel);
+    out_color = effect + src * (1.0 - u_knockout) * mix(1.0 - effect.a, 1.0, behind_object);
+}
\ No newline at end of file
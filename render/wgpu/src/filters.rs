@@ -0,0 +1,470 @@
+//! Bitmap filters applied on the GPU, as full-screen passes over offscreen textures.
+//!
+//! Filters without a GPU implementation are applied in software instead, by reading
+//! back the offscreen texture.
+//!
+//! Blend modes that can't be expressed as a fixed-function blend state are also applied
+//! here, against a copy of the backdrop.
+
+use crate::utils::create_buffer_with_data;
+use crate::Descriptors;
+use bytemuck::{Pod, Zeroable};
+use ruffle_core::backend::render::filters::{blur_radius, color_to_premultiplied, offset, Filter};
+use ruffle_core::backend::render::swf::{self, BlendMode};
+
+/// Returns whether all of the given filters can be applied on the GPU.
+pub fn is_supported(filters: &[Filter]) -> bool {
+    filters.iter().all(|filter| {
+        matches!(
+            filter,
+            Filter::Swf(swf::Filter::BlurFilter(_))
+                | Filter::Swf(swf::Filter::ColorMatrixFilter(_))
+                | Filter::Swf(swf::Filter::DropShadowFilter(_))
+                | Filter::Swf(swf::Filter::GlowFilter(_))
+                | Filter::Swf(swf::Filter::BevelFilter(_))
+        )
+    })
+}
+
+/// Returns the flags selecting the given blend mode in the blend pass,
+/// or `None` if the blend mode doesn't need the backdrop.
+pub fn shader_blend_mode(blend_mode: BlendMode) -> Option<[f32; 4]> {
+    match blend_mode {
+        BlendMode::Difference => Some([1.0, 0.0, 0.0, 0.0]),
+        BlendMode::Invert => Some([0.0, 1.0, 0.0, 0.0]),
+        BlendMode::Overlay => Some([0.0, 0.0, 1.0, 0.0]),
+        BlendMode::HardLight => Some([0.0, 0.0, 0.0, 1.0]),
+        _ => None,
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct BlendUniforms {
+    mode: [f32; 4],
+}
+
+unsafe impl Pod for BlendUniforms {}
+unsafe impl Zeroable for BlendUniforms {}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct BlurUniforms {
+    step: [f32; 2],
+    radius: i32,
+    _padding: i32,
+}
+
+unsafe impl Pod for BlurUniforms {}
+unsafe impl Zeroable for BlurUniforms {}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct ColorMatrixUniforms {
+    matrix: [[f32; 4]; 4],
+    offset: [f32; 4],
+}
+
+unsafe impl Pod for ColorMatrixUniforms {}
+unsafe impl Zeroable for ColorMatrixUniforms {}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct ShadowUniforms {
+    color: [f32; 4],
+    shadow_color: [f32; 4],
+    offset: [f32; 2],
+    strength: f32,
+    inner: f32,
+    knockout: f32,
+    bevel: f32,
+    on_top: f32,
+    _padding: f32,
+}
+
+unsafe impl Pod for ShadowUniforms {}
+unsafe impl Zeroable for ShadowUniforms {}
+
+/// Records the passes that apply filters to an offscreen texture.
+pub struct FilterPasses<'a> {
+    pub descriptors: &'a Descriptors,
+    pub encoder: &'a mut wgpu::CommandEncoder,
+    pub quad_vbo: &'a wgpu::Buffer,
+    pub quad_ibo: &'a wgpu::Buffer,
+    pub format: wgpu::TextureFormat,
+    pub extent: wgpu::Extent3d,
+}
+
+impl<'a> FilterPasses<'a> {
+    /// Applies each of the filters in order, returning the filtered texture.
+    pub fn apply(&mut self, texture: wgpu::Texture, filters: &[Filter]) -> wgpu::Texture {
+        filters.iter().fold(texture, |texture, filter| {
+            self.apply_filter(&texture, filter)
+        })
+    }
+
+    fn apply_filter(&mut self, texture: &wgpu::Texture, filter: &Filter) -> wgpu::Texture {
+        let filter = match filter {
+            Filter::Swf(filter) => filter,
+            Filter::DisplacementMap(_) => {
+                unreachable!("Filter {:?} isn't supported on the GPU", filter)
+            }
+        };
+        match filter {
+            swf::Filter::BlurFilter(filter) => {
+                self.blur(texture, filter.blur_x, filter.blur_y, filter.num_passes)
+            }
+            swf::Filter::ColorMatrixFilter(filter) => self.color_matrix(texture, &filter.matrix),
+            swf::Filter::DropShadowFilter(filter) => {
+                let blurred = self.blur(texture, filter.blur_x, filter.blur_y, filter.num_passes);
+                let color = color_to_premultiplied(&filter.color);
+                self.shadow(
+                    texture,
+                    &blurred,
+                    filter.angle,
+                    filter.distance,
+                    ShadowUniforms {
+                        color,
+                        shadow_color: [0.0; 4],
+                        offset: [0.0; 2],
+                        strength: filter.strength,
+                        inner: flag(filter.is_inner),
+                        knockout: flag(filter.is_knockout),
+                        bevel: 0.0,
+                        on_top: 0.0,
+                        _padding: 0.0,
+                    },
+                )
+            }
+            swf::Filter::GlowFilter(filter) => {
+                let blurred = self.blur(texture, filter.blur_x, filter.blur_y, filter.num_passes);
+                let color = color_to_premultiplied(&filter.color);
+                self.shadow(
+                    texture,
+                    &blurred,
+                    0.0,
+                    0.0,
+                    ShadowUniforms {
+                        color,
+                        shadow_color: [0.0; 4],
+                        offset: [0.0; 2],
+                        strength: filter.strength,
+                        inner: flag(filter.is_inner),
+                        knockout: flag(filter.is_knockout),
+                        bevel: 0.0,
+                        on_top: 0.0,
+                        _padding: 0.0,
+                    },
+                )
+            }
+            swf::Filter::BevelFilter(filter) => {
+                let blurred = self.blur(texture, filter.blur_x, filter.blur_y, filter.num_passes);
+                self.shadow(
+                    texture,
+                    &blurred,
+                    filter.angle,
+                    filter.distance,
+                    ShadowUniforms {
+                        color: color_to_premultiplied(&filter.highlight_color),
+                        shadow_color: color_to_premultiplied(&filter.shadow_color),
+                        offset: [0.0; 2],
+                        strength: filter.strength,
+                        inner: flag(filter.is_inner),
+                        knockout: flag(filter.is_knockout),
+                        bevel: 1.0,
+                        on_top: flag(filter.is_on_top),
+                        _padding: 0.0,
+                    },
+                )
+            }
+            swf::Filter::GradientGlowFilter(_)
+            | swf::Filter::GradientBevelFilter(_)
+            | swf::Filter::ConvolutionFilter(_) => {
+                unreachable!("Filter {:?} isn't supported on the GPU", filter)
+            }
+        }
+    }
+
+    /// Blurs the texture by repeatedly applying a box blur of the given size,
+    /// horizontally and then vertically.
+    fn blur(
+        &mut self,
+        texture: &wgpu::Texture,
+        blur_x: f64,
+        blur_y: f64,
+        num_passes: u8,
+    ) -> wgpu::Texture {
+        let radius_x = blur_radius(blur_x) as i32;
+        let radius_y = blur_radius(blur_y) as i32;
+        let step_x = [1.0 / self.extent.width as f32, 0.0];
+        let step_y = [0.0, 1.0 / self.extent.height as f32];
+
+        let mut blurred: Option<wgpu::Texture> = None;
+        for _ in 0..num_passes {
+            if radius_x > 0 {
+                let source = blurred.as_ref().unwrap_or(texture);
+                blurred = Some(self.blur_pass(source, step_x, radius_x));
+            }
+            if radius_y > 0 {
+                let source = blurred.as_ref().unwrap_or(texture);
+                blurred = Some(self.blur_pass(source, step_y, radius_y));
+            }
+        }
+
+        // A blur with a radius of 0 copies the texture.
+        blurred.unwrap_or_else(|| self.blur_pass(texture, step_x, 0))
+    }
+
+    fn blur_pass(&mut self, texture: &wgpu::Texture, step: [f32; 2], radius: i32) -> wgpu::Texture {
+        let uniforms = create_buffer_with_data(
+            &self.descriptors.device,
+            bytemuck::cast_slice(&[BlurUniforms {
+                step,
+                radius,
+                _padding: 0,
+            }]),
+            wgpu::BufferUsage::UNIFORM,
+            create_debug_label!("Blur filter uniforms"),
+        );
+        let view = texture.create_view(&Default::default());
+        let descriptors = self.descriptors;
+        let pipelines = &descriptors.pipelines.filter_pipelines;
+        let bind_group_label = create_debug_label!("Blur filter bind group");
+        let bind_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &pipelines.layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::Buffer {
+                            buffer: &uniforms,
+                            offset: 0,
+                            size: wgpu::BufferSize::new(std::mem::size_of::<BlurUniforms>() as u64),
+                        },
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                ],
+                label: bind_group_label.as_deref(),
+            });
+        self.draw_pass(&pipelines.blur, &bind_group)
+    }
+
+    /// Applies a 4x5 color matrix to unmultiplied colors.
+    fn color_matrix(&mut self, texture: &wgpu::Texture, matrix: &[f64; 20]) -> wgpu::Texture {
+        // The rows of the matrix become the rows of a column-major matrix,
+        // and the last column is an offset in the range of 0 to 255.
+        let mut columns = [[0.0; 4]; 4];
+        let mut offsets = [0.0; 4];
+        for (row, offset) in offsets.iter_mut().enumerate() {
+            for (column, values) in columns.iter_mut().enumerate() {
+                values[row] = matrix[row * 5 + column] as f32;
+            }
+            *offset = (matrix[row * 5 + 4] / 255.0) as f32;
+        }
+
+        let uniforms = create_buffer_with_data(
+            &self.descriptors.device,
+            bytemuck::cast_slice(&[ColorMatrixUniforms {
+                matrix: columns,
+                offset: offsets,
+            }]),
+            wgpu::BufferUsage::UNIFORM,
+            create_debug_label!("Color matrix filter uniforms"),
+        );
+        let view = texture.create_view(&Default::default());
+        let descriptors = self.descriptors;
+        let pipelines = &descriptors.pipelines.filter_pipelines;
+        let bind_group_label = create_debug_label!("Color matrix filter bind group");
+        let bind_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &pipelines.layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::Buffer {
+                            buffer: &uniforms,
+                            offset: 0,
+                            size: wgpu::BufferSize::new(
+                                std::mem::size_of::<ColorMatrixUniforms>() as u64
+                            ),
+                        },
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                ],
+                label: bind_group_label.as_deref(),
+            });
+        self.draw_pass(&pipelines.color_matrix, &bind_group)
+    }
+
+    /// Applies a glow, drop shadow or bevel cast at the given angle in radians,
+    /// given the texture and a blurred copy of it.
+    fn shadow(
+        &mut self,
+        texture: &wgpu::Texture,
+        blurred: &wgpu::Texture,
+        angle: f64,
+        distance: f64,
+        uniforms: ShadowUniforms,
+    ) -> wgpu::Texture {
+        let (dx, dy) = offset(angle, distance);
+        let uniforms = create_buffer_with_data(
+            &self.descriptors.device,
+            bytemuck::cast_slice(&[ShadowUniforms {
+                offset: [
+                    dx as f32 / self.extent.width as f32,
+                    dy as f32 / self.extent.height as f32,
+                ],
+                ..uniforms
+            }]),
+            wgpu::BufferUsage::UNIFORM,
+            create_debug_label!("Shadow filter uniforms"),
+        );
+        let view = texture.create_view(&Default::default());
+        let blurred_view = blurred.create_view(&Default::default());
+        let descriptors = self.descriptors;
+        let pipelines = &descriptors.pipelines.filter_pipelines;
+        let bind_group_label = create_debug_label!("Shadow filter bind group");
+        let bind_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &pipelines.shadow_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::Buffer {
+                            buffer: &uniforms,
+                            offset: 0,
+                            size: wgpu::BufferSize::new(
+                                std::mem::size_of::<ShadowUniforms>() as u64
+                            ),
+                        },
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(&blurred_view),
+                    },
+                ],
+                label: bind_group_label.as_deref(),
+            });
+        self.draw_pass(&pipelines.shadow, &bind_group)
+    }
+
+    /// Blends the texture with a copy of the backdrop, returning a texture that gives
+    /// the blended result when drawn over the backdrop with the normal blend mode.
+    pub fn blend(
+        &mut self,
+        texture: &wgpu::Texture,
+        backdrop: &wgpu::Texture,
+        mode: [f32; 4],
+    ) -> wgpu::Texture {
+        let uniforms = create_buffer_with_data(
+            &self.descriptors.device,
+            bytemuck::cast_slice(&[BlendUniforms { mode }]),
+            wgpu::BufferUsage::UNIFORM,
+            create_debug_label!("Blend uniforms"),
+        );
+        let view = texture.create_view(&Default::default());
+        let backdrop_view = backdrop.create_view(&Default::default());
+        let descriptors = self.descriptors;
+        let pipelines = &descriptors.pipelines.filter_pipelines;
+        let bind_group_label = create_debug_label!("Blend bind group");
+        let bind_group =
+            descriptors
+                .device
+                .create_bind_group(&wgpu::BindGroupDescriptor {
+                    layout: &pipelines.shadow_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::Buffer {
+                                buffer: &uniforms,
+                                offset: 0,
+                                size: wgpu::BufferSize::new(
+                                    std::mem::size_of::<BlendUniforms>() as u64
+                                ),
+                            },
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::TextureView(&view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: wgpu::BindingResource::TextureView(&backdrop_view),
+                        },
+                    ],
+                    label: bind_group_label.as_deref(),
+                });
+        self.draw_pass(&pipelines.blend, &bind_group)
+    }
+
+    /// Draws a full-screen pass into a new texture.
+    fn draw_pass(
+        &mut self,
+        pipeline: &wgpu::RenderPipeline,
+        bind_group: &wgpu::BindGroup,
+    ) -> wgpu::Texture {
+        let texture_label = create_debug_label!("Filter texture");
+        let texture = self
+            .descriptors
+            .device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: texture_label.as_deref(),
+                size: self.extent,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.format,
+                usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED,
+            });
+        let view = texture.create_view(&Default::default());
+
+        let mut render_pass = self.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        // Samples are taken at the center of each pixel.
+        render_pass.set_bind_group(
+            1,
+            self.descriptors
+                .bitmap_samplers
+                .get_bind_group(false, false),
+            &[],
+        );
+        render_pass.set_vertex_buffer(0, self.quad_vbo.slice(..));
+        render_pass.set_index_buffer(self.quad_ibo.slice(..));
+        render_pass.draw_indexed(0..6, 0, 0..1);
+        drop(render_pass);
+
+        texture
+    }
+}
+
+fn flag(value: bool) -> f32 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
@@ -3,6 +3,7 @@ use lyon::tessellation::{
     geometry_builder::{BuffersBuilder, FillVertexConstructor, VertexBuffers},
     FillAttributes, FillTessellator, StrokeAttributes, StrokeTessellator, StrokeVertexConstructor,
 };
+use ruffle_core::backend::render::filters::Filter;
 use ruffle_core::backend::render::swf::{self, FillStyle};
 use ruffle_core::backend::render::{
     srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, Letterbox,
@@ -21,7 +22,7 @@ use crate::shapes::{Draw, DrawType, GradientUniforms, IncompleteDrawType, Mesh};
 use crate::target::{RenderTarget, RenderTargetFrame, SwapChainTarget};
 use crate::utils::{
     create_buffer_with_data, format_list, get_backend_names, gradient_spread_mode_index,
-    ruffle_path_to_lyon_path, swf_bitmap_to_gl_matrix, swf_to_gl_matrix,
+    ruffle_path_to_lyon_path, swf_bitmap_to_gl_matrix, swf_to_gl_matrix, BufferDimensions,
 };
 use enum_map::Enum;
 use ruffle_core::color_transform::ColorTransform;
@@ -32,6 +33,7 @@ type Error = Box<dyn std::error::Error>;
 mod utils;
 
 mod bitmaps;
+mod filters;
 mod globals;
 mod pipelines;
 mod shapes;
@@ -95,6 +97,8 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     quad_vbo: wgpu::Buffer,
     quad_ibo: wgpu::Buffer,
     quad_tex_transforms: wgpu::Buffer,
+    offscreen_targets: Vec<OffscreenTarget>,
+    blend_state: Option<BlendState>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
@@ -105,6 +109,44 @@ pub enum MaskState {
     ClearMaskStencil,
 }
 
+/// A blend mode that can be drawn with fixed-function blending.
+///
+/// `Difference`, `Invert`, `Overlay` and `HardLight` can't be expressed this way,
+/// and are instead applied by a blend pass against a copy of the backdrop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum BlendState {
+    Add,
+    Subtract,
+    Multiply,
+    Screen,
+    Lighten,
+    Darken,
+    Alpha,
+    Erase,
+}
+
+impl BlendState {
+    fn from_blend_mode(blend_mode: swf::BlendMode) -> Option<Self> {
+        use swf::BlendMode;
+        match blend_mode {
+            BlendMode::Add => Some(BlendState::Add),
+            BlendMode::Subtract => Some(BlendState::Subtract),
+            BlendMode::Multiply => Some(BlendState::Multiply),
+            BlendMode::Screen => Some(BlendState::Screen),
+            BlendMode::Lighten => Some(BlendState::Lighten),
+            BlendMode::Darken => Some(BlendState::Darken),
+            BlendMode::Alpha => Some(BlendState::Alpha),
+            BlendMode::Erase => Some(BlendState::Erase),
+            BlendMode::Normal
+            | BlendMode::Layer
+            | BlendMode::Difference
+            | BlendMode::Invert
+            | BlendMode::Overlay
+            | BlendMode::HardLight => None,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 struct Transforms {
@@ -268,6 +310,8 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             quad_vbo,
             quad_ibo,
             quad_tex_transforms,
+            offscreen_targets: Vec::new(),
+            blend_state: None,
         })
     }
 
@@ -752,6 +796,391 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
     pub fn device(&self) -> &wgpu::Device {
         &self.descriptors.device
     }
+
+    /// Creates an offscreen target the size of the viewport, to draw content into
+    /// before it is filtered or blended onto the previous target.
+    fn create_offscreen_target(
+        &self,
+        filters: Vec<Filter>,
+        blend_mode: swf::BlendMode,
+    ) -> OffscreenTarget {
+        let width = self.viewport_width as u32;
+        let height = self.viewport_height as u32;
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        };
+        let msaa_sample_count = self.descriptors.msaa_sample_count;
+
+        let texture_label = create_debug_label!("Offscreen texture");
+        let texture = self
+            .descriptors
+            .device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: texture_label.as_deref(),
+                size: extent,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.target.format(),
+                usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT
+                    | wgpu::TextureUsage::SAMPLED
+                    | wgpu::TextureUsage::COPY_SRC,
+            });
+        let texture_view = texture.create_view(&Default::default());
+
+        let frame_buffer_view = if msaa_sample_count >= 2 {
+            let label = create_debug_label!("Offscreen framebuffer texture");
+            let frame_buffer = self
+                .descriptors
+                .device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: label.as_deref(),
+                    size: extent,
+                    mip_level_count: 1,
+                    sample_count: msaa_sample_count,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: self.target.format(),
+                    usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+                });
+            frame_buffer.create_view(&Default::default())
+        } else {
+            texture.create_view(&Default::default())
+        };
+
+        let depth_label = create_debug_label!("Offscreen depth texture");
+        let depth_texture = self
+            .descriptors
+            .device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: depth_label.as_deref(),
+                size: extent,
+                mip_level_count: 1,
+                sample_count: msaa_sample_count,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Depth24PlusStencil8,
+                usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+            });
+        let depth_texture_view = depth_texture.create_view(&Default::default());
+
+        // Filters without a GPU implementation are applied on the CPU, so the result
+        // must be read back.
+        let readback = if filters.is_empty() || filters::is_supported(&filters) {
+            None
+        } else {
+            let buffer_dimensions = BufferDimensions::new(width as usize, height as usize);
+            let buffer_label = create_debug_label!("Offscreen readback buffer");
+            let buffer = self
+                .descriptors
+                .device
+                .create_buffer(&wgpu::BufferDescriptor {
+                    label: buffer_label.as_deref(),
+                    size: (buffer_dimensions.padded_bytes_per_row * buffer_dimensions.height)
+                        as u64,
+                    usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+                    mapped_at_creation: false,
+                });
+            Some((buffer, buffer_dimensions))
+        };
+
+        OffscreenTarget {
+            texture,
+            texture_view,
+            frame_buffer_view,
+            depth_texture_view,
+            extent,
+            readback,
+            filters,
+            blend_mode,
+            mask_state: self.mask_state,
+            num_masks: self.num_masks,
+        }
+    }
+
+    /// Begins drawing to a new offscreen target.
+    fn push_offscreen_target(&mut self, filters: Vec<Filter>, blend_mode: swf::BlendMode) {
+        let offscreen_target = self.create_offscreen_target(filters, blend_mode);
+
+        if let Some((_frame_output, encoder)) = &mut self.current_frame {
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: &offscreen_target.frame_buffer_view,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: true,
+                    },
+                    resolve_target: None,
+                }],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                    attachment: &offscreen_target.depth_texture_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(0.0),
+                        store: true,
+                    }),
+                    stencil_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(0),
+                        store: true,
+                    }),
+                }),
+            });
+        }
+
+        // Masks outside of the offscreen content are applied when the result is composited.
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+        self.offscreen_targets.push(offscreen_target);
+    }
+
+    /// Finishes drawing to the current offscreen target, and draws its filtered
+    /// contents onto the previous target with its blend mode.
+    fn pop_offscreen_target(&mut self) {
+        let offscreen_target = if let Some(offscreen_target) = self.offscreen_targets.pop() {
+            offscreen_target
+        } else {
+            return;
+        };
+        self.mask_state = offscreen_target.mask_state;
+        self.num_masks = offscreen_target.num_masks;
+
+        let (frame_output, mut encoder) = if let Some(current_frame) = self.current_frame.take() {
+            current_frame
+        } else {
+            return;
+        };
+
+        let width = offscreen_target.extent.width;
+        let height = offscreen_target.extent.height;
+        let blend_mode = offscreen_target.blend_mode;
+
+        if offscreen_target.filters.is_empty() {
+            // The texture can be drawn directly, unless its blend mode needs the backdrop.
+            self.resolve_offscreen_target(&offscreen_target, &mut encoder);
+            let mut texture = offscreen_target.texture;
+            if let Some(mode) = filters::shader_blend_mode(blend_mode) {
+                if let Some(backdrop) = self.copy_backdrop(&mut encoder, offscreen_target.extent) {
+                    texture = filters::FilterPasses {
+                        descriptors: &self.descriptors,
+                        encoder: &mut encoder,
+                        quad_vbo: &self.quad_vbo,
+                        quad_ibo: &self.quad_ibo,
+                        format: self.target.format(),
+                        extent: offscreen_target.extent,
+                    }
+                    .blend(&texture, &backdrop, mode);
+                } else {
+                    log::warn!("Can't read the backdrop of blend mode {:?}", blend_mode);
+                }
+            }
+            self.current_frame = Some((frame_output, encoder));
+            self.textures.push((
+                0,
+                Texture {
+                    width,
+                    height,
+                    texture,
+                },
+            ));
+        } else if filters::is_supported(&offscreen_target.filters) {
+            self.resolve_offscreen_target(&offscreen_target, &mut encoder);
+            let texture = filters::FilterPasses {
+                descriptors: &self.descriptors,
+                encoder: &mut encoder,
+                quad_vbo: &self.quad_vbo,
+                quad_ibo: &self.quad_ibo,
+                format: self.target.format(),
+                extent: offscreen_target.extent,
+            }
+            .apply(offscreen_target.texture, &offscreen_target.filters);
+            self.current_frame = Some((frame_output, encoder));
+            self.textures.push((
+                0,
+                Texture {
+                    width,
+                    height,
+                    texture,
+                },
+            ));
+        } else {
+            let rgba = self.read_offscreen_target(&offscreen_target, encoder);
+
+            let label = create_debug_label!("Frame encoder");
+            let encoder =
+                self.descriptors
+                    .device
+                    .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                        label: label.as_deref(),
+                    });
+            self.current_frame = Some((frame_output, encoder));
+
+            let mut rgba = if let Some(rgba) = rgba {
+                rgba
+            } else {
+                return;
+            };
+
+            // Both the readback and the bitmap pipeline use premultiplied alpha.
+            ruffle_core::backend::render::filters::apply_filters(
+                width,
+                height,
+                &mut rgba,
+                &offscreen_target.filters,
+            );
+
+            let bitmap = Bitmap {
+                width,
+                height,
+                data: BitmapFormat::Rgba(rgba),
+            };
+            if let Err(e) = self.register_bitmap(0, bitmap, "Filter") {
+                log::error!("Couldn't register filtered bitmap: {}", e);
+                return;
+            }
+        }
+
+        self.blend_state = BlendState::from_blend_mode(blend_mode);
+        self.render_bitmap(BitmapHandle(self.textures.len() - 1), &Transform::default());
+        self.blend_state = None;
+        self.textures.pop();
+    }
+
+    /// Resolves the multisampled contents of an offscreen target into its texture.
+    fn resolve_offscreen_target(
+        &self,
+        offscreen_target: &OffscreenTarget,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        if self.descriptors.msaa_sample_count >= 2 {
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: &offscreen_target.frame_buffer_view,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: false,
+                    },
+                    resolve_target: Some(&offscreen_target.texture_view),
+                }],
+                depth_stencil_attachment: None,
+            });
+        }
+    }
+
+    /// Copies the contents of the target being drawn to into a new texture.
+    ///
+    /// Returns `None` if the target is the swap chain, which can't be read from.
+    fn copy_backdrop(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        extent: wgpu::Extent3d,
+    ) -> Option<wgpu::Texture> {
+        let multisampled = self.descriptors.msaa_sample_count >= 2;
+        let parent = self.offscreen_targets.last();
+        if parent.is_none() && !multisampled {
+            return None;
+        }
+
+        let label = create_debug_label!("Backdrop texture");
+        let backdrop = self
+            .descriptors
+            .device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: label.as_deref(),
+                size: extent,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.target.format(),
+                usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT
+                    | wgpu::TextureUsage::SAMPLED
+                    | wgpu::TextureUsage::COPY_DST,
+            });
+
+        if multisampled {
+            // Resolve the multisampled contents without discarding them.
+            let backdrop_view = backdrop.create_view(&Default::default());
+            let frame_buffer_view = parent
+                .map(|parent| &parent.frame_buffer_view)
+                .unwrap_or(&self.frame_buffer_view);
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: frame_buffer_view,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    },
+                    resolve_target: Some(&backdrop_view),
+                }],
+                depth_stencil_attachment: None,
+            });
+        } else {
+            encoder.copy_texture_to_texture(
+                wgpu::TextureCopyView {
+                    texture: &parent?.texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                },
+                wgpu::TextureCopyView {
+                    texture: &backdrop,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                },
+                extent,
+            );
+        }
+
+        Some(backdrop)
+    }
+
+    /// Reads back the contents of an offscreen target as premultiplied RGBA.
+    ///
+    /// This submits the commands recorded so far in `encoder` and waits for the GPU.
+    fn read_offscreen_target(
+        &self,
+        offscreen_target: &OffscreenTarget,
+        mut encoder: wgpu::CommandEncoder,
+    ) -> Option<Vec<u8>> {
+        let (buffer, dimensions) = offscreen_target.readback.as_ref()?;
+
+        self.resolve_offscreen_target(offscreen_target, &mut encoder);
+        encoder.copy_texture_to_buffer(
+            wgpu::TextureCopyView {
+                texture: &offscreen_target.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::BufferCopyView {
+                buffer,
+                layout: wgpu::TextureDataLayout {
+                    offset: 0,
+                    bytes_per_row: dimensions.padded_bytes_per_row as u32,
+                    rows_per_image: 0,
+                },
+            },
+            offscreen_target.extent,
+        );
+        self.descriptors.queue.submit(Some(encoder.finish()));
+
+        let buffer_future = buffer.slice(..).map_async(wgpu::MapMode::Read);
+        self.descriptors.device.poll(wgpu::Maintain::Wait);
+        if let Err(e) = block_on(buffer_future) {
+            log::error!("Unknown error reading offscreen buffer: {:?}", e);
+            return None;
+        }
+
+        let map = buffer.slice(..).get_mapped_range();
+        let mut rgba = Vec::with_capacity(dimensions.height * dimensions.unpadded_bytes_per_row);
+        for chunk in map.chunks(dimensions.padded_bytes_per_row) {
+            rgba.extend_from_slice(&chunk[..dimensions.unpadded_bytes_per_row]);
+        }
+
+        if self.target.format() == wgpu::TextureFormat::Bgra8Unorm {
+            for pixel in rgba.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        Some(rgba)
+    }
 }
 
 impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
@@ -856,6 +1285,77 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         self.register_bitmap(swf_tag.id, bitmap, "PNG")
     }
 
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(self
+            .register_bitmap(
+                0,
+                Bitmap {
+                    width,
+                    height,
+                    data: BitmapFormat::Rgba(rgba),
+                },
+                "RAW",
+            )?
+            .handle)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let texture = if let Some((_id, texture)) = self.textures.get(handle.0) {
+            texture
+        } else {
+            return Err("update_texture: Bitmap not registered".into());
+        };
+
+        if texture.width != width || texture.height != height {
+            // The texture must be recreated at the new size.
+            self.register_bitmap(
+                0,
+                Bitmap {
+                    width,
+                    height,
+                    data: BitmapFormat::Rgba(rgba),
+                },
+                "RAW",
+            )?;
+            self.textures.swap_remove(handle.0);
+            return Ok(handle);
+        }
+
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        };
+
+        self.descriptors.queue.write_texture(
+            wgpu::TextureCopyView {
+                texture: &texture.texture,
+                mip_level: 0,
+                origin: Default::default(),
+            },
+            &rgba,
+            wgpu::TextureDataLayout {
+                offset: 0,
+                bytes_per_row: 4 * extent.width,
+                rows_per_image: 0,
+            },
+            extent,
+        );
+
+        Ok(handle)
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         assert!(self.current_frame.is_none());
         self.current_frame = match self.target.get_next_texture() {
@@ -878,6 +1378,8 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
 
         self.mask_state = MaskState::NoMask;
         self.num_masks = 0;
+        self.offscreen_targets.clear();
+        self.blend_state = None;
 
         if let Some((frame_output, encoder)) = &mut self.current_frame {
             let color_attachment = if self.descriptors.msaa_sample_count >= 2 {
@@ -1020,11 +1522,17 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                         label: bitmap_bind_group_label.as_deref(),
                     });
 
-            let color_attachment = if self.descriptors.msaa_sample_count >= 2 {
-                &self.frame_buffer_view
-            } else {
-                frame_output.view()
-            };
+            let (color_attachment, depth_attachment) =
+                if let Some(offscreen_target) = self.offscreen_targets.last() {
+                    (
+                        &offscreen_target.frame_buffer_view,
+                        &offscreen_target.depth_texture_view,
+                    )
+                } else if self.descriptors.msaa_sample_count >= 2 {
+                    (&self.frame_buffer_view, &self.depth_texture_view)
+                } else {
+                    (frame_output.view(), &self.depth_texture_view)
+                };
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
                     attachment: color_attachment,
@@ -1035,7 +1543,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                     },
                 }],
                 depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                    attachment: &self.depth_texture_view,
+                    attachment: depth_attachment,
                     depth_ops: Some(wgpu::Operations {
                         load: wgpu::LoadOp::Load,
                         store: true,
@@ -1052,7 +1560,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                     .descriptors
                     .pipelines
                     .bitmap_pipelines
-                    .pipeline_for(self.mask_state),
+                    .pipeline_for(self.mask_state, self.blend_state),
             );
             render_pass.set_bind_group(0, self.descriptors.globals.bind_group(), &[]);
             render_pass.set_bind_group(1, &mesh_bind_group, &[]);
@@ -1137,11 +1645,17 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             std::mem::size_of::<Transforms>() as u64,
         );
 
-        let color_attachment = if self.descriptors.msaa_sample_count >= 2 {
-            &self.frame_buffer_view
-        } else {
-            frame_output.view()
-        };
+        let (color_attachment, depth_attachment) =
+            if let Some(offscreen_target) = self.offscreen_targets.last() {
+                (
+                    &offscreen_target.frame_buffer_view,
+                    &offscreen_target.depth_texture_view,
+                )
+            } else if self.descriptors.msaa_sample_count >= 2 {
+                (&self.frame_buffer_view, &self.depth_texture_view)
+            } else {
+                (frame_output.view(), &self.depth_texture_view)
+            };
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
                 attachment: color_attachment,
@@ -1152,7 +1666,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 },
             }],
             depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                attachment: &self.depth_texture_view,
+                attachment: depth_attachment,
                 depth_ops: Some(wgpu::Operations {
                     load: wgpu::LoadOp::Load,
                     store: true,
@@ -1175,7 +1689,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                             .descriptors
                             .pipelines
                             .color_pipelines
-                            .pipeline_for(self.mask_state),
+                            .pipeline_for(self.mask_state, self.blend_state),
                     );
                 }
                 DrawType::Gradient { bind_group, .. } => {
@@ -1184,7 +1698,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                             .descriptors
                             .pipelines
                             .gradient_pipelines
-                            .pipeline_for(self.mask_state),
+                            .pipeline_for(self.mask_state, self.blend_state),
                     );
                     render_pass.set_bind_group(2, bind_group, &[]);
                 }
@@ -1199,7 +1713,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                             .descriptors
                             .pipelines
                             .bitmap_pipelines
-                            .pipeline_for(self.mask_state),
+                            .pipeline_for(self.mask_state, self.blend_state),
                     );
                     render_pass.set_bind_group(2, bind_group, &[]);
                     render_pass.set_bind_group(
@@ -1306,11 +1820,17 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 label: bind_group_label.as_deref(),
             });
 
-        let color_attachment = if self.descriptors.msaa_sample_count >= 2 {
-            &self.frame_buffer_view
-        } else {
-            frame_output.view()
-        };
+        let (color_attachment, depth_attachment) =
+            if let Some(offscreen_target) = self.offscreen_targets.last() {
+                (
+                    &offscreen_target.frame_buffer_view,
+                    &offscreen_target.depth_texture_view,
+                )
+            } else if self.descriptors.msaa_sample_count >= 2 {
+                (&self.frame_buffer_view, &self.depth_texture_view)
+            } else {
+                (frame_output.view(), &self.depth_texture_view)
+            };
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
                 attachment: color_attachment,
@@ -1321,7 +1841,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 },
             }],
             depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                attachment: &self.depth_texture_view,
+                attachment: depth_attachment,
                 depth_ops: Some(wgpu::Operations {
                     load: wgpu::LoadOp::Load,
                     store: true,
@@ -1338,7 +1858,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 .descriptors
                 .pipelines
                 .color_pipelines
-                .pipeline_for(self.mask_state),
+                .pipeline_for(self.mask_state, self.blend_state),
         );
         render_pass.set_bind_group(0, self.descriptors.globals.bind_group(), &[]);
         render_pass.set_bind_group(1, &bind_group, &[]);
@@ -1482,6 +2002,22 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             MaskState::DrawMaskedContent
         };
     }
+
+    fn push_filters(&mut self, filters: &[Filter]) {
+        self.push_offscreen_target(filters.to_vec(), swf::BlendMode::Normal);
+    }
+
+    fn pop_filters(&mut self) {
+        self.pop_offscreen_target();
+    }
+
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode) {
+        self.push_offscreen_target(Vec::new(), blend_mode);
+    }
+
+    fn pop_blend_mode(&mut self) {
+        self.pop_offscreen_target();
+    }
 }
 
 fn create_quad_buffers(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::Buffer, wgpu::Buffer) {
@@ -1576,6 +2112,23 @@ fn swf_gradient_to_uniforms(
     }
 }
 
+/// An offscreen target for a display object with bitmap filters or a blend mode.
+#[derive(Debug)]
+struct OffscreenTarget {
+    texture: wgpu::Texture,
+    texture_view: wgpu::TextureView,
+    frame_buffer_view: wgpu::TextureView,
+    depth_texture_view: wgpu::TextureView,
+    extent: wgpu::Extent3d,
+    readback: Option<(wgpu::Buffer, BufferDimensions)>,
+    filters: Vec<Filter>,
+    blend_mode: swf::BlendMode,
+
+    /// The mask state to restore once the filtered content is drawn.
+    mask_state: MaskState,
+    num_masks: u32,
+}
+
 #[derive(Debug)]
 struct Texture {
     width: u32,
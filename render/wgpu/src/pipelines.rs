@@ -1,10 +1,20 @@
-use crate::{Error, GPUVertex, MaskState};
+use crate::{BlendState, Error, GPUVertex, MaskState};
 use enum_map::{enum_map, EnumMap};
 use wgpu::vertex_attr_array;
 
 #[derive(Debug)]
 pub struct ShapePipeline {
     pub mask_pipelines: EnumMap<MaskState, wgpu::RenderPipeline>,
+
+    /// Pipelines for drawing with a blend mode, if this pipeline supports them.
+    pub blend_pipelines: Option<EnumMap<BlendState, BlendPipelines>>,
+}
+
+/// The pipelines for a single blend mode, with and without a mask.
+#[derive(Debug)]
+pub struct BlendPipelines {
+    pub no_mask: wgpu::RenderPipeline,
+    pub masked_content: wgpu::RenderPipeline,
 }
 
 #[derive(Debug)]
@@ -18,11 +28,41 @@ pub struct Pipelines {
 
     pub gradient_pipelines: ShapePipeline,
     pub gradient_layout: wgpu::BindGroupLayout,
+
+    pub filter_pipelines: FilterPipelines,
+}
+
+/// The full-screen passes used to apply bitmap filters and blend modes to offscreen textures.
+#[derive(Debug)]
+pub struct FilterPipelines {
+    pub blur: wgpu::RenderPipeline,
+    pub color_matrix: wgpu::RenderPipeline,
+    pub shadow: wgpu::RenderPipeline,
+    pub blend: wgpu::RenderPipeline,
+
+    /// The layout of the uniforms and source texture of a blur or color matrix pass.
+    pub layout: wgpu::BindGroupLayout,
+
+    /// The layout of the uniforms, source texture and second texture of a shadow pass,
+    /// where it is blurred, or of a blend pass, where it is the backdrop.
+    pub shadow_layout: wgpu::BindGroupLayout,
 }
 
 impl ShapePipeline {
-    pub fn pipeline_for(&self, mask_state: MaskState) -> &wgpu::RenderPipeline {
-        &self.mask_pipelines[mask_state]
+    pub fn pipeline_for(
+        &self,
+        mask_state: MaskState,
+        blend_state: Option<BlendState>,
+    ) -> &wgpu::RenderPipeline {
+        match (&self.blend_pipelines, blend_state, mask_state) {
+            (Some(blend_pipelines), Some(blend_state), MaskState::NoMask) => {
+                &blend_pipelines[blend_state].no_mask
+            }
+            (Some(blend_pipelines), Some(blend_state), MaskState::DrawMaskedContent) => {
+                &blend_pipelines[blend_state].masked_content
+            }
+            _ => &self.mask_pipelines[mask_state],
+        }
     }
 }
 
@@ -43,6 +83,17 @@ impl Pipelines {
             device.create_shader_module(wgpu::include_spirv!("../shaders/gradient.frag.spv"));
         let bitmap_fs =
             device.create_shader_module(wgpu::include_spirv!("../shaders/bitmap.frag.spv"));
+        let filter_vs =
+            device.create_shader_module(wgpu::include_spirv!("../shaders/filter.vert.spv"));
+        let filter_blur_fs =
+            device.create_shader_module(wgpu::include_spirv!("../shaders/filter_blur.frag.spv"));
+        let filter_color_matrix_fs = device.create_shader_module(wgpu::include_spirv!(
+            "../shaders/filter_color_matrix.frag.spv"
+        ));
+        let filter_shadow_fs =
+            device.create_shader_module(wgpu::include_spirv!("../shaders/filter_shadow.frag.spv"));
+        let filter_blend_fs =
+            device.create_shader_module(wgpu::include_spirv!("../shaders/filter_blend.frag.spv"));
 
         let vertex_buffers_description = [wgpu::VertexBufferDescriptor {
             stride: std::mem::size_of::<GPUVertex>() as u64,
@@ -165,6 +216,16 @@ impl Pipelines {
             &gradient_bind_layout,
         );
 
+        let filter_pipelines = create_filter_pipelines(
+            &device,
+            &filter_vs,
+            &filter_blur_fs,
+            &filter_color_matrix_fs,
+            &filter_shadow_fs,
+            &filter_blend_fs,
+            sampler_layout,
+        );
+
         Ok(Self {
             mesh_layout: mesh_bind_layout,
             color_pipelines,
@@ -172,6 +233,7 @@ impl Pipelines {
             bitmap_layout: bitmap_bind_layout,
             gradient_pipelines,
             gradient_layout: gradient_bind_layout,
+            filter_pipelines,
         })
     }
 }
@@ -258,7 +320,7 @@ fn create_color_pipelines(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -290,7 +352,7 @@ fn create_color_pipelines(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -322,7 +384,7 @@ fn create_color_pipelines(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -354,7 +416,7 @@ fn create_color_pipelines(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -366,7 +428,10 @@ fn create_color_pipelines(
         },
     };
 
-    ShapePipeline { mask_pipelines }
+    ShapePipeline {
+        mask_pipelines,
+        blend_pipelines: None,
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -415,7 +480,7 @@ fn create_bitmap_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -447,7 +512,7 @@ fn create_bitmap_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -479,7 +544,7 @@ fn create_bitmap_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -511,7 +576,7 @@ fn create_bitmap_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -523,7 +588,22 @@ fn create_bitmap_pipeline(
         }
     };
 
-    ShapePipeline { mask_pipelines }
+    let blend_pipelines = enum_map! {
+        blend_state => create_blend_pipelines(
+            device,
+            vertex_shader,
+            fragment_shader,
+            &pipeline_layout,
+            vertex_buffers_description,
+            msaa_sample_count,
+            blend_state,
+        ),
+    };
+
+    ShapePipeline {
+        mask_pipelines,
+        blend_pipelines: Some(blend_pipelines),
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -566,7 +646,7 @@ fn create_gradient_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -598,7 +678,7 @@ fn create_gradient_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -631,7 +711,7 @@ fn create_gradient_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -663,7 +743,7 @@ fn create_gradient_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -675,7 +755,190 @@ fn create_gradient_pipeline(
         }
     };
 
-    ShapePipeline { mask_pipelines }
+    ShapePipeline {
+        mask_pipelines,
+        blend_pipelines: None,
+    }
+}
+
+fn create_filter_pipelines(
+    device: &wgpu::Device,
+    vertex_shader: &wgpu::ShaderModule,
+    blur_shader: &wgpu::ShaderModule,
+    color_matrix_shader: &wgpu::ShaderModule,
+    shadow_shader: &wgpu::ShaderModule,
+    blend_shader: &wgpu::ShaderModule,
+    sampler_layout: &wgpu::BindGroupLayout,
+) -> FilterPipelines {
+    let uniforms_entry = || wgpu::BindGroupLayoutEntry {
+        binding: 0,
+        visibility: wgpu::ShaderStage::FRAGMENT,
+        ty: wgpu::BindingType::UniformBuffer {
+            dynamic: false,
+            min_binding_size: None,
+        },
+        count: None,
+    };
+    let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStage::FRAGMENT,
+        ty: wgpu::BindingType::SampledTexture {
+            multisampled: false,
+            component_type: wgpu::TextureComponentType::Float,
+            dimension: wgpu::TextureViewDimension::D2,
+        },
+        count: None,
+    };
+
+    let layout_label = create_debug_label!("Filter bind group layout");
+    let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        entries: &[uniforms_entry(), texture_entry(1)],
+        label: layout_label.as_deref(),
+    });
+    let shadow_layout_label = create_debug_label!("Shadow filter bind group layout");
+    let shadow_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        entries: &[uniforms_entry(), texture_entry(1), texture_entry(2)],
+        label: shadow_layout_label.as_deref(),
+    });
+
+    // Only the position of the quad vertices is used.
+    let vertex_buffers_description = [wgpu::VertexBufferDescriptor {
+        stride: std::mem::size_of::<GPUVertex>() as u64,
+        step_mode: wgpu::InputStepMode::Vertex,
+        attributes: &vertex_attr_array![0 => Float2],
+    }];
+
+    let create_pipeline = |fragment_shader, bind_group_layout, label: Option<String>| {
+        let pipeline_layout_label = create_debug_label!("Filter pipeline layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: pipeline_layout_label.as_deref(),
+            bind_group_layouts: &[bind_group_layout, sampler_layout],
+            push_constant_ranges: &[],
+        });
+        // Each pass replaces the contents of a new texture.
+        device.create_render_pipeline(&create_pipeline_descriptor(
+            label.as_deref(),
+            vertex_shader,
+            fragment_shader,
+            &pipeline_layout,
+            None,
+            &[wgpu::ColorStateDescriptor {
+                format: wgpu::TextureFormat::Bgra8Unorm,
+                color_blend: wgpu::BlendDescriptor::REPLACE,
+                alpha_blend: wgpu::BlendDescriptor::REPLACE,
+                write_mask: wgpu::ColorWrite::ALL,
+            }],
+            &vertex_buffers_description,
+            1,
+        ))
+    };
+
+    FilterPipelines {
+        blur: create_pipeline(
+            blur_shader,
+            &layout,
+            create_debug_label!("Blur filter pipeline"),
+        ),
+        color_matrix: create_pipeline(
+            color_matrix_shader,
+            &layout,
+            create_debug_label!("Color matrix filter pipeline"),
+        ),
+        shadow: create_pipeline(
+            shadow_shader,
+            &shadow_layout,
+            create_debug_label!("Shadow filter pipeline"),
+        ),
+        blend: create_pipeline(
+            blend_shader,
+            &shadow_layout,
+            create_debug_label!("Blend pipeline"),
+        ),
+        layout,
+        shadow_layout,
+    }
+}
+
+/// Creates the bitmap pipelines used to composite premultiplied content with a blend mode.
+fn create_blend_pipelines(
+    device: &wgpu::Device,
+    vertex_shader: &wgpu::ShaderModule,
+    fragment_shader: &wgpu::ShaderModule,
+    pipeline_layout: &wgpu::PipelineLayout,
+    vertex_buffers_description: &[wgpu::VertexBufferDescriptor<'_>],
+    msaa_sample_count: u32,
+    blend_state: BlendState,
+) -> BlendPipelines {
+    let (color_blend, alpha_blend) = blend_descriptors(blend_state);
+    let create_pipeline =
+        |mask_state: MaskState, depth_compare: wgpu::CompareFunction, label: Option<String>| {
+            let (stencil, write_mask) = mask_render_state(mask_state);
+            device.create_render_pipeline(&create_pipeline_descriptor(
+                label.as_deref(),
+                vertex_shader,
+                fragment_shader,
+                pipeline_layout,
+                Some(wgpu::DepthStencilStateDescriptor {
+                    format: wgpu::TextureFormat::Depth24PlusStencil8,
+                    depth_write_enabled: true,
+                    depth_compare,
+                    stencil,
+                }),
+                &[wgpu::ColorStateDescriptor {
+                    format: wgpu::TextureFormat::Bgra8Unorm,
+                    color_blend: color_blend.clone(),
+                    alpha_blend: alpha_blend.clone(),
+                    write_mask,
+                }],
+                vertex_buffers_description,
+                msaa_sample_count,
+            ))
+        };
+
+    BlendPipelines {
+        no_mask: create_pipeline(
+            MaskState::NoMask,
+            wgpu::CompareFunction::Always,
+            create_debug_label!("Bitmap pipeline {:?} no mask", blend_state),
+        ),
+        masked_content: create_pipeline(
+            MaskState::DrawMaskedContent,
+            wgpu::CompareFunction::Equal,
+            create_debug_label!("Bitmap pipeline {:?} draw masked content", blend_state),
+        ),
+    }
+}
+
+/// The color and alpha blending for a blend mode, with a premultiplied source.
+fn blend_descriptors(blend_state: BlendState) -> (wgpu::BlendDescriptor, wgpu::BlendDescriptor) {
+    use wgpu::BlendFactor::{DstColor, One, OneMinusSrcAlpha, OneMinusSrcColor, SrcAlpha, Zero};
+    use wgpu::BlendOperation::{Add, Max, Min, ReverseSubtract};
+
+    let blend = |src_factor, dst_factor, operation| wgpu::BlendDescriptor {
+        src_factor,
+        dst_factor,
+        operation,
+    };
+
+    match blend_state {
+        BlendState::Add => (blend(One, One, Add), blend(One, One, Add)),
+        BlendState::Subtract => (blend(One, One, ReverseSubtract), blend(Zero, One, Add)),
+        BlendState::Multiply => (
+            blend(DstColor, OneMinusSrcAlpha, Add),
+            blend(One, OneMinusSrcAlpha, Add),
+        ),
+        BlendState::Screen => (
+            blend(One, OneMinusSrcColor, Add),
+            blend(One, OneMinusSrcAlpha, Add),
+        ),
+        BlendState::Lighten => (blend(One, One, Max), blend(One, One, Max)),
+        BlendState::Darken => (blend(One, One, Min), blend(One, One, Max)),
+        BlendState::Alpha => (blend(Zero, SrcAlpha, Add), blend(Zero, SrcAlpha, Add)),
+        BlendState::Erase => (
+            blend(Zero, OneMinusSrcAlpha, Add),
+            blend(Zero, OneMinusSrcAlpha, Add),
+        ),
+    }
 }
 
 fn mask_render_state(state: MaskState) -> (wgpu::StencilStateDescriptor, wgpu::ColorWrite) {
@@ -21,6 +21,16 @@ where
     pub fn seek(&mut self, relative_offset: i64) -> std::io::Result<u64> {
         self.inner.seek(SeekFrom::Current(relative_offset as i64))
     }
+
+    #[inline]
+    pub fn seek_absolute(&mut self, offset: u64) -> std::io::Result<u64> {
+        self.inner.seek(SeekFrom::Start(offset))
+    }
+
+    #[inline]
+    pub fn pos(&mut self) -> std::io::Result<u64> {
+        self.inner.seek(SeekFrom::Current(0))
+    }
 }
 
 impl<R: Read> Reader<R> {
@@ -199,6 +209,19 @@ impl<R: Read> Reader<R> {
             0x1c => Multiname::MultinameLA {
                 namespace_set: self.read_index()?,
             },
+            0x1d => {
+                let base_type = self.read_index()?;
+                let count = self.read_u30()?;
+                let mut parameters = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    parameters.push(self.read_index()?);
+                }
+
+                Multiname::TypeName {
+                    base_type,
+                    parameters,
+                }
+            }
             _ => return Err(Error::invalid_data("Invalid multiname kind")),
         })
     }
@@ -548,6 +571,9 @@ impl<R: Read> Reader<R> {
         let op = match opcode {
             OpCode::Add => Op::Add,
             OpCode::AddI => Op::AddI,
+            OpCode::ApplyType => Op::ApplyType {
+                num_types: self.read_u30()?,
+            },
             OpCode::AsType => Op::AsType {
                 type_name: self.read_index()?,
             },
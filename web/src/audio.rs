@@ -1,9 +1,11 @@
 use fnv::FnvHashMap;
 use generational_arena::Arena;
-use ruffle_core::backend::audio::decoders::{AdpcmDecoder, Mp3Decoder};
+use ruffle_core::backend::audio::decoders::{
+    self, AdpcmDecoder, Mp3Decoder, NellymoserDecoder, SpeexDecoder,
+};
 use ruffle_core::backend::audio::swf::{self, AudioCompression};
 use ruffle_core::backend::audio::{
-    AudioBackend, AudioStreamHandle, SoundHandle, SoundInstanceHandle,
+    AudioBackend, AudioStreamHandle, SoundHandle, SoundInstanceHandle, SoundTransform,
 };
 use ruffle_web_common::JsResult;
 use std::cell::{Cell, RefCell};
@@ -34,7 +36,10 @@ struct StreamData {
     num_sample_frames: u32,
     samples_per_block: u32,
     skip_sample_frames: u16,
-    adpcm_block_offsets: Vec<usize>,
+
+    /// The offsets of each `SoundStreamBlock` in `audio_data`, for formats that must decode the
+    /// blocks separately.
+    block_offsets: Vec<usize>,
 
     /// List of stream segments. Contains the frame they start on and the starting sample.
     /// Guaranteed to be in frame order.
@@ -86,6 +91,9 @@ struct SoundInstance {
     /// either decoded on the fly with Decoder, or pre-decoded
     /// and played with and AudioBufferSourceNode.
     instance_type: SoundInstanceType,
+
+    /// The mixing matrix applied to this sound, from `SoundTransform::matrix`.
+    transform: [f32; 4],
 }
 
 /// The Drop impl ensures that the sound is stopped and remove from the audio context,
@@ -95,6 +103,7 @@ impl Drop for SoundInstance {
         if let SoundInstanceType::AudioBuffer {
             node,
             buffer_source_node,
+            ..
         } = &self.instance_type
         {
             let _ = buffer_source_node.set_onended(None);
@@ -114,6 +123,10 @@ enum SoundInstanceType {
         /// This is often the same as `node`, but will be different
         /// if there is a custom envelope on this sound.
         buffer_source_node: web_sys::AudioBufferSourceNode,
+
+        /// The gain nodes that mix the left and right channels, in the
+        /// same order as `SoundTransform::matrix`.
+        transform_gains: [web_sys::GainNode; 4],
     },
 }
 
@@ -219,6 +232,10 @@ impl WebAudioBackend {
                     }
                 };
 
+                // Envelopes always output both channels.
+                let is_stereo = sound.format.is_stereo
+                    || settings.map_or(false, |settings| settings.envelope.is_some());
+                let (node, transform_gains) = self.create_sound_transform(node, is_stereo).unwrap();
                 node.connect_with_audio_node(&self.context.destination())
                     .warn_on_error();
 
@@ -229,7 +246,9 @@ impl WebAudioBackend {
                     instance_type: SoundInstanceType::AudioBuffer {
                         node,
                         buffer_source_node: buffer_source_node.clone(),
+                        transform_gains,
                     },
+                    transform: SoundTransform::default().matrix(),
                 };
                 let instance_handle = SOUND_INSTANCES.with(|instances| {
                     let mut instances = instances.borrow_mut();
@@ -282,11 +301,12 @@ impl WebAudioBackend {
                     handle: Some(handle),
                     format: sound.format.clone(),
                     instance_type: SoundInstanceType::Decoder(decoder),
+                    transform: SoundTransform::default().matrix(),
                 };
                 SOUND_INSTANCES.with(|instances| {
                     let mut instances = instances.borrow_mut();
                     let instance_handle = instances.insert(instance);
-                    let script_processor_node = self.context.create_script_processor_with_buffer_size_and_number_of_input_channels_and_number_of_output_channels(4096, 0, if sound.format.is_stereo { 2 } else { 1 }).unwrap();
+                    let script_processor_node = self.context.create_script_processor_with_buffer_size_and_number_of_input_channels_and_number_of_output_channels(4096, 0, 2).unwrap();
                     let script_node = script_processor_node.clone();
                     let closure = Closure::wrap(Box::new(move |event| {
                             SOUND_INSTANCES.with(|instances| {
@@ -384,12 +404,57 @@ impl WebAudioBackend {
         Ok(merger)
     }
 
+    /// Wires up the mixing matrix of `SoundTransform` using `ChannelSplitter`, `Gain`, and `ChannelMerger` nodes.
+    fn create_sound_transform(
+        &self,
+        node: web_sys::AudioNode,
+        is_stereo: bool,
+    ) -> Result<(web_sys::AudioNode, [web_sys::GainNode; 4]), Box<dyn std::error::Error>> {
+        let splitter = self
+            .context
+            .create_channel_splitter_with_number_of_outputs(2)
+            .into_js_result()?;
+        let merger: web_sys::AudioNode = self
+            .context
+            .create_channel_merger_with_number_of_inputs(2)
+            .into_js_result()?
+            .into();
+
+        // Note that for mono tracks, we want to use channel 0 (left) as the right channel too.
+        let right_output = if is_stereo { 1 } else { 0 };
+        let left_to_left = self.context.create_gain().into_js_result()?;
+        let left_to_right = self.context.create_gain().into_js_result()?;
+        let right_to_left = self.context.create_gain().into_js_result()?;
+        let right_to_right = self.context.create_gain().into_js_result()?;
+        left_to_right.gain().set_value(0.0);
+        right_to_left.gain().set_value(0.0);
+
+        node.connect_with_audio_node(&splitter).into_js_result()?;
+        for (gain, output, input) in &[
+            (&left_to_left, 0, 0),
+            (&left_to_right, 0, 1),
+            (&right_to_left, right_output, 0),
+            (&right_to_right, right_output, 1),
+        ] {
+            splitter
+                .connect_with_audio_node_and_output(gain, *output)
+                .into_js_result()?;
+            gain.connect_with_audio_node_and_output_and_input(&merger, 0, *input)
+                .into_js_result()?;
+        }
+
+        Ok((
+            merger,
+            [left_to_left, left_to_right, right_to_left, right_to_right],
+        ))
+    }
+
     fn decompress_to_audio_buffer(
         &mut self,
         format: &swf::SoundFormat,
         audio_data: &[u8],
         num_sample_frames: u32,
-        adpcm_block_offsets: Option<&[usize]>,
+        block_offsets: Option<&[usize]>,
     ) -> Result<AudioBufferPtr, Error> {
         if format.compression == AudioCompression::Mp3 {
             return Ok(self.decompress_mp3_to_audio_buffer(format, audio_data, num_sample_frames));
@@ -398,6 +463,7 @@ impl WebAudioBackend {
         self.left_samples.clear();
         self.right_samples.clear();
 
+        let sample_rate = decoders::effective_sample_rate(format);
         match format.compression {
             AudioCompression::Uncompressed | AudioCompression::UncompressedUnknownEndian => {
                 use byteorder::{LittleEndian, ReadBytesExt};
@@ -422,8 +488,8 @@ impl WebAudioBackend {
                 // so we must recreate the decoder for each block.
                 // Event sounds don't have this issue.
                 let full = [0, audio_data.len()];
-                let adpcm_block_offsets = adpcm_block_offsets.unwrap_or(&full);
-                for block in adpcm_block_offsets.windows(2) {
+                let block_offsets = block_offsets.unwrap_or(&full);
+                for block in block_offsets.windows(2) {
                     let start = block[0];
                     let end = block[1];
                     let mut decoder = AdpcmDecoder::new(
@@ -443,13 +509,32 @@ impl WebAudioBackend {
                     }
                 }
             }
+            AudioCompression::Nellymoser
+            | AudioCompression::Nellymoser8Khz
+            | AudioCompression::Nellymoser16Khz => {
+                let decoder = NellymoserDecoder::new(audio_data, sample_rate);
+                self.left_samples
+                    .extend(decoder.map(|n| f32::from(n[0]) / 32767.0));
+            }
+            AudioCompression::Speex => {
+                // Each block of a stream sound is a separate Speex packet,
+                // so we must feed the blocks to the decoder one at a time.
+                let full = [0, audio_data.len()];
+                let block_offsets = block_offsets.unwrap_or(&full);
+                let mut decoder = SpeexDecoder::new(&audio_data[..0]);
+                for block in block_offsets.windows(2) {
+                    decoder.next_packet(&audio_data[block[0]..block[1]]);
+                    self.left_samples
+                        .extend(decoder.by_ref().map(|n| f32::from(n[0]) / 32767.0));
+                }
+            }
             compression => return Err(format!("Unimplemented codec: {:?}", compression).into()),
         }
 
         // This sucks. Firefox and Safari don't like low sample rates,
         // so manually multiply the samples.
-        let sample_rate = if format.sample_rate < self.min_sample_rate {
-            let sample_multiplier = self.min_sample_rate / format.sample_rate;
+        let sample_rate = if sample_rate < self.min_sample_rate {
+            let sample_multiplier = self.min_sample_rate / sample_rate;
             let mut samples = Vec::with_capacity(self.left_samples.len() * 2);
             for sample in &self.left_samples {
                 for _ in 0..sample_multiplier {
@@ -470,7 +555,7 @@ impl WebAudioBackend {
 
             self.min_sample_rate
         } else {
-            format.sample_rate
+            sample_rate
         };
 
         let num_sample_frames = self.left_samples.len() as u32;
@@ -552,31 +637,27 @@ impl WebAudioBackend {
         let mut complete = false;
         let mut left_samples = vec![];
         let mut right_samples = vec![];
+        let [left_to_left, left_to_right, right_to_left, right_to_right] = instance.transform;
         if let SoundInstanceType::Decoder(ref mut decoder) = &mut instance.instance_type {
             let output_buffer = event.output_buffer().unwrap();
             let num_frames = output_buffer.length() as usize;
 
             for _ in 0..num_frames {
                 if let Some(frame) = decoder.next() {
-                    let (l, r) = (frame[0], frame[1]);
-                    left_samples.push(f32::from(l) / 32767.0);
-                    if instance.format.is_stereo {
-                        right_samples.push(f32::from(r) / 32767.0);
-                    }
+                    let l = f32::from(frame[0]) / 32767.0;
+                    let r = if instance.format.is_stereo {
+                        f32::from(frame[1]) / 32767.0
+                    } else {
+                        l
+                    };
+                    left_samples.push(l * left_to_left + r * right_to_left);
+                    right_samples.push(l * left_to_right + r * right_to_right);
                 } else {
                     complete = true;
                     break;
                 }
             }
-            copy_to_audio_buffer(
-                &output_buffer,
-                Some(&left_samples),
-                if instance.format.is_stereo {
-                    Some(&right_samples)
-                } else {
-                    None
-                },
-            );
+            copy_to_audio_buffer(&output_buffer, Some(&left_samples), Some(&right_samples));
         }
 
         complete
@@ -625,7 +706,7 @@ impl AudioBackend for WebAudioBackend {
                 num_sample_frames: 0,
                 samples_per_block: stream_info.num_samples_per_block.into(),
                 skip_sample_frames: stream_info.latency_seek as u16,
-                adpcm_block_offsets: vec![],
+                block_offsets: vec![],
                 stream_segments: vec![],
                 last_clip_frame: 0,
             });
@@ -640,7 +721,7 @@ impl AudioBackend for WebAudioBackend {
         if let Some(stream) = self.stream_data.get_mut(&clip_id) {
             // Handle gaps in streaming audio. Store the offsets for each stream segment.
             if stream.audio_data.is_empty() || stream.last_clip_frame + 1 != clip_frame {
-                let sample_mult = 44100 / stream.format.sample_rate;
+                let sample_mult = 44100 / decoders::effective_sample_rate(&stream.format);
                 let start_sample = stream.num_sample_frames * u32::from(sample_mult);
                 stream.stream_segments.push((clip_frame, start_sample));
             }
@@ -667,11 +748,18 @@ impl AudioBackend for WebAudioBackend {
                         stream.audio_data.extend_from_slice(&audio_data[4..]);
                     }
                 }
-                AudioCompression::Adpcm => {
-                    // For ADPCM data, we must keep track of where each block starts,
-                    // so that we read the header in each block.
+                AudioCompression::Adpcm | AudioCompression::Speex => {
+                    // For ADPCM and Speex data, we must keep track of where each block starts,
+                    // so that we decode each block separately.
                     stream.num_sample_frames += stream.samples_per_block;
-                    stream.adpcm_block_offsets.push(stream.audio_data.len());
+                    stream.block_offsets.push(stream.audio_data.len());
+                    stream.audio_data.extend_from_slice(audio_data);
+                }
+                AudioCompression::Nellymoser
+                | AudioCompression::Nellymoser8Khz
+                | AudioCompression::Nellymoser16Khz => {
+                    // Each 64-byte Nellymoser block decodes to 256 samples.
+                    stream.num_sample_frames += (audio_data.len() as u32 / 64) * 256;
                     stream.audio_data.extend_from_slice(audio_data);
                 }
                 _ => {
@@ -691,9 +779,11 @@ impl AudioBackend for WebAudioBackend {
                     &stream.format,
                     &stream.audio_data[..],
                     stream.num_sample_frames,
-                    if stream.format.compression == AudioCompression::Adpcm {
-                        stream.adpcm_block_offsets.push(stream.audio_data.len());
-                        Some(&stream.adpcm_block_offsets[..])
+                    if let AudioCompression::Adpcm | AudioCompression::Speex =
+                        stream.format.compression
+                    {
+                        stream.block_offsets.push(stream.audio_data.len());
+                        Some(&stream.block_offsets[..])
                     } else {
                         None
                     },
@@ -829,6 +919,10 @@ impl AudioBackend for WebAudioBackend {
         })
     }
 
+    fn is_sound_playing(&mut self, instance: SoundInstanceHandle) -> bool {
+        SOUND_INSTANCES.with(|instances| instances.borrow().contains(instance))
+    }
+
     fn get_sound_duration(&self, sound: SoundHandle) -> Option<u32> {
         if let Some(sound) = self.sounds.get(sound) {
             // AS duration does not subtract skip_sample_frames.
@@ -839,6 +933,24 @@ impl AudioBackend for WebAudioBackend {
             None
         }
     }
+
+    fn set_sound_transform(&mut self, instance: SoundInstanceHandle, transform: SoundTransform) {
+        SOUND_INSTANCES.with(|instances| {
+            let mut instances = instances.borrow_mut();
+            if let Some(instance) = instances.get_mut(instance) {
+                let matrix = transform.matrix();
+                instance.transform = matrix;
+                if let SoundInstanceType::AudioBuffer {
+                    transform_gains, ..
+                } = &instance.instance_type
+                {
+                    for (gain, value) in transform_gains.iter().zip(matrix.iter()) {
+                        gain.gain().set_value(*value);
+                    }
+                }
+            }
+        })
+    }
 }
 
 #[wasm_bindgen(module = "/packages/core/src/ruffle-imports.js")]
@@ -1,8 +1,11 @@
+use js_sys::{Function, Reflect};
 use ruffle_core::backend::input::{InputBackend, MouseCursor};
-use ruffle_core::events::KeyCode;
+use ruffle_core::events::{KeyCode, KeyLocation};
 use ruffle_web_common::JsResult;
 use std::collections::HashSet;
-use web_sys::HtmlCanvasElement;
+use std::convert::TryFrom;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{HtmlCanvasElement, KeyboardEvent};
 
 /// An implementation of `InputBackend` utilizing `web_sys` bindings to input
 /// APIs
@@ -12,6 +15,12 @@ pub struct WebInputBackend {
     cursor_visible: bool,
     cursor: MouseCursor,
     last_key: KeyCode,
+    last_char: Option<char>,
+    caps_lock: bool,
+    num_lock: bool,
+    scroll_lock: bool,
+    is_mac_os: bool,
+    clipboard_content: String,
 }
 
 impl WebInputBackend {
@@ -22,21 +31,50 @@ impl WebInputBackend {
             cursor_visible: true,
             cursor: MouseCursor::Arrow,
             last_key: KeyCode::Unknown,
+            last_char: None,
+            caps_lock: false,
+            num_lock: false,
+            scroll_lock: false,
+            is_mac_os: web_sys::window()
+                .and_then(|window| window.navigator().platform().ok())
+                .map(|platform| platform.starts_with("Mac"))
+                .unwrap_or_default(),
+            clipboard_content: String::new(),
         }
     }
 
-    /// Register a key press for a given code string.
-    pub fn keydown(&mut self, code: String) {
+    /// Register the text pasted by a `paste` event.
+    ///
+    /// Browsers only allow the clipboard to be read while handling this event.
+    pub fn paste(&mut self, content: String) {
+        self.clipboard_content = content;
+    }
+
+    /// Register a key press from a `keydown` event.
+    pub fn keydown(&mut self, event: &KeyboardEvent) {
+        let code = event.code();
         self.last_key = web_to_ruffle_key_code(&code).unwrap_or(KeyCode::Unknown);
+        self.last_char = web_key_to_codepoint(&event.key());
+        self.update_lock_keys(event);
         self.keys_down.insert(code);
     }
 
-    /// Register a key release for a given code string.
-    pub fn keyup(&mut self, code: String) {
+    /// Register a key release from a `keyup` event.
+    pub fn keyup(&mut self, event: &KeyboardEvent) {
+        let code = event.code();
         self.last_key = web_to_ruffle_key_code(&code).unwrap_or(KeyCode::Unknown);
+        self.last_char = web_key_to_codepoint(&event.key());
+        self.update_lock_keys(event);
         self.keys_down.remove(&code);
     }
 
+    /// The browser knows the state of the lock keys, so take it from each event.
+    fn update_lock_keys(&mut self, event: &KeyboardEvent) {
+        self.caps_lock = event.get_modifier_state("CapsLock");
+        self.num_lock = event.get_modifier_state("NumLock");
+        self.scroll_lock = event.get_modifier_state("ScrollLock");
+    }
+
     fn update_mouse_cursor(&self) {
         let cursor = if self.cursor_visible {
             match self.cursor {
@@ -60,12 +98,21 @@ impl InputBackend for WebInputBackend {
         match key {
             KeyCode::Unknown => false,
             KeyCode::Backspace => self.keys_down.contains("Backspace"),
-            KeyCode::Return => self.keys_down.contains("Enter"),
+            KeyCode::Tab => self.keys_down.contains("Tab"),
+            KeyCode::Return => {
+                self.keys_down.contains("Enter") || self.keys_down.contains("NumpadEnter")
+            }
             KeyCode::Shift => {
                 self.keys_down.contains("ShiftLeft") || self.keys_down.contains("ShiftRight")
             }
+            // Flash Player treats Command as Control on macOS, so shortcuts such as
+            // copy and paste work with either key.
             KeyCode::Control => {
-                self.keys_down.contains("ControlLeft") || self.keys_down.contains("ControlRight")
+                self.keys_down.contains("ControlLeft")
+                    || self.keys_down.contains("ControlRight")
+                    || (self.is_mac_os
+                        && (self.keys_down.contains("MetaLeft")
+                            || self.keys_down.contains("MetaRight")))
             }
             KeyCode::Alt => {
                 self.keys_down.contains("AltLeft") || self.keys_down.contains("AltRight")
@@ -146,6 +193,7 @@ impl InputBackend for WebInputBackend {
             KeyCode::Insert => self.keys_down.contains("Insert"),
             KeyCode::Delete => self.keys_down.contains("Delete"),
             KeyCode::Pause => self.keys_down.contains("Pause"),
+            KeyCode::NumLock => self.keys_down.contains("NumLock"),
             KeyCode::ScrollLock => self.keys_down.contains("ScrollLock"),
             KeyCode::F1 => self.keys_down.contains("F1"),
             KeyCode::F2 => self.keys_down.contains("F2"),
@@ -159,6 +207,9 @@ impl InputBackend for WebInputBackend {
             KeyCode::F10 => self.keys_down.contains("F10"),
             KeyCode::F11 => self.keys_down.contains("F11"),
             KeyCode::F12 => self.keys_down.contains("F12"),
+            KeyCode::F13 => self.keys_down.contains("F13"),
+            KeyCode::F14 => self.keys_down.contains("F14"),
+            KeyCode::F15 => self.keys_down.contains("F15"),
         }
     }
 
@@ -166,6 +217,19 @@ impl InputBackend for WebInputBackend {
         self.last_key
     }
 
+    fn get_last_key_char(&self) -> Option<char> {
+        self.last_char
+    }
+
+    fn is_key_toggled(&self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::CapsLock => self.caps_lock,
+            KeyCode::NumLock => self.num_lock,
+            KeyCode::ScrollLock => self.scroll_lock,
+            _ => false,
+        }
+    }
+
     fn mouse_visible(&self) -> bool {
         self.cursor_visible
     }
@@ -185,8 +249,27 @@ impl InputBackend for WebInputBackend {
         self.update_mouse_cursor();
     }
 
-    fn set_clipboard_content(&mut self, _content: String) {
-        log::warn!("set clipboard not implemented");
+    fn set_clipboard_content(&mut self, content: String) {
+        // `navigator.clipboard` isn't available in every browser, or outside of secure contexts.
+        let write_text = web_sys::window()
+            .and_then(|window| Reflect::get(&window.navigator(), &"clipboard".into()).ok())
+            .filter(|clipboard| !clipboard.is_undefined())
+            .and_then(|clipboard| {
+                let write_text = Reflect::get(&clipboard, &"writeText".into()).ok()?;
+                Some((clipboard, write_text.dyn_into::<Function>().ok()?))
+            });
+        if let Some((clipboard, write_text)) = write_text {
+            if let Err(e) = write_text.call1(&clipboard, &JsValue::from_str(&content)) {
+                log::warn!("Couldn't write to the clipboard: {:?}", e);
+            }
+        } else {
+            log::warn!("Writing to the clipboard is not supported by this browser");
+        }
+        self.clipboard_content = content;
+    }
+
+    fn clipboard_content(&mut self) -> String {
+        self.clipboard_content.clone()
     }
 }
 
@@ -195,7 +278,8 @@ impl InputBackend for WebInputBackend {
 pub fn web_to_ruffle_key_code(key_code: &str) -> Option<KeyCode> {
     let out = match key_code {
         "Backspace" => KeyCode::Backspace,
-        "Enter" => KeyCode::Return,
+        "Tab" => KeyCode::Tab,
+        "Enter" | "NumpadEnter" => KeyCode::Return,
         "ShiftLeft" | "ShiftRight" => KeyCode::Shift,
         "ControlLeft" | "ControlRight" => KeyCode::Control,
         "AltLeft" | "AltRight" => KeyCode::Alt,
@@ -275,6 +359,7 @@ pub fn web_to_ruffle_key_code(key_code: &str) -> Option<KeyCode> {
         "Insert" => KeyCode::Insert,
         "Delete" => KeyCode::Delete,
         "Pause" => KeyCode::Pause,
+        "NumLock" => KeyCode::NumLock,
         "ScrollLock" => KeyCode::ScrollLock,
         "F1" => KeyCode::F1,
         "F2" => KeyCode::F2,
@@ -288,11 +373,22 @@ pub fn web_to_ruffle_key_code(key_code: &str) -> Option<KeyCode> {
         "F10" => KeyCode::F10,
         "F11" => KeyCode::F11,
         "F12" => KeyCode::F12,
+        "F13" => KeyCode::F13,
+        "F14" => KeyCode::F14,
+        "F15" => KeyCode::F15,
         _ => return None,
     };
     Some(out)
 }
 
+/// Converts a Web `KeyboardEvent.location` value into a Ruffle `KeyLocation`.
+pub fn web_key_location(event: &KeyboardEvent) -> KeyLocation {
+    u8::try_from(event.location())
+        .ok()
+        .and_then(|location| KeyLocation::try_from(location).ok())
+        .unwrap_or(KeyLocation::Standard)
+}
+
 /// Converts a Web `KeyboardEvent.key` value into a character codepoint.
 /// Returns `None` if they input was not a printable character.
 pub fn web_key_to_codepoint(key: &str) -> Option<char> {
@@ -301,8 +397,9 @@ pub fn web_key_to_codepoint(key: &str) -> Option<char> {
     // All the other special values are multiple characters (e.g. "ArrowLeft").
     // It's probably better to explicitly match on all the variants.
     match key {
-        key if key.len() == 1 => key.chars().next(),
+        key if key.chars().count() == 1 => key.chars().next(),
         "Backspace" => Some(8 as char),
+        "Enter" => Some(13 as char),
         "Delete" => Some(127 as char),
         _ => None,
     }
@@ -16,11 +16,14 @@ use crate::{
 };
 use generational_arena::{Arena, Index};
 use js_sys::{Array, Function, Object, Uint8Array};
+use ruffle_core::backend::local_connection::LocalConnectionBroker;
 use ruffle_core::backend::render::RenderBackend;
+use ruffle_core::backend::socket::NullSocketBackend;
 use ruffle_core::backend::storage::MemoryStorageBackend;
 use ruffle_core::backend::storage::StorageBackend;
+use ruffle_core::backend::video::SoftwareVideoBackend;
 use ruffle_core::context::UpdateContext;
-use ruffle_core::events::MouseWheelDelta;
+use ruffle_core::events::{KeyCode, KeyLocation, MouseWheelDelta};
 use ruffle_core::external::{
     ExternalInterfaceMethod, ExternalInterfaceProvider, Value as ExternalValue, Value,
 };
@@ -34,8 +37,8 @@ use std::sync::{Arc, Mutex};
 use std::{cell::RefCell, error::Error, num::NonZeroI32};
 use wasm_bindgen::{prelude::*, JsCast, JsValue};
 use web_sys::{
-    AddEventListenerOptions, Element, EventTarget, HtmlCanvasElement, HtmlElement, KeyboardEvent,
-    PointerEvent, WheelEvent,
+    AddEventListenerOptions, ClipboardEvent, Element, EventTarget, HtmlCanvasElement, HtmlElement,
+    KeyboardEvent, PointerEvent, WheelEvent,
 };
 
 static RUFFLE_GLOBAL_PANIC: Once = Once::new();
@@ -47,6 +50,9 @@ thread_local! {
     static INSTANCES: RefCell<Arena<RefCell<RuffleInstance>>> = RefCell::new(Arena::new());
 
     static CURRENT_CONTEXT: RefCell<Option<*mut UpdateContext<'static, 'static, 'static>>> = RefCell::new(None);
+
+    /// All instances on the page share a broker, so that their movies can talk with `LocalConnection`.
+    static LOCAL_CONNECTIONS: LocalConnectionBroker = LocalConnectionBroker::new();
 }
 
 type AnimationHandler = Closure<dyn FnMut(f64)>;
@@ -69,6 +75,7 @@ struct RuffleInstance {
     mouse_wheel_callback: Option<Closure<dyn FnMut(WheelEvent)>>,
     key_down_callback: Option<Closure<dyn FnMut(KeyboardEvent)>>,
     key_up_callback: Option<Closure<dyn FnMut(KeyboardEvent)>>,
+    paste_callback: Option<Closure<dyn FnMut(ClipboardEvent)>>,
     has_focus: bool,
     trace_observer: Arc<RefCell<JsValue>>,
 }
@@ -201,6 +208,7 @@ impl Ruffle {
             // Clean up all event listeners.
             instance.key_down_callback = None;
             instance.key_up_callback = None;
+            instance.paste_callback = None;
             instance.mouse_down_callback = None;
             instance.mouse_move_callback = None;
             instance.mouse_up_callback = None;
@@ -297,8 +305,13 @@ impl Ruffle {
             input,
             local_storage,
             locale,
+            Box::new(SoftwareVideoBackend::new()),
             log,
+            Box::new(NullSocketBackend::new()),
         )?;
+        core.lock().unwrap().set_local_connection_backend(Box::new(
+            LOCAL_CONNECTIONS.with(|broker| broker.endpoint()),
+        ));
 
         // Create instance.
         let instance = RuffleInstance {
@@ -317,6 +330,7 @@ impl Ruffle {
             mouse_wheel_callback: None,
             key_down_callback: None,
             key_up_callback: None,
+            paste_callback: None,
             timestamp: None,
             has_focus: false,
             trace_observer,
@@ -541,10 +555,14 @@ impl Ruffle {
                                     .input_mut()
                                     .downcast_mut::<WebInputBackend>()
                                     .unwrap()
-                                    .keydown(code.clone());
+                                    .keydown(&js_event);
 
+                                // Keys pressed with Control or Meta are shortcuts, not text.
+                                let is_shortcut = (js_event.ctrl_key() || js_event.meta_key())
+                                    && !js_event.alt_key();
                                 if let Some(codepoint) =
                                     input::web_key_to_codepoint(&js_event.key())
+                                        .filter(|_| !is_shortcut)
                                 {
                                     instance
                                         .borrow()
@@ -554,13 +572,20 @@ impl Ruffle {
                                         .handle_event(PlayerEvent::TextInput { codepoint });
                                 }
 
+                                // The clipboard can only be read from a `paste` event, so the
+                                // paste shortcut is sent once the browser has fired it.
+                                if is_shortcut && code == "KeyV" {
+                                    return;
+                                }
+
                                 if let Some(key_code) = input::web_to_ruffle_key_code(&code) {
-                                    instance
-                                        .borrow()
-                                        .core
-                                        .lock()
-                                        .unwrap()
-                                        .handle_event(PlayerEvent::KeyDown { key_code });
+                                    instance.borrow().core.lock().unwrap().handle_event(
+                                        PlayerEvent::KeyDown {
+                                            key_code,
+                                            key_char: input::web_key_to_codepoint(&js_event.key()),
+                                            key_location: input::web_key_location(&js_event),
+                                        },
+                                    );
                                 }
 
                                 js_event.prevent_default();
@@ -595,15 +620,16 @@ impl Ruffle {
                                     .input_mut()
                                     .downcast_mut::<WebInputBackend>()
                                     .unwrap()
-                                    .keyup(code.clone());
+                                    .keyup(&js_event);
 
                                 if let Some(key_code) = input::web_to_ruffle_key_code(&code) {
-                                    instance
-                                        .borrow()
-                                        .core
-                                        .lock()
-                                        .unwrap()
-                                        .handle_event(PlayerEvent::KeyUp { key_code });
+                                    instance.borrow().core.lock().unwrap().handle_event(
+                                        PlayerEvent::KeyUp {
+                                            key_code,
+                                            key_char: input::web_key_to_codepoint(&js_event.key()),
+                                            key_location: input::web_key_location(&js_event),
+                                        },
+                                    );
                                 }
 
                                 js_event.prevent_default();
@@ -622,6 +648,43 @@ impl Ruffle {
                 instance.key_up_callback = Some(key_up_callback);
             }
 
+            {
+                let paste_callback = Closure::wrap(Box::new(move |js_event: ClipboardEvent| {
+                    INSTANCES.with(|instances| {
+                        if let Some(instance) = instances.borrow().get(index) {
+                            if instance.borrow().has_focus {
+                                let content = js_event
+                                    .clipboard_data()
+                                    .and_then(|data| data.get_data("text/plain").ok())
+                                    .unwrap_or_default();
+                                let instance = instance.borrow();
+                                let mut core = instance.core.lock().unwrap();
+                                core.input_mut()
+                                    .downcast_mut::<WebInputBackend>()
+                                    .unwrap()
+                                    .paste(content);
+                                core.handle_event(PlayerEvent::KeyDown {
+                                    key_code: KeyCode::V,
+                                    key_char: Some('v'),
+                                    key_location: KeyLocation::Standard,
+                                });
+
+                                js_event.prevent_default();
+                            }
+                        }
+                    });
+                })
+                    as Box<dyn FnMut(ClipboardEvent)>);
+                window
+                    .add_event_listener_with_callback(
+                        "paste",
+                        paste_callback.as_ref().unchecked_ref(),
+                    )
+                    .unwrap();
+                let mut instance = instances.get(index).unwrap().borrow_mut();
+                instance.paste_callback = Some(paste_callback);
+            }
+
             ruffle
         });
 
@@ -1,16 +1,22 @@
 //! Navigator backend for web
 
-use js_sys::{Array, ArrayBuffer, Uint8Array};
+use js_sys::{Array, ArrayBuffer, Function, Object, Reflect, Uint8Array};
 use ruffle_core::backend::navigator::{
     url_from_relative_url, NavigationMethod, NavigatorBackend, OwnedFuture, RequestOptions,
+    Response as FetchResponse, ResponseBody,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use std::borrow::Cow;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 use std::time::Duration;
-use wasm_bindgen::JsCast;
+use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::{spawn_local, JsFuture};
-use web_sys::{window, Blob, BlobPropertyBag, Performance, Request, RequestInit, Response};
+use web_sys::{
+    window, Blob, BlobPropertyBag, Headers, Performance, Request, RequestInit, Response,
+};
 
 pub struct WebNavigatorBackend {
     performance: Performance,
@@ -94,7 +100,7 @@ impl NavigatorBackend for WebNavigatorBackend {
         Duration::from_millis(dt as u64)
     }
 
-    fn fetch(&self, url: &str, options: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
+    fn fetch(&self, url: &str, options: RequestOptions) -> OwnedFuture<FetchResponse, Error> {
         let url = url.to_string();
         Box::pin(async move {
             let mut init = RequestInit::new();
@@ -127,6 +133,15 @@ impl NavigatorBackend for WebNavigatorBackend {
                 init.body(Some(&datablob));
             }
 
+            if !options.headers().is_empty() {
+                let headers = Headers::new().unwrap();
+                for (name, value) in options.headers() {
+                    // The browser rejects invalid header names, so skip those.
+                    let _ = headers.append(name, value);
+                }
+                init.headers(&headers);
+            }
+
             let request = Request::new_with_str_and_init(&url, &init).unwrap();
 
             let window = web_sys::window().unwrap();
@@ -139,16 +154,7 @@ impl NavigatorBackend for WebNavigatorBackend {
             }
 
             let resp: Response = fetchval.unwrap().dyn_into().unwrap();
-            let data: ArrayBuffer = JsFuture::from(resp.array_buffer().unwrap())
-                .await
-                .unwrap()
-                .dyn_into()
-                .unwrap();
-            let jsarray = Uint8Array::new(&data);
-            let mut rust_array = vec![0; jsarray.length() as usize];
-            jsarray.copy_to(&mut rust_array);
-
-            Ok(rust_array)
+            Ok(convert_response(resp))
         })
     }
 
@@ -173,3 +179,105 @@ impl NavigatorBackend for WebNavigatorBackend {
         url.into()
     }
 }
+
+/// Convert a `fetch` response into a fetch response whose body is read as it arrives.
+fn convert_response(resp: Response) -> FetchResponse {
+    let mut headers = IndexMap::new();
+    if let Ok(Some(entries)) = js_sys::try_iter(&resp.headers()) {
+        for entry in entries.flatten() {
+            let entry: Array = entry.unchecked_into();
+            if let (Some(name), Some(value)) = (entry.get(0).as_string(), entry.get(1).as_string())
+            {
+                headers.insert(name, value);
+            }
+        }
+    }
+
+    let content_length = headers
+        .get("content-length")
+        .and_then(|length| length.parse().ok());
+    let reader = resp.body().map(|stream| stream.get_reader());
+
+    let mut response = FetchResponse::new(
+        resp.url(),
+        Box::pin(StreamBody {
+            reader,
+            pending_read: None,
+        }),
+    );
+    response.status = Some(resp.status());
+    response.headers = headers;
+    response.content_length = content_length;
+
+    response
+}
+
+/// A response body that reads from the `ReadableStream` of a `fetch` response.
+struct StreamBody {
+    /// The reader of the body stream, or `None` if the response has no body.
+    reader: Option<Object>,
+
+    /// The read that is waiting for the next chunk, if any.
+    pending_read: Option<JsFuture>,
+}
+
+impl StreamBody {
+    /// Start reading the next chunk from the stream.
+    fn read(reader: &Object) -> Result<JsFuture, JsValue> {
+        let read: Function = Reflect::get(reader, &"read".into())?.dyn_into()?;
+        let promise = read.call0(reader)?.dyn_into()?;
+        Ok(JsFuture::from(promise))
+    }
+}
+
+impl ResponseBody for StreamBody {
+    fn poll_chunk(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Vec<u8>, Error>>> {
+        let this = self.get_mut();
+        let reader = match &this.reader {
+            Some(reader) => reader,
+            None => return Poll::Ready(None),
+        };
+
+        if this.pending_read.is_none() {
+            match StreamBody::read(reader) {
+                Ok(read) => this.pending_read = Some(read),
+                Err(_) => return Poll::Ready(Some(Err(read_error()))),
+            }
+        }
+
+        let result = match Pin::new(this.pending_read.as_mut().unwrap()).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        this.pending_read = None;
+
+        // Each read resolves to `{ done, value }`, where `value` is a `Uint8Array`.
+        let result = match result {
+            Ok(result) => result,
+            Err(_) => return Poll::Ready(Some(Err(read_error()))),
+        };
+        let done = Reflect::get(&result, &"done".into())
+            .ok()
+            .and_then(|done| done.as_bool())
+            .unwrap_or(true);
+        if done {
+            this.reader = None;
+            return Poll::Ready(None);
+        }
+
+        match Reflect::get(&result, &"value".into()) {
+            Ok(value) => Poll::Ready(Some(Ok(Uint8Array::new(&value).to_vec()))),
+            Err(_) => Poll::Ready(Some(Err(read_error()))),
+        }
+    }
+}
+
+fn read_error() -> Error {
+    Error::NetworkError(std::io::Error::new(
+        std::io::ErrorKind::Other,
+        "Could not read response body, got JS Error",
+    ))
+}